@@ -0,0 +1,305 @@
+//! Add-language command implementation
+//!
+//! Scaffolds a new language: creates its local directory mirroring the
+//! source language's files, registers it with the backend, and records a
+//! `languageMapping` entry when the backend uses a different code.
+//!
+//! # Features
+//!
+//! - Same files and nesting as the source language, in each file's format
+//! - Empty values by default, source values with `--fill-source`
+//! - Backend registration (skippable with `--no-register`, skipped with a
+//!   warning when the backend does not support it)
+
+use crate::cli::RunContext;
+use crate::api::capabilities::Feature;
+use crate::api::client::APIClient;
+use crate::core::config::set_language_mapping;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::{scaffold_language, scan_messages_dir};
+use crate::core::status_cache::StatusCache;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Add-language 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "add-language", about = "Scaffold a new language locally and on the backend")]
+pub struct AddLanguageCmd {
+    /// 新语言的本地代码（如 fr、pt_BR）
+    #[arg(value_name = "LANG")]
+    pub language: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 作为模板的源语言（默认：键最多的本地语言）
+    #[arg(long, value_name = "LANG")]
+    pub from: Option<String>,
+
+    /// 用源语言的值填充，而不是留空
+    #[arg(long)]
+    pub fill_source: bool,
+
+    /// 后端语言代码（与本地代码不同时写入 languageMapping）
+    #[arg(long, value_name = "CODE")]
+    pub backend_code: Option<String>,
+
+    /// 只创建本地文件，不在后端注册
+    #[arg(long)]
+    pub no_register: bool,
+}
+
+/// Add-language 结果
+#[derive(Debug, Clone, Default)]
+pub struct AddLanguageResult {
+    /// 源语言
+    pub source: String,
+    /// 创建的文件
+    pub files: Vec<PathBuf>,
+    /// 后端语言代码
+    pub backend_code: String,
+    /// 是否在后端新建了语言（已存在或跳过注册时为 false）
+    pub registered: bool,
+    /// 是否写入了 languageMapping
+    pub mapping_updated: bool,
+}
+
+impl AddLanguageCmd {
+    /// 执行 add-language 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 确定源语言
+    /// 3. 创建本地文件
+    /// 4. 更新 languageMapping（如需要）
+    /// 5. 在后端注册语言
+    pub async fn run(&self, ctx: &RunContext) -> Result<AddLanguageResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path.clone()).await?;
+        let messages_dir = config.messages();
+
+        // 2. 确定源语言
+        let source = match &self.from {
+            Some(source) => source.clone(),
+            None => {
+                let scan_result = scan_messages_dir(&messages_dir)
+                    .await
+                    .context("Failed to scan messages directory")?;
+                let mut languages: Vec<(&String, usize)> = scan_result
+                    .translations
+                    .iter()
+                    .map(|(lang, t)| (lang, t.len()))
+                    .collect();
+                languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                languages
+                    .first()
+                    .map(|(lang, _)| lang.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No local languages found; use --from to pick a source"))?
+            }
+        };
+        if source == self.language {
+            return Err(anyhow::anyhow!("Language '{}' is the source language", self.language));
+        }
+        info!("Scaffolding '{}' from '{}'", self.language, source);
+
+        // 3. 创建本地文件
+        let files = scaffold_language(&messages_dir, &source, &self.language, self.fill_source)?;
+        for file in &files {
+            info!("  - Created {}", file.display());
+        }
+
+        // 4. 更新 languageMapping
+        let mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+        let backend_code = self
+            .backend_code
+            .clone()
+            .unwrap_or_else(|| mapper.to_backend(&self.language));
+        let mapping_updated = backend_code != mapper.to_backend(&self.language);
+        if mapping_updated {
+            let path = set_language_mapping(config_path, &self.language, &backend_code)?;
+            info!(
+                "  - Added languageMapping {} -> {} to {}",
+                self.language,
+                backend_code,
+                path.display()
+            );
+        }
+
+        // 5. 在后端注册语言
+        let registered = if self.no_register {
+            false
+        } else {
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+            if client.supports(Feature::Languages).await? {
+                StatusCache::invalidate(&config.messages_dir)?;
+                let created = client
+                    .register_language(&backend_code)
+                    .await
+                    .with_context(|| format!("Local files were created, but registering '{}' failed", backend_code))?;
+                if !created {
+                    info!("  - '{}' already exists on the backend", backend_code);
+                }
+                created
+            } else {
+                info!(
+                    "  - Warning: Backend does not support {}; skipping registration",
+                    Feature::Languages.description()
+                );
+                false
+            }
+        };
+
+        let result = AddLanguageResult {
+            source,
+            files,
+            backend_code,
+            registered,
+            mapping_updated,
+        };
+
+        info!("Language '{}' added:", self.language);
+        info!("  - Source language: {}", result.source);
+        info!("  - Files created: {}", result.files.len());
+        info!("  - Backend code: {}", result.backend_code);
+        if result.mapping_updated {
+            info!("  - languageMapping updated");
+        }
+        if result.registered {
+            info!("  - Registered with the backend");
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::load_config;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(
+            messages_dir.join("en").join("common.json"),
+            r#"{"title": "Title", "buttons": {"save": "Save"}}"#,
+        )
+        .unwrap();
+        std::fs::write(messages_dir.join("de").join("common.json"), r#"{"title": "Titel"}"#).unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_add_language_local_with_mapping() {
+        let (temp_dir, config_path) = setup();
+
+        let cmd = AddLanguageCmd {
+            language: "fr_FR".to_string(),
+            config: Some(config_path.clone()),
+            backend_code: Some("fr".to_string()),
+            no_register: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        assert_eq!(result.source, "en");
+        assert_eq!(result.files.len(), 1);
+        assert!(result.mapping_updated);
+        assert!(!result.registered);
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join("messages/fr_FR/common.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written, serde_json::json!({"title": "", "buttons": {"save": ""}}));
+
+        let config = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(config.language_mapping["fr_FR"], "fr");
+    }
+
+    #[tokio::test]
+    async fn test_add_language_registers_with_backend() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let (_temp_dir, config_path) = setup();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let content = std::fs::read_to_string(&config_path)
+            .unwrap()
+            .replace("http://localhost", &base_url);
+        std::fs::write(&config_path, content).unwrap();
+
+        let server = std::thread::spawn(move || {
+            // 能力探测
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"data": {"features": ["languages"]}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut buf = [0u8; 4096];
+            while !request.contains("\"code\"") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            let body = r#"{"data": {}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        let cmd = AddLanguageCmd {
+            language: "fr".to_string(),
+            config: Some(config_path),
+            from: Some("de".to_string()),
+            fill_source: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /cli/languages"));
+        assert!(request.contains(r#""code":"fr""#));
+        assert!(result.registered);
+        assert_eq!(result.backend_code, "fr");
+        assert!(!result.mapping_updated);
+    }
+}
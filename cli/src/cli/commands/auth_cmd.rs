@@ -0,0 +1,245 @@
+//! Auth command implementation
+//!
+//! `auth login` checks an API key against the backend and saves it in the
+//! system keyring under the `apiUrl` and `projectId` of the config file, so
+//! the key no longer has to live in `.i18nrc.json`; `load_config` picks it
+//! up whenever `apiKey` is empty. `auth logout` removes the saved key.
+
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::config::read_config;
+use crate::core::I18nConfig;
+use crate::core::credentials;
+use crate::core::failure::Failure;
+use crate::core::redact;
+use crate::ui::prompt;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Auth 命令参数
+#[derive(Parser, Debug)]
+#[command(name = "auth", about = "Manage the API key saved in the system keyring")]
+pub struct AuthCmd {
+    /// 子命令
+    #[command(subcommand)]
+    pub action: AuthAction,
+}
+
+/// Auth 子命令
+#[derive(Subcommand, Debug)]
+pub enum AuthAction {
+    /// Verify an API key and save it in the system keyring
+    Login(LoginArgs),
+    /// Remove the saved API key from the system keyring
+    Logout(LogoutArgs),
+}
+
+/// auth login 参数
+#[derive(Args, Debug, Default)]
+pub struct LoginArgs {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// API 密钥（默认交互输入；非交互运行时从标准输入读取一行）
+    #[arg(long, value_name = "KEY")]
+    pub api_key: Option<String>,
+
+    /// 保存前不向后端验证密钥
+    #[arg(long)]
+    pub no_verify: bool,
+}
+
+/// auth logout 参数
+#[derive(Args, Debug, Default)]
+pub struct LogoutArgs {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+}
+
+impl AuthCmd {
+    /// 执行 auth 命令
+    pub async fn run(&self, ctx: &RunContext) -> Result<()> {
+        match &self.action {
+            AuthAction::Login(args) => args.run(ctx).await,
+            AuthAction::Logout(args) => args.run(ctx),
+        }
+    }
+}
+
+impl LoginArgs {
+    /// 执行 auth login
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 读取配置文件中的 apiUrl 和 projectId
+    /// 2. 获取 API 密钥
+    /// 3. 向后端验证密钥
+    /// 4. 保存到系统密钥环
+    pub async fn run(&self, ctx: &RunContext) -> Result<()> {
+        // 1. 读取配置（不解析密钥，也不要求 apiKey）
+        let (path, mut config) = read_config(ctx.config_path(&self.config))?;
+        require_project(&path, &config)?;
+        ctx.overrides.apply(&mut config);
+
+        // 2. 获取 API 密钥
+        let api_key = match &self.api_key {
+            Some(api_key) => api_key.clone(),
+            None => match prompt::password("API key")? {
+                Some(api_key) => api_key,
+                None => {
+                    let mut line = String::new();
+                    std::io::stdin()
+                        .lock()
+                        .read_line(&mut line)
+                        .context("Failed to read the API key from stdin")?;
+                    line
+                }
+            },
+        };
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return Err(anyhow::anyhow!("No API key given"));
+        }
+        redact::register(api_key);
+
+        // 3. 验证密钥
+        if !self.no_verify {
+            let config = I18nConfig {
+                api_key: api_key.to_string(),
+                ..config.clone()
+            };
+            let client = APIClient::from_config(&config).context("Failed to create API client")?;
+            if !client.check_auth().await? {
+                return Err(Failure::Auth.error(
+                    "API authentication failed; the key was not saved"
+                ));
+            }
+        }
+
+        // 4. 保存到密钥环
+        credentials::store_api_key(&config.api_url, config.project_id, api_key)?;
+        info!(
+            "API key for project {} at {} saved to the system keyring",
+            config.project_id, config.api_url
+        );
+        if !config.api_key.is_empty() {
+            warn!(
+                "{} still sets apiKey, which takes precedence over the keyring; remove it from the file",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl LogoutArgs {
+    /// 执行 auth logout
+    pub fn run(&self, ctx: &RunContext) -> Result<()> {
+        let (path, config) = read_config(ctx.config_path(&self.config))?;
+        require_project(&path, &config)?;
+
+        if credentials::delete_api_key(&config.api_url, config.project_id)? {
+            info!(
+                "API key for project {} at {} removed from the system keyring",
+                config.project_id, config.api_url
+            );
+        } else {
+            info!(
+                "No API key saved for project {} at {}",
+                config.project_id, config.api_url
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 密钥环条目按 apiUrl 和 projectId 区分，两者都必须配置
+fn require_project(path: &Path, config: &I18nConfig) -> Result<()> {
+    if config.api_url.is_empty() || config.project_id == 0 {
+        return Err(anyhow::anyhow!(
+            "apiUrl and projectId must be set in {} before logging in",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cmd = AuthCmd::parse_from(["auth", "login", "--api-key", "secret", "--no-verify"]);
+        let AuthAction::Login(login) = cmd.action else {
+            panic!("Expected login");
+        };
+        assert_eq!(login.api_key.as_deref(), Some("secret"));
+        assert!(login.no_verify);
+
+        let cmd = AuthCmd::parse_from(["auth", "logout", "--config", "a.json"]);
+        let AuthAction::Logout(logout) = cmd.action else {
+            panic!("Expected logout");
+        };
+        assert_eq!(logout.config, Some(PathBuf::from("a.json")));
+    }
+
+    #[tokio::test]
+    async fn test_login_rejects_invalid_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            stream
+                .write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            request
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(r#"{{"messagesDir": "messages", "projectId": 1, "apiUrl": "http://{}"}}"#, addr),
+        )
+        .unwrap();
+
+        let args = LoginArgs {
+            config: Some(config_path),
+            api_key: Some("wrong".to_string()),
+            ..Default::default()
+        };
+        let err = args.run(&RunContext::default()).await.unwrap_err();
+        assert!(err.to_string().contains("the key was not saved"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /cli/auth "));
+        assert!(request.to_ascii_lowercase().contains("x-api-key: wrong"));
+    }
+
+    #[tokio::test]
+    async fn test_login_requires_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(&config_path, r#"{"messagesDir": "messages", "projectId": 1, "apiUrl": ""}"#).unwrap();
+
+        let args = LoginArgs {
+            config: Some(config_path),
+            api_key: Some("key".to_string()),
+            no_verify: true,
+        };
+        let err = args.run(&RunContext::default()).await.unwrap_err();
+        assert!(err.to_string().contains("apiUrl and projectId must be set"));
+    }
+}
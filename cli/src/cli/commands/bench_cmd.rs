@@ -0,0 +1,323 @@
+//! Bench command implementation
+//!
+//! `yflow bench --keys 10000 --dry-backend` measures how fast this machine
+//! runs the CLI's hot paths on synthetic translations, so performance
+//! regressions and slow environments (network drives, antivirus scanning
+//! every file) can be quantified in bug reports:
+//!
+//! - **scan**: reading and flattening the generated locale files from disk
+//! - **flatten**: flattening and re-nesting the translations in memory
+//! - **upload**: pushing every key in import-sized batches to a mock backend
+//!   started on localhost (`--dry-backend`); the real backend is never used
+//!
+//! The files are generated in a temporary directory that is removed
+//! afterwards; no configuration file is needed.
+
+use super::import_cmd::BATCH_SIZE;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::flatten::{flatten_object, unflatten_object};
+use crate::core::scanner::scan_messages_dir;
+use crate::core::{MessagesDir, Translations};
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// 每个生成文件中的键数
+const KEYS_PER_FILE: usize = 500;
+
+/// 生成翻译使用的语言
+const LANGUAGES: [&str; 6] = ["en", "de", "fr", "es", "ja", "zh_CN"];
+
+/// Bench 命令参数
+#[derive(Parser, Debug)]
+#[command(name = "bench", about = "Measure scan, flatten and upload throughput")]
+pub struct BenchCmd {
+    /// 每种语言生成的键数
+    #[arg(long, value_name = "N", default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub keys: u64,
+
+    /// 生成的语言数（1-6）
+    #[arg(long, value_name = "N", default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..=6))]
+    pub languages: u64,
+
+    /// 启动本地模拟后端并测量上传吞吐量
+    #[arg(long)]
+    pub dry_backend: bool,
+
+    /// 以 JSON 输出结果
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl Default for BenchCmd {
+    fn default() -> Self {
+        Self {
+            keys: 10_000,
+            languages: 3,
+            dry_backend: false,
+            json: false,
+        }
+    }
+}
+
+/// 单个阶段的测量结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase {
+    /// 阶段名称
+    pub name: String,
+    /// 耗时（秒）
+    pub seconds: f64,
+    /// 每秒处理的键数
+    pub keys_per_second: f64,
+}
+
+/// 测量报告
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    /// CLI 版本
+    pub version: String,
+    /// 操作系统和架构
+    pub platform: String,
+    /// 可用的 CPU 数
+    pub cpus: usize,
+    /// 总键数（所有语言）
+    pub keys: usize,
+    /// 生成的文件数
+    pub files: usize,
+    /// 各阶段结果
+    pub phases: Vec<Phase>,
+}
+
+impl BenchCmd {
+    /// 执行 bench 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 在临时目录中生成翻译文件
+    /// 2. 测量扫描
+    /// 3. 测量展平与还原
+    /// 4. 测量上传到本地模拟后端（`--dry-backend`）
+    /// 5. 输出报告
+    pub async fn run(&self, ctx: &RunContext) -> Result<BenchReport> {
+        // 1. 生成翻译文件
+        let workspace = TempWorkspace::create()?;
+        let languages = &LANGUAGES[..self.languages as usize];
+        let nested = generate(self.keys as usize, languages);
+        let mut files = 0;
+        for (lang, namespaces) in &nested {
+            let dir = workspace.path().join(lang);
+            std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+            for (namespace, value) in namespaces {
+                std::fs::write(dir.join(format!("{}.json", namespace)), serde_json::to_string_pretty(value)?)?;
+                files += 1;
+            }
+        }
+        let total = self.keys as usize * languages.len();
+        let mut report = BenchReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            keys: total,
+            files,
+            phases: Vec::new(),
+        };
+
+        // 2. 扫描
+        let started = Instant::now();
+        let scan = scan_messages_dir(&MessagesDir::new(workspace.path())).await?;
+        report.phases.push(Phase::new("scan", started, scan.key_count));
+
+        // 3. 展平与还原
+        let started = Instant::now();
+        let mut flattened = 0;
+        for namespaces in nested.values() {
+            for value in namespaces.values() {
+                let flat = flatten_object(value, "");
+                flattened += flat.len();
+                std::hint::black_box(unflatten_object(flat));
+            }
+        }
+        report.phases.push(Phase::new("flatten", started, flattened));
+
+        // 4. 上传到模拟后端
+        if self.dry_backend {
+            let base_url = start_mock_backend().await?;
+            let client = APIClient::new(base_url, "bench".to_string(), 1).context("Failed to create API client")?;
+            let started = Instant::now();
+            let pushed = upload(&client, scan.translations).await?;
+            report.phases.push(Phase::new("upload", started, pushed));
+        }
+
+        // 5. 输出
+        if self.json {
+            ctx.reporter.output(serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&*ctx.reporter, &report, self.dry_backend);
+        }
+        Ok(report)
+    }
+}
+
+impl Phase {
+    /// 根据开始时间和处理的键数生成结果
+    fn new(name: &str, started: Instant, keys: usize) -> Self {
+        let seconds = started.elapsed().as_secs_f64();
+        Self {
+            name: name.to_string(),
+            seconds,
+            keys_per_second: if seconds > 0.0 { keys as f64 / seconds } else { 0.0 },
+        }
+    }
+}
+
+/// 生成嵌套翻译：语言 -> 命名空间 -> 文件内容
+///
+/// 每个命名空间最多 [`KEYS_PER_FILE`] 个键，分布在三层嵌套中，值带有占位符。
+fn generate(keys: usize, languages: &[&str]) -> HashMap<String, HashMap<String, serde_json::Value>> {
+    languages
+        .iter()
+        .map(|lang| {
+            let mut namespaces: HashMap<String, HashMap<String, String>> = HashMap::new();
+            for index in 0..keys {
+                let namespace = format!("ns{}", index / KEYS_PER_FILE);
+                let key = format!("group{}.item{}.label", index % 25, index);
+                let value = format!("{} value {} for {{name}}", lang, index);
+                namespaces.entry(namespace).or_default().insert(key, value);
+            }
+            let files = namespaces
+                .into_iter()
+                .map(|(namespace, flat)| (namespace, unflatten_object(flat)))
+                .collect();
+            (lang.to_string(), files)
+        })
+        .collect()
+}
+
+/// 按导入的批次大小逐批推送，返回推送的键数
+async fn upload(client: &APIClient, translations: Translations) -> Result<usize> {
+    let mut pushed = 0;
+    for (lang, keys) in translations {
+        let entries: Vec<(String, String)> = keys.into_iter().collect();
+        for chunk in entries.chunks(BATCH_SIZE) {
+            let batch: Translations = HashMap::from([(lang.clone(), chunk.iter().cloned().collect())]);
+            client.push_translations(batch).await?;
+            pushed += chunk.len();
+        }
+    }
+    Ok(pushed)
+}
+
+/// 启动本地模拟后端，返回其地址
+///
+/// 接受任意请求（支持 keep-alive），读取请求体后返回空的成功响应。
+async fn start_mock_backend() -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .context("Failed to start the mock backend")?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut reader = BufReader::new(reader);
+                loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        match reader.read_line(&mut line).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(_) => {}
+                        }
+                        if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body = vec![0u8; content_length];
+                    if reader.read_exact(&mut body).await.is_err() {
+                        return;
+                    }
+                    let response = r#"{"data": {"added": [], "existed": [], "failed": []}}"#;
+                    let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", response.len());
+                    if writer.write_all((head + response).as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    Ok(format!("http://{}", address))
+}
+
+/// 输出报告
+fn print_report(reporter: &dyn Reporter, report: &BenchReport, dry_backend: bool) {
+    reporter.output(format!(
+        "yflow {} on {} ({} CPUs): {} keys in {} files",
+        report.version, report.platform, report.cpus, report.keys, report.files
+    ));
+    reporter.output("");
+    reporter.output(format!("  {:<8} {:>10} {:>14}", "phase", "seconds", "keys/s"));
+    for phase in &report.phases {
+        reporter.output(format!("  {:<8} {:>10.3} {:>14.0}", phase.name, phase.seconds, phase.keys_per_second));
+    }
+    if !dry_backend {
+        reporter.output("");
+        reporter.output("  upload skipped; pass --dry-backend to measure it against a local mock backend");
+    }
+}
+
+/// 临时工作目录（结束时删除）
+struct TempWorkspace(PathBuf);
+
+impl TempWorkspace {
+    /// 在系统临时目录中创建
+    fn create() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "yflow-bench-{}-{}",
+            std::process::id(),
+            crate::core::timestamp::now()
+        ));
+        std::fs::create_dir_all(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempWorkspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bench_with_dry_backend() {
+        let cmd = BenchCmd {
+            keys: 1200,
+            languages: 2,
+            dry_backend: true,
+            json: true,
+        };
+        let report = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(report.keys, 2400);
+        assert_eq!(report.files, 6);
+        let phases: Vec<&str> = report.phases.iter().map(|phase| phase.name.as_str()).collect();
+        assert_eq!(phases, ["scan", "flatten", "upload"]);
+    }
+}
@@ -0,0 +1,181 @@
+//! Check-placeholders command implementation
+//!
+//! Compares the interpolation placeholders (`{name}`, `{{var}}`, `%s` and
+//! the project's `placeholderPatterns`) of every language with the source
+//! language and reports translations that dropped, renamed or added one.
+//! `import --check` runs the same check before pushing.
+//!
+//! Any finding fails the command, so it can gate CI.
+
+use crate::cli::RunContext;
+use crate::core::failure::Failure;
+use crate::core::placeholders::{self, PlaceholderIssue};
+use crate::core::scanner::{report_quarantine, scan_messages_dir};
+use crate::core::{MessagesDir, Translations};
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Check-placeholders 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "check-placeholders", about = "Compare placeholders of every language with the source language")]
+pub struct CheckPlaceholdersCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只检查指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 源语言（默认：配置的 sourceLanguage，否则为键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub source: Option<String>,
+}
+
+impl CheckPlaceholdersCmd {
+    /// 执行 check-placeholders 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 比较各语言与源语言的占位符
+    /// 4. 输出问题
+    ///
+    /// # Errors
+    ///
+    /// 存在任何问题时返回错误（非零退出码）。
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<PlaceholderIssue>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
+
+        // 2. 扫描 messages 目录
+        let scan = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+
+        // 3. 比较占位符
+        let source = self.source.as_deref().or(config.source_language.as_deref());
+        let issues = check_translations(&*ctx.reporter, &messages_dir, &scan.translations, source, |lang| {
+            self.languages.is_empty() || self.languages.iter().any(|l| l == lang)
+        })?;
+
+        // 4. 输出问题
+        report_quarantine(&scan.quarantine)?;
+        if issues.is_empty() {
+            ctx.reporter.success("Placeholders match the source language.");
+            return Ok(issues);
+        }
+        Err(Failure::Validation.error(format!(
+            "{} translation(s) with placeholder problems",
+            issues.len()
+        )))
+    }
+}
+
+/// 比较各语言与源语言的占位符并输出问题
+///
+/// # 参数
+///
+/// * `reporter` - 输出层
+/// * `messages_dir` - messages 目录（取自定义占位符正则）
+/// * `translations` - 所有语言的翻译
+/// * `source` - 源语言（None 时为键最多的语言）
+/// * `include` - 要检查的语言
+pub(super) fn check_translations(
+    reporter: &dyn Reporter,
+    messages_dir: &MessagesDir,
+    translations: &Translations,
+    source: Option<&str>,
+    include: impl Fn(&str) -> bool,
+) -> Result<Vec<PlaceholderIssue>> {
+    let source = match source {
+        Some(source) => source.to_string(),
+        None => {
+            let mut languages: Vec<(&String, usize)> =
+                translations.iter().map(|(lang, keys)| (lang, keys.len())).collect();
+            languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            languages
+                .first()
+                .map(|(lang, _)| lang.to_string())
+                .ok_or_else(|| anyhow::anyhow!("No source language found in {}", messages_dir.display()))?
+        }
+    };
+    if !translations.contains_key(&source) {
+        return Err(anyhow::anyhow!("Source language '{}' not found in {}", source, messages_dir.display()));
+    }
+
+    info!("Checking placeholders against {}...", source);
+    let issues: Vec<PlaceholderIssue> =
+        placeholders::check(translations, &source, &messages_dir.placeholder_patterns)
+            .into_iter()
+            .filter(|issue| include(&issue.lang))
+            .collect();
+    for issue in &issues {
+        reporter.output(issue);
+    }
+    if !issues.is_empty() {
+        let count = |kind: &str| issues.iter().filter(|issue| issue.kind() == kind).count();
+        reporter.output("");
+        reporter.output(format!(
+            "{} problem(s): {} dropped, {} renamed, {} added placeholder(s)",
+            issues.len(),
+            count("dropped"),
+            count("renamed"),
+            count("added")
+        ));
+    }
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_check_placeholders_cmd() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, content) in [
+            ("en", r#"{"title": "Hi {name}", "total": "Total: :amount"}"#),
+            ("de", r#"{"title": "Hallo {name}", "total": "Summe"}"#),
+            ("fr", r#"{"title": "Salut {nom}", "total": "Total : :amount"}"#),
+        ] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k",
+                    "placeholderPatterns": [":[a-z]+"]}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = CheckPlaceholdersCmd {
+            config: Some(config_path.clone()),
+            source: Some("en".to_string()),
+            ..Default::default()
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("2 translation(s)"));
+
+        let cmd = CheckPlaceholdersCmd {
+            config: Some(config_path),
+            source: Some("en".to_string()),
+            languages: vec!["de".to_string()],
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("1 translation(s)"));
+    }
+}
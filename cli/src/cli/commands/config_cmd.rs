@@ -0,0 +1,110 @@
+//! Config command implementation
+//!
+//! `config schema` prints (or writes) the JSON Schema of `.i18nrc.json`,
+//! generated from the same serde types the CLI parses the file with, so
+//! editors can offer autocomplete and validation that never drift from
+//! the options the CLI actually accepts.
+//!
+//! `config paths` shows which configuration file is used and where the
+//! per-user config, cache and data directories are on this platform.
+
+use crate::cli::RunContext;
+use crate::core::config::{config_schema, resolve_config_path};
+use crate::core::paths;
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Config 命令参数
+#[derive(Parser, Debug)]
+#[command(name = "config", about = "Inspect the configuration file format")]
+pub struct ConfigCmd {
+    /// 子命令
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+/// Config 子命令
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the JSON Schema of .i18nrc.json
+    Schema(SchemaArgs),
+    /// Show the config file in use and the per-user directories
+    Paths,
+}
+
+/// config schema 参数
+#[derive(Args, Debug, Default)]
+pub struct SchemaArgs {
+    /// 输出文件（默认输出到标准输出）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+}
+
+impl ConfigCmd {
+    /// 执行 config 命令
+    pub async fn run(&self, ctx: &RunContext) -> Result<()> {
+        match &self.action {
+            ConfigAction::Schema(args) => args.run(&*ctx.reporter),
+            ConfigAction::Paths => {
+                print_paths(&*ctx.reporter, ctx.config.clone());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 输出使用的配置文件和用户目录
+fn print_paths(reporter: &dyn Reporter, config_path: Option<PathBuf>) {
+    let show = |path: Option<PathBuf>| path.map_or_else(|| "(unavailable)".to_string(), |p| p.display().to_string());
+    let config_file = resolve_config_path(config_path).ok();
+    let global_file = paths::global_config_file();
+    reporter.output(format!("config file:    {}", config_file.map_or_else(|| "(none found)".to_string(), |p| p.display().to_string())));
+    reporter.output(format!(
+        "global config:  {}",
+        match global_file {
+            Some(path) => path.display().to_string(),
+            None => format!(
+                "{} (not created)",
+                show(paths::config_dir().map(|dir| dir.join(paths::GLOBAL_CONFIG_FILENAMES[0])))
+            ),
+        }
+    ));
+    reporter.output(format!("cache dir:      {}", show(paths::cache_dir())));
+    reporter.output(format!("data dir:       {}", show(paths::data_dir())));
+}
+
+impl SchemaArgs {
+    /// 输出配置文件的 JSON Schema
+    pub fn run(&self, reporter: &dyn Reporter) -> Result<()> {
+        let content = serde_json::to_string_pretty(&config_schema())? + "\n";
+        match &self.out {
+            Some(path) => {
+                std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+                info!("Config schema written to {}", path.display());
+            }
+            None => reporter.write(&content),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::reporter::HumanReporter;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_schema_written_to_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let out = temp_dir.path().join("i18nrc.schema.json");
+        SchemaArgs { out: Some(out.clone()) }.run(&HumanReporter).unwrap();
+
+        let schema: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert!(schema["$schema"].as_str().unwrap().contains("json-schema.org"));
+        assert!(schema["properties"]["messagesDir"].is_object());
+    }
+}
@@ -0,0 +1,279 @@
+//! Diff-local command implementation
+//!
+//! `yflow diff-local ./messages ./messages-old` compares two local messages
+//! directories, e.g. the locale files of two build artifacts, without a
+//! configuration file or the backend. Both trees are read with the scanner
+//! and compared with the same engine as `status`; the result is reported as
+//! the change from the second directory (the base) to the first:
+//!
+//! - **added**: keys only in the first directory
+//! - **removed**: keys only in the base
+//! - **changed**: keys in both with different values
+//!
+//! `--keys` lists every differing key with its values, `--json` prints the
+//! full result, and `--exit-code` exits non-zero when anything differs.
+//! `--comparator trimmed` or `normalized-html` ignores whitespace or markup
+//! differences (see [`crate::core::compare`]).
+
+use crate::cli::RunContext;
+use crate::core::compare::Comparator;
+use crate::core::drift::compute_drift;
+use crate::core::failure::Failure;
+use crate::core::scanner::{report_quarantine, scan_messages_dir};
+use crate::core::{MessagesDir, QuarantinedFile, Translations};
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Diff-local 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "diff-local", about = "Compare two local messages directories")]
+pub struct DiffLocalCmd {
+    /// 要比较的 messages 目录
+    #[arg(value_name = "DIR")]
+    pub dir: PathBuf,
+
+    /// 作为基准的 messages 目录
+    #[arg(value_name = "BASE")]
+    pub base: PathBuf,
+
+    /// 只比较指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 值的比较方式（exact、trimmed、normalized-html）
+    #[arg(long, value_name = "COMPARATOR", default_value = "exact", value_parser = Comparator::parse)]
+    pub comparator: Comparator,
+
+    /// 列出每个不同的键及其值
+    #[arg(long)]
+    pub keys: bool,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+
+    /// 存在差异时返回非零退出码
+    #[arg(long)]
+    pub exit_code: bool,
+}
+
+/// 变化的键
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedKey {
+    /// 键名
+    pub key: String,
+    /// 基准目录中的值
+    pub old: String,
+    /// 比较目录中的值
+    pub new: String,
+}
+
+/// 单个语言的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LanguageDiff {
+    /// 语言代码
+    pub lang: String,
+    /// 新增的键（键, 值）
+    pub added: Vec<(String, String)>,
+    /// 删除的键（键, 基准中的值）
+    pub removed: Vec<(String, String)>,
+    /// 值变化的键
+    pub changed: Vec<ChangedKey>,
+    /// 相同的键数
+    pub unchanged: usize,
+}
+
+impl LanguageDiff {
+    /// 是否存在差异
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+impl DiffLocalCmd {
+    /// 执行 diff-local 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 扫描两个目录
+    /// 2. 计算差异
+    /// 3. 输出差异
+    ///
+    /// # Errors
+    ///
+    /// 目录无法扫描、有无法解析的文件，或使用 `--exit-code` 且存在差异时返回错误
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<LanguageDiff>> {
+        // 1. 扫描两个目录
+        let (current, current_quarantine) = scan(&self.dir).await?;
+        let (base, base_quarantine) = scan(&self.base).await?;
+
+        // 2. 计算差异
+        let mut diff: Vec<LanguageDiff> = compute_drift(&current, &base, self.comparator)
+            .into_iter()
+            .filter(|drift| self.languages.is_empty() || self.languages.contains(&drift.lang))
+            .map(|drift| {
+                let new_values = current.get(&drift.lang);
+                let old_values = base.get(&drift.lang);
+                let value = |values: Option<&HashMap<String, String>>, key: &String| {
+                    values.and_then(|values| values.get(key)).cloned().unwrap_or_default()
+                };
+                LanguageDiff {
+                    added: drift.local_only.iter().map(|key| (key.clone(), value(new_values, key))).collect(),
+                    removed: drift.backend_only.iter().map(|key| (key.clone(), value(old_values, key))).collect(),
+                    changed: drift
+                        .differ
+                        .iter()
+                        .map(|key| ChangedKey {
+                            key: key.clone(),
+                            old: value(old_values, key),
+                            new: value(new_values, key),
+                        })
+                        .collect(),
+                    unchanged: drift.in_sync,
+                    lang: drift.lang,
+                }
+            })
+            .collect();
+        diff.sort_by(|a, b| a.lang.cmp(&b.lang));
+
+        // 3. 输出差异
+        if self.json {
+            ctx.reporter.output(serde_json::to_string_pretty(&diff)?);
+        } else {
+            self.print_diff(&*ctx.reporter, &diff);
+        }
+
+        report_quarantine(&[current_quarantine, base_quarantine].concat())?;
+        let changed = diff.iter().filter(|lang| lang.has_changes()).count();
+        if self.exit_code && changed > 0 {
+            return Err(Failure::Drift.error(format!("{} language(s) differ", changed)));
+        }
+        Ok(diff)
+    }
+
+    /// 输出每个语言的差异
+    fn print_diff(&self, reporter: &dyn Reporter, diff: &[LanguageDiff]) {
+        reporter.output(format!("Comparing {} against {}", self.dir.display(), self.base.display()));
+        if diff.is_empty() {
+            reporter.output("No translations found in either directory.");
+            return;
+        }
+
+        reporter.output("");
+        reporter.output(format!(
+            "{:<12} {:>8} {:>8} {:>8} {:>10}",
+            "LANGUAGE", "ADDED", "REMOVED", "CHANGED", "UNCHANGED"
+        ));
+        for lang in diff {
+            reporter.output(format!(
+                "{:<12} {:>8} {:>8} {:>8} {:>10}",
+                lang.lang,
+                lang.added.len(),
+                lang.removed.len(),
+                lang.changed.len(),
+                lang.unchanged
+            ));
+        }
+
+        if self.keys {
+            for lang in diff.iter().filter(|lang| lang.has_changes()) {
+                reporter.output("");
+                reporter.output(format!("{}:", lang.lang));
+                for (key, value) in &lang.added {
+                    reporter.output(format!("  + {} = {:?}", key, value));
+                }
+                for (key, value) in &lang.removed {
+                    reporter.output(format!("  - {} = {:?}", key, value));
+                }
+                for change in &lang.changed {
+                    reporter.output(format!("  ~ {}: {:?} -> {:?}", change.key, change.old, change.new));
+                }
+            }
+        }
+
+        reporter.output("");
+        if diff.iter().any(LanguageDiff::has_changes) {
+            reporter.output("Translations differ.");
+        } else {
+            reporter.success("Translations are identical.");
+        }
+    }
+}
+
+/// 扫描一个目录，返回翻译和无法解析的文件
+async fn scan(dir: &Path) -> Result<(Translations, Vec<QuarantinedFile>)> {
+    let result = scan_messages_dir(&MessagesDir::new(dir))
+        .await
+        .with_context(|| format!("Failed to scan {}", dir.display()))?;
+    Ok((result.translations, result.quarantine))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_diff_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let current = temp_dir.path().join("messages");
+        let base = temp_dir.path().join("messages-old");
+        write(&current, "en/common.json", r#"{"title": "Home", "body": "New body", "cta": "Go"}"#);
+        write(&current, "de/common.yaml", "title: Start\n");
+        write(&base, "en/common.json", r#"{"title": "Home", "body": "Old body", "legacy": "Old"}"#);
+        write(&base, "de/common.json", r#"{"title": "Start"}"#);
+
+        let cmd = DiffLocalCmd {
+            dir: current.clone(),
+            base: base.clone(),
+            keys: true,
+            ..Default::default()
+        };
+        let diff = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(diff.len(), 2);
+        assert!(!diff[0].has_changes());
+        assert_eq!(diff[0].unchanged, 1);
+        let en = &diff[1];
+        assert_eq!(en.added, vec![("cta".to_string(), "Go".to_string())]);
+        assert_eq!(en.removed, vec![("legacy".to_string(), "Old".to_string())]);
+        assert_eq!(
+            en.changed,
+            vec![ChangedKey {
+                key: "body".to_string(),
+                old: "Old body".to_string(),
+                new: "New body".to_string(),
+            }]
+        );
+
+        // trimmed 下首尾空白不同的值相同
+        write(&base, "de/common.json", r#"{"title": " Start "}"#);
+        let cmd = DiffLocalCmd {
+            dir: current.clone(),
+            base: base.clone(),
+            languages: vec!["de".to_string()],
+            comparator: Comparator::Trimmed,
+            ..Default::default()
+        };
+        assert!(!cmd.run(&RunContext::default()).await.unwrap()[0].has_changes());
+
+        let cmd = DiffLocalCmd {
+            dir: current,
+            base,
+            languages: vec!["en".to_string()],
+            exit_code: true,
+            json: true,
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("1 language(s) differ"));
+    }
+}
@@ -0,0 +1,448 @@
+//! Explain command implementation
+//!
+//! `yflow explain user.title` collects everything the CLI knows about one
+//! key in a single report, to debug "why is this string wrong in prod":
+//!
+//! - where the key is defined locally (file and line, per language and
+//!   including brand overlays) and its local values
+//! - the backend values, its owner, route and any key lock covering it
+//! - the value recorded by the last sync, what the next sync/import would do
+//!   with the key, and how the last sync resolved a conflict on it
+//! - the lint findings for its values
+//!
+//! `--offline` skips the backend; the status then only compares the local
+//! value with the last synced one.
+
+use super::SyncCmd;
+use crate::cli::RunContext;
+use crate::api::capabilities::Feature;
+use crate::api::client::APIClient;
+use crate::core::conflicts::{read_conflict_report, Resolution, SyncState, REPORT_FILENAME};
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::lint::{LintIssue, Linter, Severity};
+use crate::core::locks::{lock_for, KeyLock};
+use crate::core::overlays::scan_overlays;
+use crate::core::ownership::Ownership;
+use crate::core::routing::Router;
+use crate::core::scanner::{resolve_file, scan_messages_dir};
+use crate::core::validate::key_lines;
+use crate::core::Translations;
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Explain 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "explain", about = "Show everything known about one key")]
+pub struct ExplainCmd {
+    /// 键名（如 user.title）
+    #[arg(value_name = "KEY")]
+    pub key: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 不访问后端，只使用本地文件和上次同步的状态
+    #[arg(long)]
+    pub offline: bool,
+
+    /// 冲突报告路径（默认为 messages 目录下的 .yflow-conflicts.json）
+    #[arg(long, value_name = "PATH")]
+    pub conflicts_report: Option<PathBuf>,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// 键在一种语言中的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStatus {
+    /// 本地与后端一致
+    InSync,
+    /// 本地值与上次同步时相同（未查询后端）
+    Unchanged,
+    /// 只有本地修改过，下次 import 推送
+    LocalEdit,
+    /// 只有后端修改过，下次 sync 拉取
+    BackendChange,
+    /// 双方都修改过，下次 sync 按冲突处理
+    Conflict,
+}
+
+impl KeyStatus {
+    /// 三方比较得出状态
+    ///
+    /// `remote` 为 `None` 表示未查询后端；值为 `None` 表示该侧没有此键。
+    fn of(base: Option<&str>, local: Option<&str>, remote: Option<Option<&str>>) -> Self {
+        match remote {
+            None if local == base => Self::Unchanged,
+            None => Self::LocalEdit,
+            Some(remote) if local == remote => Self::InSync,
+            Some(remote) if remote == base => Self::LocalEdit,
+            Some(_) if local == base => Self::BackendChange,
+            Some(_) => Self::Conflict,
+        }
+    }
+
+    /// 状态说明
+    fn describe(self) -> &'static str {
+        match self {
+            Self::InSync => "in sync with the backend",
+            Self::Unchanged => "unchanged since the last sync (backend not checked)",
+            Self::LocalEdit => "edited locally since the last sync; the next import pushes it",
+            Self::BackendChange => "changed on the backend since the last sync; the next sync pulls it",
+            Self::Conflict => "changed on both sides since the last sync; the next sync reports a conflict",
+        }
+    }
+}
+
+/// 键在一种语言中的信息
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageExplanation {
+    /// 定义该键的文件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    /// 所在行号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 本地值
+    pub local: Option<String>,
+    /// 后端值（`--offline` 时不输出）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<Option<String>>,
+    /// 上次同步时的值
+    pub last_synced: Option<String>,
+    /// 状态
+    pub status: KeyStatus,
+    /// 路由到的后端项目
+    pub project_id: u64,
+    /// 上次同步对该键冲突的解决方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_conflict: Option<Resolution>,
+}
+
+/// 键的完整信息
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    /// 翻译键
+    pub key: String,
+    /// 负责的团队
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// 覆盖该键的锁
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<KeyLock>,
+    /// 按语言的信息
+    pub languages: BTreeMap<String, LanguageExplanation>,
+    /// lint 问题
+    pub lint: Vec<LintIssue>,
+}
+
+impl ExplainCmd {
+    /// 执行 explain 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地文件和覆盖层，定位键的来源
+    /// 3. 读取后端的值和键锁（`--offline` 时跳过）
+    /// 4. 读取上次同步的状态和冲突报告
+    /// 5. 运行 lint 并输出
+    ///
+    /// # Errors
+    ///
+    /// 键在本地、上次同步的状态和后端中都不存在时返回错误。
+    pub async fn run(&self, ctx: &RunContext) -> Result<Explanation> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
+
+        // 2. 扫描本地文件；覆盖层中的定义优先（靠后的覆盖层优先）
+        let scan = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+        let overlays = scan_overlays(&config.overlay_dirs(), false).await?;
+        let mut local: HashMap<String, (String, Option<PathBuf>)> = HashMap::new();
+        let layers = std::iter::once((&messages_dir, &scan))
+            .chain(overlays.iter().map(|overlay| (&overlay.dir, &overlay.scan)));
+        for (dir, layer) in layers {
+            for (lang, keys) in &layer.translations {
+                if let Some(value) = keys.get(&self.key) {
+                    let file = layer.origin(lang, &self.key).map(|origin| resolve_file(dir, origin));
+                    local.insert(lang.clone(), (value.clone(), file));
+                }
+            }
+        }
+
+        // 3. 读取后端
+        let mut remote: Option<Translations> = None;
+        let mut lock = None;
+        if !self.offline {
+            let client = APIClient::from_config(&config)
+                .context("Failed to create API client")?;
+            if !client.check_auth().await? {
+                return Err(Failure::Auth.error("API authentication failed. Please check your API key."));
+            }
+            let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+            remote = Some(SyncCmd::fetch_backend_translations(&config, &client, &language_mapper).await?);
+            if client.supports(Feature::Locks).await? {
+                lock = lock_for(&client.list_locks().await?, &self.key, config.separator()).cloned();
+            }
+        }
+
+        // 4. 上次同步的状态和冲突报告
+        let base = SyncState::load(&config.messages_dir)?.translations;
+        let report_path = self
+            .conflicts_report
+            .clone()
+            .unwrap_or_else(|| config.messages_dir.join(REPORT_FILENAME));
+        let conflicts = read_conflict_report(&report_path)?;
+
+        let value_in = |translations: &Translations, lang: &str| {
+            translations.get(lang).and_then(|keys| keys.get(&self.key)).cloned()
+        };
+        let mut languages: BTreeSet<String> = local.keys().cloned().collect();
+        for translations in std::iter::once(&base).chain(remote.as_ref()) {
+            languages.extend(
+                translations
+                    .iter()
+                    .filter(|(_, keys)| keys.contains_key(&self.key))
+                    .map(|(lang, _)| lang.clone()),
+            );
+        }
+        if languages.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Key '{}' not found in local files, the last sync state{}",
+                self.key,
+                if self.offline { "" } else { " or on the backend" }
+            ));
+        }
+
+        let router = Router::from_config(&config);
+        let mut explanation = Explanation {
+            key: self.key.clone(),
+            owner: Ownership::new(&config.owners, config.separator()).owner_of(&self.key).map(str::to_string),
+            lock,
+            languages: BTreeMap::new(),
+            lint: Vec::new(),
+        };
+        let mut local_values = Translations::new();
+        for lang in languages {
+            let (local_value, file) = match local.remove(&lang) {
+                Some((value, file)) => (Some(value), file),
+                None => (None, None),
+            };
+            let line = file
+                .as_deref()
+                .and_then(|file| key_lines(file, config.separator()).ok())
+                .and_then(|lines| lines.get(&self.key).copied());
+            let last_synced = value_in(&base, &lang);
+            let remote_value = remote.as_ref().map(|remote| value_in(remote, &lang));
+            let status = KeyStatus::of(
+                last_synced.as_deref(),
+                local_value.as_deref(),
+                remote_value.as_ref().map(|value| value.as_deref()),
+            );
+            let last_conflict = conflicts
+                .iter()
+                .find(|conflict| conflict.lang == lang && conflict.key == self.key)
+                .map(|conflict| conflict.resolution);
+            if let Some(value) = &local_value {
+                local_values.entry(lang.clone()).or_default().insert(self.key.clone(), value.clone());
+            }
+            explanation.languages.insert(
+                lang.clone(),
+                LanguageExplanation {
+                    file,
+                    line,
+                    local: local_value,
+                    remote: remote_value,
+                    last_synced,
+                    status,
+                    project_id: router.target_for(&lang, &self.key).project_id,
+                    last_conflict,
+                },
+            );
+        }
+
+        // 5. lint 并输出
+        explanation.lint = Linter::new(&config.lint_rules)?.lint(&local_values);
+        if self.json {
+            ctx.reporter.output(serde_json::to_string_pretty(&explanation)?);
+        } else {
+            print_explanation(&*ctx.reporter, &explanation, router.has_routes());
+        }
+
+        Ok(explanation)
+    }
+}
+
+/// 输出键的信息
+fn print_explanation(reporter: &dyn Reporter, explanation: &Explanation, show_project: bool) {
+    let show = |value: &Option<String>| {
+        value
+            .as_ref()
+            .map(|value| format!("{:?}", value))
+            .unwrap_or_else(|| "(missing)".to_string())
+    };
+
+    reporter.output(&explanation.key);
+    if let Some(owner) = &explanation.owner {
+        reporter.output(format!("  owner: {}", owner));
+    }
+    if let Some(lock) = &explanation.lock {
+        reporter.output(format!(
+            "  locked by '{}' (prefix '{}'{})",
+            lock.holder,
+            lock.prefix,
+            lock.expires_at.as_ref().map(|at| format!(", expires {}", at)).unwrap_or_default()
+        ));
+    }
+
+    for (lang, info) in &explanation.languages {
+        reporter.output("");
+        reporter.output(format!("  [{}]", lang));
+        match (&info.file, info.line) {
+            (Some(file), Some(line)) => reporter.output(format!("    defined in:  {}:{}", file.display(), line)),
+            (Some(file), None) => reporter.output(format!("    defined in:  {}", file.display())),
+            _ => reporter.output("    defined in:  (not in local files)"),
+        }
+        reporter.output(format!("    local:       {}", show(&info.local)));
+        if let Some(remote) = &info.remote {
+            reporter.output(format!("    backend:     {}", show(remote)));
+        }
+        reporter.output(format!("    last sync:   {}", show(&info.last_synced)));
+        if show_project {
+            reporter.output(format!("    project:     {}", info.project_id));
+        }
+        reporter.output(format!("    status:      {}", info.status.describe()));
+        if let Some(resolution) = info.last_conflict {
+            let decision = match resolution {
+                Resolution::Remote => "took the backend value",
+                Resolution::Local => "kept the local value",
+                Resolution::Unresolved => "left it unresolved (local value kept)",
+            };
+            reporter.output(format!("    last sync:   conflict, {}", decision));
+        }
+    }
+
+    reporter.output("");
+    if explanation.lint.is_empty() {
+        reporter.output("  lint: no issues");
+    } else {
+        reporter.output("  lint:");
+        for issue in &explanation.lint {
+            let marker = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            reporter.output(format!("    {}[{}] {}: {}", marker, issue.rule, issue.lang, issue.message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::conflicts::{write_conflict_report, Conflict};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_status() {
+        let status = |base, local, remote| KeyStatus::of(base, local, remote);
+        assert_eq!(status(Some("a"), Some("a"), None), KeyStatus::Unchanged);
+        assert_eq!(status(Some("a"), Some("b"), None), KeyStatus::LocalEdit);
+        assert_eq!(status(Some("a"), Some("b"), Some(Some("b"))), KeyStatus::InSync);
+        assert_eq!(status(Some("a"), Some("b"), Some(Some("a"))), KeyStatus::LocalEdit);
+        assert_eq!(status(Some("a"), Some("a"), Some(Some("c"))), KeyStatus::BackendChange);
+        assert_eq!(status(None, None, Some(Some("c"))), KeyStatus::BackendChange);
+        assert_eq!(status(Some("a"), Some("b"), Some(Some("c"))), KeyStatus::Conflict);
+    }
+
+    #[tokio::test]
+    async fn test_explain_offline() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, title) in [("en", "Profile!"), ("de", "Profil")] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(
+                messages_dir.join(lang).join("user.json"),
+                format!("{{\n  \"name\": \"Name\",\n  \"user\": {{\n    \"title\": \"{}\"\n  }}\n}}\n", title),
+            )
+            .unwrap();
+        }
+        SyncState {
+            translations: Translations::from([
+                ("en".to_string(), HashMap::from([("user.title".to_string(), "Profile".to_string())])),
+                ("de".to_string(), HashMap::from([("user.title".to_string(), "Profil".to_string())])),
+                ("fr".to_string(), HashMap::from([("user.title".to_string(), "Profil".to_string())])),
+            ]),
+        }
+        .save(&messages_dir)
+        .unwrap();
+        write_conflict_report(
+            &messages_dir.join(REPORT_FILENAME),
+            &[Conflict {
+                lang: "en".to_string(),
+                key: "user.title".to_string(),
+                base: None,
+                local: "Profile".to_string(),
+                remote: "Your profile".to_string(),
+                resolution: Resolution::Local,
+            }],
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k",
+                    "owners": {{"user.": "team-profile"}},
+                    "lintRules": [{{"name": "no-exclamation", "forbid": "!"}}]}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = ExplainCmd {
+            key: "user.title".to_string(),
+            config: Some(config_path.clone()),
+            offline: true,
+            json: true,
+            ..Default::default()
+        };
+        let explanation = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(explanation.owner.as_deref(), Some("team-profile"));
+
+        let en = &explanation.languages["en"];
+        assert_eq!(en.file.as_deref(), Some(messages_dir.join("en/user.json").as_path()));
+        assert_eq!(en.line, Some(4));
+        assert_eq!(en.status, KeyStatus::LocalEdit);
+        assert_eq!(en.last_conflict, Some(Resolution::Local));
+        assert!(en.remote.is_none());
+        assert_eq!(explanation.languages["de"].status, KeyStatus::Unchanged);
+        // 本地已删除，但上次同步时存在
+        assert_eq!(explanation.languages["fr"].local, None);
+        assert_eq!(explanation.languages["fr"].status, KeyStatus::LocalEdit);
+
+        assert_eq!(explanation.lint.len(), 1);
+        assert_eq!(explanation.lint[0].lang, "en");
+
+        let cmd = ExplainCmd {
+            key: "user.missing".to_string(),
+            config: Some(config_path),
+            offline: true,
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("not found"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Export command implementation
+//!
+//! Writes local translations to a handoff package (one flat JSON file per
+//! language plus a `manifest.json`). With `--since-snapshot`, only keys
+//! added or changed since that snapshot are exported, so translation
+//! vendors billing per delta receive just the new work.
+//!
+//! # Features
+//!
+//! - Full or differential export
+//! - Snapshot references by id or by timestamp
+//! - Records a new snapshot after each export (unless `--no-snapshot`)
+
+use crate::cli::RunContext;
+use crate::core::scanner::scan_messages_dir;
+use crate::core::snapshot::Snapshot;
+use crate::core::{timestamp, Translations};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// 默认导出目录
+const DEFAULT_OUT_DIR: &str = "export";
+
+/// Export 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "export", about = "Export translations as a handoff package")]
+pub struct ExportCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 输出目录（默认 ./export）
+    #[arg(short, long, value_name = "DIR")]
+    pub out: Option<PathBuf>,
+
+    /// 只导出自该快照（ID 或时间点）以来新增或修改的键
+    #[arg(long, value_name = "ID|TIMESTAMP")]
+    pub since_snapshot: Option<String>,
+
+    /// 只导出指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 导出后不记录新快照
+    #[arg(long)]
+    pub no_snapshot: bool,
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Default)]
+pub struct ExportResult {
+    /// 导出的键数
+    pub exported: usize,
+    /// 自基线快照以来删除的键数
+    pub removed: usize,
+    /// 本次记录的快照 ID
+    pub snapshot: Option<String>,
+}
+
+impl ExportCmd {
+    /// 执行导出命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 计算与基线快照的差异（如指定）
+    /// 4. 记录新快照
+    /// 5. 写入导出包
+    pub async fn run(&self, ctx: &RunContext) -> Result<ExportResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 扫描 messages 目录
+        let scan_result = scan_messages_dir(&config.messages())
+            .await
+            .context("Failed to scan messages directory")?;
+        let current = scan_result.translations;
+
+        // 3. 计算差异
+        let (since, mut exported, removed) = match &self.since_snapshot {
+            Some(reference) => {
+                let base = Snapshot::resolve(&config.messages_dir, reference)?;
+                info!(
+                    "Exporting changes since snapshot {} ({})",
+                    base.id,
+                    timestamp::format_rfc3339(base.created_at)
+                );
+                let delta = base.delta(&current);
+                (Some(base.id), delta.changed, delta.removed)
+            }
+            None => (None, current.clone(), Default::default()),
+        };
+        if !self.languages.is_empty() {
+            exported.retain(|lang, _| self.languages.contains(lang));
+        }
+
+        // 4. 记录新快照（基于完整的当前翻译）
+        let snapshot = if self.no_snapshot {
+            None
+        } else {
+            let snapshot = Snapshot::new(current);
+            snapshot.save(&config.messages_dir)?;
+            Some(snapshot.id)
+        };
+
+        // 5. 写入导出包
+        let out_dir = self.out.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_OUT_DIR));
+        let manifest = serde_json::json!({
+            "since": since,
+            "snapshot": snapshot,
+            "createdAt": timestamp::format_rfc3339(timestamp::now()),
+            "keys": exported.iter().map(|(lang, t)| (lang.clone(), t.len())).collect::<BTreeMap<_, _>>(),
+            "removed": removed.iter().collect::<BTreeMap<_, _>>(),
+        });
+        write_package(&out_dir, &exported, &manifest)?;
+
+        let result = ExportResult {
+            exported: exported.values().map(|t| t.len()).sum(),
+            removed: removed.values().map(|k| k.len()).sum(),
+            snapshot,
+        };
+
+        info!("Export complete: {}", out_dir.display());
+        info!("  - Exported keys: {}", result.exported);
+        if since.is_some() {
+            info!("  - Removed since snapshot: {}", result.removed);
+        }
+        if let Some(id) = &result.snapshot {
+            info!("  - New snapshot: {} (use with --since-snapshot next time)", id);
+        }
+
+        Ok(result)
+    }
+}
+
+/// 写入导出包：每种语言一个扁平 JSON 文件及 manifest.json
+fn write_package(out_dir: &Path, translations: &Translations, manifest: &serde_json::Value) -> Result<()> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create export directory: {}", out_dir.display()))?;
+
+    for (lang, lang_translations) in translations {
+        let sorted: BTreeMap<&String, &String> = lang_translations.iter().collect();
+        let path = out_dir.join(format!("{}.json", lang));
+        std::fs::write(&path, serde_json::to_string_pretty(&sorted)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    let path = out_dir.join("manifest.json");
+    std::fs::write(&path, serde_json::to_string_pretty(manifest)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(common: &str) -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en").join("common.json"), common).unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_export_since_snapshot() {
+        let (temp_dir, config_path) = setup(r#"{"title": "Title", "body": "Body"}"#);
+        let messages_dir = temp_dir.path().join("messages");
+        let out = temp_dir.path().join("out");
+
+        let base = Snapshot {
+            id: "20260101T000000Z".to_string(),
+            created_at: 1_767_225_600,
+            translations: std::collections::HashMap::from([(
+                "en".to_string(),
+                std::collections::HashMap::from([
+                    ("title".to_string(), "Title".to_string()),
+                    ("body".to_string(), "Old body".to_string()),
+                    ("gone".to_string(), "Gone".to_string()),
+                ]),
+            )]),
+        };
+        base.save(&messages_dir).unwrap();
+
+        let cmd = ExportCmd {
+            config: Some(config_path),
+            out: Some(out.clone()),
+            since_snapshot: Some("20260101T000000Z".to_string()),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        assert_eq!(result.exported, 1);
+        assert_eq!(result.removed, 1);
+        let en: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out.join("en.json")).unwrap()).unwrap();
+        assert_eq!(en, serde_json::json!({"body": "Body"}));
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(manifest["since"], "20260101T000000Z");
+        assert_eq!(manifest["removed"]["en"][0], "gone");
+
+        // 记录了新快照
+        assert_eq!(Snapshot::list(&messages_dir).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_export_full_without_snapshot() {
+        let (temp_dir, config_path) = setup(r#"{"title": "Title"}"#);
+        let out = temp_dir.path().join("out");
+
+        let cmd = ExportCmd {
+            config: Some(config_path),
+            out: Some(out.clone()),
+            no_snapshot: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        assert_eq!(result.exported, 1);
+        assert!(result.snapshot.is_none());
+        assert!(Snapshot::list(&temp_dir.path().join("messages")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Fmt command implementation
+//!
+//! Keeps the locale file layout within the configured limits. `fmt` lists
+//! the files holding more keys than `maxKeysPerFile` (or `--max-keys`);
+//! `fmt --split` splits each of them one namespace level deeper (see
+//! [`crate::core::file_split`]), so new keys of a group are routed to its
+//! new file on the next sync.
+
+use crate::cli::RunContext;
+use crate::core::file_split::{self, oversized_files, plan_split};
+use crate::core::scanner::scan_messages_dir;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Fmt 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "fmt", about = "Split locale files over the key limit")]
+pub struct FmtCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 按命名空间拆分超出上限的文件
+    #[arg(long)]
+    pub split: bool,
+
+    /// 每个文件的最大键数（覆盖 `maxKeysPerFile`）
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_keys: Option<u64>,
+
+    /// 只处理指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 只显示会创建的文件，不写入
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl FmtCmd {
+    /// 执行 fmt 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置，确定键数上限
+    /// 2. 扫描并找出超出上限的文件
+    /// 3. `--split` 时按命名空间拆分
+    ///
+    /// # Returns
+    ///
+    /// 拆分的文件数
+    ///
+    /// # Errors
+    ///
+    /// 没有配置上限，或文件无法读写时返回错误
+    pub async fn run(&self, ctx: &RunContext) -> Result<usize> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let max = self
+            .max_keys
+            .map(|max| max as usize)
+            .or(config.max_keys_per_file)
+            .ok_or_else(|| anyhow::anyhow!("No key limit: set maxKeysPerFile in the config or pass --max-keys"))?;
+
+        // 2. 找出超出上限的文件
+        let messages_dir = config.messages();
+        let scan = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+        let oversized: Vec<_> = oversized_files(&messages_dir, &scan.files, max)?
+            .into_iter()
+            .filter(|file| self.languages.is_empty() || self.languages.contains(&file.lang))
+            .collect();
+        if oversized.is_empty() {
+            ctx.reporter.success(format!("All locale files have at most {} keys.", max));
+            return Ok(0);
+        }
+        info!("{} file(s) have more than {} keys:", oversized.len(), max);
+        for file in &oversized {
+            info!("  - {} ({} keys)", file.path.display(), file.keys);
+        }
+        if !self.split {
+            info!("Run `yflow fmt --split` to split them by namespace.");
+            return Ok(0);
+        }
+
+        // 3. 拆分
+        let mut split = 0;
+        for file in &oversized {
+            let plan = match plan_split(&messages_dir, file) {
+                Ok(plan) => plan,
+                Err(e) => {
+                    warn!("{:#}", e);
+                    continue;
+                }
+            };
+            info!(
+                "{}{} → {} file(s), {} key(s) kept",
+                if self.dry_run { "[dry-run] " } else { "" },
+                file.path.display(),
+                plan.parts.len(),
+                plan.kept
+            );
+            for (target, (_, keys)) in &plan.parts {
+                let note = if *keys > max { " (still over the limit, run again to split deeper)" } else { "" };
+                info!("  - {} ({} keys){}", target.display(), keys, note);
+            }
+            if !self.dry_run {
+                file_split::apply(&plan)?;
+            }
+            split += 1;
+        }
+
+        if split < oversized.len() {
+            warn!("{} file(s) could not be split", oversized.len() - split);
+        }
+        Ok(split)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::core::MessagesDir;
+
+    #[tokio::test]
+    async fn test_fmt_split() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(
+            messages_dir.join("en/common.json"),
+            r#"{"auth": {"login": "Log in", "logout": "Log out"}, "nav": {"home": "Home"}, "title": "Home"}"#,
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k", "maxKeysPerFile": 2}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = FmtCmd {
+            config: Some(config_path.clone()),
+            split: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        assert_eq!(cmd.run(&RunContext::default()).await.unwrap(), 1);
+        assert!(!messages_dir.join("en/common/auth.json").exists());
+
+        let cmd = FmtCmd {
+            config: Some(config_path.clone()),
+            split: true,
+            ..Default::default()
+        };
+        assert_eq!(cmd.run(&RunContext::default()).await.unwrap(), 1);
+        assert!(messages_dir.join("en/common/auth.json").exists());
+        assert!(messages_dir.join("en/common/nav.json").exists());
+
+        // 拆分后键不变，且都在上限之内
+        let messages_dir = MessagesDir::new(&messages_dir);
+        let scan = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(scan.translations["en"].len(), 4);
+        assert!(oversized_files(&messages_dir, &scan.files, 2).unwrap().is_empty());
+    }
+}
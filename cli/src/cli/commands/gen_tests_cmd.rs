@@ -0,0 +1,389 @@
+//! Gen-tests command implementation
+//!
+//! Generates a smoke-test file for the host project that renders every
+//! ICU message with sample arguments, so a broken message fails the host's
+//! test suite instead of production.
+//!
+//! # Output formats (chosen by extension of `--out`)
+//!
+//! - `.ts` / `.js`: a Jest/Vitest suite using `intl-messageformat`
+//! - `.rs`: a self-contained Rust test with a minimal ICU renderer
+
+use crate::cli::RunContext;
+use crate::core::icu::{is_icu_message, parse_arguments, sample_arguments, SampleArgs, SampleValue};
+use crate::core::scanner::scan_messages_dir;
+use crate::core::Translations;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// 单个测试用例：(语言, 键, 消息, 示例参数)
+type TestCase = (String, String, String, SampleArgs);
+
+/// Gen-tests 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "gen-tests", about = "Generate an ICU smoke-test file for the host project")]
+pub struct GenTestsCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 输出文件（扩展名决定格式：.rs / .ts / .js）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: PathBuf,
+
+    /// 只包含指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+}
+
+impl GenTestsCmd {
+    /// 执行 gen-tests 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 收集含 ICU 参数的消息并生成示例参数
+    /// 4. 按输出扩展名生成测试文件
+    ///
+    /// # Returns
+    ///
+    /// 生成的测试用例数
+    pub async fn run(&self, ctx: &RunContext) -> Result<usize> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 扫描 messages 目录
+        let mut scan_result = scan_messages_dir(&config.messages())
+            .await
+            .context("Failed to scan messages directory")?;
+        if !self.languages.is_empty() {
+            scan_result
+                .translations
+                .retain(|lang, _| self.languages.contains(lang));
+        }
+
+        // 3. 收集测试用例
+        let cases = collect_cases(&scan_result.translations);
+
+        // 4. 生成测试文件
+        let content = match self.out.extension().and_then(|e| e.to_str()) {
+            Some("rs") => render_rust(&cases),
+            Some("ts") | Some("js") => render_typescript(&cases),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported output format: {} (expected .rs, .ts or .js)",
+                    self.out.display()
+                ));
+            }
+        };
+        write_output(&self.out, &content)?;
+
+        info!(
+            "Generated {} test case(s) into {}",
+            cases.len(),
+            self.out.display()
+        );
+        Ok(cases.len())
+    }
+}
+
+/// 收集所有含 ICU 参数的消息，为每组示例参数生成一个用例
+///
+/// 语法错误的消息也会生成一个空参数用例，使宿主项目的测试失败。
+fn collect_cases(translations: &Translations) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+
+    let mut langs: Vec<&String> = translations.keys().collect();
+    langs.sort();
+    for lang in langs {
+        let mut entries: Vec<(&String, &String)> = translations[lang].iter().collect();
+        entries.sort();
+        for (key, value) in entries {
+            if !is_icu_message(value) {
+                continue;
+            }
+            let samples = parse_arguments(value)
+                .map(|args| sample_arguments(&args))
+                .unwrap_or_else(|_| vec![SampleArgs::new()]);
+            for args in samples {
+                cases.push((lang.clone(), key.clone(), value.clone(), args));
+            }
+        }
+    }
+
+    cases
+}
+
+/// 写入输出文件（必要时创建父目录）
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write test file: {}", path.display()))
+}
+
+/// 生成 TypeScript/JavaScript 测试（Jest / Vitest）
+fn render_typescript(cases: &[TestCase]) -> String {
+    let rows: Vec<serde_json::Value> = cases
+        .iter()
+        .map(|(lang, key, message, args)| {
+            let args: serde_json::Map<String, serde_json::Value> = args
+                .iter()
+                .map(|(name, value)| {
+                    let json = match value {
+                        SampleValue::Text(s) => serde_json::Value::from(s.clone()),
+                        SampleValue::Number(n) | SampleValue::Timestamp(n) => serde_json::Value::from(*n),
+                    };
+                    (name.clone(), json)
+                })
+                .collect();
+            serde_json::json!([lang.replace('_', "-"), key, message, args])
+        })
+        .collect();
+    let rows = serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string());
+
+    format!(
+        r#"// Generated by `yflow gen-tests`. Do not edit; re-run the command instead.
+import {{ IntlMessageFormat }} from 'intl-messageformat';
+
+const cases: Array<[string, string, string, Record<string, string | number>]> = {rows};
+
+describe('i18n smoke test', () => {{
+  it.each(cases)('%s %s renders', (locale, _key, message, args) => {{
+    expect(() => new IntlMessageFormat(message, locale).format(args)).not.toThrow();
+  }});
+}});
+"#
+    )
+}
+
+/// 生成 Rust 测试（内置最小 ICU 渲染器，无外部依赖）
+fn render_rust(cases: &[TestCase]) -> String {
+    let rows: String = cases
+        .iter()
+        .map(|(lang, key, message, args)| {
+            let args = args
+                .iter()
+                .map(|(name, value)| format!("({:?}, {:?})", name, value.as_string()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("        ({:?}, {:?}, {:?}, &[{}]),\n", lang, key, message, args)
+        })
+        .collect();
+
+    format!(
+        r#"//! Generated by `yflow gen-tests`. Do not edit; re-run the command instead.
+
+use std::collections::HashMap;
+
+#[allow(clippy::type_complexity)]
+const CASES: &[(&str, &str, &str, &[(&str, &str)])] = &[
+{rows}];
+
+#[test]
+fn i18n_smoke() {{
+    let mut failures = Vec::new();
+    for (lang, key, message, args) in CASES {{
+        let args: HashMap<&str, &str> = args.iter().copied().collect();
+        if let Err(error) = render(message, &args) {{
+            failures.push(format!("{{}}:{{}}: {{}}", lang, key, error));
+        }}
+    }}
+    assert!(failures.is_empty(), "Broken messages:\n{{}}", failures.join("\n"));
+}}
+{RUST_RENDERER}"#
+    )
+}
+
+/// 嵌入生成的 Rust 测试中的 ICU 渲染器
+const RUST_RENDERER: &str = r#"
+fn render(message: &str, args: &HashMap<&str, &str>) -> Result<String, String> {
+    let chars: Vec<char> = message.chars().collect();
+    let mut pos = 0;
+    render_until(&chars, &mut pos, args, false, None)
+}
+
+fn render_until(
+    chars: &[char],
+    pos: &mut usize,
+    args: &HashMap<&str, &str>,
+    nested: bool,
+    hash: Option<&str>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    while *pos < chars.len() {
+        match chars[*pos] {
+            '}' if nested => return Ok(out),
+            '}' => return Err(format!("unmatched '}}' at {}", pos)),
+            '#' if hash.is_some() => {
+                out.push_str(hash.unwrap_or_default());
+                *pos += 1;
+            }
+            '\'' if chars.get(*pos + 1) == Some(&'\'') => {
+                out.push('\'');
+                *pos += 2;
+            }
+            '\'' if matches!(chars.get(*pos + 1), Some('{' | '}' | '#' | '|')) => {
+                *pos += 1;
+                while *pos < chars.len() && chars[*pos] != '\'' {
+                    out.push(chars[*pos]);
+                    *pos += 1;
+                }
+                *pos += 1;
+            }
+            '{' => {
+                *pos += 1;
+                out.push_str(&render_argument(chars, pos, args)?);
+            }
+            c => {
+                out.push(c);
+                *pos += 1;
+            }
+        }
+    }
+    if nested {
+        return Err("unclosed '{'".to_string());
+    }
+    Ok(out)
+}
+
+fn read_until(chars: &[char], pos: &mut usize, stops: &[char]) -> String {
+    let start = *pos;
+    while *pos < chars.len() && !stops.contains(&chars[*pos]) {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect::<String>().trim().to_string()
+}
+
+fn render_argument(chars: &[char], pos: &mut usize, args: &HashMap<&str, &str>) -> Result<String, String> {
+    let name = read_until(chars, pos, &[',', '}']);
+    if *pos >= chars.len() {
+        return Err(format!("unclosed argument '{}'", name));
+    }
+    let value = *args.get(name.as_str()).ok_or_else(|| format!("missing argument '{}'", name))?;
+    if chars[*pos] == '}' {
+        *pos += 1;
+        return Ok(value.to_string());
+    }
+    *pos += 1;
+    let kind = read_until(chars, pos, &[',', '}']);
+    if *pos >= chars.len() {
+        return Err(format!("unclosed argument '{}'", name));
+    }
+    match kind.as_str() {
+        "number" | "date" | "time" => {
+            read_until(chars, pos, &['}']);
+            *pos += 1;
+            Ok(value.to_string())
+        }
+        "plural" | "selectordinal" | "select" => {
+            *pos += 1;
+            let hash = if kind == "select" { None } else { Some(value) };
+            let mut chosen = None;
+            let mut other = None;
+            loop {
+                let selector = read_until(chars, pos, &['{', '}']);
+                if *pos >= chars.len() {
+                    return Err(format!("unclosed argument '{}'", name));
+                }
+                if chars[*pos] == '}' {
+                    *pos += 1;
+                    break;
+                }
+                let selector = selector.split_whitespace().last().unwrap_or_default().to_string();
+                *pos += 1;
+                let branch = render_until(chars, pos, args, true, hash)?;
+                *pos += 1;
+                let hit = if kind == "select" {
+                    selector == value
+                } else {
+                    selector.strip_prefix('=') == Some(value) || (selector == "one" && value == "1")
+                };
+                if selector == "other" {
+                    other = Some(branch);
+                } else if hit && chosen.is_none() {
+                    chosen = Some(branch);
+                }
+            }
+            let other = other.ok_or_else(|| format!("'{}' is missing the 'other' option", name))?;
+            Ok(chosen.unwrap_or(other))
+        }
+        other => Err(format!("unknown argument type '{}' for '{}'", other, name)),
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn translations() -> Translations {
+        HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("plain".to_string(), "Hello".to_string()),
+                ("greeting".to_string(), "Hello {name}".to_string()),
+                (
+                    "items".to_string(),
+                    "{count, plural, one {# item} other {# items}}".to_string(),
+                ),
+            ]),
+        )])
+    }
+
+    #[test]
+    fn test_collect_cases_skips_plain_messages() {
+        let cases = collect_cases(&translations());
+        assert!(cases.iter().all(|(_, key, _, _)| key != "plain"));
+        // greeting: 1 组，items: 0/1/2/5 共 4 组
+        assert_eq!(cases.len(), 5);
+    }
+
+    #[test]
+    fn test_render_typescript() {
+        let content = render_typescript(&collect_cases(&translations()));
+        assert!(content.contains("from 'intl-messageformat'"));
+        assert!(content.contains("\"greeting\""));
+        assert!(content.contains("\"count\": 5"));
+    }
+
+    #[test]
+    fn test_render_rust() {
+        let content = render_rust(&collect_cases(&translations()));
+        assert!(content.contains("fn i18n_smoke()"));
+        assert!(content.contains(r#"("en", "greeting", "Hello {name}", &[("name", "[name]")]),"#));
+        assert!(content.contains("fn render("));
+    }
+
+    #[tokio::test]
+    async fn test_gen_tests_rejects_unknown_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = GenTestsCmd {
+            config: Some(config_path),
+            out: temp_dir.path().join("smoke.py"),
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Get command implementation
+//!
+//! Prints the value(s) of one key from the local files or, with `--remote`,
+//! from the backend. With a single `--lang` only the bare value is printed,
+//! and `--json` prints a `{language: value}` object for scripts.
+
+use super::SyncCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::scan_messages_dir;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Get 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "get", about = "Print the value of a key")]
+pub struct GetCmd {
+    /// 键名（如 user.title）
+    #[arg(value_name = "KEY")]
+    pub key: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只输出指定语言（可重复）
+    #[arg(short, long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 从后端读取，而不是本地文件
+    #[arg(long)]
+    pub remote: bool,
+
+    /// 以 JSON 输出 `{语言: 值}`
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl GetCmd {
+    /// 执行 get 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 读取本地或后端翻译
+    /// 3. 输出键的值
+    ///
+    /// # Errors
+    ///
+    /// 键在所选语言中都不存在时返回错误。
+    pub async fn run(&self, ctx: &RunContext) -> Result<BTreeMap<String, String>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 读取翻译
+        let translations = if self.remote {
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+            if !client.check_auth().await? {
+                return Err(Failure::Auth.error(
+                    "API authentication failed. Please check your API key."
+                ));
+            }
+            let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+            SyncCmd::fetch_backend_translations(&config, &client, &language_mapper).await?
+        } else {
+            scan_messages_dir(&config.messages())
+                .await
+                .context("Failed to scan messages directory")?
+                .translations
+        };
+
+        let values: BTreeMap<String, String> = translations
+            .into_iter()
+            .filter(|(lang, _)| self.languages.is_empty() || self.languages.contains(lang))
+            .filter_map(|(lang, mut keys)| keys.remove(&self.key).map(|value| (lang, value)))
+            .collect();
+        if values.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Key '{}' not found{}",
+                self.key,
+                if self.remote { " on the backend" } else { " in local files" }
+            ));
+        }
+
+        // 3. 输出
+        if self.json {
+            ctx.reporter.output(serde_json::to_string_pretty(&values)?);
+        } else if self.languages.len() == 1 {
+            values.values().for_each(|value| ctx.reporter.output(value));
+        } else {
+            for (lang, value) in &values {
+                ctx.reporter.output(format!("{}: {}", lang, value));
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_get_local_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, title) in [("en", "Profile"), ("de", "Profil"), ("fr", "")] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(
+                messages_dir.join(lang).join("user.json"),
+                format!(r#"{{"user": {{"title": "{}"}}}}"#, title),
+            )
+            .unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = GetCmd {
+            key: "user.title".to_string(),
+            config: Some(config_path.clone()),
+            json: true,
+            ..Default::default()
+        };
+        let values = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["de"], "Profil");
+
+        let cmd = GetCmd {
+            key: "user.title".to_string(),
+            config: Some(config_path.clone()),
+            languages: vec!["de".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(cmd.run(&RunContext::default()).await.unwrap().len(), 1);
+
+        let cmd = GetCmd {
+            key: "user.missing".to_string(),
+            config: Some(config_path),
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("not found in local files"));
+    }
+}
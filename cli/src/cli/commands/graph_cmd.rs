@@ -0,0 +1,166 @@
+//! Graph command implementation
+//!
+//! Exports the relationships between namespaces, translation files and
+//! source-code references as Graphviz DOT or JSON, so large teams can
+//! visualize and split their translation domains.
+
+use crate::cli::RunContext;
+use crate::core::graph::GraphBuilder;
+use crate::core::scanner::{read_translation_file, resolve_file, scan_messages_dir};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+use tracing::info;
+
+/// 图输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// JSON（nodes + edges）
+    Json,
+}
+
+/// Graph 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "graph", about = "Export the key reference graph")]
+pub struct GraphCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 输出格式
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    pub format: GraphFormat,
+
+    /// 输出文件（默认输出到标准输出）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// 扫描键引用的源码目录（可重复）
+    #[arg(long = "src", value_name = "DIR")]
+    pub sources: Vec<PathBuf>,
+
+    /// 只包含指定语言的翻译文件（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+}
+
+impl GraphCmd {
+    /// 执行 graph 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 读取每个翻译文件的键
+    /// 3. 扫描源码引用
+    /// 4. 输出图
+    pub async fn run(&self, ctx: &RunContext) -> Result<()> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 读取每个翻译文件的键
+        let messages_dir = config.messages();
+        let scan_result = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+        let mut builder = GraphBuilder::new(&messages_dir.separator);
+        let mut files = scan_result.files;
+        files.sort();
+        for file in &files {
+            let lang = file
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !self.languages.is_empty() && !self.languages.contains(&lang) {
+                continue;
+            }
+            let keys = read_translation_file(&messages_dir, &resolve_file(&messages_dir, file))?;
+            builder.add_file(&file.to_string_lossy().replace('\\', "/"), keys.keys());
+        }
+
+        // 3. 扫描源码引用
+        for dir in &self.sources {
+            let scanned = builder
+                .add_source_dir(dir)
+                .with_context(|| format!("Failed to scan source directory: {}", dir.display()))?;
+            info!("Scanned {} source file(s) in {}", scanned, dir.display());
+        }
+
+        // 4. 输出图
+        let graph = builder.build();
+        let output = match self.format {
+            GraphFormat::Dot => graph.to_dot(),
+            GraphFormat::Json => serde_json::to_string_pretty(&graph)? + "\n",
+        };
+        match &self.out {
+            Some(path) => {
+                std::fs::write(path, output)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                info!(
+                    "Graph written to {} ({} nodes, {} edges)",
+                    path.display(),
+                    graph.nodes.len(),
+                    graph.edges.len()
+                );
+            }
+            None => ctx.reporter.write(&output),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_graph_json_with_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(
+            messages_dir.join("en").join("common.json"),
+            r#"{"common": {"ok": "OK"}, "checkout": {"pay": "Pay"}}"#,
+        )
+        .unwrap();
+        let src_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("Pay.tsx"), r#"<Button>{t("checkout.pay")}</Button>"#).unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let out = temp_dir.path().join("graph.json");
+        let cmd = GraphCmd {
+            config: Some(config_path),
+            format: GraphFormat::Json,
+            out: Some(out.clone()),
+            sources: vec![src_dir],
+            ..Default::default()
+        };
+        cmd.run(&RunContext::default()).await.unwrap();
+
+        let graph: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out).unwrap()).unwrap();
+        assert_eq!(graph["nodes"].as_array().unwrap().len(), 4);
+        let edges = graph["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 3);
+        assert!(edges
+            .iter()
+            .any(|e| e["kind"] == "references" && e["to"] == "checkout"));
+    }
+}
@@ -1,34 +1,90 @@
 //! Import command implementation
 //!
 //! Imports translations from the local messages directory to the YFlow backend.
-//! Supports batching, retry logic with exponential backoff, and progress display.
+//! Supports batching, concurrent uploads, and progress display.
 //!
 //! # Features
 //!
-//! - Batch processing with configurable batch size (default: 50)
-//! - Automatic retry with exponential backoff for rate limiting
+//! - Batch processing starting at 50 keys per batch; the size halves after
+//!   429/5xx responses and doubles back after sustained success (5..=200)
+//! - Concurrent batch uploads (`--concurrency`); transient failures are
+//!   retried by the API client, which pauses all batches on rate limiting
 //! - Progress bar display for long-running imports
-//! - Dry-run mode for previewing changes
+//! - Compares with the backend first and pushes only new and changed keys
+//!   (an import plan): `--dry-run` prints the plan, `--plan-file` saves it for
+//!   review and `--apply` pushes a saved plan exactly, rejecting stale plans
+//! - Honors the global `--deadline`, stopping between batches
+//! - Server-side dry-run (`--server-dry-run`) using the backend's validate-only endpoint,
+//!   falling back to a local dry-run when the backend does not advertise it
 //! - Language code mapping support
+//! - Brand overlays (`overlays` in the config) merged over the base messages
+//! - Selecting languages with `--locale` / `--exclude-locale`
+//! - Selecting keys with `--key-prefix` / `--key-glob`
+//! - Quarantining unparseable files, reported at the end (or `--fail-fast`)
+//! - Pushing only keys changed since the last run, using `.yflow-cache` (`--no-cache` to push all)
+//! - Routing keys to multiple backend projects via `routes`, several at a
+//!   time with `--project-concurrency`; a failing project stops the run
+//!   unless `--keep-going` is given, and a project × language matrix of the
+//!   results is printed at the end
+//! - A per-language table of added/updated/skipped/failed keys and the
+//!   elapsed time at the end of every run (`--summary-json` writes it, and
+//!   the project matrix, as JSON)
+//! - Ownership checks against the `owners` config for the invoking team
+//! - Skipping (and reporting) keys locked by another team via `yflow lock`
+//! - Attributing pushed changes to `--author` / `I18N_AUTHOR` / the git user
+//! - Deleting backend keys that no local file defines any more (`--prune`,
+//!   confirmed interactively or with `--yes`)
+//! - Marking translations whose source text changed since they were
+//!   translated as unreviewed on the backend (`--mark-stale`, needs
+//!   `sourceLanguage`)
+//! - Only importing keys changed in the messages directory, `languageDirs`
+//!   and overlays since a git commit or tag (`--since`), for fast per-PR CI imports
+//! - Refusing to import when translations dropped or renamed a placeholder of
+//!   the source language (`--check`, see `yflow check-placeholders`)
 
-use crate::api::client::APIClient;
-use crate::core::config::load_config;
+use super::check_placeholders_cmd::check_translations;
+use crate::cli::RunContext;
+use crate::api::capabilities::Feature;
+use crate::api::client::{APIClient, PushKeysResponse};
+use crate::api::retry::AdaptiveBatchSize;
+use crate::core::compare::Comparator;
+use crate::core::deadline;
+use crate::core::failure::Failure;
+use crate::core::import_plan::{ImportPlan, TargetPlan};
+use crate::core::import_summary::{ImportSummary, ProjectSummary};
 use crate::core::language_mapping::LanguageMapper;
-use crate::core::scanner::scan_messages_dir;
-use crate::core::{ImportResult, Translations};
-use crate::ui::progress::MultiProgressManager;
+use crate::core::locks::{current_holder, partition_locked};
+use crate::core::overlays::{self, scan_overlays};
+use crate::core::ownership::{changed_keys, Ownership, TEAM_ENV_VAR};
+use crate::core::routing::{RouteTarget, Router};
+use crate::core::scanner::{report_quarantine, scan_messages_dir_with};
+use crate::core::since;
+use crate::core::stale;
+use crate::core::status_cache::StatusCache;
+use crate::core::{
+    DeltaCache, I18nConfig, ImportResult, KeyFilter, LanguageCounts, LocaleFilter, OwnershipPolicy, ProgressCallback,
+    ProgressEvent, Translations,
+};
+use crate::ui::progress::ProgressGuard;
+use crate::ui::prompt;
+use crate::ui::reporter::Reporter;
+use crate::ui::spinner::Spinner;
+use crate::ui::summary;
 use anyhow::{Context, Result};
 use clap::Parser;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tokio::time::sleep;
-use tracing::info;
+use tracing::{info, warn};
 
 /// 导入命令参数
 ///
 /// 将本地 messages 目录的翻译导入到后端数据库。
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone, Default)]
 #[command(name = "import")]
 #[command(about = "Import translations from local messages directory to backend", long_about = None)]
 pub struct ImportCmd {
@@ -39,17 +95,107 @@ pub struct ImportCmd {
     /// 模拟运行 - 显示将要导入的内容但不实际修改
     #[arg(long)]
     pub dry_run: bool,
+
+    /// 服务端模拟运行 - 以只校验模式提交批次，报告后端将新增/更新/拒绝的键
+    #[arg(long, conflicts_with = "dry_run")]
+    pub server_dry_run: bool,
+
+    /// 当前执行导入的团队（用于归属检查，也可通过 I18N_TEAM 设置）
+    #[arg(long, value_name = "TEAM")]
+    pub team: Option<String>,
+
+    /// 并发上传的批次数（默认 1，即逐批上传）
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub concurrency: usize,
+
+    /// 只导入指定语言（本地语言代码，可重复）
+    #[arg(long = "locale", value_name = "LOCALE")]
+    pub locales: Vec<String>,
+
+    /// 排除指定语言（本地语言代码，可重复）
+    #[arg(long = "exclude-locale", value_name = "LOCALE")]
+    pub exclude_locales: Vec<String>,
+
+    /// 只处理以该前缀开头的键（可重复）
+    #[arg(long = "key-prefix", value_name = "PREFIX")]
+    pub key_prefixes: Vec<String>,
+
+    /// 只处理匹配该 glob 的键，如 `checkout.*`（可重复）
+    #[arg(long = "key-glob", value_name = "GLOB")]
+    pub key_globs: Vec<String>,
+
+    /// 遇到第一个无法解析的文件时立即中止（默认跳过并在结束时报告）；
+    /// 配置了 routes 时第一个出错的项目也会中止其余项目（默认行为）
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// 某个项目出错时继续导入其余项目，结束时报告所有出错的项目
+    #[arg(long, conflicts_with = "fail_fast")]
+    pub keep_going: bool,
+
+    /// 同时导入的项目数（配置了 routes 时，默认 1，即逐个导入）
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub project_concurrency: usize,
+
+    /// 将各语言及各项目 × 语言的导入结果以 JSON 写入文件
+    #[arg(long, value_name = "PATH")]
+    pub summary_json: Option<PathBuf>,
+
+    /// 忽略 .yflow-cache，推送所有键（默认只推送上次运行后本地修改过的键）
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// 将导入计划保存到文件而不推送（审核后用 --apply 执行）
+    #[arg(long, value_name = "PATH", conflicts_with = "server_dry_run")]
+    pub plan_file: Option<PathBuf>,
+
+    /// 执行之前保存的导入计划（不重新扫描本地文件）
+    #[arg(
+        long,
+        value_name = "PLAN",
+        conflicts_with_all = [
+            "dry_run", "server_dry_run", "plan_file", "locales", "exclude_locales",
+            "key_prefixes", "key_globs", "no_cache",
+        ]
+    )]
+    pub apply: Option<PathBuf>,
+
+    /// 删除后端存在但本地所有文件中都没有的键（需要确认或 --yes）
+    #[arg(
+        long,
+        conflicts_with_all = ["server_dry_run", "plan_file", "apply", "locales", "exclude_locales"]
+    )]
+    pub prune: bool,
+
+    /// 不询问，直接删除 --prune 找到的键
+    #[arg(long, requires = "prune")]
+    pub yes: bool,
+
+    /// 在后端将过期的翻译（源文本变化后未更新）标记为未审核（需要配置 sourceLanguage）
+    #[arg(long, conflicts_with = "apply")]
+    pub mark_stale: bool,
+
+    /// 导入前检查各语言与源语言的占位符是否一致，不一致时不导入
+    #[arg(long, conflicts_with = "apply")]
+    pub check: bool,
+
+    /// 只导入 messages 目录、languageDirs 和覆盖层中自该 git 提交/标签以来新增或修改的键
+    #[arg(long, value_name = "REF", conflicts_with_all = ["apply", "prune"])]
+    pub since: Option<String>,
 }
 
-/// 导入翻译的批次大小
-const BATCH_SIZE: usize = 50;
+/// 导入翻译的初始批次大小
+pub(super) const BATCH_SIZE: usize = 50;
+
+/// 自适应批次大小的下限
+const MIN_BATCH_SIZE: usize = 5;
+
+/// 自适应批次大小的上限
+const MAX_BATCH_SIZE: usize = 200;
 
 /// 批次间延迟（毫秒）- 避免速率限制
 const BATCH_DELAY: Duration = Duration::from_millis(200);
 
-/// 最大重试次数
-const MAX_RETRIES: usize = 3;
-
 impl ImportCmd {
     /// 执行导入命令
     ///
@@ -58,53 +204,155 @@ impl ImportCmd {
     /// 1. 加载配置
     /// 2. 创建 API 客户端
     /// 3. 验证认证
-    /// 4. 扫描本地 messages 目录
-    /// 5. 执行导入（或显示预览）
+    /// 4. 扫描本地 messages 目录（`--apply` 时改为执行保存的计划）
+    /// 5. 与后端比较生成计划，执行导入（或显示预览、保存计划）
+    /// 6. `--prune` 时删除后端存在但本地没有的键
+    /// 7. `--mark-stale` 时在后端将过期的翻译标记为未审核
     ///
     /// # 参数
     ///
-    /// * `global_config` - 可选的父级配置文件路径
-    pub async fn run(&self, global_config: Option<PathBuf>) -> Result<ImportResult> {
+    /// * `ctx` - 本次运行的上下文（全局 `--config` 和命令行覆盖）
+    pub async fn run(&self, ctx: &RunContext) -> Result<ImportResult> {
         info!("Starting import to backend...");
 
         // 合并配置选项
-        let config_path = self.config.clone().or(global_config);
+        let config_path = ctx.config_path(&self.config);
 
         // 1. 加载配置
         info!("Loading configuration...");
-        let config = load_config(config_path)?;
+        let config = ctx.load_config(config_path).await?;
+        if self.mark_stale && config.source_language.is_none() {
+            return Err(anyhow::anyhow!("--mark-stale requires sourceLanguage in the config"));
+        }
         info!("  - Messages directory: {}", config.messages_dir.display());
         info!("  - Project ID: {}", config.project_id);
         info!("  - API URL: {}", config.api_url);
 
         // 1.1 初始化语言映射器
-        let language_mapper = LanguageMapper::new(Some(config.language_mapping));
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
         if language_mapper.needs_mapping() {
             info!("  - {}", language_mapper.get_description());
         }
 
         // 2. 创建 API 客户端
-        let client = APIClient::new(
-            config.api_url.clone(),
-            config.api_key.clone(),
-            config.project_id,
-        )
+        let client = APIClient::from_config(&config)
         .context("Failed to create API client")?;
+        if let Some(author) = client.author() {
+            info!("  - Author: {}", author);
+        }
 
         // 3. 验证认证
         info!("Verifying API authentication...");
-        if !client.check_auth()? {
-            return Err(anyhow::anyhow!(
+        let mut spinner = Spinner::new("Checking authentication...");
+        spinner.start();
+        let authenticated = client.check_auth().await?;
+        spinner.stop();
+        if !authenticated {
+            return Err(Failure::Auth.error(
                 "API authentication failed. Please check your API key."
             ));
         }
         info!("  - Authentication successful");
 
+        // 3.1 执行之前保存的计划
+        if let Some(plan_path) = &self.apply {
+            return self.apply_plan(&config, &client, plan_path).await;
+        }
+
         // 4. 扫描 messages 目录
         info!("Scanning messages directory: {}...", config.messages_dir.display());
-        let scan_result = scan_messages_dir(&config.messages_dir)
+        let messages_dir = config.messages();
+        let mut scan_result = scan_messages_dir_with(&messages_dir, self.fail_fast)
             .await
             .context("Failed to scan messages directory")?;
+        // 记录源文本变化，找出过期的翻译（在合并覆盖层和过滤之前）
+        let stale = match &config.source_language {
+            Some(source) => stale::track(&config.messages_dir, source, &scan_result.translations)?,
+            None => Vec::new(),
+        };
+        let stale = stale::stale_translations(&stale, &scan_result.translations);
+        let overlays = scan_overlays(&config.overlay_dirs(), self.fail_fast).await?;
+        // 自 --since 以来修改过的键（在合并覆盖层之前，与 git 中的旧版本比较）
+        let changed_since = match &self.since {
+            Some(since) => Some(since::changed_keys(&messages_dir, &scan_result, &overlays, since)?),
+            None => None,
+        };
+        // 品牌覆盖层合并在基础翻译之上
+        overlays::apply(&mut scan_result, &overlays);
+
+        // 4.1 按 --locale / --exclude-locale 过滤语言
+        let locale_filter = LocaleFilter::new(&self.locales, &self.exclude_locales);
+        if self.check {
+            // 在过滤之前检查，源语言不导入时也能比较
+            let issues = check_translations(
+                &*ctx.reporter,
+                &messages_dir,
+                &scan_result.translations,
+                config.source_language.as_deref(),
+                |lang| locale_filter.matches(lang),
+            )?;
+            if !issues.is_empty() {
+                return Err(Failure::Validation.error(format!(
+                    "{} translation(s) with placeholder problems; fix them or import without --check",
+                    issues.len()
+                )));
+            }
+        }
+        if locale_filter.is_active() {
+            info!("  - {}", locale_filter.description());
+            for lang in locale_filter.included() {
+                if !scan_result.translations.contains_key(lang) {
+                    info!("  - Warning: locale '{}' not found locally", lang);
+                }
+            }
+            scan_result.translations = locale_filter.apply(scan_result.translations);
+            scan_result.key_count = scan_result.translations.values().map(|t| t.len()).sum();
+        }
+
+        // 4.2 按 --key-prefix / --key-glob 过滤键（扁平化之后、调用 API 之前）
+        let key_filter = KeyFilter::new(&self.key_prefixes, &self.key_globs, config.separator())?;
+        if key_filter.is_active() {
+            info!("  - {}", key_filter.description());
+            scan_result.translations = key_filter.apply(scan_result.translations);
+            scan_result.key_count = scan_result.translations.values().map(|t| t.len()).sum();
+        }
+
+        // 4.3 只保留自 --since 以来修改过的键
+        if let (Some(since), Some(changed)) = (&self.since, &changed_since) {
+            scan_result.translations = since::retain_changed(scan_result.translations, changed);
+            scan_result.key_count = scan_result.translations.values().map(|t| t.len()).sum();
+            info!("  - Keys changed since {}: {}", since, scan_result.key_count);
+        }
+        let stale = key_filter.apply(locale_filter.apply(stale));
+        let stale_count: usize = stale.values().map(|t| t.len()).sum();
+        if stale_count > 0 {
+            info!("  - Stale translations (source text changed since translated): {}", stale_count);
+        }
+
+        // 4.4 记录本地所有的键（在跳过未修改的键之前，供 --prune 比较）
+        let local_keys: HashSet<String> = if self.prune {
+            scan_result.translations.values().flat_map(|keys| keys.keys().cloned()).collect()
+        } else {
+            HashSet::new()
+        };
+
+        // 4.5 跳过自上次导入/同步后未修改的键
+        let mut cache = if self.no_cache {
+            DeltaCache::default()
+        } else {
+            DeltaCache::load(&config.messages_dir).unwrap_or_else(|e| {
+                info!("  - Ignoring unreadable delta cache: {}", e);
+                DeltaCache::default()
+            })
+        };
+        if !self.no_cache {
+            let (changed, unchanged) = cache.changed(&Router::from_config(&config), scan_result.translations);
+            scan_result.translations = changed;
+            scan_result.key_count = scan_result.translations.values().map(|t| t.len()).sum();
+            if unchanged > 0 {
+                info!("  - Unchanged since last run: {} key(s) (use --no-cache to push them)", unchanged);
+            }
+        }
 
         let languages: Vec<&str> = scan_result.translations.keys().map(|s| s.as_str()).collect();
         info!(
@@ -114,27 +362,638 @@ impl ImportCmd {
             languages.join(", ")
         );
 
+        if !scan_result.quarantine.is_empty() {
+            info!("  - Quarantined files: {}", scan_result.quarantine.len());
+        }
+
         if scan_result.key_count == 0 {
-            info!("No translations found, skipping import.");
-            return Ok(ImportResult::default());
+            info!("No new or changed translations found, skipping import.");
+            let mut result = ImportResult::default();
+            if self.prune {
+                result.deleted = self
+                    .prune_keys(&config, &client, &local_keys, &key_filter, scan_result.quarantine.len())
+                    .await?;
+            }
+            if self.mark_stale {
+                self.mark_stale_keys(&config, &client, &language_mapper, stale).await?;
+            }
+            report_quarantine(&scan_result.quarantine)?;
+            return Ok(result);
+        }
+
+        // 4.6 检查键的归属
+        self.check_ownership(&config, &client, &language_mapper, &scan_result.translations)
+            .await?;
+
+        // 4.7 跳过被其他团队锁定的键
+        let (translations, locked) = self.skip_locked_keys(&client, scan_result.translations, config.separator()).await?;
+        scan_result.translations = translations;
+
+        // 5. 按路由拆分、应用语言映射并执行导入
+        let pushed = scan_result.translations.clone();
+        let mut result = self
+            .import_translations(&*ctx.reporter, &config, &client, &language_mapper, scan_result.translations)
+            .await?;
+        result.locked = locked;
+
+        // 5.1 全部成功时记录已推送的键（有失败时下次重新推送）
+        if self.writes_backend() && result.failed == 0 {
+            cache.record(&Router::from_config(&config), &pushed, false);
+            cache.save(&config.messages_dir)?;
+        }
+
+        // 5.2 删除后端多余的键
+        if self.prune {
+            result.deleted = self
+                .prune_keys(&config, &client, &local_keys, &key_filter, scan_result.quarantine.len())
+                .await?;
+        }
+
+        // 5.3 将过期的翻译标记为未审核
+        if self.mark_stale {
+            self.mark_stale_keys(&config, &client, &language_mapper, stale).await?;
         }
 
-        // 5. 应用语言映射
-        let mapped_translations = language_mapper.apply_to_translations(scan_result.translations);
+        // 6. 报告被隔离的文件（存在时以非零状态退出）
+        report_quarantine(&scan_result.quarantine)?;
+
+        Ok(result)
+    }
+
+    /// 将翻译导入后端（或预览）
+    ///
+    /// 按路由规则拆分到各后端项目，应用语言映射后执行导入、本地预览或服务端校验。
+    /// 项目按 `--project-concurrency` 并发导入；某个项目出错时中止其余项目
+    /// （`--keep-going` 时继续，结束时报告）。结束时输出各语言的汇总表，
+    /// 配置了路由时还输出项目 × 语言的结果矩阵，`--summary-json` 时写入 JSON。
+    /// 也被 `import-delivery` 和 `import-file` 复用。
+    ///
+    /// # 参数
+    ///
+    /// * `reporter` - 输出层
+    /// * `config` - 配置
+    /// * `client` - 默认项目的 API 客户端（已验证认证）
+    /// * `language_mapper` - 语言映射器
+    /// * `translations` - 要导入的翻译（本地语言代码）
+    pub(super) async fn import_translations(
+        &self,
+        reporter: &dyn Reporter,
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        translations: Translations,
+    ) -> Result<ImportResult> {
+        // 修改后端前删除状态缓存
+        if self.writes_backend() {
+            StatusCache::invalidate(&config.messages_dir)?;
+        }
 
-        // 6. 执行导入或预览
-        if self.dry_run {
-            self.dry_run_import(&mapped_translations)?;
-            Ok(ImportResult {
-                added: scan_result.key_count,
-                ..Default::default()
+        // 1. 按路由规则拆分到各后端项目，应用语言映射
+        let router = Router::from_config(config);
+        let partitions = router.partition(translations);
+        let multi_project = router.has_routes();
+        if multi_project {
+            info!("  - Routing keys to {} project(s)", partitions.len());
+        }
+        let jobs: Vec<(RouteTarget, bool, Translations)> = partitions
+            .into_iter()
+            .map(|(target, translations)| {
+                let routed = &target != router.default_target();
+                (target, routed, language_mapper.apply_to_translations(translations))
             })
+            .collect();
+        let targets: Vec<RouteTarget> = jobs.iter().map(|(target, _, _)| target.clone()).collect();
+
+        // 2. 导入各项目（默认逐个；出错时除非 --keep-going 否则不再开始其余项目）
+        let started = Instant::now();
+        let mut outcomes: Vec<Option<(Result<TargetOutcome>, Duration)>> = targets.iter().map(|_| None).collect();
+        let concurrency = self.project_concurrency.max(1);
+        if concurrency == 1 || jobs.len() <= 1 {
+            for (index, (target, routed, translations)) in jobs.into_iter().enumerate() {
+                let project_started = Instant::now();
+                let outcome = self
+                    .import_target(&target, client.clone(), routed, translations, config.comparator, multi_project)
+                    .await;
+                let failed = outcome.is_err();
+                outcomes[index] = Some((outcome, project_started.elapsed()));
+                if failed && !self.keep_going {
+                    break;
+                }
+            }
+        } else {
+            let semaphore = Arc::new(Semaphore::new(concurrency));
+            let mut tasks = JoinSet::new();
+            for (index, (target, routed, translations)) in jobs.into_iter().enumerate() {
+                let cmd = self.clone();
+                let client = client.clone();
+                let semaphore = semaphore.clone();
+                let comparator = config.comparator;
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    let project_started = Instant::now();
+                    let outcome = cmd
+                        .import_target(&target, client, routed, translations, comparator, multi_project)
+                        .await;
+                    (index, outcome, project_started.elapsed())
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                let (index, outcome, elapsed) = match joined {
+                    Ok(joined) => joined,
+                    // 因其他项目出错而取消
+                    Err(e) if e.is_cancelled() => continue,
+                    Err(e) => return Err(e.into()),
+                };
+                if outcome.is_err() && !self.keep_going {
+                    tasks.abort_all();
+                }
+                outcomes[index] = Some((outcome, elapsed));
+            }
+        }
+
+        // 3. 汇总各项目的结果
+        let mut result = ImportResult::default();
+        let mut plans = Vec::new();
+        let mut errors = Vec::new();
+        let mut projects = Vec::new();
+        for (target, outcome) in targets.iter().zip(outcomes) {
+            let Some((outcome, elapsed)) = outcome else {
+                projects.push(ProjectSummary::skipped(target.project_id, &target.api_url));
+                continue;
+            };
+            let outcome = outcome.map(|(target_result, plan)| {
+                plans.extend(plan);
+                target_result
+            });
+            projects.push(ProjectSummary::new(target.project_id, &target.api_url, &outcome, elapsed));
+            match outcome {
+                Ok(target_result) => result.absorb(target_result),
+                Err(e) => errors.push(e),
+            }
+        }
+        let import_summary = ImportSummary::new(projects, started.elapsed());
+        if multi_project {
+            reporter.output("");
+            reporter.write(&import_summary.render());
+        }
+        if errors.is_empty() || multi_project {
+            summary::print(reporter, &import_summary.summary);
+        }
+        if let Some(path) = &self.summary_json {
+            import_summary.save(path)?;
+            info!("Import summary written to {}", path.display());
+        }
+
+        // 4. 出错的项目
+        if !errors.is_empty() {
+            if !self.keep_going || errors.len() == 1 && targets.len() == 1 {
+                return Err(errors.remove(0));
+            }
+            let failed: Vec<String> =
+                import_summary.failed().iter().map(|project| project.project_id.to_string()).collect();
+            return Err(anyhow::anyhow!(
+                "{} of {} project(s) failed: {}",
+                failed.len(),
+                targets.len(),
+                failed.join(", ")
+            ));
+        }
+
+        // 5. 保存计划供审核后执行
+        if let Some(path) = &self.plan_file {
+            ImportPlan::new(plans).save(path)?;
+            info!(
+                "Import plan written to {} (review it, then run: import --apply {})",
+                path.display(),
+                path.display()
+            );
+        }
+
+        if self.server_dry_run && result.failed > 0 {
+            return Err(Failure::Validation.error(format!(
+                "Server-side validation rejected {} key(s)",
+                result.failed
+            )));
+        }
+
+        Ok(result)
+    }
+
+    /// 本次运行是否修改后端（不是模拟运行、服务端校验或保存计划）
+    fn writes_backend(&self) -> bool {
+        !self.dry_run && !self.server_dry_run && self.plan_file.is_none()
+    }
+
+    /// 将一个项目的翻译导入后端（或预览）
+    ///
+    /// # 参数
+    ///
+    /// * `target` - 路由目标
+    /// * `client` - 默认项目的 API 客户端
+    /// * `routed` - 是否为路由到的其他项目（客户端由默认客户端按路由创建）
+    /// * `translations` - 要导入的翻译（后端语言代码）
+    /// * `comparator` - 判断值是否未变化的比较方式
+    /// * `multi_project` - 是否配置了路由（输出项目标题）
+    ///
+    /// # 返回
+    ///
+    /// 导入结果和导入计划（服务端校验时没有计划）
+    async fn import_target(
+        &self,
+        target: &RouteTarget,
+        client: APIClient,
+        routed: bool,
+        translations: Translations,
+        comparator: Comparator,
+        multi_project: bool,
+    ) -> Result<TargetOutcome> {
+        if multi_project {
+            info!("Project {} ({}):", target.project_id, target.api_url);
+        }
+        let client = if routed {
+            create_routed_client(&client, target).await?
+        } else {
+            client
+        };
+
+        // 1. 服务端校验
+        if self.server_dry_run {
+            return Ok((self.server_dry_run_import(&client, translations).await?, None));
+        }
+
+        // 2. 与后端比较生成计划，执行或预览
+        //    （模拟运行时后端不可达则视所有键为新建，import-delivery 可以离线预览）
+        let remote = match client.get_translations().await {
+            Ok(remote) => remote,
+            Err(e) if self.dry_run => {
+                warn!("Could not fetch backend translations ({:#}); showing every key as new", e);
+                Translations::new()
+            }
+            Err(e) => {
+                return Err(e.context(format!(
+                    "Failed to fetch translations of project {} to plan the import",
+                    target.project_id
+                )))
+            }
+        };
+        let plan = TargetPlan::compute(target.project_id, &target.api_url, &translations, &remote, comparator);
+        let planned = plan_counts(&plan, &translations);
+        let mut result = ImportResult {
+            unchanged: plan.unchanged,
+            ..Default::default()
+        };
+        if self.dry_run || self.plan_file.is_some() {
+            show_plan(&plan);
+            result.added += plan.create_count();
+            result.updated += plan.update_count();
+            result.languages = planned;
         } else {
-            self.execute_import(&client, mapped_translations).await
+            if plan.unchanged > 0 {
+                info!("  - Unchanged on backend: {} key(s)", plan.unchanged);
+            }
+            if !plan.is_empty() {
+                result.absorb(self.execute_import(&client, plan.to_push()).await?);
+            }
+            for (locale, counts) in planned.into_iter().filter(|(_, counts)| counts.unchanged > 0) {
+                result.languages.entry(locale).or_default().unchanged += counts.unchanged;
+            }
+        }
+        Ok((result, Some(plan)))
+    }
+
+    /// 删除后端存在但本地所有语言中都没有的键（--prune）
+    ///
+    /// 按路由目标分别比较，只考虑按路由规则属于该项目、且被 `--key-prefix` /
+    /// `--key-glob` 选中的键。模拟运行时只列出将被删除的键；否则删除前需要
+    /// 确认（`--yes` 跳过，非交互运行时中止）。有文件被隔离时拒绝执行，
+    /// 以免删除只是暂时无法解析的键。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 默认项目的 API 客户端（已验证认证）
+    /// * `local_keys` - 本地所有语言中的键
+    /// * `key_filter` - 键过滤器
+    /// * `quarantined` - 被隔离的文件数
+    ///
+    /// # 返回
+    ///
+    /// 删除的键数（模拟运行时为将被删除的键数）
+    async fn prune_keys(
+        &self,
+        config: &I18nConfig,
+        client: &APIClient,
+        local_keys: &HashSet<String>,
+        key_filter: &KeyFilter,
+        quarantined: usize,
+    ) -> Result<usize> {
+        if quarantined > 0 {
+            return Err(anyhow::anyhow!(
+                "Refusing to prune: {} file(s) could not be parsed, their keys would be deleted",
+                quarantined
+            ));
+        }
+
+        info!("Looking for backend keys missing locally...");
+        let router = Router::from_config(config);
+        let mut deleted = 0;
+        for target in router.targets() {
+            let routed_client;
+            let target_client = if target == router.default_target() {
+                client
+            } else {
+                routed_client = create_routed_client(client, target).await?;
+                &routed_client
+            };
+
+            // 1. 找出后端有、本地没有的键
+            let remote = target_client.get_translations().await.with_context(|| {
+                format!("Failed to fetch translations of project {} to prune keys", target.project_id)
+            })?;
+            let mut stale: Vec<String> = router
+                .filter_for(target, remote)
+                .into_values()
+                .flat_map(|keys| keys.into_keys())
+                .filter(|key| !local_keys.contains(key) && key_filter.matches(key))
+                .collect();
+            stale.sort();
+            stale.dedup();
+            if stale.is_empty() {
+                continue;
+            }
+
+            info!(
+                "  - Project {}: {} key(s) not found in any local file:",
+                target.project_id,
+                stale.len()
+            );
+            for key in stale.iter().take(20) {
+                info!("    - {}", key);
+            }
+            if stale.len() > 20 {
+                info!("    ... and {} more", stale.len() - 20);
+            }
+            if self.dry_run {
+                deleted += stale.len();
+                continue;
+            }
+
+            // 2. 确认后删除
+            let question = format!("Delete {} key(s) from project {}?", stale.len(), target.project_id);
+            if !self.yes && !prompt::confirm(&question)? {
+                return Err(anyhow::anyhow!(
+                    "Prune aborted: {} key(s) were not deleted. Re-run with --yes to delete them without asking.",
+                    stale.len()
+                ));
+            }
+            StatusCache::invalidate(&config.messages_dir)?;
+            let count = target_client.delete_keys(&stale).await?;
+            info!("  - Deleted {} key(s)", count);
+            deleted += count;
+        }
+
+        if deleted == 0 {
+            info!("  - No backend keys to prune");
+        }
+        Ok(deleted)
+    }
+
+    /// 检查本次导入是否修改了其他团队的键
+    ///
+    /// 仅在配置了 `owners` 且提供了团队（`--team` 或 `I18N_TEAM`）时执行。
+    /// 与后端当前翻译比较，只有新增或值发生变化的键才视为修改。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - API 客户端
+    /// * `language_mapper` - 语言映射器
+    /// * `local` - 本地翻译（本地语言代码）
+    async fn check_ownership(
+        &self,
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        local: &Translations,
+    ) -> Result<()> {
+        let ownership = Ownership::new(&config.owners, config.separator());
+        let team = match self.team.clone().or_else(|| std::env::var(TEAM_ENV_VAR).ok()) {
+            Some(team) if !ownership.is_empty() => team,
+            _ => return Ok(()),
+        };
+
+        info!("Checking key ownership for team '{}'...", team);
+        let remote = language_mapper.reverse_translations(client.get_translations().await?);
+        let violations = ownership.violations(&team, changed_keys(local, &remote));
+
+        if violations.is_empty() {
+            info!("  - All changed keys are owned by '{}' or unowned", team);
+            return Ok(());
+        }
+
+        for violation in violations.iter().take(20) {
+            info!(
+                "  - {}:{} is owned by '{}'",
+                violation.lang, violation.key, violation.owner
+            );
+        }
+        if violations.len() > 20 {
+            info!("  ... and {} more", violations.len() - 20);
+        }
+
+        match config.ownership_policy {
+            OwnershipPolicy::Warn => {
+                info!(
+                    "  - Warning: {} changed key(s) are owned by other teams",
+                    violations.len()
+                );
+                Ok(())
+            }
+            OwnershipPolicy::Block => Err(anyhow::anyhow!(
+                "Import blocked: {} changed key(s) are owned by other teams (ownershipPolicy: block)",
+                violations.len()
+            )),
+        }
+    }
+
+    /// 在后端将过期的翻译标记为未审核（--mark-stale）
+    ///
+    /// 按路由目标分别标记；后端不支持时跳过并警告。模拟运行时只统计。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 默认项目的 API 客户端（已验证认证）
+    /// * `language_mapper` - 语言映射器
+    /// * `stale` - 过期的翻译（本地语言代码）
+    ///
+    /// # 返回
+    ///
+    /// 标记的翻译数（模拟运行时为将被标记的翻译数）
+    async fn mark_stale_keys(
+        &self,
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        stale: Translations,
+    ) -> Result<usize> {
+        let router = Router::from_config(config);
+        let mut marked = 0;
+        for (target, translations) in router.partition(stale) {
+            let keys: BTreeMap<String, Vec<String>> = language_mapper
+                .apply_to_translations(translations)
+                .into_iter()
+                .map(|(lang, values)| {
+                    let mut keys: Vec<String> = values.into_keys().collect();
+                    keys.sort();
+                    (lang, keys)
+                })
+                .collect();
+            let count: usize = keys.values().map(Vec::len).sum();
+            if !self.writes_backend() {
+                info!(
+                    "  - Would mark {} stale translation(s) unreviewed in project {}",
+                    count, target.project_id
+                );
+                marked += count;
+                continue;
+            }
+
+            let routed_client;
+            let target_client = if &target == router.default_target() {
+                client
+            } else {
+                routed_client = create_routed_client(client, &target).await?;
+                &routed_client
+            };
+            if !target_client.supports(Feature::Review).await? {
+                warn!(
+                    "Project {} does not support {}; {} stale translation(s) not marked",
+                    target.project_id,
+                    Feature::Review.description(),
+                    count
+                );
+                continue;
+            }
+            StatusCache::invalidate(&config.messages_dir)?;
+            marked += target_client.mark_unreviewed(&keys).await?;
+        }
+        if marked > 0 && self.writes_backend() {
+            info!("  - Marked {} stale translation(s) unreviewed", marked);
         }
+        Ok(marked)
     }
 
-    /// 显示导入预览（dry-run 模式）
+    /// 移除被其他持有者锁定的键并报告
+    ///
+    /// 仅在后端支持键锁时执行；持有者为 `--team`、`I18N_TEAM` 或系统用户名。
+    ///
+    /// # 返回
+    ///
+    /// 剩余的翻译和被跳过的键数
+    async fn skip_locked_keys(
+        &self,
+        client: &APIClient,
+        translations: Translations,
+        separator: &str,
+    ) -> Result<(Translations, usize)> {
+        if !client.supports(Feature::Locks).await? {
+            return Ok((translations, 0));
+        }
+        let locks = client.list_locks().await?;
+        if locks.is_empty() {
+            return Ok((translations, 0));
+        }
+
+        let holder = current_holder(self.team.as_deref());
+        let (allowed, skipped) = partition_locked(translations, &locks, &holder, separator);
+        if skipped.is_empty() {
+            return Ok((allowed, 0));
+        }
+
+        info!("  - Skipping {} key(s) locked by others:", skipped.len());
+        for locked in skipped.iter().take(20) {
+            info!("    - {}:{} (locked by '{}')", locked.lang, locked.key, locked.holder);
+        }
+        if skipped.len() > 20 {
+            info!("    ... and {} more", skipped.len() - 20);
+        }
+        Ok((allowed, skipped.len()))
+    }
+
+    /// 执行之前保存的导入计划（--apply）
+    ///
+    /// 不重新扫描本地文件，推送计划中的值。推送前重新获取后端翻译：
+    /// 任何计划中的键在计划生成后被修改过时拒绝整个计划，不推送任何键。
+    /// 归属检查和键锁在生成计划时已经执行，这里不再重复。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置（用于找到计划中各项目的 API 密钥）
+    /// * `client` - 默认项目的 API 客户端（已验证认证）
+    /// * `path` - 计划文件路径
+    async fn apply_plan(&self, config: &I18nConfig, client: &APIClient, path: &Path) -> Result<ImportResult> {
+        let plan = ImportPlan::load(path)?;
+        info!("Applying import plan {} (created {})...", path.display(), plan.created_at);
+        StatusCache::invalidate(&config.messages_dir)?;
+
+        // 1. 找到每个项目的客户端，检查计划是否过期
+        let router = Router::from_config(config);
+        let mut targets = Vec::new();
+        let mut stale = Vec::new();
+        for target_plan in plan.targets.iter().filter(|target_plan| !target_plan.is_empty()) {
+            let target = router
+                .targets()
+                .into_iter()
+                .find(|target| target.project_id == target_plan.project_id && target.api_url == target_plan.api_url)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "The plan targets project {} ({}), which is not in the configuration",
+                        target_plan.project_id,
+                        target_plan.api_url
+                    )
+                })?;
+            let target_client = if target == router.default_target() {
+                client.clone()
+            } else {
+                create_routed_client(client, target).await?
+            };
+
+            let remote = target_client.get_translations().await?;
+            stale.extend(target_plan.stale_keys(&remote));
+            targets.push((target_client, target_plan));
+        }
+
+        if !stale.is_empty() {
+            for (locale, key) in stale.iter().take(20) {
+                info!("  - {}:{} changed on the backend since the plan was created", locale, key);
+            }
+            if stale.len() > 20 {
+                info!("  ... and {} more", stale.len() - 20);
+            }
+            return Err(anyhow::anyhow!(
+                "Import plan is stale: {} key(s) changed on the backend since it was created; create a new plan",
+                stale.len()
+            ));
+        }
+
+        // 2. 推送计划中的值
+        let mut result = ImportResult::default();
+        for (target_client, target_plan) in targets {
+            if router.has_routes() {
+                info!("Project {} ({}):", target_plan.project_id, target_plan.api_url);
+            }
+            result.absorb(self.execute_import(&target_client, target_plan.to_push()).await?);
+        }
+        result.unchanged = plan.targets.iter().map(|target_plan| target_plan.unchanged).sum();
+        if result.added + result.updated + result.failed == 0 {
+            info!("The plan has nothing to import.");
+        }
+
+        Ok(result)
+    }
+
+    /// 显示导入预览（后端不支持服务端校验时的本地预览）
     ///
     /// 显示将要导入的翻译，但不实际调用 API。
     ///
@@ -175,6 +1034,77 @@ impl ImportCmd {
         Ok(())
     }
 
+    /// 服务端模拟运行（--server-dry-run）
+    ///
+    /// 按与正式导入相同的批次提交到后端的校验接口，汇总后端将会新增、
+    /// 更新和拒绝的键，不产生任何写入。后端不支持校验接口时退化为本地预览。
+    ///
+    /// # 参数
+    ///
+    /// * `client` - 用于发送请求的 API 客户端
+    /// * `translations` - 要校验的翻译
+    async fn server_dry_run_import(
+        &self,
+        client: &APIClient,
+        translations: Translations,
+    ) -> Result<ImportResult> {
+        let mut result = ImportResult::default();
+
+        if !client.supports(Feature::Validate).await? {
+            info!(
+                "  - Warning: Backend does not support {}; falling back to a local dry run",
+                Feature::Validate.description()
+            );
+            self.dry_run_import(&translations)?;
+            result.added = translations.values().map(|t| t.len()).sum();
+            for (lang, keys) in &translations {
+                result.languages.entry(lang.clone()).or_default().added += keys.len();
+            }
+            return Ok(result);
+        }
+
+        info!("=== SERVER DRY RUN ===");
+
+        for (lang_code, lang_translations) in translations {
+            let entries: Vec<(String, String)> = lang_translations.into_iter().collect();
+            for (batch_idx, chunk) in entries.chunks(BATCH_SIZE).enumerate() {
+                let batch_translations: Translations =
+                    HashMap::from([(lang_code.clone(), chunk.iter().cloned().collect())]);
+
+                let response = client.validate_translations(batch_translations).await?;
+                result.added += response.added.len();
+                result.updated += response.updated.len();
+                result.failed += response.rejected.len();
+                let counts = result.languages.entry(lang_code.clone()).or_default();
+                counts.added += response.added.len();
+                counts.updated += response.updated.len();
+                counts.failed += response.rejected.len();
+                for rejected in &response.rejected {
+                    result.errors.push(format!(
+                        "{}[{}]: {} rejected - {}",
+                        lang_code,
+                        batch_idx + 1,
+                        rejected.key,
+                        rejected.reason
+                    ));
+                }
+            }
+        }
+
+        info!("Server would:");
+        info!("  - Add: {}", result.added);
+        info!("  - Update: {}", result.updated);
+        info!("  - Reject: {}", result.failed);
+        for error in result.errors.iter().take(20) {
+            info!("    - {}", error);
+        }
+        if result.errors.len() > 20 {
+            info!("    ... and {} more", result.errors.len() - 20);
+        }
+
+        Ok(result)
+    }
+
     /// 执行实际导入操作
     ///
     /// 分批导入翻译，支持重试逻辑和速率限制处理。
@@ -191,13 +1121,22 @@ impl ImportCmd {
     ) -> Result<ImportResult> {
         info!("Importing translations to backend...");
 
-        // 初始化进度管理器
-        let progress_manager = MultiProgressManager::new();
+        // 初始化进度管理器，导入过程通过事件驱动进度条
+        // （guard 在提前返回或出错时自动清除进度条）
+        let progress_manager = ProgressGuard::new();
         let show_progress = progress_manager.is_enabled();
+        let progress = progress_manager.progress_callback();
 
         let mut result = ImportResult::default();
         let total_languages = translations.len();
         let mut current_lang_index = 0;
+        // 因超过截止时间而未导入的键数
+        let mut not_imported = 0;
+        // 并发上传的批次数上限（限流重试由客户端负责，克隆之间共享暂停）
+        let semaphore = Arc::new(Semaphore::new(self.concurrency.max(1)));
+        // 批次大小：后端限流或出现暂时性失败时减小，持续成功后恢复
+        let mut batch_size =
+            AdaptiveBatchSize::new(BATCH_SIZE, MIN_BATCH_SIZE, MAX_BATCH_SIZE, client.throttle_events());
 
         for (lang_code, lang_translations) in translations {
             current_lang_index += 1;
@@ -205,6 +1144,10 @@ impl ImportCmd {
             if total_keys == 0 {
                 continue;
             }
+            if deadline::expired() {
+                not_imported += total_keys;
+                continue;
+            }
 
             if show_progress {
                 info!("Importing {} ({}/{})...", lang_code, current_lang_index, total_languages);
@@ -212,152 +1155,314 @@ impl ImportCmd {
                 info!("Importing {} ({} keys)...", lang_code, total_keys);
             }
 
-            // 为该语言创建进度条
-            let mut lang_progress = progress_manager.create_bar(&lang_code, total_keys as u64);
+            progress(ProgressEvent::LanguageStarted {
+                lang: lang_code.clone(),
+                index: current_lang_index,
+                total: total_languages,
+                items: total_keys,
+            });
+            let before = result.counts();
 
-            // 将翻译拆分为多个批次
-            let chunks: Vec<HashMap<String, String>> = lang_translations
-                .into_iter()
-                .collect::<Vec<_>>()
-                .chunks(BATCH_SIZE)
-                .map(|chunk| chunk.iter().cloned().collect())
-                .collect();
+            // 按当前批次大小逐批拆分（批次大小随后端限流情况调整）
+            let entries: Vec<(String, String)> = lang_translations.into_iter().collect();
+            let mut offset = 0;
+            let mut batch_num = 0;
+            let mut tasks = JoinSet::new();
+            while offset < entries.len() {
+                // 等待空闲的并发槽位，同时记录已完成的批次
+                let permit = semaphore.clone().acquire_owned().await?;
+                while let Some(outcome) = tasks.try_join_next() {
+                    record_batch(&mut result, &progress, show_progress, &lang_code, outcome?);
+                    adapt_batch_size(&mut batch_size, client);
+                }
 
-            let total_batches = chunks.len();
-            for (batch_idx, chunk) in chunks.iter().enumerate() {
-                let batch_num = batch_idx + 1;
-                let is_last_batch = batch_num == total_batches;
+                // 在批次边界检查截止时间，不中断正在发送的批次
+                if deadline::expired() {
+                    not_imported += entries.len() - offset;
+                    break;
+                }
 
                 // 将批次包装为 Translations 格式以供 API 使用
-                let batch_translations: Translations = [(lang_code.clone(), chunk.clone())]
-                    .iter()
-                    .cloned()
-                    .collect();
-
-                // 带指数退避的重试循环
-                let mut retry_count = 0;
-                let mut success = false;
-
-                while !success && retry_count < MAX_RETRIES {
-                    match client.push_translations(batch_translations.clone()) {
-                        Ok(response) => {
-                            // 记录结果
-                            result.added += response.added.len();
-                            result.updated += response.existed.len();
-                            result.failed += response.failed.len();
-
-                            // 更新进度条
-                            let processed_in_batch = response.added.len() + response.existed.len() + response.failed.len();
-                            lang_progress.inc_by(processed_in_batch as u64);
-
-                            // 记录失败的键
-                            if !response.failed.is_empty() {
-                                let failed_keys = response
-                                    .failed
-                                    .iter()
-                                    .take(10)
-                                    .map(|s| s.as_str())
-                                    .collect::<Vec<_>>()
-                                    .join(", ");
-                                result.errors.push(format!(
-                                    "{}[{}]: failed keys - {}",
-                                    lang_code,
-                                    batch_num,
-                                    failed_keys
-                                ));
-                                if response.failed.len() > 10 {
-                                    result.errors.push(format!(
-                                        "  ... and {} more",
-                                        response.failed.len() - 10
-                                    ));
-                                }
-                            }
+                let size = batch_size.current().min(entries.len() - offset);
+                let chunk: HashMap<String, String> = entries[offset..offset + size].iter().cloned().collect();
+                offset += size;
+                batch_num += 1;
+                let is_last_batch = offset == entries.len();
+                // 按当前批次大小估算的批次总数
+                let total_batches = batch_num + (entries.len() - offset).div_ceil(batch_size.current());
 
-                            if show_progress {
-                                info!(
-                                    "  Batch {}: +{}, ~{}, ✗{}",
-                                    batch_num,
-                                    response.added.len(),
-                                    response.existed.len(),
-                                    response.failed.len()
-                                );
-                            }
+                let bytes = chunk.iter().map(|(key, value)| key.len() + value.len()).sum();
+                let batch_translations: Translations = HashMap::from([(lang_code.clone(), chunk)]);
+                let client = client.clone();
+                let keys = size;
 
-                            success = true;
-                        }
-                        Err(e) => {
-                            // 检查是否为速率限制错误（429）
-                            if is_rate_limit_error(&e) && retry_count < MAX_RETRIES - 1 {
-                                retry_count += 1;
-                                let wait_time = BATCH_DELAY.as_millis() as u64 * (retry_count as u64 * 2);
-                                info!(
-                                    "  Rate limited, waiting {}ms before retry ({}/{})",
-                                    wait_time, retry_count, MAX_RETRIES
-                                );
-                                sleep(Duration::from_millis(wait_time)).await;
-                            } else {
-                                // 记录错误并继续下一个批次
-                                result.failed += chunk.len();
-                                result.errors.push(format!("{}[{}]: {}", lang_code, batch_num, e));
-                                info!("  Batch {}: FAILED - {}", batch_num, e);
-                                lang_progress.inc_by(chunk.len() as u64);
-                                success = true; // 即使失败也继续下一个批次
-                            }
-                        }
+                tasks.spawn(async move {
+                    let response = client.push_translations(batch_translations).await;
+
+                    // 批次间延迟（除了最后一个），延迟结束后才释放槽位
+                    if !is_last_batch {
+                        sleep(BATCH_DELAY).await;
                     }
-                }
+                    drop(permit);
 
-                // 批次间延迟（除了最后一个）
-                if !is_last_batch {
-                    sleep(BATCH_DELAY).await;
-                }
+                    BatchOutcome {
+                        batch_num,
+                        total_batches,
+                        keys,
+                        bytes,
+                        response,
+                    }
+                });
             }
 
-            // 完成该语言的进度条
-            lang_progress.finish();
+            // 等待该语言剩余的批次完成
+            while let Some(outcome) = tasks.join_next().await {
+                record_batch(&mut result, &progress, show_progress, &lang_code, outcome?);
+                adapt_batch_size(&mut batch_size, client);
+            }
+
+            // 记录该语言的结果
+            let after = result.counts();
+            result.languages.insert(
+                lang_code.clone(),
+                LanguageCounts {
+                    added: after.added - before.added,
+                    updated: after.updated - before.updated,
+                    unchanged: 0,
+                    failed: after.failed - before.failed,
+                },
+            );
+
+            // 完成该语言的进度
+            progress(ProgressEvent::LanguageFinished {
+                lang: lang_code.clone(),
+            });
         }
 
         // 停止所有进度条
-        progress_manager.stop();
+        progress_manager.finish();
 
-        info!("Import complete:");
-        info!("  - Added: {}", result.added);
-        info!("  - Updated: {}", result.updated);
-        info!("  - Failed: {}", result.failed);
+        info!("Import complete");
 
         if !result.errors.is_empty() {
             info!("  - Errors: {} detail(s)", result.errors.len());
         }
 
+        if not_imported > 0 {
+            let budget = deadline::current().map(|d| d.budget()).unwrap_or_default();
+            info!("  - Not imported (deadline): {}", not_imported);
+            return Err(anyhow::anyhow!(
+                "Deadline of {} exceeded: {} key(s) were not imported. Re-run import to send the rest.",
+                deadline::format_duration(budget),
+                not_imported
+            ));
+        }
+
         Ok(result)
     }
 }
 
-/// 检查错误是否为速率限制错误（HTTP 429）
+/// 显示导入计划（dry-run、--plan-file 模式）
+///
+/// 每种语言显示新建、更新和跳过的键数，以及前 5 个新建和更新的键。
+fn show_plan(plan: &TargetPlan) {
+    info!("=== IMPORT PLAN ===");
+    let mut locales: Vec<&String> = plan.create.keys().chain(plan.update.keys()).collect();
+    locales.sort();
+    locales.dedup();
+
+    for locale in locales {
+        let create = plan.create.get(locale);
+        let update = plan.update.get(locale);
+        info!(
+            "  {}: {} to create, {} to update",
+            locale,
+            create.map_or(0, |keys| keys.len()),
+            update.map_or(0, |keys| keys.len())
+        );
+        for (key, value) in create.into_iter().flatten().take(5) {
+            info!("    + {}: \"{}\"", key, preview(value));
+        }
+        for (key, change) in update.into_iter().flatten().take(5) {
+            info!("    ~ {}: \"{}\" -> \"{}\"", key, preview(&change.from), preview(&change.to));
+        }
+    }
+
+    info!(
+        "Would create {} key(s), update {} key(s); {} unchanged",
+        plan.create_count(),
+        plan.update_count(),
+        plan.unchanged
+    );
+}
+
+/// 计划中各语言将新增、更新和跳过的键数
+fn plan_counts(plan: &TargetPlan, translations: &Translations) -> BTreeMap<String, LanguageCounts> {
+    translations
+        .iter()
+        .map(|(locale, keys)| {
+            let added = plan.create.get(locale).map_or(0, |keys| keys.len());
+            let updated = plan.update.get(locale).map_or(0, |keys| keys.len());
+            let counts = LanguageCounts {
+                added,
+                updated,
+                unchanged: keys.len() - added - updated,
+                failed: 0,
+            };
+            (locale.clone(), counts)
+        })
+        .collect()
+}
+
+/// 截断过长的值用于显示（最多 50 个字符）
+fn preview(value: &str) -> String {
+    match value.char_indices().nth(50) {
+        Some((index, _)) => format!("{}...", &value[..index]),
+        None => value.to_string(),
+    }
+}
+
+/// 为路由目标创建 API 客户端并验证认证
 ///
 /// # 参数
 ///
-/// * `error` - 要检查的错误
+/// * `default_client` - 默认项目的客户端（新客户端沿用其超时、TLS 和签名设置）
+/// * `target` - 路由目标
+async fn create_routed_client(default_client: &APIClient, target: &RouteTarget) -> Result<APIClient> {
+    let client = default_client
+        .for_project(target.api_url.clone(), target.api_key.clone(), target.project_id)
+        .with_context(|| format!("Failed to create API client for project {}", target.project_id))?;
+
+    if !client.check_auth().await? {
+        return Err(Failure::Auth.error(format!(
+            "API authentication failed for project {}. Please check the route's API key.",
+            target.project_id
+        )));
+    }
+
+    Ok(client)
+}
+
+/// 单个项目的导入结果和导入计划
+type TargetOutcome = (ImportResult, Option<TargetPlan>);
+
+/// 根据客户端的限流计数调整批次大小
+fn adapt_batch_size(batch_size: &mut AdaptiveBatchSize, client: &APIClient) {
+    if let Some(size) = batch_size.observe(client.throttle_events()) {
+        info!("  Batch size adjusted to {} keys", size);
+    }
+}
+
+/// 单个批次的上传结果
+struct BatchOutcome {
+    /// 批次序号（从 1 开始）
+    batch_num: usize,
+    /// 发送时估算的批次总数
+    total_batches: usize,
+    /// 批次中的键数
+    keys: usize,
+    /// 批次中键和值的字节数
+    bytes: usize,
+    /// 后端响应（重试耗尽后的最终结果）
+    response: Result<PushKeysResponse>,
+}
+
+/// 记录一个批次的结果并更新进度
 ///
-/// # 返回
+/// # 参数
 ///
-/// 如果错误表示速率限制则返回 true
-fn is_rate_limit_error(error: &anyhow::Error) -> bool {
-    let error_msg = error.to_string().to_lowercase();
-    error_msg.contains("429")
-        || error_msg.contains("rate limit")
-        || error_msg.contains("too many requests")
+/// * `result` - 累计的导入结果
+/// * `progress` - 进度回调
+/// * `show_progress` - 是否显示进度条
+/// * `lang_code` - 语言代码
+/// * `outcome` - 批次结果
+fn record_batch(
+    result: &mut ImportResult,
+    progress: &ProgressCallback,
+    show_progress: bool,
+    lang_code: &str,
+    outcome: BatchOutcome,
+) {
+    let batch_num = outcome.batch_num;
+    let response = match outcome.response {
+        Ok(response) => response,
+        Err(e) => {
+            // 记录错误并继续下一个批次
+            result.failed += outcome.keys;
+            result.errors.push(format!("{}[{}]: {}", lang_code, batch_num, e));
+            info!("  Batch {}: FAILED - {}", batch_num, e);
+            progress(ProgressEvent::KeysProcessed {
+                lang: lang_code.to_string(),
+                n: outcome.keys,
+            });
+            return;
+        }
+    };
+
+    progress(ProgressEvent::BatchSent {
+        lang: lang_code.to_string(),
+        batch: batch_num,
+        total_batches: outcome.total_batches,
+        keys: outcome.keys,
+        bytes: outcome.bytes,
+    });
+
+    // 记录结果
+    result.added += response.added.len();
+    result.updated += response.existed.len();
+    result.failed += response.failed.len();
+
+    // 更新进度
+    progress(ProgressEvent::KeysProcessed {
+        lang: lang_code.to_string(),
+        n: response.total(),
+    });
+
+    // 记录失败的键
+    if !response.failed.is_empty() {
+        let failed_keys = response
+            .failed
+            .iter()
+            .take(10)
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.errors.push(format!(
+            "{}[{}]: failed keys - {}",
+            lang_code,
+            batch_num,
+            failed_keys
+        ));
+        if response.failed.len() > 10 {
+            result.errors.push(format!(
+                "  ... and {} more",
+                response.failed.len() - 10
+            ));
+        }
+    }
+
+    if show_progress {
+        info!(
+            "  Batch {}: +{}, ~{}, ✗{}",
+            batch_num,
+            response.added.len(),
+            response.existed.len(),
+            response.failed.len()
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::reporter::HumanReporter;
 
     #[test]
     fn test_import_cmd_default() {
         let cmd = ImportCmd {
             config: None,
             dry_run: false,
+            ..Default::default()
         };
         assert!(!cmd.dry_run);
     }
@@ -367,37 +1472,689 @@ mod tests {
         let cmd = ImportCmd {
             config: None,
             dry_run: true,
+            ..Default::default()
         };
         assert!(cmd.dry_run);
     }
 
     #[test]
-    fn test_is_rate_limit_error_429() {
-        let error = anyhow::anyhow!("HTTP 429: Too Many Requests");
-        assert!(is_rate_limit_error(&error));
+    fn test_server_dry_run_conflicts_with_dry_run() {
+        let result = ImportCmd::try_parse_from(["import", "--dry-run", "--server-dry-run"]);
+        assert!(result.is_err());
+
+        let cmd = ImportCmd::try_parse_from(["import", "--server-dry-run"]).unwrap();
+        assert!(cmd.server_dry_run);
     }
 
     #[test]
-    fn test_is_rate_limit_error_case_insensitive() {
-        let error1 = anyhow::anyhow!("rate limit exceeded");
-        let error2 = anyhow::anyhow!("RATE LIMIT");
-        let error3 = anyhow::anyhow!("Too Many Requests");
+    fn test_apply_conflicts_with_selection() {
+        assert!(ImportCmd::try_parse_from(["import", "--apply", "plan.json", "--locale", "de"]).is_err());
+        assert!(ImportCmd::try_parse_from(["import", "--apply", "plan.json", "--plan-file", "p.json"]).is_err());
+        let cmd = ImportCmd::try_parse_from(["import", "--plan-file", "plan.json"]).unwrap();
+        assert_eq!(cmd.plan_file, Some(PathBuf::from("plan.json")));
+    }
+
+    #[tokio::test]
+    async fn test_plan_file_and_apply() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::Mutex;
+
+        // 模拟后端：GET 返回 remote 中的翻译（键中心化格式），POST 记录推送的请求体
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let remote = Arc::new(Mutex::new(r#"{"title": {"en": "Title"}, "same": {"en": "Same"}}"#.to_string()));
+        let pushed = Arc::new(Mutex::new(Vec::<String>::new()));
+        let (remote_state, pushed_bodies) = (remote.clone(), pushed.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let (mut request_line, mut line, mut content_length) = (String::new(), String::new(), 0);
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let response = if request_line.starts_with("GET /cli/translations") {
+                    format!(r#"{{"data": {}}}"#, remote_state.lock().unwrap())
+                } else {
+                    pushed_bodies.lock().unwrap().push(String::from_utf8(body).unwrap());
+                    r#"{"data": {"added": ["new"], "existed": ["title"], "failed": []}}"#.to_string()
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
 
-        assert!(is_rate_limit_error(&error1));
-        assert!(is_rate_limit_error(&error2));
-        assert!(is_rate_limit_error(&error3));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plan_path = temp_dir.path().join("plan.json");
+        let config = I18nConfig {
+            api_url: base_url.clone(),
+            api_key: "key".to_string(),
+            project_id: 1,
+            messages_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        StatusCache::default().save(temp_dir.path()).unwrap();
+        let status_cache = StatusCache::path(temp_dir.path());
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let local: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("title".to_string(), "Title v2".to_string()),
+                ("same".to_string(), "Same".to_string()),
+                ("new".to_string(), "New".to_string()),
+            ]),
+        )]);
+
+        // 1. 生成计划：不推送
+        let cmd = ImportCmd {
+            plan_file: Some(plan_path.clone()),
+            ..Default::default()
+        };
+        let result = cmd
+            .import_translations(&HumanReporter, &config, &client, &LanguageMapper::new(None), local)
+            .await
+            .unwrap();
+        assert_eq!((result.added, result.updated, result.unchanged), (1, 1, 1));
+        assert!(pushed.lock().unwrap().is_empty());
+        assert!(status_cache.exists());
+        let plan = ImportPlan::load(&plan_path).unwrap();
+        assert_eq!(plan.targets[0].update["en"]["title"].to, "Title v2");
+
+        // 2. 后端被修改后，计划过期
+        *remote.lock().unwrap() = r#"{"title": {"en": "Edited"}}"#.to_string();
+        let err = ImportCmd::default().apply_plan(&config, &client, &plan_path).await.unwrap_err();
+        assert!(err.to_string().contains("stale: 1 key(s)"));
+        assert!(pushed.lock().unwrap().is_empty());
+
+        // 3. 后端未变化时只推送计划中的键
+        *remote.lock().unwrap() = r#"{"title": {"en": "Title"}}"#.to_string();
+        let result = ImportCmd::default().apply_plan(&config, &client, &plan_path).await.unwrap();
+        assert_eq!((result.added, result.updated), (1, 1));
+        // 推送后状态缓存被删除
+        assert!(!status_cache.exists());
+        let pushed = pushed.lock().unwrap();
+        assert_eq!(pushed.len(), 1);
+        assert!(pushed[0].contains("Title v2") && pushed[0].contains("New") && !pushed[0].contains("Same"));
+    }
+
+    #[test]
+    fn test_keep_going_conflicts_with_fail_fast() {
+        assert!(ImportCmd::try_parse_from(["import", "--keep-going", "--fail-fast"]).is_err());
+        let cmd = ImportCmd::try_parse_from(["import", "--keep-going", "--project-concurrency", "3"]).unwrap();
+        assert!(cmd.keep_going);
+        assert_eq!(cmd.project_concurrency, 3);
+        assert_eq!(ImportCmd::try_parse_from(["import"]).unwrap().project_concurrency, 1);
+    }
+
+    #[tokio::test]
+    async fn test_multi_project_keep_going_and_summary() {
+        use crate::core::Route;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：API 密钥为 bad 时认证失败，其他请求返回空翻译
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let (mut request_line, mut line, mut content_length, mut bad_key) =
+                    (String::new(), String::new(), 0, false);
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    let lower = line.to_lowercase();
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if lower.trim() == "x-api-key: bad" {
+                        bad_key = true;
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, response) = if bad_key {
+                    ("401 Unauthorized", r#"{"error": "unauthorized"}"#)
+                } else {
+                    ("200 OK", r#"{"data": {}}"#)
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let summary_path = temp_dir.path().join("summary.json");
+        let config = I18nConfig {
+            api_url: base_url.clone(),
+            api_key: "key".to_string(),
+            project_id: 1,
+            routes: vec![Route {
+                key_prefix: Some("emails.".to_string()),
+                language: None,
+                project_id: 2,
+                api_url: None,
+                api_key: Some("bad".to_string()),
+            }],
+            ..Default::default()
+        };
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let local: Translations = HashMap::from([
+            (
+                "en".to_string(),
+                HashMap::from([
+                    ("title".to_string(), "Title".to_string()),
+                    ("emails.subject".to_string(), "Subject".to_string()),
+                ]),
+            ),
+            ("de".to_string(), HashMap::from([("title".to_string(), "Titel".to_string())])),
+        ]);
+
+        let cmd = ImportCmd {
+            dry_run: true,
+            keep_going: true,
+            project_concurrency: 2,
+            summary_json: Some(summary_path.clone()),
+            ..Default::default()
+        };
+        let err = cmd
+            .import_translations(&HumanReporter, &config, &client, &LanguageMapper::new(None), local)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "1 of 2 project(s) failed: 2");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!(json["projects"][0]["projectId"], 1);
+        assert_eq!(json["projects"][0]["status"], "ok");
+        assert_eq!(json["projects"][0]["languages"]["de"]["added"], 1);
+        assert_eq!(json["projects"][0]["languages"]["en"]["added"], 1);
+        assert_eq!(json["projects"][1]["status"], "failed");
+        assert!(json["projects"][1]["error"].as_str().unwrap().contains("project 2"));
+        assert_eq!(json["total"]["added"], 2);
     }
 
     #[test]
-    fn test_is_rate_limit_error_negative() {
-        let error = anyhow::anyhow!("Not Found: 404");
-        assert!(!is_rate_limit_error(&error));
+    fn test_prune_conflicts() {
+        assert!(ImportCmd::try_parse_from(["import", "--prune", "--locale", "de"]).is_err());
+        assert!(ImportCmd::try_parse_from(["import", "--prune", "--server-dry-run"]).is_err());
+        assert!(ImportCmd::try_parse_from(["import", "--prune", "--since", "v1.0"]).is_err());
+        assert!(ImportCmd::try_parse_from(["import", "--yes"]).is_err());
+        let cmd = ImportCmd::try_parse_from(["import", "--prune", "--yes", "--key-prefix", "old."]).unwrap();
+        assert!(cmd.prune && cmd.yes);
+    }
+
+    #[tokio::test]
+    async fn test_prune_deletes_keys_missing_locally() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::Mutex;
+
+        // 模拟后端：声明支持删除键，DELETE 记录请求体
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let deleted = Arc::new(Mutex::new(Vec::<String>::new()));
+        let deleted_bodies = deleted.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let (mut request_line, mut line, mut content_length) = (String::new(), String::new(), 0);
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let response = if request_line.starts_with("GET /cli/capabilities") {
+                    r#"{"data": {"features": ["delete_keys"]}}"#.to_string()
+                } else if request_line.starts_with("GET /cli/translations") {
+                    r#"{"data": {"title": {"en": "Title"}, "old.a": {"de": "A"}, "old.b": {"en": "B"}, "legacy": {"en": "L"}}}"#
+                        .to_string()
+                } else {
+                    deleted_bodies.lock().unwrap().push(String::from_utf8(body).unwrap());
+                    r#"{"data": {"deleted": ["old.a", "old.b"]}}"#.to_string()
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        let config = I18nConfig {
+            api_url: base_url.clone(),
+            api_key: "key".to_string(),
+            project_id: 1,
+            ..Default::default()
+        };
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let local_keys = HashSet::from(["title".to_string()]);
+        let key_filter = KeyFilter::new(&["old.".to_string()], &[], ".").unwrap();
+
+        // 1. 模拟运行只统计，不删除
+        let dry_run = ImportCmd {
+            prune: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        let count = dry_run.prune_keys(&config, &client, &local_keys, &key_filter, 0).await.unwrap();
+        assert_eq!(count, 2);
+        assert!(deleted.lock().unwrap().is_empty());
+
+        // 2. 有被隔离的文件时拒绝执行
+        let cmd = ImportCmd {
+            prune: true,
+            yes: true,
+            ..Default::default()
+        };
+        let err = cmd.prune_keys(&config, &client, &local_keys, &key_filter, 1).await.unwrap_err();
+        assert!(err.to_string().contains("Refusing to prune"));
+
+        // 3. --yes 时删除被选中的多余键（legacy 不匹配 --key-prefix）
+        let count = cmd.prune_keys(&config, &client, &local_keys, &key_filter, 0).await.unwrap();
+        assert_eq!(count, 2);
+        let deleted = deleted.lock().unwrap();
+        assert_eq!(deleted.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&deleted[0]).unwrap();
+        assert_eq!(body["keys"], serde_json::json!(["old.a", "old.b"]));
+    }
+
+    #[tokio::test]
+    async fn test_server_dry_run_import_reports_rejections() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            // 能力探测
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"data": {"features": ["validate"]}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let body = r#"{"data": {"added": ["a"], "updated": [], "rejected": [{"key": "b", "reason": "too long"}]}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("a".to_string(), "A".to_string()),
+                ("b".to_string(), "B".to_string()),
+            ]),
+        )]);
+
+        let result = ImportCmd::default()
+            .server_dry_run_import(&client, translations)
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /cli/keys/validate"));
+        assert_eq!(result.added, 1);
+        assert_eq!(result.failed, 1);
+        assert!(result.errors[0].contains("too long"));
+    }
+
+    #[tokio::test]
+    async fn test_server_dry_run_falls_back_without_validate_capability() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 旧后端：没有 capabilities 接口
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            write!(stream, "HTTP/1.1 404 Not Found\r\nConnection: close\r\nContent-Length: 0\r\n\r\n").unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([("a".to_string(), "A".to_string())]),
+        )]);
+
+        let result = ImportCmd::default()
+            .server_dry_run_import(&client, translations)
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("GET /cli/capabilities"));
+        assert_eq!(result.added, 1);
+        assert_eq!(result.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_import_with_rate_limit_retry() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // 模拟后端：第一个请求返回 429，其余每个批次报告 1 个新增键
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let counter = counter.clone();
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    loop {
+                        let mut content_length = 0;
+                        let mut line = String::new();
+                        loop {
+                            line.clear();
+                            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                                return;
+                            }
+                            if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                            if line == "\r\n" {
+                                break;
+                            }
+                        }
+                        let mut body = vec![0u8; content_length];
+                        reader.read_exact(&mut body).unwrap();
+
+                        let response = if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n".to_string()
+                        } else {
+                            let body = r#"{"data": {"added": ["k"], "existed": [], "failed": []}}"#;
+                            format!(
+                                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                                body.len(),
+                                body
+                            )
+                        };
+                        stream.write_all(response.as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let keys: HashMap<String, String> = (0..BATCH_SIZE * 3)
+            .map(|i| (format!("key.{}", i), "value".to_string()))
+            .collect();
+        let translations: Translations = HashMap::from([("en".to_string(), keys)]);
+
+        let cmd = ImportCmd {
+            concurrency: 3,
+            ..Default::default()
+        };
+        let result = cmd.execute_import(&client, translations).await.unwrap();
+
+        assert_eq!(result.added, 3);
+        assert_eq!(result.failed, 0);
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_import_shrinks_batches_after_throttling() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：第一个请求返回 429，之后记录每个批次的键数
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let batches = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let seen = batches.clone();
+        std::thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let response = if index == 0 {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nConnection: close\r\nContent-Length: 0\r\n\r\n"
+                        .to_string()
+                } else {
+                    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    seen.lock().push(request["translations"]["en"].as_object().unwrap().len());
+                    let body = r#"{"data": {"added": ["k"], "existed": [], "failed": []}}"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let keys: HashMap<String, String> = (0..BATCH_SIZE * 2)
+            .map(|i| (format!("key.{}", i), "value".to_string()))
+            .collect();
+        let translations: Translations = HashMap::from([("en".to_string(), keys)]);
+
+        let result = ImportCmd::default().execute_import(&client, translations).await.unwrap();
+
+        // 首个批次被限流后，其余键以一半大小发送
+        assert_eq!(*batches.lock(), vec![BATCH_SIZE, BATCH_SIZE / 2, BATCH_SIZE / 2]);
+        assert_eq!(result.added, 3);
+        assert_eq!(result.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_skip_locked_keys() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：checkout. 被 growth 团队锁定
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let body = if buf[..n].starts_with(b"GET /cli/capabilities") {
+                    r#"{"data": {"features": ["locks"]}}"#
+                } else {
+                    r#"{"data": [{"prefix": "checkout.", "holder": "growth"}]}"#
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("checkout.title".to_string(), "Checkout".to_string()),
+                ("home.title".to_string(), "Home".to_string()),
+            ]),
+        )]);
+
+        let cmd = ImportCmd {
+            team: Some("payments".to_string()),
+            ..Default::default()
+        };
+        let (allowed, locked) = cmd.skip_locked_keys(&client, translations.clone(), ".").await.unwrap();
+        assert_eq!(locked, 1);
+        assert_eq!(allowed["en"].keys().collect::<Vec<_>>(), vec!["home.title"]);
+
+        // 锁的持有者自己可以导入
+        let cmd = ImportCmd {
+            team: Some("growth".to_string()),
+            ..Default::default()
+        };
+        let (allowed, locked) = cmd.skip_locked_keys(&client, translations, ".").await.unwrap();
+        assert_eq!((allowed["en"].len(), locked), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_mark_stale_keys() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：支持 review，记录标记请求
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let body = if request_line.starts_with("GET /cli/capabilities") {
+                    r#"{"data": {"features": ["review"]}}"#
+                } else {
+                    let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    recorded.lock().push((request_line.clone(), request["keys"].clone()));
+                    r#"{"data": {"marked": 1}}"#
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let config = I18nConfig {
+            api_url: base_url.clone(),
+            project_id: 1,
+            language_mapping: HashMap::from([("zh_CN".to_string(), "zh-CN".to_string())]),
+            ..Default::default()
+        };
+        let client = APIClient::new(base_url, "key".to_string(), 1).unwrap();
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+        let stale: Translations =
+            HashMap::from([("zh_CN".to_string(), HashMap::from([("title".to_string(), "首页".to_string())]))]);
+
+        // 模拟运行只统计
+        let cmd = ImportCmd {
+            dry_run: true,
+            mark_stale: true,
+            ..Default::default()
+        };
+        assert_eq!(cmd.mark_stale_keys(&config, &client, &language_mapper, stale.clone()).await.unwrap(), 1);
+        assert!(requests.lock().is_empty());
+
+        let cmd = ImportCmd {
+            mark_stale: true,
+            ..Default::default()
+        };
+        assert_eq!(cmd.mark_stale_keys(&config, &client, &language_mapper, stale).await.unwrap(), 1);
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.starts_with("POST /cli/keys/unreviewed"));
+        assert_eq!(requests[0].1, serde_json::json!({"zh-CN": ["title"]}));
     }
 
     #[test]
     fn test_constants() {
         assert_eq!(BATCH_SIZE, 50);
+        assert_eq!((MIN_BATCH_SIZE, MAX_BATCH_SIZE), (5, 200));
         assert_eq!(BATCH_DELAY.as_millis(), 200);
-        assert_eq!(MAX_RETRIES, 3);
     }
 }
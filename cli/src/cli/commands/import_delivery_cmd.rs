@@ -0,0 +1,255 @@
+//! Import-delivery command implementation
+//!
+//! Accepts a vendor delivery (zip, directory or single XLIFF/CSV/PO file),
+//! remaps the vendor's language codes and key names through a mapping file,
+//! validates placeholders against the source text, and pushes the result
+//! to the backend in one step.
+//!
+//! # Features
+//!
+//! - XLIFF 1.2/2.0, CSV and gettext PO inside a zip or directory
+//! - Language and key remapping via a `type,from,to` CSV
+//! - Placeholder validation; invalid entries block the import unless
+//!   `--skip-invalid` is given
+//! - Reuses the regular import pipeline (routing, batching, retries)
+
+use super::ImportCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::delivery::{parse_file, read_package, to_translations, DeliveryEntry, DeliveryMapping};
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::placeholders;
+use crate::core::ImportResult;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Import-delivery 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "import-delivery", about = "Import a vendor translation delivery")]
+pub struct ImportDeliveryCmd {
+    /// 交付包路径（.zip、目录或单个 .xlf/.csv/.po 文件）
+    #[arg(value_name = "DELIVERY")]
+    pub delivery: PathBuf,
+
+    /// 映射文件（CSV：type,from,to）
+    #[arg(long = "map", value_name = "PATH")]
+    pub mapping: Option<PathBuf>,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 模拟运行 - 只解析和校验，不推送
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// 跳过占位符不一致的条目，只推送其余条目
+    #[arg(long)]
+    pub skip_invalid: bool,
+}
+
+/// 占位符校验失败的条目
+#[derive(Debug, Clone)]
+struct InvalidEntry {
+    entry: DeliveryEntry,
+    reason: String,
+}
+
+impl ImportDeliveryCmd {
+    /// 执行 import-delivery 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置与映射文件
+    /// 2. 解包并解析交付文件
+    /// 3. 应用映射并校验占位符
+    /// 4. 创建 API 客户端并验证认证
+    /// 5. 推送（复用 import 流程）
+    pub async fn run(&self, ctx: &RunContext) -> Result<ImportResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置与映射文件
+        let config = ctx.load_config(config_path).await?;
+        let mapping = match &self.mapping {
+            Some(path) => DeliveryMapping::load(path)?,
+            None => DeliveryMapping::default(),
+        };
+
+        // 2. 解包并解析交付文件
+        info!("Reading delivery: {}", self.delivery.display());
+        let files = read_package(&self.delivery)?;
+        if files.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No XLIFF, CSV or PO files found in {}",
+                self.delivery.display()
+            ));
+        }
+
+        let mut entries = Vec::new();
+        for (name, content) in &files {
+            let parsed = parse_file(name, content)?;
+            info!("  - {}: {} entries", name, parsed.len());
+            entries.extend(parsed);
+        }
+
+        // 3. 应用映射并校验占位符
+        let entries: Vec<DeliveryEntry> = entries.into_iter().map(|e| mapping.apply(e)).collect();
+        let (valid, invalid) = validate_entries(entries);
+        report_invalid(&invalid);
+        if !invalid.is_empty() && !self.skip_invalid {
+            return Err(anyhow::anyhow!(
+                "{} entr(ies) have placeholder mismatches; fix the delivery or re-run with --skip-invalid",
+                invalid.len()
+            ));
+        }
+
+        let translations = to_translations(&valid);
+        let key_count: usize = translations.values().map(|t| t.len()).sum();
+        info!(
+            "  - Accepted {} translation(s) in {} language(s)",
+            key_count,
+            translations.len()
+        );
+        if key_count == 0 {
+            info!("Nothing to import.");
+            return Ok(ImportResult::default());
+        }
+
+        // 4. 创建 API 客户端并验证认证
+        let client = APIClient::from_config(&config)
+        .context("Failed to create API client")?;
+        if !self.dry_run && !client.check_auth().await? {
+            return Err(Failure::Auth.error(
+                "API authentication failed. Please check your API key."
+            ));
+        }
+
+        // 5. 推送
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+        let import = ImportCmd {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+        let mut result = import
+            .import_translations(&*ctx.reporter, &config, &client, &language_mapper, translations)
+            .await?;
+        result.failed += invalid.len();
+        Ok(result)
+    }
+}
+
+/// 按原文校验占位符，将条目分为有效和无效两组
+///
+/// 没有原文的条目（如 CSV 中缺少 `source` 列）不做校验。
+fn validate_entries(entries: Vec<DeliveryEntry>) -> (Vec<DeliveryEntry>, Vec<InvalidEntry>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+
+    for entry in entries {
+        let mismatch = entry
+            .source
+            .as_deref()
+            .and_then(|source| placeholders::compare(source, &entry.value));
+        match mismatch {
+            Some(reason) => invalid.push(InvalidEntry { entry, reason }),
+            None => valid.push(entry),
+        }
+    }
+
+    (valid, invalid)
+}
+
+/// 输出占位符校验失败的条目
+fn report_invalid(invalid: &[InvalidEntry]) {
+    if invalid.is_empty() {
+        return;
+    }
+    info!("Placeholder mismatches: {}", invalid.len());
+    for item in invalid.iter().take(20) {
+        info!("  - {}:{} {}", item.entry.lang, item.entry.key, item.reason);
+    }
+    if invalid.len() > 20 {
+        info!("  ... and {} more", invalid.len() - 20);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(key: &str, value: &str, source: Option<&str>) -> DeliveryEntry {
+        DeliveryEntry {
+            lang: "de".to_string(),
+            key: key.to_string(),
+            value: value.to_string(),
+            source: source.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validate_entries() {
+        let (valid, invalid) = validate_entries(vec![
+            entry("ok", "Hallo {name}", Some("Hello {name}")),
+            entry("bad", "Hallo", Some("Hello {name}")),
+            entry("unchecked", "Hallo", None),
+        ]);
+        assert_eq!(valid.len(), 2);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].entry.key, "bad");
+    }
+
+    fn setup(delivery_csv: &str) -> (TempDir, PathBuf, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(&messages_dir).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost:1", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        let delivery = temp_dir.path().join("delivery.csv");
+        std::fs::write(&delivery, delivery_csv).unwrap();
+        (temp_dir, config_path, delivery)
+    }
+
+    #[tokio::test]
+    async fn test_import_delivery_dry_run_with_mapping() {
+        let (temp_dir, config_path, delivery) =
+            setup("key,source,de-DE\nbtn_save,Save {item},Speichern {item}\n");
+        let mapping = temp_dir.path().join("mapping.csv");
+        std::fs::write(&mapping, "type,from,to\nlanguage,de-DE,de\nkey,btn_save,buttons.save\n").unwrap();
+
+        let cmd = ImportDeliveryCmd {
+            delivery,
+            mapping: Some(mapping),
+            config: Some(config_path),
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.added, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_delivery_blocks_placeholder_mismatch() {
+        let (_temp_dir, config_path, delivery) =
+            setup("key,source,de\ngreeting,Hello {name},Hallo\n");
+
+        let cmd = ImportDeliveryCmd {
+            delivery,
+            config: Some(config_path),
+            dry_run: true,
+            ..Default::default()
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("placeholder mismatches"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Import-file command implementation
+//!
+//! Loads a spreadsheet export into the backend: a CSV (`.csv`) or TSV
+//! (`.tsv`, `.tab`) file whose header row is `key,<lang>,<lang>,...`, as
+//! product managers keep copy in shared sheets. Empty cells are skipped, and
+//! `source`/`context`/`comment`/`notes` columns are ignored.
+//!
+//! The rows are pushed with the regular import pipeline (routing, language
+//! mapping, batching, retries); `--dry-run` previews them instead.
+
+use super::ImportCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::delivery::{parse_table, to_translations};
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::ImportResult;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Import-file 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "import-file", about = "Import translations from a CSV/TSV file")]
+pub struct ImportFileCmd {
+    /// 表格文件（.csv 或 .tsv，表头为 key,<语言>,...）
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 模拟运行 - 只解析和预览，不推送
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// 并发上传的批次数（默认 1，即逐批上传）
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub concurrency: usize,
+}
+
+impl ImportFileCmd {
+    /// 执行 import-file 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 解析表格
+    /// 3. 创建 API 客户端并验证认证
+    /// 4. 推送（复用 import 流程）
+    pub async fn run(&self, ctx: &RunContext) -> Result<ImportResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 解析表格
+        let delimiter = delimiter(&self.file)?;
+        let content = std::fs::read_to_string(&self.file)
+            .with_context(|| format!("Failed to read {}", self.file.display()))?;
+        let entries =
+            parse_table(&content, delimiter).with_context(|| format!("Invalid table: {}", self.file.display()))?;
+        let translations = to_translations(&entries);
+        let key_count: usize = translations.values().map(|t| t.len()).sum();
+        let mut languages: Vec<&String> = translations.keys().collect();
+        languages.sort();
+        info!(
+            "Read {} translation(s) from {} (languages: {})",
+            key_count,
+            self.file.display(),
+            languages.iter().map(|lang| lang.as_str()).collect::<Vec<_>>().join(", ")
+        );
+        if key_count == 0 {
+            info!("Nothing to import.");
+            return Ok(ImportResult::default());
+        }
+
+        // 3. 创建 API 客户端并验证认证
+        let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+        if !self.dry_run && !client.check_auth().await? {
+            return Err(Failure::Auth.error("API authentication failed. Please check your API key."));
+        }
+
+        // 4. 推送
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+        let import = ImportCmd {
+            dry_run: self.dry_run,
+            concurrency: self.concurrency,
+            ..Default::default()
+        };
+        import
+            .import_translations(&*ctx.reporter, &config, &client, &language_mapper, translations)
+            .await
+    }
+}
+
+/// 根据扩展名选择分隔符
+fn delimiter(path: &Path) -> Result<u8> {
+    match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("csv") => Ok(b','),
+        Some("tsv") | Some("tab") => Ok(b'\t'),
+        _ => Err(anyhow::anyhow!(
+            "Unsupported file {}: expected a .csv or .tsv file",
+            path.display()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(name: &str, table: &str) -> (TempDir, PathBuf, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost:1", "apiKey": "k"}}"#,
+                temp_dir.path().join("messages").display()
+            ),
+        )
+        .unwrap();
+        let file = temp_dir.path().join(name);
+        std::fs::write(&file, table).unwrap();
+        (temp_dir, config_path, file)
+    }
+
+    #[tokio::test]
+    async fn test_import_file_dry_run() {
+        let (_temp_dir, config_path, file) =
+            setup("copy.tsv", "key\ten\tde\nbuttons.save\tSave\tSpeichern\nbuttons.cancel\tCancel\t\n");
+        let cmd = ImportFileCmd {
+            file,
+            config: Some(config_path),
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.added, 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_file_rejects_unknown_extension() {
+        let (_temp_dir, config_path, file) = setup("copy.xlsx", "");
+        let cmd = ImportFileCmd {
+            file,
+            config: Some(config_path),
+            dry_run: true,
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("expected a .csv or .tsv file"));
+    }
+}
@@ -0,0 +1,214 @@
+//! Lint command implementation
+//!
+//! Checks local translation values against the content rules defined in
+//! `lintRules` (forbidden phrases, required patterns, sentence case), and
+//! reports locale files holding more keys than `maxKeysPerFile`.
+//!
+//! # Features
+//!
+//! - Per-language and per-key-pattern rules
+//! - Allowlist exceptions per rule
+//! - Warning vs error severity; only errors fail the command
+//! - Oversized files are errors; `fmt --split` fixes them
+
+use crate::cli::RunContext;
+use crate::core::failure::Failure;
+use crate::core::file_split::oversized_files;
+use crate::core::lint::{LintIssue, Linter, Severity};
+use crate::core::scanner::scan_messages_dir;
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Lint 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "lint", about = "Check translations against content rules")]
+pub struct LintCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只检查指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+}
+
+impl LintCmd {
+    /// 执行 lint 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置并编译规则
+    /// 2. 扫描本地 messages 目录
+    /// 3. 检查翻译并输出问题
+    ///
+    /// # 参数
+    ///
+    /// * `ctx` - 本次运行的上下文（全局 `--config` 和命令行覆盖）
+    ///
+    /// # Errors
+    ///
+    /// 存在 `error` 级别的问题时返回错误。
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<LintIssue>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置并编译规则
+        let config = ctx.load_config(config_path).await?;
+        let linter = Linter::new(&config.lint_rules)?;
+        if linter.is_empty() && config.max_keys_per_file.is_none() {
+            info!("No lintRules or maxKeysPerFile configured, nothing to check.");
+            return Ok(Vec::new());
+        }
+        info!("Loaded {} lint rule(s)", config.lint_rules.len());
+
+        // 2. 扫描 messages 目录
+        let messages_dir = config.messages();
+        let mut scan_result = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+        if !self.languages.is_empty() {
+            scan_result
+                .translations
+                .retain(|lang, _| self.languages.contains(lang));
+        }
+
+        // 3. 检查翻译和文件大小
+        let mut issues = linter.lint(&scan_result.translations);
+        if let Some(max) = config.max_keys_per_file {
+            for file in oversized_files(&messages_dir, &scan_result.files, max)? {
+                if !self.languages.is_empty() && !self.languages.contains(&file.lang) {
+                    continue;
+                }
+                let relative = file.path.strip_prefix(&config.messages_dir).unwrap_or(&file.path);
+                issues.push(LintIssue {
+                    rule: "maxKeysPerFile".to_string(),
+                    lang: file.lang,
+                    key: relative.display().to_string(),
+                    value: String::new(),
+                    message: format!("has {} keys (max {}), split it with `yflow fmt --split`", file.keys, max),
+                    severity: Severity::Error,
+                });
+            }
+        }
+        print_issues(&*ctx.reporter, &issues);
+
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .count();
+        if errors > 0 {
+            return Err(Failure::Validation.error(format!(
+                "Lint failed: {} error(s), {} warning(s)",
+                errors,
+                issues.len() - errors
+            )));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// 输出问题列表
+fn print_issues(reporter: &dyn Reporter, issues: &[LintIssue]) {
+    if issues.is_empty() {
+        reporter.success("No lint issues found.");
+        return;
+    }
+
+    for issue in issues {
+        let marker = match issue.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        if issue.value.is_empty() {
+            reporter.output(format!("{}[{}] {}:{} {}", marker, issue.rule, issue.lang, issue.key, issue.message));
+        } else {
+            reporter.output(format!(
+                "{}[{}] {}:{} {} ({:?})",
+                marker, issue.rule, issue.lang, issue.key, issue.message, issue.value
+            ));
+        }
+    }
+    reporter.output("");
+    reporter.output(format!("{} issue(s) found.", issues.len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(rules: &str) -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(
+            messages_dir.join("en").join("common.json"),
+            r#"{"buttons": {"save": "Save Changes"}, "help": "Click here"}"#,
+        )
+        .unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k", "lintRules": {}}}"#,
+                messages_dir.display(),
+                rules
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_lint_warnings_do_not_fail() {
+        let (_temp_dir, config_path) =
+            setup(r#"[{"name": "case", "keys": "buttons.*", "sentenceCase": true, "severity": "warning"}]"#);
+        let cmd = LintCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+
+        let issues = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].key, "buttons.save");
+    }
+
+    #[tokio::test]
+    async fn test_lint_errors_fail() {
+        let (_temp_dir, config_path) = setup(r#"[{"name": "no-click-here", "forbid": "(?i)click here"}]"#);
+        let cmd = LintCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("1 error(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_lint_max_keys_per_file() {
+        let (_temp_dir, config_path) = setup(r#"[], "maxKeysPerFile": 1"#);
+        let cmd = LintCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("1 error(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_lint_language_filter() {
+        let (_temp_dir, config_path) = setup(r#"[{"name": "no-click-here", "forbid": "(?i)click here"}]"#);
+        let cmd = LintCmd {
+            config: Some(config_path),
+            languages: vec!["de".to_string()],
+        };
+
+        assert!(cmd.run(&RunContext::default()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Lock command implementation
+//!
+//! Locks a key prefix on the backend for the duration of an editing session,
+//! so `import` runs by other teams skip those keys. Without a prefix (or with
+//! `--list`) the current locks are listed.
+//!
+//! Requires a backend that advertises the `locks` capability.
+
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::deadline::{format_duration, parse_duration};
+use crate::core::locks::{current_holder, KeyLock};
+use crate::core::status_cache::StatusCache;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Lock 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "lock", about = "Lock a key prefix while editing, or list locks")]
+pub struct LockCmd {
+    /// 要锁定的键前缀（如 `checkout.`），省略时列出当前的锁
+    #[arg(value_name = "KEY_PREFIX")]
+    pub prefix: Option<String>,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 锁的持有团队（默认 I18N_TEAM，其次为系统用户名）
+    #[arg(long, value_name = "TEAM")]
+    pub team: Option<String>,
+
+    /// 锁的有效期（如 `30m`、`2h`），默认直到解锁
+    #[arg(long, value_name = "DURATION")]
+    pub ttl: Option<String>,
+
+    /// 列出当前的锁
+    #[arg(long, conflicts_with_all = ["prefix", "ttl"])]
+    pub list: bool,
+}
+
+impl LockCmd {
+    /// 执行 lock 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置并创建 API 客户端
+    /// 2. 列出当前的锁（未指定前缀时）
+    /// 3. 锁定前缀
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<KeyLock>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置并创建 API 客户端
+        let config = ctx.load_config(config_path).await?;
+        let ttl = self.ttl.as_deref().map(parse_duration).transpose()?;
+        let client = APIClient::from_config(&config)
+        .context("Failed to create API client")?;
+
+        // 2. 列出当前的锁（未指定前缀时）
+        let prefix = match &self.prefix {
+            Some(prefix) if !self.list => prefix,
+            _ => {
+                let locks = client.list_locks().await?;
+                if locks.is_empty() {
+                    info!("No keys are locked.");
+                }
+                for lock in &locks {
+                    info!("  {} (locked by '{}'{})", lock.prefix, lock.holder, expiry(lock));
+                }
+                return Ok(locks);
+            }
+        };
+        if prefix.trim().is_empty() {
+            return Err(anyhow::anyhow!("Key prefix cannot be empty"));
+        }
+
+        // 3. 锁定前缀
+        let holder = current_holder(self.team.as_deref());
+        StatusCache::invalidate(&config.messages_dir)?;
+        let lock = client.lock_keys(prefix, &holder, ttl).await?;
+        match ttl {
+            Some(ttl) => info!("Locked '{}' for '{}' ({})", lock.prefix, lock.holder, format_duration(ttl)),
+            None => info!("Locked '{}' for '{}' until `yflow unlock {}`", lock.prefix, lock.holder, lock.prefix),
+        }
+
+        Ok(vec![lock])
+    }
+}
+
+/// 格式化锁的过期时间
+fn expiry(lock: &KeyLock) -> String {
+    lock.expires_at
+        .as_deref()
+        .map(|at| format!(", expires {}", at))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lock_sends_prefix_and_holder() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟支持键锁的后端，记录锁定请求的请求体
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut lock_request = String::new();
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let body = if request.starts_with("GET /cli/capabilities") {
+                    r#"{"data": {"features": ["locks"]}}"#
+                } else {
+                    lock_request = request;
+                    r#"{"data": {"prefix": "checkout.", "holder": "payments", "expires_at": "2026-01-01T02:00:00Z"}}"#
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 201 Created\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            lock_request
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                temp_dir.path().display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        let cmd = LockCmd {
+            prefix: Some("checkout.".to_string()),
+            config: Some(config_path),
+            team: Some("payments".to_string()),
+            ttl: Some("2h".to_string()),
+            ..Default::default()
+        };
+        let locks = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(locks[0].expires_at.as_deref(), Some("2026-01-01T02:00:00Z"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /cli/locks"));
+        assert!(request.contains(r#""prefix":"checkout.""#));
+        assert!(request.contains(r#""holder":"payments""#));
+        assert!(request.contains(r#""ttl_seconds":7200"#));
+    }
+}
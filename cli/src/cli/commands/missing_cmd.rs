@@ -0,0 +1,157 @@
+//! Missing command implementation
+//!
+//! Lists, per language, every key the base language has a value for but the
+//! language lacks or leaves empty, with counts and completion percentages,
+//! as a table (default), JSON or CSV for dashboards.
+
+use crate::cli::RunContext;
+use crate::core::missing::MissingReport;
+use crate::core::scanner::{report_quarantine, scan_messages_dir};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+use tracing::info;
+
+/// 报告输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MissingFormat {
+    /// 统计表和未翻译的键
+    #[default]
+    Table,
+    /// JSON
+    Json,
+    /// CSV，每个未翻译的键一行
+    Csv,
+}
+
+/// Missing 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "missing", about = "List untranslated keys of every language")]
+pub struct MissingCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 基准语言（默认：配置的 sourceLanguage，否则为键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub base: Option<String>,
+
+    /// 只统计指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 输出格式
+    #[arg(long, value_enum, default_value_t = MissingFormat::Table)]
+    pub format: MissingFormat,
+
+    /// 输出文件（默认输出到标准输出）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+}
+
+impl MissingCmd {
+    /// 执行 missing 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 比较各语言与基准语言
+    /// 4. 输出报告
+    pub async fn run(&self, ctx: &RunContext) -> Result<MissingReport> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 扫描 messages 目录
+        let scan = scan_messages_dir(&config.messages())
+            .await
+            .context("Failed to scan messages directory")?;
+
+        let base = match self.base.as_ref().or(config.source_language.as_ref()) {
+            Some(base) => base.clone(),
+            None => {
+                let mut languages: Vec<(&String, usize)> =
+                    scan.translations.iter().map(|(lang, keys)| (lang, keys.len())).collect();
+                languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                languages
+                    .first()
+                    .map(|(lang, _)| lang.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No languages found in {}", config.messages_dir.display()))?
+            }
+        };
+
+        // 3. 比较各语言与基准语言
+        let report = MissingReport::collect(&scan.translations, &base, &self.languages)?;
+        info!(
+            "{} untranslated key(s) in {} language(s) compared to {}",
+            report.untranslated(),
+            report.languages.len(),
+            report.base
+        );
+
+        // 4. 输出报告
+        let content = match self.format {
+            MissingFormat::Table => report.render_table(),
+            MissingFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+            MissingFormat::Csv => report.to_csv()?,
+        };
+        match &self.out {
+            Some(path) => {
+                std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+                info!("Missing-translation report written to {}", path.display());
+            }
+            None => ctx.reporter.write(&content),
+        }
+
+        report_quarantine(&scan.quarantine)?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_missing_report_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, content) in [
+            ("en", r#"{"title": "Welcome", "save": "Save"}"#),
+            ("de", r#"{"title": "Willkommen"}"#),
+        ] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let out = temp_dir.path().join("missing.json");
+        let cmd = MissingCmd {
+            config: Some(config_path),
+            base: Some("en".to_string()),
+            format: MissingFormat::Json,
+            out: Some(out.clone()),
+            ..Default::default()
+        };
+        let report = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(report.untranslated(), 1);
+
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(json["base"], "en");
+        assert_eq!(json["languages"][0]["lang"], "de");
+        assert_eq!(json["languages"][0]["completion"], 50.0);
+        assert_eq!(json["languages"][0]["keys"][0]["key"], "save");
+        assert_eq!(json["languages"][0]["keys"][0]["status"], "missing");
+    }
+}
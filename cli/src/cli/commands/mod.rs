@@ -1,5 +1,61 @@
+mod add_language_cmd;
+mod auth_cmd;
+mod bench_cmd;
+mod check_placeholders_cmd;
+mod config_cmd;
+mod diff_local_cmd;
+mod explain_cmd;
+mod export_cmd;
+mod fmt_cmd;
+mod gen_tests_cmd;
+mod get_cmd;
+mod graph_cmd;
 mod import_cmd;
+mod import_delivery_cmd;
+mod import_file_cmd;
+mod lint_cmd;
+mod lock_cmd;
+mod missing_cmd;
+mod qa_report_cmd;
+mod remove_language_cmd;
+mod replace_cmd;
+mod rollback_cmd;
+mod set_cmd;
+mod stats_cmd;
+mod status_cmd;
 mod sync_cmd;
+mod todo_cmd;
+mod unlock_cmd;
+mod validate_cmd;
+mod watch_cmd;
 
+pub use add_language_cmd::AddLanguageCmd;
+pub use auth_cmd::AuthCmd;
+pub use bench_cmd::BenchCmd;
+pub use check_placeholders_cmd::CheckPlaceholdersCmd;
+pub use config_cmd::ConfigCmd;
+pub use diff_local_cmd::DiffLocalCmd;
+pub use explain_cmd::ExplainCmd;
+pub use export_cmd::ExportCmd;
+pub use fmt_cmd::FmtCmd;
+pub use gen_tests_cmd::GenTestsCmd;
+pub use get_cmd::GetCmd;
+pub use graph_cmd::GraphCmd;
 pub use import_cmd::ImportCmd;
+pub use import_delivery_cmd::ImportDeliveryCmd;
+pub use import_file_cmd::ImportFileCmd;
+pub use lint_cmd::LintCmd;
+pub use lock_cmd::LockCmd;
+pub use missing_cmd::MissingCmd;
+pub use qa_report_cmd::QaReportCmd;
+pub use remove_language_cmd::RemoveLanguageCmd;
+pub use replace_cmd::ReplaceCmd;
+pub use rollback_cmd::RollbackCmd;
+pub use set_cmd::SetCmd;
+pub use stats_cmd::StatsCmd;
+pub use status_cmd::StatusCmd;
 pub use sync_cmd::SyncCmd;
+pub use todo_cmd::TodoCmd;
+pub use unlock_cmd::UnlockCmd;
+pub use validate_cmd::ValidateCmd;
+pub use watch_cmd::WatchCmd;
@@ -0,0 +1,211 @@
+//! QA report command implementation
+//!
+//! Scores every language against the source language (placeholder errors,
+//! length violations, empty values, markup issues) and exports the result as
+//! JSON or a self-contained HTML page, so quality can be tracked per release.
+
+use crate::cli::RunContext;
+use crate::core::qa::{score_translations, QaOptions, QaReport, DEFAULT_MAX_LENGTH_RATIO};
+use crate::core::scanner::scan_messages_dir;
+use crate::core::timestamp;
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+use tracing::info;
+
+/// 报告输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum QaReportFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// 独立的 HTML 页面
+    Html,
+}
+
+/// QA report 命令参数
+#[derive(Parser, Debug)]
+#[command(name = "qa-report", about = "Export a translation quality report")]
+pub struct QaReportCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 输出格式
+    #[arg(long, value_enum, default_value_t = QaReportFormat::Json)]
+    pub format: QaReportFormat,
+
+    /// 输出文件（默认输出到标准输出）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// 源语言（默认：键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub source: Option<String>,
+
+    /// 只评分指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 译文长度超过原文的倍数时视为过长
+    #[arg(long, value_name = "RATIO", default_value_t = DEFAULT_MAX_LENGTH_RATIO)]
+    pub max_length_ratio: f64,
+
+    /// 写入报告的版本标签（如 v2.3）
+    #[arg(long, value_name = "LABEL")]
+    pub release: Option<String>,
+}
+
+impl Default for QaReportCmd {
+    fn default() -> Self {
+        Self {
+            config: None,
+            format: QaReportFormat::default(),
+            out: None,
+            source: None,
+            languages: Vec::new(),
+            max_length_ratio: DEFAULT_MAX_LENGTH_RATIO,
+            release: None,
+        }
+    }
+}
+
+impl QaReportCmd {
+    /// 执行 qa-report 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 为各语言评分
+    /// 4. 输出报告
+    pub async fn run(&self, ctx: &RunContext) -> Result<QaReport> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        if self.max_length_ratio <= 0.0 {
+            return Err(anyhow::anyhow!("--max-length-ratio must be positive"));
+        }
+
+        // 2. 扫描 messages 目录
+        let mut translations = scan_messages_dir(&config.messages())
+            .await
+            .context("Failed to scan messages directory")?
+            .translations;
+
+        let source = match &self.source {
+            Some(source) => source.clone(),
+            None => {
+                let mut languages: Vec<(&String, usize)> =
+                    translations.iter().map(|(lang, keys)| (lang, keys.len())).collect();
+                languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                languages
+                    .first()
+                    .map(|(lang, _)| lang.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No languages found in {}", config.messages_dir.display()))?
+            }
+        };
+        if !translations.contains_key(&source) {
+            return Err(anyhow::anyhow!("Source language '{}' not found", source));
+        }
+        if !self.languages.is_empty() {
+            translations.retain(|lang, _| *lang == source || self.languages.contains(lang));
+        }
+
+        // 3. 为各语言评分
+        let options = QaOptions {
+            source: source.clone(),
+            max_length_ratio: self.max_length_ratio,
+        };
+        let report = QaReport {
+            release: self.release.clone(),
+            generated_at: timestamp::format_rfc3339(timestamp::now()),
+            source,
+            max_length_ratio: self.max_length_ratio,
+            languages: score_translations(&translations, &options),
+        };
+        for language in &report.languages {
+            info!(
+                "  - {}: score {:.1}, {} issue(s) in {} key(s)",
+                language.lang,
+                language.score,
+                language.issues.len(),
+                language.checked
+            );
+        }
+
+        // 4. 输出报告
+        let content = match self.format {
+            QaReportFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+            QaReportFormat::Html => report.to_html(),
+        };
+        match &self.out {
+            Some(path) => {
+                std::fs::write(path, content)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                info!("QA report written to {}", path.display());
+            }
+            None => ctx.reporter.write(&content),
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_qa_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, content) in [
+            ("en", r#"{"greeting": "Hello {name}", "bye": "Bye", "extra": "Only in English"}"#),
+            ("de", r#"{"greeting": "Hallo", "bye": "Tschüss"}"#),
+            ("fr", r#"{"greeting": "Bonjour {name}", "bye": ""}"#),
+        ] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let out = temp_dir.path().join("qa.json");
+        let cmd = QaReportCmd {
+            config: Some(config_path.clone()),
+            out: Some(out.clone()),
+            release: Some("v1.0".to_string()),
+            ..Default::default()
+        };
+        let report = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(report.source, "en");
+        assert_eq!(report.languages.len(), 2);
+        assert_eq!(report.languages[0].score, 50.0);
+        assert_eq!(report.languages[0].missing, 1);
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(written["release"], "v1.0");
+        assert_eq!(written["languages"][1]["counts"]["empty"], 1);
+
+        let cmd = QaReportCmd {
+            config: Some(config_path),
+            format: QaReportFormat::Html,
+            out: Some(temp_dir.path().join("qa.html")),
+            languages: vec!["fr".to_string()],
+            ..Default::default()
+        };
+        let report = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(report.languages.len(), 1);
+        assert!(std::fs::read_to_string(temp_dir.path().join("qa.html")).unwrap().starts_with("<!DOCTYPE html>"));
+    }
+}
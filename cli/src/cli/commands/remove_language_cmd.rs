@@ -0,0 +1,256 @@
+//! Remove-language command implementation
+//!
+//! Removes a language: moves its local directory into a backup, drops its
+//! `languageMapping` entry, and with `--remote` asks the backend to delete
+//! the locale as well.
+//!
+//! # Features
+//!
+//! - Local files are moved to `<messagesDir>/.yflow-backups/`, never deleted
+//! - Optional backend deletion (`--remote`)
+
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::config::remove_language_mapping;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::Layout;
+use crate::core::{scanner, timestamp, MessagesDir};
+use crate::core::status_cache::StatusCache;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// 备份目录名（位于 messages 目录下）
+pub const BACKUP_DIR: &str = ".yflow-backups";
+
+/// Remove-language 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "remove-language", about = "Remove a language locally and optionally on the backend")]
+pub struct RemoveLanguageCmd {
+    /// 要删除的本地语言代码（如 nb_NO）
+    #[arg(value_name = "LANG")]
+    pub language: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 同时从后端删除该语言及其所有翻译
+    #[arg(long)]
+    pub remote: bool,
+}
+
+/// Remove-language 结果
+#[derive(Debug, Clone, Default)]
+pub struct RemoveLanguageResult {
+    /// 本地目录的备份位置（本地不存在该语言时为 None）
+    pub backup: Option<PathBuf>,
+    /// 是否删除了 languageMapping 条目
+    pub mapping_removed: bool,
+    /// 是否从后端删除了语言
+    pub remote_deleted: bool,
+}
+
+impl RemoveLanguageCmd {
+    /// 执行 remove-language 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 从后端删除（如指定 `--remote`）
+    /// 3. 备份并移除本地目录
+    /// 4. 删除 languageMapping 条目
+    pub async fn run(&self, ctx: &RunContext) -> Result<RemoveLanguageResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path.clone()).await?;
+        let messages_dir = config.messages();
+        let language_dir = scanner::language_dir(&messages_dir, &self.language);
+        let has_local = has_local_files(&messages_dir, &self.language)?;
+        if !has_local && !self.remote {
+            return Err(anyhow::anyhow!(
+                "Language directory not found: {}",
+                language_dir.display()
+            ));
+        }
+
+        // 2. 从后端删除（先于本地操作，失败时本地保持不变）
+        let remote_deleted = if self.remote {
+            let backend_code =
+                LanguageMapper::new(Some(config.language_mapping.clone())).to_backend(&self.language);
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+            StatusCache::invalidate(&config.messages_dir)?;
+            let deleted = client.delete_language(&backend_code).await?;
+            if !deleted {
+                info!("  - '{}' does not exist on the backend", backend_code);
+            }
+            deleted
+        } else {
+            false
+        };
+
+        // 3. 备份并移除本地目录
+        let backup = if has_local {
+            Some(backup_language_dir(&messages_dir, &self.language)?)
+        } else {
+            None
+        };
+
+        // 4. 删除 languageMapping 条目
+        let mapping_removed = remove_language_mapping(config_path, &self.language)?;
+
+        let result = RemoveLanguageResult {
+            backup,
+            mapping_removed,
+            remote_deleted,
+        };
+
+        info!("Language '{}' removed:", self.language);
+        if let Some(backup) = &result.backup {
+            info!("  - Local files backed up to {}", backup.display());
+        }
+        if result.mapping_removed {
+            info!("  - languageMapping entry removed");
+        }
+        if result.remote_deleted {
+            info!("  - Deleted from the backend");
+        }
+
+        Ok(result)
+    }
+}
+
+/// 本地是否存在该语言（平铺布局下检查 `<lang>.*` 文件）
+fn has_local_files(messages_dir: &MessagesDir, language: &str) -> Result<bool> {
+    Ok(match messages_dir.layout {
+        Layout::FlatFiles => !scanner::language_files(messages_dir, language)?.is_empty(),
+        Layout::NestedDirs => scanner::language_dir(messages_dir, language).is_dir(),
+    })
+}
+
+/// 将语言目录移动到备份目录
+///
+/// 平铺布局下将该语言的文件移动到备份目录中。
+///
+/// # Returns
+///
+/// 备份路径：`<messages_dir>/.yflow-backups/<lang>-<timestamp>`
+fn backup_language_dir(messages_dir: &MessagesDir, language: &str) -> Result<PathBuf> {
+    let backup_root = messages_dir.join(BACKUP_DIR);
+    std::fs::create_dir_all(&backup_root)
+        .with_context(|| format!("Failed to create backup directory: {}", backup_root.display()))?;
+
+    let stamp = timestamp::format_compact(timestamp::now());
+    let mut backup = backup_root.join(format!("{}-{}", language, stamp));
+    let mut suffix = 1;
+    while backup.exists() {
+        backup = backup_root.join(format!("{}-{}-{}", language, stamp, suffix));
+        suffix += 1;
+    }
+
+    if messages_dir.layout == Layout::FlatFiles {
+        std::fs::create_dir_all(&backup)
+            .with_context(|| format!("Failed to create backup directory: {}", backup.display()))?;
+        for file in scanner::language_files(messages_dir, language)? {
+            let source = messages_dir.join(&file);
+            std::fs::rename(&source, backup.join(&file)).with_context(|| {
+                format!("Failed to move {} to {}", source.display(), backup.display())
+            })?;
+        }
+        return Ok(backup);
+    }
+
+    let source = scanner::language_dir(messages_dir, language);
+    std::fs::rename(&source, &backup).with_context(|| {
+        format!("Failed to move {} to {}", source.display(), backup.display())
+    })?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::load_config;
+    use crate::core::scanner::scan_messages_dir;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_remove_language_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for lang in ["en", "nb_NO"] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), r#"{"title": "T"}"#).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k", "languageMapping": {{"nb_NO": "nb"}}}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = RemoveLanguageCmd {
+            language: "nb_NO".to_string(),
+            config: Some(config_path.clone()),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        let backup = result.backup.unwrap();
+        assert!(backup.join("common.json").exists());
+        assert!(!messages_dir.join("nb_NO").exists());
+        assert!(result.mapping_removed);
+        assert!(!result.remote_deleted);
+
+        // 备份目录不会被当作语言扫描
+        let scan = scan_messages_dir(&MessagesDir::new(&messages_dir)).await.unwrap();
+        assert_eq!(scan.translations.keys().collect::<Vec<_>>(), vec!["en"]);
+        assert!(load_config(Some(config_path.clone())).await.unwrap().language_mapping.is_empty());
+
+        // 再次删除：本地已不存在
+        assert!(cmd.run(&RunContext::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_language_flat_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(&messages_dir).unwrap();
+        for lang in ["en", "nb_NO"] {
+            std::fs::write(messages_dir.join(format!("{}.json", lang)), r#"{"title": "T"}"#).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k", "layout": "flat-files"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = RemoveLanguageCmd {
+            language: "nb_NO".to_string(),
+            config: Some(config_path),
+            ..Default::default()
+        };
+        let backup = cmd.run(&RunContext::default()).await.unwrap().backup.unwrap();
+        assert!(backup.join("nb_NO.json").exists());
+        assert!(!messages_dir.join("nb_NO.json").exists());
+        assert!(messages_dir.join("en.json").exists());
+
+        let flat = MessagesDir {
+            layout: Layout::FlatFiles,
+            ..MessagesDir::new(&messages_dir)
+        };
+        let scan = scan_messages_dir(&flat).await.unwrap();
+        assert_eq!(scan.translations.keys().collect::<Vec<_>>(), vec!["en"]);
+        assert!(cmd.run(&RunContext::default()).await.is_err());
+    }
+}
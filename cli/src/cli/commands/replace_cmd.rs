@@ -0,0 +1,296 @@
+//! Replace command implementation
+//!
+//! Finds and replaces text inside translation values across the local
+//! files, e.g. for brand renames, and reports every changed key. Matches
+//! are literal unless `--regex` is given, in which case `$1`-style
+//! capture references work in the replacement.
+//!
+//! With `--push` the changed keys are also pushed to the backend.
+
+use super::ImportCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::{
+    language_dir, language_files, read_translation_file, scan_messages_dir, update_translation_file,
+};
+use crate::core::Translations;
+use anyhow::{Context, Result};
+use clap::Parser;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Replace 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "replace", about = "Find and replace text in translation values")]
+pub struct ReplaceCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只处理指定语言（可重复，默认全部）
+    #[arg(short, long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 要查找的文本（`--regex` 时为正则表达式）
+    #[arg(long, value_name = "TEXT")]
+    pub find: String,
+
+    /// 替换后的文本（`--regex` 时支持 `$1` 引用）
+    #[arg(long, value_name = "TEXT")]
+    pub replace: String,
+
+    /// 将 `--find` 视为正则表达式
+    #[arg(long)]
+    pub regex: bool,
+
+    /// 模拟运行 - 只报告将要修改的键
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// 写入后将修改的键推送到后端
+    #[arg(long, conflicts_with = "dry_run")]
+    pub push: bool,
+}
+
+/// 单个键的修改
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// 语言代码
+    pub lang: String,
+    /// 文件（相对于语言目录）
+    pub file: PathBuf,
+    /// 键名
+    pub key: String,
+    /// 修改前的值
+    pub before: String,
+    /// 修改后的值
+    pub after: String,
+}
+
+/// Replace 结果
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceResult {
+    /// 所有修改（dry-run 时为将要进行的修改）
+    pub replacements: Vec<Replacement>,
+    /// 修改的文件数
+    pub files: usize,
+    /// 是否已推送到后端
+    pub pushed: bool,
+}
+
+impl ReplaceCmd {
+    /// 执行 replace 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 逐个文件查找并替换
+    /// 3. 输出修改报告
+    /// 4. 推送到后端（如指定 `--push`）
+    pub async fn run(&self, ctx: &RunContext) -> Result<ReplaceResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
+        if self.find.is_empty() {
+            return Err(anyhow::anyhow!("--find must not be empty"));
+        }
+        let pattern = if self.regex {
+            Some(Regex::new(&self.find).with_context(|| format!("Invalid regex: {}", self.find))?)
+        } else {
+            None
+        };
+
+        let mut languages = self.languages.clone();
+        if languages.is_empty() {
+            languages = scan_messages_dir(&messages_dir)
+                .await
+                .context("Failed to scan messages directory")?
+                .translations
+                .into_keys()
+                .collect();
+            languages.sort();
+        }
+
+        // 2. 逐个文件查找并替换
+        let mut result = ReplaceResult::default();
+        for lang in &languages {
+            for file in language_files(&messages_dir, lang)? {
+                let path = language_dir(&messages_dir, lang).join(&file);
+                let mut entries: Vec<(String, String)> =
+                    read_translation_file(&messages_dir, &path)?.into_iter().collect();
+                entries.sort();
+
+                let mut changed = HashMap::new();
+                for (key, before) in entries {
+                    let after = match &pattern {
+                        Some(pattern) => pattern.replace_all(&before, self.replace.as_str()).into_owned(),
+                        None => before.replace(&self.find, &self.replace),
+                    };
+                    if after == before {
+                        continue;
+                    }
+                    changed.insert(key.clone(), after.clone());
+                    result.replacements.push(Replacement {
+                        lang: lang.clone(),
+                        file: file.clone(),
+                        key,
+                        before,
+                        after,
+                    });
+                }
+
+                if !changed.is_empty() {
+                    result.files += 1;
+                    if !self.dry_run {
+                        update_translation_file(&messages_dir, &path, &changed)?;
+                    }
+                }
+            }
+        }
+
+        // 3. 输出修改报告
+        if self.dry_run {
+            info!("=== DRY RUN ===");
+        }
+        for replacement in &result.replacements {
+            ctx.reporter.output(format!(
+                "{}/{}: {}: {:?} -> {:?}",
+                replacement.lang,
+                replacement.file.display(),
+                replacement.key,
+                replacement.before,
+                replacement.after
+            ));
+        }
+        info!(
+            "{} {} key(s) in {} file(s)",
+            if self.dry_run { "Would change" } else { "Changed" },
+            result.replacements.len(),
+            result.files
+        );
+
+        // 4. 推送到后端
+        if self.push && !result.replacements.is_empty() {
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+            if !client.check_auth().await? {
+                return Err(Failure::Auth.error(
+                    "API authentication failed. Please check your API key."
+                ));
+            }
+
+            let mut translations = Translations::new();
+            for replacement in &result.replacements {
+                translations
+                    .entry(replacement.lang.clone())
+                    .or_default()
+                    .insert(replacement.key.clone(), replacement.after.clone());
+            }
+            let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+            let import = ImportCmd::default()
+                .import_translations(&*ctx.reporter, &config, &client, &language_mapper, translations)
+                .await?;
+            if import.failed > 0 {
+                return Err(anyhow::anyhow!(
+                    "Local files were updated, but pushing {} key(s) failed: {}",
+                    import.failed,
+                    import.errors.join("; ")
+                ));
+            }
+            result.pushed = true;
+            info!("  - Pushed {} key(s) to the backend", result.replacements.len());
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MessagesDir;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(
+            messages_dir.join("de").join("common.json"),
+            r#"{"footer": {"company": "Fa. ACME", "copy": "© Fa. ACME 2024"}, "ok": "OK"}"#,
+        )
+        .unwrap();
+        std::fs::write(messages_dir.join("en").join("common.yaml"), "footer:\n  company: ACME Inc.\n").unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_replace_literal() {
+        let (temp_dir, config_path) = setup();
+        let file = temp_dir.path().join("messages/de/common.json");
+
+        let cmd = ReplaceCmd {
+            config: Some(config_path.clone()),
+            find: "Fa. ACME".to_string(),
+            replace: "ACME GmbH".to_string(),
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.replacements.len(), 2);
+        assert_eq!(result.replacements[0].key, "footer.company");
+        assert_eq!(read_translation_file(&MessagesDir::default(), &file).unwrap()["footer.company"], "Fa. ACME");
+
+        let cmd = ReplaceCmd { dry_run: false, ..cmd };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.files, 1);
+        assert!(!result.pushed);
+        let values = read_translation_file(&MessagesDir::default(), &file).unwrap();
+        assert_eq!(values["footer.company"], "ACME GmbH");
+        assert_eq!(values["footer.copy"], "© ACME GmbH 2024");
+        assert_eq!(values["ok"], "OK");
+    }
+
+    #[tokio::test]
+    async fn test_replace_regex() {
+        let (temp_dir, config_path) = setup();
+
+        let cmd = ReplaceCmd {
+            config: Some(config_path),
+            languages: vec!["en".to_string()],
+            find: r"ACME (\w+)\.".to_string(),
+            replace: "ACME ${1}orporated".to_string(),
+            regex: true,
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.replacements.len(), 1);
+        assert_eq!(
+            read_translation_file(&MessagesDir::default(), &temp_dir.path().join("messages/en/common.yaml")).unwrap()["footer.company"],
+            "ACME Incorporated"
+        );
+
+        let cmd = ReplaceCmd {
+            find: "(".to_string(),
+            ..cmd
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("Invalid regex"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Rollback command implementation
+//!
+//! Restores the local files saved by `sync` in `.yflow-backup/` before it
+//! wrote them. By default the latest backup is restored; `--to` picks one by
+//! id or by time (the latest backup taken at or before it). Files the sync
+//! created are removed again. Backups are kept, so a rollback can itself be
+//! repeated or followed by another one.
+
+use crate::cli::RunContext;
+use crate::core::backup::Backup;
+use crate::core::timestamp;
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Rollback 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "rollback", about = "Restore local files from a backup taken by `sync`")]
+pub struct RollbackCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 要恢复的备份：备份 ID 或时间点（默认最近的备份）
+    #[arg(long, value_name = "ID|TIMESTAMP")]
+    pub to: Option<String>,
+
+    /// 列出可用的备份，不恢复
+    #[arg(long, conflicts_with = "to")]
+    pub list: bool,
+}
+
+impl RollbackCmd {
+    /// 执行 rollback 命令
+    ///
+    /// # 返回
+    ///
+    /// 恢复的备份；`--list` 时返回 `None`
+    pub async fn run(&self, ctx: &RunContext) -> Result<Option<Backup>> {
+        let config_path = ctx.config_path(&self.config);
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = &config.messages_dir;
+
+        // 1. 列出备份
+        if self.list {
+            let ids = Backup::list(messages_dir)?;
+            if ids.is_empty() {
+                ctx.reporter.output(format!("No backups in {}", Backup::root(messages_dir).display()));
+            }
+            for id in ids.iter().rev() {
+                let backup = Backup::load(messages_dir, id)?;
+                ctx.reporter.output(format!(
+                    "{}  {}  {} ({} file(s))",
+                    backup.id,
+                    timestamp::format_rfc3339(backup.created_at),
+                    backup.command,
+                    backup.files.len()
+                ));
+            }
+            return Ok(None);
+        }
+
+        // 2. 恢复选定的备份
+        let backup = Backup::resolve(messages_dir, self.to.as_deref())?;
+        info!("Restoring backup {} taken by `{}`...", backup.id, backup.command);
+        let (restored, removed) = backup.restore(messages_dir)?;
+        for path in &restored {
+            info!("  - Restored {}", path.display());
+        }
+        for path in &removed {
+            info!("  - Removed {}", path.display());
+        }
+        info!(
+            "Rolled back to {}: {} file(s) restored, {} removed",
+            timestamp::format_rfc3339(backup.created_at),
+            restored.len(),
+            removed.len()
+        );
+        Ok(Some(backup))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_rollback_latest_and_to() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let file = messages_dir.join("en/common.json");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        std::fs::write(&file, "v1").unwrap();
+        let first = Backup::create(&messages_dir, "sync", std::slice::from_ref(&file)).unwrap();
+        std::fs::write(&file, "v2").unwrap();
+        Backup::create(&messages_dir, "sync", std::slice::from_ref(&file)).unwrap();
+        std::fs::write(&file, "v3").unwrap();
+
+        let cmd = RollbackCmd {
+            config: Some(config_path.clone()),
+            ..Default::default()
+        };
+        cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+
+        let cmd = RollbackCmd {
+            config: Some(config_path),
+            to: Some(first.id.clone()),
+            ..Default::default()
+        };
+        assert_eq!(cmd.run(&RunContext::default()).await.unwrap(), Some(first));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+    }
+}
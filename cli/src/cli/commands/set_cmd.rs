@@ -0,0 +1,280 @@
+//! Set command implementation
+//!
+//! Updates the value of a single key in the right local file, keeping the
+//! file's format and nesting, and optionally pushes the change to the
+//! backend right away. Meant for quick hotfixes without opening an editor.
+//!
+//! # Target file
+//!
+//! 1. `--file`, when given
+//! 2. The language's file that already defines the key
+//! 3. The file at the same relative path that defines the key in another
+//!    language
+//! 4. The language's only file
+
+use super::ImportCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::{
+    find_key_file, language_dir, language_files, language_of, read_translation_file, update_translation_file,
+};
+use crate::core::{MessagesDir, Translations};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Set 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "set", about = "Set the value of a key in the local files")]
+pub struct SetCmd {
+    /// 键名（如 user.title）
+    #[arg(value_name = "KEY")]
+    pub key: String,
+
+    /// 新值
+    #[arg(value_name = "VALUE")]
+    pub value: String,
+
+    /// 语言代码
+    #[arg(short, long, value_name = "LANG")]
+    pub lang: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 写入的文件（相对于语言目录，如 common.json）
+    #[arg(long, value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// 写入后立即推送到后端
+    #[arg(long)]
+    pub push: bool,
+}
+
+/// Set 结果
+#[derive(Debug, Clone, Default)]
+pub struct SetResult {
+    /// 被修改的文件
+    pub file: PathBuf,
+    /// 修改前的值（新键为 None）
+    pub previous: Option<String>,
+    /// 是否已推送到后端
+    pub pushed: bool,
+}
+
+impl SetCmd {
+    /// 执行 set 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 确定目标文件
+    /// 3. 写入新值
+    /// 4. 推送到后端（如指定 `--push`）
+    pub async fn run(&self, ctx: &RunContext) -> Result<SetResult> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
+        let lang_dir = language_dir(&messages_dir, &self.lang);
+        if !lang_dir.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Language directory not found: {}",
+                lang_dir.display()
+            ));
+        }
+
+        // 2. 确定目标文件
+        let relative = match &self.file {
+            Some(file) => file.clone(),
+            None => resolve_target_file(&messages_dir, &self.lang, &self.key)?,
+        };
+        let path = lang_dir.join(&relative);
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("File not found: {}", path.display()));
+        }
+
+        // 3. 写入新值
+        let previous = read_translation_file(&messages_dir, &path)?.remove(&self.key);
+        if previous.as_deref() != Some(self.value.as_str()) {
+            update_translation_file(&messages_dir, &path, &HashMap::from([(self.key.clone(), self.value.clone())]))?;
+        }
+
+        // 4. 推送到后端
+        let pushed = if self.push {
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+            if !client.check_auth().await? {
+                return Err(Failure::Auth.error(
+                    "API authentication failed. Please check your API key."
+                ));
+            }
+
+            let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+            let translations: Translations = HashMap::from([(
+                self.lang.clone(),
+                HashMap::from([(self.key.clone(), self.value.clone())]),
+            )]);
+            let result = ImportCmd::default()
+                .import_translations(&*ctx.reporter, &config, &client, &language_mapper, translations)
+                .await?;
+            if result.failed > 0 {
+                return Err(anyhow::anyhow!(
+                    "Local file was updated, but pushing {} failed: {}",
+                    self.key,
+                    result.errors.join("; ")
+                ));
+            }
+            true
+        } else {
+            false
+        };
+
+        let result = SetResult {
+            file: path,
+            previous,
+            pushed,
+        };
+
+        match result.previous.as_deref() {
+            Some(previous) if previous == self.value => {
+                info!("{} already has this value in {}", self.key, result.file.display())
+            }
+            Some(previous) => info!("Updated {} in {} (was: {:?})", self.key, result.file.display(), previous),
+            None => info!("Added {} to {}", self.key, result.file.display()),
+        }
+        if result.pushed {
+            info!("  - Pushed to the backend");
+        }
+
+        Ok(result)
+    }
+}
+
+/// 确定应写入键的文件（相对于语言目录）
+fn resolve_target_file(messages_dir: &MessagesDir, lang: &str, key: &str) -> Result<PathBuf> {
+    // 该语言已定义此键
+    if let Some(file) = find_key_file(messages_dir, lang, key)? {
+        return Ok(file);
+    }
+
+    // 其他语言在同名文件中定义了此键
+    let files = language_files(messages_dir, lang)?;
+    let mut others: Vec<String> = std::fs::read_dir(messages_dir)
+        .with_context(|| format!("Failed to read {}", messages_dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir() && !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| language_of(messages_dir, &entry.path()))
+        .chain(messages_dir.language_dirs.keys().cloned())
+        .filter(|other| other != lang)
+        .collect();
+    others.sort();
+    others.dedup();
+    for other in others {
+        if let Some(file) = find_key_file(messages_dir, &other, key)? {
+            if files.contains(&file) {
+                return Ok(file);
+            }
+        }
+    }
+
+    // 该语言只有一个文件
+    match files.as_slice() {
+        [file] => Ok(file.clone()),
+        [] => Err(anyhow::anyhow!("No translation files found for '{}'", lang)),
+        _ => Err(anyhow::anyhow!(
+            "Cannot tell which file should contain '{}'; use --file",
+            key
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for lang in ["en", "de"] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), r#"{"ok": "OK"}"#).unwrap();
+        }
+        std::fs::write(
+            messages_dir.join("en").join("user.json"),
+            r#"{"user": {"title": "Profile"}}"#,
+        )
+        .unwrap();
+        std::fs::write(messages_dir.join("de").join("user.yaml"), "user:\n  name: Name\n").unwrap();
+        std::fs::write(messages_dir.join("de").join("user.json"), "{}").unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_set_uses_mirrored_file() {
+        let (temp_dir, config_path) = setup();
+
+        let cmd = SetCmd {
+            key: "user.title".to_string(),
+            value: "Profil".to_string(),
+            lang: "de".to_string(),
+            config: Some(config_path),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        assert_eq!(result.file, temp_dir.path().join("messages/de/user.json"));
+        assert_eq!(result.previous, None);
+        assert!(!result.pushed);
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result.file).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!({"user": {"title": "Profil"}}));
+    }
+
+    #[tokio::test]
+    async fn test_set_updates_existing_key_in_place() {
+        let (temp_dir, config_path) = setup();
+
+        let cmd = SetCmd {
+            key: "user.name".to_string(),
+            value: "Vorname".to_string(),
+            lang: "de".to_string(),
+            config: Some(config_path.clone()),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.file, temp_dir.path().join("messages/de/user.yaml"));
+        assert_eq!(result.previous.as_deref(), Some("Name"));
+        assert_eq!(
+            read_translation_file(&MessagesDir::default(), &result.file).unwrap()["user.name"],
+            "Vorname"
+        );
+
+        // 无法判断目标文件时要求 --file
+        let cmd = SetCmd {
+            key: "brand.new".to_string(),
+            value: "Neu".to_string(),
+            lang: "de".to_string(),
+            config: Some(config_path),
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("--file"));
+    }
+}
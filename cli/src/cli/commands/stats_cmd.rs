@@ -0,0 +1,156 @@
+//! Stats command implementation
+//!
+//! Shows how complete every language is compared to the base language, per
+//! top-level namespace (`checkout`: en 100%, de 82%) and in total, from the
+//! local files or, with `--source backend`, from the backend.
+
+use super::SyncCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::coverage::Coverage;
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::scanner::{report_quarantine, scan_messages_dir};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// 统计的翻译来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StatsSource {
+    /// 本地 messages 目录
+    #[default]
+    Local,
+    /// 后端
+    Backend,
+}
+
+/// Stats 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "stats", about = "Show translation coverage per language and namespace")]
+pub struct StatsCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 翻译来源
+    #[arg(long, value_enum, default_value_t = StatsSource::Local)]
+    pub source: StatsSource,
+
+    /// 基准语言（默认：配置的 sourceLanguage，否则为键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub base: Option<String>,
+
+    /// 只统计指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl StatsCmd {
+    /// 执行 stats 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 读取本地或后端翻译
+    /// 3. 统计覆盖率
+    /// 4. 输出
+    pub async fn run(&self, ctx: &RunContext) -> Result<Coverage> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 读取翻译
+        let translations = match self.source {
+            StatsSource::Local => {
+                let scan = scan_messages_dir(&config.messages())
+                    .await
+                    .context("Failed to scan messages directory")?;
+                report_quarantine(&scan.quarantine)?;
+                scan.translations
+            }
+            StatsSource::Backend => {
+                let client = APIClient::from_config(&config)
+                .context("Failed to create API client")?;
+                if !client.check_auth().await? {
+                    return Err(Failure::Auth.error(
+                        "API authentication failed. Please check your API key."
+                    ));
+                }
+                let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+                SyncCmd::fetch_backend_translations(&config, &client, &language_mapper).await?
+            }
+        };
+
+        let base = match self.base.as_ref().or(config.source_language.as_ref()) {
+            Some(base) => base.clone(),
+            None => {
+                let mut languages: Vec<(&String, usize)> =
+                    translations.iter().map(|(lang, keys)| (lang, keys.len())).collect();
+                languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                languages
+                    .first()
+                    .map(|(lang, _)| lang.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No translations found"))?
+            }
+        };
+        if !translations.contains_key(&base) {
+            return Err(anyhow::anyhow!("Base language '{}' not found", base));
+        }
+
+        // 3. 统计覆盖率
+        let coverage = Coverage::compute(&translations, &base, &self.languages, config.separator());
+
+        // 4. 输出
+        if self.json {
+            ctx.reporter.output(serde_json::to_string_pretty(&coverage)?);
+        } else {
+            ctx.reporter.write(&coverage.render_table());
+        }
+        Ok(coverage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_stats_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, content) in [
+            ("en", r#"{"checkout": {"title": "Checkout", "pay": "Pay"}, "home": {"title": "Home"}}"#),
+            ("de", r#"{"checkout": {"title": "Kasse"}, "home": {"title": "Start"}}"#),
+        ] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = StatsCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+        let coverage = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(coverage.base, "en");
+        assert_eq!(coverage.namespaces[0].namespace, "checkout");
+        assert_eq!(coverage.namespaces[0].percent("de"), 50.0);
+        assert_eq!(coverage.namespaces[1].percent("de"), 100.0);
+        assert_eq!(coverage.total.translated["de"], 2);
+    }
+}
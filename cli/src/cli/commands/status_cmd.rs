@@ -0,0 +1,320 @@
+//! Status command implementation
+//!
+//! Compares the local messages directory with the backend and prints a
+//! per-language summary of keys that are local-only, backend-only, or
+//! differ in value. Exits non-zero when any drift exists so CI can gate
+//! merges on it.
+//!
+//! The per-language results are cached in `.yflow-cache/status.json`: while
+//! the local files are unchanged and the backend snapshot is younger than
+//! `--max-age`, repeat runs answer without contacting the backend. The cache
+//! belongs to one backend project and is deleted by every command that
+//! writes to the backend.
+//!
+//! With `sourceLanguage` configured, translations whose source text changed
+//! since they were translated are reported as stale (see
+//! [`crate::core::stale`]); they do not affect the exit status.
+
+use super::SyncCmd;
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::drift::LanguageDrift;
+use crate::core::failure::Failure;
+use crate::core::language_mapping::LanguageMapper;
+use crate::core::{I18nConfig, Translations};
+use crate::core::scanner::scan_messages_dir;
+use crate::core::stale::{self, StaleKey};
+use crate::core::credentials;
+use crate::core::status_cache::StatusCache;
+use crate::core::timestamp;
+use crate::ui::reporter::Reporter;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::info;
+
+/// 每类差异最多列出的键数
+const KEY_PREVIEW_LIMIT: usize = 10;
+
+/// Status 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "status", about = "Show drift between local files and the backend")]
+pub struct StatusCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只检查指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 列出有差异的键
+    #[arg(long)]
+    pub keys: bool,
+
+    /// 本地文件未变化时，复用不超过此时长的后端快照（如 30s、5m）
+    #[arg(long, value_name = "DURATION", default_value = "30s", value_parser = crate::core::deadline::parse_duration)]
+    pub max_age: Duration,
+
+    /// 忽略状态缓存，总是从后端获取
+    #[arg(long)]
+    pub no_cache: bool,
+}
+
+impl StatusCmd {
+    /// 执行 status 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录，记录源文本变化
+    /// 3. 本地未变化且缓存未过期时直接使用缓存的差异
+    /// 4. 否则获取后端翻译，计算差异并更新缓存
+    /// 5. 输出差异
+    ///
+    /// # Errors
+    ///
+    /// 存在差异时返回错误（非零退出码）。
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<LanguageDrift>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 扫描本地 messages 目录
+        let local = scan_messages_dir(&config.messages())
+            .await
+            .context("Failed to scan messages directory")?
+            .translations;
+
+        // 2.1 找出源文本变化后未更新的翻译
+        let mut stale = match &config.source_language {
+            Some(source) => stale::track(&config.messages_dir, source, &local)?,
+            None => Vec::new(),
+        };
+
+        // 3. 使用缓存
+        let now = timestamp::now();
+        let mut cache = if self.no_cache {
+            StatusCache::default()
+        } else {
+            StatusCache::load(&config.messages_dir)
+        };
+        let backend_id = credentials::account(&config.api_url, config.project_id);
+        let mut drift = match cache.fresh(&backend_id, &local, config.comparator, now, self.max_age) {
+            Some(drift) => {
+                info!(
+                    "Using cached backend snapshot from {}s ago (--no-cache to refresh)",
+                    now.saturating_sub(cache.fetched_at)
+                );
+                drift
+            }
+            None => {
+                // 4. 获取后端翻译并更新缓存
+                let backend = self.fetch_backend(&config).await?;
+                let drift = cache.refresh(&backend_id, &local, &backend, config.comparator, now);
+                cache.save(&config.messages_dir)?;
+                drift
+            }
+        };
+
+        if !self.languages.is_empty() {
+            drift.retain(|lang| self.languages.contains(&lang.lang));
+            stale.retain(|entry| self.languages.contains(&entry.lang));
+        }
+
+        // 5. 输出差异
+        self.print_status(&*ctx.reporter, &drift, &stale);
+
+        let drifted = drift.iter().filter(|d| d.has_drift()).count();
+        if drifted > 0 {
+            return Err(Failure::Drift.error(format!(
+                "Drift detected in {} language(s); run 'yflow sync' or 'yflow import' to reconcile",
+                drifted
+            )));
+        }
+
+        Ok(drift)
+    }
+
+    /// 验证认证并获取后端翻译（按本地语言代码）
+    async fn fetch_backend(&self, config: &I18nConfig) -> Result<Translations> {
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+        let client = APIClient::from_config(config)
+        .context("Failed to create API client")?;
+        if !client.check_auth().await? {
+            return Err(Failure::Auth.error(
+                "API authentication failed. Please check your API key."
+            ));
+        }
+
+        info!("Fetching translations from backend...");
+        SyncCmd::fetch_backend_translations(config, &client, &language_mapper).await
+    }
+
+    /// 输出每个语言的差异
+    fn print_status(&self, reporter: &dyn Reporter, drift: &[LanguageDrift], stale: &[StaleKey]) {
+        if drift.is_empty() {
+            reporter.output("No translations found locally or on the backend.");
+            return;
+        }
+
+        reporter.output(format!(
+            "{:<12} {:>10} {:>12} {:>8} {:>8}",
+            "LANGUAGE", "LOCAL-ONLY", "BACKEND-ONLY", "DIFFER", "IN-SYNC"
+        ));
+        for lang in drift {
+            reporter.output(format!(
+                "{:<12} {:>10} {:>12} {:>8} {:>8}",
+                lang.lang,
+                lang.local_only.len(),
+                lang.backend_only.len(),
+                lang.differ.len(),
+                lang.in_sync
+            ));
+        }
+
+        if self.keys {
+            for lang in drift {
+                let stale_keys: Vec<String> = stale
+                    .iter()
+                    .filter(|entry| entry.lang == lang.lang)
+                    .map(|entry| entry.key.clone())
+                    .collect();
+                if !lang.has_drift() && stale_keys.is_empty() {
+                    continue;
+                }
+                reporter.output("");
+                reporter.output(format!("{}:", lang.lang));
+                print_keys(reporter, "local-only", &lang.local_only);
+                print_keys(reporter, "backend-only", &lang.backend_only);
+                print_keys(reporter, "differ", &lang.differ);
+                print_keys(reporter, "stale", &stale_keys);
+            }
+        }
+
+        reporter.output("");
+        if drift.iter().any(|d| d.has_drift()) {
+            reporter.warning("Local files and backend have drifted.");
+        } else {
+            reporter.success("Local files and backend are in sync.");
+        }
+        if !stale.is_empty() {
+            reporter.warning(format!(
+                "{} translation(s) are stale: their source text changed since they were translated.",
+                stale.len()
+            ));
+        }
+    }
+}
+
+/// 输出一类差异的键（最多 [`KEY_PREVIEW_LIMIT`] 个）
+fn print_keys(reporter: &dyn Reporter, label: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    let preview: Vec<&str> = keys.iter().take(KEY_PREVIEW_LIMIT).map(|k| k.as_str()).collect();
+    let more = if keys.len() > KEY_PREVIEW_LIMIT {
+        format!(" ... and {} more", keys.len() - KEY_PREVIEW_LIMIT)
+    } else {
+        String::new()
+    };
+    reporter.output(format!("  {} ({}): {}{}", label, keys.len(), preview.join(", "), more));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    /// 启动返回固定后端翻译的模拟服务（认证 + 获取翻译两次请求）
+    fn mock_backend(translations_body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for body in [r#"{"data": {}}"#, translations_body] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        base_url
+    }
+
+    fn setup(base_url: &str, common: &str) -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en").join("common.json"), common).unwrap();
+
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+        (temp_dir, config_path)
+    }
+
+    #[tokio::test]
+    async fn test_status_in_sync() {
+        let base_url = mock_backend(r#"{"data": {"title": {"en": "Title"}}}"#);
+        let (_temp_dir, config_path) = setup(&base_url, r#"{"title": "Title"}"#);
+
+        let cmd = StatusCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+        let drift = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(drift.len(), 1);
+        assert!(!drift[0].has_drift());
+    }
+
+    #[tokio::test]
+    async fn test_status_uses_cache_until_local_change() {
+        // 模拟后端只响应一次运行（认证 + 获取翻译）
+        let base_url = mock_backend(r#"{"data": {"title": {"en": "Title"}}}"#);
+        let (temp_dir, config_path) = setup(&base_url, r#"{"title": "Title"}"#);
+
+        let cmd = StatusCmd {
+            config: Some(config_path),
+            max_age: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert!(!cmd.run(&RunContext::default()).await.unwrap()[0].has_drift());
+        // 第二次运行不访问后端
+        assert!(!cmd.run(&RunContext::default()).await.unwrap()[0].has_drift());
+
+        // 本地修改后需要重新获取（后端已不可用）
+        std::fs::write(temp_dir.path().join("messages/en/common.json"), r#"{"title": "New"}"#).unwrap();
+        assert!(cmd.run(&RunContext::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_fails_on_drift() {
+        let base_url = mock_backend(r#"{"data": {"title": {"en": "Remote"}, "body": {"en": "Body"}}}"#);
+        let (_temp_dir, config_path) = setup(&base_url, r#"{"title": "Title"}"#);
+
+        let cmd = StatusCmd {
+            config: Some(config_path),
+            keys: true,
+            ..Default::default()
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("Drift detected in 1 language(s)"));
+    }
+}
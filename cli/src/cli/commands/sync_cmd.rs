@@ -7,28 +7,127 @@
 //!
 //! - Downloads translations from backend
 //! - Preserves original file structure
-//! - Supports force overwrite mode
-//! - Dry-run mode for previewing changes
+//! - Supports force overwrite mode, for every key (`--force`) or only keys
+//!   matching `--force-keys` patterns
+//! - Computes a per-file plan before writing anything: `--dry-run` prints it
+//!   as a table of every key with its old and new value (or as JSON with
+//!   `--output json`), `--check` fails when it is not empty, `--patch` prints
+//!   it as a unified diff
 //! - Progress bar display for file writing
 //! - Language code mapping support
-
+//! - Alias languages (`aliases` in the config) only receive the keys that
+//!   differ from their base language
+//! - Brand overlays (`overlays` in the config): keys an overlay defines are
+//!   compared with and written back to that overlay, the rest to the base messages
+//! - Merging keys routed to multiple backend projects via `routes`
+//! - Three-way merge against the last synced state: local-only edits are kept,
+//!   conflicts are reported in `.yflow-conflicts.json` and resolved with
+//!   `--prefer local|remote|interactive`; the interactive menu shows both
+//!   values side by side, offers "apply to all" shortcuts and is skipped when
+//!   stdout is not a terminal (conflicts stay unresolved, local values kept)
+//! - Resumable chunked downloads (`--chunked`) for very large projects
+//! - Selecting languages with `--locale` / `--exclude-locale`
+//! - Selecting keys with `--key-prefix` / `--key-glob`
+//! - Quarantining unparseable local files, reported at the end (or `--fail-fast`)
+//! - Skipping languages unchanged on both sides since the last sync, using `.yflow-cache`
+//! - Point-in-time sync (`--as-of`) from backends that keep translation history,
+//!   for reproducing the locale state of a past release
+//! - Offline sync (`--offline`): replays the last successful fetch kept in
+//!   `.yflow-cache/backend.bin` instead of contacting the backend
+//! - Pruning (`--prune`): local keys deleted on the backend are removed from
+//!   their files, leaving the rest of each file as it is; every removed key
+//!   is listed in the `--dry-run` plan first. Without a sync snapshot for a
+//!   language nothing is pruned there unless `--force`
+//! - Large change guard: overwriting (or pruning) more than `maxChangePercent` of the local
+//!   keys asks for confirmation (`--yes` to skip), and fails when not interactive
+//! - Backing up every file it is about to change into `.yflow-backup/<timestamp>/`
+//!   (`--no-backup` to skip); `yflow rollback` restores the previous state
+//! - Committing the written files to git (`--commit <message>`, `--sign`), so
+//!   scheduled bots produce single-purpose commits
+//! - Automated update PRs: `--branch-per-run` commits on a new
+//!   `yflow/sync-<timestamp>` branch, `--push-remote` pushes it and prints the
+//!   pull request URL (or runs the configured `prCommand`)
+//! - A per-language table of added/updated/skipped keys and the elapsed time
+//!   at the end of the run (`--summary-json` writes it as JSON)
+
+use crate::cli::RunContext;
+use crate::api::capabilities::Feature;
 use crate::api::client::APIClient;
-use crate::core::config::load_config;
+use crate::core::backup::Backup;
+use crate::core::change_guard::{ChangeMagnitude, DEFAULT_MAX_CHANGE_PERCENT};
+use crate::core::conflicts::{
+    removed_on_backend, three_way_merge, write_conflict_report, Conflict, MergeOutcome, PruneOutcome, Resolution,
+    SyncState, REPORT_FILENAME,
+};
+use crate::core::failure::Failure;
+use crate::core::{deadline, timestamp};
+use crate::core::download::{assemble, plan_chunks, Chunk, ChunkCache};
+use crate::core::fetch_cache::FetchCache;
+use crate::core::git::{
+    commit_files, create_branch, current_branch, discard_branch, pull_request_url, push_branch, remote_url,
+    run_pr_command,
+};
 use crate::core::language_mapping::LanguageMapper;
-use crate::core::scanner::{scan_messages_dir, write_translations_with_structure};
-use crate::core::{ScanResult, SyncResult, Translations};
-use crate::ui::progress::MultiProgressManager;
+use crate::core::overlays::{self, scan_overlays, Overlay};
+use crate::core::routing::Router;
+use crate::core::scanner::{
+    plan_translations_with_removals, report_quarantine, scan_messages_dir, scan_messages_dir_with,
+    verify_written_files,
+};
+use crate::core::run_summary::RunSummary;
+use crate::core::{
+    DeltaCache, I18nConfig, KeyFilter, LocaleFilter, MessagesDir, ProgressEvent, ScanResult, SyncPlan, SyncResult,
+    Translations,
+};
+use crate::ui::progress::ProgressGuard;
+use crate::ui::prompt;
+use crate::ui::reporter::Reporter;
+use crate::ui::spinner::Spinner;
+use crate::ui::summary;
 use anyhow::{Context, Result};
-use clap::Parser;
-use std::path::PathBuf;
-use tracing::info;
+use clap::{ArgGroup, Parser, ValueEnum};
+use dialoguer::console::{measure_text_width, pad_str, truncate_str, Alignment};
+use std::collections::{BTreeMap, HashMap};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// `--branch-per-run` 创建的分支名前缀
+const BRANCH_PREFIX: &str = "yflow/sync-";
+
+/// `--branch-per-run` 未指定 `--commit` 时的提交信息
+const DEFAULT_COMMIT_MESSAGE: &str = "chore: sync translations";
+
+/// 冲突解决策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Prefer {
+    /// 采用后端的值
+    #[default]
+    Remote,
+    /// 保留本地的值
+    Local,
+    /// 逐个询问
+    Interactive,
+}
+
+/// 同步计划的输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PlanOutput {
+    /// 表格（预览时输出）
+    #[default]
+    Table,
+    /// JSON（输出到标准输出，实际同步时为已执行的计划）
+    Json,
+}
 
 /// 同步命令参数
 ///
 /// 将后端翻译同步到本地 messages 目录。
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Default)]
 #[command(name = "sync")]
 #[command(about = "Sync translations from backend to local messages directory", long_about = None)]
+#[command(group(ArgGroup::new("git_commit").args(["commit", "branch_per_run"]).multiple(true)))]
 pub struct SyncCmd {
     /// 配置文件路径
     #[arg(short, long, value_name = "PATH")]
@@ -38,9 +137,110 @@ pub struct SyncCmd {
     #[arg(long)]
     pub dry_run: bool,
 
-    /// 强制覆盖所有现有翻译
+    /// 检查本地文件是否与后端一致，有需要写入的修改时以非零状态退出（不写入）
+    #[arg(long)]
+    pub check: bool,
+
+    /// 以统一差异格式（unified diff）输出将要进行的修改（不写入）
+    #[arg(long)]
+    pub patch: bool,
+
+    /// 同步计划的输出格式
+    #[arg(long, value_enum, default_value_t = PlanOutput::Table, conflicts_with = "patch")]
+    pub output: PlanOutput,
+
+    /// 强制覆盖所有现有翻译（包括只在本地修改过的键）
     #[arg(long)]
     pub force: bool,
+
+    /// 只强制覆盖匹配的键（逗号分隔的前缀或 glob，如 `legal.*,emails.*`），其余键按非强制规则合并
+    #[arg(long, value_name = "PATTERNS", value_delimiter = ',', conflicts_with = "force")]
+    pub force_keys: Vec<String>,
+
+    /// 从本地文件中删除后端已不存在的键（--dry-run 先列出每个要删除的键）
+    ///
+    /// 上次同步后本地新增、尚未导入的键，以及从未同步过（没有快照）的语言中的键会保留，
+    /// `--force` 时也删除。
+    #[arg(long, conflicts_with = "as_of")]
+    pub prune: bool,
+
+    /// 冲突报告输出路径（默认: <messagesDir>/.yflow-conflicts.json）
+    #[arg(long, value_name = "PATH")]
+    pub conflicts_report: Option<PathBuf>,
+
+    /// 本地和后端都修改过的键如何解决
+    #[arg(long, value_enum, default_value_t = Prefer::Remote)]
+    pub prefer: Prefer,
+
+    /// 分块下载（按语言、命名空间），中断后重新运行可从缓存继续
+    #[arg(long)]
+    pub chunked: bool,
+
+    /// 只同步指定语言（本地语言代码，可重复）
+    #[arg(long = "locale", value_name = "LOCALE")]
+    pub locales: Vec<String>,
+
+    /// 排除指定语言（本地语言代码，可重复）
+    #[arg(long = "exclude-locale", value_name = "LOCALE")]
+    pub exclude_locales: Vec<String>,
+
+    /// 只处理以该前缀开头的键（可重复）
+    #[arg(long = "key-prefix", value_name = "PREFIX")]
+    pub key_prefixes: Vec<String>,
+
+    /// 只处理匹配该 glob 的键，如 `checkout.*`（可重复）
+    #[arg(long = "key-glob", value_name = "GLOB")]
+    pub key_globs: Vec<String>,
+
+    /// 遇到第一个无法解析的文件时立即中止（默认跳过并在结束时报告）
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// 忽略 .yflow-cache，重新写入所有语言（默认跳过后端和本地文件都未变化的语言）
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// 同步某个时间点的翻译（如 `2024-05-01T00:00:00Z`，需要后端保存历史）
+    ///
+    /// 不检测冲突，也不更新同步快照和 .yflow-cache。
+    #[arg(long, value_name = "TIMESTAMP", conflicts_with = "chunked")]
+    pub as_of: Option<String>,
+
+    /// 不访问后端，重放 .yflow-cache 中上次成功获取的翻译
+    #[arg(long, conflicts_with_all = ["as_of", "chunked"])]
+    pub offline: bool,
+
+    /// 允许覆盖的本地键比例上限（百分比，覆盖配置中的 maxChangePercent）
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u32).range(0..=100))]
+    pub max_change_percent: Option<u32>,
+
+    /// 超过修改比例上限时不再确认，直接写入
+    #[arg(long)]
+    pub yes: bool,
+
+    /// 写入前不备份将被修改的文件（默认备份到 <messagesDir>/.yflow-backup/）
+    #[arg(long)]
+    pub no_backup: bool,
+
+    /// 同步后将写入的文件提交到 git，使用此提交信息（只提交这些文件）
+    #[arg(long, value_name = "MESSAGE", conflicts_with_all = ["dry_run", "check", "patch"])]
+    pub commit: Option<String>,
+
+    /// 签名 git 提交（`git commit -S`）
+    #[arg(long, requires = "git_commit")]
+    pub sign: bool,
+
+    /// 在新分支 `yflow/sync-<时间戳>` 上提交写入的文件（提交信息见 --commit）
+    #[arg(long, conflicts_with_all = ["dry_run", "check", "patch"])]
+    pub branch_per_run: bool,
+
+    /// 提交后将分支推送到此远程仓库，并输出创建拉取请求的地址（或运行配置的 prCommand）
+    #[arg(long, value_name = "REMOTE", requires = "branch_per_run")]
+    pub push_remote: Option<String>,
+
+    /// 将各语言的同步结果以 JSON 写入文件
+    #[arg(long, value_name = "PATH")]
+    pub summary_json: Option<PathBuf>,
 }
 
 impl SyncCmd {
@@ -53,69 +253,118 @@ impl SyncCmd {
     /// 3. 验证认证
     /// 4. 从后端获取翻译
     /// 5. 扫描本地 messages 目录
-    /// 6. 执行同步（或显示差异）
+    /// 6. 计算同步计划，执行（或预览、检查）
+    /// 7. 输出各语言的汇总表（`--summary-json` 时写入 JSON）
     ///
     /// # 参数
     ///
-    /// * `global_config` - 可选的父级配置文件路径
-    pub async fn run(&self, global_config: Option<PathBuf>) -> Result<SyncResult> {
+    /// * `ctx` - 本次运行的上下文（全局 `--config` 和命令行覆盖）
+    pub async fn run(&self, ctx: &RunContext) -> Result<SyncResult> {
+        let started = Instant::now();
+
         // 合并配置选项
-        let config_path = self.config.clone().or(global_config);
+        let config_path = ctx.config_path(&self.config);
 
         info!("Starting sync from backend...");
 
         // 1. 加载配置
         info!("Loading configuration...");
-        let config = load_config(config_path)?;
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
         info!("  - Messages directory: {}", config.messages_dir.display());
         info!("  - Project ID: {}", config.project_id);
         info!("  - API URL: {}", config.api_url);
+        let as_of = self.as_of.as_deref().map(timestamp::parse).transpose()?;
+        if let Some(as_of) = as_of {
+            info!("  - As of: {}", timestamp::format_rfc3339(as_of));
+        }
 
         // 1.1 初始化语言映射器
-        let language_mapper = LanguageMapper::new(Some(config.language_mapping));
+        let language_mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
         if language_mapper.needs_mapping() {
             info!("  - {}", language_mapper.get_description());
         }
 
-        // 2. 创建 API 客户端
-        let client = APIClient::new(
-            config.api_url.clone(),
-            config.api_key.clone(),
-            config.project_id,
-        )
-        .context("Failed to create API client")?;
+        // 2. 创建 API 客户端（离线时不访问后端）
+        let client = if self.offline {
+            None
+        } else {
+            let client = APIClient::from_config(&config)
+            .context("Failed to create API client")?;
+
+            // 3. 验证认证
+            info!("Verifying API authentication...");
+            let mut spinner = Spinner::new("Checking authentication...");
+            spinner.start();
+            let authenticated = client.check_auth().await?;
+            spinner.stop();
+            if !authenticated {
+                return Err(Failure::Auth.error(
+                    "API authentication failed. Please check your API key."
+                ));
+            }
+            info!("  - Authentication successful");
+            Some(client)
+        };
 
-        // 3. 验证认证
-        info!("Verifying API authentication...");
-        if !client.check_auth()? {
-            return Err(anyhow::anyhow!(
-                "API authentication failed. Please check your API key."
-            ));
+        // 4. 从后端获取翻译（离线时读取上次获取的缓存）
+        let locale_filter = LocaleFilter::new(&self.locales, &self.exclude_locales);
+        if locale_filter.is_active() {
+            info!("  - {}", locale_filter.description());
         }
-        info!("  - Authentication successful");
-
-        // 4. 从后端获取翻译
-        info!("Fetching translations from backend...");
-        let backend_translations = client.get_translations()?;
-
-        let total_keys: usize = backend_translations.values().map(|v| v.len()).sum();
-        let languages: Vec<&str> = backend_translations.keys().map(|s| s.as_str()).collect();
-        info!(
-            "  - Fetched {} keys from {} languages: {}",
-            total_keys,
-            languages.len(),
-            languages.join(", ")
-        );
+        let key_filter = KeyFilter::new(&self.key_prefixes, &self.key_globs, config.separator())?;
+        if key_filter.is_active() {
+            info!("  - {}", key_filter.description());
+        }
+        let force_filter = self.force_filter(config.separator())?;
+        if force_filter.is_active() {
+            info!("  - Overwriting keys matching: {}", self.force_keys.join(", "));
+        }
+        let local_translations = match &client {
+            None => Self::load_cached_translations(&config, &locale_filter)?,
+            Some(client) => {
+                info!("Fetching translations from backend...");
+                // 分块下载显示每个语言的进度条，其余情况显示 spinner
+                let mut spinner = Spinner::new("Fetching translations...");
+                if !self.chunked {
+                    spinner.start();
+                }
+                let translations = if let Some(as_of) = as_of {
+                    let translations =
+                        Self::fetch_translations_as_of(&config, client, &language_mapper, as_of).await?;
+                    locale_filter.apply(translations)
+                } else if self.chunked {
+                    Self::fetch_chunked_translations(&config, client, &language_mapper, &locale_filter)
+                        .await?
+                } else if locale_filter.is_active() {
+                    Self::fetch_selected_translations(&config, client, &language_mapper, &locale_filter)
+                        .await?
+                } else {
+                    Self::fetch_backend_translations(&config, client, &language_mapper).await?
+                };
+                spinner.stop();
+                // 4.1 保存本次获取的翻译，供 --offline 重放（时间点同步不是最新状态）
+                if as_of.is_none() {
+                    if let Err(e) = FetchCache::record(
+                        &config.messages_dir,
+                        config.project_id,
+                        &translations,
+                        !locale_filter.is_active(),
+                    ) {
+                        warn!("Failed to cache backend translations for --offline: {:#}", e);
+                    }
+                }
+                translations
+            }
+        };
+        let local_translations = key_filter.apply(local_translations);
 
-        if total_keys == 0 {
+        let local_key_count: usize = local_translations.values().map(|v| v.len()).sum();
+        if local_key_count == 0 {
             info!("No translations found on backend, skipping sync.");
             return Ok(SyncResult::default());
         }
 
-        // 4.1 应用反向语言映射（后端代码 -> 本地代码）
-        let local_translations = language_mapper.reverse_translations(backend_translations);
-
-        let local_key_count: usize = local_translations.values().map(|v| v.len()).sum();
         let lang_list: Vec<String> = local_translations.keys().cloned().collect();
         info!(
             "  - After mapping: {} keys, languages: {}",
@@ -124,161 +373,886 @@ impl SyncCmd {
         );
 
         // 5. 扫描本地 messages 目录
-        let local_scan_result = match scan_messages_dir(&config.messages_dir).await {
+        let mut local_scan_result = match scan_messages_dir_with(&messages_dir, self.fail_fast).await {
             Ok(result) => result,
+            Err(e) if self.fail_fast && config.messages_dir.exists() => return Err(e),
             Err(_) => {
                 // 如果目录不存在，创建空结构
                 info!("Local messages directory not found, creating empty structure.");
-                crate::core::ScanResult {
-                    translations: Translations::new(),
-                    files: Vec::new(),
-                    key_count: 0,
-                }
+                crate::core::ScanResult::default()
             }
         };
+        // 品牌覆盖层合并在基础翻译之上，写入时再按键所在的层拆分
+        let overlays = scan_overlays(&config.overlay_dirs(), self.fail_fast).await?;
+        overlays::apply(&mut local_scan_result, &overlays);
+        // 5.1 未选中的语言和键不参与比较和写入
+        if locale_filter.is_active() || key_filter.is_active() {
+            local_scan_result.translations =
+                key_filter.apply(locale_filter.apply(local_scan_result.translations));
+            local_scan_result.key_count =
+                local_scan_result.translations.values().map(|t| t.len()).sum();
+        }
         info!(
             "  - Local files: {}, local keys: {}",
             local_scan_result.files.len(),
             local_scan_result.key_count
         );
 
-        // 6. 执行同步或显示差异
-        if self.dry_run {
-            self.show_sync_diff(&local_translations, &local_scan_result.translations)?;
-            return Ok(SyncResult::default());
+        // 5.2 与上次同步的快照进行三方合并，检测冲突（时间点同步有意回退，不合并）
+        let (incoming, conflicts) = if as_of.is_some() {
+            (local_translations.clone(), 0)
+        } else {
+            let outcome = self.merge_with_base(
+                &config.messages_dir,
+                &local_scan_result.translations,
+                &local_translations,
+                &force_filter,
+            )?;
+            (outcome.incoming, outcome.conflicts.len())
+        };
+
+        // 5.3 找出后端已删除的本地键（--prune）
+        let removals = if self.prune {
+            self.find_removals(&config.messages_dir, &local_scan_result.translations, &local_translations)
+        } else {
+            BTreeMap::new()
+        };
+
+        // 6. 跳过自上次同步后后端值和本地文件都未变化的语言
+        let router = Router::from_config(&config);
+        let mut cache = if self.no_cache || as_of.is_some() {
+            DeltaCache::default()
+        } else {
+            DeltaCache::load(&config.messages_dir).unwrap_or_else(|e| {
+                info!("  - Ignoring unreadable delta cache: {}", e);
+                DeltaCache::default()
+            })
+        };
+        let mut pending = Translations::new();
+        let mut unchanged = Vec::new();
+        for (lang, keys) in &local_translations {
+            if cache.language_unchanged(&router, lang, keys) && cache.files_unchanged(&messages_dir, lang)? {
+                unchanged.push(lang.clone());
+            } else if let Some(keys) = incoming.get(lang) {
+                pending.insert(lang.clone(), keys.clone());
+            }
+        }
+        if !unchanged.is_empty() {
+            unchanged.sort();
+            info!(
+                "  - Unchanged since last sync: {} (use --no-cache to rewrite)",
+                unchanged.join(", ")
+            );
         }
 
-        self.execute_sync(
-            &config.messages_dir,
+        // 6.1 计算同步计划（写入前确定每个文件的修改，覆盖层中的键写回覆盖层）
+        let (base_pending, layers) = overlays::split(&pending, &overlays);
+        let mut plan = plan_translations_with_removals(
+            &messages_dir,
             &local_scan_result.files,
-            &local_translations,
-            &local_scan_result,
+            &base_pending,
+            &removals,
+            self.force,
         )
         .await
+        .context("Failed to plan sync")?;
+        for (overlay, layer) in overlays.iter().zip(&layers) {
+            let overlay_plan =
+                plan_translations_with_removals(&overlay.dir, &overlay.scan.files, layer, &removals, self.force)
+                    .await
+                    .with_context(|| format!("Failed to plan sync for overlay {}", overlay.dir.display()))?;
+            plan.languages.extend(overlay_plan.languages);
+        }
+
+        // 6.2 按最终计划统计将被覆盖或删除的本地键
+        let magnitude = ChangeMagnitude::from_plan(&local_scan_result.translations, &plan);
+        let max_change_percent = self
+            .max_change_percent
+            .or(config.max_change_percent)
+            .unwrap_or(DEFAULT_MAX_CHANGE_PERCENT);
+
+        // 6.3 只预览时输出计划（--dry-run、--patch）或检查计划是否为空（--check）
+        if self.is_preview() {
+            if self.patch {
+                let cwd = std::env::current_dir().unwrap_or_default();
+                ctx.reporter.write(&plan.render_patch(&cwd));
+            } else if self.output == PlanOutput::Json {
+                ctx.reporter.write(&plan.render_json(&config.messages_dir, None)?);
+            } else {
+                self.show_sync_plan(&*ctx.reporter, &config.messages_dir, &plan);
+            }
+            if magnitude.exceeds(max_change_percent) {
+                warn!(
+                    "Sync would overwrite {}, above the {}% limit; a real run needs confirmation or --yes",
+                    magnitude.describe(),
+                    max_change_percent
+                );
+            }
+            report_quarantine(&local_scan_result.quarantine)?;
+            if self.check && !plan.is_empty() {
+                return Err(out_of_sync_error(&plan));
+            }
+            return Ok(SyncResult {
+                conflicts,
+                ..Default::default()
+            });
+        }
+
+        // 6.4 修改规模过大时确认（非交互运行直接失败）
+        self.confirm_large_change(&magnitude, max_change_percent)?;
+
+        // 写入前检查截止时间：超时则不写入任何文件，保持本地状态完整
+        deadline::check("writing files; no local files were changed")?;
+
+        let mut result = self
+            .execute_sync(&messages_dir, &plan, &pending, &local_scan_result, &overlays)
+            .await?;
+        result.unchanged = unchanged.len();
+
+        result.conflicts = conflicts;
+
+        // 汇总各语言的结果（--output json 时包含在 JSON 报告中，不输出表格）
+        let run_summary = RunSummary::new(&result.languages, started.elapsed());
+        if self.output != PlanOutput::Json {
+            summary::print(&*ctx.reporter, &run_summary);
+        }
+        if let Some(path) = &self.summary_json {
+            run_summary.save(path)?;
+            info!("Sync summary written to {}", path.display());
+        }
+
+        // 7. 校验失败时以非零状态退出
+        if !result.verification_failures.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Verification failed for {} written file(s):\n{}",
+                result.verification_failures.len(),
+                result.verification_failures.join("\n")
+            ));
+        }
+
+        if self.output == PlanOutput::Json {
+            ctx.reporter.write(&plan.render_json(&config.messages_dir, Some(&run_summary))?);
+        }
+
+        // 7.1 提交写入的文件（--branch-per-run 时提交到新分支并推送）
+        if self.branch_per_run {
+            result.commit = self.commit_on_branch(&*ctx.reporter, &config, &plan)?;
+        } else if let Some(message) = &self.commit {
+            let written: Vec<PathBuf> = plan.changes().map(|change| change.path.clone()).collect();
+            result.commit = commit_files(&config.messages_dir, &written, message, config.author.as_ref(), self.sign)
+                .context("Failed to commit synced files")?;
+            match &result.commit {
+                Some(commit) => info!("Committed {} file(s) as {}", written.len(), commit),
+                None => info!("Nothing to commit"),
+            }
+        }
+
+        // 时间点同步不是新的同步基线：保留快照和增量缓存
+        if as_of.is_some() {
+            report_quarantine(&local_scan_result.quarantine)?;
+            return Ok(result);
+        }
+
+        // 8. 保存本次同步的快照，作为下次三方比较的基线
+        //    （只同步了部分语言或键时，保留其余部分的旧快照）
+        let mut state = if locale_filter.is_active() || key_filter.is_active() {
+            SyncState::load(&config.messages_dir).unwrap_or_default()
+        } else {
+            SyncState::default()
+        };
+        for (lang, keys) in state.translations.iter_mut() {
+            if locale_filter.matches(lang) {
+                keys.retain(|key, _| !key_filter.matches(key));
+            }
+        }
+        for (lang, keys) in &local_translations {
+            state.translations.entry(lang.clone()).or_default().extend(keys.clone());
+        }
+        state.translations.retain(|_, keys| !keys.is_empty());
+        state.save(&config.messages_dir)?;
+
+        // 8.1 记录本次同步的键值和文件哈希，供下次增量判断
+        cache.record(&router, &local_translations, !key_filter.is_active());
+        for lang in local_translations.keys() {
+            cache.record_files(&messages_dir, lang)?;
+        }
+        cache.save(&config.messages_dir)?;
+
+        // 9. 报告被隔离的本地文件（存在时以非零状态退出）
+        report_quarantine(&local_scan_result.quarantine)?;
+
+        Ok(result)
+    }
+
+    /// 在新分支上提交写入的文件，推送并给出拉取请求
+    ///
+    /// 从当前分支创建 `yflow/sync-<时间戳>` 并留在该分支上；没有可提交的
+    /// 内容时切换回原分支并删除新分支。指定 `--push-remote` 时推送分支，
+    /// 然后运行配置的 `prCommand`，未配置时输出创建拉取请求的地址。
+    ///
+    /// # Returns
+    ///
+    /// 新提交的哈希；没有可提交的内容时返回 `None`
+    fn commit_on_branch(
+        &self,
+        reporter: &dyn Reporter,
+        config: &I18nConfig,
+        plan: &SyncPlan,
+    ) -> Result<Option<String>> {
+        let dir = &config.messages_dir;
+        let written: Vec<PathBuf> = plan.changes().map(|change| change.path.clone()).collect();
+        if written.is_empty() {
+            info!("Nothing to commit; no branch created");
+            return Ok(None);
+        }
+
+        // 1. 创建分支并提交
+        let base = current_branch(dir)?;
+        let branch = format!("{}{}", BRANCH_PREFIX, timestamp::format_compact(timestamp::now()));
+        create_branch(dir, &branch)?;
+        let message = self.commit.as_deref().unwrap_or(DEFAULT_COMMIT_MESSAGE);
+        let commit = match commit_files(dir, &written, message, config.author.as_ref(), self.sign) {
+            Ok(Some(commit)) => commit,
+            Ok(None) => {
+                discard_branch(dir, &branch, &base)?;
+                info!("Nothing to commit; no branch created");
+                return Ok(None);
+            }
+            Err(e) => {
+                let _ = discard_branch(dir, &branch, &base);
+                return Err(e.context("Failed to commit synced files"));
+            }
+        };
+        info!("Committed {} file(s) as {} on branch {}", written.len(), commit, branch);
+
+        // 2. 推送并给出拉取请求
+        let Some(remote) = &self.push_remote else {
+            return Ok(Some(commit));
+        };
+        push_branch(dir, remote, &branch)?;
+        info!("Pushed {} to {}", branch, remote);
+        let url = remote_url(dir, remote)
+            .ok()
+            .and_then(|remote_url| pull_request_url(&remote_url, &base, &branch));
+        match &config.pr_command {
+            Some(command) => {
+                let output = run_pr_command(dir, command, &branch, &base, remote, url.as_deref())?;
+                if !output.is_empty() {
+                    reporter.output(output);
+                }
+            }
+            None => match &url {
+                Some(url) => reporter.output(url),
+                None => warn!(
+                    "Cannot derive a pull request URL for remote '{}'; open one for {} → {} manually or set prCommand",
+                    remote, branch, base
+                ),
+            },
+        }
+        Ok(Some(commit))
+    }
+
+    /// 修改比例超过上限时要求确认
+    ///
+    /// 显示修改规模和各语言的修改数；`--yes` 时直接继续，
+    /// 交互运行时询问，CI 等非交互运行时返回错误。
+    ///
+    /// # 参数
+    ///
+    /// * `magnitude` - 将被覆盖的本地键统计
+    /// * `max_percent` - 允许的比例上限
+    fn confirm_large_change(&self, magnitude: &ChangeMagnitude, max_percent: u32) -> Result<()> {
+        if !magnitude.exceeds(max_percent) {
+            return Ok(());
+        }
+
+        warn!(
+            "Sync would overwrite {}, above the {}% limit (maxChangePercent):",
+            magnitude.describe(),
+            max_percent
+        );
+        for (lang, changed) in &magnitude.per_language {
+            warn!("  - {}: {} key(s)", lang, changed);
+        }
+        if self.yes {
+            info!("  - Proceeding (--yes)");
+            return Ok(());
+        }
+        if prompt::confirm("Overwrite these local translations?")? {
+            return Ok(());
+        }
+
+        Err(anyhow::anyhow!(
+            "Sync aborted: it would overwrite {}, above the {}% limit. \
+             Check the backend project, then re-run with --yes or raise --max-change-percent.",
+            magnitude.describe(),
+            max_percent
+        ))
+    }
+
+    /// 找出要删除的本地键（后端已不存在）
+    ///
+    /// 上次同步后本地新增、尚未导入的键，以及没有同步快照的语言中后端没有的键
+    /// 保留（`--force` 时除外）。
+    ///
+    /// # 参数
+    ///
+    /// * `messages_dir` - Messages 目录路径（存放同步快照）
+    /// * `local` - 本地翻译
+    /// * `remote` - 后端翻译（经过本地映射后）
+    ///
+    /// # 返回
+    ///
+    /// 按语言分组的要删除的键
+    fn find_removals(
+        &self,
+        messages_dir: &Path,
+        local: &Translations,
+        remote: &Translations,
+    ) -> BTreeMap<String, Vec<String>> {
+        let base = SyncState::load(messages_dir).unwrap_or_default();
+        let PruneOutcome { removals, kept } = removed_on_backend(&base.translations, local, remote, !self.force);
+        let count: usize = removals.values().map(Vec::len).sum();
+        if count > 0 {
+            info!("  - Pruning {} key(s) no longer on the backend", count);
+        }
+        if !kept.is_empty() {
+            info!(
+                "  - Keeping {} key(s) missing on the backend that were added locally or have no sync snapshot \
+                 (import them, or use --force to prune them)",
+                kept.len()
+            );
+        }
+        removals
     }
 
-    /// 显示同步差异（dry-run 模式）
+    /// 与上次同步的快照三方合并，并写入冲突报告
     ///
-    /// 显示将要下载和将要跳过的键。
+    /// 只在本地修改过的键保留本地值（`--force` 或键匹配 `--force-keys` 时除外）；冲突按 `--prefer`
+    /// 解决，`interactive` 时逐个询问（模拟运行或不在终端中运行时不询问，
+    /// 记为未解决，保留本地值）。
     ///
     /// # 参数
     ///
-    /// * `backend` - 后端翻译（经过本地映射后）
+    /// * `messages_dir` - Messages 目录路径（存放同步快照）
     /// * `local` - 本地翻译
-    fn show_sync_diff(
+    /// * `remote` - 后端翻译（经过本地映射后）
+    /// * `force_filter` - `--force-keys` 选中的键
+    ///
+    /// # 返回
+    ///
+    /// 合并结果，`incoming` 为需要写入本地的翻译
+    fn merge_with_base(
         &self,
-        backend: &Translations,
+        messages_dir: &Path,
         local: &Translations,
-    ) -> Result<()> {
-        info!("=== DRY RUN ===");
-
-        let mut total_downloaded = 0;
-        let mut total_skipped = 0;
-
-        info!("Sync diff preview:");
-        for (lang, translations) in backend {
-            let local_lang = local.get(lang).cloned().unwrap_or_default();
-            let new_keys: Vec<&String> = translations.keys()
-                .filter(|k| !local_lang.contains_key(*k))
-                .collect();
-            let existing_keys: Vec<&String> = translations.keys()
-                .filter(|k| local_lang.contains_key(*k))
-                .collect();
-
-            let new_count = new_keys.len();
-            let existing_count = existing_keys.len();
-
-            info!("  {}:", lang);
-            if new_count > 0 {
-                let preview: Vec<String> = new_keys.iter().take(5).map(|s| s.to_string()).collect();
-                info!("    New ({}): {}", new_count, preview.join(", "));
+        remote: &Translations,
+        force_filter: &KeyFilter,
+    ) -> Result<MergeOutcome> {
+        let base = match SyncState::load(messages_dir) {
+            Ok(state) => state,
+            Err(e) => {
+                info!("  - Ignoring unreadable sync state: {}", e);
+                SyncState::default()
             }
-            if existing_count > 0 {
-                let preview: Vec<String> = existing_keys.iter().take(3).map(|s| s.to_string()).collect();
-                info!("    Existing ({}): {}", existing_count, preview.join(", "));
+        };
+
+        let resolution = match self.prefer {
+            Prefer::Remote => Resolution::Remote,
+            Prefer::Local => Resolution::Local,
+            Prefer::Interactive => Resolution::Unresolved,
+        };
+        let mut outcome = three_way_merge(&base.translations, local, remote, resolution, !self.force);
+        // --force-keys 选中的键不保留本地修改，写入后端值
+        if force_filter.is_active() {
+            outcome.kept_local.retain(|(lang, key)| {
+                if !force_filter.matches(key) {
+                    return true;
+                }
+                if let Some(value) = remote.get(lang).and_then(|keys| keys.get(key)) {
+                    outcome.incoming.entry(lang.clone()).or_default().insert(key.clone(), value.clone());
+                }
+                false
+            });
+        }
+        if !outcome.kept_local.is_empty() {
+            info!(
+                "  - Keeping {} key(s) edited only locally since the last sync (use --force to overwrite)",
+                outcome.kept_local.len()
+            );
+        }
+        if outcome.conflicts.is_empty() {
+            return Ok(outcome);
+        }
+
+        if self.prefer == Prefer::Interactive && !self.is_preview() {
+            let total = outcome.conflicts.len();
+            let mut for_all: Option<Resolution> = None;
+            for (index, conflict) in outcome.conflicts.iter_mut().enumerate() {
+                let resolution = match for_all {
+                    Some(resolution) => resolution,
+                    None => match ask_resolution(conflict, index + 1, total)? {
+                        Some((resolution, apply_to_all)) => {
+                            if apply_to_all {
+                                for_all = Some(resolution);
+                            }
+                            resolution
+                        }
+                        None => {
+                            warn!(
+                                "Not running in a terminal; leaving {} conflict(s) unresolved (local values kept)",
+                                total - index
+                            );
+                            break;
+                        }
+                    },
+                };
+                conflict.resolution = resolution;
+                if resolution == Resolution::Remote {
+                    outcome
+                        .incoming
+                        .entry(conflict.lang.clone())
+                        .or_default()
+                        .insert(conflict.key.clone(), conflict.remote.clone());
+                }
             }
+        }
+
+        let report_path = self
+            .conflicts_report
+            .clone()
+            .unwrap_or_else(|| messages_dir.join(REPORT_FILENAME));
+        write_conflict_report(&report_path, &outcome.conflicts)?;
+        info!(
+            "  - Conflicts: {} key(s) changed both locally and on backend, report written to {}",
+            outcome.conflicts.len(),
+            report_path.display()
+        );
+
+        Ok(outcome)
+    }
+
+    /// 读取上次从后端获取的翻译（`--offline`）
+    ///
+    /// # Errors
+    ///
+    /// 没有缓存、缓存属于其他项目，或缓存中没有所选语言时返回错误
+    fn load_cached_translations(config: &I18nConfig, locale_filter: &LocaleFilter) -> Result<Translations> {
+        let path = FetchCache::path(&config.messages_dir);
+        let cache = FetchCache::load(&config.messages_dir)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No cached backend translations at {}; run `yflow sync` online once first",
+                path.display()
+            )
+        })?;
+        if cache.project_id != config.project_id {
+            return Err(anyhow::anyhow!(
+                "Cached backend translations at {} belong to project {}, not {}",
+                path.display(),
+                cache.project_id,
+                config.project_id
+            ));
+        }
+        info!(
+            "Offline: replaying backend translations fetched at {}",
+            timestamp::format_rfc3339(cache.fetched_at)
+        );
+
+        let translations = locale_filter.apply(cache.translations);
+        if locale_filter.is_active() && translations.is_empty() {
+            return Err(anyhow::anyhow!(
+                "None of the selected languages are in the cached backend translations"
+            ));
+        }
+        Ok(translations)
+    }
 
-            total_downloaded += new_count;
-            total_skipped += existing_count;
+    /// 获取后端翻译（已转换为本地语言代码）
+    ///
+    /// 配置了 `routes` 时从所有路由目标获取并合并。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 已认证的默认项目客户端
+    /// * `language_mapper` - 语言映射器
+    pub(super) async fn fetch_backend_translations(
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+    ) -> Result<Translations> {
+        let router = Router::from_config(config);
+        if router.has_routes() {
+            return Self::fetch_routed_translations(&router, client, language_mapper).await;
         }
 
-        info!("Summary:");
-        info!("  - Would download: {}", total_downloaded);
-        info!("  - Would skip: {}", total_skipped);
+        let backend_translations = client.get_translations().await?;
+        Self::log_fetched(&backend_translations);
+
+        // 应用反向语言映射（后端代码 -> 本地代码）
+        Ok(language_mapper.reverse_translations(backend_translations))
+    }
+
+    /// 获取某个时间点的后端翻译（已转换为本地语言代码）
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 已认证的默认项目客户端
+    /// * `language_mapper` - 语言映射器
+    /// * `as_of` - 时间点（UNIX 秒）
+    async fn fetch_translations_as_of(
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        as_of: u64,
+    ) -> Result<Translations> {
+        if Router::from_config(config).has_routes() {
+            return Err(anyhow::anyhow!("--as-of cannot be combined with 'routes'"));
+        }
+
+        let backend_translations = client.get_translations_as_of(as_of).await?;
+        Self::log_fetched(&backend_translations);
+
+        // 应用反向语言映射（后端代码 -> 本地代码）
+        Ok(language_mapper.reverse_translations(backend_translations))
+    }
+
+    /// 只获取选中语言的后端翻译（已转换为本地语言代码）
+    ///
+    /// 显式指定了 `--locale` 且未配置路由时逐个语言下载，
+    /// 否则下载全部后再过滤。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 已认证的默认项目客户端
+    /// * `language_mapper` - 语言映射器
+    /// * `locale_filter` - 语言过滤器
+    async fn fetch_selected_translations(
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        locale_filter: &LocaleFilter,
+    ) -> Result<Translations> {
+        if locale_filter.included().is_empty() || Router::from_config(config).has_routes() {
+            let translations = Self::fetch_backend_translations(config, client, language_mapper).await?;
+            return Ok(locale_filter.apply(translations));
+        }
+
+        let mut backend_translations = Translations::new();
+        for lang in locale_filter.included().iter().filter(|lang| locale_filter.matches(lang)) {
+            let locale = language_mapper.to_backend(lang);
+            let values = client
+                .get_translations_by_locale(&locale)
+                .await
+                .with_context(|| format!("Failed to fetch translations for {}", locale))?;
+            if !values.is_empty() {
+                backend_translations.insert(locale, values);
+            }
+        }
+        Self::log_fetched(&backend_translations);
+
+        // 应用反向语言映射（后端代码 -> 本地代码）
+        Ok(language_mapper.reverse_translations(backend_translations))
+    }
+
+    /// 分块获取后端翻译（已转换为本地语言代码）
+    ///
+    /// 按本地已有的语言（支持命名空间过滤时再按命名空间）拆分下载，
+    /// 每个完成的块写入缓存；失败后重新运行会跳过已缓存的块。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - 配置
+    /// * `client` - 已认证的默认项目客户端
+    /// * `language_mapper` - 语言映射器
+    /// * `locale_filter` - 语言过滤器（显式指定的语言即使本地不存在也会下载）
+    async fn fetch_chunked_translations(
+        config: &I18nConfig,
+        client: &APIClient,
+        language_mapper: &LanguageMapper,
+        locale_filter: &LocaleFilter,
+    ) -> Result<Translations> {
+        if Router::from_config(config).has_routes() {
+            return Err(anyhow::anyhow!("--chunked cannot be combined with 'routes'"));
+        }
+
+        // 1. 确定要下载的语言（本地语言对应的后端代码）
+        let mut locales: Vec<String> = scan_messages_dir(&config.messages())
+            .await
+            .map(|scan| scan.translations.into_keys().collect())
+            .unwrap_or_default();
+        locales.extend(locale_filter.included().iter().cloned());
+        locales.sort();
+        locales.dedup();
+        locales.retain(|lang| locale_filter.matches(lang));
+        if locales.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No local languages found; chunked download only fetches languages that exist locally"
+            ));
+        }
+        locales = locales.iter().map(|lang| language_mapper.to_backend(lang)).collect();
+
+        // 2. 后端支持时按命名空间进一步拆分
+        let namespaces = if client.supports(Feature::Namespaces).await? {
+            client.list_namespaces().await?
+        } else {
+            Vec::new()
+        };
+        let chunks = plan_chunks(&locales, &namespaces);
+
+        // 3. 逐块下载，已缓存的块直接复用；每个语言一个进度条（单位为块）
+        let cache = ChunkCache::new(&config.messages_dir, config.project_id);
+        let mut downloaded = Vec::with_capacity(chunks.len());
+        let mut resumed = 0;
+        let progress_manager = ProgressGuard::new();
+        let progress = progress_manager.progress_callback();
+        let mut chunks_per_locale: BTreeMap<&str, usize> = BTreeMap::new();
+        for chunk in &chunks {
+            *chunks_per_locale.entry(chunk.locale.as_str()).or_default() += 1;
+        }
+        let mut current_locale: Option<&str> = None;
+        for (index, chunk) in chunks.iter().enumerate() {
+            // 块按语言排序，语言变化时结束上一个进度条
+            if current_locale != Some(chunk.locale.as_str()) {
+                if let Some(previous) = current_locale.replace(chunk.locale.as_str()) {
+                    progress(ProgressEvent::LanguageFinished {
+                        lang: previous.to_string(),
+                    });
+                }
+                progress(ProgressEvent::LanguageStarted {
+                    lang: chunk.locale.clone(),
+                    index: chunks_per_locale.range(..chunk.locale.as_str()).count() + 1,
+                    total: chunks_per_locale.len(),
+                    items: chunks_per_locale[chunk.locale.as_str()],
+                });
+            }
+            let chunk_done = || {
+                progress(ProgressEvent::KeysProcessed {
+                    lang: chunk.locale.clone(),
+                    n: 1,
+                })
+            };
+
+            if let Some(values) = cache.load(chunk)? {
+                resumed += 1;
+                downloaded.push((chunk.clone(), values));
+                chunk_done();
+                continue;
+            }
+
+            let values = Self::fetch_chunk(client, chunk).await.with_context(|| {
+                format!(
+                    "Failed to download chunk {}; {} of {} chunk(s) are cached in {}. Re-run with --chunked to resume",
+                    chunk,
+                    index,
+                    chunks.len(),
+                    cache.dir().display()
+                )
+            })?;
+            cache.store(chunk, &values)?;
+            info!("  - Chunk {}/{}: {} ({} keys)", index + 1, chunks.len(), chunk, values.len());
+            downloaded.push((chunk.clone(), values));
+            chunk_done();
+        }
+        if let Some(locale) = current_locale {
+            progress(ProgressEvent::LanguageFinished {
+                lang: locale.to_string(),
+            });
+        }
+        progress_manager.finish();
+        if resumed > 0 {
+            info!("  - Resumed {} cached chunk(s)", resumed);
+        }
+
+        // 4. 合并并清除缓存
+        let backend_translations = assemble(downloaded);
+        cache.clear()?;
+        Self::log_fetched(&backend_translations);
+
+        // 应用反向语言映射（后端代码 -> 本地代码）
+        Ok(language_mapper.reverse_translations(backend_translations))
+    }
+
+    /// 下载单个块（暂时性失败由客户端的重试策略处理）
+    async fn fetch_chunk(client: &APIClient, chunk: &Chunk) -> Result<HashMap<String, String>> {
+        match &chunk.namespace {
+            Some(namespace) => client.get_translations_by_namespace(&chunk.locale, namespace).await,
+            None => client.get_translations_by_locale(&chunk.locale).await,
+        }
+    }
+
+    /// 从所有路由目标获取翻译并合并
+    ///
+    /// 每个项目只采用按路由规则属于它的键，避免不同项目之间的同名键互相覆盖。
+    ///
+    /// # 参数
+    ///
+    /// * `router` - 路由器
+    /// * `default_client` - 已认证的默认项目客户端
+    /// * `language_mapper` - 语言映射器
+    async fn fetch_routed_translations(
+        router: &Router,
+        default_client: &APIClient,
+        language_mapper: &LanguageMapper,
+    ) -> Result<Translations> {
+        let mut merged = Translations::new();
+
+        for target in router.targets() {
+            info!("  Project {} ({}):", target.project_id, target.api_url);
+            let backend_translations = if target == router.default_target() {
+                default_client.get_translations().await?
+            } else {
+                let client = default_client
+                    .for_project(target.api_url.clone(), target.api_key.clone(), target.project_id)
+                    .with_context(|| {
+                        format!("Failed to create API client for project {}", target.project_id)
+                    })?;
+                client.get_translations().await?
+            };
+            Self::log_fetched(&backend_translations);
+
+            let local = language_mapper.reverse_translations(backend_translations);
+            for (lang, lang_translations) in router.filter_for(target, local) {
+                merged.entry(lang).or_default().extend(lang_translations);
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// 输出获取到的翻译统计
+    fn log_fetched(translations: &Translations) {
+        let total_keys: usize = translations.values().map(|v| v.len()).sum();
+        let languages: Vec<&str> = translations.keys().map(|s| s.as_str()).collect();
+        info!(
+            "  - Fetched {} keys from {} languages: {}",
+            total_keys,
+            languages.len(),
+            languages.join(", ")
+        );
+    }
+
+    /// `--force-keys` 的键过滤器（未指定时未激活，调用方需检查 `is_active()`）
+    fn force_filter(&self, separator: &str) -> Result<KeyFilter> {
+        KeyFilter::from_patterns(&self.force_keys, separator)
+    }
+
+    /// 是否只预览（`--dry-run`、`--check`、`--patch`），不写入任何文件
+    fn is_preview(&self) -> bool {
+        self.dry_run || self.check || self.patch
+    }
+
+    /// 显示同步计划（dry-run、check 模式）
+    ///
+    /// 以表格列出每个将被创建或修改的文件中新增、修改和删除的键及前后的值，
+    /// 并输出汇总。
+    ///
+    /// # 参数
+    ///
+    /// * `reporter` - 输出层
+    /// * `messages_dir` - Messages 目录路径（用于显示相对路径）
+    /// * `plan` - 同步计划
+    fn show_sync_plan(&self, reporter: &dyn Reporter, messages_dir: &Path, plan: &SyncPlan) {
+        if self.dry_run {
+            info!("=== DRY RUN ===");
+        }
+
+        info!("Sync plan:");
+        if plan.is_empty() {
+            info!("  Local files are up to date");
+        }
+        if !plan.is_empty() {
+            reporter.write(&render_plan_table(messages_dir, plan));
+        }
 
-        Ok(())
+        let (added, updated, removed) = plan.key_counts();
+        info!("Summary:");
+        info!("  - Files to create: {}", plan.files_to_create());
+        info!("  - Files to update: {}", plan.files_to_update());
+        info!("  - Keys added: {}, updated: {}, removed: {}", added, updated, removed);
     }
 
     /// 执行同步操作
     ///
-    /// 将翻译写入本地文件，同时保留原始文件结构。
+    /// 执行同步计划，将翻译写入本地文件（保留原始文件结构）。
     /// 显示每个语言写入的进度条。
     ///
     /// # Arguments
     ///
     /// * `messages_dir` - Messages 目录路径
-    /// * `local_files` - 本地文件列表（相对路径）
-    /// * `translations` - 要写入的翻译数据
+    /// * `plan` - 同步计划
+    /// * `translations` - 计划写入的翻译数据（用于校验和统计）
     /// * `local_scan_result` - 本地扫描结果（包含现有翻译，用于统计计算）
     ///
     /// # 统计计算
     ///
     /// 统计逻辑说明：
-    /// - `downloaded`: 新下载的键数量（force=true 或本地不存在的键）
-    /// - `skipped`: 跳过的键数量（force=false 且本地已存在的键）
+    /// - `downloaded`: 新下载的键数量（force=true、匹配 `--force-keys` 或本地不存在的键）
+    /// - `skipped`: 跳过的键数量（其余本地已存在的键）
     /// - `written`: 写入的文件数量
     ///
     /// 通过传入 `local_scan_result` 避免重复扫描目录，提高性能。
     async fn execute_sync(
         &self,
-        messages_dir: &PathBuf,
-        local_files: &[PathBuf],
+        messages_dir: &MessagesDir,
+        plan: &SyncPlan,
         translations: &Translations,
         local_scan_result: &ScanResult,
+        overlays: &[Overlay],
     ) -> Result<SyncResult> {
-        // 初始化进度管理器
-        let progress_manager = MultiProgressManager::new();
+        // 初始化进度管理器（写入失败时 guard 自动清除进度条）
+        let progress_manager = ProgressGuard::new();
         let show_progress = progress_manager.is_enabled();
 
+        // 写入前备份将被修改的文件，以及同步快照和增量缓存
+        if !self.no_backup && !plan.is_empty() {
+            let mut paths: Vec<PathBuf> = plan.changes().map(|change| change.path.clone()).collect();
+            paths.push(SyncState::path(messages_dir));
+            paths.push(DeltaCache::path(messages_dir));
+            let backup = Backup::create(messages_dir, "sync", &paths).context("Failed to back up local files")?;
+            info!(
+                "Backed up {} file(s) to {} (undo with `yflow rollback`)",
+                backup.files.len(),
+                backup.dir(messages_dir).display()
+            );
+        }
+
         info!("Writing translations to local files...");
 
-        // 跟踪进度的回调
-        let total_languages = translations.len();
-        let _total_languages = total_languages; // 抑制警告
-
-        // 创建进度回调（使用线程安全的内部可变性）
-        let progress_callback: crate::core::scanner::ProgressCallback = if show_progress {
-            let bar = progress_manager.create_bar("", 1);
-            use std::sync::{Arc, Mutex};
-            let bar = Arc::new(Mutex::new(bar));
-            Box::new(move |_lang: String, _index: usize, _total: usize| {
-                let mut bar = bar.lock().unwrap();
-                bar.inc();
-                bar.finish();
-            })
+        // 将写入事件转发给进度管理器
+        let progress_callback = if show_progress {
+            Some(progress_manager.progress_callback())
         } else {
-            Box::new(|_lang: String, _index: usize, _total: usize| {})
+            None
         };
 
-        // 写入翻译（保留文件结构）
-        let written = write_translations_with_structure(
-            messages_dir,
-            local_files,
-            translations,
-            self.force,
-            Some(progress_callback),
-        )
-        .await
-        .context("Failed to write translations")?;
+        // 执行计划
+        let written = plan
+            .apply(progress_callback)
+            .await
+            .context("Failed to write translations")?;
 
         // 停止进度显示
-        progress_manager.stop();
+        progress_manager.finish();
+
+        // 重新读取写入的文件，校验所有期望的键值都已落盘（每一层分别校验）
+        let (base, layers) = overlays::split(translations, overlays);
+        let mut verification_failures = verify_written_files(messages_dir, &written, &base).await;
+        for (overlay, layer) in overlays.iter().zip(&layers) {
+            verification_failures.extend(verify_written_files(&overlay.dir, &written, layer).await);
+        }
 
         // 计算统计结果
         // 使用传入的 local_scan_result，避免重复扫描目录
-        let mut result = SyncResult::default();
-        result.written = written.len();
+        let mut result = SyncResult {
+            written: written.len(),
+            verification_failures,
+            ..Default::default()
+        };
 
+        let force_filter = self.force_filter(&messages_dir.separator)?;
         for (lang, translations) in translations {
             // 从本地扫描结果获取该语言的现有翻译
             let local_translations = local_scan_result
@@ -288,29 +1262,187 @@ impl SyncCmd {
                 .unwrap_or_default();
 
             // 遍历所有翻译键，计算下载/跳过数量
-            for key in translations.keys() {
-                if self.force || !local_translations.contains_key(key) {
-                    // force=true 或键不存在于本地 -> 下载
-                    result.downloaded += 1;
-                } else {
-                    // force=false 且键已存在于本地 -> 跳过
-                    result.skipped += 1;
+            let counts = result.languages.entry(lang.clone()).or_default();
+            for (key, value) in translations {
+                let forced = self.force || (force_filter.is_active() && force_filter.matches(key));
+                match local_translations.get(key) {
+                    // 键不存在于本地 -> 下载
+                    None => {
+                        result.downloaded += 1;
+                        counts.added += 1;
+                    }
+                    // 强制覆盖 -> 下载（值相同时不计为更新）
+                    Some(local) if forced => {
+                        result.downloaded += 1;
+                        if local == value {
+                            counts.unchanged += 1;
+                        } else {
+                            counts.updated += 1;
+                        }
+                    }
+                    // 非强制且键已存在于本地 -> 跳过
+                    Some(_) => {
+                        result.skipped += 1;
+                        counts.unchanged += 1;
+                    }
                 }
             }
         }
 
-        info!("Sync complete:");
-        info!("  - Downloaded: {}", result.downloaded);
-        info!("  - Skipped: {}", result.skipped);
+        info!("Sync complete");
         info!("  - Files written: {}", result.written);
+        if !result.verification_failures.is_empty() {
+            info!("  - Verification failures: {}", result.verification_failures.len());
+        }
 
         Ok(result)
     }
 }
 
+/// 冲突菜单的选项：(显示文本, 解决方式, 是否应用到其余所有冲突)
+const CONFLICT_CHOICES: [(&str, Resolution, bool); 4] = [
+    ("Keep local", Resolution::Local, false),
+    ("Take remote", Resolution::Remote, false),
+    ("Keep local for all remaining conflicts", Resolution::Local, true),
+    ("Take remote for all remaining conflicts", Resolution::Remote, true),
+];
+
+/// 并排显示时每列的最小宽度
+const MIN_COLUMN_WIDTH: usize = 20;
+
+/// 询问如何解决一个冲突
+///
+/// 并排显示本地值和后端值，返回选择的解决方式及是否应用到其余冲突。
+/// 不在终端中运行时返回 `None`。
+///
+/// # 参数
+///
+/// * `conflict` - 冲突
+/// * `index` - 冲突序号（从 1 开始）
+/// * `total` - 冲突总数
+fn ask_resolution(conflict: &Conflict, index: usize, total: usize) -> Result<Option<(Resolution, bool)>> {
+    if !prompt::is_interactive() || !std::io::stdout().is_terminal() {
+        return Ok(None);
+    }
+
+    let width = usize::from(dialoguer::console::Term::stderr().size().1);
+    eprintln!();
+    eprintln!("Conflict {}/{}: {} {}", index, total, conflict.lang, conflict.key);
+    if let Some(base) = &conflict.base {
+        eprintln!("Last synced: {}", base);
+    }
+    eprint!("{}", side_by_side(&conflict.local, &conflict.remote, width));
+
+    let labels: Vec<&str> = CONFLICT_CHOICES.iter().map(|(label, _, _)| *label).collect();
+    let choice = prompt::select("Resolve", &labels, 1)?;
+    Ok(choice.map(|choice| {
+        let (_, resolution, apply_to_all) = CONFLICT_CHOICES[choice];
+        (resolution, apply_to_all)
+    }))
+}
+
+/// `--check` 发现本地文件与后端不一致时的错误（`--ci` 时退出码为 2）
+fn out_of_sync_error(plan: &SyncPlan) -> anyhow::Error {
+    Failure::Drift.error(format!(
+        "Local files are out of sync with the backend: {} file(s) would be created, {} updated",
+        plan.files_to_create(),
+        plan.files_to_update()
+    ))
+}
+
+/// 同步计划表格中值的最大显示宽度
+const MAX_VALUE_WIDTH: usize = 40;
+
+/// 将同步计划渲染为表格：每个修改的键一行，包含文件、操作、原值和新值
+///
+/// 值中的换行显示为 `\n`，超过 [`MAX_VALUE_WIDTH`] 的值被截断（完整的值见 `--output json`）。
+fn render_plan_table(messages_dir: &Path, plan: &SyncPlan) -> String {
+    let cell = |value: &Option<String>| {
+        let value = value.as_deref().unwrap_or_default().replace('\n', "\\n");
+        truncate_str(&value, MAX_VALUE_WIDTH, "…").into_owned()
+    };
+
+    let mut rows = vec![["FILE", "ACTION", "KEY", "OLD", "NEW"].map(String::from)];
+    for change in plan.changes() {
+        let path = change.path.strip_prefix(messages_dir).unwrap_or(&change.path);
+        let mut file = path.display().to_string();
+        if change.create {
+            file.push_str(" (new)");
+        }
+        for key in &change.keys {
+            rows.push([
+                file.clone(),
+                key.action.name().to_string(),
+                key.key.clone(),
+                cell(&key.old),
+                cell(&key.new),
+            ]);
+        }
+    }
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(measure_text_width(value));
+        }
+    }
+    let mut table = String::new();
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(value, width)| pad_str(value, width, Alignment::Left, None).into_owned())
+            .collect();
+        table.push_str(line.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// 将本地值和后端值并排排列
+///
+/// 每列占终端宽度的一半，超出列宽的内容按显示宽度换行（中日韩字符占两列）。
+fn side_by_side(local: &str, remote: &str, width: usize) -> String {
+    let column = (width.saturating_sub(3) / 2).max(MIN_COLUMN_WIDTH);
+    let pad = |text: &str| pad_str(text, column, Alignment::Left, None).into_owned();
+    let left = wrap(local, column);
+    let right = wrap(remote, column);
+
+    let mut table = format!("{} │ remote\n", pad("local"));
+    table.push_str(&format!("{}─┼─{}\n", "─".repeat(column), "─".repeat(column)));
+    for line in 0..left.len().max(right.len()) {
+        let left_line = left.get(line).map(String::as_str).unwrap_or_default();
+        let right_line = right.get(line).map(String::as_str).unwrap_or_default();
+        table.push_str(&format!("{} │ {}\n", pad(left_line), right_line));
+    }
+    table
+}
+
+/// 按显示宽度换行（保留原有的换行）
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for raw in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for ch in raw.chars() {
+            let char_width = measure_text_width(ch.encode_utf8(&mut [0; 4]));
+            if line_width + char_width > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(ch);
+            line_width += char_width;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::scanner::plan_translations_with_structure;
+    use crate::core::sync_plan::{FileChange, LanguagePlan};
     use tempfile::TempDir;
     use std::path::PathBuf;
 
@@ -320,6 +1452,7 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         };
         assert!(!cmd.dry_run);
         assert!(!cmd.force);
@@ -331,6 +1464,7 @@ mod tests {
             config: None,
             dry_run: false,
             force: true,
+            ..Default::default()
         };
         assert!(cmd.force);
     }
@@ -341,6 +1475,7 @@ mod tests {
             config: None,
             dry_run: true,
             force: false,
+            ..Default::default()
         };
         assert!(cmd.dry_run);
     }
@@ -351,19 +1486,229 @@ mod tests {
             config: Some(PathBuf::from("/custom/path")),
             dry_run: true,
             force: true,
+            ..Default::default()
         };
         assert!(cmd.dry_run);
         assert!(cmd.force);
         assert_eq!(cmd.config, Some(PathBuf::from("/custom/path")));
     }
 
+    // ========== 冲突检测测试 ==========
+
+    #[test]
+    fn test_merge_with_base_writes_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let report_path = temp_dir.path().join("conflicts.json");
+
+        // 基线快照
+        SyncState {
+            translations: [("en".to_string(), [("title".to_string(), "Base".to_string())].iter().cloned().collect())]
+                .iter()
+                .cloned()
+                .collect(),
+        }
+        .save(temp_dir.path())
+        .unwrap();
+
+        let local: Translations = [("en".to_string(), [("title".to_string(), "Local".to_string())].iter().cloned().collect())]
+            .iter()
+            .cloned()
+            .collect();
+        let remote: Translations = [("en".to_string(), [("title".to_string(), "Remote".to_string())].iter().cloned().collect())]
+            .iter()
+            .cloned()
+            .collect();
+
+        let cmd = SyncCmd {
+            conflicts_report: Some(report_path.clone()),
+            ..Default::default()
+        };
+
+        let outcome = cmd.merge_with_base(temp_dir.path(), &local, &remote, &KeyFilter::default()).unwrap();
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.incoming["en"]["title"], "Remote");
+        assert!(report_path.exists());
+
+        // --prefer local：冲突键不写入，报告中记录为 local
+        let cmd = SyncCmd {
+            conflicts_report: Some(report_path.clone()),
+            prefer: Prefer::Local,
+            ..Default::default()
+        };
+        let outcome = cmd.merge_with_base(temp_dir.path(), &local, &remote, &KeyFilter::default()).unwrap();
+        assert!(outcome.incoming["en"].is_empty());
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["conflicts"][0]["resolution"], "local");
+
+        // --prefer interactive：不在终端中运行或模拟运行时不询问，记为未解决（保留本地值）
+        std::env::set_var("CI", "true");
+        let cmd = SyncCmd {
+            conflicts_report: Some(report_path.clone()),
+            prefer: Prefer::Interactive,
+            ..Default::default()
+        };
+        let outcome = cmd.merge_with_base(temp_dir.path(), &local, &remote, &KeyFilter::default()).unwrap();
+        assert_eq!(outcome.conflicts[0].resolution, Resolution::Unresolved);
+        assert!(outcome.incoming["en"].is_empty());
+        let cmd = SyncCmd {
+            dry_run: true,
+            ..cmd
+        };
+        let outcome = cmd.merge_with_base(temp_dir.path(), &local, &remote, &KeyFilter::default()).unwrap();
+        assert_eq!(outcome.conflicts[0].resolution, Resolution::Unresolved);
+    }
+
+    #[test]
+    fn test_merge_with_base_keeps_local_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let single = |value: &str| -> Translations {
+            HashMap::from([("en".to_string(), HashMap::from([("title".to_string(), value.to_string())]))])
+        };
+        SyncState { translations: single("Base") }.save(temp_dir.path()).unwrap();
+
+        let outcome = SyncCmd::default()
+            .merge_with_base(temp_dir.path(), &single("Local"), &single("Base"), &KeyFilter::default())
+            .unwrap();
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.kept_local.len(), 1);
+        assert!(outcome.incoming["en"].is_empty());
+        assert!(!temp_dir.path().join(REPORT_FILENAME).exists());
+
+        let cmd = SyncCmd {
+            force: true,
+            ..Default::default()
+        };
+        let outcome = cmd.merge_with_base(temp_dir.path(), &single("Local"), &single("Base"), &KeyFilter::default()).unwrap();
+        assert_eq!(outcome.incoming["en"]["title"], "Base");
+    }
+
+    #[test]
+    fn test_merge_with_base_force_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let translations = |legal: &str, title: &str| -> Translations {
+            HashMap::from([(
+                "en".to_string(),
+                HashMap::from([
+                    ("legal.terms".to_string(), legal.to_string()),
+                    ("title".to_string(), title.to_string()),
+                ]),
+            )])
+        };
+        SyncState { translations: translations("Base", "Base") }.save(temp_dir.path()).unwrap();
+
+        // 只有匹配 --force-keys 的键覆盖本地修改
+        let cmd = SyncCmd::try_parse_from(["sync", "--force-keys", "legal.*,emails.*"]).unwrap();
+        assert_eq!(cmd.force_keys, ["legal.*", "emails.*"]);
+        let local = translations("Local", "Local");
+        let remote = translations("Base", "Base");
+        let outcome = cmd
+            .merge_with_base(temp_dir.path(), &local, &remote, &cmd.force_filter(".").unwrap())
+            .unwrap();
+        assert_eq!(outcome.kept_local, [("en".to_string(), "title".to_string())]);
+        assert_eq!(outcome.incoming["en"]["legal.terms"], "Base");
+        assert!(!outcome.incoming["en"].contains_key("title"));
+
+        assert!(SyncCmd::try_parse_from(["sync", "--force", "--force-keys", "legal."]).is_err());
+    }
+
+    #[test]
+    fn test_render_plan_table() {
+        let old: HashMap<String, String> = [("title".to_string(), "Titel".to_string())].into();
+        let new: HashMap<String, String> = [
+            ("title".to_string(), "Überschrift".to_string()),
+            ("body".to_string(), format!("Zeile 1\nZeile 2 {}", "x".repeat(50))),
+        ]
+        .into();
+        let plan = SyncPlan {
+            languages: vec![LanguagePlan {
+                lang: "de".to_string(),
+                changes: vec![FileChange::new(
+                    PathBuf::from("/messages/de/common.json"),
+                    false,
+                    String::new(),
+                    String::new(),
+                    &old,
+                    &new,
+                )],
+            }],
+        };
+        let table = render_plan_table(Path::new("/messages"), &plan);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("FILE            ACTION  KEY    OLD    NEW"));
+        assert!(lines[1].starts_with("de/common.json  add     body          Zeile 1\\nZeile 2 xxx"));
+        assert!(lines[1].ends_with('…'));
+        assert_eq!(lines[2], "de/common.json  update  title  Titel  Überschrift");
+    }
+
+    #[test]
+    fn test_check_error_is_drift() {
+        let new: HashMap<String, String> = [("title".to_string(), "Titel".to_string())].into();
+        let plan = SyncPlan {
+            languages: vec![LanguagePlan {
+                lang: "de".to_string(),
+                changes: vec![FileChange::new(
+                    PathBuf::from("/messages/de/common.json"),
+                    true,
+                    String::new(),
+                    String::new(),
+                    &HashMap::new(),
+                    &new,
+                )],
+            }],
+        };
+        let error = out_of_sync_error(&plan);
+        assert_eq!(
+            error.to_string(),
+            "Local files are out of sync with the backend: 1 file(s) would be created, 0 updated"
+        );
+        assert_eq!(crate::core::failure::exit_code(&error), 2);
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let table = side_by_side("Save", "Speichern und schließen", 49);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], format!("{:<23} │ remote", "local"));
+        assert_eq!(lines[2], format!("{:<23} │ Speichern und schließen", "Save"));
+
+        // 超出列宽时按显示宽度换行（每个汉字占两列）
+        let table = side_by_side(&"保存".repeat(12), "Save", 0);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], format!("{} │ Save", "保存".repeat(5)));
+        assert_eq!(lines[4], format!("{}{} │ ", "保存".repeat(2), " ".repeat(12)));
+        assert_eq!(wrap("a\nbc", 1), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_merge_with_base_no_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        let report_path = temp_dir.path().join("conflicts.json");
+
+        let same: Translations = [("en".to_string(), [("title".to_string(), "Same".to_string())].iter().cloned().collect())]
+            .iter()
+            .cloned()
+            .collect();
+
+        let cmd = SyncCmd {
+            conflicts_report: Some(report_path.clone()),
+            ..Default::default()
+        };
+
+        let outcome = cmd.merge_with_base(temp_dir.path(), &same, &same, &KeyFilter::default()).unwrap();
+        assert!(outcome.conflicts.is_empty());
+        assert!(!report_path.exists());
+    }
+
     // ========== execute_sync 统计逻辑测试 ==========
 
     /// 测试 force=true 时，所有键都应该被下载
     #[tokio::test]
     async fn test_execute_sync_stats_with_force() {
         let temp_dir = TempDir::new().unwrap();
-        let messages_dir = temp_dir.path().join("messages");
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
         std::fs::create_dir_all(&messages_dir).unwrap();
 
         // 创建本地文件（en 目录和文件）
@@ -386,6 +1731,8 @@ mod tests {
             .collect(),
             files: vec![PathBuf::from("en/common.json")],
             key_count: 1,
+            quarantine: Vec::new(),
+            ..Default::default()
         };
 
         // 要写入的翻译（包含现有键和新键）
@@ -407,10 +1754,14 @@ mod tests {
             config: None,
             dry_run: false,
             force: true,
+            ..Default::default()
         };
 
+        let plan = plan_translations_with_structure(&messages_dir, &local_scan_result.files, &translations, cmd.force)
+            .await
+            .unwrap();
         let result = cmd
-            .execute_sync(&messages_dir, &local_scan_result.files, &translations, &local_scan_result)
+            .execute_sync(&messages_dir, &plan, &translations, &local_scan_result, &[])
             .await
             .unwrap();
 
@@ -418,13 +1769,21 @@ mod tests {
         assert_eq!(result.downloaded, 2);
         assert_eq!(result.skipped, 0);
         assert!(result.written >= 1);
+        // 写入的文件应通过校验
+        assert!(result.verification_failures.is_empty());
+
+        // 写入前的文件已备份，可以恢复
+        let backup = Backup::resolve(&messages_dir, None).unwrap();
+        assert_eq!(backup.command, "sync");
+        backup.restore(&messages_dir).unwrap();
+        assert_eq!(std::fs::read_to_string(&en_file).unwrap(), r#"{"greeting": "Hello"}"#);
     }
 
     /// 测试 force=false 时，已存在的键被跳过
     #[tokio::test]
     async fn test_execute_sync_stats_without_force() {
         let temp_dir = TempDir::new().unwrap();
-        let messages_dir = temp_dir.path().join("messages");
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
         std::fs::create_dir_all(&messages_dir).unwrap();
 
         // 创建本地文件
@@ -447,6 +1806,8 @@ mod tests {
             .collect(),
             files: vec![PathBuf::from("en/common.json")],
             key_count: 1,
+            quarantine: Vec::new(),
+            ..Default::default()
         };
 
         // 要写入的翻译
@@ -468,10 +1829,14 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         };
 
+        let plan = plan_translations_with_structure(&messages_dir, &local_scan_result.files, &translations, cmd.force)
+            .await
+            .unwrap();
         let result = cmd
-            .execute_sync(&messages_dir, &local_scan_result.files, &translations, &local_scan_result)
+            .execute_sync(&messages_dir, &plan, &translations, &local_scan_result, &[])
             .await
             .unwrap();
 
@@ -485,7 +1850,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_sync_stats_multiple_languages() {
         let temp_dir = TempDir::new().unwrap();
-        let messages_dir = temp_dir.path().join("messages");
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
         std::fs::create_dir_all(&messages_dir).unwrap();
 
         // 创建 en 和 zh_CN 目录
@@ -512,6 +1877,8 @@ mod tests {
                 PathBuf::from("zh_CN/common.json"),
             ],
             key_count: 2,
+            quarantine: Vec::new(),
+            ..Default::default()
         };
 
         // 要写入的翻译
@@ -533,10 +1900,14 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         };
 
+        let plan = plan_translations_with_structure(&messages_dir, &local_scan_result.files, &translations, cmd.force)
+            .await
+            .unwrap();
         let result = cmd
-            .execute_sync(&messages_dir, &local_scan_result.files, &translations, &local_scan_result)
+            .execute_sync(&messages_dir, &plan, &translations, &local_scan_result, &[])
             .await
             .unwrap();
 
@@ -550,7 +1921,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_sync_stats_new_language() {
         let temp_dir = TempDir::new().unwrap();
-        let messages_dir = temp_dir.path().join("messages");
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
         std::fs::create_dir_all(&messages_dir).unwrap();
 
         // 只创建 en 目录
@@ -572,6 +1943,8 @@ mod tests {
             .collect(),
             files: vec![PathBuf::from("en/common.json")],
             key_count: 1,
+            quarantine: Vec::new(),
+            ..Default::default()
         };
 
         // 要写入 en 和新语言 ja_JP
@@ -591,10 +1964,14 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         };
 
+        let plan = plan_translations_with_structure(&messages_dir, &local_scan_result.files, &translations, cmd.force)
+            .await
+            .unwrap();
         let result = cmd
-            .execute_sync(&messages_dir, &local_scan_result.files, &translations, &local_scan_result)
+            .execute_sync(&messages_dir, &plan, &translations, &local_scan_result, &[])
             .await
             .unwrap();
 
@@ -607,13 +1984,15 @@ mod tests {
     #[tokio::test]
     async fn test_execute_sync_empty_translations() {
         let temp_dir = TempDir::new().unwrap();
-        let messages_dir = temp_dir.path().join("messages");
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
         std::fs::create_dir_all(&messages_dir).unwrap();
 
         let local_scan_result = ScanResult {
             translations: std::collections::HashMap::new(),
             files: vec![],
             key_count: 0,
+            quarantine: Vec::new(),
+            ..Default::default()
         };
 
         let translations: Translations = std::collections::HashMap::new();
@@ -622,10 +2001,14 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         };
 
+        let plan = plan_translations_with_structure(&messages_dir, &local_scan_result.files, &translations, cmd.force)
+            .await
+            .unwrap();
         let result = cmd
-            .execute_sync(&messages_dir, &local_scan_result.files, &translations, &local_scan_result)
+            .execute_sync(&messages_dir, &plan, &translations, &local_scan_result, &[])
             .await
             .unwrap();
 
@@ -633,4 +2016,561 @@ mod tests {
         assert_eq!(result.skipped, 0);
         assert_eq!(result.written, 0);
     }
+
+    #[tokio::test]
+    async fn test_chunked_sync_resumes_from_cache() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // 模拟后端：旧后端（无 capabilities），en 块在 fail_en 为 true 时返回 500
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let fail_en = Arc::new(AtomicBool::new(true));
+        let de_requests = Arc::new(AtomicUsize::new(0));
+        let (fail, de_count) = (fail_en.clone(), de_requests.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let (status, body) = if path.starts_with("/cli/auth") {
+                    ("200 OK", r#"{"data": {}}"#)
+                } else if path.starts_with("/cli/capabilities") {
+                    ("404 Not Found", "")
+                } else if path.contains("locale=de") {
+                    // 与后端一致：按语言过滤的响应仍是 {key: {locale: value}}
+                    de_count.fetch_add(1, Ordering::SeqCst);
+                    ("200 OK", r#"{"data": {"title": {"de": "Titel"}}}"#)
+                } else if fail.load(Ordering::SeqCst) {
+                    ("500 Internal Server Error", "boom")
+                } else {
+                    ("200 OK", r#"{"data": {"title": {"en": "Title"}}}"#)
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for lang in ["en", "de"] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), r#"{"title": ""}"#).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            chunked: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+
+        // 第一次：en 块失败，de 块已缓存
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("Re-run with --chunked to resume"));
+        let cache = ChunkCache::new(&messages_dir, 1);
+        assert!(cache.load(&Chunk::new("de", None)).unwrap().is_some());
+
+        // 第二次：只下载 en 块，完成后清除缓存
+        fail_en.store(false, Ordering::SeqCst);
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(de_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(result.verification_failures.len(), 0);
+        assert!(!cache.dir().exists());
+        let de: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(messages_dir.join("de").join("common.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(de["title"], "Titel");
+        let en: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(messages_dir.join("en").join("common.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(en["title"], "Title");
+    }
+
+    #[tokio::test]
+    async fn test_sync_selected_locale_only() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：只应请求 de，请求全部翻译或其他语言时返回 500
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let (status, body) = if path.starts_with("/cli/auth") {
+                    ("200 OK", r#"{"data": {}}"#)
+                } else if path.contains("locale=de") {
+                    // 与后端一致：按语言过滤的响应仍是 {key: {locale: value}}
+                    ("200 OK", r#"{"data": {"title": {"de": "Titel"}}}"#)
+                } else {
+                    ("500 Internal Server Error", "unexpected")
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for lang in ["en", "de"] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), r#"{"title": ""}"#).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        // 已有 en 的同步快照，只同步 de 时应保留
+        SyncState {
+            translations: HashMap::from([(
+                "en".to_string(),
+                HashMap::from([("title".to_string(), String::new())]),
+            )]),
+        }
+        .save(&messages_dir)
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            locales: vec!["de".to_string()],
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.written, 1);
+
+        let read = |lang: &str| -> serde_json::Value {
+            serde_json::from_str(
+                &std::fs::read_to_string(messages_dir.join(lang).join("common.json")).unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(read("de")["title"], "Titel");
+        assert_eq!(read("en")["title"], "");
+
+        let state = SyncState::load(&messages_dir).unwrap();
+        assert_eq!(state.translations["de"]["title"], "Titel");
+        assert!(state.translations.contains_key("en"));
+
+        let cached = FetchCache::load(&messages_dir).unwrap().unwrap();
+        assert_eq!(cached.translations["de"]["title"], "Titel");
+    }
+
+    #[tokio::test]
+    async fn test_sync_offline_replays_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(messages_dir.join("de").join("common.json"), r#"{"title": ""}"#).unwrap();
+        // 后端不可达：离线同步不应访问它
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://127.0.0.1:1", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            offline: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("No cached backend translations"));
+
+        let fetched = HashMap::from([(
+            "de".to_string(),
+            HashMap::from([("title".to_string(), "Titel".to_string())]),
+        )]);
+        FetchCache::record(&messages_dir, 2, &fetched, true).unwrap();
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("belong to project 2"));
+
+        FetchCache::record(&messages_dir, 1, &fetched, true).unwrap();
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.written, 1);
+        let de: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(messages_dir.join("de").join("common.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(de["title"], "Titel");
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_unchanged_languages() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                let body = r#"{"data": {"title": {"de": "Titel"}}}"#;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let file = messages_dir.join("de").join("common.json");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(&file, r#"{"title": ""}"#).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            locales: vec!["de".to_string()],
+            force: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!((result.written, result.unchanged), (1, 0));
+
+        // 后端和文件都未变化：跳过该语言
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!((result.written, result.unchanged), (0, 1));
+
+        // 本地文件被修改后重新写入
+        std::fs::write(&file, r#"{"title": "Lokal"}"#).unwrap();
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!((result.written, result.unchanged), (1, 0));
+
+        // --no-cache 时不跳过，但内容相同的文件不会被重写
+        let cmd = SyncCmd { no_cache: true, ..cmd };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!((result.written, result.unchanged), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_sync_writes_overlay_keys_to_overlay() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                let body = r#"{"data": {"title": {"de": "Acme Titel"}, "save": {"de": "Speichern"}}}"#;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let overlay_dir = temp_dir.path().join("brand/acme");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::create_dir_all(overlay_dir.join("de")).unwrap();
+        std::fs::write(messages_dir.join("de/common.json"), r#"{"title": "Titel", "save": ""}"#).unwrap();
+        std::fs::write(overlay_dir.join("de/common.json"), r#"{"title": "Acme"}"#).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k", "overlays": ["{}"]}}"#,
+                messages_dir.display(),
+                base_url,
+                overlay_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            locales: vec!["de".to_string()],
+            force: true,
+            no_backup: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.written, 2);
+        assert!(result.verification_failures.is_empty());
+
+        // 覆盖层中的键写回覆盖层，基础文件中的值保持不变
+        let read = |path: PathBuf| -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+        };
+        assert_eq!(
+            read(messages_dir.join("de/common.json")),
+            serde_json::json!({"title": "Titel", "save": "Speichern"})
+        );
+        assert_eq!(read(overlay_dir.join("de/common.json")), serde_json::json!({"title": "Acme Titel"}));
+    }
+
+    #[tokio::test]
+    async fn test_sync_prune_removes_keys_deleted_on_backend() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                let body = r#"{"data": {"title": {"de": "Titel"}}}"#;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let file = messages_dir.join("de").join("common.json");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        let original = r#"{"title": "Titel", "old": "Alt", "nested": {"gone": "Weg"}, "added": "Neu"}"#;
+        std::fs::write(&file, original).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+        // 上次同步时后端还有 old 和 nested.gone；added 是之后本地新增的
+        SyncState {
+            translations: HashMap::from([(
+                "de".to_string(),
+                HashMap::from([
+                    ("title".to_string(), "Titel".to_string()),
+                    ("old".to_string(), "Alt".to_string()),
+                    ("nested.gone".to_string(), "Weg".to_string()),
+                ]),
+            )]),
+        }
+        .save(&messages_dir)
+        .unwrap();
+
+        let cmd = |dry_run: bool| SyncCmd {
+            config: Some(config_path.clone()),
+            locales: vec!["de".to_string()],
+            prune: true,
+            dry_run,
+            no_backup: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+
+        // 模拟运行不修改文件
+        cmd(true).run(&RunContext::default()).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), original);
+
+        let result = cmd(false).run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.written, 1);
+        let content: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(content, serde_json::json!({"title": "Titel", "added": "Neu"}));
+    }
+
+    #[tokio::test]
+    async fn test_sync_as_of_fetches_history() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟保存历史的后端：记录翻译请求的查询参数
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let queries = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let seen = queries.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let body = if path.starts_with("/cli/capabilities") {
+                    r#"{"data": {"features": ["history"]}}"#
+                } else if path.starts_with("/cli/translations") {
+                    seen.lock().push(path);
+                    r#"{"data": {"title": {"de": "Alt"}}}"#
+                } else {
+                    r#"{"data": {}}"#
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let file = messages_dir.join("de").join("common.json");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(&file, r#"{"title": "Neu"}"#).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            force: true,
+            as_of: Some("2024-05-01".to_string()),
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+
+        assert_eq!(result.written, 1);
+        assert!(std::fs::read_to_string(&file).unwrap().contains("Alt"));
+        assert_eq!(
+            *queries.lock(),
+            vec!["/cli/translations?project_id=1&as_of=2024-05-01T00:00:00Z".to_string()]
+        );
+        // 时间点同步不更新增量缓存
+        assert!(!DeltaCache::path(&messages_dir).exists());
+    }
+
+    #[tokio::test]
+    async fn test_sync_force_guards_large_changes() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟出错的后端：所有值都与本地不同
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                let keys: Vec<String> = (0..12).map(|i| format!(r#""k{}": {{"de": "kaputt"}}"#, i)).collect();
+                let body = format!(r#"{{"data": {{{}}}}}"#, keys.join(", "));
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let file = messages_dir.join("de").join("common.json");
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        let local: serde_json::Map<String, serde_json::Value> =
+            (0..12).map(|i| (format!("k{}", i), serde_json::json!(format!("Wert {}", i)))).collect();
+        let original = serde_json::to_string_pretty(&local).unwrap();
+        std::fs::write(&file, &original).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                messages_dir.display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        // 非交互运行（CI）超过上限时失败，且不写入任何文件
+        std::env::set_var("CI", "true");
+        let cmd = SyncCmd {
+            config: Some(config_path),
+            force: true,
+            conflicts_report: Some(temp_dir.path().join("conflicts.json")),
+            ..Default::default()
+        };
+        let err = cmd.run(&RunContext::default()).await.unwrap_err().to_string();
+        assert!(err.contains("12 of 12 local keys (100.0%)"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), original);
+
+        // --yes 时不再确认，直接写入
+        let cmd = SyncCmd { yes: true, ..cmd };
+        let result = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(result.written, 1);
+        assert!(std::fs::read_to_string(&file).unwrap().contains("kaputt"));
+    }
 }
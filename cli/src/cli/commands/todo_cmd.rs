@@ -0,0 +1,143 @@
+//! Todo command implementation
+//!
+//! Writes a Markdown checklist of the keys a language still lacks (missing
+//! or empty while the source language has a value), with the source text
+//! and the files and lines involved, for handing to a translator who works
+//! directly in the repository.
+
+use crate::cli::RunContext;
+use crate::core::scanner::{report_quarantine, scan_messages_dir};
+use crate::core::timestamp;
+use crate::core::todo::TodoList;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Todo 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "todo", about = "Write a checklist of untranslated keys for a language")]
+pub struct TodoCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 目标语言
+    #[arg(long, value_name = "LANG")]
+    pub lang: String,
+
+    /// 源语言（默认：键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub source: Option<String>,
+
+    /// 输出文件（默认输出到标准输出）
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+}
+
+impl TodoCmd {
+    /// 执行 todo 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 扫描本地 messages 目录
+    /// 3. 收集未翻译的键
+    /// 4. 输出清单
+    pub async fn run(&self, ctx: &RunContext) -> Result<TodoList> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+
+        // 2. 扫描 messages 目录
+        let messages_dir = config.messages();
+        let scan = scan_messages_dir(&messages_dir)
+            .await
+            .context("Failed to scan messages directory")?;
+
+        let source = match &self.source {
+            Some(source) => source.clone(),
+            None => {
+                let mut languages: Vec<(&String, usize)> = scan
+                    .translations
+                    .iter()
+                    .filter(|(lang, _)| **lang != self.lang)
+                    .map(|(lang, keys)| (lang, keys.len()))
+                    .collect();
+                languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                languages
+                    .first()
+                    .map(|(lang, _)| lang.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No source language found in {}", config.messages_dir.display()))?
+            }
+        };
+        if source == self.lang {
+            return Err(anyhow::anyhow!("--lang and --source must differ"));
+        }
+
+        // 3. 收集未翻译的键
+        let todo = TodoList::collect(&messages_dir, &scan, &source, &self.lang)?;
+        let missing = todo.items.iter().filter(|item| item.missing).count();
+        info!(
+            "{}: {} untranslated key(s) compared to {} ({} missing, {} empty)",
+            todo.lang,
+            todo.items.len(),
+            todo.source,
+            missing,
+            todo.items.len() - missing
+        );
+
+        // 4. 输出清单
+        let content = todo.render_markdown(&timestamp::format_rfc3339(timestamp::now()));
+        match &self.out {
+            Some(path) => {
+                std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+                info!("TODO list written to {}", path.display());
+            }
+            None => ctx.reporter.write(&content),
+        }
+
+        report_quarantine(&scan.quarantine)?;
+        Ok(todo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_todo_writes_checklist() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Welcome", "save": "Save"}"#).unwrap();
+        std::fs::write(messages_dir.join("de/common.json"), r#"{"title": "Willkommen"}"#).unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let out = temp_dir.path().join("TODO_de.md");
+        let cmd = TodoCmd {
+            config: Some(config_path),
+            lang: "de".to_string(),
+            out: Some(out.clone()),
+            ..Default::default()
+        };
+        let todo = cmd.run(&RunContext::default()).await.unwrap();
+        assert_eq!(todo.source, "en");
+        assert_eq!(todo.items.len(), 1);
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert!(content.contains("- [ ] `save` — missing"));
+        assert!(content.contains("  > Save\n"));
+    }
+}
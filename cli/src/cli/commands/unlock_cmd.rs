@@ -0,0 +1,116 @@
+//! Unlock command implementation
+//!
+//! Releases a key prefix locked with `yflow lock`. Only the holder may
+//! unlock unless `--force` is given (e.g. for a lock left behind by a
+//! teammate who is away).
+
+use crate::cli::RunContext;
+use crate::api::client::APIClient;
+use crate::core::locks::current_holder;
+use crate::core::status_cache::StatusCache;
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Unlock 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "unlock", about = "Release a key prefix locked with `lock`")]
+pub struct UnlockCmd {
+    /// 要解锁的键前缀
+    #[arg(value_name = "KEY_PREFIX")]
+    pub prefix: String,
+
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 锁的持有团队（默认 I18N_TEAM，其次为系统用户名）
+    #[arg(long, value_name = "TEAM")]
+    pub team: Option<String>,
+
+    /// 解除其他持有者的锁
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl UnlockCmd {
+    /// 执行 unlock 命令
+    ///
+    /// # 返回
+    ///
+    /// 解锁成功返回 `true`，该前缀未被锁定时返回 `false`
+    pub async fn run(&self, ctx: &RunContext) -> Result<bool> {
+        let config_path = ctx.config_path(&self.config);
+        let config = ctx.load_config(config_path).await?;
+        let client = APIClient::from_config(&config)
+        .context("Failed to create API client")?;
+
+        let holder = current_holder(self.team.as_deref());
+        StatusCache::invalidate(&config.messages_dir)?;
+        let unlocked = client.unlock_keys(&self.prefix, &holder, self.force).await?;
+        if unlocked {
+            info!("Unlocked '{}'", self.prefix);
+        } else {
+            info!("'{}' is not locked", self.prefix);
+        }
+        Ok(unlocked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unlock_held_by_someone_else() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // 模拟后端：锁属于其他团队时返回 409
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let (status, body) = if request.starts_with("GET /cli/capabilities") {
+                    ("200 OK", r#"{"data": {"features": ["locks"]}}"#)
+                } else {
+                    ("409 Conflict", r#"{"error": "locked by growth"}"#)
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "{}", "apiKey": "k"}}"#,
+                temp_dir.path().display(),
+                base_url
+            ),
+        )
+        .unwrap();
+
+        let cmd = UnlockCmd {
+            prefix: "checkout.".to_string(),
+            config: Some(config_path),
+            team: Some("payments".to_string()),
+            force: false,
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err().to_string();
+        assert!(error.contains("--force"));
+    }
+}
@@ -0,0 +1,181 @@
+//! Validate command implementation
+//!
+//! Checks all local message files for invalid syntax, keys duplicated after
+//! flattening, empty values and placeholder mismatches against the source
+//! language, reporting each finding with file and line context. With
+//! `sourceLanguage` configured, translations whose source text changed since
+//! they were translated are reported as stale, and with `"dialect": "i18next"`
+//! plural keys missing a form the language needs are reported.
+//!
+//! Any finding fails the command, so it can gate CI before an import.
+
+use crate::cli::RunContext;
+use crate::core::failure::Failure;
+use crate::core::scanner::scan_messages_dir;
+use crate::core::stale;
+use crate::core::validate::{validate_messages_dir, Finding, FindingKind};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Validate 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "validate", about = "Validate local message files")]
+pub struct ValidateCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 只检查指定语言（可重复）
+    #[arg(long = "lang", value_name = "LANG")]
+    pub languages: Vec<String>,
+
+    /// 占位符比较的源语言（默认：配置的 sourceLanguage，否则为键最多的语言）
+    #[arg(long, value_name = "LANG")]
+    pub source: Option<String>,
+}
+
+impl ValidateCmd {
+    /// 执行 validate 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置
+    /// 2. 找出过期的翻译（配置了 sourceLanguage 时）
+    /// 3. 校验 messages 目录下的所有文件
+    /// 4. 输出问题
+    ///
+    /// # Errors
+    ///
+    /// 存在任何问题时返回错误（非零退出码）。
+    pub async fn run(&self, ctx: &RunContext) -> Result<Vec<Finding>> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置
+        let config = ctx.load_config(config_path).await?;
+        let messages_dir = config.messages();
+
+        // 2. 找出过期的翻译
+        let stale = match &config.source_language {
+            Some(source) => {
+                let local = scan_messages_dir(&messages_dir)
+                    .await
+                    .context("Failed to scan messages directory")?
+                    .translations;
+                stale::track(&config.messages_dir, source, &local)?
+            }
+            None => Vec::new(),
+        };
+
+        // 3. 校验文件
+        info!("Validating {}...", config.messages_dir.display());
+        let findings = validate_messages_dir(
+            &messages_dir,
+            &self.languages,
+            self.source.as_deref().or(config.source_language.as_deref()),
+            &stale,
+        )?;
+
+        // 4. 输出问题
+        if findings.is_empty() {
+            ctx.reporter.success("No problems found.");
+            return Ok(findings);
+        }
+        for finding in &findings {
+            ctx.reporter.output(finding);
+        }
+        ctx.reporter.output("");
+
+        let count = |kind: FindingKind| findings.iter().filter(|f| f.kind == kind).count();
+        ctx.reporter.output(format!(
+            "{} problem(s): {} invalid file(s), {} duplicate key(s), {} empty value(s), {} placeholder mismatch(es), {} stale translation(s), {} incomplete plural(s)",
+            findings.len(),
+            count(FindingKind::InvalidFile),
+            count(FindingKind::DuplicateKey),
+            count(FindingKind::EmptyValue),
+            count(FindingKind::PlaceholderMismatch),
+            count(FindingKind::StaleTranslation),
+            count(FindingKind::IncompletePlural)
+        ));
+
+        Err(Failure::Validation.error(format!(
+            "Validation failed with {} problem(s)",
+            findings.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_validate_cmd() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        for (lang, content) in [("en", r#"{"title": "Hi {name}"}"#), ("de", r#"{"title": "Hallo"}"#)] {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        }
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+
+        let cmd = ValidateCmd {
+            config: Some(config_path.clone()),
+            source: Some("en".to_string()),
+            ..Default::default()
+        };
+        let error = cmd.run(&RunContext::default()).await.unwrap_err();
+        assert!(error.to_string().contains("1 problem(s)"));
+
+        let cmd = ValidateCmd {
+            config: Some(config_path),
+            languages: vec!["en".to_string()],
+            ..Default::default()
+        };
+        assert!(cmd.run(&RunContext::default()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_stale_translations() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let write = |lang: &str, content: &str| {
+            std::fs::create_dir_all(messages_dir.join(lang)).unwrap();
+            std::fs::write(messages_dir.join(lang).join("common.json"), content).unwrap();
+        };
+        write("en", r#"{"title": "Home", "cta": "Buy"}"#);
+        write("de", r#"{"title": "Start", "cta": "Kaufen"}"#);
+        let config_path = temp_dir.path().join(".i18nrc.json");
+        std::fs::write(
+            &config_path,
+            format!(
+                r#"{{"messagesDir": "{}", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "k", "sourceLanguage": "en"}}"#,
+                messages_dir.display()
+            ),
+        )
+        .unwrap();
+        let cmd = ValidateCmd {
+            config: Some(config_path),
+            ..Default::default()
+        };
+
+        // 第一次运行只记录
+        assert!(cmd.run(&RunContext::default()).await.unwrap().is_empty());
+
+        write("en", "{\n  \"title\": \"Home page\",\n  \"cta\": \"Buy\"\n}");
+        assert!(cmd.run(&RunContext::default()).await.unwrap_err().to_string().contains("1 problem(s)"));
+
+        write("de", r#"{"title": "Startseite", "cta": "Kaufen"}"#);
+        assert!(cmd.run(&RunContext::default()).await.unwrap().is_empty());
+    }
+}
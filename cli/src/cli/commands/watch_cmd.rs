@@ -0,0 +1,223 @@
+//! Watch command implementation
+//!
+//! Watches the messages directory and re-runs `import` whenever locale files
+//! change, so edits reach the backend while developing.
+//!
+//! # Features
+//!
+//! - File system notifications via `notify`, debounced (`--debounce`) so a
+//!   burst of saves triggers a single import
+//! - Only pushes keys changed since the last import (delta cache), after an
+//!   initial import on start-up
+//! - Ignores non-translation files and the `.yflow-cache` and `.yflow-backup` directories
+//! - Also watches languages configured in `languageDirs`
+//! - Import errors (e.g. a half-saved file) are reported and watching continues
+
+use super::ImportCmd;
+use crate::cli::RunContext;
+use crate::core::backup::BACKUP_DIR;
+use crate::core::download::CACHE_DIR;
+use crate::core::MessagesDir;
+use anyhow::{Context, Result};
+use clap::Parser;
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+/// Watch 命令参数
+#[derive(Parser, Debug, Default)]
+#[command(name = "watch", about = "Watch the messages directory and import changes automatically")]
+pub struct WatchCmd {
+    /// 配置文件路径
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// 最后一次文件变化后等待的时间（毫秒），期间的变化合并为一次导入
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub debounce: u64,
+
+    /// 当前执行导入的团队（用于归属检查，也可通过 I18N_TEAM 设置）
+    #[arg(long, value_name = "TEAM")]
+    pub team: Option<String>,
+
+    /// 只导入指定语言（本地语言代码，可重复）
+    #[arg(long = "locale", value_name = "LOCALE")]
+    pub locales: Vec<String>,
+
+    /// 排除指定语言（本地语言代码，可重复）
+    #[arg(long = "exclude-locale", value_name = "LOCALE")]
+    pub exclude_locales: Vec<String>,
+
+    /// 启动时不执行首次导入
+    #[arg(long)]
+    pub no_initial: bool,
+}
+
+impl WatchCmd {
+    /// 执行 watch 命令
+    ///
+    /// # 处理流程
+    ///
+    /// 1. 加载配置，确定监听目录
+    /// 2. 执行首次导入（记录增量缓存）
+    /// 3. 启动文件监听
+    /// 4. 合并变化并重新导入，直到进程被中断
+    pub async fn run(&self, ctx: &RunContext) -> Result<()> {
+        let config_path = ctx.config_path(&self.config);
+
+        // 1. 加载配置，确定监听目录
+        let config = ctx.load_config(config_path.clone()).await?;
+        let messages_dir = config.messages();
+        if !messages_dir.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Messages directory not found: {}",
+                messages_dir.display()
+            ));
+        }
+        let import = ImportCmd {
+            config: config_path.clone(),
+            team: self.team.clone(),
+            locales: self.locales.clone(),
+            exclude_locales: self.exclude_locales.clone(),
+            ..ImportCmd::default()
+        };
+
+        // 2. 执行首次导入（记录增量缓存）
+        if !self.no_initial {
+            run_import(&import, ctx).await;
+        }
+
+        // 3. 启动文件监听（回调在 notify 的线程中执行，通过通道交给异步循环）
+        let (tx, mut rx) = unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            match event {
+                Ok(event) if !event.kind.is_access() => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Watch error: {}", e),
+            }
+        })
+        .context("Failed to start file watcher")?;
+        watcher
+            .watch(&messages_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", messages_dir.display()))?;
+        // languageDirs 中位于 messages 目录之外的语言目录
+        for dir in messages_dir.language_dirs.values().filter(|dir| dir.is_dir() && !dir.starts_with(&messages_dir)) {
+            watcher
+                .watch(dir, RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        }
+        info!(
+            "Watching {} for changes (debounce {}ms, Ctrl-C to stop)...",
+            messages_dir.display(),
+            self.debounce
+        );
+
+        // 4. 合并变化并重新导入
+        let debounce = Duration::from_millis(self.debounce);
+        while let Some(changed) = next_batch(&mut rx, &messages_dir, debounce).await {
+            info!("Detected changes in {} file(s):", changed.len());
+            for path in changed.iter().take(10) {
+                info!("  - {}", path.strip_prefix(&messages_dir).unwrap_or(path).display());
+            }
+            if changed.len() > 10 {
+                info!("  ... and {} more", changed.len() - 10);
+            }
+            run_import(&import, ctx).await;
+        }
+
+        Ok(())
+    }
+}
+
+/// 执行一次导入，失败时只报告错误（继续监听）
+async fn run_import(import: &ImportCmd, ctx: &RunContext) {
+    match import.run(ctx).await {
+        Ok(result) if result.added + result.updated + result.failed > 0 => info!(
+            "Imported: {} added, {} updated, {} failed",
+            result.added, result.updated, result.failed
+        ),
+        Ok(_) => {}
+        Err(e) => warn!("Import failed: {:#}", e),
+    }
+}
+
+/// 等待下一组文件变化
+///
+/// 收到第一个相关变化后继续收集，直到 `debounce` 内没有新的变化。
+/// 通道关闭时返回 `None`。
+async fn next_batch(
+    rx: &mut UnboundedReceiver<PathBuf>,
+    messages_dir: &MessagesDir,
+    debounce: Duration,
+) -> Option<BTreeSet<PathBuf>> {
+    let mut changed = BTreeSet::new();
+    while changed.is_empty() {
+        let path = rx.recv().await?;
+        if is_relevant(messages_dir, &path) {
+            changed.insert(path);
+        }
+    }
+    while let Ok(Some(path)) = timeout(debounce, rx.recv()).await {
+        if is_relevant(messages_dir, &path) {
+            changed.insert(path);
+        }
+    }
+    Some(changed)
+}
+
+/// 判断变化的路径是否是需要导入的翻译文件
+///
+/// 忽略 `.yflow-cache` 下的文件（导入本身会写入）、`.yflow-backup` 下的备份和非翻译文件。
+fn is_relevant(messages_dir: &MessagesDir, path: &Path) -> bool {
+    let relative = path.strip_prefix(messages_dir).unwrap_or(path);
+    if relative
+        .components()
+        .any(|c| matches!(c, Component::Normal(name) if name == CACHE_DIR || name == BACKUP_DIR))
+    {
+        return false;
+    }
+    messages_dir.is_translation_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_relevant() {
+        let dir = &MessagesDir::new("/project/messages");
+        assert!(is_relevant(dir, &dir.join("en/common.json")));
+        assert!(is_relevant(dir, &dir.join("de/app.yml")));
+        assert!(!is_relevant(dir, &dir.join("en/notes.txt")));
+        assert!(!is_relevant(dir, &dir.join("template.pot")));
+        assert!(!is_relevant(dir, &dir.join(".yflow-cache/delta.json")));
+        assert!(!is_relevant(dir, &dir.join(".yflow-backup/20261016T120000Z/files/en/common.json")));
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_debounces() {
+        let dir = MessagesDir::new("/project/messages");
+        let (tx, mut rx) = unbounded_channel();
+        tx.send(dir.join(".yflow-cache/delta.json")).unwrap();
+        tx.send(dir.join("en/common.json")).unwrap();
+        tx.send(dir.join("en/common.json")).unwrap();
+        tx.send(dir.join("de/common.json")).unwrap();
+
+        let batch = next_batch(&mut rx, &dir, Duration::from_millis(20)).await.unwrap();
+        assert_eq!(
+            batch.into_iter().collect::<Vec<_>>(),
+            vec![dir.join("de/common.json"), dir.join("en/common.json")]
+        );
+
+        drop(tx);
+        assert!(next_batch(&mut rx, &dir, Duration::from_millis(20)).await.is_none());
+    }
+}
@@ -0,0 +1,52 @@
+//! Per-run context
+//!
+//! Global flags that change how a command loads its configuration
+//! (`--config`, `--timeout`, `--connect-timeout`, `--ignore-pins`,
+//! `--author`) and the reporter chosen by `--message-format`/`-q` are
+//! collected once by `main` and passed to every command's `run`, so nothing
+//! about the run is kept in process-wide state.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::core::config::{load_config_with, ConfigOverrides};
+use crate::core::I18nConfig;
+use crate::ui::reporter::{HumanReporter, Reporter};
+
+/// 一次运行的上下文
+#[derive(Debug, Clone)]
+pub struct RunContext {
+    /// 全局 `--config` 指定的配置文件
+    pub config: Option<PathBuf>,
+    /// 命令行对配置的覆盖
+    pub overrides: ConfigOverrides,
+    /// 本次运行的输出层
+    pub reporter: Arc<dyn Reporter>,
+}
+
+impl Default for RunContext {
+    fn default() -> Self {
+        Self {
+            config: None,
+            overrides: ConfigOverrides::default(),
+            reporter: Arc::new(HumanReporter),
+        }
+    }
+}
+
+impl RunContext {
+    /// 配置文件路径（命令自己的 `--config` 优先）
+    pub fn config_path(&self, command_config: &Option<PathBuf>) -> Option<PathBuf> {
+        command_config.clone().or_else(|| self.config.clone())
+    }
+
+    /// 加载配置并应用命令行覆盖
+    ///
+    /// # Errors
+    ///
+    /// 配置文件不存在、格式错误或校验失败时返回错误
+    pub async fn load_config(&self, config_path: Option<PathBuf>) -> Result<I18nConfig> {
+        load_config_with(config_path, &self.overrides).await
+    }
+}
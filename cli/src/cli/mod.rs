@@ -1,15 +1,46 @@
 //! CLI argument parsing and command definitions
 //!
 //! Uses clap for command-line interface parsing.
-//! Provides subcommands for import, sync, init, version, and help operations.
+//! Provides subcommands for import, import-delivery, import-file, sync, export, lint, fmt, gen-tests, add-language, status, diff-local, todo, missing, stats, remove-language, graph, validate, check-placeholders, set, get, explain, replace, rollback, qa-report, watch, lock, unlock, auth, config, bench, init, version, and help operations.
 
 mod commands;
+mod context;
 
+pub use commands::AddLanguageCmd;
+pub use commands::AuthCmd;
+pub use commands::BenchCmd;
+pub use commands::CheckPlaceholdersCmd;
+pub use commands::ConfigCmd;
+pub use commands::DiffLocalCmd;
+pub use commands::ExplainCmd;
+pub use commands::ExportCmd;
+pub use commands::FmtCmd;
+pub use commands::GenTestsCmd;
+pub use commands::GetCmd;
+pub use commands::GraphCmd;
 pub use commands::ImportCmd;
+pub use commands::ImportDeliveryCmd;
+pub use commands::ImportFileCmd;
+pub use commands::LintCmd;
+pub use commands::LockCmd;
+pub use commands::MissingCmd;
+pub use commands::QaReportCmd;
+pub use commands::RemoveLanguageCmd;
+pub use commands::ReplaceCmd;
+pub use commands::RollbackCmd;
+pub use commands::SetCmd;
+pub use commands::StatsCmd;
+pub use commands::StatusCmd;
 pub use commands::SyncCmd;
+pub use commands::TodoCmd;
+pub use commands::UnlockCmd;
+pub use commands::ValidateCmd;
+pub use commands::WatchCmd;
+pub use context::RunContext;
 
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// YFlow CLI - Translation management tool
 ///
@@ -25,9 +56,53 @@ pub struct CliArgs {
     #[arg(short, long, value_name = "PATH", global = true)]
     pub config: Option<PathBuf>,
 
-    /// Enable verbose output
-    #[arg(short, long, global = true)]
-    pub verbose: bool,
+    /// Increase log verbosity (-v debug, -vv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only print errors and requested results: no info logs, progress bars or spinners
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Log format: pretty (one line per event) or json (one object per event)
+    #[arg(long, value_enum, value_name = "FORMAT", global = true, default_value = "pretty")]
+    pub log_format: crate::ui::logging::LogFormat,
+
+    /// Also append logs to this file
+    #[arg(long, value_name = "PATH", global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// Abort gracefully once the run exceeds this time budget (e.g. 90s, 10m, 1h)
+    #[arg(long, value_name = "DURATION", global = true, value_parser = crate::core::deadline::parse_duration)]
+    pub deadline: Option<Duration>,
+
+    /// Request timeout in seconds for backend calls (overrides `timeoutSecs`, default 30)
+    #[arg(long, value_name = "SECS", global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Connect timeout in seconds for backend calls (overrides `connectTimeoutSecs`, default 10)
+    #[arg(long, value_name = "SECS", global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    pub connect_timeout: Option<u64>,
+
+    /// Skip `pinnedSha256` certificate checks for this run (e.g. after a certificate rotation)
+    #[arg(long, global = true)]
+    pub ignore_pins: bool,
+
+    /// Attribute pushed changes to this author, "Name <email>" (default: I18N_AUTHOR or the git user)
+    #[arg(long, value_name = "AUTHOR", global = true, value_parser = crate::core::attribution::Author::parse)]
+    pub author: Option<crate::core::attribution::Author>,
+
+    /// Print API keys and secrets in logs and errors instead of `***` (for debugging)
+    #[arg(long, global = true)]
+    pub show_secrets: bool,
+
+    /// Status message format: human (default) or json (one object per message on stderr)
+    #[arg(long, value_enum, value_name = "FORMAT", global = true, default_value = "human")]
+    pub message_format: crate::ui::reporter::MessageFormat,
+
+    /// CI mode: no progress bars, spinners or prompts, plain logs, and exit codes 2 (drift), 3 (validation), 4 (auth)
+    #[arg(long, global = true)]
+    pub ci: bool,
 
     #[command(subcommand)]
     pub command: Commands,
@@ -37,7 +112,32 @@ pub struct CliArgs {
 ///
 /// 包含所有可用的子命令：
 /// - import: 从本地 messages 目录导入翻译到后端
+/// - import-delivery: 导入供应商交付包
+/// - import-file: 从 CSV/TSV 表格导入翻译
 /// - sync: 从后端同步翻译到本地 messages 目录
+/// - export: 导出翻译交付包（支持按快照增量导出）
+/// - lint: 按内容规则检查本地翻译
+/// - fmt: 按命名空间拆分超出键数上限的文件
+/// - gen-tests: 生成 ICU 消息冒烟测试文件
+/// - add-language: 创建新语言的本地文件并在后端注册
+/// - status: 显示本地与后端之间的差异
+/// - diff-local: 比较两个本地 messages 目录
+/// - todo: 生成某个语言未翻译键的清单
+/// - remove-language: 删除语言（本地备份，可选删除后端）
+/// - graph: 导出命名空间、文件与源码引用关系图
+/// - validate: 校验本地翻译文件（语法、重复键、空值、占位符）
+/// - set: 修改单个键在本地文件中的值（可立即推送）
+/// - get: 查询单个键在本地或后端的值
+/// - explain: 显示单个键的来源、各语言的值、同步状态和 lint 问题
+/// - replace: 批量查找并替换翻译值
+/// - rollback: 从 sync 的备份恢复本地文件
+/// - qa-report: 导出各语言的翻译质量评分报告
+/// - watch: 监听目录并自动导入变化
+/// - lock: 锁定键前缀或列出当前的锁
+/// - unlock: 解除键前缀的锁
+/// - auth: 在系统密钥环中保存或删除 API 密钥
+/// - config: 输出配置文件的 JSON Schema，或配置文件与用户目录的位置
+/// - bench: 测量扫描、展平和上传的吞吐量
 /// - init: 创建示例配置文件
 /// - version: 显示版本信息
 /// - help: 显示帮助信息
@@ -53,6 +153,26 @@ pub enum Commands {
     #[command(name = "import")]
     Import(ImportCmd),
 
+    /// Import a vendor translation delivery
+    ///
+    /// Unpacks a zip/directory of XLIFF, CSV or PO files, remaps language
+    /// codes and key names with `--map`, validates placeholders against the
+    /// source text and pushes the result to the backend.
+    ///
+    /// Example: `yflow import-delivery delivery.zip --map mapping.csv`
+    #[command(name = "import-delivery")]
+    ImportDelivery(ImportDeliveryCmd),
+
+    /// Import translations from a CSV/TSV spreadsheet export
+    ///
+    /// Reads a `.csv` or `.tsv` file with a `key,<lang>,<lang>,...` header
+    /// and pushes it with the regular import pipeline (routing, batching,
+    /// retries). Empty cells are skipped.
+    ///
+    /// Example: `yflow import-file copy.tsv --dry-run`
+    #[command(name = "import-file")]
+    ImportFile(ImportFileCmd),
+
     /// Sync translations from backend to local messages directory
     ///
     /// Downloads translations from the YFlow backend and writes them to
@@ -62,6 +182,260 @@ pub enum Commands {
     #[command(name = "sync")]
     Sync(SyncCmd),
 
+    /// Export translations as a handoff package
+    ///
+    /// Writes one flat JSON file per language plus a manifest. With
+    /// `--since-snapshot`, only keys changed since that snapshot (id or
+    /// timestamp) are exported. Each export records a new snapshot.
+    ///
+    /// Example: `yflow export --since-snapshot 20261001T000000Z --out handoff`
+    #[command(name = "export")]
+    Export(ExportCmd),
+
+    /// Check local translations against content rules
+    ///
+    /// Evaluates the `lintRules` from the config (forbidden phrases, required
+    /// patterns, sentence case) per language. Fails if any error-level rule
+    /// is violated.
+    ///
+    /// Example: `yflow lint --lang en`
+    #[command(name = "lint")]
+    Lint(LintCmd),
+
+    /// Split locale files holding more keys than allowed
+    ///
+    /// Lists the files over `maxKeysPerFile`; with `--split` each of them is
+    /// split one namespace level deeper (`en/common.json` →
+    /// `en/common/auth.json`, ...).
+    ///
+    /// Example: `yflow fmt --split`
+    #[command(name = "fmt")]
+    Fmt(FmtCmd),
+
+    /// Generate an ICU smoke-test file for the host project
+    ///
+    /// Renders every message that uses ICU arguments with sample values
+    /// (covering plural and select branches). The output format follows the
+    /// extension of `--out`: `.rs`, `.ts` or `.js`.
+    ///
+    /// Example: `yflow gen-tests --out tests/i18n_smoke.ts`
+    #[command(name = "gen-tests")]
+    GenTests(GenTestsCmd),
+
+    /// Scaffold a new language
+    ///
+    /// Creates the language directory mirroring the source language's files
+    /// (empty values, or source values with `--fill-source`), registers the
+    /// language with the backend and updates `languageMapping` when the backend
+    /// code differs.
+    ///
+    /// Example: `yflow add-language fr --from en`
+    #[command(name = "add-language")]
+    AddLanguage(AddLanguageCmd),
+
+    /// Show drift between local files and the backend
+    ///
+    /// Prints, per language, the number of keys that are local-only,
+    /// backend-only, or differ in value. Exits non-zero when drift exists so
+    /// CI can gate merges on it.
+    ///
+    /// Example: `yflow status --keys`
+    #[command(name = "status")]
+    Status(StatusCmd),
+
+    /// Compare two local messages directories
+    ///
+    /// Scans both directories and prints, per language, the keys added,
+    /// removed and changed relative to the second (base) directory. Needs no
+    /// configuration file or backend.
+    ///
+    /// Example: `yflow diff-local ./messages ./messages-old --keys`
+    #[command(name = "diff-local")]
+    DiffLocal(DiffLocalCmd),
+
+    /// Write a checklist of untranslated keys for a language
+    ///
+    /// Lists the keys missing or empty in the language but present in the
+    /// source language, grouped by the file to translate them in, with the
+    /// source text and file locations, as Markdown.
+    ///
+    /// Example: `yflow todo --lang de --out TODO_de.md`
+    #[command(name = "todo")]
+    Todo(TodoCmd),
+
+    /// List untranslated keys of every language
+    ///
+    /// Compares every language with the base language and lists the keys it
+    /// lacks or leaves empty, with counts and completion percentages, as a
+    /// table, JSON or CSV.
+    ///
+    /// Example: `yflow missing --base en --format csv --out missing.csv`
+    #[command(name = "missing")]
+    Missing(MissingCmd),
+
+    /// Show translation coverage per language and namespace
+    ///
+    /// Shows the share of the base language's keys every language translates,
+    /// per top-level namespace and in total, computed from the local files or
+    /// from the backend with `--source backend`.
+    ///
+    /// Example: `yflow stats --source backend --lang de`
+    #[command(name = "stats")]
+    Stats(StatsCmd),
+
+    /// Remove a language
+    ///
+    /// Moves the local language directory into `.yflow-backups/`, removes its
+    /// `languageMapping` entry and, with `--remote`, deletes the locale on the
+    /// backend.
+    ///
+    /// Example: `yflow remove-language nb_NO --remote`
+    #[command(name = "remove-language")]
+    RemoveLanguage(RemoveLanguageCmd),
+
+    /// Export the key reference graph
+    ///
+    /// Outputs namespaces, the translation files defining them and the source
+    /// files referencing their keys (`--src`) as Graphviz DOT or JSON.
+    ///
+    /// Example: `yflow graph --format dot --src src | dot -Tsvg > i18n.svg`
+    #[command(name = "graph")]
+    Graph(GraphCmd),
+
+    /// Validate local message files
+    ///
+    /// Reports invalid files, keys duplicated after flattening, empty values and
+    /// placeholders that differ from the source language, with file and line
+    /// context. Exits non-zero when anything is found.
+    ///
+    /// Example: `yflow validate --source en`
+    #[command(name = "validate")]
+    Validate(ValidateCmd),
+
+    /// Compare placeholders with the source language
+    ///
+    /// Reports translations that dropped, renamed or added an interpolation
+    /// placeholder (`{name}`, `{{var}}`, `%s` or a `placeholderPatterns`
+    /// regex) compared to the source language. Exits non-zero when anything
+    /// is found.
+    ///
+    /// Example: `yflow check-placeholders --source en`
+    #[command(name = "check-placeholders")]
+    CheckPlaceholders(CheckPlaceholdersCmd),
+
+    /// Set the value of a key in the local files
+    ///
+    /// Writes the value into the file that holds the key (keeping its format and
+    /// nesting) and, with `--push`, sends it to the backend right away.
+    ///
+    /// Example: `yflow set user.title --lang de "Profil"`
+    #[command(name = "set")]
+    Set(SetCmd),
+
+    /// Print the value of a key
+    ///
+    /// Reads the key from the local files, or from the backend with `--remote`.
+    /// With a single `--lang` only the value is printed; `--json` prints a
+    /// `{language: value}` object.
+    ///
+    /// Example: `yflow get user.title --lang de`
+    #[command(name = "get")]
+    Get(GetCmd),
+
+    /// Show everything known about one key
+    ///
+    /// Prints where the key is defined locally, its local and backend values
+    /// per language, the value of the last sync and what the next sync would
+    /// do with it, its owner and lock, and any lint findings.
+    ///
+    /// Example: `yflow explain user.title`
+    #[command(name = "explain")]
+    Explain(ExplainCmd),
+
+    /// Find and replace text in translation values
+    ///
+    /// Rewrites matching values in the local files and reports each changed key;
+    /// `--regex` enables regular expressions and `--push` sends the changes to
+    /// the backend.
+    ///
+    /// Example: `yflow replace --lang de --find "Fa. ACME" --replace "ACME GmbH"`
+    #[command(name = "replace")]
+    Replace(ReplaceCmd),
+
+    /// Restore local files from a backup taken by `sync`
+    ///
+    /// Restores the latest backup in `.yflow-backup/`, or the one chosen with
+    /// `--to` (id or timestamp), and removes files the sync created. `--list`
+    /// shows the available backups.
+    ///
+    /// Example: `yflow rollback --to 2026-10-01T12:00:00Z`
+    #[command(name = "rollback")]
+    Rollback(RollbackCmd),
+
+    /// Export a translation quality report
+    ///
+    /// Scores each language against the source language (placeholder errors,
+    /// length violations, empty values, markup issues) and writes the result as
+    /// JSON or HTML.
+    ///
+    /// Example: `yflow qa-report --format html --out qa.html --release v2.3`
+    #[command(name = "qa-report")]
+    QaReport(QaReportCmd),
+
+    /// Watch the messages directory and import changes automatically
+    ///
+    /// Runs an import on start-up, then re-imports the keys changed in
+    /// locale files after each burst of edits (debounced).
+    ///
+    /// Example: `yflow watch --debounce 1000`
+    #[command(name = "watch")]
+    Watch(WatchCmd),
+
+    /// Lock a key prefix while editing, or list locks
+    ///
+    /// Imports by other teams skip keys under a locked prefix. Without a
+    /// prefix the current locks are listed.
+    ///
+    /// Example: `yflow lock checkout. --ttl 2h`
+    #[command(name = "lock")]
+    Lock(LockCmd),
+
+    /// Release a key prefix locked with `lock`
+    ///
+    /// Example: `yflow unlock checkout.`
+    #[command(name = "unlock")]
+    Unlock(UnlockCmd),
+
+    /// Save or remove the API key in the system keyring
+    ///
+    /// `auth login` verifies the key and stores it per `apiUrl` and
+    /// `projectId`; the config then works with an empty `apiKey`.
+    ///
+    /// Example: `yflow auth login`
+    #[command(name = "auth")]
+    Auth(AuthCmd),
+
+    /// Inspect the configuration
+    ///
+    /// `config schema` prints the JSON Schema generated from the same types
+    /// the configuration is parsed with, for editor autocomplete and
+    /// validation; `config paths` shows the config file in use and the
+    /// per-user config, cache and data directories.
+    ///
+    /// Example: `yflow config schema --out .i18nrc.schema.json`
+    #[command(name = "config")]
+    Config(ConfigCmd),
+
+    /// Measure scan, flatten and upload throughput on synthetic translations
+    ///
+    /// Generates locale files in a temporary directory and times each
+    /// phase; `--dry-backend` also times uploads to a local mock backend.
+    /// Attach the output to performance bug reports.
+    ///
+    /// Example: `yflow bench --keys 10000 --dry-backend`
+    #[command(name = "bench")]
+    Bench(BenchCmd),
+
     /// Initialize a sample configuration file
     ///
     /// Creates a `.i18nrc.json` configuration file in the current directory
@@ -115,6 +489,7 @@ mod tests {
         let cmd = Commands::Import(ImportCmd {
             config: None,
             dry_run: false,
+            ..Default::default()
         });
         assert!(matches!(cmd, Commands::Import(_)));
     }
@@ -125,6 +500,7 @@ mod tests {
             config: None,
             dry_run: false,
             force: false,
+            ..Default::default()
         });
         assert!(matches!(cmd, Commands::Sync(_)));
     }
@@ -178,26 +554,155 @@ mod tests {
     /// 测试 CLI 参数解析 - 基本解析
     #[test]
     fn test_cli_args_parse_import() {
-        let args = CliArgs::parse_from(&["yflow", "import"]);
+        let args = CliArgs::parse_from(["yflow", "import"]);
         assert!(matches!(args.command, Commands::Import(_)));
         assert!(args.config.is_none());
     }
 
     #[test]
     fn test_cli_args_parse_sync() {
-        let args = CliArgs::parse_from(&["yflow", "sync"]);
+        let args = CliArgs::parse_from(["yflow", "sync"]);
         assert!(matches!(args.command, Commands::Sync(_)));
     }
 
+    #[test]
+    fn test_cli_args_parse_sync_as_of() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--as-of", "2024-05-01T00:00:00Z"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.as_of.as_deref(), Some("2024-05-01T00:00:00Z"));
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--as-of", "2024-05-01", "--chunked"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_offline() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--offline", "--locale", "de"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert!(cmd.offline);
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--offline", "--as-of", "2024-05-01"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_change_guard() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--force", "--max-change-percent", "50", "--yes"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.max_change_percent, Some(50));
+            assert!(cmd.yes);
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--max-change-percent", "150"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_prefer() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--prefer", "interactive"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.prefer, clap::ValueEnum::from_str("interactive", false).unwrap());
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--prefer", "both"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_check_and_patch() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--check", "--patch"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert!(cmd.check);
+            assert!(cmd.patch);
+            assert!(!cmd.dry_run);
+        } else {
+            panic!("Expected Sync command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_commit() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--commit", "chore: sync translations", "--sign"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.commit.as_deref(), Some("chore: sync translations"));
+            assert!(cmd.sign);
+        } else {
+            panic!("Expected Sync command");
+        }
+        let args = CliArgs::parse_from(["yflow", "sync", "--branch-per-run", "--push-remote", "origin"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert!(cmd.branch_per_run);
+            assert_eq!(cmd.push_remote.as_deref(), Some("origin"));
+            assert_eq!(cmd.commit, None);
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--push-remote", "origin"]).is_err());
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--branch-per-run", "--sign"]).is_ok());
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--branch-per-run", "--check"]).is_err());
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--commit", "x", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_summary_json() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--summary-json", "summary.json"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.summary_json, Some(PathBuf::from("summary.json")));
+        } else {
+            panic!("Expected Sync command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_output() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--dry-run", "--output", "json"]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.output, clap::ValueEnum::from_str("json", false).unwrap());
+        } else {
+            panic!("Expected Sync command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--patch", "--output", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_import_key_filters() {
+        let args = CliArgs::parse_from([
+            "yflow", "import", "--key-prefix", "checkout.", "--key-glob", "*.label", "--fail-fast",
+        ]);
+        if let Commands::Import(cmd) = args.command {
+            assert_eq!(cmd.key_prefixes, vec!["checkout.".to_string()]);
+            assert_eq!(cmd.key_globs, vec!["*.label".to_string()]);
+            assert!(cmd.fail_fast);
+            assert!(!cmd.no_cache);
+        } else {
+            panic!("Expected Import command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_sync_locales() {
+        let args = CliArgs::parse_from([
+            "yflow", "sync", "--locale", "zh_CN", "--locale", "de", "--exclude-locale", "de",
+        ]);
+        if let Commands::Sync(cmd) = args.command {
+            assert_eq!(cmd.locales, vec!["zh_CN".to_string(), "de".to_string()]);
+            assert_eq!(cmd.exclude_locales, vec!["de".to_string()]);
+        } else {
+            panic!("Expected Sync command");
+        }
+    }
+
     #[test]
     fn test_cli_args_parse_version() {
-        let args = CliArgs::parse_from(&["yflow", "version"]);
+        let args = CliArgs::parse_from(["yflow", "version"]);
         assert!(matches!(args.command, Commands::Version));
     }
 
     #[test]
     fn test_cli_args_parse_help_cmd() {
-        let args = CliArgs::parse_from(&["yflow", "help-cmd"]);
+        let args = CliArgs::parse_from(["yflow", "help-cmd"]);
         if let Commands::HelpCmd { command } = args.command {
             assert!(command.is_none());
         } else {
@@ -207,7 +712,7 @@ mod tests {
 
     #[test]
     fn test_cli_args_parse_help_cmd_with_command() {
-        let args = CliArgs::parse_from(&["yflow", "help-cmd", "sync"]);
+        let args = CliArgs::parse_from(["yflow", "help-cmd", "sync"]);
         if let Commands::HelpCmd { command } = args.command {
             assert_eq!(command, Some("sync".to_string()));
         } else {
@@ -215,22 +720,372 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_args_parse_lint_with_lang() {
+        let args = CliArgs::parse_from(["yflow", "lint", "--lang", "en", "--lang", "de"]);
+        if let Commands::Lint(cmd) = args.command {
+            assert_eq!(cmd.languages, vec!["en".to_string(), "de".to_string()]);
+        } else {
+            panic!("Expected Lint command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_fmt() {
+        let args = CliArgs::parse_from(["yflow", "fmt", "--split", "--max-keys", "300", "--dry-run"]);
+        if let Commands::Fmt(cmd) = args.command {
+            assert!(cmd.split && cmd.dry_run);
+            assert_eq!(cmd.max_keys, Some(300));
+        } else {
+            panic!("Expected Fmt command");
+        }
+
+        assert!(CliArgs::try_parse_from(["yflow", "fmt", "--max-keys", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_gen_tests() {
+        let args = CliArgs::parse_from(["yflow", "gen-tests", "--out", "tests/i18n_smoke.rs"]);
+        if let Commands::GenTests(cmd) = args.command {
+            assert_eq!(cmd.out, PathBuf::from("tests/i18n_smoke.rs"));
+        } else {
+            panic!("Expected GenTests command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_export_since_snapshot() {
+        let args = CliArgs::parse_from(["yflow", "export", "--since-snapshot", "2026-10-01"]);
+        if let Commands::Export(cmd) = args.command {
+            assert_eq!(cmd.since_snapshot.as_deref(), Some("2026-10-01"));
+            assert!(!cmd.no_snapshot);
+        } else {
+            panic!("Expected Export command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_import_delivery() {
+        let args = CliArgs::parse_from(["yflow", "import-delivery", "delivery.zip", "--map", "mapping.csv"]);
+        if let Commands::ImportDelivery(cmd) = args.command {
+            assert_eq!(cmd.delivery, PathBuf::from("delivery.zip"));
+            assert_eq!(cmd.mapping, Some(PathBuf::from("mapping.csv")));
+        } else {
+            panic!("Expected ImportDelivery command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_import_file() {
+        let args = CliArgs::parse_from(["yflow", "import-file", "copy.csv", "--dry-run", "--concurrency", "4"]);
+        if let Commands::ImportFile(cmd) = args.command {
+            assert_eq!(cmd.file, PathBuf::from("copy.csv"));
+            assert!(cmd.dry_run);
+            assert_eq!(cmd.concurrency, 4);
+        } else {
+            panic!("Expected ImportFile command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_add_language() {
+        let args = CliArgs::parse_from(["yflow", "add-language", "fr", "--from", "en", "--fill-source"]);
+        if let Commands::AddLanguage(cmd) = args.command {
+            assert_eq!(cmd.language, "fr");
+            assert_eq!(cmd.from.as_deref(), Some("en"));
+            assert!(cmd.fill_source);
+        } else {
+            panic!("Expected AddLanguage command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_status() {
+        let args = CliArgs::parse_from(["yflow", "status", "--lang", "en", "--keys"]);
+        if let Commands::Status(cmd) = args.command {
+            assert_eq!(cmd.languages, vec!["en".to_string()]);
+            assert!(cmd.keys);
+            assert_eq!(cmd.max_age, Duration::from_secs(30));
+        } else {
+            panic!("Expected Status command");
+        }
+        let args = CliArgs::parse_from(["yflow", "status", "--max-age", "5m"]);
+        if let Commands::Status(cmd) = args.command {
+            assert_eq!(cmd.max_age, Duration::from_secs(300));
+        } else {
+            panic!("Expected Status command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_diff_local() {
+        let args = CliArgs::parse_from([
+            "yflow",
+            "diff-local",
+            "./messages",
+            "./messages-old",
+            "--lang",
+            "de",
+            "--comparator",
+            "normalized-html",
+            "--keys",
+            "--exit-code",
+        ]);
+        if let Commands::DiffLocal(cmd) = args.command {
+            assert_eq!(cmd.dir, PathBuf::from("./messages"));
+            assert_eq!(cmd.base, PathBuf::from("./messages-old"));
+            assert_eq!(cmd.languages, vec!["de".to_string()]);
+            assert_eq!(cmd.comparator, crate::core::compare::Comparator::NormalizedHtml);
+            assert!(cmd.keys);
+            assert!(cmd.exit_code);
+            assert!(!cmd.json);
+        } else {
+            panic!("Expected DiffLocal command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_remove_language() {
+        let args = CliArgs::parse_from(["yflow", "remove-language", "nb_NO", "--remote"]);
+        if let Commands::RemoveLanguage(cmd) = args.command {
+            assert_eq!(cmd.language, "nb_NO");
+            assert!(cmd.remote);
+        } else {
+            panic!("Expected RemoveLanguage command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_graph() {
+        let args = CliArgs::parse_from(["yflow", "graph", "--format", "json", "--src", "src"]);
+        if let Commands::Graph(cmd) = args.command {
+            assert_eq!(format!("{:?}", cmd.format), "Json");
+            assert_eq!(cmd.sources, vec![PathBuf::from("src")]);
+        } else {
+            panic!("Expected Graph command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_validate() {
+        let args = CliArgs::parse_from(["yflow", "validate", "--source", "en", "--lang", "de"]);
+        if let Commands::Validate(cmd) = args.command {
+            assert_eq!(cmd.source.as_deref(), Some("en"));
+            assert_eq!(cmd.languages, vec!["de".to_string()]);
+        } else {
+            panic!("Expected Validate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_set() {
+        let args = CliArgs::parse_from(["yflow", "set", "user.title", "--lang", "de", "Profil", "--push"]);
+        if let Commands::Set(cmd) = args.command {
+            assert_eq!(cmd.key, "user.title");
+            assert_eq!(cmd.value, "Profil");
+            assert_eq!(cmd.lang, "de");
+            assert!(cmd.push);
+        } else {
+            panic!("Expected Set command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_get() {
+        let args = CliArgs::parse_from(["yflow", "get", "user.title", "--lang", "de", "--remote", "--json"]);
+        if let Commands::Get(cmd) = args.command {
+            assert_eq!(cmd.key, "user.title");
+            assert_eq!(cmd.languages, vec!["de".to_string()]);
+            assert!(cmd.remote);
+            assert!(cmd.json);
+        } else {
+            panic!("Expected Get command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_explain() {
+        let args = CliArgs::parse_from(["yflow", "explain", "user.title", "--offline", "--json"]);
+        if let Commands::Explain(cmd) = args.command {
+            assert_eq!(cmd.key, "user.title");
+            assert!(cmd.offline);
+            assert!(cmd.json);
+        } else {
+            panic!("Expected Explain command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_replace() {
+        let args = CliArgs::parse_from(["yflow", "replace", "--lang", "de", "--find", "Fa. ACME", "--replace", "ACME GmbH", "--regex", "--dry-run"]);
+        if let Commands::Replace(cmd) = args.command {
+            assert_eq!(cmd.languages, vec!["de".to_string()]);
+            assert_eq!(cmd.find, "Fa. ACME");
+            assert_eq!(cmd.replace, "ACME GmbH");
+            assert!(cmd.regex);
+            assert!(cmd.dry_run);
+        } else {
+            panic!("Expected Replace command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_qa_report() {
+        let args = CliArgs::parse_from(["yflow", "qa-report", "--format", "html", "--max-length-ratio", "1.5", "--release", "v2"]);
+        if let Commands::QaReport(cmd) = args.command {
+            assert_eq!(cmd.format, clap::ValueEnum::from_str("html", false).unwrap());
+            assert_eq!(cmd.max_length_ratio, 1.5);
+            assert_eq!(cmd.release.as_deref(), Some("v2"));
+        } else {
+            panic!("Expected QaReport command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_watch() {
+        let args = CliArgs::parse_from(["yflow", "watch", "--debounce", "1000", "--no-initial"]);
+        if let Commands::Watch(cmd) = args.command {
+            assert_eq!(cmd.debounce, 1000);
+            assert!(cmd.no_initial);
+        } else {
+            panic!("Expected Watch command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_lock() {
+        let args = CliArgs::parse_from(["yflow", "lock", "checkout.", "--ttl", "2h"]);
+        if let Commands::Lock(cmd) = args.command {
+            assert_eq!(cmd.prefix.as_deref(), Some("checkout."));
+            assert_eq!(cmd.ttl.as_deref(), Some("2h"));
+        } else {
+            panic!("Expected Lock command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "lock", "checkout.", "--list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_unlock() {
+        let args = CliArgs::parse_from(["yflow", "unlock", "checkout.", "--force"]);
+        if let Commands::Unlock(cmd) = args.command {
+            assert_eq!(cmd.prefix, "checkout.");
+            assert!(cmd.force);
+        } else {
+            panic!("Expected Unlock command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_auth() {
+        let args = CliArgs::parse_from(["yflow", "auth", "login", "--api-key", "secret"]);
+        assert!(matches!(args.command, Commands::Auth(_)));
+
+        let args = CliArgs::parse_from(["yflow", "--config", "a.json", "auth", "logout"]);
+        assert!(matches!(args.command, Commands::Auth(_)));
+        assert_eq!(args.config, Some(PathBuf::from("a.json")));
+    }
+
+    #[test]
+    fn test_cli_args_parse_bench() {
+        let args = CliArgs::parse_from(["yflow", "bench", "--keys", "500", "--dry-backend"]);
+        if let Commands::Bench(cmd) = args.command {
+            assert_eq!((cmd.keys, cmd.languages), (500, 3));
+            assert!(cmd.dry_backend);
+        } else {
+            panic!("Expected Bench command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "bench", "--languages", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_config_schema() {
+        let args = CliArgs::parse_from(["yflow", "config", "schema", "--out", "schema.json"]);
+        assert!(matches!(args.command, Commands::Config(_)));
+
+        let args = CliArgs::parse_from(["yflow", "config", "paths"]);
+        if let Commands::Config(cmd) = args.command {
+            assert_eq!(format!("{:?}", cmd.action), "Paths");
+        } else {
+            panic!("Expected Config command");
+        }
+    }
+
+    #[test]
+    fn test_cli_args_parse_rollback() {
+        let args = CliArgs::parse_from(["yflow", "rollback", "--to", "20261016T120000Z"]);
+        if let Commands::Rollback(cmd) = args.command {
+            assert_eq!(cmd.to.as_deref(), Some("20261016T120000Z"));
+            assert!(!cmd.list);
+        } else {
+            panic!("Expected Rollback command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "rollback", "--list", "--to", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_todo() {
+        let args = CliArgs::parse_from(["yflow", "todo", "--lang", "de", "--out", "TODO_de.md"]);
+        if let Commands::Todo(cmd) = args.command {
+            assert_eq!(cmd.lang, "de");
+            assert_eq!(cmd.out, Some(PathBuf::from("TODO_de.md")));
+            assert_eq!(cmd.source, None);
+        } else {
+            panic!("Expected Todo command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "todo"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_missing() {
+        let args = CliArgs::parse_from(["yflow", "missing", "--base", "en", "--format", "csv"]);
+        if let Commands::Missing(cmd) = args.command {
+            assert_eq!(cmd.base.as_deref(), Some("en"));
+            assert!(cmd.languages.is_empty());
+        } else {
+            panic!("Expected Missing command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "missing", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_stats() {
+        let args = CliArgs::parse_from(["yflow", "stats", "--source", "backend", "--json"]);
+        if let Commands::Stats(cmd) = args.command {
+            assert!(cmd.json);
+            assert!(cmd.base.is_none());
+        } else {
+            panic!("Expected Stats command");
+        }
+        assert!(CliArgs::try_parse_from(["yflow", "stats", "--source", "cache"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_parse_check_placeholders() {
+        let args = CliArgs::parse_from(["yflow", "check-placeholders", "--source", "en", "--lang", "de"]);
+        if let Commands::CheckPlaceholders(cmd) = args.command {
+            assert_eq!(cmd.source.as_deref(), Some("en"));
+            assert_eq!(cmd.languages, ["de"]);
+        } else {
+            panic!("Expected CheckPlaceholders command");
+        }
+    }
+
     /// 测试 CLI 参数解析 - 带选项
     #[test]
     fn test_cli_args_parse_with_config() {
-        let args = CliArgs::parse_from(&["yflow", "-c", "custom.json", "import"]);
+        let args = CliArgs::parse_from(["yflow", "-c", "custom.json", "import"]);
         assert_eq!(args.config, Some(PathBuf::from("custom.json")));
     }
 
     #[test]
     fn test_cli_args_parse_with_long_config() {
-        let args = CliArgs::parse_from(&["yflow", "--config", "custom.json", "sync"]);
+        let args = CliArgs::parse_from(["yflow", "--config", "custom.json", "sync"]);
         assert_eq!(args.config, Some(PathBuf::from("custom.json")));
     }
 
     #[test]
     fn test_cli_args_parse_import_with_dry_run() {
-        let args = CliArgs::parse_from(&["yflow", "import", "--dry-run"]);
+        let args = CliArgs::parse_from(["yflow", "import", "--dry-run"]);
         if let Commands::Import(cmd) = args.command {
             assert!(cmd.dry_run);
         } else {
@@ -240,7 +1095,7 @@ mod tests {
 
     #[test]
     fn test_cli_args_parse_sync_with_force() {
-        let args = CliArgs::parse_from(&["yflow", "sync", "--force"]);
+        let args = CliArgs::parse_from(["yflow", "sync", "--force"]);
         if let Commands::Sync(cmd) = args.command {
             assert!(cmd.force);
         } else {
@@ -250,7 +1105,7 @@ mod tests {
 
     #[test]
     fn test_cli_args_parse_init_with_output() {
-        let args = CliArgs::parse_from(&["yflow", "init", "-o", "/path/to/config.json"]);
+        let args = CliArgs::parse_from(["yflow", "init", "-o", "/path/to/config.json"]);
         if let Commands::Init { output } = args.command {
             assert_eq!(output, Some(PathBuf::from("/path/to/config.json")));
         } else {
@@ -261,7 +1116,7 @@ mod tests {
     /// 测试 CLI 参数解析 - 组合选项
     #[test]
     fn test_cli_args_parse_with_multiple_options() {
-        let args = CliArgs::parse_from(&["yflow", "-c", "config.json", "sync", "--dry-run", "--force"]);
+        let args = CliArgs::parse_from(["yflow", "-c", "config.json", "sync", "--dry-run", "--force"]);
         if let Commands::Sync(cmd) = args.command {
             assert_eq!(args.config, Some(PathBuf::from("config.json")));
             assert!(cmd.dry_run);
@@ -271,10 +1126,88 @@ mod tests {
         }
     }
 
+    /// 测试 CLI 参数解析 - 全局 ignore-pins 选项
+    #[test]
+    fn test_cli_args_parse_ignore_pins() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--ignore-pins"]);
+        assert!(args.ignore_pins);
+        assert!(!CliArgs::parse_from(["yflow", "sync"]).ignore_pins);
+    }
+
+    /// 测试 CLI 参数解析 - 全局超时选项
+    #[test]
+    fn test_cli_args_parse_timeouts() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--timeout", "300", "--connect-timeout", "20"]);
+        assert_eq!((args.timeout, args.connect_timeout), (Some(300), Some(20)));
+        assert!(CliArgs::try_parse_from(["yflow", "sync", "--timeout", "0"]).is_err());
+    }
+
+    /// 测试 CLI 参数解析 - 全局 show-secrets 选项
+    #[test]
+    fn test_cli_args_parse_show_secrets() {
+        assert!(CliArgs::parse_from(["yflow", "--show-secrets", "status"]).show_secrets);
+        assert!(!CliArgs::parse_from(["yflow", "status"]).show_secrets);
+    }
+
+    /// 测试 CLI 参数解析 - 全局消息格式
+    #[test]
+    fn test_cli_args_parse_message_format() {
+        use crate::ui::reporter::MessageFormat;
+        assert_eq!(CliArgs::parse_from(["yflow", "lint"]).message_format, MessageFormat::Human);
+        let args = CliArgs::parse_from(["yflow", "lint", "--message-format", "json"]);
+        assert_eq!(args.message_format, MessageFormat::Json);
+        assert!(CliArgs::try_parse_from(["yflow", "--message-format", "xml", "lint"]).is_err());
+    }
+
+    /// 测试 CLI 参数解析 - 全局 ci 选项
+    #[test]
+    fn test_cli_args_parse_ci() {
+        assert!(CliArgs::parse_from(["yflow", "--ci", "status"]).ci);
+        assert!(CliArgs::parse_from(["yflow", "validate", "--ci"]).ci);
+        assert!(!CliArgs::parse_from(["yflow", "status"]).ci);
+    }
+
+    /// 测试 CLI 参数解析 - 全局 author 选项
+    #[test]
+    fn test_cli_args_parse_with_author() {
+        let args = CliArgs::parse_from(["yflow", "import", "--author", "Jane Doe <jane@example.com>"]);
+        let author = args.author.unwrap();
+        assert_eq!(author.name, "Jane Doe");
+        assert_eq!(author.email.as_deref(), Some("jane@example.com"));
+
+        assert!(CliArgs::parse_from(["yflow", "import"]).author.is_none());
+        assert!(CliArgs::try_parse_from(["yflow", "--author", "<jane@example.com>", "import"]).is_err());
+    }
+
+    /// 测试 CLI 参数解析 - 全局 deadline 选项
+    #[test]
+    fn test_cli_args_parse_with_deadline() {
+        let args = CliArgs::parse_from(["yflow", "sync", "--deadline", "10m"]);
+        assert_eq!(args.deadline, Some(Duration::from_secs(600)));
+
+        assert!(CliArgs::try_parse_from(["yflow", "--deadline", "soon", "sync"]).is_err());
+    }
+
     /// 测试 CLI 参数解析 - 全局 verbose 选项
     #[test]
     fn test_cli_args_parse_with_verbose() {
-        let args = CliArgs::parse_from(&["yflow", "-v", "import"]);
-        assert!(args.verbose);
+        let args = CliArgs::parse_from(["yflow", "-v", "import"]);
+        assert_eq!(args.verbose, 1);
+        assert_eq!(CliArgs::parse_from(["yflow", "-vv", "import"]).verbose, 2);
+        assert!(CliArgs::parse_from(["yflow", "sync", "-q"]).quiet);
+        assert!(CliArgs::try_parse_from(["yflow", "-v", "-q", "sync"]).is_err());
+        // 版本使用 -V，不与 -v 冲突
+        let error = CliArgs::try_parse_from(["yflow", "-V"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::DisplayVersion);
+    }
+
+    /// 测试 CLI 参数解析 - 日志格式和日志文件
+    #[test]
+    fn test_cli_args_parse_log_options() {
+        let args = CliArgs::parse_from(["yflow", "--log-format", "json", "--log-file", "run.log", "sync"]);
+        assert_eq!(args.log_format, crate::ui::logging::LogFormat::Json);
+        assert_eq!(args.log_file, Some(PathBuf::from("run.log")));
+        assert_eq!(CliArgs::parse_from(["yflow", "sync"]).log_format, crate::ui::logging::LogFormat::Pretty);
+        assert!(CliArgs::try_parse_from(["yflow", "--log-format", "xml", "sync"]).is_err());
     }
 }
@@ -4,10 +4,10 @@
 //! local files and the YFlow backend.
 
 mod cli;
-mod core;
-mod api;
 mod ui;
 
+use yflow_core::{api, core};
+
 use anyhow::Result;
 use clap::Parser;
 use cli::{CliArgs, Commands};
@@ -15,7 +15,7 @@ use core::config::create_sample_config;
 use std::path::PathBuf;
 use std::process;
 use tracing::info;
-use tracing_subscriber;
+use ui::reporter::{self, Reporter};
 
 /// 程序名称
 const PROGRAM_NAME: &str = "yflow";
@@ -28,37 +28,105 @@ const BUILD_INFO: &str = concat!(env!("CARGO_PKG_VERSION"), " (build)");
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 初始化日志
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .with_target(false)
-        .init();
-
     // 解析命令行参数
     let args = CliArgs::parse();
 
-    // 如果启用了 verbose 模式，启用更详细的日志
-    if args.verbose {
-        tracing_subscriber::fmt()
-            .with_max_level(tracing::Level::DEBUG)
-            .with_target(true)
-            .init();
+    // CI 模式：不显示进度、不提示，日志不带颜色
+    ui::ci::set_enabled(args.ci);
+
+    // -q 只输出错误（不显示进度），-v/-vv 输出调试/跟踪日志
+    let verbosity = ui::verbosity::Verbosity::from_flags(args.verbose, args.quiet);
+    ui::verbosity::set(verbosity);
+
+    // 所有面向用户的输出都经过 reporter
+    let reporter = reporter::for_run(args.message_format, verbosity);
+
+    // 颜色跟随终端检测（NO_COLOR、CLICOLOR_FORCE、--ci）
+    let color = ui::term::capabilities().color;
+    dialoguer::console::set_colors_enabled(color);
+    dialoguer::console::set_colors_enabled_stderr(color);
+
+    // 初始化日志，输出中的密钥替换为 ***
+    ui::logging::init(&ui::logging::LogOptions {
+        verbosity,
+        format: args.log_format,
+        file: args.log_file.clone(),
+        ansi: color,
+    })?;
+    ui::redact::install_panic_hook();
+    if args.show_secrets {
+        core::redact::set_show_secrets(true);
+        tracing::warn!("--show-secrets: API keys are printed in logs and errors");
     }
 
+    // 启动全局截止时间计时
+    if let Some(budget) = args.deadline {
+        core::deadline::start(budget, ui::progress::clear_active);
+    }
+
+    // 命令行指定的请求超时、证书固定和作者覆盖配置文件
+    if args.ignore_pins {
+        tracing::warn!("--ignore-pins: backend certificate pins are not checked");
+    }
+    let ctx = cli::RunContext {
+        config: args.config.clone(),
+        overrides: core::config::ConfigOverrides {
+            timeout_secs: args.timeout,
+            connect_timeout_secs: args.connect_timeout,
+            ignore_pins: args.ignore_pins,
+            author: args.author.clone(),
+        },
+        reporter: reporter.clone(),
+    };
+
+    // Ctrl-C 时清除进度条和 spinner 后退出
+    ui::progress::install_interrupt_handler(reporter.clone());
+
+    // 等待后端响应时显示 spinner 和已用时间
+    ui::activity::install();
+
     // 执行命令
     let result: Result<()> = match &args.command {
-        Commands::Import(cmd) => cmd.run(args.config.clone()).await.map(|_| ()),
-        Commands::Sync(cmd) => cmd.run(args.config.clone()).await.map(|_| ()),
+        Commands::Import(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::ImportDelivery(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::ImportFile(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Sync(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Export(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Lint(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Fmt(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::GenTests(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::AddLanguage(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Status(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::DiffLocal(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Todo(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Missing(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Stats(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::RemoveLanguage(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Graph(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Validate(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::CheckPlaceholders(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Set(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Get(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Explain(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Replace(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Rollback(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::QaReport(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Watch(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Lock(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Unlock(cmd) => cmd.run(&ctx).await.map(|_| ()),
+        Commands::Auth(cmd) => cmd.run(&ctx).await,
+        Commands::Config(cmd) => cmd.run(&ctx).await,
+        Commands::Bench(cmd) => cmd.run(&ctx).await.map(|_| ()),
         Commands::Init { output } => {
-            init_config(output.as_ref())?;
+            init_config(&*reporter, output.as_ref())?;
             Ok(())
         }
         Commands::Version => {
-            show_version();
+            show_version(&*reporter);
             Ok(())
         }
         Commands::HelpCmd { command } => {
-            show_help(command.as_deref());
+            show_help(&*reporter, command.as_deref());
             Ok(())
         }
     };
@@ -70,14 +138,25 @@ async fn main() -> Result<()> {
             Ok(())
         }
         Err(e) => {
-            eprintln!("\n❌ Error: {}", e);
+            reporter.error(core::redact::redact(&e.to_string()));
 
             // 检查是否是配置文件错误
             if e.to_string().contains("Config") {
-                println!("\n💡 Hint: Run 'yflow init' to create a sample configuration file.");
+                reporter.hint("Run 'yflow init' to create a sample configuration file.");
             }
 
-            process::exit(1);
+            // 检查是否是证书固定失败
+            if let Some(fingerprint) = api::tls::rejected_fingerprint(&e) {
+                reporter.hint(format!(
+                    "The backend certificate (SHA-256 {}) does not match pinnedSha256. \
+                     If the certificate was rotated, add the new fingerprint to pinnedSha256 \
+                     or re-run with --ignore-pins.",
+                    fingerprint
+                ));
+            }
+
+            // CI 模式下按失败类型退出（见 core::failure）
+            process::exit(if args.ci { core::failure::exit_code(&e) } else { 1 });
         }
     }
 }
@@ -85,12 +164,12 @@ async fn main() -> Result<()> {
 /// 显示版本信息
 ///
 /// 输出程序名称、版本号和构建信息。
-fn show_version() {
-    println!("{} v{}", PROGRAM_NAME, VERSION);
-    println!("Build: {}", BUILD_INFO);
-    println!();
-    println!("A CLI tool for importing and syncing translations between");
-    println!("local files and the YFlow backend.");
+fn show_version(reporter: &dyn Reporter) {
+    reporter.output(format!("{} v{}", PROGRAM_NAME, VERSION));
+    reporter.output(format!("Build: {}", BUILD_INFO));
+    reporter.output("");
+    reporter.output("A CLI tool for importing and syncing translations between");
+    reporter.output("local files and the YFlow backend.");
 }
 
 /// 显示帮助信息
@@ -99,14 +178,15 @@ fn show_version() {
 ///
 /// # Arguments
 ///
+/// * `reporter` - 输出层
 /// * `command` - 可选的命令名称，如果提供则显示该命令的详细帮助
-fn show_help(command: Option<&str>) {
+fn show_help(reporter: &dyn Reporter, command: Option<&str>) {
     if let Some(cmd_name) = command {
         // 显示特定命令的帮助信息
-        show_command_help(cmd_name);
+        show_command_help(reporter, cmd_name);
     } else {
         // 显示全局帮助信息
-        println!(
+        reporter.output(format!(
             r#"{PROGRAM_NAME} - YFlow Internationalization Management CLI Tool
 
 Usage:
@@ -114,18 +194,76 @@ Usage:
 
 Commands:
   import    Import translations from local messages directory to backend
+  import-delivery
+            Import a vendor delivery (zip/XLIFF/CSV/PO) with a mapping file
+  import-file
+            Import translations from a CSV/TSV spreadsheet (key,<lang>,...)
   sync      Sync translations from backend to local messages directory
+  export    Export translations (or changes since a snapshot) as a handoff package
+  lint      Check translations against content rules
+  fmt       Split locale files over maxKeysPerFile by namespace (fmt --split)
+  gen-tests Generate an ICU smoke-test file (.rs/.ts/.js)
+  add-language
+            Create a new language locally and register it with the backend
+  status    Show keys that differ between local files and the backend
+  diff-local
+            Compare two local messages directories (added/removed/changed keys)
+  todo      Write a Markdown checklist of a language's untranslated keys
+  missing   List every language's untranslated keys with completion (table/JSON/CSV)
+  stats     Show translation coverage per language and top-level namespace
+  remove-language
+            Remove a language locally (with backup) and optionally on the backend
+  graph     Export namespace/file/source relationships (dot or json)
+  validate  Check local files for syntax, duplicate keys, empty values, placeholders
+  check-placeholders
+            Report translations whose placeholders differ from the source language
+  set       Update one key's value in the local files (optionally push)
+  get       Print a key's value(s) from local files or the backend
+  explain   Show a key's files, values, sync status, owner and lint findings
+  replace   Find and replace text in translation values
+  rollback  Restore local files from the backup taken before a sync
+  qa-report Export per-language translation quality scores (JSON/HTML)
+  watch     Watch locale files and import changes automatically
+  lock      Lock a key prefix while editing (or list locks)
+  unlock    Release a key prefix locked with lock
+  auth      Save the API key in the system keyring (login/logout)
+  config    Print the config JSON Schema or the config/cache paths (config schema|paths)
+  bench     Measure scan, flatten and (mock) upload throughput for bug reports
   init      Create a sample configuration file
   version   Display version information
   help      Show this help message or help for a specific command
 
 Options:
-  --config <path>    Configuration file path (default: .i18nrc.json)
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
   --dry-run          Simulate execution without making changes
   --force            Force overwrite all translations (sync command)
   --help, -h         Show help information
-  --version, -v      Show version information
-  --verbose, -v      Enable verbose output
+  --version, -V      Show version information
+  --verbose, -v      Log debug output (-vv: trace)
+  --quiet, -q        Only print errors and requested results (no info logs, progress or spinners)
+  --log-format <f>   Log format: pretty (default) or json (one object per line)
+  --log-file <path>  Also append logs to this file
+  --message-format <f>  Status messages: human (default) or json (one object per line on stderr)
+  --show-secrets     Print API keys in logs and errors instead of ***
+  --deadline <dur>   Stop gracefully after this time budget (e.g. 10m, 1h30m)
+  --timeout <secs>   Request timeout for backend and secretProvider calls (default: timeoutSecs or 30)
+  --connect-timeout <secs>
+                     Connect timeout for backend and secretProvider calls (default: connectTimeoutSecs or 10)
+  --ignore-pins      Skip pinnedSha256 certificate checks (after a cert rotation)
+  --author <who>     Author for pushed changes, "Name <email>" (default: git user)
+  --ci               No progress bars, spinners or prompts; plain logs; exit codes below
+
+Exit codes:
+  0  Success
+  1  Error
+  2  Drift detected (status, diff-local, sync --check) — with --ci
+  3  Validation errors (validate, lint, check-placeholders, import --check) — with --ci
+  4  Authentication failure — with --ci
+
+Environment:
+  NO_COLOR=1         Disable colors
+  CLICOLOR_FORCE=1   Force colors and emoji even when output is piped
+  I18N_FORCE_PROGRESS=0|1  Hide or force progress bars (default: only on a terminal)
 
 Examples:
   {PROGRAM_NAME} import                    # Import translations
@@ -136,7 +274,7 @@ Examples:
   {PROGRAM_NAME} help import               # Show help for import command
   {PROGRAM_NAME} version                   # Show version information
 "#
-        );
+        ));
     }
 }
 
@@ -144,49 +282,807 @@ Examples:
 ///
 /// # Arguments
 ///
+/// * `reporter` - 输出层
 /// * `command` - 命令名称
-fn show_command_help(command: &str) {
+fn show_command_help(reporter: &dyn Reporter, command: &str) {
     match command.to_lowercase().as_str() {
         "import" => {
-            println!(
+            reporter.output(format!(
                 r#"Import translations from local messages directory to backend
 
 Usage: {PROGRAM_NAME} import [options]
 
 Options:
-  --config <path>    Configuration file path (default: .i18nrc.json)
-  --dry-run          Simulate import without making changes
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --dry-run          Show the import plan (keys to create/update, unchanged) without pushing
+  --server-dry-run   Validate batches on the server without writing; report adds/updates/rejections
+  --plan-file <path> Save the import plan to a file without pushing
+  --apply <plan>     Push a saved plan exactly; fails if its keys changed on the backend since
+  --team <name>      Team running the import, checked against `owners` (env: I18N_TEAM)
+  --concurrency <n>  Number of batches uploaded in parallel (default: 1)
+  --locale <code>    Only import this language (repeatable)
+  --exclude-locale <code>  Skip this language (repeatable)
+  --key-prefix <prefix>  Only import keys starting with this prefix (repeatable)
+  --key-glob <glob>  Only import keys matching this glob, e.g. 'checkout.*' (repeatable)
+  --fail-fast        Abort on the first file that cannot be parsed or project that fails
+  --keep-going       With routes, import the other projects when one fails and report at the end
+  --project-concurrency <n>  Number of routed projects imported in parallel (default: 1)
+  --summary-json <path>  Write the per-language summary and project × language results as JSON
+  --no-cache         Push every key, not only those changed since the last run
+  --prune            Delete backend keys that no local file defines (asks for confirmation)
+  --yes              Delete the keys found by --prune without asking
+  --mark-stale       Mark translations whose source text changed since they were
+                     translated as unreviewed on the backend (needs sourceLanguage)
+  --check            Refuse to import when placeholders differ from the source language
+  --since <ref>      Only import keys added or changed in the messages directory, its
+                     languageDirs or the overlays since this git commit, tag or branch
+                     (e.g. origin/main)
   --help, -h         Show this help message
 
 Examples:
   {PROGRAM_NAME} import                    # Import translations
   {PROGRAM_NAME} import --dry-run          # Preview what would be imported
+  {PROGRAM_NAME} import --server-dry-run   # Ask the server what it would accept
+  {PROGRAM_NAME} import --plan-file plan.json  # Save a plan for review
+  {PROGRAM_NAME} import --apply plan.json  # Push the reviewed plan
+  {PROGRAM_NAME} import --concurrency 4    # Upload 4 batches at a time
+  {PROGRAM_NAME} import --project-concurrency 3 --keep-going  # Import 3 projects at a time
+  {PROGRAM_NAME} import --locale zh_CN     # Import only zh_CN
+  {PROGRAM_NAME} import --key-prefix checkout.  # Import only checkout keys
+  {PROGRAM_NAME} import --since origin/main  # Import only keys changed on this branch
+  {PROGRAM_NAME} import --prune --dry-run  # List backend keys missing locally
   {PROGRAM_NAME} import --config .i18nrc   # Use custom config file
 "#
-            );
+            ));
+        }
+        "import-delivery" => {
+            reporter.output(format!(
+                r#"Import a vendor translation delivery
+
+Usage: {PROGRAM_NAME} import-delivery <delivery> [options]
+
+Arguments:
+  <delivery>         .zip archive, directory, or single .xlf/.xliff/.csv/.po file
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --map <path>       Mapping CSV with `type,from,to` rows (type: language or key)
+  --dry-run          Parse and validate without pushing
+  --skip-invalid     Push valid entries even if some have placeholder mismatches
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} import-delivery delivery.zip --map mapping.csv
+  {PROGRAM_NAME} import-delivery de.xlf --dry-run
+"#
+            ));
+        }
+        "import-file" => {
+            reporter.output(format!(
+                r#"Import translations from a CSV/TSV spreadsheet export
+
+Usage: {PROGRAM_NAME} import-file <file> [options]
+
+Arguments:
+  <file>             .csv or .tsv file whose header row is key,<lang>,<lang>,...
+
+Empty cells are skipped; source, context, comment and notes columns are
+ignored. Keys are pushed with the regular import pipeline (routes, language
+mapping, batching, retries).
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --dry-run          Parse and preview without pushing
+  --concurrency <n>  Upload up to n batches in parallel (default: 1)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} import-file copy.csv --dry-run
+  {PROGRAM_NAME} import-file marketing.tsv
+"#
+            ));
         }
         "sync" => {
-            println!(
+            reporter.output(format!(
                 r#"Sync translations from backend to local messages directory
 
 Usage: {PROGRAM_NAME} sync [options]
 
 Options:
-  --config <path>    Configuration file path (default: .i18nrc.json)
-  --dry-run          Simulate sync without making changes
-  --force            Force overwrite all existing translations
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --dry-run          Show every key to add/update/delete with old and new values, without writing
+  --output <fmt>     Plan output: table (default) or json (printed on real runs too, with the
+                     per-language summary instead of the summary table)
+  --check            Exit with a non-zero status if any file would change (writes nothing)
+  --patch            Print the planned changes as a unified diff (writes nothing)
+  --force            Force overwrite all existing translations, including local-only edits
+  --force-keys <patterns>
+                     Force overwrite only keys matching these comma-separated prefixes
+                     or globs, e.g. 'legal.*,emails.*'; other keys keep local edits
+  --prune            Remove local keys deleted on the backend (listed by --dry-run first);
+                     keys added locally since the last sync, and all keys of languages
+                     never synced before, are kept unless --force
+  --conflicts-report <path>
+                     Where to write the conflict report (default: <messagesDir>/.yflow-conflicts.json)
+  --prefer <side>    Resolve keys changed both locally and on the backend:
+                     remote (default), local or interactive (a menu per conflict
+                     with "apply to all" choices; skipped when not a terminal)
+  --chunked          Download per language/namespace; re-run resumes from cached chunks
+  --locale <code>    Only sync this language (repeatable)
+  --exclude-locale <code>  Skip this language (repeatable)
+  --key-prefix <prefix>  Only sync keys starting with this prefix (repeatable)
+  --key-glob <glob>  Only sync keys matching this glob, e.g. 'checkout.*' (repeatable)
+  --fail-fast        Abort on the first file that cannot be parsed
+  --no-cache         Rewrite every language, ignoring the .yflow-cache delta state
+  --as-of <time>     Sync translations as they were at this time, e.g. 2024-05-01T00:00:00Z
+                     (backend must keep history; combine with --force to reproduce exactly)
+  --offline          Replay the last successful fetch from <messagesDir>/.yflow-cache/backend.bin
+                     instead of contacting the backend
+  --max-change-percent <n>  Confirm before overwriting more than n% of local keys
+                     (default: maxChangePercent or 30; fails when not interactive)
+  --yes              Apply large overwrites without confirmation
+  --no-backup        Do not back up changed files to <messagesDir>/.yflow-backup/
+  --commit <message> Commit the written files to git (only those files) with this message
+  --sign             Sign the commit (git commit -S)
+  --branch-per-run   Commit on a new yflow/sync-<timestamp> branch (message: --commit or
+                     "chore: sync translations"); stays on that branch
+  --push-remote <remote>
+                     Push the branch and print the pull request URL, or run `prCommand`
+                     from the config ({{branch}}, {{base}}, {{remote}}, {{url}} refer to the
+                     YFLOW_BRANCH, YFLOW_BASE, YFLOW_REMOTE, YFLOW_PR_URL environment variables)
+  --summary-json <path>  Write the per-language summary as JSON
   --help, -h         Show this help message
 
+Changed files are backed up before writing; undo a sync with `{PROGRAM_NAME} rollback`.
+
 Examples:
   {PROGRAM_NAME} sync                      # Sync translations
   {PROGRAM_NAME} sync --dry-run            # Preview what would be synced
+  {PROGRAM_NAME} sync --dry-run --output json > plan.json  # Plan for a PR review
+  {PROGRAM_NAME} sync --check              # Fail in CI when local files are out of date
+  {PROGRAM_NAME} sync --patch > sync.patch # Review the changes as a diff
+  {PROGRAM_NAME} sync --chunked            # Resumable download for large projects
+  {PROGRAM_NAME} sync --locale zh_CN       # Sync only zh_CN
+  {PROGRAM_NAME} sync --key-glob 'checkout.*'  # Sync only checkout keys
   {PROGRAM_NAME} sync --force              # Force overwrite all
+  {PROGRAM_NAME} sync --force-keys 'legal.*,emails.*'  # Backend wins for legal and email copy
+  {PROGRAM_NAME} sync --prune --dry-run    # List local keys deleted on the backend
+  {PROGRAM_NAME} sync --prefer interactive # Decide each conflict
+  {PROGRAM_NAME} sync --commit "chore: sync translations"  # Sync and commit
+  {PROGRAM_NAME} sync --branch-per-run --push-remote origin  # Open an update PR (cron)
+  {PROGRAM_NAME} sync --as-of 2024-05-01 --force  # Reproduce a past release's locales
+  {PROGRAM_NAME} sync --offline            # Regenerate locale files without network access
   {PROGRAM_NAME} sync --config .i18nrc     # Use custom config file
 "#
-            );
+            ));
+        }
+        "export" => {
+            reporter.output(format!(
+                r#"Export translations as a handoff package (one JSON file per language + manifest.json)
+
+Usage: {PROGRAM_NAME} export [options]
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --out, -o <dir>    Output directory (default: ./export)
+  --since-snapshot <id|timestamp>
+                     Only export keys added or changed since the snapshot
+  --lang <code>      Only export the given language (repeatable)
+  --no-snapshot      Do not record a new snapshot after exporting
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} export                                  # Full export, records a snapshot
+  {PROGRAM_NAME} export --since-snapshot 20261001T120000Z
+  {PROGRAM_NAME} export --since-snapshot 2026-10-01 --lang en
+"#
+            ));
+        }
+        "lint" => {
+            reporter.output(format!(
+                r#"Check local translations against content rules (`lintRules`)
+
+Usage: {PROGRAM_NAME} lint [options]
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --lang <code>      Only check the given language (repeatable)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} lint                      # Check all languages
+  {PROGRAM_NAME} lint --lang en            # Check English only
+
+With "maxKeysPerFile" set, files holding more keys are reported as errors too.
+"#
+            ));
+        }
+        "fmt" => {
+            reporter.output(format!(
+                r#"Split locale files holding more keys than allowed
+
+Usage: {PROGRAM_NAME} fmt [--split] [options]
+
+Lists the files with more keys than "maxKeysPerFile" (or --max-keys). With
+--split, the keys of each file are moved one namespace level deeper:
+en/common.json keeps its top-level keys and common.auth.* (or auth.*) moves
+to en/common/auth.json, whose namespace routes new auth keys on sync.
+Only JSON and YAML files in the one-directory-per-language layout are split.
+
+Options:
+  --split            Split the oversized files
+  --max-keys <n>     Key limit per file (overrides maxKeysPerFile)
+  --lang <code>      Only process the given language (repeatable)
+  --dry-run          Show the files that would be written
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} fmt
+  {PROGRAM_NAME} fmt --split --dry-run
+  {PROGRAM_NAME} fmt --split --max-keys 300 --lang en
+"#
+            ));
+        }
+        "gen-tests" => {
+            reporter.output(format!(
+                r#"Generate a smoke-test file rendering every ICU message with sample arguments
+
+Usage: {PROGRAM_NAME} gen-tests --out <path> [options]
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --out, -o <path>   Output file; .rs, .ts or .js selects the format
+  --lang <code>      Only include the given language (repeatable)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} gen-tests --out tests/i18n_smoke.ts   # Jest/Vitest + intl-messageformat
+  {PROGRAM_NAME} gen-tests --out tests/i18n_smoke.rs   # Self-contained Rust test
+"#
+            ));
+        }
+        "add-language" => {
+            reporter.output(format!(
+                r#"Scaffold a new language locally and on the backend
+
+Usage: {PROGRAM_NAME} add-language <lang> [options]
+
+Arguments:
+  <lang>                Local language code (directory name), e.g. fr or pt_BR
+
+Options:
+  --config <path>       Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --from <lang>         Source language to mirror (default: language with most keys)
+  --fill-source         Copy source values instead of leaving them empty
+  --backend-code <code> Backend language code; added to languageMapping if different
+  --no-register         Only create local files
+  --help, -h            Show this help message
+
+Examples:
+  {PROGRAM_NAME} add-language fr
+  {PROGRAM_NAME} add-language pt_BR --backend-code pt-BR --fill-source
+"#
+            ));
+        }
+        "status" => {
+            reporter.output(format!(
+                r#"Show drift between local files and the backend
+
+Usage: {PROGRAM_NAME} status [options]
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --lang <lang>      Only check this language (repeatable)
+  --keys             List the drifted keys per language
+  --max-age <dur>    Reuse the cached backend snapshot while local files are unchanged
+                     and it is younger than this (default: 30s)
+  --no-cache         Always fetch from the backend
+  --help, -h         Show this help message
+
+Exit status is non-zero when any language has drifted. Results are cached in
+<messagesDir>/.yflow-cache/status.json, so polling status is cheap. Commands
+that write to the backend (import, set/replace --push, lock, ...) clear the cache.
+
+With sourceLanguage in the config, translations whose source text changed
+since they were translated are reported as stale (tracked in
+<messagesDir>/.yflow-source-state.json; commit it with the locale files).
+
+Examples:
+  {PROGRAM_NAME} status
+  {PROGRAM_NAME} status --lang en --keys
+"#
+            ));
+        }
+        "remove-language" => {
+            reporter.output(format!(
+                r#"Remove a language locally and optionally on the backend
+
+Usage: {PROGRAM_NAME} remove-language <lang> [options]
+
+Arguments:
+  <lang>             Local language code (directory name), e.g. nb_NO
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --remote           Also delete the language and its translations on the backend
+  --help, -h         Show this help message
+
+Local files are moved to <messagesDir>/.yflow-backups/ rather than deleted.
+
+Examples:
+  {PROGRAM_NAME} remove-language nb_NO
+  {PROGRAM_NAME} remove-language nb_NO --remote
+"#
+            ));
+        }
+        "graph" => {
+            reporter.output(format!(
+                r#"Export the key reference graph
+
+Usage: {PROGRAM_NAME} graph [options]
+
+Options:
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --format <format>   Output format: dot (default) or json
+  --out, -o <path>    Write to a file instead of stdout
+  --src <dir>         Source directory to scan for key references (repeatable)
+  --lang <lang>       Only include this language's files (repeatable)
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} graph --src src | dot -Tsvg > i18n.svg
+  {PROGRAM_NAME} graph --format json --lang en -o graph.json
+"#
+            ));
+        }
+        "validate" => {
+            reporter.output(format!(
+                r#"Validate local message files
+
+Usage: {PROGRAM_NAME} validate [options]
+
+Checks:
+  invalid-file          File fails to parse
+  duplicate-key         Key defined more than once after flattening
+  empty-value           Value is empty
+  placeholder-mismatch  Placeholders differ from the source language
+  stale-translation     Source text changed since the key was translated
+                        (with sourceLanguage in the config)
+  incomplete-plural     Plural key lacks a form the language needs, e.g. ru
+                        item_many (with "dialect": "i18next" in the config)
+
+Options:
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --lang <lang>       Only check this language (repeatable)
+  --source <lang>     Source language for placeholder checks
+                      (default: sourceLanguage, else the language with most keys)
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} validate
+  {PROGRAM_NAME} validate --source en --lang zh_CN
+"#
+            ));
+        }
+        "check-placeholders" => {
+            reporter.output(format!(
+                r#"Compare placeholders with the source language
+
+Usage: {PROGRAM_NAME} check-placeholders [options]
+
+Recognised placeholders: {{name}}, {{count, plural, ...}}, {{{{var}}}}, %s, %d, %1$s,
+%@ and the regexes listed in placeholderPatterns (e.g. [":[a-z_]+"]).
+Each translation is reported as dropped (missing a placeholder), renamed
+(missing one and having another) or added (having an extra one).
+
+Options:
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --lang <lang>       Only check this language (repeatable)
+  --source <lang>     Source language (default: sourceLanguage, else the language with most keys)
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} check-placeholders
+  {PROGRAM_NAME} check-placeholders --source en --lang zh_CN
+  {PROGRAM_NAME} import --check            # Refuse to import when placeholders differ
+"#
+            ));
+        }
+        "set" => {
+            reporter.output(format!(
+                r#"Set the value of a key in the local files
+
+Usage: {PROGRAM_NAME} set <key> <value> --lang <lang> [options]
+
+The value is written to the language's file that defines the key, else the
+file with the same name that defines it in another language, else the
+language's only file.
+
+Options:
+  --lang, -l <lang>   Language to update (required)
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --file <file>       File to write, relative to the language directory
+  --push              Push the new value to the backend
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} set user.title --lang de "Profil"
+  {PROGRAM_NAME} set checkout.pay --lang fr "Payer" --push
+"#
+            ));
+        }
+        "get" => {
+            reporter.output(format!(
+                r#"Print the value of a key
+
+Usage: {PROGRAM_NAME} get <key> [options]
+
+Options:
+  --lang, -l <lang>   Only print this language (repeatable)
+  --remote            Read from the backend instead of local files
+  --json              Print a {{language: value}} JSON object
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} get user.title
+  {PROGRAM_NAME} get user.title --lang de
+  {PROGRAM_NAME} get user.title --remote --json
+"#
+            ));
+        }
+        "explain" => {
+            reporter.output(format!(
+                r#"Show everything known about one key
+
+Usage: {PROGRAM_NAME} explain <key> [options]
+
+For each language: the file and line defining the key (brand overlays
+included), the local value, the backend value, the value of the last sync,
+what the next sync/import would do with it and how the last sync resolved a
+conflict on it. Also prints the key's owner, the lock covering it and the
+lint findings for its values.
+
+Options:
+  --offline                   Skip the backend (compare with the last sync only)
+  --conflicts-report <path>   Conflict report to read (default: <messagesDir>/.yflow-conflicts.json)
+  --json                      Print the report as JSON
+  --config <path>             Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h                  Show this help message
+
+Examples:
+  {PROGRAM_NAME} explain user.title
+  {PROGRAM_NAME} explain checkout.pay --offline
+  {PROGRAM_NAME} explain user.title --json
+"#
+            ));
+        }
+        "replace" => {
+            reporter.output(format!(
+                r#"Find and replace text in translation values
+
+Usage: {PROGRAM_NAME} replace --find <text> --replace <text> [options]
+
+Options:
+  --find <text>       Text to find (a regular expression with --regex)
+  --replace <text>    Replacement text ($1 references with --regex)
+  --lang, -l <lang>   Only change this language (repeatable, default: all)
+  --regex             Treat --find as a regular expression
+  --dry-run           Report the changes without writing files
+  --push              Push the changed keys to the backend
+  --config <path>     Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h          Show this help message
+
+Examples:
+  {PROGRAM_NAME} replace --lang de --find "Fa. ACME" --replace "ACME GmbH" --dry-run
+  {PROGRAM_NAME} replace --find "ACME (\w+)" --replace "Acme $1" --regex --push
+"#
+            ));
+        }
+        "diff-local" => {
+            reporter.output(format!(
+                r#"Compare two local messages directories
+
+Usage: {PROGRAM_NAME} diff-local <dir> <base> [options]
+
+Arguments:
+  <dir>              Messages directory to compare
+  <base>             Messages directory to compare against
+
+Options:
+  --lang <lang>      Only compare this language (repeatable)
+  --comparator <c>   How values are compared: exact (default), trimmed or
+                     normalized-html
+  --keys             List every added, removed and changed key with its values
+  --json             Print the result as JSON
+  --exit-code        Exit non-zero when the directories differ
+  --help, -h         Show this help message
+
+No configuration file or backend is needed. Changes are reported from <base>
+to <dir>: added keys exist only in <dir>, removed keys only in <base>.
+
+Examples:
+  {PROGRAM_NAME} diff-local ./messages ./messages-old
+  {PROGRAM_NAME} diff-local dist/locales build-1.2/locales --keys --exit-code
+"#
+            ));
+        }
+        "todo" => {
+            reporter.output(format!(
+                r#"Write a checklist of untranslated keys for a language
+
+Usage: {PROGRAM_NAME} todo --lang <lang> [options]
+
+Lists every key that is missing or empty in <lang> but has a value in the
+source language, grouped by the file it belongs in, with the source text and
+file:line locations, as a Markdown checklist.
+
+Options:
+  --lang <lang>      Language to list untranslated keys for (required)
+  --source <lang>    Source language (default: language with most keys)
+  --out, -o <path>   Output file (default: stdout)
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} todo --lang de --out TODO_de.md
+  {PROGRAM_NAME} todo --lang ja --source en
+"#
+            ));
+        }
+        "missing" => {
+            reporter.output(format!(
+                r#"List untranslated keys of every language
+
+Usage: {PROGRAM_NAME} missing [options]
+
+Lists, per language, every key that has a value in the base language but is
+missing or empty in that language, with counts and completion percentages.
+
+Options:
+  --base <lang>      Base language (default: sourceLanguage, else the language with most keys)
+  --lang <lang>      Only report this language (repeatable)
+  --format <fmt>     table (default), json, or csv (one row per untranslated key)
+  --out, -o <path>   Output file (default: stdout)
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} missing
+  {PROGRAM_NAME} missing --base en --lang de --lang fr
+  {PROGRAM_NAME} missing --format csv --out missing.csv
+"#
+            ));
+        }
+        "stats" => {
+            reporter.output(format!(
+                r#"Show translation coverage per language and namespace
+
+Usage: {PROGRAM_NAME} stats [options]
+
+For every top-level namespace (the first segment of a key, `checkout` in
+`checkout.title`) shows how many of the base language's keys each language
+translates. Keys without a namespace are counted under (root).
+
+Options:
+  --source <src>     local (default) or backend
+  --base <lang>      Base language (default: sourceLanguage, else the language with most keys)
+  --lang <lang>      Only show this language (repeatable)
+  --json             Print the statistics as JSON
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} stats
+  {PROGRAM_NAME} stats --source backend --lang de --lang fr
+"#
+            ));
+        }
+        "rollback" => {
+            reporter.output(format!(
+                r#"Restore local files from a backup taken by `sync`
+
+Usage: {PROGRAM_NAME} rollback [options]
+
+Options:
+  --to <id|time>     Backup id, or a time: restores the latest backup taken at or before it
+                     (default: the latest backup)
+  --list             List the available backups
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h         Show this help message
+
+Files created by the sync are removed; the backups themselves are kept
+(the 20 most recent, in <messagesDir>/.yflow-backup/).
+
+Examples:
+  {PROGRAM_NAME} rollback                  # Undo the last sync
+  {PROGRAM_NAME} rollback --list
+  {PROGRAM_NAME} rollback --to 2026-10-01T12:00:00Z
+"#
+            ));
+        }
+        "qa-report" => {
+            reporter.output(format!(
+                r#"Export a translation quality report
+
+Usage: {PROGRAM_NAME} qa-report [options]
+
+Each language is scored against the source language as the percentage of
+keys without placeholder errors, length violations, empty values or
+markup issues.
+
+Options:
+  --format <fmt>            json (default) or html
+  --out, -o <path>          Output file (default: stdout)
+  --source <lang>           Source language (default: language with most keys)
+  --lang <lang>             Only score this language (repeatable)
+  --max-length-ratio <n>    Flag translations longer than n times the source (default: 2.0)
+  --release <label>         Release label recorded in the report
+  --config <path>           Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --help, -h                Show this help message
+
+Examples:
+  {PROGRAM_NAME} qa-report --out qa.json --release v2.3
+  {PROGRAM_NAME} qa-report --format html --out qa.html
+"#
+            ));
+        }
+        "watch" => {
+            reporter.output(format!(
+                r#"Watch the messages directory and import changes automatically
+
+Usage: {PROGRAM_NAME} watch [options]
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --debounce <ms>    Wait this long after the last change before importing (default: 500)
+  --team <team>      Team making the changes (ownership checks, or I18N_TEAM)
+  --locale <code>    Only import this language (repeatable)
+  --exclude-locale <code>  Skip this language (repeatable)
+  --no-initial       Skip the import on start-up
+  --help, -h         Show this help message
+
+Only keys changed since the last import are pushed (see .yflow-cache).
+Press Ctrl-C to stop.
+
+Examples:
+  {PROGRAM_NAME} watch
+  {PROGRAM_NAME} watch --locale en --debounce 1000
+"#
+            ));
+        }
+        "lock" => {
+            reporter.output(format!(
+                r#"Lock a key prefix while editing, or list locks
+
+Usage: {PROGRAM_NAME} lock [key-prefix] [options]
+
+Arguments:
+  [key-prefix]       Key prefix to lock, e.g. checkout. (omit to list locks)
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --team <team>      Lock holder (default: I18N_TEAM, then the OS user name)
+  --ttl <duration>   Release the lock automatically after e.g. 30m or 2h
+  --list             List the current locks
+  --help, -h         Show this help message
+
+While a prefix is locked, `import` by any other holder skips its keys and
+reports them. Requires a backend with key lock support.
+
+Examples:
+  {PROGRAM_NAME} lock checkout. --team payments --ttl 2h
+  {PROGRAM_NAME} lock --list
+"#
+            ));
+        }
+        "unlock" => {
+            reporter.output(format!(
+                r#"Release a key prefix locked with `lock`
+
+Usage: {PROGRAM_NAME} unlock <key-prefix> [options]
+
+Arguments:
+  <key-prefix>       Locked key prefix
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --team <team>      Lock holder (default: I18N_TEAM, then the OS user name)
+  --force            Release a lock held by someone else
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} unlock checkout. --team payments
+  {PROGRAM_NAME} unlock checkout. --force
+"#
+            ));
+        }
+        "auth" => {
+            reporter.output(format!(
+                r#"Manage the API key saved in the system keyring
+
+Usage: {PROGRAM_NAME} auth <login|logout> [options]
+
+Subcommands:
+  login              Verify an API key and save it in the system keyring
+  logout             Remove the saved API key from the system keyring
+
+The key is stored per apiUrl and projectId of the config file. Commands use
+it whenever apiKey is empty and neither I18N_API_KEY, apiKeyCommand nor
+secretProvider supplies one.
+
+Options:
+  --config <path>    Configuration file path (default: .i18nrc.json, .toml or .yaml)
+  --api-key <key>    Key to save (login; default: prompt, or one line of stdin)
+  --no-verify        Save without checking the key against the backend (login)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} auth login
+  echo "$YFLOW_KEY" | {PROGRAM_NAME} auth login --config ci/.i18nrc.json
+  {PROGRAM_NAME} auth logout
+"#
+            ));
+        }
+        "config" => {
+            reporter.output(format!(
+                r#"Inspect the configuration
+
+Usage: {PROGRAM_NAME} config <schema|paths> [options]
+
+Subcommands:
+  schema             Print the JSON Schema of the configuration file
+  paths              Show the config file in use and the per-user directories
+
+The schema is generated from the types the configuration is parsed with, so
+it always matches the options this version accepts. Point your editor at the
+written file (e.g. VS Code "json.schemas") or add "$schema" to .i18nrc.json.
+
+A global user config (config.json, .toml or .yaml) in the per-user config
+directory provides defaults for fields the repository's .i18nrc file does not
+set, e.g. apiUrl. The directory is ~/.config/yflow on Linux,
+~/Library/Application Support/yflow on macOS and %APPDATA%\yflow\config on
+Windows; YFLOW_CONFIG_DIR overrides it.
+
+Options (schema):
+  --out, -o <path>   Write the schema to a file (default: stdout)
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} config schema --out .i18nrc.schema.json
+  {PROGRAM_NAME} config paths
+  {PROGRAM_NAME} config schema | jq '.properties | keys'
+"#
+            ));
+        }
+        "bench" => {
+            reporter.output(format!(
+                r#"Measure throughput on synthetic translations
+
+Usage: {PROGRAM_NAME} bench [options]
+
+Generates locale files in a temporary directory (removed afterwards) and
+times scanning them, flattening them in memory and, with --dry-backend,
+pushing them in import-sized batches to a mock backend on localhost. The
+configured backend is never contacted. Attach the output to bug reports.
+
+Options:
+  --keys <n>         Keys per language (default: 10000)
+  --languages <n>    Number of languages, 1-6 (default: 3)
+  --dry-backend      Also measure uploads against a local mock backend
+  --json             Print the report as JSON
+  --help, -h         Show this help message
+
+Examples:
+  {PROGRAM_NAME} bench
+  {PROGRAM_NAME} bench --keys 10000 --dry-backend
+  {PROGRAM_NAME} bench --keys 50000 --languages 6 --json > bench.json
+"#
+            ));
         }
         "init" => {
-            println!(
+            reporter.output(format!(
                 r#"Create a sample configuration file
 
 Usage: {PROGRAM_NAME} init [options]
@@ -199,13 +1095,13 @@ Examples:
   {PROGRAM_NAME} init                      # Create .i18nrc.json in current directory
   {PROGRAM_NAME} init --output /path/to/config.json  # Custom output path
 "#
-            );
+            ));
         }
         "version" => {
-            show_version();
+            show_version(reporter);
         }
         "help" => {
-            println!(
+            reporter.output(format!(
                 r#"Show help information
 
 Usage: {PROGRAM_NAME} help [command]
@@ -219,7 +1115,7 @@ Examples:
   {PROGRAM_NAME} help import       # Show help for import command
   {PROGRAM_NAME} help sync         # Show help for sync command
 "#
-            );
+            ));
         }
         _ => {
             eprintln!("Unknown command: {}", command);
@@ -234,16 +1130,17 @@ Examples:
 ///
 /// # Arguments
 ///
+/// * `reporter` - 输出层
 /// * `output` - 可选的输出路径
-fn init_config(output: Option<&PathBuf>) -> Result<()> {
+fn init_config(reporter: &dyn Reporter, output: Option<&PathBuf>) -> Result<()> {
     let path = output
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| PathBuf::from(".i18nrc.json"));
 
     // 检查文件是否已存在
     if path.exists() {
-        println!("⚠️  Configuration file already exists: {}", path.display());
-        println!("   To re-create, please delete the existing file first.");
+        reporter.warning(format!("Configuration file already exists: {}", path.display()));
+        reporter.hint("To re-create it, delete the existing file first.");
         return Ok(());
     }
 
@@ -251,14 +1148,14 @@ fn init_config(output: Option<&PathBuf>) -> Result<()> {
     std::fs::write(&path, &sample)?;
 
     info!("Created sample configuration file: {}", path.display());
-    println!("✅ Created sample configuration file: {}", path.display());
-    println!();
-    println!("Please edit the configuration file to set the correct project ID and API key.");
-    println!("Required fields:");
-    println!("  - messagesDir: Path to your messages directory");
-    println!("  - projectId: Your YFlow project ID");
-    println!("  - apiUrl: Your YFlow API URL");
-    println!("  - apiKey: Your API key");
+    reporter.success(format!("Created sample configuration file: {}", path.display()));
+    reporter.output("");
+    reporter.output("Please edit the configuration file to set the correct project ID and API key.");
+    reporter.output("Required fields:");
+    reporter.output("  - messagesDir: Path to your messages directory");
+    reporter.output("  - projectId: Your YFlow project ID");
+    reporter.output("  - apiUrl: Your YFlow API URL");
+    reporter.output("  - apiKey: Your API key");
 
     Ok(())
 }
@@ -266,6 +1163,7 @@ fn init_config(output: Option<&PathBuf>) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::reporter::HumanReporter;
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -290,7 +1188,7 @@ mod tests {
     fn test_show_version_no_panic() {
         // 测试 show_version 不 panic
         let result = std::panic::catch_unwind(|| {
-            show_version();
+            show_version(&HumanReporter);
         });
         // 如果 panic，测试失败
         assert!(result.is_ok());
@@ -302,7 +1200,7 @@ mod tests {
     fn test_show_help_no_command() {
         // 应该不 panic
         let result = std::panic::catch_unwind(|| {
-            show_help(None);
+            show_help(&HumanReporter, None);
         });
         assert!(result.is_ok());
     }
@@ -310,7 +1208,7 @@ mod tests {
     #[test]
     fn test_show_help_with_import() {
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("import"));
+            show_help(&HumanReporter, Some("import"));
         });
         assert!(result.is_ok());
     }
@@ -318,7 +1216,15 @@ mod tests {
     #[test]
     fn test_show_help_with_sync() {
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("sync"));
+            show_help(&HumanReporter, Some("sync"));
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_show_help_with_lint() {
+        let result = std::panic::catch_unwind(|| {
+            show_help(&HumanReporter, Some("lint"));
         });
         assert!(result.is_ok());
     }
@@ -326,7 +1232,7 @@ mod tests {
     #[test]
     fn test_show_help_with_init() {
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("init"));
+            show_help(&HumanReporter, Some("init"));
         });
         assert!(result.is_ok());
     }
@@ -334,7 +1240,7 @@ mod tests {
     #[test]
     fn test_show_help_with_version() {
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("version"));
+            show_help(&HumanReporter, Some("version"));
         });
         assert!(result.is_ok());
     }
@@ -342,7 +1248,7 @@ mod tests {
     #[test]
     fn test_show_help_with_help() {
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("help"));
+            show_help(&HumanReporter, Some("help"));
         });
         assert!(result.is_ok());
     }
@@ -351,7 +1257,7 @@ mod tests {
     fn test_show_help_with_unknown_command() {
         // 未知命令应该打印错误信息到 stderr，但不 panic
         let result = std::panic::catch_unwind(|| {
-            show_help(Some("unknown_command"));
+            show_help(&HumanReporter, Some("unknown_command"));
         });
         assert!(result.is_ok());
     }
@@ -360,12 +1266,12 @@ mod tests {
     fn test_show_command_help_case_insensitive() {
         // 测试命令名大小写不敏感
         let result = std::panic::catch_unwind(|| {
-            show_command_help("IMPORT");
+            show_command_help(&HumanReporter, "IMPORT");
         });
         assert!(result.is_ok());
 
         let result = std::panic::catch_unwind(|| {
-            show_command_help("Sync");
+            show_command_help(&HumanReporter, "Sync");
         });
         assert!(result.is_ok());
     }
@@ -380,7 +1286,7 @@ mod tests {
         // 确保文件不存在
         assert!(!config_path.exists());
 
-        let result = init_config(Some(&config_path));
+        let result = init_config(&HumanReporter, Some(&config_path));
 
         assert!(result.is_ok());
         assert!(config_path.exists());
@@ -403,7 +1309,7 @@ mod tests {
         std::fs::write(&config_path, "existing content").unwrap();
 
         // 应该成功但不覆盖文件
-        let result = init_config(Some(&config_path));
+        let result = init_config(&HumanReporter, Some(&config_path));
 
         assert!(result.is_ok());
         assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "existing content");
@@ -418,7 +1324,7 @@ mod tests {
         std::env::set_current_dir(&temp_dir).unwrap();
 
         // 使用默认路径
-        let result = init_config(None);
+        let result = init_config(&HumanReporter, None);
 
         // 恢复原始目录
         std::env::set_current_dir(&original_cwd).unwrap();
@@ -435,7 +1341,7 @@ mod tests {
         // 使用不存在的路径（会导致父目录不存在）
         let invalid_path = PathBuf::from("/nonexistent/path/.i18nrc.json");
 
-        let result = init_config(Some(&invalid_path));
+        let result = init_config(&HumanReporter, Some(&invalid_path));
 
         // 应该返回错误
         assert!(result.is_err());
@@ -446,14 +1352,14 @@ mod tests {
     #[test]
     fn test_cli_args_parse_version_command() {
         // 测试 version 子命令解析
-        let args = CliArgs::parse_from(&["yflow", "version"]);
+        let args = CliArgs::parse_from(["yflow", "version"]);
         assert!(matches!(args.command, Commands::Version));
     }
 
     #[test]
     fn test_cli_args_parse_help_cmd_command() {
         // 测试 help-cmd 子命令解析（无参数）
-        let args = CliArgs::parse_from(&["yflow", "help-cmd"]);
+        let args = CliArgs::parse_from(["yflow", "help-cmd"]);
         if let Commands::HelpCmd { command } = args.command {
             assert!(command.is_none());
         } else {
@@ -464,7 +1370,7 @@ mod tests {
     #[test]
     fn test_cli_args_parse_help_cmd_with_command() {
         // 测试 help-cmd 子命令解析（带参数）
-        let args = CliArgs::parse_from(&["yflow", "help-cmd", "import"]);
+        let args = CliArgs::parse_from(["yflow", "help-cmd", "import"]);
         if let Commands::HelpCmd { command } = args.command {
             assert_eq!(command, Some("import".to_string()));
         } else {
@@ -0,0 +1,140 @@
+//! Network activity display
+//!
+//! Installs a [`RequestObserver`] on the API client that shows a spinner
+//! while a backend call is in flight, with the running call, the number of
+//! responses received so far and the elapsed time:
+//!
+//! ```text
+//! ⠹ Fetching translations [00:00:12] 1 response(s)
+//! ```
+//!
+//! Nothing is drawn while progress bars or a phase [`Spinner`](super::spinner::Spinner)
+//! are on screen (they already show the activity), when progress output is disabled (`I18N_FORCE_PROGRESS=0`,
+//! `--ci`, `-q`, stdout not a terminal) or when stderr is not a terminal.
+
+use crate::api::observer::{self, RequestObserver};
+use indicatif::ProgressBar;
+use std::io::IsTerminal;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{progress, spinner};
+
+/// Spinner 模板
+const TEMPLATE: &str = "{spinner} {msg} [{elapsed_precise}] {pos} response(s)";
+
+/// Spinner 刷新间隔
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 当前显示状态
+static STATE: parking_lot::Mutex<State> = parking_lot::const_mutex(State::new());
+
+/// 进行中的调用和 spinner
+struct State {
+    /// 进行中的调用（按开始顺序）
+    active: Vec<String>,
+    /// 正在显示的 spinner
+    bar: Option<ProgressBar>,
+}
+
+impl State {
+    const fn new() -> Self {
+        Self {
+            active: Vec::new(),
+            bar: None,
+        }
+    }
+
+    /// 开始一个调用，`show` 为 false 时不创建 spinner
+    fn begin(&mut self, label: &str, show: bool) {
+        self.active.push(label.to_string());
+        if self.bar.is_none() && show {
+            let bar = ProgressBar::new_spinner();
+            bar.set_style(progress::spinner_style(TEMPLATE));
+            bar.enable_steady_tick(TICK_INTERVAL);
+            self.bar = Some(bar);
+        }
+        if let Some(bar) = &self.bar {
+            bar.set_message(label.to_string());
+        }
+    }
+
+    /// 结束一个调用，没有进行中的调用时清除 spinner
+    fn end(&mut self, label: &str) {
+        if let Some(index) = self.active.iter().rposition(|active| active == label) {
+            self.active.remove(index);
+        }
+        match self.active.last() {
+            Some(current) => {
+                if let Some(bar) = &self.bar {
+                    bar.set_message(current.clone());
+                }
+            }
+            None => {
+                if let Some(bar) = self.bar.take() {
+                    bar.finish_and_clear();
+                }
+            }
+        }
+    }
+}
+
+/// 显示网络活动的观察者
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ActivitySpinner;
+
+impl RequestObserver for ActivitySpinner {
+    fn begin(&self, label: &str) {
+        // 进度条或阶段 spinner 显示期间不再显示
+        STATE.lock().begin(label, !progress::is_active() && !spinner::is_active());
+    }
+
+    fn response(&self, _status: Option<u16>) {
+        if let Some(bar) = &STATE.lock().bar {
+            bar.inc(1);
+        }
+    }
+
+    fn end(&self, label: &str) {
+        STATE.lock().end(label);
+    }
+}
+
+/// 在终端中运行时为 API 客户端安装 [`ActivitySpinner`]
+pub fn install() {
+    if progress::should_show_progress() && std::io::stderr().is_terminal() {
+        observer::set_observer(Some(Arc::new(ActivitySpinner)));
+    }
+}
+
+/// 清除正在显示的 spinner（Ctrl-C 或超过截止时间时）
+pub fn clear() {
+    if let Some(bar) = STATE.lock().bar.take() {
+        bar.finish_and_clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinner_follows_active_calls() {
+        let mut state = State::new();
+        state.begin("Fetching translations", true);
+        state.begin("Checking authentication", true);
+        assert_eq!(state.bar.as_ref().unwrap().message(), "Checking authentication");
+
+        state.end("Checking authentication");
+        assert_eq!(state.bar.as_ref().unwrap().message(), "Fetching translations");
+        state.end("Fetching translations");
+        assert!(state.bar.is_none());
+        assert!(state.active.is_empty());
+
+        // 进度条显示期间只记录调用
+        state.begin("Pushing translations", false);
+        assert!(state.bar.is_none());
+        state.end("Pushing translations");
+        assert!(state.active.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+//! CI mode
+//!
+//! `--ci` makes a run suitable for pipelines: progress bars and spinners are
+//! hidden, logs are plain (no colors), prompts are never shown and errors
+//! exit with the codes of [`crate::core::failure`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 是否以 CI 模式运行
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 开启或关闭 CI 模式
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// 是否以 CI 模式运行
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,80 @@
+//! Log output setup
+//!
+//! Installs the tracing subscriber once per run: human-readable lines
+//! (`--log-format pretty`, the default) or one JSON object per event
+//! (`--log-format json`) for log collectors, on stderr and optionally
+//! appended to `--log-file`. stdout is left to the command's results (see
+//! [`super::reporter`]), so JSON and CSV output can be redirected cleanly.
+//! The level follows the run's [`Verbosity`](super::verbosity::Verbosity).
+//! Secrets are redacted in every output.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{Layer, Registry};
+
+use super::redact::Redacted;
+use super::verbosity::Verbosity;
+
+/// 日志格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// 每条日志一行文本
+    #[default]
+    Pretty,
+    /// 每条日志一个 JSON 对象
+    Json,
+}
+
+/// 日志设置
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// 详细程度
+    pub verbosity: Verbosity,
+    /// 日志格式
+    pub format: LogFormat,
+    /// 同时追加写入的日志文件
+    pub file: Option<PathBuf>,
+    /// 终端输出是否带颜色
+    pub ansi: bool,
+}
+
+/// 安装全局日志输出
+///
+/// # Errors
+///
+/// 日志文件无法打开或已安装过日志输出时返回错误
+pub fn init(options: &LogOptions) -> Result<()> {
+    let target = options.verbosity >= Verbosity::Verbose;
+    let mut layers = vec![layer(options.format, target, options.ansi, Redacted(std::io::stderr))];
+    if let Some(path) = &options.file {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log file {}", path.display()))?;
+        layers.push(layer(options.format, target, false, Redacted(Mutex::new(file))));
+    }
+
+    tracing_subscriber::registry()
+        .with(layers.with_filter(options.verbosity.level()))
+        .try_init()
+        .context("Failed to initialize logging")
+}
+
+/// 一个输出的日志层
+fn layer<W>(format: LogFormat, target: bool, ansi: bool, writer: W) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_target(target).with_ansi(ansi).with_writer(writer);
+    match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
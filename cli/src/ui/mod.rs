@@ -1,8 +1,21 @@
 //! UI utilities for progress display
 //!
-//! Provides progress bars and spinners for command-line feedback.
+//! Provides progress bars, spinners (including the network activity
+//! spinner) and confirmation prompts for command-line feedback, the
+//! terminal detection that decides what may be drawn, the `--ci` switch
+//! that turns them off, the reporter every command prints through and the
+//! summary table printed at the end of `import` and `sync`.
 
 #![allow(dead_code)]
 
+pub mod activity;
+pub mod ci;
+pub mod logging;
 pub mod progress;
+pub mod prompt;
+pub mod redact;
+pub mod reporter;
 pub mod spinner;
+pub mod summary;
+pub mod term;
+pub mod verbosity;
@@ -6,17 +6,30 @@
 //! # Architecture
 //!
 //! - `MultiProgressManager`: Manages multiple concurrent progress bars (one per language)
+//! - Progress bars are driven by `ProgressEvent`s emitted from the core layer
 //! - Progress bars support automatic cleanup on completion
+//! - `ProgressGuard` owns a manager for the length of an operation and clears
+//!   its bars on every exit path: early return, error, panic, or Ctrl-C (via
+//!   [`install_interrupt_handler`])
 //!
 //! # Environment Variables
 //!
-//! - `I18N_FORCE_PROGRESS=0`: Disable all progress displays
+//! - `I18N_FORCE_PROGRESS=0`: Disable all progress displays (as do `--ci` and `-q`)
+//! - `I18N_FORCE_PROGRESS=1`: Show progress even when stdout is not a terminal
 
-use indicatif::{HumanDuration, MultiProgress, ProgressBar, ProgressStyle};
+use crate::core::{ProgressCallback, ProgressEvent};
+use indicatif::{HumanBytes, HumanDuration, MultiProgress, ProgressBar, ProgressStyle};
 use std::collections::HashMap;
+use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use super::reporter::Reporter;
+use super::{activity, spinner, term};
+
+/// Spinner frames shared by every spinner (the last one is shown when finished)
+const SPINNER_TICKS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", " "];
 
 /// Progress bar style template
 const DEFAULT_TEMPLATE: &str = "{msg} [{elapsed_precise}] {wide_bar} {pos}/{len} ({percent}%)";
@@ -24,12 +37,28 @@ const DEFAULT_TEMPLATE: &str = "{msg} [{elapsed_precise}] {wide_bar} {pos}/{len}
 /// Characters used for the progress bar fill
 const PROGRESS_CHARS: &str = "█░";
 
+/// Exit code used when the run is interrupted with Ctrl-C (128 + SIGINT)
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Progress displays owned by live guards, cleared on Ctrl-C
+static ACTIVE: parking_lot::Mutex<Vec<(usize, MultiProgress)>> = parking_lot::const_mutex(Vec::new());
+
+/// Id source for registered guards
+static NEXT_GUARD_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// Check if progress display should be shown
+///
+/// `--ci` and `-q` always hide it; otherwise `I18N_FORCE_PROGRESS` (`0` or
+/// `1`) overrides the terminal detection of [`term`].
 pub fn should_show_progress() -> bool {
-    if std::env::var("I18N_FORCE_PROGRESS") == Ok("0".to_string()) {
+    if super::ci::is_enabled() || super::verbosity::is_quiet() {
         return false;
     }
-    true
+    match std::env::var("I18N_FORCE_PROGRESS").as_deref() {
+        Ok("0") => false,
+        Ok("1") => true,
+        _ => term::capabilities().progress,
+    }
 }
 
 /// Progress state for tracking completion status
@@ -37,6 +66,7 @@ pub fn should_show_progress() -> bool {
 pub struct ProgressState {
     total: Arc<AtomicUsize>,
     completed: Arc<AtomicUsize>,
+    bytes: Arc<AtomicUsize>,
 }
 
 impl ProgressState {
@@ -44,6 +74,7 @@ impl ProgressState {
         Self {
             total: Arc::new(AtomicUsize::new(total)),
             completed: Arc::new(AtomicUsize::new(0)),
+            bytes: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -55,6 +86,14 @@ impl ProgressState {
         self.completed.fetch_add(n, Ordering::SeqCst);
     }
 
+    pub fn add_bytes(&self, n: usize) {
+        self.bytes.fetch_add(n, Ordering::SeqCst);
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::SeqCst)
+    }
+
     pub fn completed(&self) -> usize {
         self.completed.load(Ordering::SeqCst)
     }
@@ -82,6 +121,7 @@ pub struct LanguageProgressBar {
     bar: ProgressBar,
     lang: String,
     state: ProgressState,
+    started: Instant,
     active: bool,
 }
 
@@ -99,6 +139,7 @@ impl LanguageProgressBar {
             bar,
             lang: lang.to_string(),
             state: ProgressState::new(total as usize),
+            started: Instant::now(),
             active: true,
         }
     }
@@ -131,6 +172,27 @@ impl LanguageProgressBar {
         }
     }
 
+    pub fn set_message(&self, message: String) {
+        if self.active {
+            self.bar.set_message(message);
+        }
+    }
+
+    /// Records an uploaded batch: counts its bytes and shows the batch,
+    /// the bytes sent so far and the ETA of the language
+    pub fn batch_sent(&self, batch: usize, total_batches: usize, keys: usize, bytes: usize) {
+        self.state.add_bytes(bytes);
+        let eta = calculate_eta(self.started.elapsed(), self.state.completed() + keys, self.state.total());
+        self.set_message(format!(
+            "📦 {} (batch {}/{}, {}, ETA {})",
+            self.lang,
+            batch,
+            total_batches,
+            HumanBytes(self.state.bytes() as u64),
+            eta
+        ));
+    }
+
     pub fn lang(&self) -> &str {
         &self.lang
     }
@@ -173,6 +235,7 @@ impl MultiProgressManager {
                 bar: ProgressBar::hidden(),
                 lang: lang.to_string(),
                 state: ProgressState::new(total as usize),
+                started: Instant::now(),
                 active: false,
             };
         }
@@ -182,6 +245,53 @@ impl MultiProgressManager {
         bar
     }
 
+    /// Applies a progress event to the managed bars
+    ///
+    /// - `LanguageStarted` creates a bar sized to the language's item count
+    /// - `FileWritten` advances the bar by one
+    /// - `KeysProcessed` advances the bar by `n`
+    /// - `BatchSent` counts the batch's bytes and shows them with the ETA
+    /// - `LanguageFinished` finishes the bar
+    pub fn handle_event(&self, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::LanguageStarted { lang, items, .. } => {
+                self.create_bar(lang, *items as u64);
+            }
+            ProgressEvent::FileWritten { lang, .. } => {
+                if let Some(bar) = self.bars.lock().get(lang) {
+                    bar.inc();
+                }
+            }
+            ProgressEvent::KeysProcessed { lang, n } => {
+                if let Some(bar) = self.bars.lock().get(lang) {
+                    bar.inc_by(*n as u64);
+                }
+            }
+            ProgressEvent::BatchSent {
+                lang,
+                batch,
+                total_batches,
+                keys,
+                bytes,
+            } => {
+                if let Some(bar) = self.bars.lock().get(lang) {
+                    bar.batch_sent(*batch, *total_batches, *keys, *bytes);
+                }
+            }
+            ProgressEvent::LanguageFinished { lang } => {
+                if let Some(bar) = self.bars.lock().get_mut(lang) {
+                    bar.finish();
+                }
+            }
+        }
+    }
+
+    /// Creates a progress callback that forwards events to this manager
+    pub fn progress_callback(&self) -> ProgressCallback {
+        let manager = self.clone();
+        Box::new(move |event| manager.handle_event(&event))
+    }
+
     pub fn stop(&self) {
         if self.enabled {
             self.multi_bar.clear().ok();
@@ -215,6 +325,96 @@ impl MultiProgressManager {
     }
 }
 
+/// RAII guard for the progress display of one operation
+///
+/// Dereferences to the wrapped [`MultiProgressManager`]. Call
+/// [`finish`](Self::finish) on success; if the guard is dropped without it
+/// (early return, `?` error, panic) the bars are abandoned and cleared so no
+/// half-drawn bar corrupts later output. Live guards are also cleared by the
+/// Ctrl-C handler.
+pub struct ProgressGuard {
+    manager: MultiProgressManager,
+    id: usize,
+    finished: bool,
+}
+
+impl Default for ProgressGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressGuard {
+    /// Creates a manager and registers it for interrupt cleanup
+    pub fn new() -> Self {
+        let manager = MultiProgressManager::new();
+        let id = NEXT_GUARD_ID.fetch_add(1, Ordering::SeqCst);
+        if manager.is_enabled() {
+            ACTIVE.lock().push((id, manager.multi_bar.clone()));
+        }
+        Self {
+            manager,
+            id,
+            finished: false,
+        }
+    }
+
+    /// Clears the bars after a successful operation
+    pub fn finish(mut self) {
+        self.manager.stop();
+        self.finished = true;
+    }
+}
+
+impl Deref for ProgressGuard {
+    type Target = MultiProgressManager;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        ACTIVE.lock().retain(|(id, _)| *id != self.id);
+        if !self.finished {
+            safe_stop_multi_progress(&self.manager);
+        }
+    }
+}
+
+/// Whether a progress display owned by a live guard is on screen
+pub fn is_active() -> bool {
+    !ACTIVE.lock().is_empty()
+}
+
+/// Clears every live progress display and spinner
+///
+/// Used when the process is about to exit without unwinding.
+pub fn clear_active() {
+    for (_, multi_bar) in ACTIVE.lock().drain(..) {
+        multi_bar.clear().ok();
+    }
+    if spinner::is_active() {
+        spinner::safe_stop_spinner();
+    }
+    activity::clear();
+}
+
+/// Installs a Ctrl-C handler that clears progress output before exiting
+///
+/// Must be called from within the Tokio runtime. Exits with
+/// [`INTERRUPTED_EXIT_CODE`].
+pub fn install_interrupt_handler(reporter: Arc<dyn Reporter>) {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            clear_active();
+            reporter.error("Interrupted.");
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+    });
+}
+
 /// Creates a single progress bar for simple use cases
 pub fn create_single_progress_bar(total: u64, prefix: &str) -> ProgressBar {
     let bar = ProgressBar::new(total);
@@ -227,6 +427,12 @@ pub fn create_single_progress_bar(total: u64, prefix: &str) -> ProgressBar {
     bar
 }
 
+/// Creates the spinner style shared by [`spinner::Spinner`] and the network
+/// activity spinner
+pub fn spinner_style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).unwrap().tick_strings(SPINNER_TICKS)
+}
+
 /// Format a number
 pub fn format_number(num: usize) -> String {
     num.to_string()
@@ -369,17 +575,54 @@ pub fn safe_stop_multi_progress(manager: &MultiProgressManager) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indicatif::ProgressDrawTarget;
     use std::time::Duration;
 
+    /// 不依赖终端检测的已启用管理器（不绘制）
+    fn enabled_manager() -> MultiProgressManager {
+        MultiProgressManager {
+            multi_bar: MultiProgress::with_draw_target(ProgressDrawTarget::hidden()),
+            bars: Arc::default(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_progress_guard_unregisters_on_drop() {
+        let registered = |id: usize| ACTIVE.lock().iter().any(|(active, _)| *active == id);
+
+        let guard = ProgressGuard::new();
+        let id = guard.id;
+        assert_eq!(registered(id), guard.is_enabled());
+        guard.create_bar("en", 10).inc();
+        drop(guard);
+        assert!(!registered(id));
+
+        let guard = ProgressGuard::new();
+        let id = guard.id;
+        guard.finish();
+        assert!(!registered(id));
+    }
+
+    #[test]
+    fn test_progress_guard_cleans_up_on_panic() {
+        let next_id = NEXT_GUARD_ID.load(Ordering::SeqCst);
+        let result = std::panic::catch_unwind(|| {
+            let guard = ProgressGuard::new();
+            guard.create_bar("de", 5);
+            panic!("boom");
+        });
+        assert!(result.is_err());
+        assert!(ACTIVE.lock().iter().all(|(id, _)| *id < next_id));
+    }
+
     #[test]
     fn test_should_show_progress_default() {
         // 确保环境变量未被设置
         std::env::remove_var("I18N_FORCE_PROGRESS");
-        // 默认应该返回 true（除非在特殊环境中）
-        // 这个测试检查函数的基本行为
+        // 未设置环境变量时由终端检测决定（stdout 是终端时显示）
         let result = should_show_progress();
-        // 验证函数不会 panic 并且返回可预测的值
-        assert!(result || !result, "should_show_progress should return a boolean");
+        assert_eq!(result, term::capabilities().progress);
     }
 
     #[test]
@@ -475,12 +718,13 @@ mod tests {
     #[test]
     fn test_multi_progress_manager_default() {
         let manager = MultiProgressManager::new();
-        assert!(manager.is_enabled());
+        // 是否启用取决于终端检测（管道中运行测试时不启用）
+        assert_eq!(manager.is_enabled(), should_show_progress());
     }
 
     #[test]
     fn test_multi_progress_manager_create_bar() {
-        let manager = MultiProgressManager::new();
+        let manager = enabled_manager();
         let bar = manager.create_bar("en", 100);
         assert_eq!(bar.lang(), "en");
         assert!(!manager.is_empty());
@@ -489,7 +733,7 @@ mod tests {
 
     #[test]
     fn test_multi_progress_manager_finish_all() {
-        let manager = MultiProgressManager::new();
+        let manager = enabled_manager();
         manager.create_bar("en", 10);
         manager.create_bar("zh", 20);
         manager.finish_all();
@@ -498,7 +742,7 @@ mod tests {
 
     #[test]
     fn test_language_progress_bar_increment() {
-        let manager = MultiProgressManager::new();
+        let manager = enabled_manager();
         let bar = manager.create_bar("en", 100);
         for _ in 0..5 {
             bar.inc();
@@ -509,7 +753,7 @@ mod tests {
 
     #[test]
     fn test_language_progress_bar_finish() {
-        let manager = MultiProgressManager::new();
+        let manager = enabled_manager();
         let mut bar = manager.create_bar("en", 10);
         bar.inc_by(10);
         bar.finish();
@@ -551,4 +795,56 @@ mod tests {
         safe_stop_multi_progress(&manager);
         // If this doesn't panic, the test passes
     }
+
+    #[test]
+    fn test_multi_progress_manager_handle_events() {
+        let manager = enabled_manager();
+        manager.handle_event(&ProgressEvent::LanguageStarted {
+            lang: "en".to_string(),
+            index: 1,
+            total: 1,
+            items: 10,
+        });
+        manager.handle_event(&ProgressEvent::KeysProcessed {
+            lang: "en".to_string(),
+            n: 4,
+        });
+        manager.handle_event(&ProgressEvent::FileWritten {
+            lang: "en".to_string(),
+            path: std::path::PathBuf::from("en/common.json"),
+        });
+        for batch in 1..=2 {
+            manager.handle_event(&ProgressEvent::BatchSent {
+                lang: "en".to_string(),
+                batch,
+                total_batches: 3,
+                keys: 2,
+                bytes: 120,
+            });
+        }
+
+        {
+            let bars = manager.bars.lock();
+            let bar = bars.get("en").unwrap();
+            assert_eq!(bar.state().completed(), 5);
+            assert_eq!(bar.state().bytes(), 240);
+            assert!(bar.is_active());
+        }
+
+        manager.handle_event(&ProgressEvent::LanguageFinished {
+            lang: "en".to_string(),
+        });
+        assert!(!manager.bars.lock().get("en").unwrap().is_active());
+        manager.stop();
+    }
+
+    #[test]
+    fn test_multi_progress_manager_ignores_unknown_language() {
+        let manager = MultiProgressManager::new();
+        manager.handle_event(&ProgressEvent::KeysProcessed {
+            lang: "xx".to_string(),
+            n: 1,
+        });
+        assert!(manager.is_empty());
+    }
 }
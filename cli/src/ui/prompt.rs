@@ -0,0 +1,90 @@
+//! Interactive confirmation prompts
+//!
+//! Prompts are only shown when both stdin and stderr are terminals and the
+//! run is not in CI (`--ci`, or `CI` set to anything but `false`/`0`); callers treat
+//! non-interactive runs as "not confirmed" (or "no answer"). Menus
+//! ([`select`]) additionally require stdout to be a terminal, so piping the
+//! output of a command skips them.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use super::{ci, progress};
+
+/// 判断当前运行是否可以交互确认
+pub fn is_interactive() -> bool {
+    let in_ci = std::env::var("CI")
+        .map(|value| !matches!(value.trim(), "" | "0" | "false"))
+        .unwrap_or(false);
+    !in_ci && !ci::is_enabled() && io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// 询问是否继续（默认否）
+///
+/// 非交互运行时不提示，直接返回 `false`。
+pub fn confirm(question: &str) -> io::Result<bool> {
+    if !is_interactive() {
+        return Ok(false);
+    }
+    progress::clear_active();
+
+    let mut stderr = io::stderr();
+    write!(stderr, "{} [y/N] ", question)?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(parse_answer(&answer))
+}
+
+/// 显示选择菜单，返回选中项的下标（方向键或数字键选择）
+///
+/// 非交互运行或 stdout 不是终端时不提示，直接返回 `None`。
+pub fn select(prompt: &str, items: &[&str], default: usize) -> io::Result<Option<usize>> {
+    if !is_interactive() || !io::stdout().is_terminal() {
+        return Ok(None);
+    }
+    progress::clear_active();
+
+    dialoguer::Select::new()
+        .with_prompt(prompt)
+        .items(items)
+        .default(default)
+        .interact()
+        .map(Some)
+        .map_err(|dialoguer::Error::IO(e)| e)
+}
+
+/// 询问密码类输入（不回显）
+///
+/// 非交互运行时不提示，直接返回 `None`。
+pub fn password(prompt: &str) -> io::Result<Option<String>> {
+    if !is_interactive() {
+        return Ok(None);
+    }
+    progress::clear_active();
+
+    dialoguer::Password::new()
+        .with_prompt(prompt)
+        .interact()
+        .map(Some)
+        .map_err(|dialoguer::Error::IO(e)| e)
+}
+
+/// 解析用户输入（只有 y/yes 表示确认）
+fn parse_answer(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        assert!(parse_answer("y\n"));
+        assert!(parse_answer(" YES "));
+        assert!(!parse_answer("\n"));
+        assert!(!parse_answer("no"));
+        assert!(!parse_answer("yep"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Redacted output
+//!
+//! Wraps the log writer and the panic hook so every registered secret (see
+//! [`crate::core::redact`]) is printed as `***`.
+
+use crate::core::redact::redact;
+use std::io::{self, Write};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// 隐藏密钥的日志输出
+pub struct Redacted<M>(pub M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacted<M> {
+    type Writer = RedactedWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactedWriter(self.0.make_writer())
+    }
+}
+
+/// 写入前替换密钥的 writer
+///
+/// tracing 每条日志只写入一次，密钥不会被拆分到两次写入中。
+pub struct RedactedWriter<W>(W);
+
+impl<W: Write> Write for RedactedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// 安装隐藏密钥的 panic 输出
+///
+/// 与默认输出相同的格式（线程名、位置、消息），消息中的密钥被替换。
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        let thread = std::thread::current();
+        let location = info
+            .location()
+            .map(|location| format!(" at {}:{}:{}", location.file(), location.line(), location.column()))
+            .unwrap_or_default();
+        eprintln!(
+            "\nthread '{}' panicked{}:\n{}",
+            thread.name().unwrap_or("<unnamed>"),
+            location,
+            redact(message)
+        );
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::redact::register;
+
+    #[test]
+    fn test_redacted_writer() {
+        register("ui-secret-key-42");
+        let mut out = Vec::new();
+        {
+            let mut writer = RedactedWriter(&mut out);
+            write!(writer, "request with ui-secret-key-42 failed").unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "request with *** failed");
+    }
+}
@@ -0,0 +1,222 @@
+//! User-facing output
+//!
+//! Every command prints through the run's [`Reporter`] instead of calling
+//! `println!` directly, so the output can be switched as a whole:
+//!
+//! - [`HumanReporter`] (default): results on stdout, status messages and
+//!   errors with emoji (when the terminal allows them) on stderr
+//! - [`JsonReporter`] (`--message-format json`): results unchanged on stdout,
+//!   each status message as one JSON object on stderr
+//! - [`QuietReporter`] (`-q`): results and errors only
+//!
+//! Results are what a command was asked for (reports, tables, values) and
+//! are printed by every reporter; status messages (success, warning, hint)
+//! are not. Logs stay with `tracing` on stderr (see [`super::logging`]).
+//!
+//! `main` picks the reporter once ([`for_run`]) and hands it to every
+//! command in its [`RunContext`](crate::cli::RunContext); helpers that print
+//! take it as a parameter. `yflow-core` cannot depend on it, so warnings
+//! raised inside the core (skipped files, unmergeable keys) are logged with
+//! `tracing::warn!` instead.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use super::term;
+use super::verbosity::Verbosity;
+
+/// 消息格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// 面向人的文本
+    #[default]
+    Human,
+    /// 每条消息一个 JSON 对象
+    Json,
+}
+
+/// 消息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    /// 操作成功
+    Success,
+    /// 警告
+    Warning,
+    /// 错误
+    Error,
+    /// 提示
+    Hint,
+}
+
+impl MessageKind {
+    /// 面向人的前缀（终端不支持 emoji 时不含 emoji）
+    fn prefix(self) -> String {
+        match self {
+            Self::Success => term::emoji("✅ ").to_string(),
+            Self::Warning => term::emoji("⚠️  ").to_string(),
+            Self::Error => format!("{}Error: ", term::emoji("❌ ")),
+            Self::Hint => format!("{}Hint: ", term::emoji("💡 ")),
+        }
+    }
+}
+
+/// 输出层
+pub trait Reporter: fmt::Debug + Send + Sync {
+    /// 原样输出命令的结果（不追加换行）
+    fn write(&self, text: &str);
+
+    /// 输出一条状态消息
+    fn message(&self, kind: MessageKind, text: &str);
+
+    /// 消息格式（只面向人的输出，如汇总表，在 JSON 格式下不输出）
+    fn message_format(&self) -> MessageFormat {
+        MessageFormat::Human
+    }
+}
+
+/// 面向人的输出
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanReporter;
+
+impl HumanReporter {
+    /// 消息的文本形式
+    pub fn format(kind: MessageKind, text: &str) -> String {
+        format!("{}{}", kind.prefix(), text)
+    }
+}
+
+impl Reporter for HumanReporter {
+    fn write(&self, text: &str) {
+        write_stdout(text);
+    }
+
+    fn message(&self, kind: MessageKind, text: &str) {
+        eprintln!("{}", Self::format(kind, text));
+    }
+}
+
+/// JSON 消息输出
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonReporter;
+
+impl JsonReporter {
+    /// 消息的 JSON 形式（一行）
+    pub fn format(kind: MessageKind, text: &str) -> String {
+        #[derive(Serialize)]
+        struct Message<'a> {
+            kind: MessageKind,
+            message: &'a str,
+        }
+        serde_json::to_string(&Message { kind, message: text }).unwrap_or_default()
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn write(&self, text: &str) {
+        write_stdout(text);
+    }
+
+    fn message(&self, kind: MessageKind, text: &str) {
+        eprintln!("{}", Self::format(kind, text));
+    }
+
+    fn message_format(&self) -> MessageFormat {
+        MessageFormat::Json
+    }
+}
+
+/// 只输出结果和错误
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuietReporter;
+
+impl Reporter for QuietReporter {
+    fn write(&self, text: &str) {
+        write_stdout(text);
+    }
+
+    fn message(&self, kind: MessageKind, text: &str) {
+        if kind == MessageKind::Error {
+            eprintln!("{}", HumanReporter::format(kind, text));
+        }
+    }
+}
+
+/// 写入 stdout（管道已关闭时忽略错误）
+fn write_stdout(text: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = stdout.write_all(text.as_bytes());
+    let _ = stdout.flush();
+}
+
+/// 按消息格式和详细程度选择输出层（`-q` 优先）
+pub fn for_run(format: MessageFormat, verbosity: Verbosity) -> Arc<dyn Reporter> {
+    match (verbosity, format) {
+        (Verbosity::Quiet, _) => Arc::new(QuietReporter),
+        (_, MessageFormat::Json) => Arc::new(JsonReporter),
+        (_, MessageFormat::Human) => Arc::new(HumanReporter),
+    }
+}
+
+impl dyn Reporter + '_ {
+    /// 输出一行结果
+    pub fn output(&self, text: impl fmt::Display) {
+        self.write(&format!("{}\n", text));
+    }
+
+    /// 输出成功消息
+    pub fn success(&self, text: impl fmt::Display) {
+        self.message(MessageKind::Success, &text.to_string());
+    }
+
+    /// 输出警告
+    pub fn warning(&self, text: impl fmt::Display) {
+        self.message(MessageKind::Warning, &text.to_string());
+    }
+
+    /// 输出错误
+    pub fn error(&self, text: impl fmt::Display) {
+        self.message(MessageKind::Error, &text.to_string());
+    }
+
+    /// 输出提示
+    pub fn hint(&self, text: impl fmt::Display) {
+        self.message(MessageKind::Hint, &text.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_format() {
+        assert_eq!(
+            JsonReporter::format(MessageKind::Success, "No \"lint\" issues found."),
+            r#"{"kind":"success","message":"No \"lint\" issues found."}"#
+        );
+        assert_eq!(
+            JsonReporter::format(MessageKind::Hint, "Run init"),
+            r#"{"kind":"hint","message":"Run init"}"#
+        );
+    }
+
+    #[test]
+    fn test_human_format() {
+        let line = HumanReporter::format(MessageKind::Warning, "3 stale");
+        assert!(line.ends_with("3 stale"));
+        assert!(line == "3 stale" || line.starts_with("⚠️"));
+        assert!(HumanReporter::format(MessageKind::Error, "boom").ends_with("Error: boom"));
+    }
+
+    #[test]
+    fn test_message_format() {
+        assert_eq!(HumanReporter.message_format(), MessageFormat::Human);
+        assert_eq!(QuietReporter.message_format(), MessageFormat::Human);
+        assert_eq!(JsonReporter.message_format(), MessageFormat::Json);
+        assert_eq!(for_run(MessageFormat::Json, Verbosity::Normal).message_format(), MessageFormat::Json);
+    }
+}
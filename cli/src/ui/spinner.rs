@@ -1,18 +1,45 @@
 //! Spinner utilities
 //!
-//! Provides animated spinner for indicating ongoing operations.
+//! An animated spinner for phases of unknown length (authentication,
+//! fetching translations). The animation is driven by a Tokio task, so it
+//! keeps turning while the phase awaits the backend; outside a runtime it is
+//! drawn once. The style is shared with the progress module. A spinner that
+//! is dropped without `stop` (early return, `?` error, panic) stops its task
+//! and clears its line itself.
 
-use std::io::Write;
+use indicatif::ProgressBar;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::task::JoinHandle;
 
-/// Spinner 字符集
-const SPINNER_CHARS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+use super::progress;
+
+/// Spinner 模板
+const TEMPLATE: &str = "{spinner} {msg} [{elapsed_precise}]";
+
+/// Spinner 刷新间隔
+const TICK_INTERVAL: Duration = Duration::from_millis(80);
+
+/// 正在显示的 spinner，Ctrl-C 时清除
+static ACTIVE: parking_lot::Mutex<Vec<(usize, ProgressBar)>> = parking_lot::const_mutex(Vec::new());
+
+/// spinner 的 id 来源
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// Spinner 实例
 ///
 /// 用于显示正在进行的操作。
 pub struct Spinner {
     message: String,
-    timer: Option<std::time::Instant>,
+    running: Option<Running>,
+}
+
+/// 正在显示的 spinner
+struct Running {
+    id: usize,
+    bar: ProgressBar,
+    /// 驱动动画的任务（不在 Tokio 运行时中时为 None）
+    task: Option<JoinHandle<()>>,
 }
 
 impl Spinner {
@@ -24,46 +51,111 @@ impl Spinner {
     pub fn new(message: &str) -> Self {
         Self {
             message: message.to_string(),
-            timer: None,
+            running: None,
         }
     }
 
-    /// 启动 spinner
+    /// 启动 spinner（不显示进度时不做任何事）
     pub fn start(&mut self) {
-        self.timer = Some(std::time::Instant::now());
-        self.tick(0);
+        if self.running.is_some() || !progress::should_show_progress() {
+            return;
+        }
+        self.draw();
     }
 
-    /// 更新 spinner
-    fn tick(&self, index: usize) {
-        let spin = SPINNER_CHARS[index % SPINNER_CHARS.len()];
-        print!("\r{} {}", spin, self.message);
-        let _ = std::io::stdout().flush();
+    /// 显示 spinner 并启动动画任务
+    fn draw(&mut self) {
+        let bar = ProgressBar::new_spinner();
+        bar.set_style(progress::spinner_style(TEMPLATE));
+        bar.set_message(self.message.clone());
+        bar.tick();
+
+        let task = tokio::runtime::Handle::try_current().ok().map(|handle| {
+            let bar = bar.clone();
+            handle.spawn(async move {
+                let mut interval = tokio::time::interval(TICK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    bar.tick();
+                }
+            })
+        });
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        ACTIVE.lock().push((id, bar.clone()));
+        self.running = Some(Running { id, bar, task });
     }
 
-    /// 停止 spinner
-    ///
-    /// # Arguments
+    /// 更新消息
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+        if let Some(running) = &self.running {
+            running.bar.set_message(self.message.clone());
+        }
+    }
+
+    /// 停止 spinner 并清除其所在行
     ///
-    /// * `success` - 是否成功完成
-    /// * `message` - 可选的完成消息
-    pub fn stop(&self, success: bool, message: Option<&str>) {
-        // 清除 spinner 行
-        print!("\r{}\r", " ".repeat(50));
-        let _ = std::io::stdout().flush();
-
-        if let Some(msg) = message {
-            if success {
-                println!("   ✓ {}", msg);
-            } else {
-                println!("   ✗ {}", msg);
+    /// 完成消息由调用方通过本次运行的输出层输出。
+    pub fn stop(&mut self) {
+        self.clear();
+    }
+
+    /// 是否正在显示
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// 停止动画任务并清除 spinner 行
+    fn clear(&mut self) {
+        if let Some(running) = self.running.take() {
+            if let Some(task) = running.task {
+                task.abort();
             }
+            ACTIVE.lock().retain(|(id, _)| *id != running.id);
+            running.bar.finish_and_clear();
         }
     }
 }
 
-/// 安全停止 spinner
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// 是否有正在显示的 spinner
+pub fn is_active() -> bool {
+    !ACTIVE.lock().is_empty()
+}
+
+/// 安全停止 spinner（清除所有正在显示的 spinner）
 pub fn safe_stop_spinner() {
-    print!("\r{}\r", " ".repeat(50));
-    let _ = std::io::stdout().flush();
+    for (_, bar) in ACTIVE.lock().drain(..) {
+        bar.finish_and_clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spinner_lifecycle() {
+        let mut spinner = Spinner::new("Checking authentication...");
+        spinner.draw();
+        assert!(spinner.is_running());
+        assert!(is_active());
+
+        spinner.set_message("Fetching translations...");
+        assert_eq!(
+            spinner.running.as_ref().unwrap().bar.message(),
+            "Fetching translations..."
+        );
+        let task = spinner.running.as_ref().unwrap().task.as_ref().unwrap().abort_handle();
+
+        // drop 时停止动画任务并清除
+        drop(spinner);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(task.is_finished());
+    }
 }
@@ -0,0 +1,102 @@
+//! End-of-run summary table
+//!
+//! Renders a [`RunSummary`] as a table with one row per language and a
+//! total row, followed by the elapsed time. Box-drawing characters are used
+//! on terminals that can show emoji, plain ASCII otherwise (pipes, `--ci`).
+//! The table is not printed with `-q` or `--message-format json`; the same
+//! data is in the JSON reports (`--summary-json`, `sync --output json`).
+
+use comfy_table::{presets, Cell, CellAlignment, Table};
+
+use crate::core::deadline::format_duration;
+use crate::core::run_summary::{LanguageSummary, RunSummary};
+
+use super::reporter::{MessageFormat, Reporter};
+use super::{term, verbosity};
+
+/// 表头
+const HEADER: [&str; 5] = ["LANGUAGE", "ADDED", "UPDATED", "SKIPPED", "FAILED"];
+
+/// 渲染汇总表
+///
+/// # 参数
+///
+/// * `summary` - 运行汇总
+/// * `unicode` - 是否使用制表符绘制边框
+pub fn render(summary: &RunSummary, unicode: bool) -> String {
+    let mut table = Table::new();
+    table.load_preset(if unicode {
+        presets::UTF8_FULL_CONDENSED
+    } else {
+        presets::ASCII_FULL_CONDENSED
+    });
+    table.set_header(HEADER);
+    for (lang, counts) in &summary.languages {
+        table.add_row(row(lang, counts));
+    }
+    table.add_row(row("TOTAL", &summary.total));
+    for index in 1..HEADER.len() {
+        if let Some(column) = table.column_mut(index) {
+            column.set_cell_alignment(CellAlignment::Right);
+        }
+    }
+    format!("{}\nElapsed: {}\n", table, format_duration(summary.duration()))
+}
+
+/// 输出汇总表（`-q` 或 JSON 消息格式时不输出）
+pub fn print(reporter: &dyn Reporter, summary: &RunSummary) {
+    if verbosity::is_quiet() || reporter.message_format() == MessageFormat::Json {
+        return;
+    }
+    reporter.write(&render(summary, term::capabilities().emoji));
+}
+
+/// 表格的一行
+fn row(label: &str, counts: &LanguageSummary) -> Vec<Cell> {
+    vec![
+        Cell::new(label),
+        Cell::new(counts.added),
+        Cell::new(counts.updated),
+        Cell::new(counts.skipped),
+        Cell::new(counts.failed),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LanguageCounts;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render() {
+        let languages = BTreeMap::from([
+            (
+                "de".to_string(),
+                LanguageCounts {
+                    added: 3,
+                    updated: 1,
+                    unchanged: 0,
+                    failed: 0,
+                },
+            ),
+            (
+                "en".to_string(),
+                LanguageCounts {
+                    added: 12,
+                    updated: 0,
+                    unchanged: 4,
+                    failed: 2,
+                },
+            ),
+        ]);
+        let rendered = render(&RunSummary::new(&languages, Duration::from_secs(2)), false);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "| LANGUAGE | ADDED | UPDATED | SKIPPED | FAILED |");
+        assert_eq!(lines[3], "| de       |     3 |       1 |       0 |      0 |");
+        assert_eq!(lines[4], "| en       |    12 |       0 |       4 |      2 |");
+        assert_eq!(lines[5], "| TOTAL    |    15 |       1 |       4 |      2 |");
+        assert_eq!(lines.last(), Some(&"Elapsed: 2s"));
+    }
+}
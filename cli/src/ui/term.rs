@@ -0,0 +1,95 @@
+//! Terminal capabilities
+//!
+//! Decides what the UI may draw: progress bars and spinners only when
+//! stdout is a terminal, emoji only on a terminal (or with
+//! `CLICOLOR_FORCE`), and colors unless `NO_COLOR` is set — `CLICOLOR_FORCE`
+//! (anything but `0`) forces them on even through a pipe. `--ci` turns all of
+//! them off.
+
+use std::io::{self, IsTerminal};
+
+use super::ci;
+
+/// 终端能力
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// 可以显示进度条和 spinner
+    pub progress: bool,
+    /// 可以输出 emoji
+    pub emoji: bool,
+    /// 可以输出颜色
+    pub color: bool,
+}
+
+impl Capabilities {
+    /// 由终端状态和环境变量得到能力
+    ///
+    /// # 参数
+    ///
+    /// * `tty` - stdout 是否为终端
+    /// * `no_color` - `NO_COLOR` 的值
+    /// * `clicolor_force` - `CLICOLOR_FORCE` 的值
+    /// * `ci` - 是否以 CI 模式运行
+    pub fn from_env(tty: bool, no_color: Option<&str>, clicolor_force: Option<&str>, ci: bool) -> Self {
+        if ci {
+            return Self {
+                progress: false,
+                emoji: false,
+                color: false,
+            };
+        }
+        let force = clicolor_force.is_some_and(|value| value != "0");
+        let no_color = no_color.is_some_and(|value| !value.is_empty());
+        Self {
+            progress: tty,
+            emoji: tty || force,
+            color: force || (tty && !no_color),
+        }
+    }
+}
+
+/// 当前运行的终端能力
+pub fn capabilities() -> Capabilities {
+    Capabilities::from_env(
+        io::stdout().is_terminal(),
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+        ci::is_enabled(),
+    )
+}
+
+/// 可以输出 emoji 时返回 `symbol`，否则返回空字符串
+///
+/// 用作消息前缀：`format!("{}Done", term::emoji("✅ "))`。
+pub fn emoji(symbol: &str) -> &str {
+    if capabilities().emoji {
+        symbol
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_env() {
+        let terminal = Capabilities::from_env(true, None, None, false);
+        assert!(terminal.progress && terminal.emoji && terminal.color);
+
+        let pipe = Capabilities::from_env(false, None, None, false);
+        assert!(!pipe.progress && !pipe.emoji && !pipe.color);
+
+        let no_color = Capabilities::from_env(true, Some("1"), None, false);
+        assert!(no_color.progress && no_color.emoji && !no_color.color);
+        assert!(Capabilities::from_env(true, Some(""), None, false).color);
+
+        let forced = Capabilities::from_env(false, Some("1"), Some("1"), false);
+        assert!(!forced.progress && forced.emoji && forced.color);
+        assert!(!Capabilities::from_env(false, None, Some("0"), false).color);
+
+        let ci = Capabilities::from_env(true, None, Some("1"), true);
+        assert!(!ci.progress && !ci.emoji && !ci.color);
+    }
+}
@@ -0,0 +1,83 @@
+//! Output verbosity
+//!
+//! `-q` keeps only errors: info logs, progress bars, spinners and success
+//! confirmations are suppressed, while the results a command was asked for
+//! (reports, values, tables) and errors are still printed. `-v` and `-vv`
+//! raise the log level to debug and trace.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::level_filters::LevelFilter;
+
+/// 输出详细程度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// 只输出错误（`-q`）
+    Quiet,
+    /// 默认
+    #[default]
+    Normal,
+    /// 调试日志（`-v`）
+    Verbose,
+    /// 跟踪日志（`-vv`）
+    Trace,
+}
+
+impl Verbosity {
+    /// 由 `-v` 的次数和 `-q` 得到详细程度
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            (false, _) => Self::Trace,
+        }
+    }
+
+    /// 对应的日志级别
+    pub fn level(self) -> LevelFilter {
+        match self {
+            Self::Quiet => LevelFilter::ERROR,
+            Self::Normal => LevelFilter::INFO,
+            Self::Verbose => LevelFilter::DEBUG,
+            Self::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// 当前的详细程度
+static CURRENT: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// 设置本次运行的详细程度
+pub fn set(verbosity: Verbosity) {
+    CURRENT.store(verbosity as u8, Ordering::SeqCst);
+}
+
+/// 本次运行的详细程度
+pub fn current() -> Verbosity {
+    match CURRENT.load(Ordering::SeqCst) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Trace,
+    }
+}
+
+/// 是否只输出错误
+pub fn is_quiet() -> bool {
+    current() == Verbosity::Quiet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_flags() {
+        assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(3, false), Verbosity::Trace);
+        assert_eq!(Verbosity::from_flags(0, true), Verbosity::Quiet);
+        assert_eq!(Verbosity::Quiet.level(), LevelFilter::ERROR);
+        assert_eq!(Verbosity::Trace.level(), LevelFilter::TRACE);
+    }
+}
@@ -0,0 +1,181 @@
+//! Backend capability discovery
+//!
+//! Newer backends advertise optional features through
+//! `GET /cli/capabilities`. Backends that predate the endpoint are treated as
+//! supporting only the core CLI routes (auth, keys, translations), so
+//! commands can disable optional features up front with a clear message
+//! instead of failing halfway through a run.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// 后端可选功能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Feature {
+    /// 只校验模式导入（`POST /cli/keys/validate`）
+    Validate,
+    /// 注册语言（`POST /cli/languages`）
+    Languages,
+    /// 删除语言（`DELETE /cli/languages/{code}`）
+    DeleteLanguage,
+    /// 删除键（`DELETE /cli/keys`）
+    DeleteKeys,
+    /// 按命名空间获取翻译（`GET /cli/namespaces`）
+    Namespaces,
+    /// 获取历史时间点的翻译（`as_of` 参数）
+    History,
+    /// 键锁（`/cli/locks`）
+    Locks,
+    /// 将翻译标记为未审核（`POST /cli/keys/unreviewed`）
+    Review,
+}
+
+impl Feature {
+    /// 所有可选功能
+    pub const ALL: [Feature; 8] = [
+        Feature::Validate,
+        Feature::Languages,
+        Feature::DeleteLanguage,
+        Feature::DeleteKeys,
+        Feature::Namespaces,
+        Feature::History,
+        Feature::Locks,
+        Feature::Review,
+    ];
+
+    /// 功能在 capabilities 响应中的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feature::Validate => "validate",
+            Feature::Languages => "languages",
+            Feature::DeleteLanguage => "delete_language",
+            Feature::DeleteKeys => "delete_keys",
+            Feature::Namespaces => "namespaces",
+            Feature::History => "history",
+            Feature::Locks => "locks",
+            Feature::Review => "review",
+        }
+    }
+
+    /// 面向用户的功能描述
+    pub fn description(&self) -> &'static str {
+        match self {
+            Feature::Validate => "server-side validation (POST /cli/keys/validate)",
+            Feature::Languages => "registering languages (POST /cli/languages)",
+            Feature::DeleteLanguage => "deleting languages (DELETE /cli/languages)",
+            Feature::DeleteKeys => "deleting keys (DELETE /cli/keys)",
+            Feature::Namespaces => "namespace-filtered fetches (GET /cli/namespaces)",
+            Feature::History => "point-in-time fetches (as_of)",
+            Feature::Locks => "key locks (/cli/locks)",
+            Feature::Review => "marking translations unreviewed (POST /cli/keys/unreviewed)",
+        }
+    }
+
+    /// 根据名称查找功能
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name() == name)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 后端能力
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// 后端版本（如有）
+    pub version: Option<String>,
+    /// 支持的可选功能
+    pub features: BTreeSet<Feature>,
+}
+
+impl Capabilities {
+    /// 不支持 capabilities 接口的旧后端：只有核心接口可用
+    pub fn legacy() -> Self {
+        Self::default()
+    }
+
+    /// 从响应的 `data` 字段解析
+    ///
+    /// `features` 既可以是名称数组，也可以是 `{名称: bool}` 对象；未知名称会被忽略。
+    pub fn from_data(data: &serde_json::Value) -> Self {
+        let features = match data.get("features") {
+            Some(serde_json::Value::Array(names)) => names
+                .iter()
+                .filter_map(|n| n.as_str())
+                .filter_map(Feature::from_name)
+                .collect(),
+            Some(serde_json::Value::Object(flags)) => flags
+                .iter()
+                .filter(|(_, enabled)| enabled.as_bool().unwrap_or(false))
+                .filter_map(|(name, _)| Feature::from_name(name))
+                .collect(),
+            _ => BTreeSet::new(),
+        };
+
+        Self {
+            version: data.get("version").and_then(|v| v.as_str()).map(|v| v.to_string()),
+            features,
+        }
+    }
+
+    /// 是否支持指定功能
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// 要求支持指定功能，否则返回说明性错误
+    pub fn require(&self, feature: Feature) -> anyhow::Result<()> {
+        if self.supports(feature) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Backend does not support {}",
+                feature.description()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_from_feature_list() {
+        let data = serde_json::json!({
+            "version": "1.4.0",
+            "features": ["validate", "languages", "future_feature"]
+        });
+        let caps = Capabilities::from_data(&data);
+        assert_eq!(caps.version.as_deref(), Some("1.4.0"));
+        assert!(caps.supports(Feature::Validate));
+        assert!(caps.supports(Feature::Languages));
+        assert!(!caps.supports(Feature::DeleteLanguage));
+        assert_eq!(caps.features.len(), 2);
+    }
+
+    #[test]
+    fn test_capabilities_from_feature_flags() {
+        let data = serde_json::json!({
+            "features": {"delete_language": true, "locks": false, "history": true, "tags": true}
+        });
+        let caps = Capabilities::from_data(&data);
+        assert!(caps.supports(Feature::DeleteLanguage));
+        assert!(caps.supports(Feature::History));
+        assert!(!caps.supports(Feature::Locks));
+        // 未知功能被忽略
+        assert_eq!(caps.features.len(), 2);
+    }
+
+    #[test]
+    fn test_legacy_requires_fail_with_description() {
+        let caps = Capabilities::legacy();
+        assert!(Feature::ALL.iter().all(|f| !caps.supports(*f)));
+        let error = caps.require(Feature::Languages).unwrap_err();
+        assert!(error.to_string().contains("POST /cli/languages"));
+    }
+}
@@ -0,0 +1,1883 @@
+//! API client implementation
+//!
+//! Handles all HTTP communication with the YFlow backend API.
+//! Provides methods for authentication, fetching translations, and pushing translations.
+//!
+//! The client is asynchronous and built on `reqwest`. A single underlying
+//! `reqwest::Client` (and therefore its connection pool) is shared by all
+//! clones of an [`APIClient`], so batched requests reuse open connections.
+//!
+//! Every request goes through the client's [`RetryPolicy`], so transient
+//! 5xx/429 responses and connection errors are retried with backoff no
+//! matter which command issued them.
+//!
+//! With `"authMode": "hmac"` each attempt is additionally signed by a
+//! [`RequestSigner`] (see [`super::signing`]).
+//!
+//! Push payloads carry the contributor's [`Author`] when one is known (see
+//! [`crate::core::attribution`]).
+//!
+//! Timeouts, TLS settings, the signer and the author are the client's
+//! [`ClientOptions`], taken from the loaded configuration with
+//! [`ClientOptions::from_config`]; clients for routed projects are derived
+//! with [`APIClient::for_project`] and share them.
+//!
+//! Fetches, pushes and other calls that can take a while are reported to the
+//! installed [`RequestObserver`](super::observer::RequestObserver) so the
+//! CLI can show that it is waiting on the backend.
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use rustls::pki_types::CertificateDer;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tokio::time::sleep;
+use tracing::{debug, info};
+
+use super::capabilities::{Capabilities, Feature};
+use super::observer;
+use super::signing::{AuthMode, RequestSigner};
+use super::tls::{self, Fingerprint};
+use super::proxy;
+use super::retry::{
+    is_retryable_error, is_retryable_status, parse_retry_after, RateLimitGate, RetryPolicy, ThrottleCounter,
+};
+use crate::core::attribution::{self, Author};
+use crate::core::failure::Failure;
+use crate::core::locks::KeyLock;
+use crate::core::{deadline, timestamp, I18nConfig, Translations};
+
+/// 默认请求超时（整个请求，包括读取响应体）
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 默认连接超时
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 每个主机保留的最大空闲连接数
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// TCP keepalive 间隔（避免长时间下载时连接被中间设备断开）
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+/// 请求超时设置（未设置的项使用默认值）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// 单个请求的总超时
+    pub timeout: Option<Duration>,
+    /// 建立连接的超时
+    pub connect: Option<Duration>,
+}
+
+impl Timeouts {
+    /// 配置中的超时（`timeoutSecs` / `connectTimeoutSecs`，已包含 `--timeout` 等命令行覆盖）
+    pub fn from_config(config: &I18nConfig) -> Self {
+        Self {
+            timeout: config.timeout_secs.map(Duration::from_secs),
+            connect: config.connect_timeout_secs.map(Duration::from_secs),
+        }
+    }
+
+    /// 为未设置的项补上默认值
+    ///
+    /// # Returns
+    ///
+    /// `(请求超时, 连接超时)`
+    pub fn resolve(self) -> (Duration, Duration) {
+        (
+            self.timeout.unwrap_or(DEFAULT_TIMEOUT),
+            self.connect.unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+        )
+    }
+}
+
+/// 客户端设置：超时、TLS、请求签名和推送时附带的作者
+///
+/// 同一次运行中的所有客户端（包括路由到其他项目的客户端）使用相同的设置。
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// 请求超时
+    pub timeouts: Timeouts,
+    /// 额外信任的 CA 证书（`tls.caCertPath`）
+    pub ca_certs: Vec<CertificateDer<'static>>,
+    /// 允许的服务端证书指纹（`pinnedSha256`，为空时不固定）
+    pub pins: Vec<Fingerprint>,
+    /// HMAC 请求签名器（`authMode: hmac` 时存在）
+    pub signer: Option<RequestSigner>,
+    /// 推送时附带的作者
+    pub author: Option<Author>,
+}
+
+impl ClientOptions {
+    /// 从加载后的配置得到客户端设置
+    ///
+    /// 作者依次取 `--author`、`I18N_AUTHOR`、本地 git 身份（见 [`attribution::current_author`]）。
+    ///
+    /// # Errors
+    ///
+    /// CA 证书无法读取或证书指纹无效时返回错误
+    pub fn from_config(config: &I18nConfig) -> Result<Self> {
+        Ok(Self {
+            timeouts: Timeouts::from_config(config),
+            ca_certs: config.tls.ca_certs()?,
+            pins: config
+                .pinned_sha256
+                .iter()
+                .map(|pin| tls::parse_fingerprint(pin))
+                .collect::<Result<_>>()?,
+            signer: match config.auth_mode {
+                AuthMode::Hmac => config.api_secret.clone().map(RequestSigner::new),
+                AuthMode::ApiKey => None,
+            },
+            author: attribution::current_author(config.author.as_ref()),
+        })
+    }
+}
+
+/// 带有超时、额外信任的 CA 证书和 `HTTPS_PROXY` / `HTTP_PROXY` 代理的 HTTP
+/// 客户端构建器
+///
+/// 后端客户端和密钥提供方（Vault / AWS Secrets Manager）共用这些设置；
+/// 证书指纹（pinnedSha256）只用于后端，不在此设置。
+///
+/// # Errors
+///
+/// CA 证书或代理地址无效时返回错误
+pub fn http_client_builder(timeouts: Timeouts, ca_certs: &[CertificateDer<'static>]) -> Result<reqwest::ClientBuilder> {
+    let (timeout, connect_timeout) = timeouts.resolve();
+    let mut builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(connect_timeout)
+        .user_agent(concat!("yflow/", env!("CARGO_PKG_VERSION")));
+    for cert in ca_certs {
+        let cert = reqwest::Certificate::from_der(cert).context("Invalid CA certificate (tls.caCertPath)")?;
+        builder = builder.add_root_certificate(cert);
+    }
+    for proxy in proxy::env_proxies()? {
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder)
+}
+
+/// API 客户端
+///
+/// 负责与后端 API 通信，包括：
+/// - 认证检查
+/// - 获取翻译
+/// - 推送翻译
+///
+/// 克隆客户端时共享同一个连接池。
+///
+/// # Example
+///
+/// ```ignore
+/// let client = APIClient::new(
+///     "http://localhost:8080/api".to_string(),
+///     "your-api-key".to_string(),
+///     1,
+/// )?;
+///
+/// // 检查认证
+/// if client.check_auth().await? {
+///     println!("Authenticated!");
+/// }
+///
+/// // 获取翻译
+/// let translations = client.get_translations().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct APIClient {
+    /// API 基础 URL
+    base_url: String,
+    /// API 密钥
+    api_key: String,
+    /// 项目 ID
+    project_id: u64,
+    /// HTTP 客户端（内部持有共享连接池）
+    http: reqwest::Client,
+    /// 后端能力（首次使用时探测，克隆之间共享）
+    capabilities: Arc<OnceCell<Capabilities>>,
+    /// 重试策略
+    retry: RetryPolicy,
+    /// 速率限制闸门（克隆之间共享）
+    gate: RateLimitGate,
+    /// 限流/暂时性失败计数（克隆之间共享）
+    throttled: ThrottleCounter,
+    /// 超时、TLS、签名器和作者（为路由项目创建客户端时沿用）
+    options: ClientOptions,
+}
+
+impl APIClient {
+    /// 使用默认设置创建 API 客户端
+    ///
+    /// 超时为 [`DEFAULT_TIMEOUT`]、[`DEFAULT_CONNECT_TIMEOUT`]，不固定证书、不签名请求、
+    /// 不附带作者；按配置创建时使用 [`APIClient::from_config`]。
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - API 基础 URL（会自动移除末尾斜杠）
+    /// * `api_key` - API 密钥
+    /// * `project_id` - 项目 ID
+    ///
+    /// # Errors
+    ///
+    /// 如果 URL 为空或格式无效，返回错误
+    ///
+    /// # URL 规范化说明
+    ///
+    /// 该方法会自动处理以下情况：
+    /// - 移除 URL 末尾的斜杠（如 `http://localhost/api/` → `http://localhost/api`）
+    /// - 去除首尾空白字符
+    /// - 验证 URL 必须以 `http://` 或 `https://` 开头
+    pub fn new(base_url: String, api_key: String, project_id: u64) -> Result<Self> {
+        Self::with_options(base_url, api_key, project_id, ClientOptions::default())
+    }
+
+    /// 为配置中的项目创建 API 客户端
+    ///
+    /// 超时、TLS、签名和作者取自 [`ClientOptions::from_config`]。
+    ///
+    /// # Errors
+    ///
+    /// 设置无效或 URL 无效时返回错误
+    pub fn from_config(config: &I18nConfig) -> Result<Self> {
+        Self::with_options(
+            config.api_url.clone(),
+            config.api_key.clone(),
+            config.project_id,
+            ClientOptions::from_config(config)?,
+        )
+    }
+
+    /// 以相同的设置为另一个项目创建 API 客户端（用于路由）
+    ///
+    /// 新客户端有自己的连接池、能力缓存和限流状态。
+    ///
+    /// # Errors
+    ///
+    /// URL 无效时返回错误
+    pub fn for_project(&self, base_url: String, api_key: String, project_id: u64) -> Result<Self> {
+        let client = Self::with_options(base_url, api_key, project_id, self.options.clone())?;
+        Ok(client.with_retry_policy(self.retry))
+    }
+
+    /// 使用指定设置创建 API 客户端
+    ///
+    /// 参数与校验规则同 [`APIClient::new`]。
+    pub fn with_options(base_url: String, api_key: String, project_id: u64, options: ClientOptions) -> Result<Self> {
+        // 验证 URL 不为空
+        if base_url.trim().is_empty() {
+            return Err(anyhow::anyhow!("API URL cannot be empty"));
+        }
+
+        // 规范化 URL：移除末尾斜杠和空白字符
+        // 这样可以容忍用户配置的 URL 末尾有或没有斜杠，保持与 TypeScript 实现一致
+        let normalized_url = base_url
+            .trim()
+            .trim_end_matches('/')
+            .to_string();
+
+        // 验证 URL 格式（简单验证，必须以 http:// 或 https:// 开头）
+        if !normalized_url.starts_with("http://") && !normalized_url.starts_with("https://") {
+            return Err(anyhow::anyhow!(
+                "API URL must start with 'http://' or 'https://', got: {}",
+                normalized_url
+            ));
+        }
+
+        // 验证项目 ID 为正数
+        if project_id == 0 {
+            return Err(anyhow::anyhow!("Project ID must be a positive integer"));
+        }
+
+        // 创建共享连接池的 HTTP 客户端
+        let mut builder = http_client_builder(options.timeouts, &options.ca_certs)?
+            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+            .tcp_keepalive(TCP_KEEPALIVE);
+        // 配置了 pinnedSha256 时校验服务端证书指纹（同时信任 tls.caCertPath 中的 CA）
+        if let Some(tls_config) = tls::pinned_client_config(&options.pins, &options.ca_certs)? {
+            builder = builder.use_preconfigured_tls(tls_config);
+        }
+        let http = builder.build().context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            base_url: normalized_url,
+            api_key,
+            project_id,
+            http,
+            capabilities: Arc::new(OnceCell::new()),
+            retry: RetryPolicy::default(),
+            gate: RateLimitGate::default(),
+            throttled: ThrottleCounter::default(),
+            options,
+        })
+    }
+
+    /// 设置重试策略（默认 [`RetryPolicy::default`]）
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// 使用 HMAC 签名请求（[`APIClient::from_config`] 取自配置的 `apiSecret`）
+    pub fn with_hmac_secret(mut self, secret: String) -> Self {
+        self.options.signer = Some(RequestSigner::new(secret));
+        self
+    }
+
+    /// 设置推送时附带的作者（[`APIClient::from_config`] 取自 [`attribution::current_author`]）
+    pub fn with_author(mut self, author: Option<Author>) -> Self {
+        self.options.author = author;
+        self
+    }
+
+    /// 获取推送时附带的作者
+    pub fn author(&self) -> Option<&Author> {
+        self.options.author.as_ref()
+    }
+
+    /// 获取重试策略
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// 此客户端（含克隆）收到 429/5xx 或连接失败的累计次数
+    ///
+    /// 用于 [`AdaptiveBatchSize`](super::retry::AdaptiveBatchSize) 调整批次大小。
+    pub fn throttle_events(&self) -> usize {
+        self.throttled.get()
+    }
+
+    /// 获取 API 基础 URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 获取 API 密钥
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// 获取项目 ID
+    pub fn project_id(&self) -> u64 {
+        self.project_id
+    }
+
+    /// 获取后端能力
+    ///
+    /// 首次调用时请求 `GET /cli/capabilities` 并缓存结果（克隆的客户端共享缓存）。
+    /// 不提供该接口的旧后端（404/405）视为只支持核心接口。
+    ///
+    /// # Errors
+    ///
+    /// 认证失败或请求失败时返回错误
+    pub async fn capabilities(&self) -> Result<&Capabilities> {
+        self.capabilities
+            .get_or_try_init(|| self.probe_capabilities())
+            .await
+    }
+
+    /// 是否支持指定的可选功能
+    pub async fn supports(&self, feature: Feature) -> Result<bool> {
+        Ok(self.capabilities().await?.supports(feature))
+    }
+
+    /// 请求 `GET /cli/capabilities`
+    async fn probe_capabilities(&self) -> Result<Capabilities> {
+        let _activity = observer::activity("Checking backend capabilities");
+        let url = format!("{}/cli/capabilities", self.base_url);
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .context("Failed to probe backend capabilities")?;
+
+        let capabilities = match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED => Capabilities::legacy(),
+            StatusCode::UNAUTHORIZED => return Err(Failure::Auth.error("API authentication failed")),
+            status if !status.is_success() => return Err(Self::api_error(response).await),
+            _ => {
+                let json: serde_json::Value = response
+                    .json()
+                    .await
+                    .context("Failed to parse response as JSON")?;
+                Capabilities::from_data(json.get("data").unwrap_or(&serde_json::Value::Null))
+            }
+        };
+
+        debug!(
+            "Backend capabilities: {}",
+            capabilities
+                .features
+                .iter()
+                .map(|f| f.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(capabilities)
+    }
+
+    /// 发送请求，按重试策略重试暂时性失败
+    ///
+    /// 429/5xx 响应和连接错误会在退避后重试；重试耗尽后返回最后一次的
+    /// 响应或错误，由调用方照常处理。等待会超过全局截止时间时不再重试。
+    async fn send(&self, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            self.gate.wait().await;
+            // 流式请求体无法克隆，只发送一次
+            let Some(current) = request.try_clone() else {
+                return self.execute(request).await;
+            };
+            let outcome = self.execute(current).await;
+
+            let (reason, retry_after) = match &outcome {
+                Ok(response) if is_retryable_status(response.status()) => (
+                    response.status().to_string(),
+                    response
+                        .headers()
+                        .get("Retry-After")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_retry_after),
+                ),
+                Err(e) if is_retryable_error(e) => (e.to_string(), None),
+                _ => return outcome,
+            };
+            self.throttled.record();
+            if attempt >= self.retry.max_attempts {
+                return outcome;
+            }
+
+            let delay = self.retry.delay(attempt, retry_after);
+            if deadline::current().is_some_and(|d| d.remaining() < delay) {
+                return outcome;
+            }
+            info!(
+                "  Request failed ({}), retrying in {}ms ({}/{})",
+                reason,
+                delay.as_millis(),
+                attempt,
+                self.retry.max_attempts - 1
+            );
+            match &outcome {
+                // 限流时暂停所有共享连接池的请求
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => self.gate.pause_for(delay),
+                _ => sleep(delay).await,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// 构建并发送单个请求，需要时附加签名（每次尝试使用新的时间戳）
+    async fn execute(&self, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+        let mut request = request.build()?;
+        if let Some(signer) = &self.options.signer {
+            signer.sign(&mut request, timestamp::now());
+        }
+        let outcome = self.http.execute(request).await;
+        observer::response(outcome.as_ref().ok().map(|response| response.status().as_u16()));
+        outcome
+    }
+
+    /// 在推送请求体中附带作者
+    fn attach_author(&self, body: &mut serde_json::Value) -> Result<()> {
+        if let Some(author) = &self.options.author {
+            body["author"] = serde_json::to_value(author)?;
+        }
+        Ok(())
+    }
+
+    /// 读取非成功响应并转换为错误
+    async fn api_error(response: reqwest::Response) -> anyhow::Error {
+        let status = response.status().as_u16();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::anyhow!("API error ({}): {}", status, error_text)
+    }
+
+    /// 检查 API 认证状态
+    ///
+    /// 向后端发送认证检查请求。
+    ///
+    /// # Returns
+    ///
+    /// 认证成功返回 `true`，失败返回 `false`
+    ///
+    /// # Errors
+    ///
+    /// 如果网络请求失败，返回错误
+    pub async fn check_auth(&self) -> Result<bool> {
+        let _activity = observer::activity("Checking authentication");
+        let url = format!("{}/cli/auth", self.base_url);
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Auth check failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(anyhow::anyhow!("Auth check failed: status code {}", status.as_u16())),
+        }
+    }
+
+    /// 获取所有翻译
+    ///
+    /// 从后端获取指定项目的所有翻译数据。
+    ///
+    /// # Returns
+    ///
+    /// 翻译数据，格式为 `{语言代码: {键: 值}}`
+    ///
+    /// # Errors
+    ///
+    /// 如果请求失败或响应格式错误，返回错误
+    pub async fn get_translations(&self) -> Result<Translations> {
+        let url = format!(
+            "{}/cli/translations?project_id={}",
+            self.base_url, self.project_id
+        );
+        self.fetch_translations(&url).await
+    }
+
+    /// 获取某个时间点的所有翻译
+    ///
+    /// 需要后端保存翻译历史（`history` 能力）。
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - 时间点（UNIX 秒，以 RFC 3339 UTC 形式发送）
+    ///
+    /// # Errors
+    ///
+    /// 后端不支持历史查询、请求失败或响应格式错误时返回错误
+    pub async fn get_translations_as_of(&self, as_of: u64) -> Result<Translations> {
+        self.capabilities().await?.require(Feature::History)?;
+        let url = format!(
+            "{}/cli/translations?project_id={}&as_of={}",
+            self.base_url,
+            self.project_id,
+            timestamp::format_rfc3339(as_of)
+        );
+        self.fetch_translations(&url).await
+    }
+
+    /// 请求翻译列表接口并转换为语言中心化格式
+    async fn fetch_translations(&self, url: &str) -> Result<Translations> {
+        let _activity = observer::activity("Fetching translations");
+        let response = self
+            .send(
+                self.http
+                .get(url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .context("Failed to fetch translations")?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(Failure::Auth.error("API authentication failed"));
+        }
+
+        if !status.is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        // 解析响应
+        let data = json.get("data")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in response"))?;
+
+        // 处理空响应
+        if data.is_null() {
+            return Ok(HashMap::new());
+        }
+
+        // API 返回键中心化格式: {key: {lang: value}}
+        // 需要转换为语言中心化格式: {lang: {key: value}}
+        let translations = Self::transform_translations_format(data.clone())?;
+
+        Ok(translations)
+    }
+
+    /// 转换翻译数据格式
+    ///
+    /// 从键中心化格式 `{key: {lang: value}}` 转换为语言中心化格式 `{lang: {key: value}}`
+    fn transform_translations_format(data: serde_json::Value) -> Result<Translations> {
+        let mut result: Translations = HashMap::new();
+
+        if let Some(keys_map) = data.as_object() {
+            for (key, langs_map) in keys_map {
+                if let Some(langs) = langs_map.as_object() {
+                    for (lang_code, value) in langs {
+                        let lang_translations = result
+                            .entry(lang_code.clone())
+                            .or_default();
+                        if let Some(value_str) = value.as_str() {
+                            lang_translations.insert(key.clone(), value_str.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 获取指定语言的翻译
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - 语言代码（如 "en"、"zh_CN"）
+    ///
+    /// # Returns
+    ///
+    /// 该语言的翻译数据
+    ///
+    /// # Errors
+    ///
+    /// 如果请求失败，返回错误
+    pub async fn get_translations_by_locale(&self, locale: &str) -> Result<HashMap<String, String>> {
+        let _activity = observer::activity(&format!("Fetching {} translations", locale));
+        let url = format!("{}/cli/translations", self.base_url);
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key)
+                .query(&[
+                    ("project_id", self.project_id.to_string()),
+                    ("locale", locale.to_string()),
+                ]),
+            )
+            .await
+            .context("Failed to fetch translations by locale")?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        Self::locale_translations(json, locale)
+    }
+
+    /// 取出按语言过滤的响应中该语言的翻译
+    ///
+    /// 过滤后的响应仍是键中心化格式 `{key: {locale: value}}`。
+    fn locale_translations(json: serde_json::Value, locale: &str) -> Result<HashMap<String, String>> {
+        let data = json.get("data")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in response"))?;
+        if data.is_null() {
+            return Ok(HashMap::new());
+        }
+        let mut translations = Self::transform_translations_format(data.clone())?;
+        Ok(translations.remove(locale).unwrap_or_default())
+    }
+
+    /// 获取指定语言下某个命名空间的翻译
+    ///
+    /// 需要后端支持命名空间过滤（[`Feature::Namespaces`]）。
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - 后端语言代码
+    /// * `namespace` - 命名空间（键的第一段）
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持或请求失败时返回错误
+    pub async fn get_translations_by_namespace(
+        &self,
+        locale: &str,
+        namespace: &str,
+    ) -> Result<HashMap<String, String>> {
+        self.capabilities().await?.require(Feature::Namespaces)?;
+        let _activity = observer::activity(&format!("Fetching {} translations ({})", locale, namespace));
+        let url = format!("{}/cli/translations", self.base_url);
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key)
+                .query(&[
+                    ("project_id", self.project_id.to_string()),
+                    ("locale", locale.to_string()),
+                    ("namespace", namespace.to_string()),
+                ]),
+            )
+            .await
+            .context("Failed to fetch translations by namespace")?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        Self::locale_translations(json, locale)
+    }
+
+    /// 获取项目的命名空间列表
+    ///
+    /// 调用 `GET /cli/namespaces`，需要后端支持 [`Feature::Namespaces`]。
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持或请求失败时返回错误
+    pub async fn list_namespaces(&self) -> Result<Vec<String>> {
+        self.capabilities().await?.require(Feature::Namespaces)?;
+        let _activity = observer::activity("Listing namespaces");
+        let url = format!(
+            "{}/cli/namespaces?project_id={}",
+            self.base_url, self.project_id
+        );
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .context("Failed to fetch namespaces")?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        let namespaces = json
+            .get("data")
+            .and_then(|d| d.as_array())
+            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default();
+
+        Ok(namespaces)
+    }
+
+    /// 批量推送翻译
+    ///
+    /// 将翻译数据批量导入到后端数据库。
+    /// 支持增量更新，只导入不存在的键或值发生变化的键。
+    ///
+    /// # Arguments
+    ///
+    /// * `translations` - 要推送的翻译数据，格式为 `{语言代码: {键: 值}}`
+    ///
+    /// # Returns
+    ///
+    /// 推送响应，包含 added、existed、failed 键名列表
+    ///
+    /// # Errors
+    ///
+    /// 如果请求失败，返回错误
+    pub async fn push_translations(&self, translations: Translations) -> Result<PushKeysResponse> {
+        let _activity = observer::activity("Pushing translations");
+        let url = format!("{}/cli/keys", self.base_url);
+
+        let mut body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "keys": [],
+            "translations": translations,
+        });
+        self.attach_author(&mut body)?;
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        // 处理速率限制
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(60);
+
+            return Err(anyhow::anyhow!(
+                "Rate limited. Retry after {} seconds",
+                retry_after
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        // 解析响应
+        let data = json.get("data")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in response"))?;
+
+        // 处理 data 为 null 的情况
+        if data.is_null() {
+            return Ok(PushKeysResponse::default());
+        }
+
+        Ok(PushKeysResponse {
+            added: data.get("added")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+            existed: data.get("existed")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+            failed: data.get("failed")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// 以只校验模式提交翻译
+    ///
+    /// 调用 `POST /cli/keys/validate`，后端按正式导入的规则校验但不写入，
+    /// 返回将会新增、更新和拒绝的键。
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持、不支持校验接口（404/405）或请求失败时返回错误
+    pub async fn validate_translations(&self, translations: Translations) -> Result<ValidateKeysResponse> {
+        self.capabilities().await?.require(Feature::Validate)?;
+        let url = format!("{}/cli/keys/validate", self.base_url);
+
+        let body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "keys": [],
+            "translations": translations,
+        });
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED => {
+                return Err(anyhow::anyhow!(
+                    "Backend does not support server-side validation (POST /cli/keys/validate)"
+                ));
+            }
+            status if !status.is_success() => return Err(Self::api_error(response).await),
+            _ => {}
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        let data = json.get("data")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in response"))?;
+
+        Ok(ValidateKeysResponse::from_data(data))
+    }
+
+    /// 在后端注册语言
+    ///
+    /// 调用 `POST /cli/languages`。后端导入时会忽略未注册的语言，
+    /// 因此新增语言需要先注册。
+    ///
+    /// # Returns
+    ///
+    /// 新建时返回 `true`，语言已存在（409）时返回 `false`
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持、不支持该接口（404/405）或请求失败时返回错误
+    pub async fn register_language(&self, code: &str) -> Result<bool> {
+        self.capabilities().await?.require(Feature::Languages)?;
+        let url = format!("{}/cli/languages", self.base_url);
+
+        let body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "code": code,
+        });
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::CONFLICT => Ok(false),
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED => Err(anyhow::anyhow!(
+                "Backend does not support registering languages (POST /cli/languages)"
+            )),
+            StatusCode::UNAUTHORIZED => Err(Failure::Auth.error("API authentication failed")),
+            status if status.is_success() => Ok(true),
+            _ => Err(Self::api_error(response).await),
+        }
+    }
+
+    /// 从后端删除语言
+    ///
+    /// 调用 `DELETE /cli/languages/{code}`，同时删除该语言在项目中的所有翻译。
+    ///
+    /// # Returns
+    ///
+    /// 删除成功返回 `true`，语言不存在时返回 `false`
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持、不支持该接口（405）或请求失败时返回错误
+    pub async fn delete_language(&self, code: &str) -> Result<bool> {
+        self.capabilities().await?.require(Feature::DeleteLanguage)?;
+        let url = format!(
+            "{}/cli/languages/{}?project_id={}",
+            self.base_url, code, self.project_id
+        );
+
+        let response = self
+            .send(
+                self.http
+                .delete(&url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            StatusCode::METHOD_NOT_ALLOWED => Err(anyhow::anyhow!(
+                "Backend does not support deleting languages (DELETE /cli/languages)"
+            )),
+            StatusCode::UNAUTHORIZED => Err(Failure::Auth.error("API authentication failed")),
+            status if status.is_success() => Ok(true),
+            _ => Err(Self::api_error(response).await),
+        }
+    }
+
+    /// 删除翻译键（包括其所有语言的翻译）
+    ///
+    /// 调用 `DELETE /cli/keys`，请求体与推送相同（`project_id`、`keys`，有作者时附带 `author`）。
+    ///
+    /// # Returns
+    ///
+    /// 后端实际删除的键数（响应中没有 `deleted` 时视为全部删除）
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持、不支持该接口（405）或请求失败时返回错误
+    pub async fn delete_keys(&self, keys: &[String]) -> Result<usize> {
+        self.capabilities().await?.require(Feature::DeleteKeys)?;
+        let url = format!("{}/cli/keys", self.base_url);
+
+        let mut body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "keys": keys,
+        });
+        self.attach_author(&mut body)?;
+
+        let response = self
+            .send(
+                self.http
+                .delete(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::METHOD_NOT_ALLOWED => {
+                return Err(anyhow::anyhow!(
+                    "Backend does not support deleting keys (DELETE /cli/keys)"
+                ))
+            }
+            StatusCode::UNAUTHORIZED => return Err(Failure::Auth.error("API authentication failed")),
+            status if !status.is_success() => return Err(Self::api_error(response).await),
+            _ => {}
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+        Ok(json
+            .get("data")
+            .and_then(|data| data.get("deleted"))
+            .and_then(|deleted| deleted.as_array())
+            .map(|deleted| deleted.len())
+            .unwrap_or(keys.len()))
+    }
+
+    /// 获取项目当前的键锁
+    ///
+    /// 调用 `GET /cli/locks`。
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持键锁或请求失败时返回错误
+    pub async fn list_locks(&self) -> Result<Vec<KeyLock>> {
+        self.capabilities().await?.require(Feature::Locks)?;
+        let url = format!("{}/cli/locks?project_id={}", self.base_url, self.project_id);
+
+        let response = self
+            .send(
+                self.http
+                .get(&url)
+                .header("X-API-Key", &self.api_key),
+            )
+            .await
+            .context("Failed to fetch key locks")?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        match json.get("data") {
+            Some(serde_json::Value::Null) | None => Ok(Vec::new()),
+            Some(data) => serde_json::from_value(data.clone()).context("Failed to parse key locks"),
+        }
+    }
+
+    /// 锁定键前缀
+    ///
+    /// 调用 `POST /cli/locks`。
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - 键前缀
+    /// * `holder` - 持有者（团队或用户）
+    /// * `ttl` - 锁的有效期（`None` 表示直到解锁）
+    ///
+    /// # Errors
+    ///
+    /// 前缀已被其他持有者锁定（409）、后端不支持或请求失败时返回错误
+    pub async fn lock_keys(&self, prefix: &str, holder: &str, ttl: Option<Duration>) -> Result<KeyLock> {
+        self.capabilities().await?.require(Feature::Locks)?;
+        let url = format!("{}/cli/locks", self.base_url);
+
+        let body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "prefix": prefix,
+            "holder": holder,
+            "ttl_seconds": ttl.map(|ttl| ttl.as_secs()),
+        });
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        if response.status() == StatusCode::CONFLICT {
+            let error = Self::api_error(response).await;
+            return Err(anyhow::anyhow!("'{}' is already locked: {}", prefix, error));
+        }
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        // 后端未返回锁详情时使用请求内容
+        Ok(json
+            .get("data")
+            .and_then(|data| serde_json::from_value(data.clone()).ok())
+            .unwrap_or_else(|| KeyLock {
+                prefix: prefix.to_string(),
+                holder: holder.to_string(),
+                expires_at: None,
+            }))
+    }
+
+    /// 解除键前缀的锁
+    ///
+    /// 调用 `DELETE /cli/locks`。`force` 为 true 时可解除其他持有者的锁。
+    ///
+    /// # Returns
+    ///
+    /// 解锁成功返回 `true`，该前缀未被锁定时返回 `false`
+    ///
+    /// # Errors
+    ///
+    /// 锁属于其他持有者（409）、后端不支持或请求失败时返回错误
+    pub async fn unlock_keys(&self, prefix: &str, holder: &str, force: bool) -> Result<bool> {
+        self.capabilities().await?.require(Feature::Locks)?;
+        let url = format!("{}/cli/locks", self.base_url);
+
+        let response = self
+            .send(
+                self.http
+                .delete(&url)
+                .header("X-API-Key", &self.api_key)
+                .query(&[
+                    ("project_id", self.project_id.to_string()),
+                    ("prefix", prefix.to_string()),
+                    ("holder", holder.to_string()),
+                    ("force", force.to_string()),
+                ]),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            StatusCode::CONFLICT => {
+                let error = Self::api_error(response).await;
+                Err(anyhow::anyhow!(
+                    "'{}' is locked by someone else (use --force to break the lock): {}",
+                    prefix,
+                    error
+                ))
+            }
+            status if status.is_success() => Ok(true),
+            _ => Err(Self::api_error(response).await),
+        }
+    }
+
+    /// 将翻译标记为未审核
+    ///
+    /// 调用 `POST /cli/keys/unreviewed`，请求体包含 `project_id` 和
+    /// `keys`（`{语言代码: [键]}`），有作者时附带 `author`。
+    ///
+    /// # Returns
+    ///
+    /// 后端标记的翻译数（响应中没有 `marked` 时视为全部标记）
+    ///
+    /// # Errors
+    ///
+    /// 后端未声明支持或请求失败时返回错误
+    pub async fn mark_unreviewed(&self, keys: &BTreeMap<String, Vec<String>>) -> Result<usize> {
+        self.capabilities().await?.require(Feature::Review)?;
+        let url = format!("{}/cli/keys/unreviewed", self.base_url);
+
+        let mut body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "keys": keys,
+        });
+        self.attach_author(&mut body)?;
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+        Ok(json
+            .get("data")
+            .and_then(|data| data.get("marked"))
+            .and_then(|marked| marked.as_u64())
+            .map(|marked| marked as usize)
+            .unwrap_or_else(|| keys.values().map(Vec::len).sum()))
+    }
+
+    /// 推送翻译键
+    ///
+    /// 创建新的翻译键（如果不存在），并可选地设置初始翻译值。
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - 要创建的键名列表
+    /// * `translations` - 可选的翻译数据
+    ///
+    /// # Returns
+    ///
+    /// 推送响应
+    ///
+    /// # Errors
+    ///
+    /// 如果请求失败，返回错误
+    pub async fn push_keys(
+        &self,
+        keys: Vec<String>,
+        translations: Option<Translations>,
+    ) -> Result<PushKeysResponse> {
+        let url = format!("{}/cli/keys", self.base_url);
+
+        let mut body = serde_json::json!({
+            "project_id": self.project_id.to_string(),
+            "keys": keys,
+        });
+
+        if let Some(trans) = translations {
+            body["translations"] = serde_json::to_value(trans)?;
+        }
+        self.attach_author(&mut body)?;
+
+        let response = self
+            .send(
+                self.http
+                .post(&url)
+                .header("X-API-Key", &self.api_key)
+                .json(&body),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::api_error(response).await);
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse response as JSON")?;
+
+        let data = json.get("data")
+            .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in response"))?;
+
+        Ok(PushKeysResponse {
+            added: data.get("added")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+            existed: data.get("existed")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+            failed: data.get("failed")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// 服务端校验响应
+///
+/// 描述后端在只校验模式下对一批翻译的判定结果，不会产生任何写入。
+#[derive(Debug, Clone, Default)]
+pub struct ValidateKeysResponse {
+    /// 将会新建的键
+    pub added: Vec<String>,
+    /// 将会更新的键
+    pub updated: Vec<String>,
+    /// 将被拒绝的键及原因
+    pub rejected: Vec<RejectedKey>,
+}
+
+/// 被服务端拒绝的键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedKey {
+    /// 键名
+    pub key: String,
+    /// 拒绝原因
+    pub reason: String,
+}
+
+impl ValidateKeysResponse {
+    /// 从响应的 `data` 字段解析
+    ///
+    /// `rejected` 的元素可以是 `{"key": ..., "reason": ...}` 对象，也可以是纯键名。
+    fn from_data(data: &serde_json::Value) -> Self {
+        let strings = |field: &str| -> Vec<String> {
+            data.get(field)
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default()
+        };
+
+        let rejected = data
+            .get("rejected")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|item| match item {
+                        serde_json::Value::String(key) => Some(RejectedKey {
+                            key: key.clone(),
+                            reason: String::new(),
+                        }),
+                        serde_json::Value::Object(obj) => Some(RejectedKey {
+                            key: obj.get("key")?.as_str()?.to_string(),
+                            reason: obj
+                                .get("reason")
+                                .and_then(|r| r.as_str())
+                                .unwrap_or_default()
+                                .to_string(),
+                        }),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            added: strings("added"),
+            updated: strings("updated"),
+            rejected,
+        }
+    }
+
+    /// 获取总处理数
+    pub fn total(&self) -> usize {
+        self.added.len() + self.updated.len() + self.rejected.len()
+    }
+}
+
+/// 推送键响应
+///
+/// 描述批量推送操作的结果。
+#[derive(Debug, Clone, Default)]
+pub struct PushKeysResponse {
+    /// 新创建的键
+    pub added: Vec<String>,
+    /// 已存在的键
+    pub existed: Vec<String>,
+    /// 失败的键
+    pub failed: Vec<String>,
+}
+
+impl PushKeysResponse {
+    /// 获取总处理数
+    pub fn total(&self) -> usize {
+        self.added.len() + self.existed.len() + self.failed.len()
+    }
+
+    /// 检查是否全部成功
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keys_response_default() {
+        let response = PushKeysResponse::default();
+        assert_eq!(response.total(), 0);
+        assert!(response.is_success());
+    }
+
+    #[test]
+    fn test_push_keys_response_with_data() {
+        let response = PushKeysResponse {
+            added: vec!["key1".to_string(), "key2".to_string()],
+            existed: vec!["key3".to_string()],
+            failed: Vec::new(),
+        };
+        assert_eq!(response.total(), 3);
+        assert!(response.is_success());
+    }
+
+    #[test]
+    fn test_push_keys_response_with_failures() {
+        let response = PushKeysResponse {
+            added: vec!["key1".to_string()],
+            existed: Vec::new(),
+            failed: vec!["key2".to_string()],
+        };
+        assert_eq!(response.total(), 2);
+        assert!(!response.is_success());
+    }
+
+    #[test]
+    fn test_validate_keys_response_from_data() {
+        let data = serde_json::json!({
+            "added": ["new.key"],
+            "updated": ["old.key"],
+            "rejected": [
+                {"key": "bad.key", "reason": "value too long"},
+                "other.key"
+            ]
+        });
+
+        let response = ValidateKeysResponse::from_data(&data);
+        assert_eq!(response.added, vec!["new.key".to_string()]);
+        assert_eq!(response.updated, vec!["old.key".to_string()]);
+        assert_eq!(response.rejected.len(), 2);
+        assert_eq!(response.rejected[0].reason, "value too long");
+        assert_eq!(response.rejected[1].key, "other.key");
+        assert_eq!(response.total(), 4);
+    }
+
+    #[test]
+    fn test_validate_keys_response_null_data() {
+        let response = ValidateKeysResponse::from_data(&serde_json::Value::Null);
+        assert_eq!(response.total(), 0);
+    }
+
+    #[test]
+    fn test_transform_translations_format_key_centric_to_language_centric() {
+        // Key-centric format (API response)
+        let key_centric = serde_json::json!({
+            "greeting": {
+                "en": "Hello",
+                "zh_CN": "你好"
+            },
+            "farewell": {
+                "en": "Goodbye"
+            },
+            "user.name": {
+                "en": "John",
+                "zh_CN": "张三"
+            }
+        });
+
+        let result = APIClient::transform_translations_format(key_centric).unwrap();
+
+        // Verify structure
+        assert_eq!(result.len(), 2);
+        assert!(result.contains_key("en"));
+        assert!(result.contains_key("zh_CN"));
+
+        // Verify English translations
+        let en = result.get("en").unwrap();
+        assert_eq!(en.len(), 3);
+        assert_eq!(en.get("greeting"), Some(&"Hello".to_string()));
+        assert_eq!(en.get("farewell"), Some(&"Goodbye".to_string()));
+        assert_eq!(en.get("user.name"), Some(&"John".to_string()));
+
+        // Verify Chinese translations
+        let zh = result.get("zh_CN").unwrap();
+        assert_eq!(zh.len(), 2);
+        assert_eq!(zh.get("greeting"), Some(&"你好".to_string()));
+        assert_eq!(zh.get("user.name"), Some(&"张三".to_string()));
+    }
+
+    #[test]
+    fn test_locale_translations() {
+        let json = serde_json::json!({
+            "data": {
+                "title": {"de": "Titel"},
+                "nav.home": {"de": "Start"}
+            }
+        });
+        let de = APIClient::locale_translations(json, "de").unwrap();
+        assert_eq!(de.len(), 2);
+        assert_eq!(de.get("nav.home"), Some(&"Start".to_string()));
+        assert!(APIClient::locale_translations(serde_json::json!({"data": null}), "de").unwrap().is_empty());
+        assert!(APIClient::locale_translations(serde_json::json!({}), "de").is_err());
+    }
+
+    #[test]
+    fn test_transform_translations_format_empty() {
+        let empty = serde_json::json!({});
+        let result = APIClient::transform_translations_format(empty).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_api_client_request_timeout() {
+        // 服务端接受连接但从不响应，请求应在超时后失败
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let options = ClientOptions {
+            timeouts: Timeouts {
+                timeout: Some(Duration::from_millis(200)),
+                connect: Some(Duration::from_millis(200)),
+            },
+            ..Default::default()
+        };
+        let client = APIClient::with_options(base_url, "test-key".to_string(), 1, options)
+            .unwrap()
+        .with_retry_policy(RetryPolicy::none());
+
+        let started = std::time::Instant::now();
+        assert!(client.check_auth().await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[test]
+    fn test_timeouts_resolve() {
+        assert_eq!(Timeouts::default().resolve(), (DEFAULT_TIMEOUT, DEFAULT_CONNECT_TIMEOUT));
+
+        let config = I18nConfig {
+            timeout_secs: Some(300),
+            ..Default::default()
+        };
+        assert_eq!(
+            Timeouts::from_config(&config).resolve(),
+            (Duration::from_secs(300), DEFAULT_CONNECT_TIMEOUT)
+        );
+    }
+
+    #[test]
+    fn test_client_options_from_config() {
+        let config = I18nConfig {
+            api_url: "https://example.com/api".to_string(),
+            api_key: "key-id".to_string(),
+            project_id: 1,
+            auth_mode: AuthMode::Hmac,
+            api_secret: Some("secret".to_string()),
+            pinned_sha256: vec!["ab".repeat(32)],
+            author: Some(Author::parse("Jane <jane@example.com>").unwrap()),
+            ..Default::default()
+        };
+        let options = ClientOptions::from_config(&config).unwrap();
+        assert_eq!(options.pins, vec![[0xab; 32]]);
+        assert!(options.signer.is_some());
+        assert_eq!(options.author, config.author);
+
+        // 路由到其他项目的客户端沿用相同的设置
+        let client = APIClient::with_options(config.api_url.clone(), config.api_key.clone(), 1, options).unwrap();
+        let routed = client.for_project("https://other.example.com".to_string(), "other".to_string(), 2).unwrap();
+        assert_eq!(routed.author(), config.author.as_ref());
+        assert_eq!(routed.project_id(), 2);
+
+        let config = I18nConfig {
+            auth_mode: AuthMode::ApiKey,
+            pinned_sha256: vec!["zz".to_string()],
+            ..config
+        };
+        assert!(ClientOptions::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_api_client_clones_share_pool() {
+        // 同一客户端的克隆复用连接：两次请求只建立一个连接
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            for _ in 0..2 {
+                let _ = stream.read(&mut buf).unwrap();
+                let body = r#"{"data": {}}"#;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let client = APIClient::new(base_url, "test-key".to_string(), 1).unwrap();
+        assert!(client.check_auth().await.unwrap());
+        assert!(client.clone().check_auth().await.unwrap());
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_client_retries_transient_errors() {
+        // 依次返回 503、429（Retry-After: 0）和 200，客户端应重试到成功
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let responses = [
+                "HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            ];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let client = APIClient::new(base_url.clone(), "test-key".to_string(), 1)
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                base_delay: Duration::from_millis(10),
+                ..RetryPolicy::default()
+            });
+        assert!(client.check_auth().await.unwrap());
+        server.join().unwrap();
+
+        // 不重试时直接返回 503 错误
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        });
+        let client = APIClient::new(base_url, "test-key".to_string(), 1)
+            .unwrap()
+            .with_retry_policy(RetryPolicy::none());
+        let error = client.get_translations().await.unwrap_err();
+        assert!(error.to_string().contains("503"));
+    }
+
+    #[tokio::test]
+    async fn test_api_client_signs_requests() {
+        // 配置 HMAC 密钥后，请求应带有可由服务端复算的签名头
+        use crate::api::signing;
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let client = APIClient::new(base_url, "key-id".to_string(), 1)
+            .unwrap()
+            .with_hmac_secret("secret".to_string());
+        assert!(client.check_auth().await.unwrap());
+
+        let raw = server.join().unwrap();
+        let header = |name: &str| {
+            raw.lines()
+                .find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    key.eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+                })
+                .unwrap()
+        };
+        let path = raw.split_whitespace().nth(1).unwrap();
+        let timestamp: u64 = header(signing::TIMESTAMP_HEADER).parse().unwrap();
+        let canonical = signing::canonical_string("GET", path, timestamp, &header(signing::CONTENT_SHA256_HEADER));
+        assert_eq!(header("x-api-key"), "key-id");
+        assert_eq!(
+            header(signing::SIGNATURE_HEADER),
+            RequestSigner::new("secret".to_string()).signature(&canonical)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_client_push_includes_author() {
+        // 推送请求体应带有作者；未设置作者时不带该字段
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body = loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length: usize = head
+                            .lines()
+                            .find_map(|line| {
+                                let (key, value) = line.split_once(':')?;
+                                key.eq_ignore_ascii_case("content-length").then(|| value.trim().parse().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length || n == 0 {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let response = r#"{"data":{"added":[],"existed":[],"failed":[]}}"#;
+                stream
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            response.len(),
+                            response
+                        )
+                        .as_bytes(),
+                    )
+                    .unwrap();
+                bodies.push(serde_json::from_str::<serde_json::Value>(&body).unwrap());
+            }
+            bodies
+        });
+
+        let author = Author::parse("Jane Doe <jane@example.com>").unwrap();
+        let client = APIClient::new(base_url, "key".to_string(), 1)
+            .unwrap()
+            .with_author(Some(author.clone()));
+        assert_eq!(client.author(), Some(&author));
+        client.push_translations(Translations::new()).await.unwrap();
+        client.with_author(None).push_keys(vec!["a".to_string()], None).await.unwrap();
+
+        let bodies = server.join().unwrap();
+        assert_eq!(
+            bodies[0]["author"],
+            serde_json::json!({"name": "Jane Doe", "email": "jane@example.com"})
+        );
+        assert!(bodies[1].get("author").is_none());
+    }
+
+    // ========== URL 规范化测试 ==========
+
+    #[test]
+    fn test_api_client_new_url_normalization_trailing_slash() {
+        // 测试移除 URL 末尾斜杠
+        let client = APIClient::new(
+            "http://localhost:8080/api/".to_string(),
+            "test-key".to_string(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/api");
+        assert_eq!(client.project_id(), 1);
+        assert_eq!(client.api_key(), "test-key");
+    }
+
+    #[test]
+    fn test_api_client_new_url_normalization_multiple_slashes() {
+        // 测试移除多个末尾斜杠
+        let client = APIClient::new(
+            "http://localhost:8080/api///".to_string(),
+            "test-key".to_string(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/api");
+    }
+
+    #[test]
+    fn test_api_client_new_url_normalization_with_whitespace() {
+        // 测试去除首尾空白字符
+        let client = APIClient::new(
+            "  http://localhost:8080/api  ".to_string(),
+            "test-key".to_string(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/api");
+    }
+
+    #[test]
+    fn test_api_client_new_url_with_path() {
+        // 测试带有路径的 URL
+        let client = APIClient::new(
+            "https://api.example.com/v1/".to_string(),
+            "test-key".to_string(),
+            42,
+        )
+        .unwrap();
+        assert_eq!(client.base_url(), "https://api.example.com/v1");
+        assert_eq!(client.project_id(), 42);
+    }
+
+    #[test]
+    fn test_api_client_new_url_invalid_empty() {
+        // 测试空 URL
+        let result = APIClient::new("".to_string(), "test-key".to_string(), 1);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("cannot be empty"));
+    }
+
+    #[test]
+    fn test_api_client_new_url_invalid_whitespace_only() {
+        // 测试只包含空白的 URL
+        let result = APIClient::new("   ".to_string(), "test-key".to_string(), 1);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("cannot be empty"));
+    }
+
+    #[test]
+    fn test_api_client_new_url_invalid_format_no_protocol() {
+        // 测试缺少协议的 URL
+        let result = APIClient::new("localhost:8080/api".to_string(), "test-key".to_string(), 1);
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("http://") || error_msg.contains("https://"));
+    }
+
+    #[test]
+    fn test_api_client_new_url_invalid_format_relative_path() {
+        // 测试相对路径 URL
+        let result = APIClient::new("/api/v1".to_string(), "test-key".to_string(), 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_api_client_new_project_id_zero() {
+        // 测试无效的项目 ID
+        let result = APIClient::new(
+            "http://localhost:8080/api".to_string(),
+            "test-key".to_string(),
+            0,
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("positive"));
+    }
+
+    #[test]
+    fn test_api_client_new_https_url() {
+        // 测试 HTTPS URL
+        let client = APIClient::new(
+            "https://secure-api.example.com/".to_string(),
+            "secure-key".to_string(),
+            99,
+        )
+        .unwrap();
+        assert_eq!(client.base_url(), "https://secure-api.example.com");
+        assert_eq!(client.project_id(), 99);
+    }
+}
@@ -4,4 +4,10 @@
 
 #![allow(dead_code)]
 
+pub mod capabilities;
 pub mod client;
+pub mod observer;
+pub mod proxy;
+pub mod retry;
+pub mod signing;
+pub mod tls;
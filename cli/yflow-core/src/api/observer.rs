@@ -0,0 +1,118 @@
+//! Request lifecycle observer
+//!
+//! Long backend calls (fetching every translation of a big project) used to
+//! be a silent pause. The API client now reports their lifecycle to the
+//! installed [`RequestObserver`]: an [`Activity`] spans one client call from
+//! the first request until the response body has been read, and every HTTP
+//! response (including retried attempts) is reported while it runs. The CLI
+//! installs an observer that shows a spinner with the elapsed time; without
+//! one (library use, tests) reporting is a no-op.
+
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// 请求生命周期观察者
+pub trait RequestObserver: Send + Sync {
+    /// 开始一个客户端调用（可能包含多个请求）
+    fn begin(&self, label: &str);
+    /// 收到一个响应（请求失败时 `status` 为 `None`）
+    fn response(&self, status: Option<u16>);
+    /// 客户端调用结束（成功或失败）
+    fn end(&self, label: &str);
+}
+
+/// 当前安装的观察者
+static OBSERVER: RwLock<Option<Arc<dyn RequestObserver>>> = parking_lot::const_rwlock(None);
+
+/// 安装观察者（`None` 时移除）
+pub fn set_observer(observer: Option<Arc<dyn RequestObserver>>) {
+    *OBSERVER.write() = observer;
+}
+
+/// 当前安装的观察者
+pub fn current() -> Option<Arc<dyn RequestObserver>> {
+    OBSERVER.read().clone()
+}
+
+/// 报告收到的响应
+pub fn response(status: Option<u16>) {
+    if let Some(observer) = current() {
+        observer.response(status);
+    }
+}
+
+/// 开始一个客户端调用，返回的 [`Activity`] 被丢弃时结束
+pub fn activity(label: &str) -> Activity {
+    let observer = current();
+    if let Some(observer) = &observer {
+        observer.begin(label);
+    }
+    Activity {
+        label: label.to_string(),
+        observer,
+    }
+}
+
+/// 进行中的客户端调用
+///
+/// 丢弃时（包括 `?` 提前返回）通知观察者调用结束。
+pub struct Activity {
+    /// 调用描述
+    label: String,
+    /// 开始时安装的观察者
+    observer: Option<Arc<dyn RequestObserver>>,
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        if let Some(observer) = &self.observer {
+            observer.end(&self.label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// 记录指定调用的开始和结束
+    struct Recorder {
+        label: &'static str,
+        events: Mutex<Vec<String>>,
+    }
+
+    impl RequestObserver for Recorder {
+        fn begin(&self, label: &str) {
+            if label == self.label {
+                self.events.lock().push("begin".to_string());
+            }
+        }
+
+        fn response(&self, _status: Option<u16>) {}
+
+        fn end(&self, label: &str) {
+            if label == self.label {
+                self.events.lock().push("end".to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_activity_reports_begin_and_end() {
+        // 未安装观察者时不报告
+        drop(activity("observer test"));
+
+        let recorder = Arc::new(Recorder {
+            label: "observer test",
+            events: Mutex::new(Vec::new()),
+        });
+        set_observer(Some(recorder.clone()));
+        {
+            let _activity = activity("observer test");
+            assert_eq!(*recorder.events.lock(), vec!["begin"]);
+        }
+        set_observer(None);
+        assert_eq!(*recorder.events.lock(), vec!["begin", "end"]);
+    }
+}
@@ -0,0 +1,350 @@
+//! Retry policy for API requests
+//!
+//! Transient failures (HTTP 429, 500, 502, 503, 504, connection errors and
+//! timeouts) are retried with exponential backoff. A `Retry-After` header
+//! (delta-seconds or HTTP-date) takes precedence over the computed backoff,
+//! and jitter spreads concurrent retries apart.
+//!
+//! A 429 pauses every clone of the client through a shared [`RateLimitGate`],
+//! so concurrent batches stop hitting the backend while it is throttling.
+//! Callers that send many batches can also feed the client's throttle count
+//! into an [`AdaptiveBatchSize`], which shrinks batches while the backend
+//! struggles and grows them back after sustained success.
+
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+
+use crate::core::timestamp::{self, DateTime};
+
+/// 默认最大尝试次数（含首次请求）
+pub const DEFAULT_MAX_ATTEMPTS: usize = 3;
+
+/// 默认首次重试延迟
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// 默认最大重试延迟（同样限制 `Retry-After`）
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// 重试策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（含首次请求，1 表示不重试）
+    pub max_attempts: usize,
+    /// 首次重试延迟，之后每次翻倍
+    pub base_delay: Duration,
+    /// 单次重试的最大延迟
+    pub max_delay: Duration,
+    /// 是否对延迟加随机抖动
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 不重试的策略
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// 第 `attempt` 次尝试失败后的等待时间
+    ///
+    /// 有 `Retry-After` 时使用该值，否则为 `base_delay * 2^(attempt-1)`；
+    /// 结果不超过 `max_delay`。抖动只作用于计算出的退避，不会缩短
+    /// 服务端要求的等待。
+    pub fn delay(&self, attempt: usize, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+        let exponent = attempt.saturating_sub(1).min(16) as u32;
+        let delay = self.base_delay.saturating_mul(1 << exponent).min(self.max_delay);
+        if self.jitter {
+            // 在 [delay/2, delay] 之间随机
+            let half = delay / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+/// 状态码是否值得重试
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// 请求错误是否值得重试（连接失败或超时）
+pub fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// 解析 `Retry-After` 头
+///
+/// 支持秒数（`120`）和 HTTP 日期（`Wed, 21 Oct 2015 07:28:00 GMT`）；
+/// 已过去的日期视为立即重试。
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = parse_http_date(value)?;
+    Some(Duration::from_secs(at.saturating_sub(timestamp::now())))
+}
+
+/// 解析 IMF-fixdate 格式的 HTTP 日期，返回 UNIX 时间戳
+fn parse_http_date(value: &str) -> Option<u64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let (_, rest) = value.split_once(", ")?;
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+    let [day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+    let month = MONTHS.iter().position(|m| m == month)? as u32 + 1;
+    let time: Vec<u32> = time.split(':').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    let [hour, minute, second] = time.as_slice() else {
+        return None;
+    };
+    Some(timestamp::from_datetime(DateTime {
+        year: year.parse().ok()?,
+        month,
+        day: day.parse().ok()?,
+        hour: *hour,
+        minute: *minute,
+        second: *second,
+    }))
+}
+
+/// [0, 1) 之间的随机数（足够用于抖动，无需引入随机数依赖）
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// 速率限制闸门
+///
+/// 任一请求收到 429 后，共享该闸门的所有请求都暂停到同一时间点再发送，
+/// 避免并发请求在限流期间继续冲击后端。
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitGate {
+    /// 暂停截止时间
+    paused_until: Arc<parking_lot::Mutex<Option<Instant>>>,
+}
+
+impl RateLimitGate {
+    /// 等待暂停结束（未暂停时立即返回）
+    pub async fn wait(&self) {
+        let paused_until = *self.paused_until.lock();
+        if let Some(until) = paused_until {
+            sleep_until(until).await;
+        }
+    }
+
+    /// 从现在起暂停 `duration`（不会缩短已有的暂停）
+    pub fn pause_for(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut paused_until = self.paused_until.lock();
+        if paused_until.is_none_or(|current| current < until) {
+            *paused_until = Some(until);
+        }
+    }
+}
+
+/// 被限流或暂时性失败的请求计数（克隆之间共享）
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleCounter {
+    /// 累计次数
+    count: Arc<AtomicUsize>,
+}
+
+impl ThrottleCounter {
+    /// 记录一次 429/5xx 响应或连接失败
+    pub fn record(&self) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 获取累计次数
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+/// 连续成功多少个批次后增大批次
+pub const GROW_AFTER: usize = 5;
+
+/// 自适应批次大小
+///
+/// 每个批次完成后调用 [`AdaptiveBatchSize::observe`]：期间客户端出现过限流或
+/// 暂时性失败时批次减半（不低于下限），连续 [`GROW_AFTER`] 个批次顺利时
+/// 翻倍（不超过上限）。
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    /// 当前批次大小
+    current: usize,
+    /// 下限
+    min: usize,
+    /// 上限
+    max: usize,
+    /// 连续成功的批次数
+    streak: usize,
+    /// 上次观察时的限流计数
+    last_throttled: usize,
+}
+
+impl AdaptiveBatchSize {
+    /// 创建自适应批次大小
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - 初始大小
+    /// * `min` / `max` - 取值范围
+    /// * `throttled` - 当前的限流计数（之前的限流不计入）
+    pub fn new(initial: usize, min: usize, max: usize, throttled: usize) -> Self {
+        let min = min.max(1);
+        Self {
+            current: initial.clamp(min, max.max(min)),
+            min,
+            max: max.max(min),
+            streak: 0,
+            last_throttled: throttled,
+        }
+    }
+
+    /// 当前批次大小
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// 根据最新的限流计数调整批次大小
+    ///
+    /// # Returns
+    ///
+    /// 大小发生变化时返回新的大小
+    pub fn observe(&mut self, throttled: usize) -> Option<usize> {
+        let previous = self.current;
+        if throttled > self.last_throttled {
+            self.last_throttled = throttled;
+            self.streak = 0;
+            self.current = (self.current / 2).max(self.min);
+        } else {
+            self.streak += 1;
+            if self.streak >= GROW_AFTER {
+                self.streak = 0;
+                self.current = (self.current * 2).min(self.max);
+            }
+        }
+        (self.current != previous).then_some(self.current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_backoff_and_cap() {
+        let policy = RetryPolicy {
+            jitter: false,
+            max_delay: Duration::from_secs(3),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay(1, None), Duration::from_millis(500));
+        assert_eq!(policy.delay(2, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(3, None), Duration::from_secs(2));
+        assert_eq!(policy.delay(4, None), Duration::from_secs(3));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(2))), Duration::from_secs(2));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(60))), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_delay_jitter_range() {
+        let policy = RetryPolicy::default();
+        for _ in 0..20 {
+            let delay = policy.delay(2, None);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+        assert_eq!(parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"), Some(1_445_412_480));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_is_retryable_status() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_gate_pauses_all_waiters() {
+        let gate = RateLimitGate::default();
+        gate.pause_for(Duration::from_millis(100));
+        // 较短的暂停不会缩短已有的暂停
+        gate.pause_for(Duration::from_millis(10));
+
+        let started = std::time::Instant::now();
+        gate.clone().wait().await;
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn test_adaptive_batch_size() {
+        let mut size = AdaptiveBatchSize::new(50, 5, 200, 3);
+        assert_eq!(size.current(), 50);
+
+        // 限流计数增加时减半，直到下限
+        assert_eq!(size.observe(4), Some(25));
+        assert_eq!(size.observe(5), Some(12));
+        assert_eq!(size.observe(6), Some(6));
+        assert_eq!(size.observe(7), Some(5));
+        assert_eq!(size.observe(8), None);
+
+        // 连续成功后翻倍，直到上限
+        for _ in 1..GROW_AFTER {
+            assert_eq!(size.observe(8), None);
+        }
+        assert_eq!(size.observe(8), Some(10));
+
+        let mut size = AdaptiveBatchSize::new(150, 5, 200, 0);
+        for _ in 1..GROW_AFTER {
+            size.observe(0);
+        }
+        assert_eq!(size.observe(0), Some(200));
+    }
+}
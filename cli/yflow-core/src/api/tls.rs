@@ -0,0 +1,303 @@
+//! TLS certificate pinning
+//!
+//! When `pinnedSha256` is configured, HTTPS connections to the backend are
+//! only accepted if the server certificate passes normal WebPKI validation
+//! *and* the SHA-256 fingerprint of its leaf certificate (DER, the value
+//! shown by `openssl x509 -fingerprint -sha256`) matches one of the pins.
+//!
+//! Pins are passed to each client in its
+//! [`ClientOptions`](super::client::ClientOptions); `--ignore-pins` drops
+//! them for a single run, e.g. right after the backend certificate was
+//! rotated. A rejected certificate's fingerprint is part of the error, so the
+//! CLI can print it (see [`rejected_fingerprint`]).
+//!
+//! A private CA (`"tls": {"caCertPath": "certs/corp-ca.pem"}`) is trusted in
+//! addition to the built-in WebPKI roots, both with and without pins, so the
+//! CLI works behind TLS-inspecting corporate proxies.
+
+use anyhow::{Context, Result};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// SHA-256 指纹
+pub type Fingerprint = [u8; 32];
+
+/// 证书被拒绝时错误信息的开头（后接指纹）
+const MISMATCH_PREFIX: &str = "server certificate SHA-256 ";
+
+/// TLS 配置（配置文件中的 `tls`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct TlsSettings {
+    /// 额外信任的 CA 证书文件（PEM，可包含多个证书）
+    #[serde(rename = "caCertPath", default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<PathBuf>,
+}
+
+impl TlsSettings {
+    /// 是否没有任何设置
+    pub fn is_empty(&self) -> bool {
+        self.ca_cert_path.is_none()
+    }
+
+    /// 读取 `caCertPath` 中的 CA 证书（未设置时为空）
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读取或不包含有效证书时返回错误
+    pub fn ca_certs(&self) -> Result<Vec<CertificateDer<'static>>> {
+        match &self.ca_cert_path {
+            Some(path) => read_ca_certs(path).context("Invalid tls.caCertPath"),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// 解析指纹（64 位十六进制，允许冒号分隔，不区分大小写）
+///
+/// # Errors
+///
+/// 格式无效时返回错误
+pub fn parse_fingerprint(value: &str) -> Result<Fingerprint> {
+    let hex_digits: String = value.trim().chars().filter(|c| *c != ':').collect();
+    let bytes = hex::decode(&hex_digits)
+        .ok()
+        .filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| anyhow::anyhow!("Invalid SHA-256 fingerprint '{}': expected 64 hex digits", value))?;
+    let mut fingerprint = [0u8; 32];
+    fingerprint.copy_from_slice(&bytes);
+    Ok(fingerprint)
+}
+
+/// 格式化指纹（大写、冒号分隔）
+pub fn format_fingerprint(fingerprint: &Fingerprint) -> String {
+    fingerprint
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 计算证书（DER）的 SHA-256 指纹
+pub fn certificate_fingerprint(der: &[u8]) -> Fingerprint {
+    Sha256::digest(der).into()
+}
+
+/// 读取 PEM 文件中的 CA 证书
+///
+/// # Errors
+///
+/// 文件无法读取、格式无效或不包含任何证书时返回错误
+pub fn read_ca_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path).with_context(|| format!("Failed to read CA certificate: {}", path.display()))?;
+    let certs = CertificateDer::pem_slice_iter(&pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid PEM in {}: {:?}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!("No certificate found in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+/// 错误链中被拒绝的证书指纹（用于错误提示）
+pub fn rejected_fingerprint(error: &anyhow::Error) -> Option<String> {
+    error.chain().find_map(|cause| {
+        let message = cause.to_string();
+        let start = message.find(MISMATCH_PREFIX)? + MISMATCH_PREFIX.len();
+        message[start..].split_whitespace().next().map(str::to_string)
+    })
+}
+
+/// 构建带证书固定的 rustls 配置
+///
+/// 未配置指纹（或指定了 `--ignore-pins`）时返回 `None`，使用 reqwest 默认配置。
+///
+/// # Arguments
+///
+/// * `pins` - 允许的叶子证书指纹
+/// * `ca_certs` - 除 WebPKI 根证书外额外信任的 CA 证书
+///
+/// # Errors
+///
+/// 构建 TLS 配置失败时返回错误
+pub fn pinned_client_config(
+    pins: &[Fingerprint],
+    ca_certs: &[CertificateDer<'static>],
+) -> Result<Option<rustls::ClientConfig>> {
+    if pins.is_empty() {
+        return Ok(None);
+    }
+    build_client_config(pins.to_vec(), ca_certs).map(Some)
+}
+
+/// 构建只接受指定指纹的 rustls 配置
+fn build_client_config(pins: Vec<Fingerprint>, ca_certs: &[CertificateDer<'static>]) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    for cert in ca_certs {
+        roots.add(cert.clone()).context("Invalid CA certificate (tls.caCertPath)")?;
+    }
+    let roots = Arc::new(roots);
+    let inner = WebPkiServerVerifier::builder_with_provider(roots, provider.clone())
+        .build()
+        .context("Failed to build TLS certificate verifier")?;
+
+    let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("Failed to configure TLS protocol versions")?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedVerifier { inner, pins, provider }))
+        .with_no_client_auth();
+    Ok(config)
+}
+
+/// 检查证书指纹是否在固定列表中
+fn check_pin(end_entity: &[u8], pins: &[Fingerprint]) -> Result<(), rustls::Error> {
+    let fingerprint = certificate_fingerprint(end_entity);
+    if pins.contains(&fingerprint) {
+        return Ok(());
+    }
+    Err(rustls::Error::General(format!(
+        "{}{} does not match any pinnedSha256 entry",
+        MISMATCH_PREFIX,
+        format_fingerprint(&fingerprint)
+    )))
+}
+
+/// 先做常规 WebPKI 校验，再比对叶子证书指纹
+#[derive(Debug)]
+struct PinnedVerifier {
+    /// 常规证书链校验
+    inner: Arc<WebPkiServerVerifier>,
+    /// 允许的指纹
+    pins: Vec<Fingerprint>,
+    /// 签名校验使用的加密实现
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+        check_pin(end_entity, &self.pins)?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fingerprint() {
+        let hex = "ab".repeat(32);
+        assert_eq!(parse_fingerprint(&hex).unwrap(), [0xab; 32]);
+
+        let colons = vec!["AB"; 32].join(":");
+        assert_eq!(parse_fingerprint(&colons).unwrap(), [0xab; 32]);
+        assert_eq!(format_fingerprint(&[0xab; 32]), colons);
+
+        assert!(parse_fingerprint("abcd").is_err());
+        assert!(parse_fingerprint(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_check_pin() {
+        let der = b"certificate";
+        let pin = certificate_fingerprint(der);
+        assert!(check_pin(der, &[[0u8; 32], pin]).is_ok());
+
+        let error = check_pin(der, &[[0u8; 32]]).unwrap_err();
+        assert!(error.to_string().contains(&format_fingerprint(&pin)));
+
+        let error = anyhow::Error::new(std::io::Error::other(error)).context("error sending request");
+        assert_eq!(rejected_fingerprint(&error), Some(format_fingerprint(&pin)));
+        assert_eq!(rejected_fingerprint(&anyhow::anyhow!("timed out")), None);
+    }
+
+    /// 自签名测试 CA（`openssl req -x509 -newkey ec`）
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBiDCCAS2gAwIBAgIUN1SA2ZseStTR6qS/4A8AXzWODNAwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNWUZsb3cgVGVzdCBDQTAgFw0yNjEwMTYyMDA1MDdaGA8yMTI2
+MDkyMjIwMDUwN1owGDEWMBQGA1UEAwwNWUZsb3cgVGVzdCBDQTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABBD/7ukfxK9kxo4yCtWYexs6X5Q1d+t+8wGjT/8zSv/X
+keI1+OFMA8gyrF6ZSLBcHm1k59rZ+V8i/XF6PTdodnSjUzBRMB0GA1UdDgQWBBSP
+Ratx/kYWgThKfN5mIuPx4L3esTAfBgNVHSMEGDAWgBSPRatx/kYWgThKfN5mIuPx
+4L3esTAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQCunBtGSyQh
+qsFTD7X+K83qH5169lZJH3o5Es0BWkIiaQIhAPWZPitESQkQNQYGW7RaHn8kdZER
+SyTNjA5MceJJKTel
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_read_ca_certs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("ca.pem");
+        std::fs::write(&path, format!("{}{}", TEST_CA, TEST_CA)).unwrap();
+        let certs = read_ca_certs(&path).unwrap();
+        assert_eq!(certs.len(), 2);
+        assert!(reqwest::Certificate::from_der(&certs[0]).is_ok());
+
+        std::fs::write(&path, "not a certificate").unwrap();
+        assert!(read_ca_certs(&path).unwrap_err().to_string().contains("No certificate"));
+        assert!(read_ca_certs(&temp_dir.path().join("missing.pem")).is_err());
+    }
+
+    #[test]
+    fn test_pinned_client_config_builds() {
+        // reqwest 只接受同一 rustls 版本的配置
+        let config = pinned_client_config(&[[0u8; 32]], &[]).unwrap().unwrap();
+        assert!(reqwest::Client::builder().use_preconfigured_tls(config).build().is_ok());
+        assert!(pinned_client_config(&[], &[]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! Language alias groups
+//!
+//! With `"aliases": {"en_GB": "en"}` the `en_GB` files only hold the keys
+//! whose text differs from `en`. Scanning treats an alias as a full copy of
+//! its base language with the alias files layered on top, so import, status
+//! and the backend see complete languages; when translations are written
+//! back, alias files only receive the keys that override the base.
+//!
+//! Aliases are a setting of the [`MessagesDir`], like the layout and
+//! `languageDirs` used by the [scanner](super::scanner).
+
+use std::collections::HashMap;
+
+use super::scanner::{language_dir, language_files, read_translation_file};
+use super::{MessagesDir, ScanResult, Translations};
+
+/// 将别名语言展开为基础语言的副本加上别名文件中的覆盖值
+///
+/// 继承的键不记录来源文件（它们不在别名语言的任何文件中），
+/// 基础语言不存在时别名语言保持不变。
+pub fn expand(messages_dir: &MessagesDir, scan: &mut ScanResult) {
+    for (alias, base) in &messages_dir.aliases {
+        let Some(base_keys) = scan.translations.get(base).cloned() else {
+            continue;
+        };
+        let overrides = scan.translations.remove(alias).unwrap_or_default();
+        let mut merged = base_keys;
+        merged.extend(overrides);
+        scan.translations.insert(alias.clone(), merged);
+    }
+}
+
+/// 写入前去掉别名语言中与基础语言相同的键
+///
+/// 基础语言的值取自同一批翻译；不在其中时（如只同步别名语言）
+/// 读取本地的基础语言文件。
+pub fn overrides(messages_dir: &MessagesDir, translations: &Translations) -> Translations {
+    let aliases = &messages_dir.aliases;
+    if aliases.is_empty() {
+        return translations.clone();
+    }
+
+    let mut result = translations.clone();
+    for (alias, base) in aliases {
+        let Some(alias_keys) = result.get_mut(alias) else {
+            continue;
+        };
+        let local_base;
+        let base_keys = match translations.get(base) {
+            Some(base_keys) => base_keys,
+            None => {
+                local_base = read_language(messages_dir, base);
+                &local_base
+            }
+        };
+        alias_keys.retain(|key, value| base_keys.get(key) != Some(value));
+    }
+    result
+}
+
+/// 读取本地某个语言的所有键
+fn read_language(messages_dir: &MessagesDir, lang: &str) -> HashMap<String, String> {
+    let dir = language_dir(messages_dir, lang);
+    let mut keys = HashMap::new();
+    for file in language_files(messages_dir, lang).unwrap_or_default() {
+        if let Ok(flat) = read_translation_file(messages_dir, &dir.join(file)) {
+            keys.extend(flat);
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scanner::{plan_translations_with_structure, scan_messages_dir};
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn keys(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_scan_expands_aliases() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "en/common.json", r#"{"color": "Color", "title": "Welcome"}"#);
+        write(temp_dir.path(), "en_GB/common.json", r#"{"color": "Colour"}"#);
+        let dir = MessagesDir {
+            aliases: BTreeMap::from([
+                ("en_GB".to_string(), "en".to_string()),
+                ("en_AU".to_string(), "en".to_string()),
+            ]),
+            ..MessagesDir::new(temp_dir.path())
+        };
+
+        let scan = scan_messages_dir(&dir).await.unwrap();
+        assert_eq!(scan.translations["en_GB"], keys(&[("color", "Colour"), ("title", "Welcome")]));
+        // 没有文件的别名语言是基础语言的完整副本
+        assert_eq!(scan.translations["en_AU"], scan.translations["en"]);
+        assert!(scan.origin("en_GB", "title").is_none());
+        assert!(scan.origin("en_GB", "color").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_write_keeps_only_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "en/common.json", r#"{"color": "Color", "title": "Welcome"}"#);
+        write(temp_dir.path(), "en_GB/common.json", r#"{"color": "Colour"}"#);
+        let dir = &MessagesDir {
+            aliases: BTreeMap::from([("en_GB".to_string(), "en".to_string())]),
+            ..MessagesDir::new(temp_dir.path())
+        };
+
+        let remote = Translations::from([
+            ("en".to_string(), keys(&[("color", "Color"), ("title", "Welcome"), ("save", "Save")])),
+            (
+                "en_GB".to_string(),
+                keys(&[("color", "Colour"), ("title", "Welcome"), ("save", "Save"), ("cancel", "Cancel")]),
+            ),
+        ]);
+        let stripped = overrides(dir, &remote);
+        assert_eq!(stripped["en_GB"], keys(&[("color", "Colour"), ("cancel", "Cancel")]));
+        assert_eq!(stripped["en"], remote["en"]);
+
+        // 基础语言不在本次写入中时读取本地文件
+        let only_alias = Translations::from([("en_GB".to_string(), remote["en_GB"].clone())]);
+        assert_eq!(
+            overrides(dir, &only_alias)["en_GB"],
+            keys(&[("color", "Colour"), ("save", "Save"), ("cancel", "Cancel")])
+        );
+
+        let files = vec![PathBuf::from("en/common.json"), PathBuf::from("en_GB/common.json")];
+        let plan = plan_translations_with_structure(dir, &files, &remote, false).await.unwrap();
+        let alias_change = plan
+            .languages
+            .iter()
+            .find(|language| language.lang == "en_GB")
+            .and_then(|language| language.changes.first())
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&alias_change.after).unwrap(),
+            serde_json::json!({"color": "Colour", "cancel": "Cancel"})
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Contributor attribution module
+//!
+//! Many engineers often share one CI API key, so the key alone does not say
+//! who changed a translation. Push payloads therefore carry an `author`
+//! object, taken from (in order):
+//!
+//! 1. the global `--author "Name <email>"` flag
+//! 2. the `I18N_AUTHOR` environment variable (same format)
+//! 3. the local git identity (`git config user.name` / `user.email`)
+//!
+//! When none of these is available the payload has no `author` field and the
+//! backend attributes the change to the API key as before.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::process::Command;
+
+/// 作者环境变量
+pub const AUTHOR_ENV_VAR: &str = "I18N_AUTHOR";
+
+/// 变更的作者
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Author {
+    /// 姓名
+    pub name: String,
+    /// 邮箱
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl Author {
+    /// 解析 `Name <email>` 或 `Name` 格式的作者
+    ///
+    /// # Errors
+    ///
+    /// 姓名为空或尖括号不完整时返回错误
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (name, email) = match input.split_once('<') {
+            Some((name, rest)) => {
+                let email = rest
+                    .strip_suffix('>')
+                    .ok_or_else(|| anyhow::anyhow!("Invalid author '{}': expected \"Name <email>\"", input))?
+                    .trim();
+                (name.trim(), (!email.is_empty()).then(|| email.to_string()))
+            }
+            None => (input, None),
+        };
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Invalid author '{}': name is required", input));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            email,
+        })
+    }
+
+    /// 读取本地 git 身份（`user.name` / `user.email`）
+    ///
+    /// 未安装 git 或未配置 `user.name` 时返回 `None`。
+    pub fn from_git() -> Option<Self> {
+        let name = git_config("user.name")?;
+        Some(Self {
+            name,
+            email: git_config("user.email"),
+        })
+    }
+}
+
+impl fmt::Display for Author {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.email {
+            Some(email) => write!(f, "{} <{}>", self.name, email),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// 确定当前作者
+///
+/// 依次使用显式指定的作者（`--author`）、`I18N_AUTHOR`、本地 git 身份。
+/// `I18N_AUTHOR` 格式无效时忽略。
+pub fn current_author(explicit: Option<&Author>) -> Option<Author> {
+    explicit
+        .cloned()
+        .or_else(|| {
+            std::env::var(AUTHOR_ENV_VAR)
+                .ok()
+                .and_then(|value| Author::parse(&value).ok())
+        })
+        .or_else(Author::from_git)
+}
+
+/// 读取一个 git 配置项（不存在或为空时返回 `None`）
+fn git_config(key: &str) -> Option<String> {
+    let output = Command::new("git").args(["config", "--get", key]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_author() {
+        assert_eq!(
+            Author::parse("Jane Doe <jane@example.com>").unwrap(),
+            Author {
+                name: "Jane Doe".to_string(),
+                email: Some("jane@example.com".to_string()),
+            }
+        );
+        assert_eq!(
+            Author::parse("  ci-bot ").unwrap(),
+            Author {
+                name: "ci-bot".to_string(),
+                email: None,
+            }
+        );
+        assert_eq!(Author::parse("Jane <>").unwrap().email, None);
+        assert!(Author::parse("").is_err());
+        assert!(Author::parse("<jane@example.com>").is_err());
+        assert!(Author::parse("Jane <jane@example.com").is_err());
+    }
+
+    #[test]
+    fn test_author_display_and_serialize() {
+        let author = Author::parse("Jane Doe <jane@example.com>").unwrap();
+        assert_eq!(author.to_string(), "Jane Doe <jane@example.com>");
+        assert_eq!(
+            serde_json::to_value(&author).unwrap(),
+            serde_json::json!({"name": "Jane Doe", "email": "jane@example.com"})
+        );
+
+        let author = Author::parse("ci-bot").unwrap();
+        assert_eq!(author.to_string(), "ci-bot");
+        assert_eq!(serde_json::to_value(&author).unwrap(), serde_json::json!({"name": "ci-bot"}));
+    }
+}
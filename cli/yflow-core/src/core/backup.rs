@@ -0,0 +1,304 @@
+//! File backups
+//!
+//! Before `sync` writes anything it copies every file it is about to change
+//! into `<messagesDir>/.yflow-backup/<id>/`, where the id is a compact UTC
+//! timestamp (`20261016T171600Z`). A `manifest.json` lists the original
+//! paths; files that did not exist yet are recorded too, so restoring a
+//! backup (`yflow rollback`) also removes the files the sync created.
+//!
+//! Only the [`MAX_BACKUPS`] most recent backups are kept.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use super::timestamp;
+
+/// 备份目录名（位于 messages 目录下）
+pub const BACKUP_DIR: &str = ".yflow-backup";
+
+/// 备份清单文件名
+const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// 保留的备份数量上限（创建新备份时删除更早的备份）
+pub const MAX_BACKUPS: usize = 20;
+
+/// 备份中的单个文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// 原始路径（位于 messages 目录内时为相对路径）
+    pub path: PathBuf,
+    /// 备份副本在备份目录内的路径；备份时文件不存在则为 `None`
+    pub copy: Option<PathBuf>,
+}
+
+/// 一次备份
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Backup {
+    /// 备份 ID（紧凑 UTC 时间戳，同一秒内的备份带序号后缀）
+    pub id: String,
+    /// 创建时间（UNIX 秒）
+    pub created_at: u64,
+    /// 创建备份的命令
+    pub command: String,
+    /// 备份的文件
+    pub files: Vec<BackupEntry>,
+}
+
+impl Backup {
+    /// 备份根目录
+    pub fn root(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(BACKUP_DIR)
+    }
+
+    /// 该备份的目录
+    pub fn dir(&self, messages_dir: &Path) -> PathBuf {
+        Self::root(messages_dir).join(&self.id)
+    }
+
+    /// 备份即将被修改的文件
+    ///
+    /// 复制 `paths` 中已存在的文件，不存在的文件只记录在清单中。
+    /// 创建后删除超出 [`MAX_BACKUPS`] 的旧备份。
+    ///
+    /// # 参数
+    ///
+    /// * `messages_dir` - messages 目录
+    /// * `command` - 创建备份的命令（如 `sync`）
+    /// * `paths` - 将被修改或创建的文件
+    pub fn create(messages_dir: &Path, command: &str, paths: &[PathBuf]) -> Result<Self> {
+        // 1. 确定备份 ID 和目录
+        let created_at = timestamp::now();
+        let stamp = timestamp::format_compact(created_at);
+        let root = Self::root(messages_dir);
+        let mut id = stamp.clone();
+        let mut suffix = 1;
+        while root.join(&id).exists() {
+            id = format!("{}-{}", stamp, suffix);
+            suffix += 1;
+        }
+        let dir = root.join(&id);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create backup directory: {}", dir.display()))?;
+
+        // 2. 复制已存在的文件
+        let mut files = Vec::with_capacity(paths.len());
+        for (index, path) in paths.iter().enumerate() {
+            let relative = path.strip_prefix(messages_dir).ok().filter(|relative| is_plain(relative));
+            let copy = if path.exists() {
+                let copy = match relative {
+                    Some(relative) => Path::new("files").join(relative),
+                    None => Path::new("external")
+                        .join(index.to_string())
+                        .join(path.file_name().unwrap_or_default()),
+                };
+                let target = dir.join(&copy);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create backup directory: {}", parent.display()))?;
+                }
+                std::fs::copy(path, &target)
+                    .with_context(|| format!("Failed to back up {}", path.display()))?;
+                Some(copy)
+            } else {
+                None
+            };
+            files.push(BackupEntry {
+                path: relative.map(Path::to_path_buf).unwrap_or_else(|| path.clone()),
+                copy,
+            });
+        }
+
+        // 3. 写入清单
+        let backup = Self {
+            id,
+            created_at,
+            command: command.to_string(),
+            files,
+        };
+        let manifest = dir.join(MANIFEST_FILENAME);
+        let content = serde_json::to_string_pretty(&backup).context("Failed to serialize backup manifest")?;
+        std::fs::write(&manifest, content)
+            .with_context(|| format!("Failed to write backup manifest: {}", manifest.display()))?;
+
+        // 4. 删除超出上限的旧备份
+        let ids = Self::list(messages_dir)?;
+        for old in ids.iter().take(ids.len().saturating_sub(MAX_BACKUPS)) {
+            let old_dir = root.join(old);
+            std::fs::remove_dir_all(&old_dir)
+                .with_context(|| format!("Failed to remove old backup: {}", old_dir.display()))?;
+        }
+
+        Ok(backup)
+    }
+
+    /// 列出所有备份 ID（按时间升序）
+    pub fn list(messages_dir: &Path) -> Result<Vec<String>> {
+        let root = Self::root(messages_dir);
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut ids: Vec<String> = std::fs::read_dir(&root)
+            .with_context(|| format!("Failed to read backup directory: {}", root.display()))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(MANIFEST_FILENAME).is_file())
+            .filter_map(|entry| entry.file_name().to_str().map(|s| s.to_string()))
+            .collect();
+        ids.sort_by(|a, b| backup_order(a).cmp(&backup_order(b)));
+        Ok(ids)
+    }
+
+    /// 按 ID 加载备份
+    pub fn load(messages_dir: &Path, id: &str) -> Result<Self> {
+        let manifest = Self::root(messages_dir).join(id).join(MANIFEST_FILENAME);
+        let content = std::fs::read_to_string(&manifest).with_context(|| format!("Backup not found: {}", id))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid backup manifest: {}", manifest.display()))
+    }
+
+    /// 解析备份引用
+    ///
+    /// 未指定时为最近的备份；引用可以是备份 ID，也可以是时间点
+    /// （见 [`timestamp::parse`]），后者解析为该时间点及之前最近的一个备份。
+    pub fn resolve(messages_dir: &Path, reference: Option<&str>) -> Result<Self> {
+        let ids = Self::list(messages_dir)?;
+        let Some(reference) = reference else {
+            let latest = ids.last().ok_or_else(|| {
+                anyhow::anyhow!("No backups found in {}", Self::root(messages_dir).display())
+            })?;
+            return Self::load(messages_dir, latest);
+        };
+        if ids.iter().any(|id| id == reference) {
+            return Self::load(messages_dir, reference);
+        }
+
+        let at = timestamp::parse(reference)
+            .with_context(|| format!("Unknown backup '{}' and not a valid timestamp", reference))?;
+        let cutoff = timestamp::format_compact(at);
+        let id = ids
+            .iter()
+            .rev()
+            .find(|id| backup_order(id).0 <= cutoff.as_str())
+            .ok_or_else(|| anyhow::anyhow!("No backup taken at or before {}", timestamp::format_rfc3339(at)))?;
+        Self::load(messages_dir, id)
+    }
+
+    /// 恢复备份
+    ///
+    /// 将备份的文件复制回原位置，删除备份时不存在的文件。
+    ///
+    /// # Returns
+    ///
+    /// 恢复的文件和删除的文件
+    pub fn restore(&self, messages_dir: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        let dir = self.dir(messages_dir);
+        let mut restored = Vec::new();
+        let mut removed = Vec::new();
+
+        for entry in &self.files {
+            let path = messages_dir.join(&entry.path);
+            match &entry.copy {
+                Some(copy) => {
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)
+                            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+                    }
+                    std::fs::copy(dir.join(copy), &path)
+                        .with_context(|| format!("Failed to restore {}", path.display()))?;
+                    restored.push(path);
+                }
+                None if path.exists() => {
+                    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+                    // 新建文件所在的目录为空时一并删除（如同步创建的新语言目录）
+                    if let Some(parent) = path.parent().filter(|parent| parent.starts_with(messages_dir)) {
+                        let _ = std::fs::remove_dir(parent);
+                    }
+                    removed.push(path);
+                }
+                None => {}
+            }
+        }
+        Ok((restored, removed))
+    }
+}
+
+/// 路径是否只由普通组成部分构成（不含 `..` 等）
+fn is_plain(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// 备份 ID 的排序键：时间戳部分和同一秒内的序号
+fn backup_order(id: &str) -> (&str, u32) {
+    match id.split_once('-') {
+        Some((stamp, suffix)) => (stamp, suffix.parse().unwrap_or(0)),
+        None => (id, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let outside = temp_dir.path().join("vendor/de.json");
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(outside.parent().unwrap()).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Old"}"#).unwrap();
+        std::fs::write(&outside, r#"{"title": "Alt"}"#).unwrap();
+
+        let paths = vec![messages_dir.join("en/common.json"), messages_dir.join("ja/sync.json"), outside.clone()];
+        let backup = Backup::create(&messages_dir, "sync", &paths).unwrap();
+        assert_eq!(backup.files[0].path, PathBuf::from("en/common.json"));
+        assert_eq!(backup.files[1].copy, None);
+        assert_eq!(backup.files[2].path, outside);
+
+        // 模拟同步：修改文件并创建新语言
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "New"}"#).unwrap();
+        std::fs::write(&outside, "{}").unwrap();
+        std::fs::create_dir_all(messages_dir.join("ja")).unwrap();
+        std::fs::write(messages_dir.join("ja/sync.json"), "{}").unwrap();
+
+        let backup = Backup::resolve(&messages_dir, None).unwrap();
+        let (restored, removed) = backup.restore(&messages_dir).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(removed, vec![messages_dir.join("ja/sync.json")]);
+        assert_eq!(std::fs::read_to_string(messages_dir.join("en/common.json")).unwrap(), r#"{"title": "Old"}"#);
+        assert_eq!(std::fs::read_to_string(&outside).unwrap(), r#"{"title": "Alt"}"#);
+        assert!(!messages_dir.join("ja").exists());
+    }
+
+    #[test]
+    fn test_list_resolve_and_prune() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path();
+        assert!(Backup::resolve(messages_dir, None).is_err());
+
+        let first = Backup::create(messages_dir, "sync", &[]).unwrap();
+        let second = Backup::create(messages_dir, "sync", &[]).unwrap();
+        assert_ne!(first.id, second.id);
+        assert_eq!(Backup::list(messages_dir).unwrap().last(), Some(&second.id));
+        assert_eq!(Backup::resolve(messages_dir, Some(&first.id)).unwrap(), first);
+        assert!(Backup::resolve(messages_dir, Some("2000-01-01T00:00:00Z")).is_err());
+        assert!(Backup::resolve(messages_dir, Some("nonsense")).is_err());
+
+        for _ in 0..MAX_BACKUPS {
+            Backup::create(messages_dir, "sync", &[]).unwrap();
+        }
+        let ids = Backup::list(messages_dir).unwrap();
+        assert_eq!(ids.len(), MAX_BACKUPS);
+        assert!(!ids.contains(&first.id));
+    }
+
+    #[test]
+    fn test_backup_order() {
+        let mut ids = vec!["20261016T171600Z-10", "20261016T171600Z-2", "20261016T171600Z", "20261015T000000Z"];
+        ids.sort_by(|a, b| backup_order(a).cmp(&backup_order(b)));
+        assert_eq!(ids, ["20261015T000000Z", "20261016T171600Z", "20261016T171600Z-2", "20261016T171600Z-10"]);
+    }
+}
@@ -0,0 +1,180 @@
+//! Large change guard module
+//!
+//! Protects a repository against backend mishaps (an emptied project, a
+//! wrong project ID, a bad bulk edit) by measuring how many local keys the
+//! sync plan would overwrite or delete before any file is written. The count
+//! comes from the final plan, so it does not depend on which flags
+//! (`--force`, `--force-keys`, `--prune`) produced it. When the share exceeds
+//! `maxChangePercent` (default 30%) `sync` asks for confirmation, or fails
+//! in non-interactive runs.
+//!
+//! Only non-empty local values count (filling in a blank is not a loss), and
+//! runs changing fewer than [`MIN_GUARDED_CHANGES`] keys are never blocked so
+//! small projects are not nagged.
+
+use std::collections::BTreeMap;
+
+use super::sync_plan::{KeyAction, SyncPlan};
+use super::Translations;
+
+/// 默认的最大修改比例（百分比）
+pub const DEFAULT_MAX_CHANGE_PERCENT: u32 = 30;
+
+/// 修改的键数低于此值时不检查比例
+pub const MIN_GUARDED_CHANGES: usize = 10;
+
+/// 一次同步将修改的本地键数量
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeMagnitude {
+    /// 将被覆盖为不同值（或删除）的本地键数
+    pub changed: usize,
+    /// 本地键总数
+    pub total: usize,
+    /// 每个语言被修改的键数（只包含有修改的语言）
+    pub per_language: BTreeMap<String, usize>,
+}
+
+impl ChangeMagnitude {
+    /// 统计同步计划会覆盖或删除多少个本地键
+    ///
+    /// 按最终计划计算，与 `--force`、`--force-keys`、`--prune` 等选项无关：
+    /// 只计算原值非空、将被改为不同值或删除的键；新增的键不算作修改。
+    ///
+    /// # 参数
+    ///
+    /// * `local` - 本地翻译（用于统计本地键总数）
+    /// * `plan` - 同步计划
+    pub fn from_plan(local: &Translations, plan: &SyncPlan) -> Self {
+        let mut magnitude = Self {
+            total: local.values().map(|keys| keys.len()).sum(),
+            ..Self::default()
+        };
+        for language in &plan.languages {
+            let changed = language
+                .changes
+                .iter()
+                .flat_map(|change| &change.keys)
+                .filter(|key| key.action != KeyAction::Add && key.old.as_deref().is_some_and(|old| !old.is_empty()))
+                .count();
+            if changed > 0 {
+                magnitude.changed += changed;
+                *magnitude.per_language.entry(language.lang.clone()).or_default() += changed;
+            }
+        }
+        magnitude
+    }
+
+    /// 修改比例（百分比，本地没有键时为 0）
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.changed as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    /// 修改比例是否超过上限（修改数少于 [`MIN_GUARDED_CHANGES`] 时不算）
+    pub fn exceeds(&self, max_percent: u32) -> bool {
+        self.changed >= MIN_GUARDED_CHANGES && self.percent() > f64::from(max_percent)
+    }
+
+    /// 描述修改规模，如 `1200 of 1500 local keys (80.0%)`
+    pub fn describe(&self) -> String {
+        format!(
+            "{} of {} local keys ({:.1}%)",
+            self.changed,
+            self.total,
+            self.percent()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::sync_plan::{FileChange, LanguagePlan};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn translations(entries: &[(&str, &[(&str, &str)])]) -> Translations {
+        entries
+            .iter()
+            .map(|(lang, keys)| {
+                let keys: HashMap<String, String> = keys
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect();
+                (lang.to_string(), keys)
+            })
+            .collect()
+    }
+
+    /// 由本地文件和计划写入后的内容生成同步计划（每个语言一个文件）
+    fn plan(local: &Translations, written: &Translations) -> SyncPlan {
+        let mut languages: Vec<LanguagePlan> = written
+            .iter()
+            .map(|(lang, new)| {
+                let old = local.get(lang).cloned().unwrap_or_default();
+                let path = PathBuf::from(format!("{}.json", lang));
+                LanguagePlan {
+                    lang: lang.clone(),
+                    changes: vec![FileChange::new(path, false, String::new(), String::new(), &old, new)],
+                }
+            })
+            .collect();
+        languages.sort_by(|a, b| a.lang.cmp(&b.lang));
+        SyncPlan { languages }
+    }
+
+    #[test]
+    fn test_from_plan_counts_only_overwritten_keys() {
+        let local = translations(&[
+            ("en", &[("a", "A"), ("b", "B"), ("c", "C"), ("d", "D"), ("e", "")]),
+            ("de", &[("a", "A-de")]),
+        ]);
+        // b 被清空、c 被修改、e 从空值填充、new 新增
+        let written = translations(&[
+            ("en", &[("a", "A"), ("b", ""), ("c", "changed"), ("d", "D"), ("e", "E"), ("new", "N")]),
+            ("fr", &[("a", "A-fr")]),
+        ]);
+
+        let magnitude = ChangeMagnitude::from_plan(&local, &plan(&local, &written));
+        assert_eq!(magnitude.changed, 2);
+        assert_eq!(magnitude.total, 6);
+        assert_eq!(magnitude.per_language, BTreeMap::from([("en".to_string(), 2)]));
+        assert_eq!(magnitude.describe(), "2 of 6 local keys (33.3%)");
+        // 修改数太少，不拦截
+        assert!(!magnitude.exceeds(30));
+    }
+
+    #[test]
+    fn test_exceeds_limit() {
+        let magnitude = ChangeMagnitude {
+            changed: 40,
+            total: 100,
+            ..Default::default()
+        };
+        assert!(magnitude.exceeds(30));
+        assert!(!magnitude.exceeds(40));
+        assert!(!magnitude.exceeds(100));
+    }
+
+    #[test]
+    fn test_from_plan_counts_removals() {
+        let local = translations(&[("en", &[("a", "A"), ("b", "B")]), ("de", &[("a", "A-de")])]);
+        let written = translations(&[("en", &[("a", "A2")]), ("de", &[])]);
+        let magnitude = ChangeMagnitude::from_plan(&local, &plan(&local, &written));
+        assert_eq!(magnitude.changed, 3);
+        assert_eq!(magnitude.describe(), "3 of 3 local keys (100.0%)");
+        assert_eq!(magnitude.per_language["en"], 2);
+        assert_eq!(magnitude.per_language["de"], 1);
+    }
+
+    #[test]
+    fn test_from_plan_empty_local() {
+        let written = translations(&[("en", &[("a", "A")])]);
+        let magnitude = ChangeMagnitude::from_plan(&Translations::new(), &plan(&Translations::new(), &written));
+        assert_eq!(magnitude.percent(), 0.0);
+        assert!(!magnitude.exceeds(0));
+    }
+}
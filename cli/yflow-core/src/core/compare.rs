@@ -0,0 +1,155 @@
+//! Value comparators
+//!
+//! Decides whether two values of the same key are "the same". `status`,
+//! `diff-local`, the import plan (unchanged keys are not pushed) and the
+//! merge of backend values into local files all compare through the
+//! project's `comparator`:
+//!
+//! - **exact** (default): byte-for-byte equality
+//! - **trimmed**: ignores leading and trailing whitespace
+//! - **normalized-html**: collapses whitespace runs, ignores case and
+//!   spacing inside tags (`<BR />` equals `<br>`) and decodes the common
+//!   entities (`&nbsp;`, `&amp;`, `&quot;`, `&#39;`, `&apos;`)
+//!
+//! Equivalent values never show up as drift and are never pushed or
+//! written; the side that already has a value keeps it as it is.
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// 值的比较方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Comparator {
+    /// 逐字节比较
+    #[default]
+    Exact,
+    /// 忽略首尾空白
+    Trimmed,
+    /// 规范化空白、标签和常见实体后比较
+    NormalizedHtml,
+}
+
+impl Comparator {
+    /// 所有比较方式的名称
+    pub const NAMES: [&'static str; 3] = ["exact", "trimmed", "normalized-html"];
+
+    /// 解析比较方式名称
+    ///
+    /// # Errors
+    ///
+    /// 名称未知时返回错误
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "exact" => Ok(Self::Exact),
+            "trimmed" => Ok(Self::Trimmed),
+            "normalized-html" => Ok(Self::NormalizedHtml),
+            other => Err(anyhow::anyhow!(
+                "Unknown comparator '{}' (expected one of: {})",
+                other,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    /// 是否为默认的逐字节比较
+    pub fn is_exact(&self) -> bool {
+        *self == Self::Exact
+    }
+
+    /// 两个值是否相同
+    pub fn equivalent(&self, a: &str, b: &str) -> bool {
+        a == b || (!self.is_exact() && self.normalize(a) == self.normalize(b))
+    }
+
+    /// 值的规范形式（相同的值规范形式相等）
+    pub fn normalize<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Exact => Cow::Borrowed(value),
+            Self::Trimmed => Cow::Borrowed(value.trim()),
+            Self::NormalizedHtml => Cow::Owned(normalize_html(value)),
+        }
+    }
+}
+
+/// 规范化 HTML 片段
+fn normalize_html(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    let mut rest = value;
+    while !rest.is_empty() {
+        // 标签
+        if rest.starts_with('<') {
+            if let Some(end) = rest.find('>') {
+                normalized.push_str(&normalize_tag(&rest[1..end]));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        // 文本直到下一个标签
+        let next = rest[1..].find('<').map_or(rest.len(), |i| i + 1);
+        normalized.push_str(&decode_entities(&rest[..next]));
+        rest = &rest[next..];
+    }
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 规范化标签内容：名称小写、空白折叠、去掉自闭合的 `/`
+fn normalize_tag(inner: &str) -> String {
+    let inner = inner.trim().trim_end_matches('/');
+    let mut parts = inner.split_whitespace();
+    let name = parts.next().unwrap_or_default().to_lowercase();
+    let attributes: Vec<&str> = parts.collect();
+    if attributes.is_empty() {
+        format!("<{}>", name)
+    } else {
+        format!("<{} {}>", name, attributes.join(" "))
+    }
+}
+
+/// 解码常见实体（`&nbsp;` 视为空格）
+fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') && !text.contains('\u{a0}') {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.replace("&nbsp;", " ")
+            .replace('\u{a0}', " ")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparators() {
+        assert!(Comparator::Exact.equivalent("Home", "Home"));
+        assert!(!Comparator::Exact.equivalent("Home ", "Home"));
+
+        assert!(Comparator::Trimmed.equivalent(" Home\n", "Home"));
+        assert!(!Comparator::Trimmed.equivalent("Home  page", "Home page"));
+
+        let html = Comparator::NormalizedHtml;
+        assert!(html.equivalent("Home  page", " Home page "));
+        assert!(html.equivalent("Line<BR />next", "Line<br>next"));
+        assert!(html.equivalent("<a  href=\"/x\" >Tom&nbsp;&amp;&nbsp;Jerry</A>", "<a href=\"/x\">Tom & Jerry</a>"));
+        assert!(!html.equivalent("<b>Bold</b>", "<i>Bold</i>"));
+        assert!(!html.equivalent("a < b", "a <b"));
+        assert_eq!(html.normalize("x <unclosed"), "x <unclosed");
+    }
+
+    #[test]
+    fn test_parse() {
+        for name in Comparator::NAMES {
+            let comparator = Comparator::parse(name).unwrap();
+            assert_eq!(serde_json::to_value(comparator).unwrap(), name);
+        }
+        assert!(Comparator::parse("fuzzy").unwrap_err().to_string().contains("normalized-html"));
+    }
+}
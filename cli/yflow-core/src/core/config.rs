@@ -0,0 +1,1167 @@
+//! Configuration management module
+//!
+//! Handles loading and validating the YFlow configuration file
+//! (`.i18nrc.json`, `.i18nrc.toml` or `.i18nrc.yaml`; the format is chosen
+//! by file extension and all formats use the same camelCase keys)
+//!
+//! A global user config (`config.json`, `.toml` or `.yaml` in the per-user
+//! config directory, see [`super::paths`]) provides defaults for every
+//! repository: its values are used for the fields the repo-local file does
+//! not set, which keeps e.g. `apiUrl` out of every checkout.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::attribution::Author;
+use super::credentials;
+use super::secrets::read_command_secret;
+use super::{flatten, paths, redact, scanner, I18nConfig};
+use crate::api::client::{http_client_builder, Timeouts};
+use crate::api::signing::AuthMode;
+use crate::api::tls;
+
+const CONFIG_FILENAME: &str = ".i18nrc.json";
+
+/// 自动发现的配置文件名（同一目录下按此顺序优先）
+const CONFIG_FILENAMES: [&str; 4] = [CONFIG_FILENAME, ".i18nrc.toml", ".i18nrc.yaml", ".i18nrc.yml"];
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// 根据扩展名判断格式；未知扩展名按 JSON 处理
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("toml") => ConfigFormat::Toml,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    /// 解析配置内容
+    fn parse<T: DeserializeOwned>(self, content: &str) -> Result<T> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Toml => toml::from_str(content)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+        })
+    }
+
+    /// 序列化配置内容（用于写回文件）
+    fn render(self, value: &serde_json::Value) -> Result<String> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value)? + "\n",
+            ConfigFormat::Toml => toml::to_string_pretty(value)?,
+            ConfigFormat::Yaml => serde_yaml::to_string(value)?,
+        })
+    }
+}
+
+/// 命令行对配置的覆盖（全局参数，优先于配置文件）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    /// 单个请求的超时（秒，`--timeout`）
+    pub timeout_secs: Option<u64>,
+    /// 建立连接的超时（秒，`--connect-timeout`）
+    pub connect_timeout_secs: Option<u64>,
+    /// 本次运行不检查证书指纹（`--ignore-pins`）
+    pub ignore_pins: bool,
+    /// 推送和提交时使用的作者（`--author`）
+    pub author: Option<Author>,
+}
+
+impl ConfigOverrides {
+    /// 将覆盖应用到配置
+    pub fn apply(&self, config: &mut I18nConfig) {
+        if let Some(timeout) = self.timeout_secs {
+            config.timeout_secs = Some(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout_secs {
+            config.connect_timeout_secs = Some(connect_timeout);
+        }
+        if self.ignore_pins {
+            config.pinned_sha256.clear();
+        }
+        if let Some(author) = &self.author {
+            config.author = Some(author.clone());
+        }
+    }
+}
+
+/// 加载配置文件
+///
+/// 搜索路径（按优先级）：
+/// 1. 命令行显式指定的路径
+/// 2. 当前目录的 .i18nrc.json / .i18nrc.toml / .i18nrc.yaml / .i18nrc.yml
+/// 3. 用户主目录的同名文件
+///
+/// 文件格式由扩展名决定（`.toml`、`.yaml`/`.yml`，其他按 JSON 解析）。
+///
+/// # Arguments
+///
+/// * `config_path` - 可选的配置文件路径
+///
+/// # Errors
+///
+/// 如果配置文件不存在、无法读取或格式错误，返回错误
+///
+/// # Example
+///
+/// ```ignore
+/// let config = load_config(None).await?;  // 使用默认路径
+/// let config = load_config(Some(PathBuf::from("/path/to/config"))).await?;
+/// ```
+pub async fn load_config(config_path: Option<PathBuf>) -> Result<I18nConfig> {
+    load_config_with(config_path, &ConfigOverrides::default()).await
+}
+
+/// 加载配置文件并应用命令行覆盖
+///
+/// 覆盖在解析密钥之前应用，因此 `--timeout` 等参数对密钥提供方的请求同样生效。
+/// 其余行为同 [`load_config`]。
+///
+/// # Errors
+///
+/// 如果配置文件不存在、无法读取或格式错误，返回错误
+pub async fn load_config_with(config_path: Option<PathBuf>, overrides: &ConfigOverrides) -> Result<I18nConfig> {
+    let (_, mut config) = read_config(config_path)?;
+    overrides.apply(&mut config);
+
+    // 从外部来源解析密钥
+    let config = resolve_secrets(config).await?;
+
+    // 验证必需字段
+    validate_config(&config)?;
+
+    // 应用环境变量覆盖
+    let config = apply_env_overrides(config)?;
+    if config.auth_mode == AuthMode::Hmac && config.api_secret.as_deref().is_none_or(str::is_empty) {
+        return Err(anyhow::anyhow!(
+            "Config validation failed:\n  - authMode 'hmac' requires apiSecret (or I18N_API_SECRET)"
+        ));
+    }
+
+    // 登记密钥，输出日志和错误时替换为 ***
+    redact::register(&config.api_key);
+    if let Some(secret) = &config.api_secret {
+        redact::register(secret);
+    }
+    // 校验自定义占位符语法（messages 目录的设置见 I18nConfig::messages）
+    placeholder_patterns(&config)?;
+
+    Ok(config)
+}
+
+/// 在配置文件中写入一条语言映射（本地代码 -> 后端代码）
+///
+/// 直接修改原始配置，其他字段（包括未解析的密钥配置）保持不变。
+/// TOML / YAML 文件会按原格式写回（注释不会保留）。
+///
+/// # Returns
+///
+/// 被修改的配置文件路径
+pub fn set_language_mapping(config_path: Option<PathBuf>, local: &str, backend: &str) -> Result<PathBuf> {
+    let (path, _) = edit_raw_config(config_path, |root| {
+        let mapping = root
+            .entry("languageMapping")
+            .or_insert_with(|| serde_json::json!({}));
+        if !mapping.is_object() {
+            *mapping = serde_json::json!({});
+        }
+        mapping[local] = serde_json::Value::String(backend.to_string());
+        true
+    })?;
+    Ok(path)
+}
+
+/// 从配置文件中删除本地语言代码的映射
+///
+/// # Returns
+///
+/// 存在映射并已删除时返回 `true`
+pub fn remove_language_mapping(config_path: Option<PathBuf>, local: &str) -> Result<bool> {
+    let (_, removed) = edit_raw_config(config_path, |root| {
+        root.get_mut("languageMapping")
+            .and_then(|mapping| mapping.as_object_mut())
+            .map(|mapping| mapping.remove(local).is_some())
+            .unwrap_or(false)
+    })?;
+    Ok(removed)
+}
+
+/// 修改原始配置（统一转换为 JSON 值）；`edit` 返回 `true` 时按原格式写回文件
+fn edit_raw_config<F>(config_path: Option<PathBuf>, edit: F) -> Result<(PathBuf, bool)>
+where
+    F: FnOnce(&mut serde_json::Map<String, serde_json::Value>) -> bool,
+{
+    let path = resolve_config_path(config_path)?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let format = ConfigFormat::from_path(&path);
+    let mut raw: serde_json::Value = format
+        .parse(&content)
+        .with_context(|| format!("Invalid config file format: {}", path.display()))?;
+
+    let root = raw
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Config file must contain an object at the top level: {}", path.display()))?;
+    let changed = edit(root);
+    if changed {
+        let rendered = format
+            .render(&raw)
+            .with_context(|| format!("Failed to serialize config file: {}", path.display()))?;
+        fs::write(&path, rendered)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+    }
+    Ok((path, changed))
+}
+
+/// 解析配置文件路径
+///
+/// 顺序：显式路径 > 当前目录 > 全局用户配置 > 用户主目录（旧位置）
+pub fn resolve_config_path(config_path: Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(path) = config_path {
+        return Ok(path);
+    }
+
+    // 检查当前目录
+    let current_dir = env::current_dir()?;
+    if let Some(path) = find_config_in(&current_dir) {
+        return Ok(path);
+    }
+
+    // 检查全局用户配置
+    if let Some(path) = paths::global_config_file() {
+        return Ok(path);
+    }
+
+    // 检查用户主目录（旧位置）
+    if let Some(path) = paths::home_dir().and_then(|home_dir| find_config_in(&home_dir)) {
+        return Ok(path);
+    }
+
+    let global = paths::config_dir()
+        .map(|dir| format!(", {}", dir.join(paths::GLOBAL_CONFIG_FILENAMES[0]).display()))
+        .unwrap_or_default();
+    Err(anyhow::anyhow!(
+        "Config file not found. Expected {} in {} (current dir){} or ~",
+        CONFIG_FILENAMES.join(", "),
+        current_dir.display(),
+        global
+    ))
+}
+
+/// 在目录中查找第一个存在的配置文件
+fn find_config_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILENAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// 读取配置文件，不解析密钥、不校验、不应用环境变量
+///
+/// 供 `auth login` 等需要在缺少 API 密钥时读取配置的命令使用。
+///
+/// # Returns
+///
+/// 配置文件路径和解析出的配置
+pub fn read_config(config_path: Option<PathBuf>) -> Result<(PathBuf, I18nConfig)> {
+    let path = resolve_config_path(config_path)?;
+    let config = read_layered(&path, paths::global_config_file().as_deref())?;
+    Ok((path, config))
+}
+
+/// 读取配置文件，并用全局用户配置补全未设置的字段
+///
+/// `global` 与 `path` 相同（只找到了全局配置）时只读取一次。
+fn read_layered(path: &Path, global: Option<&Path>) -> Result<I18nConfig> {
+    let Some(global) = global.filter(|global| *global != path) else {
+        return parse_file(path);
+    };
+
+    let mut raw: serde_json::Value = parse_file(path)?;
+    let defaults: serde_json::Value = parse_file(global)?;
+    merge_defaults(&mut raw, defaults);
+    serde_json::from_value(raw).with_context(|| {
+        format!(
+            "Invalid config file format: {} (with defaults from {})",
+            path.display(),
+            global.display()
+        )
+    })
+}
+
+/// 读取并解析配置文件（格式由扩展名决定）
+fn parse_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    ConfigFormat::from_path(path)
+        .parse(&content)
+        .with_context(|| format!("Invalid config file format: {}", path.display()))
+}
+
+/// 将 `defaults` 中未设置的字段补全到 `value`
+///
+/// 对象逐字段递归合并（例如 `languageMapping`），其他值（包括数组）以
+/// `value` 为准。
+fn merge_defaults(value: &mut serde_json::Value, defaults: serde_json::Value) {
+    let (serde_json::Value::Object(target), serde_json::Value::Object(defaults)) = (value, defaults) else {
+        return;
+    };
+    for (key, default) in defaults {
+        match target.get_mut(&key) {
+            Some(existing) => merge_defaults(existing, default),
+            None => {
+                target.insert(key, default);
+            }
+        }
+    }
+}
+
+/// 从外部来源解析 API 密钥
+///
+/// 优先级：`apiKeyCommand` > `secretProvider` > 配置文件中的 `apiKey` >
+/// 系统密钥环（`yflow auth login` 保存的密钥）。
+/// 如果已通过 `I18N_API_KEY` 环境变量提供密钥，则不会访问任何外部来源。
+async fn resolve_secrets(mut config: I18nConfig) -> Result<I18nConfig> {
+    // 额外信任的 CA 证书在加载时检查（密钥提供方的请求也使用）
+    let ca_certs = config.tls.ca_certs()?;
+    if env::var("I18N_API_KEY").is_ok() {
+        return Ok(config);
+    }
+
+    if let Some(command) = config.api_key_command.clone() {
+        // 外部命令可能运行较久，不占用异步运行时的工作线程
+        config.api_key = tokio::task::spawn_blocking(move || read_command_secret(&command))
+            .await
+            .context("apiKeyCommand task failed")??;
+    } else if let Some(provider) = &config.secret_provider {
+        // 与后端客户端使用相同的超时、CA 证书和代理
+        let http = http_client_builder(Timeouts::from_config(&config), &ca_certs)?
+            .build()
+            .context("Failed to build HTTP client")?;
+        config.api_key = provider
+            .resolve(&http)
+            .await
+            .context("Failed to resolve apiKey from secretProvider")?;
+    } else if config.api_key.is_empty() && !config.api_url.is_empty() {
+        // 密钥环不可用时不中断加载，缺少密钥由校验报告
+        match credentials::load_api_key(&config.api_url, config.project_id) {
+            Ok(Some(api_key)) => config.api_key = api_key,
+            Ok(None) => {}
+            Err(e) => tracing::debug!("Keyring lookup failed: {:#}", e),
+        }
+    }
+
+    Ok(config)
+}
+
+/// 验证配置文件必需字段
+fn validate_config(config: &I18nConfig) -> Result<()> {
+    let mut errors = Vec::new();
+
+    if config.messages_dir.as_os_str().is_empty() {
+        errors.push("messagesDir (messages directory path) is required");
+    }
+
+    if config.project_id == 0 {
+        errors.push("projectId must be a positive integer");
+    }
+
+    if config.api_url.is_empty() {
+        errors.push("apiUrl (API URL) is required");
+    }
+
+    if config.api_key.is_empty() {
+        errors.push("apiKey (API key), apiKeyCommand, secretProvider or `yflow auth login` is required");
+    }
+
+    if config.routes.iter().any(|route| route.project_id == 0) {
+        errors.push("routes[].projectId must be a positive integer");
+    }
+
+    if let Some(separator) = &config.key_separator {
+        if separator.is_empty() || separator.contains(flatten::ESCAPE) {
+            errors.push("keySeparator must be non-empty and must not contain a backslash");
+        }
+    }
+
+    if config.pinned_sha256.iter().any(|pin| tls::parse_fingerprint(pin).is_err()) {
+        errors.push("pinnedSha256 entries must be SHA-256 fingerprints (64 hex digits, colons allowed)");
+    }
+
+    if config.language_dirs.iter().any(|(lang, dir)| lang.is_empty() || dir.as_os_str().is_empty()) {
+        errors.push("languageDirs entries must map a language code to a directory");
+    }
+
+    if config.layout == scanner::Layout::FlatFiles && !config.language_dirs.is_empty() {
+        errors.push("languageDirs is only supported with the nested-dirs layout");
+    }
+
+    if config.layout == scanner::Layout::FlatFiles && config.format.is_some() {
+        errors.push("format is only supported with the nested-dirs layout");
+    }
+
+    if config.aliases.iter().any(|(alias, base)| alias.is_empty() || base.is_empty() || alias == base) {
+        errors.push("aliases entries must map a language code to a different base language");
+    }
+
+    if config.aliases.values().any(|base| config.aliases.contains_key(base)) {
+        errors.push("aliases must point to a base language, not to another alias");
+    }
+
+    if config
+        .overlays
+        .iter()
+        .any(|overlay| overlay.as_os_str().is_empty() || overlay.starts_with(&config.messages_dir))
+    {
+        errors.push("overlays must be directories outside messagesDir");
+    }
+
+    if config.source_language.as_deref().is_some_and(str::is_empty) {
+        errors.push("sourceLanguage must not be empty");
+    }
+
+    if config.placeholder_patterns.iter().any(|pattern| Regex::new(pattern).is_err()) {
+        errors.push("placeholderPatterns entries must be valid regular expressions");
+    }
+
+    if config.timeout_secs == Some(0) || config.connect_timeout_secs == Some(0) {
+        errors.push("timeoutSecs and connectTimeoutSecs must be positive");
+    }
+
+    if config.max_keys_per_file == Some(0) {
+        errors.push("maxKeysPerFile must be positive");
+    }
+
+    if config.max_change_percent.is_some_and(|percent| percent > 100) {
+        errors.push("maxChangePercent must be between 0 and 100");
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Config validation failed:\n{}",
+            errors.join("\n")
+        ))
+    }
+}
+
+/// 编译自定义占位符正则
+fn placeholder_patterns(config: &I18nConfig) -> Result<Vec<Regex>> {
+    config
+        .placeholder_patterns
+        .iter()
+        .map(|pattern| Regex::new(pattern).with_context(|| format!("Invalid placeholder pattern: {}", pattern)))
+        .collect()
+}
+
+/// 应用环境变量覆盖
+///
+/// 环境变量优先级高于配置文件：
+/// - I18N_MESSAGES_DIR
+/// - I18N_PROJECT_ID
+/// - I18N_API_URL
+/// - I18N_API_KEY
+fn apply_env_overrides(config: I18nConfig) -> Result<I18nConfig> {
+    Ok(I18nConfig {
+        messages_dir: env::var("I18N_MESSAGES_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| config.messages_dir.clone()),
+        project_id: env::var("I18N_PROJECT_ID")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(config.project_id),
+        api_url: env::var("I18N_API_URL")
+            .ok()
+            .unwrap_or_else(|| config.api_url.clone()),
+        api_key: env::var("I18N_API_KEY")
+            .ok()
+            .unwrap_or_else(|| config.api_key.clone()),
+        api_secret: env::var("I18N_API_SECRET").ok().or(config.api_secret.clone()),
+        ..config
+    })
+}
+
+/// 获取默认配置文件搜索路径
+pub fn get_default_config_path() -> PathBuf {
+    env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(CONFIG_FILENAME)
+}
+
+/// 检查配置文件是否存在
+pub fn config_exists(config_path: Option<PathBuf>) -> bool {
+    match resolve_config_path(config_path) {
+        Ok(path) => path.exists(),
+        Err(_) => false,
+    }
+}
+
+/// 创建示例配置文件内容
+///
+/// # Example
+///
+/// ```ignore
+/// let sample = create_sample_config();
+/// std::fs::write(".i18nrc.json", sample).unwrap();
+/// ```
+pub fn create_sample_config() -> String {
+    let sample = SampleConfig {
+        messages_dir: "./src/locales".to_string(),
+        project_id: 1,
+        api_url: "http://localhost:8080/api".to_string(),
+        api_key: "your-api-key-here".to_string(),
+    };
+    serde_json::to_string_pretty(&sample).unwrap()
+}
+
+/// 生成配置文件的 JSON Schema
+///
+/// 由配置类型（[`I18nConfig`] 及其字段类型）的 serde 定义生成，字段名、
+/// 默认值和枚举取值与解析配置时完全一致，新增配置项会自动出现在 schema 中。
+/// 编辑器可通过 `"$schema"` 或工作区设置引用生成的文件，提供补全和校验。
+pub fn config_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(I18nConfig);
+    schema.insert("title".to_string(), "YFlow CLI configuration (.i18nrc.json)".into());
+    // 允许配置文件通过 "$schema" 引用 schema 本身
+    if let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) {
+        properties.insert("$schema".to_string(), serde_json::json!({"type": "string"}));
+    }
+    schema.to_value()
+}
+
+/// 示例配置结构（用于生成 JSON）
+#[derive(Serialize)]
+struct SampleConfig {
+    pub messages_dir: String,
+    pub project_id: u64,
+    pub api_url: String,
+    pub api_key: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::formats::MobileFormat;
+    use crate::core::{compare, i18next};
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_schema_covers_every_field() {
+        let schema = config_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        for field in ["messagesDir", "projectId", "apiUrl"] {
+            assert!(required.contains(&field), "{} should be required", field);
+        }
+        assert!(!required.contains(&"apiKey"));
+
+        // 所有可选字段都填写后，序列化出的每个键都应出现在 schema 中
+        let config = I18nConfig {
+            api_secret: Some("s".to_string()),
+            api_key_command: Some("cmd".to_string()),
+            secret_provider: Some(super::super::SecretProvider::Vault {
+                path: "p".to_string(),
+                field: None,
+                address: None,
+            }),
+            language_dirs: [("en".to_string(), PathBuf::from("en"))].into(),
+            aliases: [("en_GB".to_string(), "en".to_string())].into(),
+            overlays: vec![PathBuf::from("brand")],
+            layout: scanner::Layout::FlatFiles,
+            format: Some(super::super::formats::MobileFormat::Android),
+            comparator: super::super::compare::Comparator::NormalizedHtml,
+            dialect: super::super::i18next::Dialect::I18next,
+            routes: vec![super::super::Route {
+                key_prefix: None,
+                language: None,
+                project_id: 2,
+                api_url: None,
+                api_key: None,
+            }],
+            owners: [("a.".to_string(), "team".to_string())].into(),
+            lint_rules: vec![Default::default()],
+            max_keys_per_file: Some(500),
+            key_separator: Some(".".to_string()),
+            pinned_sha256: vec!["00".to_string()],
+            tls: crate::api::tls::TlsSettings {
+                ca_cert_path: Some(PathBuf::from("ca.pem")),
+            },
+            timeout_secs: Some(300),
+            connect_timeout_secs: Some(20),
+            max_change_percent: Some(10),
+            pr_command: Some("gh".to_string()),
+            ..Default::default()
+        };
+        let serialized = serde_json::to_value(&config).unwrap();
+        for key in serialized.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "{} missing from the schema", key);
+        }
+        assert_eq!(properties["layout"]["$ref"], "#/$defs/Layout");
+    }
+
+    #[tokio::test]
+    async fn test_load_config_missing_file() {
+        let result = load_config(Some(PathBuf::from("/nonexistent/path"))).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_config_invalid_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let mut file = File::create(&config_path).unwrap();
+        writeln!(file, "invalid json {{").unwrap();
+
+        let result = load_config(Some(config_path)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_config_valid() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKey": "test-key"
+        }"#;
+        let mut file = File::create(&config_path).unwrap();
+        writeln!(file, "{}", config_content).unwrap();
+
+        let result = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(result.project_id, 1);
+        assert_eq!(result.api_key, "test-key");
+    }
+
+    #[tokio::test]
+    async fn test_load_config_invalid_pinned_sha256() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./messages",
+            "projectId": 1,
+            "apiUrl": "https://example.com/api",
+            "apiKey": "test-key",
+            "pinnedSha256": ["not-a-fingerprint"]
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let error = load_config(Some(config_path)).await.unwrap_err();
+        assert!(error.to_string().contains("pinnedSha256"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_hmac_requires_secret() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./messages",
+            "projectId": 1,
+            "apiUrl": "https://example.com/api",
+            "apiKey": "key-id",
+            "authMode": "hmac"
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let error = load_config(Some(config_path)).await.unwrap_err();
+        assert!(error.to_string().contains("apiSecret"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_invalid_key_separator() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKey": "test-key",
+            "keySeparator": ""
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let error = load_config(Some(config_path)).await.unwrap_err();
+        assert!(error.to_string().contains("keySeparator"));
+    }
+
+    #[tokio::test]
+    async fn test_env_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKey": "original-key"
+        }"#;
+        let mut file = File::create(&config_path).unwrap();
+        writeln!(file, "{}", config_content).unwrap();
+
+        // 设置环境变量
+        std::env::set_var("I18N_API_KEY", "env-override-key");
+
+        let result = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(result.api_key, "env-override-key");
+
+        // 清理环境变量
+        std::env::remove_var("I18N_API_KEY");
+    }
+
+    #[tokio::test]
+    async fn test_load_config_with_routes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKey": "test-key",
+            "routes": [
+                {"keyPrefix": "emails.", "projectId": 7},
+                {"language": "ja_JP", "projectId": 9, "apiUrl": "http://jp.example.com/api"}
+            ]
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let result = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(result.routes.len(), 2);
+        assert_eq!(result.routes[0].key_prefix.as_deref(), Some("emails."));
+        assert_eq!(result.routes[0].project_id, 7);
+        assert_eq!(result.routes[1].language.as_deref(), Some("ja_JP"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_route_invalid_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKey": "test-key",
+            "routes": [{"keyPrefix": "emails.", "projectId": 0}]
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let result = load_config(Some(config_path)).await;
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_load_config_api_key_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKeyCommand": "echo command-key"
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let result = load_config(Some(config_path)).await.unwrap();
+        if std::env::var("I18N_API_KEY").is_err() {
+            assert_eq!(result.api_key, "command-key");
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_load_config_api_key_command_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config_content = r#"{
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "http://localhost:8080/api",
+            "apiKeyCommand": "exit 1"
+        }"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        if std::env::var("I18N_API_KEY").is_err() {
+            assert!(load_config(Some(config_path)).await.is_err());
+        }
+    }
+
+    #[test]
+    fn test_set_language_mapping_preserves_other_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &config_path,
+            r#"{"messagesDir": "./messages", "projectId": 1, "apiUrl": "http://localhost", "apiKeyCommand": "echo k", "languageMapping": {"zh_CN": "zh-CN"}}"#,
+        )
+        .unwrap();
+
+        set_language_mapping(Some(config_path.clone()), "fr_FR", "fr").unwrap();
+
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(raw["languageMapping"]["fr_FR"], "fr");
+        assert_eq!(raw["languageMapping"]["zh_CN"], "zh-CN");
+        assert_eq!(raw["apiKeyCommand"], "echo k");
+        assert!(raw.get("apiKey").is_none());
+
+        assert!(remove_language_mapping(Some(config_path.clone()), "zh_CN").unwrap());
+        assert!(!remove_language_mapping(Some(config_path.clone()), "zh_CN").unwrap());
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(raw["languageMapping"], serde_json::json!({"fr_FR": "fr"}));
+    }
+
+    #[test]
+    fn test_config_format_from_path() {
+        assert_eq!(ConfigFormat::from_path(Path::new(".i18nrc.json")), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path(Path::new("/etc/.i18nrc.TOML")), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path(Path::new(".i18nrc.yaml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new(".i18nrc.yml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(Path::new("custom-config")), ConfigFormat::Json);
+    }
+
+    #[tokio::test]
+    async fn test_load_config_toml_and_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let toml_path = temp_dir.path().join(".i18nrc.toml");
+        std::fs::write(
+            &toml_path,
+            r#"messagesDir = "./locales"
+projectId = 2
+apiUrl = "http://localhost:8080/api"
+apiKey = "toml-key"
+
+[languageMapping]
+zh_CN = "zh-CN"
+"#,
+        )
+        .unwrap();
+        let config = load_config(Some(toml_path)).await.unwrap();
+        assert_eq!(config.project_id, 2);
+        assert_eq!(config.api_key, "toml-key");
+        assert_eq!(config.language_mapping.get("zh_CN").map(String::as_str), Some("zh-CN"));
+
+        let yaml_path = temp_dir.path().join(".i18nrc.yaml");
+        std::fs::write(
+            &yaml_path,
+            "messagesDir: ./locales\nprojectId: 3\napiUrl: http://localhost:8080/api\napiKey: yaml-key\n",
+        )
+        .unwrap();
+        let config = load_config(Some(yaml_path)).await.unwrap();
+        assert_eq!(config.project_id, 3);
+        assert_eq!(config.api_key, "yaml-key");
+
+        let invalid = temp_dir.path().join("broken.toml");
+        std::fs::write(&invalid, "messagesDir = [").unwrap();
+        assert!(load_config(Some(invalid)).await.is_err());
+    }
+
+    #[test]
+    fn test_read_layered_uses_global_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let global = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &global,
+            "messagesDir = \"./messages\"\napiUrl = \"https://i18n.example.com/api\"\nprojectId = 9\n[languageMapping]\nzh_CN = \"zh-Hans\"\nde = \"de-DE\"\n",
+        )
+        .unwrap();
+        let local = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &local,
+            r#"{"messagesDir": "./locales", "projectId": 2, "apiKey": "k", "languageMapping": {"de": "de"}}"#,
+        )
+        .unwrap();
+
+        let config = read_layered(&local, Some(&global)).unwrap();
+        assert_eq!(config.api_url, "https://i18n.example.com/api");
+        assert_eq!(config.messages_dir, PathBuf::from("./locales"));
+        assert_eq!(config.project_id, 2);
+        assert_eq!(config.language_mapping["de"], "de");
+        assert_eq!(config.language_mapping["zh_CN"], "zh-Hans");
+
+        // 只有全局配置时直接使用
+        let config = read_layered(&global, Some(&global)).unwrap();
+        assert_eq!(config.project_id, 9);
+    }
+
+    #[test]
+    fn test_find_config_in_prefers_json() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(find_config_in(temp_dir.path()).is_none());
+
+        std::fs::write(temp_dir.path().join(".i18nrc.yml"), "projectId: 1\n").unwrap();
+        assert_eq!(find_config_in(temp_dir.path()), Some(temp_dir.path().join(".i18nrc.yml")));
+
+        std::fs::write(temp_dir.path().join(".i18nrc.toml"), "projectId = 1\n").unwrap();
+        assert_eq!(find_config_in(temp_dir.path()), Some(temp_dir.path().join(".i18nrc.toml")));
+
+        std::fs::write(temp_dir.path().join(CONFIG_FILENAME), "{}").unwrap();
+        assert_eq!(find_config_in(temp_dir.path()), Some(temp_dir.path().join(CONFIG_FILENAME)));
+    }
+
+    #[tokio::test]
+    async fn test_set_language_mapping_keeps_toml_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".i18nrc.toml");
+        std::fs::write(
+            &config_path,
+            "messagesDir = \"./messages\"\nprojectId = 1\napiUrl = \"http://localhost\"\napiKey = \"k\"\n",
+        )
+        .unwrap();
+
+        set_language_mapping(Some(config_path.clone()), "fr_FR", "fr").unwrap();
+
+        let raw: toml::Value = toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(raw["languageMapping"]["fr_FR"].as_str(), Some("fr"));
+        assert_eq!(raw["projectId"].as_integer(), Some(1));
+        let config = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(config.language_mapping.get("fr_FR").map(String::as_str), Some("fr"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_registers_secrets() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &config_path,
+            r#"{"messagesDir": "./locales", "projectId": 1, "apiUrl": "http://localhost", "apiKey": "registered-key-0042"}"#,
+        )
+        .unwrap();
+
+        let config = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(redact::redact(&format!("key={}", config.api_key)), "key=***");
+    }
+
+    #[tokio::test]
+    async fn test_load_config_language_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("translations");
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &config_path,
+            serde_json::json!({
+                "messagesDir": messages_dir,
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k",
+                "languageDirs": {"en": "src/locales/en"}
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let config = load_config(Some(config_path)).await.unwrap();
+        assert_eq!(config.language_dirs["en"], PathBuf::from("src/locales/en"));
+        let messages = config.messages();
+        assert_eq!(scanner::language_dir(&messages, "en"), PathBuf::from("src/locales/en"));
+        assert_eq!(scanner::language_dir(&messages, "de"), messages_dir.join("de"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let write_config = |extra: serde_json::Value| {
+            let mut config = serde_json::json!({
+                "messagesDir": messages_dir,
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k"
+            });
+            config.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            std::fs::write(&config_path, config.to_string()).unwrap();
+        };
+
+        write_config(serde_json::json!({"layout": "flat-files"}));
+        let config = load_config(Some(config_path.clone())).await.unwrap();
+        assert_eq!(config.layout, scanner::Layout::FlatFiles);
+        assert_eq!(config.messages().layout, scanner::Layout::FlatFiles);
+
+        write_config(serde_json::json!({"layout": "flat-files", "languageDirs": {"en": "src/en"}}));
+        let error = load_config(Some(config_path.clone())).await.unwrap_err();
+        assert!(error.to_string().contains("nested-dirs layout"));
+
+        write_config(serde_json::json!({"layout": "per-language"}));
+        assert!(load_config(Some(config_path.clone())).await.is_err());
+
+        write_config(serde_json::json!({"format": "ios"}));
+        let config = load_config(Some(config_path.clone())).await.unwrap();
+        assert_eq!(config.format, Some(MobileFormat::Ios));
+        let messages = config.messages();
+        assert_eq!(messages.detect(Path::new("de.lproj/Localizable.strings")).unwrap().name(), "iOS strings");
+
+        write_config(serde_json::json!({"layout": "flat-files", "format": "android"}));
+        let error = load_config(Some(config_path.clone())).await.unwrap_err();
+        assert!(error.to_string().contains("format is only supported"));
+
+        write_config(serde_json::json!({"comparator": "trimmed", "dialect": "i18next"}));
+        let messages = load_config(Some(config_path.clone())).await.unwrap().messages();
+        assert_eq!(messages.comparator, compare::Comparator::Trimmed);
+        assert_eq!(messages.dialect, i18next::Dialect::I18next);
+
+        write_config(serde_json::json!({}));
+        let messages = load_config(Some(config_path)).await.unwrap().messages();
+        assert_eq!(messages.layout, scanner::Layout::NestedDirs);
+        assert_eq!(messages.format, None);
+        assert!(messages.comparator.is_exact());
+        assert!(messages.dialect.is_plain());
+    }
+
+    #[tokio::test]
+    async fn test_load_config_aliases() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let write_config = |aliases: serde_json::Value| {
+            let config = serde_json::json!({
+                "messagesDir": messages_dir,
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k",
+                "aliases": aliases
+            });
+            std::fs::write(&config_path, config.to_string()).unwrap();
+        };
+
+        write_config(serde_json::json!({"en_GB": "en"}));
+        let messages = load_config(Some(config_path.clone())).await.unwrap().messages();
+        assert_eq!(messages.aliases["en_GB"], "en");
+
+        write_config(serde_json::json!({"en": "en"}));
+        assert!(load_config(Some(config_path.clone())).await.unwrap_err().to_string().contains("different base"));
+
+        write_config(serde_json::json!({"en_AU": "en_GB", "en_GB": "en"}));
+        assert!(load_config(Some(config_path.clone())).await.unwrap_err().to_string().contains("another alias"));
+
+        write_config(serde_json::json!({}));
+        assert!(load_config(Some(config_path)).await.unwrap().messages().aliases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_config_timeouts() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let write_config = |timeout: u64| {
+            let config = serde_json::json!({
+                "messagesDir": "./locales",
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k",
+                "timeoutSecs": timeout,
+                "connectTimeoutSecs": 15
+            });
+            std::fs::write(&config_path, config.to_string()).unwrap();
+        };
+
+        write_config(300);
+        let config = load_config(Some(config_path.clone())).await.unwrap();
+        assert_eq!((config.timeout_secs, config.connect_timeout_secs), (Some(300), Some(15)));
+
+        write_config(0);
+        assert!(load_config(Some(config_path)).await.unwrap_err().to_string().contains("must be positive"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_with_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let config = serde_json::json!({
+            "messagesDir": "./locales",
+            "projectId": 1,
+            "apiUrl": "https://example.com/api",
+            "apiKey": "k",
+            "timeoutSecs": 300,
+            "connectTimeoutSecs": 15,
+            "pinnedSha256": ["ab".repeat(32)]
+        });
+        std::fs::write(&config_path, config.to_string()).unwrap();
+
+        let overrides = ConfigOverrides {
+            timeout_secs: Some(5),
+            connect_timeout_secs: None,
+            ignore_pins: true,
+            author: Some(Author::parse("Jane <jane@example.com>").unwrap()),
+        };
+        let config = load_config_with(Some(config_path), &overrides).await.unwrap();
+        assert_eq!((config.timeout_secs, config.connect_timeout_secs), (Some(5), Some(15)));
+        assert!(config.pinned_sha256.is_empty());
+        assert_eq!(config.author.unwrap().email.as_deref(), Some("jane@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_invalid_ca_cert_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &config_path,
+            serde_json::json!({
+                "messagesDir": "./locales",
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k",
+                "tls": {"caCertPath": temp_dir.path().join("missing.pem")}
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let error = load_config(Some(config_path)).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("tls.caCertPath"));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_overlays() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path().join("messages");
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let write_config = |overlays: serde_json::Value| {
+            let config = serde_json::json!({
+                "messagesDir": messages_dir,
+                "projectId": 1,
+                "apiUrl": "http://localhost",
+                "apiKey": "k",
+                "layout": "flat-files",
+                "overlays": overlays
+            });
+            std::fs::write(&config_path, config.to_string()).unwrap();
+        };
+
+        let overlay = temp_dir.path().join("brand/acme");
+        write_config(serde_json::json!([overlay]));
+        let config = load_config(Some(config_path.clone())).await.unwrap();
+        assert_eq!(config.overlays, vec![overlay.clone()]);
+        let overlay_dirs = config.overlay_dirs();
+        assert_eq!(overlay_dirs[0].path, overlay);
+        assert_eq!(overlay_dirs[0].layout, scanner::Layout::FlatFiles);
+
+        write_config(serde_json::json!([messages_dir.join("acme")]));
+        assert!(load_config(Some(config_path)).await.unwrap_err().to_string().contains("outside messagesDir"));
+    }
+}
@@ -0,0 +1,542 @@
+//! Sync conflict detection module
+//!
+//! Performs a three-way comparison between the last synced state (base),
+//! the local files and the backend (remote). A key is in conflict when both
+//! sides changed it since the last sync and ended up with different values.
+//!
+//! [`three_way_merge`] turns the comparison into what a sync writes: keys
+//! only the backend changed take the backend value, keys only edited locally
+//! keep the local value, and conflicts follow their [`Resolution`].
+//! [`removed_on_backend`] finds the local keys `sync --prune` deletes.
+//!
+//! The base state is stored as a packed snapshot file (see [`super::packed`])
+//! inside the messages directory after every successful sync; conflicts are
+//! written to a JSON report (`.yflow-conflicts.json`) so teams can review
+//! what a sync decided.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::{packed, Translations};
+
+/// 同步状态快照文件名（位于 messages 目录下）
+pub const STATE_FILENAME: &str = ".yflow-sync-state.bin";
+
+/// 旧版 JSON 格式的状态快照文件名（加载时迁移）
+const LEGACY_STATE_FILENAME: &str = ".yflow-sync-state.json";
+
+/// 默认冲突报告文件名（位于 messages 目录下）
+pub const REPORT_FILENAME: &str = ".yflow-conflicts.json";
+
+/// 冲突的解决方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// 采用后端的值
+    Remote,
+    /// 保留本地的值
+    Local,
+    /// 尚未决定（模拟运行或非终端运行时的交互解决）
+    Unresolved,
+}
+
+/// 单个冲突
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// 语言代码（本地）
+    pub lang: String,
+    /// 翻译键
+    pub key: String,
+    /// 上次同步时的值（首次同步时为空）
+    pub base: Option<String>,
+    /// 本地值
+    pub local: String,
+    /// 后端值
+    pub remote: String,
+    /// 采用的解决方式
+    pub resolution: Resolution,
+}
+
+/// 同步状态快照
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    /// 上次同步完成时的翻译（本地语言代码）
+    pub translations: Translations,
+}
+
+impl SyncState {
+    /// 获取状态快照文件路径
+    pub fn path(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(STATE_FILENAME)
+    }
+
+    /// 加载状态快照
+    ///
+    /// 文件不存在或格式版本已失效时返回空状态（视为首次同步）。
+    /// 只有旧版 JSON 快照时读取它，下次保存时转换为打包格式。
+    pub fn load(messages_dir: &Path) -> Result<Self> {
+        let path = Self::path(messages_dir);
+        if let Some(state) = packed::read(&path)
+            .with_context(|| format!("Invalid sync state file: {}", path.display()))?
+        {
+            return Ok(state);
+        }
+
+        let legacy = messages_dir.join(LEGACY_STATE_FILENAME);
+        if !legacy.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&legacy)
+            .with_context(|| format!("Failed to read sync state: {}", legacy.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid sync state file: {}", legacy.display()))
+    }
+
+    /// 保存状态快照（同时删除旧版 JSON 快照）
+    pub fn save(&self, messages_dir: &Path) -> Result<()> {
+        let path = Self::path(messages_dir);
+        packed::write(&path, self)
+            .with_context(|| format!("Failed to write sync state: {}", path.display()))?;
+
+        let legacy = messages_dir.join(LEGACY_STATE_FILENAME);
+        if legacy.exists() {
+            std::fs::remove_file(&legacy)
+                .with_context(|| format!("Failed to remove {}", legacy.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// 三方比较检测冲突
+///
+/// 当本地值与后端值不同，且两者都相对于基线发生了变化时视为冲突。
+/// 没有基线时（首次同步），两侧值不同即视为冲突。
+///
+/// # Arguments
+///
+/// * `base` - 上次同步的翻译
+/// * `local` - 本地翻译
+/// * `remote` - 后端翻译（已映射为本地语言代码）
+/// * `resolution` - 本次同步对冲突采用的解决方式
+///
+/// # Returns
+///
+/// 按语言和键排序的冲突列表
+pub fn detect_conflicts(
+    base: &Translations,
+    local: &Translations,
+    remote: &Translations,
+    resolution: Resolution,
+) -> Vec<Conflict> {
+    let empty = HashMap::new();
+    let mut conflicts = Vec::new();
+
+    for (lang, remote_lang) in remote {
+        let local_lang = match local.get(lang) {
+            Some(l) => l,
+            None => continue,
+        };
+        let base_lang = base.get(lang).unwrap_or(&empty);
+
+        for (key, remote_value) in remote_lang {
+            let local_value = match local_lang.get(key) {
+                Some(v) => v,
+                None => continue,
+            };
+            if local_value == remote_value {
+                continue;
+            }
+
+            let base_value = base_lang.get(key);
+            let local_changed = base_value != Some(local_value);
+            let remote_changed = base_value != Some(remote_value);
+
+            if local_changed && remote_changed {
+                conflicts.push(Conflict {
+                    lang: lang.clone(),
+                    key: key.clone(),
+                    base: base_value.cloned(),
+                    local: local_value.clone(),
+                    remote: remote_value.clone(),
+                    resolution,
+                });
+            }
+        }
+    }
+
+    conflicts.sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+    conflicts
+}
+
+/// 三方合并结果
+#[derive(Debug, Clone, Default)]
+pub struct MergeOutcome {
+    /// 需要写入本地的后端翻译
+    pub incoming: Translations,
+    /// 只在本地修改过、因此保留本地值的键（语言, 键）
+    pub kept_local: Vec<(String, String)>,
+    /// 双方都修改过的键
+    pub conflicts: Vec<Conflict>,
+}
+
+impl MergeOutcome {
+    /// 按冲突的解决方式调整要写入的翻译
+    ///
+    /// 解决为 `local`（或尚未解决）的冲突键不会写入。
+    pub fn apply_resolutions(&mut self) {
+        for conflict in &self.conflicts {
+            if conflict.resolution != Resolution::Remote {
+                if let Some(keys) = self.incoming.get_mut(&conflict.lang) {
+                    keys.remove(&conflict.key);
+                }
+            }
+        }
+    }
+}
+
+/// 三方合并
+///
+/// 基于上次同步的基线决定每个后端键是否写入本地：
+///
+/// - 本地没有此键、与后端相同或只有后端修改过：写入后端值
+/// - 只有本地修改过：保留本地值（`keep_local_edits` 为 `false` 时仍写入后端值）
+/// - 双方都修改过：记为冲突，解决方式为 `resolution`
+///
+/// 返回的 `incoming` 已按 `resolution` 处理冲突。
+pub fn three_way_merge(
+    base: &Translations,
+    local: &Translations,
+    remote: &Translations,
+    resolution: Resolution,
+    keep_local_edits: bool,
+) -> MergeOutcome {
+    let empty = HashMap::new();
+    let mut outcome = MergeOutcome {
+        incoming: remote.clone(),
+        kept_local: Vec::new(),
+        conflicts: detect_conflicts(base, local, remote, resolution),
+    };
+
+    if keep_local_edits {
+        for (lang, keys) in outcome.incoming.iter_mut() {
+            let local_lang = local.get(lang).unwrap_or(&empty);
+            let Some(base_lang) = base.get(lang) else {
+                continue;
+            };
+            keys.retain(|key, remote_value| {
+                let (Some(local_value), Some(base_value)) = (local_lang.get(key), base_lang.get(key)) else {
+                    return true;
+                };
+                let local_only = local_value != base_value && remote_value == base_value;
+                if local_only {
+                    outcome.kept_local.push((lang.clone(), key.clone()));
+                }
+                !local_only
+            });
+        }
+        outcome.kept_local.sort();
+    }
+
+    outcome.apply_resolutions();
+    outcome
+}
+
+/// 后端已不存在的本地键
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneOutcome {
+    /// 要删除的键（按语言分组、排序）
+    pub removals: BTreeMap<String, Vec<String>>,
+    /// 保留的本地新增键（语言, 键），排序
+    pub kept: Vec<(String, String)>,
+}
+
+/// 找出后端已不存在的本地键（`sync --prune` 删除的键）
+///
+/// 只考虑后端存在的语言。本地有、后端没有的键中，该语言上次同步的快照里
+/// 也没有的视为本地新增、尚未导入的键：`keep_local_additions` 为 `true` 时
+/// 保留。该语言没有快照时无法区分后端删除的键和本地新增的键，同样全部保留，
+/// 只有 `keep_local_additions` 为 `false`（`--force`）时删除。
+pub fn removed_on_backend(
+    base: &Translations,
+    local: &Translations,
+    remote: &Translations,
+    keep_local_additions: bool,
+) -> PruneOutcome {
+    let mut outcome = PruneOutcome::default();
+    for (lang, local_keys) in local {
+        let Some(remote_keys) = remote.get(lang) else {
+            continue;
+        };
+        let base_keys = base.get(lang);
+        let mut removed = Vec::new();
+        for key in local_keys.keys().filter(|key| !remote_keys.contains_key(*key)) {
+            let added_locally = base_keys.is_none_or(|base_keys| !base_keys.contains_key(key));
+            if added_locally && keep_local_additions {
+                outcome.kept.push((lang.clone(), key.clone()));
+            } else {
+                removed.push(key.clone());
+            }
+        }
+        if !removed.is_empty() {
+            removed.sort();
+            outcome.removals.insert(lang.clone(), removed);
+        }
+    }
+    outcome.kept.sort();
+    outcome
+}
+
+/// 写入冲突报告
+///
+/// # Arguments
+///
+/// * `path` - 报告文件路径
+/// * `conflicts` - 冲突列表
+pub fn write_conflict_report(path: &Path, conflicts: &[Conflict]) -> Result<()> {
+    let report = serde_json::json!({
+        "count": conflicts.len(),
+        "conflicts": conflicts,
+    });
+    let content = serde_json::to_string_pretty(&report)?;
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write conflict report: {}", path.display()))
+}
+
+/// 读取冲突报告
+///
+/// 报告不存在时返回空列表。
+pub fn read_conflict_report(path: &Path) -> Result<Vec<Conflict>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    #[derive(Deserialize)]
+    struct Report {
+        conflicts: Vec<Conflict>,
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read conflict report: {}", path.display()))?;
+    let report: Report = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid conflict report: {}", path.display()))?;
+    Ok(report.conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn single(lang: &str, key: &str, value: &str) -> Translations {
+        HashMap::from([(
+            lang.to_string(),
+            HashMap::from([(key.to_string(), value.to_string())]),
+        )])
+    }
+
+    #[test]
+    fn test_detect_conflicts_both_changed() {
+        let base = single("en", "title", "Base");
+        let local = single("en", "title", "Local");
+        let remote = single("en", "title", "Remote");
+
+        let conflicts = detect_conflicts(&base, &local, &remote, Resolution::Remote);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].base.as_deref(), Some("Base"));
+        assert_eq!(conflicts[0].local, "Local");
+        assert_eq!(conflicts[0].remote, "Remote");
+        assert_eq!(conflicts[0].resolution, Resolution::Remote);
+    }
+
+    #[test]
+    fn test_detect_conflicts_only_one_side_changed() {
+        let base = single("en", "title", "Base");
+
+        // 只有后端修改
+        let conflicts = detect_conflicts(
+            &base,
+            &single("en", "title", "Base"),
+            &single("en", "title", "Remote"),
+            Resolution::Remote,
+        );
+        assert!(conflicts.is_empty());
+
+        // 只有本地修改
+        let conflicts = detect_conflicts(
+            &base,
+            &single("en", "title", "Local"),
+            &single("en", "title", "Base"),
+            Resolution::Remote,
+        );
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_detect_conflicts_without_base() {
+        let conflicts = detect_conflicts(
+            &Translations::new(),
+            &single("en", "title", "Local"),
+            &single("en", "title", "Remote"),
+            Resolution::Local,
+        );
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].base.is_none());
+        assert_eq!(conflicts[0].resolution, Resolution::Local);
+    }
+
+    #[test]
+    fn test_detect_conflicts_same_value() {
+        let conflicts = detect_conflicts(
+            &single("en", "title", "Base"),
+            &single("en", "title", "Same"),
+            &single("en", "title", "Same"),
+            Resolution::Remote,
+        );
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_three_way_merge() {
+        let translations = |pairs: &[(&str, &str)]| -> Translations {
+            HashMap::from([(
+                "en".to_string(),
+                pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            )])
+        };
+        let base = translations(&[("remote_only", "A"), ("local_only", "B"), ("both", "C"), ("same", "D")]);
+        let local = translations(&[("remote_only", "A"), ("local_only", "B2"), ("both", "C-local"), ("same", "D")]);
+        let remote = translations(&[
+            ("remote_only", "A2"),
+            ("local_only", "B"),
+            ("both", "C-remote"),
+            ("same", "D"),
+            ("new", "E"),
+        ]);
+
+        let outcome = three_way_merge(&base, &local, &remote, Resolution::Remote, true);
+        assert_eq!(outcome.kept_local, vec![("en".to_string(), "local_only".to_string())]);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].key, "both");
+        let incoming = &outcome.incoming["en"];
+        assert_eq!(incoming.get("remote_only").map(String::as_str), Some("A2"));
+        assert_eq!(incoming.get("both").map(String::as_str), Some("C-remote"));
+        assert_eq!(incoming.get("new").map(String::as_str), Some("E"));
+        assert!(!incoming.contains_key("local_only"));
+
+        // 冲突保留本地值
+        let outcome = three_way_merge(&base, &local, &remote, Resolution::Local, true);
+        assert!(!outcome.incoming["en"].contains_key("both"));
+
+        // 不保留本地修改（--force）
+        let outcome = three_way_merge(&base, &local, &remote, Resolution::Remote, false);
+        assert!(outcome.kept_local.is_empty());
+        assert_eq!(outcome.incoming["en"]["local_only"], "B");
+    }
+
+    #[test]
+    fn test_removed_on_backend() {
+        let translations = |entries: &[(&str, &[&str])]| -> Translations {
+            entries
+                .iter()
+                .map(|(lang, keys)| (lang.to_string(), keys.iter().map(|k| (k.to_string(), "v".to_string())).collect()))
+                .collect()
+        };
+        let base = translations(&[("en", &["kept", "deleted"])]);
+        let local = translations(&[("en", &["kept", "deleted", "added"]), ("de", &["kept", "old"]), ("fr", &["x"])]);
+        let remote = translations(&[("en", &["kept"]), ("de", &["kept"])]);
+
+        // de 没有快照：无法判断，后端没有的键都保留；fr 不在后端，不处理
+        let outcome = removed_on_backend(&base, &local, &remote, true);
+        assert_eq!(outcome.removals, BTreeMap::from([("en".to_string(), vec!["deleted".to_string()])]));
+        assert_eq!(
+            outcome.kept,
+            vec![("de".to_string(), "old".to_string()), ("en".to_string(), "added".to_string())]
+        );
+
+        let outcome = removed_on_backend(&base, &local, &remote, false);
+        assert_eq!(outcome.removals["en"], ["added", "deleted"]);
+        assert_eq!(outcome.removals["de"], ["old"]);
+        assert!(outcome.kept.is_empty());
+    }
+
+    #[test]
+    fn test_removed_on_backend_without_snapshot() {
+        let local = single("en", "local_only", "L");
+        let remote = single("en", "title", "T");
+
+        // 第一次同步前没有快照：不删除任何本地键
+        let outcome = removed_on_backend(&Translations::new(), &local, &remote, true);
+        assert!(outcome.removals.is_empty());
+        assert_eq!(outcome.kept, vec![("en".to_string(), "local_only".to_string())]);
+
+        let outcome = removed_on_backend(&Translations::new(), &local, &remote, false);
+        assert_eq!(outcome.removals["en"], ["local_only"]);
+    }
+
+    #[test]
+    fn test_apply_resolutions() {
+        let mut outcome = three_way_merge(
+            &Translations::new(),
+            &single("en", "title", "Local"),
+            &single("en", "title", "Remote"),
+            Resolution::Remote,
+            true,
+        );
+        assert_eq!(outcome.incoming["en"]["title"], "Remote");
+
+        outcome.conflicts[0].resolution = Resolution::Unresolved;
+        outcome.apply_resolutions();
+        assert!(outcome.incoming["en"].is_empty());
+    }
+
+    #[test]
+    fn test_sync_state_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(SyncState::load(temp_dir.path()).unwrap().translations.is_empty());
+
+        let state = SyncState {
+            translations: single("en", "title", "Hello"),
+        };
+        state.save(temp_dir.path()).unwrap();
+
+        let loaded = SyncState::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded.translations, state.translations);
+    }
+
+    #[test]
+    fn test_sync_state_migrates_legacy_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let legacy = temp_dir.path().join(LEGACY_STATE_FILENAME);
+        std::fs::write(&legacy, r#"{"translations": {"en": {"title": "Hello"}}}"#).unwrap();
+
+        let state = SyncState::load(temp_dir.path()).unwrap();
+        assert_eq!(state.translations, single("en", "title", "Hello"));
+
+        state.save(temp_dir.path()).unwrap();
+        assert!(!legacy.exists());
+        assert!(SyncState::path(temp_dir.path()).exists());
+        assert_eq!(SyncState::load(temp_dir.path()).unwrap().translations, state.translations);
+    }
+
+    #[test]
+    fn test_write_conflict_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("conflicts.json");
+        let conflicts = detect_conflicts(
+            &Translations::new(),
+            &single("en", "title", "Local"),
+            &single("en", "title", "Remote"),
+            Resolution::Remote,
+        );
+
+        write_conflict_report(&path, &conflicts).unwrap();
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(report["count"], 1);
+        assert_eq!(report["conflicts"][0]["key"], "title");
+        assert_eq!(report["conflicts"][0]["resolution"], "remote");
+        assert_eq!(read_conflict_report(&path).unwrap(), conflicts);
+        assert!(read_conflict_report(&temp_dir.path().join("missing.json")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Translation coverage statistics
+//!
+//! Counts, per top-level namespace (the first segment of a key, see
+//! [`super::key_path`]), how many of the base language's keys every
+//! language translates. Keys without a namespace (`title`) are counted under
+//! [`ROOT_NAMESPACE`]. Base keys with an empty value are not counted, and a
+//! translation counts only when its value is not empty.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::key_path::KeyPath;
+use super::Translations;
+
+/// 没有命名空间的键的分组名
+pub const ROOT_NAMESPACE: &str = "(root)";
+
+/// 一组键的覆盖率
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NamespaceCoverage {
+    /// 命名空间（顶层键名片段）
+    pub namespace: String,
+    /// 基准语言中有值的键数
+    pub keys: usize,
+    /// 语言 -> 已翻译的键数
+    pub translated: BTreeMap<String, usize>,
+}
+
+impl NamespaceCoverage {
+    /// 语言的完成度（百分比，没有键时为 100%）
+    pub fn percent(&self, lang: &str) -> f64 {
+        if self.keys == 0 {
+            return 100.0;
+        }
+        self.translated.get(lang).copied().unwrap_or(0) as f64 * 100.0 / self.keys as f64
+    }
+}
+
+/// 所有语言和命名空间的覆盖率
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    /// 基准语言
+    pub base: String,
+    /// 统计的语言（排序）
+    pub languages: Vec<String>,
+    /// 各命名空间（按名称排序）
+    pub namespaces: Vec<NamespaceCoverage>,
+    /// 合计
+    pub total: NamespaceCoverage,
+}
+
+impl Coverage {
+    /// 统计各语言对基准语言的覆盖率
+    ///
+    /// # 参数
+    ///
+    /// * `translations` - 所有语言的翻译
+    /// * `base` - 基准语言
+    /// * `languages` - 要统计的语言（为空时为所有语言，包括基准语言）
+    /// * `separator` - 键分隔符（命名空间为键的第一段）
+    pub fn compute(translations: &Translations, base: &str, languages: &[String], separator: &str) -> Self {
+        let mut langs: Vec<String> = if languages.is_empty() {
+            translations.keys().cloned().collect()
+        } else {
+            languages.to_vec()
+        };
+        langs.sort_unstable();
+        langs.dedup();
+
+        let mut namespaces: BTreeMap<String, NamespaceCoverage> = BTreeMap::new();
+        let mut total = NamespaceCoverage {
+            namespace: "TOTAL".to_string(),
+            ..Default::default()
+        };
+        for (key, value) in translations.get(base).into_iter().flatten() {
+            if value.trim().is_empty() {
+                continue;
+            }
+            let path = KeyPath::parse(key, separator);
+            let namespace = match path.segments() {
+                [first, _, ..] => first.clone(),
+                _ => ROOT_NAMESPACE.to_string(),
+            };
+            let entry = namespaces.entry(namespace.clone()).or_insert_with(|| NamespaceCoverage {
+                namespace,
+                ..Default::default()
+            });
+            entry.keys += 1;
+            total.keys += 1;
+            for lang in &langs {
+                let translated = translations
+                    .get(lang)
+                    .and_then(|keys| keys.get(key))
+                    .is_some_and(|value| !value.trim().is_empty());
+                if translated {
+                    *entry.translated.entry(lang.clone()).or_default() += 1;
+                    *total.translated.entry(lang.clone()).or_default() += 1;
+                }
+            }
+        }
+
+        Self {
+            base: base.to_string(),
+            languages: langs,
+            namespaces: namespaces.into_values().collect(),
+            total,
+        }
+    }
+
+    /// 渲染为文本表格（命名空间 × 语言的完成度）
+    pub fn render_table(&self) -> String {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut header = vec!["NAMESPACE".to_string(), "KEYS".to_string()];
+        header.extend(self.languages.iter().cloned());
+        rows.push(header);
+        for namespace in self.namespaces.iter().chain([&self.total]) {
+            let mut row = vec![namespace.namespace.clone(), namespace.keys.to_string()];
+            row.extend(self.languages.iter().map(|lang| format!("{:.1}%", namespace.percent(lang))));
+            rows.push(row);
+        }
+
+        let columns = rows[0].len();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        let mut out = String::new();
+        for row in rows {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations() -> Translations {
+        [
+            (
+                "en",
+                vec![
+                    ("checkout.title", "Checkout"),
+                    ("checkout.pay", "Pay"),
+                    ("checkout.promo.code", "Code"),
+                    ("checkout.blank", ""),
+                    ("title", "App"),
+                ],
+            ),
+            ("de", vec![("checkout.title", "Kasse"), ("checkout.pay", ""), ("title", "App")]),
+        ]
+        .into_iter()
+        .map(|(lang, keys)| {
+            let keys = keys.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            (lang.to_string(), keys)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_compute() {
+        let coverage = Coverage::compute(&translations(), "en", &[], ".");
+        assert_eq!(coverage.languages, ["de", "en"]);
+        let names: Vec<&str> = coverage.namespaces.iter().map(|ns| ns.namespace.as_str()).collect();
+        assert_eq!(names, ["(root)", "checkout"]);
+        let checkout = &coverage.namespaces[1];
+        assert_eq!(checkout.keys, 3);
+        assert_eq!(checkout.translated["de"], 1);
+        assert_eq!(checkout.percent("en"), 100.0);
+        assert_eq!(coverage.total.keys, 4);
+        assert_eq!(coverage.total.percent("de"), 50.0);
+
+        let only_fr = Coverage::compute(&translations(), "en", &["fr".to_string()], ".");
+        assert_eq!(only_fr.total.percent("fr"), 0.0);
+    }
+
+    #[test]
+    fn test_render_table() {
+        let coverage = Coverage::compute(&translations(), "en", &[], ".");
+        assert_eq!(
+            coverage.render_table(),
+            "NAMESPACE  KEYS  de      en\n\
+             (root)     1     100.0%  100.0%\n\
+             checkout   3     33.3%   100.0%\n\
+             TOTAL      4     50.0%   100.0%\n"
+        );
+    }
+}
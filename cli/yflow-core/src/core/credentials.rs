@@ -0,0 +1,68 @@
+//! OS keyring credentials
+//!
+//! `yflow auth login` stores the API key in the system keyring (macOS
+//! Keychain, Windows Credential Manager, the Linux kernel keyring) instead
+//! of `.i18nrc.json`, where it tends to leak into commits. Entries are keyed
+//! by the backend URL and project ID from the config file, and `load_config`
+//! falls back to them when `apiKey` is empty.
+
+use anyhow::{Context, Result};
+use keyring::Entry;
+
+/// 密钥环中的服务名
+pub const SERVICE: &str = "yflow";
+
+/// 密钥环条目的账户名：`<apiUrl>#<projectId>`（忽略 URL 末尾的 `/`）
+pub fn account(api_url: &str, project_id: u64) -> String {
+    format!("{}#{}", api_url.trim_end_matches('/'), project_id)
+}
+
+/// 打开项目对应的密钥环条目
+fn entry(api_url: &str, project_id: u64) -> Result<Entry> {
+    Entry::new(SERVICE, &account(api_url, project_id)).context("Failed to open the system keyring")
+}
+
+/// 将 API 密钥保存到系统密钥环（覆盖已有的密钥）
+pub fn store_api_key(api_url: &str, project_id: u64, api_key: &str) -> Result<()> {
+    entry(api_url, project_id)?
+        .set_password(api_key)
+        .context("Failed to save the API key to the system keyring")
+}
+
+/// 从系统密钥环读取 API 密钥
+///
+/// # Returns
+///
+/// 没有保存过密钥时返回 `None`
+pub fn load_api_key(api_url: &str, project_id: u64) -> Result<Option<String>> {
+    match entry(api_url, project_id)?.get_password() {
+        Ok(api_key) => Ok(Some(api_key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read the API key from the system keyring"),
+    }
+}
+
+/// 从系统密钥环删除 API 密钥
+///
+/// # Returns
+///
+/// 删除成功返回 `true`，没有保存过密钥时返回 `false`
+pub fn delete_api_key(api_url: &str, project_id: u64) -> Result<bool> {
+    match entry(api_url, project_id)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e).context("Failed to remove the API key from the system keyring"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account() {
+        assert_eq!(account("https://api.example.com/", 7), "https://api.example.com#7");
+        assert_eq!(account("https://api.example.com", 7), account("https://api.example.com/", 7));
+        assert_ne!(account("https://api.example.com", 7), account("https://api.example.com", 8));
+    }
+}
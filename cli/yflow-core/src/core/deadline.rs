@@ -0,0 +1,204 @@
+//! Global run deadline
+//!
+//! Implements `--deadline <duration>`: long-running commands check the
+//! deadline between batches/stages and stop gracefully (never mid-batch,
+//! never with half-written files) once it has passed, summarizing what was
+//! done. A watchdog terminates the process if a blocking call keeps it
+//! running past the deadline plus a grace period, so scheduled CI jobs can
+//! never hang indefinitely.
+
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// 超过截止时间后允许优雅退出的宽限期
+pub const GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// 看门狗强制退出时使用的退出码（与 `timeout(1)` 一致）
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+static DEADLINE: OnceLock<Deadline> = OnceLock::new();
+
+/// 截止时间
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    start: Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    /// 从现在开始计时
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            budget,
+        }
+    }
+
+    /// 时间预算
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// 剩余时间（已超时返回 0）
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.start.elapsed())
+    }
+
+    /// 是否已超过截止时间
+    pub fn is_expired(&self) -> bool {
+        self.start.elapsed() >= self.budget
+    }
+}
+
+/// 设置全局截止时间并启动看门狗
+///
+/// 只在进程启动时调用一次，重复调用将被忽略。看门狗强制退出前会调用
+/// `on_abort`（如清除进度条）。
+pub fn start(budget: Duration, on_abort: fn()) {
+    let deadline = Deadline::new(budget);
+    if DEADLINE.set(deadline).is_err() {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        std::thread::sleep(budget + GRACE_PERIOD);
+        on_abort();
+        eprintln!(
+            "\n❌ Error: Deadline of {} exceeded and the run did not stop within {}s, aborting.",
+            format_duration(budget),
+            GRACE_PERIOD.as_secs()
+        );
+        std::process::exit(TIMEOUT_EXIT_CODE);
+    });
+}
+
+/// 获取全局截止时间（未设置时为 `None`）
+pub fn current() -> Option<Deadline> {
+    DEADLINE.get().copied()
+}
+
+/// 全局截止时间是否已过
+pub fn expired() -> bool {
+    current().map(|d| d.is_expired()).unwrap_or(false)
+}
+
+/// 已超时则返回错误
+///
+/// # Arguments
+///
+/// * `stage` - 当前阶段描述，用于错误信息
+pub fn check(stage: &str) -> Result<()> {
+    match current() {
+        Some(deadline) if deadline.is_expired() => Err(anyhow::anyhow!(
+            "Deadline of {} exceeded before {}",
+            format_duration(deadline.budget()),
+            stage
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 解析时长（如 `90s`、`10m`、`1h30m`、`500ms`，纯数字按秒处理）
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(anyhow::anyhow!("Duration must not be empty"));
+    }
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(anyhow::anyhow!("Invalid duration: {}", input));
+        }
+        let value: u64 = rest[..digits]
+            .parse()
+            .with_context(|| format!("Invalid duration: {}", input))?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+        let unit = &rest[..unit_len];
+        rest = &rest[unit_len..];
+
+        let part = match unit {
+            "ms" => Some(Duration::from_millis(value)),
+            "s" => Some(Duration::from_secs(value)),
+            "m" => value.checked_mul(60).map(Duration::from_secs),
+            "h" => value.checked_mul(3_600).map(Duration::from_secs),
+            _ => return Err(anyhow::anyhow!("Invalid duration unit '{}' in: {}", unit, input)),
+        };
+        total = part
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(|| anyhow::anyhow!("Duration is too long: {}", input))?;
+    }
+
+    Ok(total)
+}
+
+/// 将时长格式化为简短形式（如 `10m`、`1h5m`、`45s`）
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs == 0 {
+        return format!("{}ms", duration.as_millis());
+    }
+
+    let (h, m, s) = (secs / 3_600, (secs % 3_600) / 60, secs % 60);
+    let mut out = String::new();
+    if h > 0 {
+        out.push_str(&format!("{}h", h));
+    }
+    if m > 0 {
+        out.push_str(&format!("{}m", m));
+    }
+    if s > 0 {
+        out.push_str(&format!("{}s", s));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5_400));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("m10").is_err());
+        // 溢出时返回错误而不是 panic
+        assert!(parse_duration("18446744073709551615h").is_err());
+        assert!(parse_duration("307445734561825861m").is_err());
+        assert!(parse_duration("18446744073709551615s1s").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(600)), "10m");
+        assert_eq!(format_duration(Duration::from_secs(3_905)), "1h5m5s");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+    }
+
+    #[test]
+    fn test_deadline_expiry() {
+        let deadline = Deadline::new(Duration::ZERO);
+        assert!(deadline.is_expired());
+        assert_eq!(deadline.remaining(), Duration::ZERO);
+
+        let deadline = Deadline::new(Duration::from_secs(3_600));
+        assert!(!deadline.is_expired());
+        assert!(deadline.remaining() > Duration::from_secs(3_500));
+    }
+}
@@ -0,0 +1,531 @@
+//! Vendor delivery packages
+//!
+//! Reads translation deliveries from vendors (a `.zip`, a directory or a
+//! single file) in XLIFF 1.2/2.0, CSV or gettext PO format, and applies a
+//! mapping file that translates the vendor's language codes and key names
+//! into ours.
+//!
+//! # Mapping file
+//!
+//! A CSV with a `type,from,to` header, where `type` is `language` or `key`:
+//!
+//! ```text
+//! type,from,to
+//! language,de-DE,de
+//! key,btn_save,buttons.save
+//! ```
+//!
+//! # CSV deliveries
+//!
+//! The first row is a header: a `key` column, an optional `source` column,
+//! and one column per language code. `context`/`comment`/`notes` columns are
+//! ignored.
+
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use super::formats::po::PoCatalog;
+use super::scanner::walk_files;
+use super::Translations;
+
+/// 交付包中的单条翻译
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryEntry {
+    /// 语言代码（供应商）
+    pub lang: String,
+    /// 键名（供应商）
+    pub key: String,
+    /// 译文
+    pub value: String,
+    /// 原文（用于占位符校验）
+    pub source: Option<String>,
+}
+
+/// 交付文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryFormat {
+    Xliff,
+    Csv,
+    Po,
+}
+
+impl DeliveryFormat {
+    /// 根据扩展名识别格式
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())?
+            .to_lowercase();
+        match ext.as_str() {
+            "xlf" | "xliff" => Some(DeliveryFormat::Xliff),
+            "csv" => Some(DeliveryFormat::Csv),
+            "po" => Some(DeliveryFormat::Po),
+            _ => None,
+        }
+    }
+}
+
+/// 读取交付包中所有可识别的文件
+///
+/// # Returns
+///
+/// (文件名, 内容) 列表，不可识别的文件被忽略
+pub fn read_package(path: &Path) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+
+    if path.is_dir() {
+        collect_dir(path, &mut files)?;
+    } else if path.extension().and_then(|e| e.to_str()).map(|e| e.eq_ignore_ascii_case("zip")) == Some(true) {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open delivery: {}", path.display()))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .with_context(|| format!("Invalid zip archive: {}", path.display()))?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().to_string();
+            if entry.is_dir() || DeliveryFormat::from_path(&name).is_none() {
+                continue;
+            }
+            let mut content = String::new();
+            entry
+                .read_to_string(&mut content)
+                .with_context(|| format!("Failed to read {} from archive", name))?;
+            files.push((name, content));
+        }
+    } else {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read delivery: {}", path.display()))?;
+        files.push((path.display().to_string(), content));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn collect_dir(root: &Path, files: &mut Vec<(String, String)>) -> Result<()> {
+    for path in walk_files(root)? {
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if DeliveryFormat::from_path(&name).is_some() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            files.push((name, content));
+        }
+    }
+    Ok(())
+}
+
+/// 解析单个交付文件
+///
+/// # Arguments
+///
+/// * `name` - 文件名（用于识别格式，以及 PO 文件缺少 `Language` 头时推断语言）
+/// * `content` - 文件内容
+pub fn parse_file(name: &str, content: &str) -> Result<Vec<DeliveryEntry>> {
+    match DeliveryFormat::from_path(name) {
+        Some(DeliveryFormat::Xliff) => parse_xliff(content).with_context(|| format!("Invalid XLIFF: {}", name)),
+        Some(DeliveryFormat::Csv) => parse_csv(content).with_context(|| format!("Invalid CSV: {}", name)),
+        Some(DeliveryFormat::Po) => parse_po(name, content).with_context(|| format!("Invalid PO: {}", name)),
+        None => Err(anyhow::anyhow!("Unsupported delivery file: {}", name)),
+    }
+}
+
+/// 解析 XLIFF 1.2（`trans-unit`）和 2.0（`unit`/`segment`）
+fn parse_xliff(content: &str) -> Result<Vec<DeliveryEntry>> {
+    let mut reader = Reader::from_str(content);
+    let mut entries = Vec::new();
+
+    let mut target_lang = String::new();
+    let mut unit_id: Option<String> = None;
+    let mut source = String::new();
+    let mut target = String::new();
+    let mut has_target = false;
+    // 当前文本写入位置：0 = 无，1 = source，2 = target
+    let mut capture = 0u8;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if capture == 0 => {
+                let attr = |name: &[u8]| -> Option<String> {
+                    e.attributes()
+                        .flatten()
+                        .find(|a| a.key.as_ref() == name)
+                        .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+                };
+                match e.local_name().as_ref() {
+                    b"file" | b"xliff" => {
+                        if let Some(lang) = attr(b"target-language").or_else(|| attr(b"trgLang")) {
+                            target_lang = lang;
+                        }
+                    }
+                    b"trans-unit" | b"unit" => {
+                        unit_id = attr(b"resname").or_else(|| attr(b"name")).or_else(|| attr(b"id"));
+                        source.clear();
+                        target.clear();
+                        has_target = false;
+                    }
+                    b"source" if unit_id.is_some() => capture = 1,
+                    b"target" if unit_id.is_some() => {
+                        capture = 2;
+                        has_target = true;
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(t) => match capture {
+                1 => source.push_str(&t.unescape()?),
+                2 => target.push_str(&t.unescape()?),
+                _ => {}
+            },
+            Event::CData(t) => {
+                let text = String::from_utf8_lossy(&t).to_string();
+                match capture {
+                    1 => source.push_str(&text),
+                    2 => target.push_str(&text),
+                    _ => {}
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"source" | b"target" => capture = 0,
+                b"trans-unit" | b"unit" => {
+                    if let Some(key) = unit_id.take() {
+                        if has_target && !target.is_empty() {
+                            if target_lang.is_empty() {
+                                return Err(anyhow::anyhow!("Missing target language"));
+                            }
+                            entries.push(DeliveryEntry {
+                                lang: target_lang.clone(),
+                                key,
+                                value: target.clone(),
+                                source: Some(source.clone()),
+                            });
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+/// 解析 CSV（`key,[source,]<lang>...`）
+fn parse_csv(content: &str) -> Result<Vec<DeliveryEntry>> {
+    parse_table(content, b',')
+}
+
+/// 解析带表头的表格（CSV/TSV）：`key` 列、可选的 `source` 列和每种语言一列
+///
+/// 空单元格被跳过；表格软件导出时添加的 UTF-8 BOM 被忽略。
+///
+/// # Arguments
+///
+/// * `content` - 表格内容
+/// * `delimiter` - 分隔符（`b','` 或 `b'\t'`）
+pub fn parse_table(content: &str, delimiter: u8) -> Result<Vec<DeliveryEntry>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    let key_col = headers
+        .iter()
+        .position(|h| h.eq_ignore_ascii_case("key"))
+        .ok_or_else(|| anyhow::anyhow!("Missing 'key' column"))?;
+    let source_col = headers.iter().position(|h| h.eq_ignore_ascii_case("source"));
+    let ignored = ["key", "source", "context", "comment", "notes"];
+    let lang_cols: Vec<(usize, &String)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !h.is_empty() && !ignored.contains(&h.to_lowercase().as_str()))
+        .collect();
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let key = match record.get(key_col) {
+            Some(key) if !key.trim().is_empty() => key.trim().to_string(),
+            _ => continue,
+        };
+        let source = source_col.and_then(|c| record.get(c)).map(|s| s.to_string());
+        for (col, lang) in &lang_cols {
+            if let Some(value) = record.get(*col).filter(|v| !v.is_empty()) {
+                entries.push(DeliveryEntry {
+                    lang: (*lang).clone(),
+                    key: key.clone(),
+                    value: value.to_string(),
+                    source: source.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// 解析 gettext PO（键规则见 [`formats::po`](super::formats::po)）
+///
+/// 语言取自头部的 `Language:`，缺失时使用文件名（`de.po`、`de/messages.po`）。
+fn parse_po(name: &str, content: &str) -> Result<Vec<DeliveryEntry>> {
+    let catalog = PoCatalog::parse(content)?;
+    let lang = catalog
+        .language()
+        .or_else(|| lang_from_filename(name))
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine language (no 'Language' header)"))?;
+
+    let mut sources = catalog.sources();
+    let mut entries: Vec<DeliveryEntry> = catalog
+        .to_translations(false)
+        .into_iter()
+        .map(|(key, value)| DeliveryEntry {
+            lang: lang.clone(),
+            source: sources.remove(&key),
+            key,
+            value,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(entries)
+}
+
+/// 从 PO 文件路径推断语言：优先目录名（`de/messages.po`），其次文件名（`de.po`）
+fn lang_from_filename(name: &str) -> Option<String> {
+    let looks_like_locale = |s: &str| {
+        let mut parts = s.splitn(2, ['_', '-']);
+        let lang = parts.next().unwrap_or_default();
+        let region_ok = parts
+            .next()
+            .map(|r| (2..=4).contains(&r.len()) && r.chars().all(|c| c.is_ascii_alphanumeric()))
+            .unwrap_or(true);
+        (2..=3).contains(&lang.len()) && lang.chars().all(|c| c.is_ascii_lowercase()) && region_ok
+    };
+
+    let path = Path::new(name);
+    let parent = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str());
+    let stem = path.file_stem().and_then(|s| s.to_str());
+    parent
+        .filter(|p| looks_like_locale(p))
+        .or(stem.filter(|s| looks_like_locale(s)))
+        .map(|s| s.to_string())
+}
+
+/// 映射文件：供应商语言代码/键名 -> 本地
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryMapping {
+    /// 语言代码映射
+    pub languages: HashMap<String, String>,
+    /// 键名映射
+    pub keys: HashMap<String, String>,
+}
+
+impl DeliveryMapping {
+    /// 从 CSV 映射文件加载（`type,from,to`）
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mapping file: {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid mapping file: {}", path.display()))
+    }
+
+    /// 解析 CSV 映射内容
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+        let mut mapping = Self::default();
+
+        for (line, record) in reader.records().enumerate() {
+            let record = record?;
+            let (kind, from, to) = match (record.get(0), record.get(1), record.get(2)) {
+                (Some(kind), Some(from), Some(to)) => (kind, from, to),
+                _ => return Err(anyhow::anyhow!("Row {} must have type,from,to", line + 2)),
+            };
+            match kind.to_lowercase().as_str() {
+                "language" | "lang" => mapping.languages.insert(from.to_string(), to.to_string()),
+                "key" => mapping.keys.insert(from.to_string(), to.to_string()),
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Row {}: unknown mapping type '{}' (expected language or key)",
+                        line + 2,
+                        other
+                    ));
+                }
+            };
+        }
+
+        Ok(mapping)
+    }
+
+    /// 应用映射
+    pub fn apply(&self, entry: DeliveryEntry) -> DeliveryEntry {
+        DeliveryEntry {
+            lang: self.languages.get(&entry.lang).cloned().unwrap_or(entry.lang),
+            key: self.keys.get(&entry.key).cloned().unwrap_or(entry.key),
+            ..entry
+        }
+    }
+}
+
+/// 将条目转换为翻译数据（同一语言和键以后出现的为准）
+pub fn to_translations(entries: &[DeliveryEntry]) -> Translations {
+    let mut translations = Translations::new();
+    for entry in entries {
+        translations
+            .entry(entry.lang.clone())
+            .or_default()
+            .insert(entry.key.clone(), entry.value.clone());
+    }
+    translations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xliff_12() {
+        let content = r#"<?xml version="1.0"?>
+<xliff version="1.2">
+  <file source-language="en" target-language="de-DE" original="app">
+    <body>
+      <trans-unit id="1" resname="btn_save">
+        <source>Save {name}</source>
+        <target>Speichern {name}</target>
+      </trans-unit>
+      <trans-unit id="untranslated">
+        <source>Skip</source>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>"#;
+
+        let entries = parse_file("de.xlf", content).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].lang, "de-DE");
+        assert_eq!(entries[0].key, "btn_save");
+        assert_eq!(entries[0].value, "Speichern {name}");
+        assert_eq!(entries[0].source.as_deref(), Some("Save {name}"));
+    }
+
+    #[test]
+    fn test_parse_xliff_20() {
+        let content = r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="fr">
+  <file id="f1">
+    <unit id="title"><segment><source>Title</source><target>Titre</target></segment></unit>
+  </file>
+</xliff>"#;
+
+        let entries = parse_file("fr.xliff", content).unwrap();
+        assert_eq!(entries[0].lang, "fr");
+        assert_eq!(entries[0].key, "title");
+        assert_eq!(entries[0].value, "Titre");
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "key,source,de,fr,notes\ntitle,Title,Titel,,n/a\nbody,Body,Text,Corps,\n";
+        let entries = parse_file("delivery.csv", content).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.lang != "notes"));
+        assert_eq!(entries[0].source.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn test_parse_table_tsv_with_bom() {
+        let content = "\u{feff}Key\ten\tde\nbuttons.save\tSave, now\tSpeichern\nempty\t\t\n";
+        let entries = parse_table(content, b'\t').unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].value, "Save, now");
+        assert_eq!(entries[1].lang, "de");
+        assert!(parse_table("id\ten\nx\ty\n", b'\t').unwrap_err().to_string().contains("Missing 'key' column"));
+    }
+
+    #[test]
+    fn test_parse_po() {
+        let content = r#"
+msgid ""
+msgstr ""
+"Language: ja\n"
+
+#: src/app.js:10
+msgid "Hello %s"
+msgstr "こんにちは %s"
+
+msgctxt "menu.file"
+msgid "File"
+msgstr ""
+"ファ"
+"イル"
+
+msgid "Untranslated"
+msgstr ""
+"#;
+        let entries = parse_file("messages.po", content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].lang, "ja");
+        assert_eq!(entries[0].key, "Hello %s");
+        assert_eq!(entries[1].key, "menu.file");
+        assert_eq!(entries[1].value, "ファイル");
+        assert_eq!(entries[1].source.as_deref(), Some("File"));
+    }
+
+    #[test]
+    fn test_parse_po_language_from_filename() {
+        let entries = parse_file("de/app.po", "msgid \"Hi\"\nmsgstr \"Hallo\"\n").unwrap();
+        assert_eq!(entries[0].lang, "de");
+    }
+
+    #[test]
+    fn test_mapping() {
+        let mapping = DeliveryMapping::parse("type,from,to\nlanguage,de-DE,de\nkey,btn_save,buttons.save\n").unwrap();
+        let entry = mapping.apply(DeliveryEntry {
+            lang: "de-DE".to_string(),
+            key: "btn_save".to_string(),
+            value: "Speichern".to_string(),
+            source: None,
+        });
+        assert_eq!(entry.lang, "de");
+        assert_eq!(entry.key, "buttons.save");
+
+        assert!(DeliveryMapping::parse("type,from,to\nregion,a,b\n").is_err());
+    }
+
+    #[test]
+    fn test_read_package_zip() {
+        use std::io::Write;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("delivery.zip");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("de/app.po", options).unwrap();
+        writer.write_all(b"msgid \"Hi\"\nmsgstr \"Hallo\"\n").unwrap();
+        writer.start_file("README.txt", options).unwrap();
+        writer.write_all(b"ignored").unwrap();
+        writer.finish().unwrap();
+
+        let files = read_package(&path).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "de/app.po");
+    }
+}
@@ -0,0 +1,288 @@
+//! Delta cache module
+//!
+//! Keeps `.yflow-cache/delta.json` in the messages directory (next to the
+//! chunked-download cache) with a hash of every key/value known to match
+//! the backend (after a sync or a successful import) and of every locale
+//! file as the last sync left it.
+//!
+//! Key hashes are kept per backend project (`<apiUrl>#<projectId>`, see
+//! [`credentials::account`]) and looked up in the project the key is routed
+//! to, so changing `projectId` / `apiUrl` or adding a route makes the keys
+//! that now go to another project count as changed.
+//!
+//! `import` uses it to push only keys whose local value changed since, and
+//! `sync` skips rewriting languages whose backend values and files are both
+//! unchanged. Deleting the file (or `--no-cache`) falls back to a full run.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::credentials;
+use super::download::CACHE_DIR;
+use super::routing::{RouteTarget, Router};
+use super::scanner::{language_dir, language_files};
+use super::{MessagesDir, Translations};
+
+/// 缓存文件名（位于 messages 目录的 `.yflow-cache/` 下）
+pub const CACHE_FILENAME: &str = "delta.json";
+
+/// 哈希保留的十六进制位数
+const HASH_LEN: usize = 16;
+
+/// 增量缓存
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaCache {
+    /// 与后端一致的键值哈希：后端项目 -> 语言 -> 键 -> 哈希
+    #[serde(default)]
+    pub backends: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+    /// 上次同步后的文件哈希：语言 -> 文件（相对语言目录）-> 哈希
+    #[serde(default)]
+    pub files: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl DeltaCache {
+    /// 获取缓存文件路径
+    pub fn path(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(CACHE_DIR).join(CACHE_FILENAME)
+    }
+
+    /// 加载缓存（文件不存在时返回空缓存）
+    pub fn load(messages_dir: &Path) -> Result<Self> {
+        let path = Self::path(messages_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read delta cache: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid delta cache file: {}", path.display()))
+    }
+
+    /// 保存缓存
+    pub fn save(&self, messages_dir: &Path) -> Result<()> {
+        let path = Self::path(messages_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string(self)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write delta cache: {}", path.display()))
+    }
+
+    /// 计算内容哈希（SHA-256 前 16 位十六进制）
+    pub fn hash(content: &[u8]) -> String {
+        let mut digest = hex::encode(Sha256::digest(content));
+        digest.truncate(HASH_LEN);
+        digest
+    }
+
+    /// 键在其路由到的项目中记录的哈希
+    fn known_hash(&self, router: &Router, lang: &str, key: &str) -> Option<&String> {
+        self.backends
+            .get(&backend_id(router.target_for(lang, key)))
+            .and_then(|languages| languages.get(lang))
+            .and_then(|known| known.get(key))
+    }
+
+    /// 只保留自上次记录后发生变化的键
+    ///
+    /// # Returns
+    ///
+    /// 变化的翻译和跳过的键数
+    pub fn changed(&self, router: &Router, translations: Translations) -> (Translations, usize) {
+        let mut skipped = 0;
+        let changed = translations
+            .into_iter()
+            .map(|(lang, keys)| {
+                let keys: HashMap<String, String> = keys
+                    .into_iter()
+                    .filter(|(key, value)| {
+                        let unchanged = self
+                            .known_hash(router, &lang, key)
+                            .is_some_and(|hash| *hash == Self::hash(value.as_bytes()));
+                        skipped += usize::from(unchanged);
+                        !unchanged
+                    })
+                    .collect();
+                (lang, keys)
+            })
+            .filter(|(_, keys)| !keys.is_empty())
+            .collect();
+        (changed, skipped)
+    }
+
+    /// 记录与后端一致的键值（记在各键路由到的项目下）
+    ///
+    /// `replace` 为 true 时替换整个语言的记录（全量同步），否则只更新给出的键。
+    pub fn record(&mut self, router: &Router, translations: &Translations, replace: bool) {
+        for (lang, keys) in translations {
+            if replace {
+                for languages in self.backends.values_mut() {
+                    languages.remove(lang);
+                }
+            }
+            for (key, value) in keys {
+                self.backends
+                    .entry(backend_id(router.target_for(lang, key)))
+                    .or_default()
+                    .entry(lang.clone())
+                    .or_default()
+                    .insert(key.clone(), Self::hash(value.as_bytes()));
+            }
+        }
+        self.backends.retain(|_, languages| !languages.is_empty());
+    }
+
+    /// 检查语言的后端值是否与记录完全一致
+    pub fn language_unchanged(&self, router: &Router, lang: &str, values: &HashMap<String, String>) -> bool {
+        let known: usize = self
+            .backends
+            .values()
+            .filter_map(|languages| languages.get(lang))
+            .map(BTreeMap::len)
+            .sum();
+        known > 0
+            && known == values.len()
+            && values.iter().all(|(key, value)| {
+                self.known_hash(router, lang, key)
+                    .is_some_and(|hash| *hash == Self::hash(value.as_bytes()))
+            })
+    }
+
+    /// 检查语言目录下的文件是否与上次同步后完全一致
+    pub fn files_unchanged(&self, messages_dir: &MessagesDir, lang: &str) -> Result<bool> {
+        let current = hash_language_files(messages_dir, lang)?;
+        Ok(!current.is_empty() && self.files.get(lang) == Some(&current))
+    }
+
+    /// 记录语言目录下当前的文件哈希
+    pub fn record_files(&mut self, messages_dir: &MessagesDir, lang: &str) -> Result<()> {
+        let current = hash_language_files(messages_dir, lang)?;
+        self.files.insert(lang.to_string(), current);
+        Ok(())
+    }
+}
+
+/// 缓存中后端项目的标识
+fn backend_id(target: &RouteTarget) -> String {
+    credentials::account(&target.api_url, target.project_id)
+}
+
+/// 计算语言目录下所有翻译文件的哈希
+fn hash_language_files(messages_dir: &MessagesDir, lang: &str) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for file in language_files(messages_dir, lang)? {
+        let path = language_dir(messages_dir, lang).join(&file);
+        let content = std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        hashes.insert(file.to_string_lossy().replace('\\', "/"), DeltaCache::hash(&content));
+    }
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{I18nConfig, Route};
+    use tempfile::TempDir;
+
+    fn router(project_id: u64, routes: Vec<Route>) -> Router {
+        Router::from_config(&I18nConfig {
+            api_url: "https://api.example.com".to_string(),
+            project_id,
+            routes,
+            ..Default::default()
+        })
+    }
+
+    fn translations(lang: &str, pairs: &[(&str, &str)]) -> Translations {
+        HashMap::from([(
+            lang.to_string(),
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        )])
+    }
+
+    #[test]
+    fn test_changed_keys() {
+        let router = router(1, Vec::new());
+        let mut cache = DeltaCache::default();
+        cache.record(&router, &translations("en", &[("a", "A"), ("b", "B")]), false);
+
+        let (changed, skipped) = cache.changed(&router, translations("en", &[("a", "A"), ("b", "B2"), ("c", "C")]));
+        assert_eq!(skipped, 1);
+        assert_eq!(changed["en"].len(), 2);
+        assert!(!changed["en"].contains_key("a"));
+
+        let (changed, skipped) = cache.changed(&router, translations("en", &[("a", "A")]));
+        assert_eq!(skipped, 1);
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn test_changed_keys_per_backend() {
+        let mut cache = DeltaCache::default();
+        let local = translations("en", &[("emails.welcome", "Hi"), ("title", "Title")]);
+        cache.record(&router(1, Vec::new()), &local, false);
+
+        // 另一个项目：所有键都需要推送
+        let (changed, skipped) = cache.changed(&router(2, Vec::new()), local.clone());
+        assert_eq!((changed["en"].len(), skipped), (2, 0));
+
+        // 新增路由：路由到新项目的键需要推送，其余仍然跳过
+        let route = Route {
+            key_prefix: Some("emails".to_string()),
+            language: None,
+            project_id: 7,
+            api_url: None,
+            api_key: None,
+        };
+        let routed = router(1, vec![route]);
+        let (changed, skipped) = cache.changed(&routed, local.clone());
+        assert_eq!(skipped, 1);
+        assert!(changed["en"].contains_key("emails.welcome"));
+
+        cache.record(&routed, &local, false);
+        assert!(cache.changed(&routed, local).0.is_empty());
+        assert_eq!(cache.backends.len(), 2);
+    }
+
+    #[test]
+    fn test_language_unchanged() {
+        let other = router(2, Vec::new());
+        let router = router(1, Vec::new());
+        let mut cache = DeltaCache::default();
+        cache.record(&router, &translations("en", &[("a", "A"), ("b", "B")]), false);
+        cache.record(&router, &translations("en", &[("a", "A")]), true);
+
+        let values = translations("en", &[("a", "A")]).remove("en").unwrap();
+        assert!(cache.language_unchanged(&router, "en", &values));
+        assert!(!cache.language_unchanged(&router, "de", &values));
+        assert!(!cache.language_unchanged(&other, "en", &values));
+        let values = translations("en", &[("a", "A2")]).remove("en").unwrap();
+        assert!(!cache.language_unchanged(&router, "en", &values));
+    }
+
+    #[test]
+    fn test_files_unchanged_and_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = &MessagesDir::new(temp_dir.path());
+        std::fs::create_dir_all(dir.join("en")).unwrap();
+        std::fs::write(dir.join("en/common.json"), r#"{"a": "A"}"#).unwrap();
+
+        let mut cache = DeltaCache::default();
+        assert!(!cache.files_unchanged(dir, "en").unwrap());
+        cache.record_files(dir, "en").unwrap();
+        assert!(cache.files_unchanged(dir, "en").unwrap());
+        assert!(!cache.files_unchanged(dir, "de").unwrap());
+
+        cache.save(dir).unwrap();
+        assert_eq!(DeltaCache::load(dir).unwrap(), cache);
+
+        std::fs::write(dir.join("en/common.json"), r#"{"a": "B"}"#).unwrap();
+        assert!(!cache.files_unchanged(dir, "en").unwrap());
+    }
+}
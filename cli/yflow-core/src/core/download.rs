@@ -0,0 +1,186 @@
+//! Resumable chunked downloads
+//!
+//! Very large projects can be downloaded in chunks (one per locale, or per
+//! locale and namespace when the backend supports namespace filtering).
+//! Every finished chunk is written to
+//! `<messagesDir>/.yflow-cache/chunks/<projectId>/`, so a flaky connection
+//! only costs the chunk in flight: re-running the download skips chunks that
+//! are already cached. The cache is cleared once every chunk has arrived.
+//!
+//! Chunks are stored in the packed format (see [`super::packed`]); chunks
+//! cached by an older format version are simply downloaded again.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{packed, Translations};
+
+/// 缓存目录名（位于 messages 目录下）
+pub const CACHE_DIR: &str = ".yflow-cache";
+
+/// 分块缓存子目录
+const CHUNKS_DIR: &str = "chunks";
+
+/// 下载块：一个语言，或一个语言下的一个命名空间
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Chunk {
+    /// 后端语言代码
+    pub locale: String,
+    /// 命名空间（None 表示整个语言）
+    pub namespace: Option<String>,
+}
+
+impl Chunk {
+    /// 创建下载块
+    pub fn new(locale: &str, namespace: Option<&str>) -> Self {
+        Self {
+            locale: locale.to_string(),
+            namespace: namespace.map(|ns| ns.to_string()),
+        }
+    }
+
+    /// 缓存文件名（`<locale>.bin` 或 `<locale>@<namespace>.bin`）
+    fn file_name(&self) -> String {
+        let sanitize = |s: &str| s.replace(['/', '\\', ':'], "_");
+        match &self.namespace {
+            Some(ns) => format!("{}@{}.{}", sanitize(&self.locale), sanitize(ns), packed::EXTENSION),
+            None => format!("{}.{}", sanitize(&self.locale), packed::EXTENSION),
+        }
+    }
+}
+
+impl std::fmt::Display for Chunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.namespace {
+            Some(ns) => write!(f, "{}/{}", self.locale, ns),
+            None => f.write_str(&self.locale),
+        }
+    }
+}
+
+/// 生成下载计划：每个语言与每个命名空间的组合
+///
+/// `namespaces` 为空时每个语言一个块。
+pub fn plan_chunks(locales: &[String], namespaces: &[String]) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    for locale in locales {
+        if namespaces.is_empty() {
+            chunks.push(Chunk::new(locale, None));
+        } else {
+            chunks.extend(namespaces.iter().map(|ns| Chunk::new(locale, Some(ns))));
+        }
+    }
+    chunks.sort();
+    chunks.dedup();
+    chunks
+}
+
+/// 已下载块的本地缓存
+#[derive(Debug, Clone)]
+pub struct ChunkCache {
+    /// 缓存目录
+    dir: PathBuf,
+}
+
+impl ChunkCache {
+    /// 打开指定项目的块缓存
+    pub fn new(messages_dir: &Path, project_id: u64) -> Self {
+        Self {
+            dir: messages_dir
+                .join(CACHE_DIR)
+                .join(CHUNKS_DIR)
+                .join(project_id.to_string()),
+        }
+    }
+
+    /// 缓存目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 读取已缓存的块（未缓存或缓存已失效时返回 None）
+    pub fn load(&self, chunk: &Chunk) -> Result<Option<HashMap<String, String>>> {
+        let path = self.dir.join(chunk.file_name());
+        packed::read(&path).with_context(|| format!("Failed to parse cached chunk: {}", path.display()))
+    }
+
+    /// 缓存一个已下载的块
+    ///
+    /// 先写入临时文件再重命名，中断时不会留下不完整的块。
+    pub fn store(&self, chunk: &Chunk, translations: &HashMap<String, String>) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory: {}", self.dir.display()))?;
+        packed::write(&self.dir.join(chunk.file_name()), translations)
+    }
+
+    /// 清除所有已缓存的块
+    pub fn clear(&self) -> Result<()> {
+        if self.dir.exists() {
+            std::fs::remove_dir_all(&self.dir)
+                .with_context(|| format!("Failed to clear {}", self.dir.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// 将下载的块合并为 `{语言: {键: 值}}`
+pub fn assemble(chunks: Vec<(Chunk, HashMap<String, String>)>) -> Translations {
+    let mut translations = Translations::new();
+    for (chunk, values) in chunks {
+        translations.entry(chunk.locale).or_default().extend(values);
+    }
+    translations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_plan_chunks() {
+        let locales = vec!["en".to_string(), "de".to_string()];
+        assert_eq!(
+            plan_chunks(&locales, &[]),
+            vec![Chunk::new("de", None), Chunk::new("en", None)]
+        );
+
+        let namespaces = vec!["common".to_string(), "checkout".to_string()];
+        let chunks = plan_chunks(&locales, &namespaces);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0].to_string(), "de/checkout");
+    }
+
+    #[test]
+    fn test_chunk_cache_roundtrip_and_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ChunkCache::new(temp_dir.path(), 7);
+        let chunk = Chunk::new("zh/CN", Some("common"));
+        assert_eq!(cache.load(&chunk).unwrap(), None);
+
+        let values = HashMap::from([("title".to_string(), "标题".to_string())]);
+        cache.store(&chunk, &values).unwrap();
+        assert!(cache.dir().join("zh_CN@common.bin").exists());
+        assert_eq!(cache.load(&chunk).unwrap(), Some(values));
+
+        cache.clear().unwrap();
+        assert!(!cache.dir().exists());
+        assert_eq!(cache.load(&chunk).unwrap(), None);
+    }
+
+    #[test]
+    fn test_assemble_merges_namespaces() {
+        let translations = assemble(vec![
+            (
+                Chunk::new("en", Some("a")),
+                HashMap::from([("a.x".to_string(), "X".to_string())]),
+            ),
+            (
+                Chunk::new("en", Some("b")),
+                HashMap::from([("b.y".to_string(), "Y".to_string())]),
+            ),
+        ]);
+        assert_eq!(translations["en"].len(), 2);
+    }
+}
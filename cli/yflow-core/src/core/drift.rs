@@ -0,0 +1,122 @@
+//! Local vs backend drift
+//!
+//! Compares local translations with the backend (both keyed by local
+//! language codes) and reports, per language, the keys that exist only
+//! locally, only on the backend, or on both sides with different values.
+//! Values are compared with the project's [`Comparator`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use super::compare::Comparator;
+use super::Translations;
+
+/// 单个语言的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageDrift {
+    /// 语言代码（本地）
+    pub lang: String,
+    /// 仅存在于本地的键
+    pub local_only: Vec<String>,
+    /// 仅存在于后端的键
+    pub backend_only: Vec<String>,
+    /// 两边都存在但值不同的键
+    pub differ: Vec<String>,
+    /// 两边一致的键数
+    pub in_sync: usize,
+}
+
+impl LanguageDrift {
+    /// 是否存在差异
+    pub fn has_drift(&self) -> bool {
+        !self.local_only.is_empty() || !self.backend_only.is_empty() || !self.differ.is_empty()
+    }
+}
+
+/// 计算本地与后端的差异
+///
+/// 返回按语言代码排序的结果，包含两边出现过的所有语言。
+/// `comparator` 认为相同的值不算差异。
+pub fn compute_drift(local: &Translations, backend: &Translations, comparator: Comparator) -> Vec<LanguageDrift> {
+    let empty = HashMap::new();
+    let languages: BTreeSet<&String> = local.keys().chain(backend.keys()).collect();
+
+    languages
+        .into_iter()
+        .map(|lang| {
+            let local_lang = local.get(lang).unwrap_or(&empty);
+            let backend_lang = backend.get(lang).unwrap_or(&empty);
+            let mut drift = LanguageDrift {
+                lang: lang.clone(),
+                ..Default::default()
+            };
+
+            for (key, value) in local_lang {
+                match backend_lang.get(key) {
+                    None => drift.local_only.push(key.clone()),
+                    Some(remote) if !comparator.equivalent(remote, value) => drift.differ.push(key.clone()),
+                    Some(_) => drift.in_sync += 1,
+                }
+            }
+            drift.backend_only = backend_lang
+                .keys()
+                .filter(|key| !local_lang.contains_key(*key))
+                .cloned()
+                .collect();
+
+            drift.local_only.sort();
+            drift.backend_only.sort();
+            drift.differ.sort();
+            drift
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations(lang: &str, entries: &[(&str, &str)]) -> Translations {
+        HashMap::from([(
+            lang.to_string(),
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )])
+    }
+
+    #[test]
+    fn test_compute_drift() {
+        let mut local = translations("en", &[("same", "A"), ("changed", "Local"), ("new", "N")]);
+        local.extend(translations("fr", &[("same", "A")]));
+        let mut backend = translations("en", &[("same", "A"), ("changed", "Remote"), ("old", "O")]);
+        backend.extend(translations("de", &[("same", "A")]));
+
+        let drift = compute_drift(&local, &backend, Comparator::Exact);
+        let langs: Vec<&str> = drift.iter().map(|d| d.lang.as_str()).collect();
+        assert_eq!(langs, vec!["de", "en", "fr"]);
+
+        assert_eq!(drift[0].backend_only, vec!["same"]);
+        assert_eq!(drift[1].local_only, vec!["new"]);
+        assert_eq!(drift[1].backend_only, vec!["old"]);
+        assert_eq!(drift[1].differ, vec!["changed"]);
+        assert_eq!(drift[1].in_sync, 1);
+        assert_eq!(drift[2].local_only, vec!["same"]);
+        assert!(drift.iter().all(|d| d.has_drift()));
+    }
+
+    #[test]
+    fn test_no_drift() {
+        let local = translations("en", &[("a", "A")]);
+        let drift = compute_drift(&local, &local.clone(), Comparator::Exact);
+        assert!(!drift[0].has_drift());
+        assert_eq!(drift[0].in_sync, 1);
+
+        // 首尾空白不同的值在 trimmed 下相同
+        let padded = translations("en", &[("a", " A\n")]);
+        assert!(compute_drift(&local, &padded, Comparator::Exact)[0].has_drift());
+        assert!(!compute_drift(&local, &padded, Comparator::Trimmed)[0].has_drift());
+    }
+}
@@ -0,0 +1,96 @@
+//! Classified failures and exit codes
+//!
+//! Errors that scripts need to tell apart carry a [`Failure`] kind; in CI
+//! mode (`--ci`) the process exits with its code instead of the generic 1:
+//!
+//! - 0: success
+//! - 1: any other error
+//! - 2: drift detected (`status`, `diff-local`, `sync --check`)
+//! - 3: validation errors (`validate`, `lint`, `check-placeholders`,
+//!   `import --check`, `import --server-dry-run` rejections)
+//! - 4: authentication failure
+//!
+//! The kind survives `.context(...)` added by callers; [`exit_code`] looks
+//! for it anywhere in the error chain.
+
+use std::fmt;
+
+/// 失败的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// 本地与后端（或两个目录）之间存在差异
+    Drift,
+    /// 翻译未通过校验
+    Validation,
+    /// 认证失败
+    Auth,
+}
+
+impl Failure {
+    /// 退出码
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Drift => 2,
+            Self::Validation => 3,
+            Self::Auth => 4,
+        }
+    }
+
+    /// 创建带有该类型的错误
+    pub fn error(self, message: impl fmt::Display) -> anyhow::Error {
+        anyhow::Error::new(ClassifiedError {
+            failure: self,
+            message: message.to_string(),
+        })
+    }
+}
+
+/// 带有失败类型的错误（显示为原消息）
+#[derive(Debug)]
+struct ClassifiedError {
+    failure: Failure,
+    message: String,
+}
+
+impl fmt::Display for ClassifiedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ClassifiedError {}
+
+/// 错误链中的失败类型
+pub fn classify(error: &anyhow::Error) -> Option<Failure> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ClassifiedError>())
+        .map(|classified| classified.failure)
+}
+
+/// 错误对应的退出码（未分类的错误为 1）
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    classify(error).map_or(1, Failure::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code() {
+        let error = Failure::Auth.error("API authentication failed");
+        assert_eq!(error.to_string(), "API authentication failed");
+        assert_eq!(exit_code(&error), 4);
+
+        let wrapped = Err::<(), _>(Failure::Drift.error("Drift detected"))
+            .context("Failed to check status")
+            .unwrap_err();
+        assert_eq!(classify(&wrapped), Some(Failure::Drift));
+        assert_eq!(exit_code(&wrapped), 2);
+
+        assert_eq!(exit_code(&anyhow::anyhow!("boom")), 1);
+        assert_eq!(Failure::Validation.exit_code(), 3);
+    }
+}
@@ -0,0 +1,100 @@
+//! Backend fetch cache
+//!
+//! Every successful `sync` fetch is kept in `.yflow-cache/backend.bin`
+//! inside the messages directory (packed, see [`super::packed`]), already
+//! mapped to local language codes. `sync --offline` replays it instead of
+//! contacting the backend, so locale files can be regenerated without
+//! network access (on a plane, or in a network-restricted CI job that
+//! restores the cache directory).
+//!
+//! A sync restricted with `--locale` only replaces the fetched languages;
+//! key filters are applied after the fetch, so the cache always holds whole
+//! languages.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::download::CACHE_DIR;
+use super::{packed, timestamp, Translations};
+
+/// 缓存文件名（位于 messages 目录的 `.yflow-cache/` 下）
+pub const CACHE_FILENAME: &str = "backend.bin";
+
+/// 上次从后端获取的翻译
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchCache {
+    /// 项目 ID
+    pub project_id: u64,
+    /// 获取时间（Unix 秒）
+    pub fetched_at: u64,
+    /// 翻译（本地语言代码）
+    pub translations: Translations,
+}
+
+impl FetchCache {
+    /// 获取缓存文件路径
+    pub fn path(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(CACHE_DIR).join(CACHE_FILENAME)
+    }
+
+    /// 加载缓存
+    ///
+    /// 文件不存在或格式版本已失效时返回 `None`。
+    pub fn load(messages_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(messages_dir);
+        packed::read(&path).with_context(|| format!("Invalid backend cache: {}", path.display()))
+    }
+
+    /// 记录一次成功的获取
+    ///
+    /// `replace` 为 `false`（只获取了部分语言）时保留其他语言的旧缓存；
+    /// 缓存属于其他项目时整体替换。
+    pub fn record(messages_dir: &Path, project_id: u64, fetched: &Translations, replace: bool) -> Result<()> {
+        let mut cache = match Self::load(messages_dir) {
+            Ok(Some(cache)) if !replace && cache.project_id == project_id => cache,
+            _ => Self::default(),
+        };
+        cache.project_id = project_id;
+        cache.fetched_at = timestamp::now();
+        cache
+            .translations
+            .extend(fetched.iter().map(|(lang, keys)| (lang.clone(), keys.clone())));
+
+        let path = Self::path(messages_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        packed::write(&path, &cache).with_context(|| format!("Failed to write backend cache: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn single(lang: &str, key: &str, value: &str) -> Translations {
+        Translations::from([(lang.to_string(), HashMap::from([(key.to_string(), value.to_string())]))])
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = temp_dir.path();
+        assert!(FetchCache::load(messages_dir).unwrap().is_none());
+
+        FetchCache::record(messages_dir, 1, &single("en", "title", "Home"), true).unwrap();
+        FetchCache::record(messages_dir, 1, &single("de", "title", "Start"), false).unwrap();
+        let cache = FetchCache::load(messages_dir).unwrap().unwrap();
+        assert_eq!(cache.project_id, 1);
+        assert_eq!(cache.translations.len(), 2);
+        assert!(cache.fetched_at > 0);
+
+        // 完整获取或其他项目替换整个缓存
+        FetchCache::record(messages_dir, 2, &single("fr", "title", "Accueil"), false).unwrap();
+        let cache = FetchCache::load(messages_dir).unwrap().unwrap();
+        assert_eq!(cache.translations, single("fr", "title", "Accueil"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Locale file size limit
+//!
+//! `"maxKeysPerFile": 500` caps how many keys one locale file may hold:
+//! `lint` reports every file over the limit, and `fmt --split` splits such a
+//! file one namespace level deeper. The keys of `en/common.json` below
+//! `common.auth` (or below `auth`, for keys outside the file's own
+//! namespace) move to `en/common/auth.json`, whose namespace `common.auth`
+//! then routes new keys of that group to it (see [`super::namespaces`]).
+//! Keys without a deeper namespace stay in the original file, which is
+//! removed once it is empty.
+//!
+//! Only JSON and YAML files are split, and only in the default
+//! one-directory-per-language layout.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::flatten::unflatten_object_with;
+use super::key_path::KeyPath;
+use super::namespaces::{file_namespace, key_in_namespace};
+use super::scanner::{file_language, language_dir, read_translation_file, resolve_file, FileFormat, Layout};
+use super::MessagesDir;
+
+/// 超出键数上限的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizedFile {
+    /// 文件路径
+    pub path: PathBuf,
+    /// 语言代码
+    pub lang: String,
+    /// 文件中的键数
+    pub keys: usize,
+}
+
+/// 单个文件的拆分计划
+#[derive(Debug, Clone, Default)]
+pub struct SplitPlan {
+    /// 被拆分的文件
+    pub source: PathBuf,
+    /// 拆分后原文件的内容（为空时删除原文件）
+    pub source_content: Option<String>,
+    /// 原文件中保留的键数
+    pub kept: usize,
+    /// 目标文件 -> (新内容, 移入的键数)
+    pub parts: BTreeMap<PathBuf, (String, usize)>,
+}
+
+/// 查找键数超过上限的文件
+///
+/// # Arguments
+///
+/// * `messages_dir` - messages 目录
+/// * `files` - 扫描到的文件（[`super::ScanResult::files`] 中的逻辑路径）
+/// * `max` - 每个文件的最大键数
+///
+/// # Errors
+///
+/// 文件无法读取或解析时返回错误
+pub fn oversized_files(messages_dir: &MessagesDir, files: &[PathBuf], max: usize) -> Result<Vec<OversizedFile>> {
+    let mut oversized = Vec::new();
+    for file in files {
+        let Some(lang) = file_language(messages_dir, file) else {
+            continue;
+        };
+        let path = resolve_file(messages_dir, file);
+        let keys = read_translation_file(messages_dir, &path)?.len();
+        if keys > max {
+            oversized.push(OversizedFile { path, lang, keys });
+        }
+    }
+    oversized.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(oversized)
+}
+
+/// 计划按命名空间拆分一个文件
+///
+/// 键按文件命名空间之下（键不属于文件命名空间时按键本身）的第一段分组，
+/// 每组写入 `<文件去掉扩展名>/<段>.<扩展名>`；目标文件已存在时合并进去。
+///
+/// # Errors
+///
+/// 平铺布局、非 JSON/YAML 文件，或没有可以拆分出去的键时返回错误
+pub fn plan_split(messages_dir: &MessagesDir, file: &OversizedFile) -> Result<SplitPlan> {
+    let path = &file.path;
+    if messages_dir.layout == Layout::FlatFiles {
+        return Err(anyhow::anyhow!(
+            "Cannot split {}: the flat-files layout keeps one file per language",
+            path.display()
+        ));
+    }
+    if !matches!(FileFormat::from_path(path), Some(FileFormat::Json | FileFormat::Yaml)) {
+        return Err(anyhow::anyhow!("Cannot split {}: only JSON and YAML files can be split", path.display()));
+    }
+    let format = messages_dir.detect(path).ok_or_else(|| anyhow::anyhow!("Unsupported translation file: {}", path.display()))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("json");
+
+    // 1. 按下一级命名空间分组
+    let relative = path.strip_prefix(language_dir(messages_dir, &file.lang)).unwrap_or(path);
+    let sep = &messages_dir.separator;
+    let namespace = file_namespace(relative, sep);
+    let mut groups: BTreeMap<String, HashMap<String, String>> = BTreeMap::new();
+    let mut kept = 0;
+    for (key, value) in read_translation_file(messages_dir, path)? {
+        let key_path = KeyPath::parse(&key, sep);
+        let rest = if key_in_namespace(&key_path, &namespace) {
+            key_path.strip_prefix(&namespace).unwrap_or_default()
+        } else {
+            key_path
+        };
+        let segments = rest.segments();
+        match segments.first() {
+            Some(segment) if segments.len() > 1 && is_file_name(segment, sep) => {
+                groups.entry(segment.clone()).or_default().insert(key, value);
+            }
+            _ => kept += 1,
+        }
+    }
+    if groups.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Cannot split {}: none of its keys has a namespace below '{}'",
+            path.display(),
+            namespace.to_key_with(sep)
+        ));
+    }
+
+    // 2. 生成目标文件内容
+    let mut plan = SplitPlan {
+        source: path.clone(),
+        kept,
+        ..Default::default()
+    };
+    let target_dir = path.with_extension("");
+    let mut moved = Vec::new();
+    for (segment, keys) in groups {
+        let target = target_dir.join(format!("{}.{}", segment, extension));
+        let content = if target.exists() {
+            let existing = std::fs::read_to_string(&target)
+                .with_context(|| format!("Failed to read file {}", target.display()))?;
+            format
+                .merge(&existing, &keys, true, sep)
+                .with_context(|| format!("Failed to parse {} {}", format.name(), target.display()))?
+                .ok_or_else(|| anyhow::anyhow!("{} is a read-only template", target.display()))?
+        } else {
+            serialize(&target, keys.clone(), sep)?
+        };
+        moved.extend(keys.keys().cloned());
+        plan.parts.insert(target, (content, keys.len()));
+    }
+
+    // 3. 从原文件中移除移走的键
+    if kept > 0 {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read file {}", path.display()))?;
+        plan.source_content = format
+            .remove(&content, &moved, sep)
+            .with_context(|| format!("Failed to parse {} {}", format.name(), path.display()))?;
+    }
+    Ok(plan)
+}
+
+/// 执行拆分计划
+///
+/// # Errors
+///
+/// 文件无法写入或删除时返回错误
+pub fn apply(plan: &SplitPlan) -> Result<()> {
+    for (target, (content, _)) in &plan.parts {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(target, content).with_context(|| format!("Failed to write file {}", target.display()))?;
+    }
+    match &plan.source_content {
+        Some(content) => std::fs::write(&plan.source, content),
+        None => std::fs::remove_file(&plan.source),
+    }
+    .with_context(|| format!("Failed to update {}", plan.source.display()))
+}
+
+/// 命名空间段能否作为文件名（文件名中的分隔符会改变文件的命名空间）
+fn is_file_name(segment: &str, separator: &str) -> bool {
+    !segment.is_empty()
+        && segment != ".."
+        && !segment.contains(separator)
+        && !segment.contains(['/', '\\'])
+}
+
+/// 将展平翻译序列化为新文件的内容
+fn serialize(path: &Path, keys: HashMap<String, String>, separator: &str) -> Result<String> {
+    let nested = unflatten_object_with(keys, separator);
+    match FileFormat::from_path(path) {
+        Some(FileFormat::Yaml) => serde_yaml::to_string(&nested).context("Failed to serialize translations to YAML"),
+        _ => serde_json::to_string_pretty(&nested).context("Failed to serialize translations to JSON"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_by_namespace() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        let en = messages_dir.join("en");
+        std::fs::create_dir_all(en.join("common")).unwrap();
+        let common = en.join("common.json");
+        std::fs::write(
+            &common,
+            r#"{"title": "Home", "common": {"auth": {"login": "Log in"}, "ok": "OK"}, "nav": {"home": "Home", "back": "Back"}}"#,
+        )
+        .unwrap();
+        std::fs::write(en.join("common/nav.json"), r#"{"nav": {"next": "Next"}}"#).unwrap();
+        std::fs::write(en.join("small.json"), r#"{"a": "A"}"#).unwrap();
+
+        let files = vec![PathBuf::from("en/common.json"), PathBuf::from("en/small.json")];
+        let oversized = oversized_files(&messages_dir, &files, 3).unwrap();
+        assert_eq!(oversized, vec![OversizedFile { path: common.clone(), lang: "en".to_string(), keys: 5 }]);
+
+        let plan = plan_split(&messages_dir, &oversized[0]).unwrap();
+        assert_eq!(plan.kept, 2);
+        assert_eq!(plan.parts.len(), 2);
+        apply(&plan).unwrap();
+
+        assert_eq!(
+            read_translation_file(&messages_dir, &common).unwrap(),
+            HashMap::from([("title".to_string(), "Home".to_string()), ("common.ok".to_string(), "OK".to_string())])
+        );
+        assert_eq!(
+            read_translation_file(&messages_dir, &en.join("common/auth.json")).unwrap(),
+            HashMap::from([("common.auth.login".to_string(), "Log in".to_string())])
+        );
+        // 已存在的目标文件被合并
+        assert_eq!(read_translation_file(&messages_dir, &en.join("common/nav.json")).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_split_removes_empty_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        let errors = messages_dir.join("de/errors.yaml");
+        std::fs::write(&errors, "errors:\n  network:\n    timeout: Zeitüberschreitung\n  auth:\n    denied: Verweigert\n").unwrap();
+
+        let file = OversizedFile { path: errors.clone(), lang: "de".to_string(), keys: 2 };
+        let plan = plan_split(&messages_dir, &file).unwrap();
+        assert!(plan.source_content.is_none());
+        apply(&plan).unwrap();
+        assert!(!errors.exists());
+        assert!(messages_dir.join("de/errors/network.yaml").exists());
+        assert!(messages_dir.join("de/errors/auth.yaml").exists());
+
+        std::fs::write(&errors, "errors:\n  title: Fehler\n").unwrap();
+        let file = OversizedFile { path: errors, lang: "de".to_string(), keys: 1 };
+        assert!(plan_split(&messages_dir, &file).unwrap_err().to_string().contains("has a namespace below"));
+    }
+}
@@ -0,0 +1,619 @@
+//! JSON flattening and unflattening module
+//!
+//! Provides high-performance functions to flatten nested JSON objects into
+//! single-level key-value pairs and vice versa. This is essential for
+//! translating nested locale files into a flat format for storage.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use serde_json::json;
+//!
+//! let input = json!({
+//!     "user": {
+//!         "name": "John",
+//!         "profile": {
+//!             "age": 30
+//!         }
+//!     }
+//! });
+//!
+//! let flat = flatten_object(&input, "");
+//! // {"user.name": "John", "user.profile.age": "30"}
+//!
+//! let nested = unflatten_object(flat);
+//! // {"user": {"name": "John", "profile": {"age": "30"}}}
+//! ```
+//!
+//! # Separator and escaping
+//!
+//! The separator defaults to `.` and can be changed with `keySeparator` in
+//! the config. It belongs to the messages directory
+//! ([`MessagesDir::separator`](super::MessagesDir::separator)), so code that
+//! reads or writes locale files uses the `_with` variants;
+//! [`flatten_object`] and [`unflatten_object`] always use `.`. Keys that
+//! contain the separator (or a backslash) are escaped with a backslash, so
+//! `{"key.with.dots": "v"}` flattens to `key\.with\.dots` and unflattens
+//! back to the same single key.
+//! [`KeyPath`](super::key_path::KeyPath) applies the same rules to whole keys
+//! for namespace and prefix matching.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 默认键分隔符
+pub const DEFAULT_SEPARATOR: &str = ".";
+
+/// 转义字符
+pub const ESCAPE: char = '\\';
+
+/// 转义键名片段中的分隔符和转义字符
+pub fn escape_segment(segment: &str, separator: &str) -> String {
+    let mut escaped = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix(separator) {
+            escaped.push(ESCAPE);
+            escaped.push_str(separator);
+            rest = after;
+        } else {
+            if c == ESCAPE {
+                escaped.push(ESCAPE);
+            }
+            escaped.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    escaped
+}
+
+/// 按未转义的分隔符拆分键名，并还原转义
+pub fn split_key(key: &str, separator: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut rest = key;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix(separator) {
+            parts.push(std::mem::take(&mut current));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(ESCAPE).filter(|after| !after.is_empty()) {
+            // 转义：下一个分隔符或字符原样保留
+            if let Some(after_separator) = after.strip_prefix(separator) {
+                current.push_str(separator);
+                rest = after_separator;
+            } else {
+                let next = after.chars().next().unwrap_or(ESCAPE);
+                current.push(next);
+                rest = &after[next.len_utf8()..];
+            }
+        } else {
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+/// 将嵌套的 JSON 对象展平为单层键值对
+///
+/// 嵌套的对象会被转换为点分键名（dot-separated keys）：
+/// - 输入: `{"user": {"name": "John"}}`
+/// - 输出: `{"user.name": "John"}`
+///
+/// 使用默认分隔符 `.`，其他分隔符见 [`flatten_object_with`]。
+///
+/// 只有字符串类型的值会被保留，其他类型（数字、布尔值、数组、null）会被忽略。
+///
+/// # Arguments
+///
+/// * `value` - 要展平的 JSON 值
+/// * `prefix` - 键名前缀，用于递归构建完整键名
+///
+/// # Returns
+///
+/// 展平后的键值对 HashMap
+///
+/// # Performance
+///
+/// 该函数使用预分配的 HashMap 和迭代器遍历，性能优于递归实现。
+/// 对于深度嵌套的结构，建议使用迭代器版本的实现。
+pub fn flatten_object(value: &Value, prefix: &str) -> HashMap<String, String> {
+    flatten_object_with(value, prefix, DEFAULT_SEPARATOR)
+}
+
+/// 使用指定分隔符展平 JSON 对象
+///
+/// 键名片段中出现的分隔符和反斜杠会被转义；`prefix` 应已转义。
+pub fn flatten_object_with(value: &Value, prefix: &str, separator: &str) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    flatten_recursive(value, prefix, separator, &mut result);
+    result
+}
+
+/// 递归展平辅助函数
+///
+/// 使用深度优先遍历将嵌套对象展平。
+/// 每次递归都会创建新的键名（通过拼接 prefix 和当前 key）。
+fn flatten_recursive(
+    value: &Value,
+    prefix: &str,
+    separator: &str,
+    result: &mut HashMap<String, String>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map {
+                // 构建新键名：如果有前缀则使用 "prefix.key" 格式，否则只用 "key"
+                let key = escape_segment(key, separator);
+                let new_key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}{}{}", prefix, separator, key)
+                };
+                // 递归处理嵌套值
+                flatten_recursive(val, &new_key, separator, result);
+            }
+        }
+        Value::String(s) => {
+            // 只有字符串类型才保留
+            result.insert(prefix.to_string(), s.clone());
+        }
+        // 忽略其他类型：数字、布尔值、数组、null
+        _ => {}
+    }
+}
+
+/// 将展平的键值对还原为嵌套的 JSON 对象
+///
+/// 是 `flatten_object` 的逆操作（默认分隔符 `.`）：
+/// - 输入: `{"user.name": "John"}`
+/// - 输出: `{"user": {"name": "John"}}`
+///
+/// # Arguments
+///
+/// * `flat` - 展平的键值对
+///
+/// # Returns
+///
+/// 嵌套的 JSON 对象
+///
+/// # Panics
+///
+/// 如果键名格式无效（如连续的点、开头或结尾的点），可能会导致意外行为。
+pub fn unflatten_object(flat: HashMap<String, String>) -> Value {
+    unflatten_object_with(flat, DEFAULT_SEPARATOR)
+}
+
+/// 使用指定分隔符还原嵌套 JSON 对象
+///
+/// 转义的分隔符不会拆分键名，是 [`flatten_object_with`] 的逆操作。
+pub fn unflatten_object_with(flat: HashMap<String, String>, separator: &str) -> Value {
+    let mut root = serde_json::Map::new();
+
+    for (key, value) in flat {
+        let parts = split_key(&key, separator);
+        let parts: Vec<&str> = parts.iter().map(|p| p.as_str()).collect();
+        insert_into_nested(&mut root, &parts, value);
+    }
+
+    Value::Object(root)
+}
+
+/// 递归插入到嵌套结构中
+///
+/// 根据键名片段路径，将值插入到嵌套的 JSON Map 中。
+/// 如果中间路径不存在，会自动创建空对象。
+fn insert_into_nested(
+    map: &mut serde_json::Map<String, Value>,
+    parts: &[&str],
+    value: String,
+) {
+    if parts.len() == 1 {
+        // 到达叶子节点，插入字符串值
+        map.insert(parts[0].to_string(), Value::String(value));
+    } else {
+        // 还有中间节点
+        let head = parts[0];
+        let tail = &parts[1..];
+
+        // 如果中间节点不存在，创建空对象
+        if !map.contains_key(head) {
+            map.insert(head.to_string(), Value::Object(serde_json::Map::new()));
+        }
+
+        // 递归插入到嵌套对象中
+        if let Some(Value::Object(nested_map)) = map.get_mut(head) {
+            insert_into_nested(nested_map, tail, value);
+        }
+    }
+}
+
+/// 将展平的翻译合并回原始嵌套结构
+///
+/// 只更新展平映射中存在的键，保留原始结构中的其他键（包括非字符串值），
+/// 使用默认分隔符 `.`，见 [`merge_flat_into`]。
+///
+/// # Arguments
+///
+/// * `original` - 原始嵌套对象
+/// * `flat_translations` - 要合并的展平翻译
+///
+/// # Returns
+///
+/// 合并后的嵌套对象
+pub fn merge_with_flat(
+    original: &Value,
+    flat_translations: HashMap<String, String>,
+) -> Value {
+    let mut merged = original.clone();
+    merge_flat_into(&mut merged, &flat_translations, DEFAULT_SEPARATOR);
+    merged
+}
+
+/// 将展平的翻译无损合并到嵌套结构中
+///
+/// 只覆盖已存在的字符串叶子，不存在的键会创建对应路径；
+/// 数字、布尔值、null、数组以及其他所有节点保持不变。
+/// 键指向非字符串值，或路径经过非对象节点时，该键被跳过。
+///
+/// # Returns
+///
+/// 被跳过的键（已排序）
+pub fn merge_flat_into(
+    target: &mut Value,
+    flat_translations: &HashMap<String, String>,
+    separator: &str,
+) -> Vec<String> {
+    // 根节点不是对象时（如空文件）以空对象开始
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+
+    let mut skipped: Vec<String> = flat_translations
+        .iter()
+        .filter(|(key, value)| !set_string_leaf(target, &split_key(key, separator), value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    skipped.sort();
+    skipped
+}
+
+/// 沿路径写入字符串叶子，无法无损写入时返回 `false`
+fn set_string_leaf(node: &mut Value, parts: &[String], value: &str) -> bool {
+    let (Value::Object(map), Some((head, tail))) = (node, parts.split_first()) else {
+        return false;
+    };
+
+    if tail.is_empty() {
+        return match map.get_mut(head) {
+            Some(Value::String(existing)) => {
+                *existing = value.to_string();
+                true
+            }
+            Some(_) => false,
+            None => {
+                map.insert(head.clone(), Value::String(value.to_string()));
+                true
+            }
+        };
+    }
+
+    let child = map
+        .entry(head.clone())
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    set_string_leaf(child, tail, value)
+}
+
+/// 按扁平键删除字符串叶子，因此变空的父级对象也一并删除
+///
+/// 其余节点保持不变；不存在或不是字符串的键被忽略。
+///
+/// # Returns
+///
+/// 实际删除的键数
+pub fn remove_flat_from(target: &mut Value, keys: &[String], separator: &str) -> usize {
+    keys.iter()
+        .filter(|key| remove_string_leaf(target, &split_key(key, separator)))
+        .count()
+}
+
+/// 沿路径删除字符串叶子，删除成功时返回 `true`
+fn remove_string_leaf(node: &mut Value, parts: &[String]) -> bool {
+    let (Value::Object(map), Some((head, tail))) = (node, parts.split_first()) else {
+        return false;
+    };
+
+    if tail.is_empty() {
+        if !matches!(map.get(head), Some(Value::String(_))) {
+            return false;
+        }
+        map.remove(head);
+        return true;
+    }
+
+    let Some(child) = map.get_mut(head) else {
+        return false;
+    };
+    let removed = remove_string_leaf(child, tail);
+    if removed && child.as_object().is_some_and(|object| object.is_empty()) {
+        map.remove(head);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten_simple_object() {
+        let input = json!({
+            "hello": "world"
+        });
+        let result = flatten_object(&input, "");
+        assert_eq!(result.get("hello"), Some(&"world".to_string()));
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_flatten_nested_object() {
+        let input = json!({
+            "user": {
+                "name": "John",
+                "profile": {
+                    "age": "30"
+                }
+            }
+        });
+        let result = flatten_object(&input, "");
+        assert_eq!(result.get("user.name"), Some(&"John".to_string()));
+        assert_eq!(result.get("user.profile.age"), Some(&"30".to_string()));
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_flatten_deeply_nested() {
+        let input = json!({
+            "a": {
+                "b": {
+                    "c": {
+                        "d": "deep"
+                    }
+                }
+            }
+        });
+        let result = flatten_object(&input, "");
+        assert_eq!(result.get("a.b.c.d"), Some(&"deep".to_string()));
+    }
+
+    #[test]
+    fn test_flatten_ignores_non_strings() {
+        let input = json!({
+            "string": "value",
+            "number": 123,
+            "bool": true,
+            "array": [1, 2, 3],
+            "null": null,
+            "nested": {
+                "inner": "secret"
+            }
+        });
+        let result = flatten_object(&input, "");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("string"), Some(&"value".to_string()));
+        assert_eq!(result.get("nested.inner"), Some(&"secret".to_string()));
+        // 其他类型应该被忽略
+        assert!(!result.contains_key("number"));
+        assert!(!result.contains_key("bool"));
+        assert!(!result.contains_key("array"));
+        assert!(!result.contains_key("null"));
+    }
+
+    #[test]
+    fn test_flatten_empty_object() {
+        let input = json!({});
+        let result = flatten_object(&input, "");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_flatten_empty_nested() {
+        let input = json!({
+            "empty": {},
+            "nonempty": "value"
+        });
+        let result = flatten_object(&input, "");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("nonempty"), Some(&"value".to_string()));
+    }
+
+    #[test]
+    fn test_unflatten_simple() {
+        let flat = HashMap::from([
+            ("hello".to_string(), "world".to_string()),
+        ]);
+        let result = unflatten_object(flat);
+        assert_eq!(result, json!({"hello": "world"}));
+    }
+
+    #[test]
+    fn test_unflatten_nested() {
+        let flat = HashMap::from([
+            ("user.name".to_string(), "John".to_string()),
+            ("user.profile.age".to_string(), "30".to_string()),
+        ]);
+        let result = unflatten_object(flat);
+        assert_eq!(
+            result,
+            json!({
+                "user": {
+                    "name": "John",
+                    "profile": {
+                        "age": "30"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let original = json!({
+            "user": {
+                "name": "John",
+                "profile": {
+                    "age": "30"
+                }
+            },
+            "greeting": "Hello"
+        });
+
+        let flat = flatten_object(&original, "");
+        let restored = unflatten_object(flat);
+
+        assert_eq!(original, restored);
+    }
+
+    #[test]
+    fn test_roundtrip_multiple_languages() {
+        let original = json!({
+            "en": {
+                "user": {
+                    "name": "John"
+                }
+            },
+            "zh": {
+                "user": {
+                    "name": "张三"
+                }
+            }
+        });
+
+        let flat = flatten_object(&original, "");
+        let restored = unflatten_object(flat);
+
+        assert_eq!(original, restored);
+    }
+
+    #[test]
+    fn test_merge_with_flat() {
+        let original = json!({
+            "user": {
+                "name": "Old Name",
+                "email": "old@example.com"
+            }
+        });
+
+        let updates = HashMap::from([
+            ("user.name".to_string(), "New Name".to_string()),
+        ]);
+
+        let result = merge_with_flat(&original, updates);
+        assert_eq!(
+            result,
+            json!({
+                "user": {
+                    "name": "New Name",
+                    "email": "old@example.com"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_preserves_non_string_values() {
+        let mut original = json!({
+            "title": "Old",
+            "limits": {"max": 10, "enabled": true, "none": null},
+            "list": ["a", "b"],
+            "count": 3,
+            "label": "x"
+        });
+        let updates = HashMap::from([
+            ("title".to_string(), "New".to_string()),
+            ("limits.max".to_string(), "20".to_string()),
+            ("label.nested".to_string(), "y".to_string()),
+            ("added.key".to_string(), "z".to_string()),
+        ]);
+
+        let skipped = merge_flat_into(&mut original, &updates, ".");
+        assert_eq!(skipped, vec!["label.nested".to_string(), "limits.max".to_string()]);
+        assert_eq!(
+            original,
+            json!({
+                "title": "New",
+                "limits": {"max": 10, "enabled": true, "none": null},
+                "list": ["a", "b"],
+                "count": 3,
+                "label": "x",
+                "added": {"key": "z"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_remove_flat_from() {
+        let mut original = json!({
+            "title": "Title",
+            "old": {"a": "A", "b": {"c": "C"}},
+            "user": {"name": "Name", "age": 3},
+            "dotted.key": "D"
+        });
+        let keys: Vec<String> = ["old.a", "old.b.c", "user.name", "user.age", "dotted\\.key", "missing.key"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+
+        assert_eq!(remove_flat_from(&mut original, &keys, "."), 4);
+        assert_eq!(original, json!({"title": "Title", "user": {"age": 3}}));
+    }
+
+    #[test]
+    fn test_special_characters_in_keys() {
+        let input = json!({
+            "key_with_underscore": "value1",
+            "key-with-dash": "value2",
+            "key.with.dots": "value3"
+        });
+        let result = flatten_object_with(&input, "", ".");
+        assert_eq!(result.get("key_with_underscore"), Some(&"value1".to_string()));
+        assert_eq!(result.get("key-with-dash"), Some(&"value2".to_string()));
+        assert_eq!(result.get(r"key\.with\.dots"), Some(&"value3".to_string()));
+        assert_eq!(unflatten_object_with(result, "."), input);
+    }
+
+    #[test]
+    fn test_roundtrip_escaped_keys() {
+        let original = json!({
+            "a.b": {"c\\d": "1", "e": {"f.": "2"}},
+            "plain": "3"
+        });
+        let flat = flatten_object_with(&original, "", ".");
+        assert_eq!(flat.get(r"a\.b.c\\d"), Some(&"1".to_string()));
+        assert_eq!(flat.get(r"a\.b.e.f\."), Some(&"2".to_string()));
+        assert_eq!(unflatten_object_with(flat, "."), original);
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let original = json!({"user": {"first.name": "John"}, "a/b": "x"});
+        let flat = flatten_object_with(&original, "", "/");
+        assert_eq!(flat.get("user/first.name"), Some(&"John".to_string()));
+        assert_eq!(flat.get(r"a\/b"), Some(&"x".to_string()));
+        assert_eq!(unflatten_object_with(flat, "/"), original);
+
+        let flat = flatten_object_with(&json!({"a": {"b": "c"}}), "", "::");
+        assert_eq!(flat.get("a::b"), Some(&"c".to_string()));
+    }
+
+    #[test]
+    fn test_split_key() {
+        assert_eq!(split_key("a.b.c", "."), vec!["a", "b", "c"]);
+        assert_eq!(split_key(r"a\.b.c", "."), vec!["a.b", "c"]);
+        assert_eq!(split_key(r"a\\.b", "."), vec![r"a\", "b"]);
+        assert_eq!(split_key("a", "."), vec!["a"]);
+        assert_eq!(escape_segment(r"x.y\z", "."), r"x\.y\\z");
+    }
+}
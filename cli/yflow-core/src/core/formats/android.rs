@@ -0,0 +1,649 @@
+//! Android string resources (`strings.xml`)
+//!
+//! Reads and writes the `<resources>` files of `values-<lang>/` directories:
+//!
+//! ```text
+//! <string name="title">Inbox</string>                    -> title
+//! <plurals name="messages">
+//!     <item quantity="one">%d message</item>             -> messages.one
+//!     <item quantity="other">%d messages</item>          -> messages.other
+//! </plurals>
+//! <string-array name="days"><item>Mon</item></string-array>  -> days.0
+//! ```
+//!
+//! Values are unescaped (`\'`, `\"`, `\n`, `\uXXXX`, XML entities); inline
+//! markup such as `<b>` or `<xliff:g>` is kept as written. Strings marked
+//! `translatable="false"` are neither read nor overwritten.
+//!
+//! Writing only touches the values that changed, so comments, attributes
+//! and other resources (`<color>`, `<integer>`) are kept byte for byte. New
+//! keys become `<string>` elements at the end of the file, new plural forms
+//! are added to their `<plurals>`; array items can only be updated.
+
+use anyhow::{Context, Result};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use tracing::warn;
+
+use super::mobile::{indentation, splice, whole_lines};
+use super::registry::TranslationFormat;
+use crate::core::flatten::DEFAULT_SEPARATOR;
+
+/// 新文件的内容
+const EMPTY_DOCUMENT: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n</resources>\n";
+
+/// 复数形式的 quantity 取值
+const QUANTITIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// 子元素的缩进
+const INDENT: &str = "    ";
+
+/// Android `strings.xml` 格式
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AndroidStrings;
+
+/// 资源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `<string>`
+    String,
+    /// `<plurals>`
+    Plurals,
+    /// `<string-array>`
+    Array,
+}
+
+/// 一个值（`<string>` 本身，或复数/数组中的 `<item>`）
+#[derive(Debug, Clone)]
+struct Item {
+    /// quantity 或数组下标（`<string>` 为空）
+    id: String,
+    /// 解码后的值
+    value: String,
+    /// 元素的字节范围
+    span: Range<usize>,
+    /// 元素内容的字节范围（空元素 `<string/>` 为 `None`）
+    inner: Option<Range<usize>>,
+}
+
+/// 一个资源元素
+#[derive(Debug, Clone)]
+struct Resource {
+    /// `name` 属性
+    name: String,
+    /// 资源类型
+    kind: Kind,
+    /// 是否需要翻译（`translatable="false"` 时为 `false`）
+    translatable: bool,
+    /// 元素的字节范围
+    span: Range<usize>,
+    /// 复数/数组结束标签的位置
+    close: Option<usize>,
+    /// 值
+    items: Vec<Item>,
+}
+
+impl Resource {
+    /// 每个值对应的键
+    fn keys<'a>(&'a self, sep: &'a str) -> impl Iterator<Item = (String, &'a Item)> + 'a {
+        self.items.iter().map(move |item| match self.kind {
+            Kind::String => (self.name.clone(), item),
+            _ => (format!("{}{}{}", self.name, sep, item.id), item),
+        })
+    }
+}
+
+/// 解析后的文件
+#[derive(Debug, Default)]
+struct Document {
+    /// 资源元素（按文件顺序）
+    resources: Vec<Resource>,
+    /// `</resources>` 的位置
+    close: Option<usize>,
+    /// 空的 `<resources/>` 元素的范围
+    empty_root: Option<Range<usize>>,
+}
+
+impl TranslationFormat for AndroidStrings {
+    fn name(&self) -> &str {
+        "Android strings"
+    }
+
+    fn read_flat(&self, content: &str, separator: &str) -> Result<HashMap<String, String>> {
+        Ok(parse(content)?
+            .resources
+            .iter()
+            .filter(|resource| resource.translatable)
+            .flat_map(|resource| resource.keys(separator).map(|(key, item)| (key, item.value.clone())).collect::<Vec<_>>())
+            .collect())
+    }
+
+    fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        _force: bool,
+        separator: &str,
+    ) -> Result<Option<String>> {
+        let content = if content.trim().is_empty() { EMPTY_DOCUMENT } else { content };
+        let document = parse(content)?;
+
+        // 1. 修改已有的值，收集新键
+        let mut edits = Vec::new();
+        let mut new_strings = Vec::new();
+        let mut new_items: BTreeMap<usize, Vec<(&str, &str)>> = BTreeMap::new();
+        let mut keys: Vec<(&String, &String)> = translations.iter().collect();
+        keys.sort();
+        for (key, value) in keys {
+            if let Some(resource) = document.resources.iter().find(|r| r.kind == Kind::String && r.name == *key) {
+                if resource.translatable && resource.items[0].value != *value {
+                    edits.push(replace_item(resource, &resource.items[0], value));
+                }
+                continue;
+            }
+            let group = key.rsplit_once(separator).and_then(|(name, id)| {
+                document
+                    .resources
+                    .iter()
+                    .enumerate()
+                    .find(|(_, r)| r.kind != Kind::String && r.name == name)
+                    .map(|(index, resource)| (index, resource, id))
+            });
+            match group {
+                Some((_, resource, _)) if !resource.translatable => {}
+                Some((index, resource, id)) => match resource.items.iter().find(|item| item.id == id) {
+                    Some(item) if item.value != *value => edits.push(replace_item(resource, item, value)),
+                    Some(_) => {}
+                    None if resource.kind == Kind::Plurals && QUANTITIES.contains(&id) => {
+                        new_items.entry(index).or_default().push((id, value));
+                    }
+                    None => warn!("Skipped {}: {} has no such item", key, resource.name),
+                },
+                None => new_strings.push(render_string(key, value)),
+            }
+        }
+
+        // 2. 新的复数形式加到所属的 <plurals> 中
+        for (index, items) in new_items {
+            let resource = &document.resources[index];
+            let lines: Vec<String> = items.iter().map(|(id, value)| render_item(Kind::Plurals, id, value)).collect();
+            match resource.close {
+                Some(close) => edits.push(insert_lines(content, close, &lines)),
+                None => {
+                    let indent = indentation(content, resource.span.start);
+                    edits.push((resource.span.clone(), render_group(resource, &lines, indent)));
+                }
+            }
+        }
+
+        // 3. 新键作为 <string> 加到文件末尾
+        if !new_strings.is_empty() {
+            match (document.close, &document.empty_root) {
+                (Some(close), _) => edits.push(insert_lines(content, close, &new_strings)),
+                (None, Some(span)) => {
+                    let body: String = new_strings.iter().map(|line| format!("{}{}\n", INDENT, line)).collect();
+                    edits.push((span.clone(), format!("<resources>\n{}</resources>", body)));
+                }
+                (None, None) => return Err(anyhow::anyhow!("Missing <resources> element")),
+            }
+        }
+
+        Ok(Some(splice(content, edits)))
+    }
+
+    fn remove(&self, content: &str, keys: &[String], separator: &str) -> Result<Option<String>> {
+        let document = parse(content)?;
+        let keys: HashSet<&str> = keys.iter().map(String::as_str).collect();
+        let mut edits = Vec::new();
+        for resource in document.resources.iter().filter(|r| r.translatable) {
+            let removed: Vec<&Item> = resource
+                .keys(separator)
+                .filter(|(key, _)| keys.contains(key.as_str()))
+                .map(|(_, item)| item)
+                .collect();
+            if removed.is_empty() {
+                continue;
+            }
+            if removed.len() == resource.items.len() {
+                edits.push((whole_lines(content, resource.span.clone()), String::new()));
+            } else {
+                edits.extend(removed.iter().map(|item| (whole_lines(content, item.span.clone()), String::new())));
+            }
+        }
+        Ok(Some(splice(content, edits)))
+    }
+
+    /// 新语言不保留空值（空字符串会覆盖默认语言），只复制不需要翻译的资源
+    fn scaffold(&self, content: &str, _lang: &str, fill_source: bool) -> Result<Option<String>> {
+        if fill_source {
+            return Ok(Some(content.to_string()));
+        }
+        let keys: Vec<String> = self.read_flat(content, DEFAULT_SEPARATOR)?.into_keys().collect();
+        self.remove(content, &keys, DEFAULT_SEPARATOR)
+    }
+}
+
+/// 解析文件
+fn parse(content: &str) -> Result<Document> {
+    let mut reader = Reader::from_str(content);
+    let mut document = Document::default();
+    let mut in_root = false;
+    loop {
+        let position = reader.buffer_position() as usize;
+        let event = reader.read_event().with_context(|| format!("Invalid XML at byte {}", position))?;
+        match event {
+            Event::Start(e) if !in_root => {
+                expect_root(&e)?;
+                in_root = true;
+            }
+            Event::Empty(e) if !in_root => {
+                expect_root(&e)?;
+                document.empty_root = Some(position..reader.buffer_position() as usize);
+            }
+            Event::Start(e) => {
+                let tag = e.local_name().as_ref().to_vec();
+                let kind = match tag.as_slice() {
+                    b"string" => Kind::String,
+                    b"plurals" => Kind::Plurals,
+                    b"string-array" => Kind::Array,
+                    _ => {
+                        reader.read_to_end(e.name())?;
+                        continue;
+                    }
+                };
+                let (name, translatable) = resource_attributes(&e)?;
+                let mut resource = Resource {
+                    name,
+                    kind,
+                    translatable,
+                    span: position..position,
+                    close: None,
+                    items: Vec::new(),
+                };
+                if kind == Kind::String {
+                    let inner_start = reader.buffer_position() as usize;
+                    let (value, inner_end) = read_value(&mut reader)?;
+                    resource.items.push(Item {
+                        id: String::new(),
+                        value,
+                        span: position..reader.buffer_position() as usize,
+                        inner: Some(inner_start..inner_end),
+                    });
+                } else {
+                    read_items(&mut reader, &mut resource)?;
+                }
+                resource.span.end = reader.buffer_position() as usize;
+                document.resources.push(resource);
+            }
+            Event::Empty(e) => {
+                let kind = match e.local_name().as_ref() {
+                    b"string" => Kind::String,
+                    b"plurals" => Kind::Plurals,
+                    b"string-array" => Kind::Array,
+                    _ => continue,
+                };
+                let (name, translatable) = resource_attributes(&e)?;
+                let span = position..reader.buffer_position() as usize;
+                let items = match kind {
+                    Kind::String => vec![Item {
+                        id: String::new(),
+                        value: String::new(),
+                        span: span.clone(),
+                        inner: None,
+                    }],
+                    _ => Vec::new(),
+                };
+                document.resources.push(Resource {
+                    name,
+                    kind,
+                    translatable,
+                    span,
+                    close: None,
+                    items,
+                });
+            }
+            Event::End(_) => {
+                document.close = Some(position);
+                in_root = false;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(document)
+}
+
+/// 检查根元素是否为 `<resources>`
+fn expect_root(element: &BytesStart) -> Result<()> {
+    if element.local_name().as_ref() != b"resources" {
+        return Err(anyhow::anyhow!(
+            "Expected a <resources> element, found <{}>",
+            String::from_utf8_lossy(element.name().as_ref())
+        ));
+    }
+    Ok(())
+}
+
+/// 读取资源的 `name` 和 `translatable` 属性
+fn resource_attributes(element: &BytesStart) -> Result<(String, bool)> {
+    let mut name = None;
+    let mut translatable = true;
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        match attribute.key.local_name().as_ref() {
+            b"name" => name = Some(attribute.unescape_value()?.to_string()),
+            b"translatable" => translatable = attribute.unescape_value()? != "false",
+            _ => {}
+        }
+    }
+    let name = name.ok_or_else(|| {
+        anyhow::anyhow!("<{}> without a name attribute", String::from_utf8_lossy(element.name().as_ref()))
+    })?;
+    Ok((name, translatable))
+}
+
+/// 读取复数/数组中的 `<item>`，直到结束标签
+fn read_items(reader: &mut Reader<&[u8]>, resource: &mut Resource) -> Result<()> {
+    loop {
+        let position = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let id = item_id(&e, resource)?;
+                let inner_start = reader.buffer_position() as usize;
+                let (value, inner_end) = read_value(reader)?;
+                resource.items.push(Item {
+                    id,
+                    value,
+                    span: position..reader.buffer_position() as usize,
+                    inner: Some(inner_start..inner_end),
+                });
+            }
+            Event::Empty(e) => {
+                let id = item_id(&e, resource)?;
+                resource.items.push(Item {
+                    id,
+                    value: String::new(),
+                    span: position..reader.buffer_position() as usize,
+                    inner: None,
+                });
+            }
+            Event::End(_) => {
+                resource.close = Some(position);
+                return Ok(());
+            }
+            Event::Eof => return Err(anyhow::anyhow!("Unexpected end of file in <{}>", resource.name)),
+            _ => {}
+        }
+    }
+}
+
+/// `<item>` 的标识：复数为 quantity，数组为下标
+fn item_id(element: &BytesStart, resource: &Resource) -> Result<String> {
+    if resource.kind == Kind::Array {
+        return Ok(resource.items.len().to_string());
+    }
+    element
+        .try_get_attribute("quantity")?
+        .map(|attribute| attribute.unescape_value().map(|value| value.to_string()))
+        .transpose()?
+        .ok_or_else(|| anyhow::anyhow!("<item> without a quantity in <plurals name=\"{}\">", resource.name))
+}
+
+/// 读取元素内容直到其结束标签，返回解码后的值和结束标签的位置
+///
+/// 内嵌的标记（`<b>`、`<xliff:g>`）原样保留。
+fn read_value(reader: &mut Reader<&[u8]>) -> Result<(String, usize)> {
+    let mut value = String::new();
+    let mut depth = 0;
+    loop {
+        let position = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Text(text) => value.push_str(&unescape(&text.unescape()?)),
+            Event::CData(text) => value.push_str(&String::from_utf8_lossy(&text)),
+            Event::Start(e) => {
+                depth += 1;
+                value.push_str(&format!("<{}>", String::from_utf8_lossy(&e)));
+            }
+            Event::Empty(e) => value.push_str(&format!("<{}/>", String::from_utf8_lossy(&e))),
+            Event::End(_) if depth == 0 => return Ok((value, position)),
+            Event::End(e) => {
+                depth -= 1;
+                value.push_str(&format!("</{}>", String::from_utf8_lossy(e.name().as_ref())));
+            }
+            Event::Eof => return Err(anyhow::anyhow!("Unexpected end of file")),
+            _ => {}
+        }
+    }
+}
+
+/// 替换一个值：有内容范围时只替换内容，否则替换整个元素
+fn replace_item(resource: &Resource, item: &Item, value: &str) -> (Range<usize>, String) {
+    match &item.inner {
+        Some(inner) => (inner.clone(), escape_value(value)),
+        None if resource.kind == Kind::String => (item.span.clone(), render_string(&resource.name, value)),
+        None => (item.span.clone(), render_item(resource.kind, &item.id, value)),
+    }
+}
+
+/// 在结束标签前插入若干行（比结束标签多一级缩进）
+fn insert_lines(content: &str, close: usize, lines: &[String]) -> (Range<usize>, String) {
+    let indent = indentation(content, close);
+    let line_start = content[..close].rfind('\n').map_or(0, |i| i + 1);
+    // 结束标签前还有其他内容时先换行
+    let inline = !content[line_start..close].trim().is_empty();
+    let mut inserted = String::new();
+    if inline {
+        inserted.push('\n');
+    }
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 || inline {
+            inserted.push_str(indent);
+        }
+        inserted.push_str(INDENT);
+        inserted.push_str(line);
+        inserted.push('\n');
+    }
+    inserted.push_str(indent);
+    (close..close, inserted)
+}
+
+/// 生成 `<string>` 元素
+fn render_string(name: &str, value: &str) -> String {
+    format!("<string name=\"{}\">{}</string>", escape(name), escape_value(value))
+}
+
+/// 生成复数/数组中的 `<item>`
+fn render_item(kind: Kind, id: &str, value: &str) -> String {
+    match kind {
+        Kind::Plurals => format!("<item quantity=\"{}\">{}</item>", escape(id), escape_value(value)),
+        _ => format!("<item>{}</item>", escape_value(value)),
+    }
+}
+
+/// 用给定的 `<item>` 行生成完整的复数/数组元素
+fn render_group(resource: &Resource, lines: &[String], indent: &str) -> String {
+    let tag = if resource.kind == Kind::Plurals { "plurals" } else { "string-array" };
+    let mut rendered = format!("<{} name=\"{}\">\n", tag, escape(&resource.name));
+    for line in lines {
+        rendered.push_str(&format!("{}{}{}\n", indent, INDENT, line));
+    }
+    rendered.push_str(&format!("{}</{}>", indent, tag));
+    rendered
+}
+
+/// 解码 Android 转义（`\'`、`\"`、`\n`、`\t`、`\uXXXX`），未转义的双引号只用于界定，不属于值
+fn unescape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(decoded) => value.push(decoded),
+                        None => value.push_str(&format!("\\u{}", hex)),
+                    }
+                }
+                Some(other) => value.push(other),
+                None => value.push('\\'),
+            },
+            '"' => {}
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+/// 编码值：Android 转义和 XML 转义，内嵌的标记保持不变
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut in_tag = false;
+    for (index, c) in value.char_indices() {
+        if in_tag {
+            escaped.push(c);
+            in_tag = c != '>';
+            continue;
+        }
+        match c {
+            '<' if starts_tag(&value[index + 1..]) => {
+                escaped.push(c);
+                in_tag = true;
+            }
+            '<' => escaped.push_str("&lt;"),
+            '&' => escaped.push_str("&amp;"),
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '@' | '?' if index == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `<` 之后是否为标签（`b>`、`/b>`、`xliff:g id="x">`）
+fn starts_tag(rest: &str) -> bool {
+    let name = rest.strip_prefix('/').unwrap_or(rest);
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) && rest.contains('>')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<resources xmlns:xliff="urn:oasis:names:tc:xliff:document:1.2">
+    <!-- Inbox -->
+    <string name="title">Inbox</string>
+    <string name="greeting">Hello, <xliff:g id="name">%1$s</xliff:g>!</string>
+    <string name="quote">Don\'t say \"hi\" &amp; leave\nnow</string>
+    <string name="app_id" translatable="false">com.example</string>
+    <color name="accent">#ff0000</color>
+    <plurals name="messages">
+        <item quantity="one">%d message</item>
+        <item quantity="other">%d messages</item>
+    </plurals>
+    <string-array name="days">
+        <item>Mon</item>
+        <item>Tue</item>
+    </string-array>
+    <string name="empty"/>
+</resources>
+"#;
+
+    #[test]
+    fn test_read_flat() {
+        let flat = AndroidStrings.read_flat(SAMPLE, ".").unwrap();
+        assert_eq!(flat["title"], "Inbox");
+        assert_eq!(flat["greeting"], "Hello, <xliff:g id=\"name\">%1$s</xliff:g>!");
+        assert_eq!(flat["quote"], "Don't say \"hi\" & leave\nnow");
+        assert_eq!(flat["messages.one"], "%d message");
+        assert_eq!(flat["days.1"], "Tue");
+        assert_eq!(flat["empty"], "");
+        assert_eq!(AndroidStrings.read_flat(SAMPLE, "/").unwrap()["messages/one"], "%d message");
+        assert!(!flat.contains_key("app_id"));
+        assert!(!flat.contains_key("accent"));
+        assert_eq!(flat.len(), 8);
+    }
+
+    #[test]
+    fn test_merge_keeps_untouched_content() {
+        let translations = HashMap::from([
+            ("title".to_string(), "Posteingang".to_string()),
+            ("quote".to_string(), "Don't say \"hi\" & leave\nnow".to_string()),
+            ("messages.few".to_string(), "%d zprávy".to_string()),
+            ("app_id".to_string(), "ignored".to_string()),
+            ("settings.title".to_string(), "@Settings <3".to_string()),
+        ]);
+        let merged = AndroidStrings.merge(SAMPLE, &translations, false, ".").unwrap().unwrap();
+        assert!(merged.contains("    <string name=\"title\">Posteingang</string>\n"));
+        assert!(merged.contains(r#"<string name="quote">Don\'t say \"hi\" &amp; leave\nnow</string>"#));
+        assert!(merged.contains(
+            "        <item quantity=\"other\">%d messages</item>\n        <item quantity=\"few\">%d zprávy</item>\n    </plurals>"
+        ));
+        assert!(merged.contains("<string name=\"app_id\" translatable=\"false\">com.example</string>"));
+        assert!(merged.ends_with(
+            "    <string name=\"empty\"/>\n    <string name=\"settings.title\">\\@Settings &lt;3</string>\n</resources>\n"
+        ));
+
+        let flat = AndroidStrings.read_flat(&merged, ".").unwrap();
+        for (key, value) in &translations {
+            if key != "app_id" {
+                assert_eq!(&flat[key], value, "{}", key);
+            }
+        }
+
+        // 没有变化时内容不变
+        let unchanged = AndroidStrings.merge(SAMPLE, &AndroidStrings.read_flat(SAMPLE, ".").unwrap(), false, ".").unwrap();
+        assert_eq!(unchanged.as_deref(), Some(SAMPLE));
+    }
+
+    #[test]
+    fn test_merge_into_new_file() {
+        let translations = HashMap::from([("title".to_string(), "Inbox".to_string())]);
+        let created = AndroidStrings.merge("", &translations, false, ".").unwrap().unwrap();
+        assert_eq!(
+            created,
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n    <string name=\"title\">Inbox</string>\n</resources>\n"
+        );
+        let from_empty_root = AndroidStrings.merge("<resources/>\n", &translations, false, ".").unwrap().unwrap();
+        assert_eq!(AndroidStrings.read_flat(&from_empty_root, ".").unwrap(), translations);
+    }
+
+    #[test]
+    fn test_remove_and_scaffold() {
+        let keys = ["title".to_string(), "messages.one".to_string(), "days.0".to_string(), "days.1".to_string()];
+        let removed = AndroidStrings
+            .remove(SAMPLE, &keys, ".")
+            .unwrap()
+            .unwrap();
+        assert!(!removed.contains(">Inbox<"));
+        assert!(!removed.contains("%d message<"));
+        assert!(removed.contains("    <plurals name=\"messages\">\n        <item quantity=\"other\">"));
+        assert!(!removed.contains("string-array"));
+        assert!(removed.contains("    <!-- Inbox -->\n    <string name=\"greeting\">"));
+
+        let scaffold = AndroidStrings.scaffold(SAMPLE, "de", false).unwrap().unwrap();
+        assert!(AndroidStrings.read_flat(&scaffold, ".").unwrap().is_empty());
+        assert!(scaffold.contains("app_id"));
+        assert!(scaffold.contains("<color name=\"accent\">"));
+    }
+
+    #[test]
+    fn test_invalid_documents() {
+        assert!(AndroidStrings.read_flat("<manifest/>", ".").is_err());
+        assert!(AndroidStrings.read_flat("<resources><string>x</string></resources>", ".").is_err());
+        assert!(AndroidStrings.read_flat("<resources><string name=\"a\">x</resources>", ".").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! iOS/macOS strings files (`Localizable.strings`)
+//!
+//! Reads and writes the `"key" = "value";` files of `<lang>.lproj/`
+//! directories. `/* ... */` and `//` comments are allowed anywhere; a
+//! comment directly above an entry belongs to it and is removed with it.
+//! Values are unescaped (`\"`, `\\`, `\n`, `\t`, `\r`, `\U` + 4 hex digits,
+//! including surrogate pairs).
+//!
+//! Writing only replaces the values that changed and appends new keys at
+//! the end, so comments and ordering are kept. Only UTF-8 files are
+//! supported (Xcode's default); `.stringsdict` plural files are not read.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use super::mobile::{splice, whole_lines};
+use super::registry::TranslationFormat;
+use crate::core::flatten::DEFAULT_SEPARATOR;
+
+/// iOS `.strings` 格式
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IosStrings;
+
+/// 一个条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    /// 键
+    key: String,
+    /// 解码后的值
+    value: String,
+    /// 值（含引号）的字节范围
+    value_span: Range<usize>,
+    /// 条目（含紧邻的前置注释）的字节范围
+    span: Range<usize>,
+}
+
+impl TranslationFormat for IosStrings {
+    fn name(&self) -> &str {
+        "iOS strings"
+    }
+
+    fn read_flat(&self, content: &str, _separator: &str) -> Result<HashMap<String, String>> {
+        Ok(parse(content)?.into_iter().map(|entry| (entry.key, entry.value)).collect())
+    }
+
+    fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        _force: bool,
+        _separator: &str,
+    ) -> Result<Option<String>> {
+        let entries = parse(content)?;
+
+        // 1. 修改已有的值（重复的键以最后一个为准）
+        let mut edits = Vec::new();
+        let mut new_keys = Vec::new();
+        for (key, value) in translations {
+            match entries.iter().rev().find(|entry| entry.key == *key) {
+                Some(entry) if entry.value != *value => edits.push((entry.value_span.clone(), quote(value))),
+                Some(_) => {}
+                None => new_keys.push((key, value)),
+            }
+        }
+        let mut merged = splice(content, edits);
+
+        // 2. 新键加到文件末尾
+        new_keys.sort();
+        if !new_keys.is_empty() && !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        for (key, value) in new_keys {
+            merged.push_str(&format!("{} = {};\n", quote(key), quote(value)));
+        }
+        Ok(Some(merged))
+    }
+
+    fn remove(&self, content: &str, keys: &[String], _separator: &str) -> Result<Option<String>> {
+        let keys: HashSet<&str> = keys.iter().map(String::as_str).collect();
+        let edits = parse(content)?
+            .into_iter()
+            .filter(|entry| keys.contains(entry.key.as_str()))
+            .map(|entry| (whole_lines(content, entry.span), String::new()))
+            .collect();
+        Ok(Some(splice(content, edits)))
+    }
+
+    /// 新语言不保留空值（空字符串会覆盖默认语言），只保留注释
+    fn scaffold(&self, content: &str, _lang: &str, fill_source: bool) -> Result<Option<String>> {
+        if fill_source {
+            return Ok(Some(content.to_string()));
+        }
+        let keys: Vec<String> = self.read_flat(content, DEFAULT_SEPARATOR)?.into_keys().collect();
+        self.remove(content, &keys, DEFAULT_SEPARATOR)
+    }
+}
+
+/// 解析文件
+fn parse(content: &str) -> Result<Vec<Entry>> {
+    let bytes = content.as_bytes();
+    let mut entries = Vec::new();
+    let mut position = 0;
+    // 紧邻下一个条目的注释的起始位置
+    let mut comment_start = None;
+    loop {
+        let skipped = skip_whitespace(content, position);
+        if content[position..skipped].matches('\n').count() > 1 {
+            comment_start = None;
+        }
+        position = skipped;
+        if position >= bytes.len() {
+            break;
+        }
+
+        // 注释
+        if content[position..].starts_with("/*") {
+            let end = content[position + 2..]
+                .find("*/")
+                .map(|i| position + 2 + i + 2)
+                .ok_or_else(|| error(content, position, "unterminated comment"))?;
+            comment_start.get_or_insert(position);
+            position = end;
+            continue;
+        }
+        if content[position..].starts_with("//") {
+            comment_start.get_or_insert(position);
+            position = content[position..].find('\n').map_or(bytes.len(), |i| position + i);
+            continue;
+        }
+
+        // "key" = "value";
+        let start = position;
+        let (key, after_key) = read_token(content, position)?;
+        position = skip_whitespace(content, after_key);
+        if bytes.get(position) != Some(&b'=') {
+            return Err(error(content, position, "expected '='"));
+        }
+        position = skip_whitespace(content, position + 1);
+        if bytes.get(position) != Some(&b'"') {
+            return Err(error(content, position, "expected a quoted value"));
+        }
+        let (value, after_value) = read_quoted(content, position)?;
+        let value_span = position..after_value;
+        position = skip_whitespace(content, after_value);
+        if bytes.get(position) != Some(&b';') {
+            return Err(error(content, position, "expected ';'"));
+        }
+        position += 1;
+        entries.push(Entry {
+            key,
+            value,
+            value_span,
+            span: comment_start.take().unwrap_or(start)..position,
+        });
+    }
+    Ok(entries)
+}
+
+/// 跳过空白，返回下一个非空白字符的位置
+fn skip_whitespace(content: &str, position: usize) -> usize {
+    let rest = &content[position..];
+    position + (rest.len() - rest.trim_start().len())
+}
+
+/// 读取键：带引号的字符串或不带引号的标识符
+fn read_token(content: &str, position: usize) -> Result<(String, usize)> {
+    if content[position..].starts_with('"') {
+        return read_quoted(content, position);
+    }
+    let rest = &content[position..];
+    let length = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '$')))
+        .unwrap_or(rest.len());
+    if length == 0 {
+        return Err(error(content, position, "expected a key"));
+    }
+    Ok((rest[..length].to_string(), position + length))
+}
+
+/// 读取带引号的字符串，返回解码后的值和结束引号之后的位置
+fn read_quoted(content: &str, position: usize) -> Result<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = content[position + 1..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, position + 1 + offset + 1)),
+            '\\' => match chars.next().map(|(_, escaped)| escaped) {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => value.push('\r'),
+                Some('0') => value.push('\0'),
+                Some('U') | Some('u') => {
+                    let mut units = vec![read_hex(&mut chars, content, position)?];
+                    // 代理对：高位之后是另一个 \U 转义
+                    if (0xD800..0xDC00).contains(&units[0]) {
+                        let rest = chars.as_str();
+                        if rest.starts_with("\\U") || rest.starts_with("\\u") {
+                            chars.next();
+                            chars.next();
+                            units.push(read_hex(&mut chars, content, position)?);
+                        }
+                    }
+                    for decoded in char::decode_utf16(units) {
+                        value.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                }
+                Some(other) => value.push(other),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+    Err(error(content, position, "unterminated string"))
+}
+
+/// 读取 4 位十六进制数
+fn read_hex(chars: &mut std::str::CharIndices, content: &str, position: usize) -> Result<u16> {
+    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+    u16::from_str_radix(&hex, 16).map_err(|_| error(content, position, "invalid \\U escape"))
+}
+
+/// 编码为带引号的字符串
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// 带行号的解析错误
+fn error(content: &str, position: usize, message: &str) -> anyhow::Error {
+    let line = content[..position.min(content.len())].matches('\n').count() + 1;
+    anyhow::anyhow!("line {}: {}", line, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"/* Generated by Xcode */
+
+/* Inbox title */
+"inbox.title" = "Inbox";
+"greeting" = "Hello, \"%@\"!\nWelcome";
+// Emoji
+"emoji" = "\UD83D\UDE00 \U00E9";
+NSCameraUsageDescription = "Scan receipts";
+"#;
+
+    #[test]
+    fn test_read_flat() {
+        let flat = IosStrings.read_flat(SAMPLE, ".").unwrap();
+        assert_eq!(flat["inbox.title"], "Inbox");
+        assert_eq!(flat["greeting"], "Hello, \"%@\"!\nWelcome");
+        assert_eq!(flat["emoji"], "😀 é");
+        assert_eq!(flat["NSCameraUsageDescription"], "Scan receipts");
+        assert_eq!(flat.len(), 4);
+    }
+
+    #[test]
+    fn test_merge_and_remove() {
+        let translations = HashMap::from([
+            ("inbox.title".to_string(), "Posteingang".to_string()),
+            ("greeting".to_string(), "Hello, \"%@\"!\nWelcome".to_string()),
+            ("settings".to_string(), "Ein\\stellungen".to_string()),
+        ]);
+        let merged = IosStrings.merge(SAMPLE, &translations, false, ".").unwrap().unwrap();
+        assert!(merged.contains("/* Inbox title */\n\"inbox.title\" = \"Posteingang\";\n"));
+        assert!(merged.contains(r#""greeting" = "Hello, \"%@\"!\nWelcome";"#));
+        assert!(merged.ends_with("= \"Scan receipts\";\n\"settings\" = \"Ein\\\\stellungen\";\n"));
+        assert_eq!(IosStrings.read_flat(&merged, ".").unwrap()["settings"], "Ein\\stellungen");
+
+        let removed = IosStrings
+            .remove(&merged, &["inbox.title".to_string(), "emoji".to_string()], ".")
+            .unwrap()
+            .unwrap();
+        assert!(removed.starts_with("/* Generated by Xcode */\n\n\"greeting\""));
+        assert!(!removed.contains("Emoji"));
+        assert_eq!(IosStrings.read_flat(&removed, ".").unwrap().len(), 3);
+
+        let created = IosStrings.merge("", &translations, false, ".").unwrap().unwrap();
+        assert_eq!(IosStrings.read_flat(&created, ".").unwrap(), translations);
+
+        let scaffold = IosStrings.scaffold(SAMPLE, "de", false).unwrap().unwrap();
+        assert_eq!(scaffold, "/* Generated by Xcode */\n\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = IosStrings.read_flat("\"a\" = \"b\";\n\"c\" \"d\";", ".").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected '='");
+        assert!(IosStrings.read_flat("\"a\" = \"b\"", ".").is_err());
+        assert!(IosStrings.read_flat("/* open", ".").is_err());
+    }
+}
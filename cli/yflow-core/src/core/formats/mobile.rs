@@ -0,0 +1,204 @@
+//! Mobile resource layouts
+//!
+//! With `"format": "android"` or `"format": "ios"` in the config, the
+//! messages directory is a mobile resource directory and every language
+//! lives in a platform-named directory instead of `<lang>/`:
+//!
+//! ```text
+//! res/                              Resources/
+//!   ├── values-de/strings.xml         ├── de.lproj/Localizable.strings
+//!   ├── values-zh-rCN/strings.xml     ├── zh-Hans.lproj/Localizable.strings
+//!   └── values-b+sr+Latn/strings.xml  └── Base.lproj/Main.storyboard
+//! ```
+//!
+//! Language codes use BCP 47 separators (`zh-CN`, `sr-Latn`; `zh_CN` is
+//! accepted when creating a directory); map them to backend codes with
+//! `languageMapping`. Directories that are not a language (`values-night`,
+//! `values-v21`, `Base.lproj`) are skipped. The default `values/` directory
+//! has no language; list it in `languageDirs` (`"en": "res/values"`) to
+//! include it.
+//!
+//! The messages directory asks [`MobileFormat::detect`] for its resource
+//! files before the format registry; [`MobileFormat::register`] adds the
+//! file adapters ([`super::android`], [`super::ios`]) to the registry for
+//! code that reads them without a messages directory.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::android::AndroidStrings;
+use super::ios::IosStrings;
+use super::registry::{self, TranslationFormat};
+
+/// 移动端资源格式（配置中的 `format`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MobileFormat {
+    /// Android `values-<lang>/strings.xml`
+    Android,
+    /// iOS `<lang>.lproj/Localizable.strings`
+    Ios,
+}
+
+impl MobileFormat {
+    /// 新语言使用的文件名
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Android => "strings.xml",
+            Self::Ios => "Localizable.strings",
+        }
+    }
+
+    /// 资源文件的格式（`strings.xml` / `*.strings`），其他文件返回 `None`
+    pub fn detect(&self, path: &Path) -> Option<Arc<dyn TranslationFormat>> {
+        let format: Arc<dyn TranslationFormat> = match self {
+            Self::Android if path.file_name()? == "strings.xml" => Arc::new(AndroidStrings),
+            Self::Ios if path.extension()?.eq_ignore_ascii_case("strings") => Arc::new(IosStrings),
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// 在共享注册表中注册文件格式
+    pub fn register(&self) {
+        match self {
+            Self::Android => registry::register_glob("strings.xml", Arc::new(AndroidStrings))
+                .expect("static glob pattern is valid"),
+            Self::Ios => registry::register_extension("strings", Arc::new(IosStrings)),
+        }
+    }
+
+    /// 语言对应的目录名
+    pub fn dir_name(&self, lang: &str) -> String {
+        match self {
+            Self::Android => {
+                let parts: Vec<&str> = lang.split(['-', '_']).collect();
+                match parts.as_slice() {
+                    [language] => format!("values-{}", language),
+                    [language, region] if is_region(region) => format!("values-{}-r{}", language, region),
+                    _ => format!("values-b+{}", parts.join("+")),
+                }
+            }
+            Self::Ios => format!("{}.lproj", lang),
+        }
+    }
+
+    /// 目录名对应的语言，不是语言目录时返回 `None`
+    pub fn dir_language(&self, dir_name: &str) -> Option<String> {
+        match self {
+            Self::Android => {
+                let qualifier = dir_name.strip_prefix("values-")?;
+                let parts: Vec<&str> = match qualifier.strip_prefix("b+") {
+                    Some(tag) => tag.split('+').collect(),
+                    None => match qualifier.split('-').collect::<Vec<_>>().as_slice() {
+                        [language] => vec![*language],
+                        [language, region] => vec![*language, region.strip_prefix('r').filter(|r| is_region(r))?],
+                        _ => return None,
+                    },
+                };
+                is_language(parts[0]).then(|| parts.join("-"))
+            }
+            Self::Ios => {
+                let lang = dir_name.strip_suffix(".lproj")?;
+                (!lang.is_empty() && lang != "Base").then(|| lang.to_string())
+            }
+        }
+    }
+}
+
+/// 是否为 ISO 639 语言代码（2-3 个小写字母）
+fn is_language(code: &str) -> bool {
+    (2..=3).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// 是否为地区代码（2 个大写字母或 3 位数字）
+fn is_region(code: &str) -> bool {
+    (code.len() == 2 && code.bytes().all(|b| b.is_ascii_uppercase()))
+        || (code.len() == 3 && code.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// 按字节范围替换内容（范围不重叠，顺序任意）
+pub(crate) fn splice(content: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut output = String::with_capacity(content.len());
+    let mut position = 0;
+    for (range, replacement) in edits {
+        output.push_str(&content[position..range.start]);
+        output.push_str(&replacement);
+        position = range.end;
+    }
+    output.push_str(&content[position..]);
+    output
+}
+
+/// 将范围扩展到整行（所在行的其余部分只有空白时，包括缩进和换行）
+pub(crate) fn whole_lines(content: &str, range: Range<usize>) -> Range<usize> {
+    let line_start = content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let start = if content[line_start..range.start].trim().is_empty() { line_start } else { range.start };
+    let rest = &content[range.end..];
+    let end = match rest.find('\n') {
+        Some(i) if rest[..i].trim().is_empty() => range.end + i + 1,
+        None if rest.trim().is_empty() => content.len(),
+        _ => range.end,
+    };
+    start..end
+}
+
+/// 范围起始处所在行的缩进
+pub(crate) fn indentation(content: &str, position: usize) -> &str {
+    let line_start = content[..position].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[line_start..position];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_android_dir_names() {
+        let android = MobileFormat::Android;
+        assert_eq!(android.dir_name("de"), "values-de");
+        assert_eq!(android.dir_name("zh_CN"), "values-zh-rCN");
+        assert_eq!(android.dir_name("es-419"), "values-es-r419");
+        assert_eq!(android.dir_name("sr-Latn"), "values-b+sr+Latn");
+
+        assert_eq!(android.dir_language("values-de").as_deref(), Some("de"));
+        assert_eq!(android.dir_language("values-zh-rCN").as_deref(), Some("zh-CN"));
+        assert_eq!(android.dir_language("values-b+sr+Latn").as_deref(), Some("sr-Latn"));
+        for other in ["values", "values-night", "values-v21", "values-land", "values-de-land", "drawable-de"] {
+            assert!(android.dir_language(other).is_none(), "{}", other);
+        }
+    }
+
+    #[test]
+    fn test_ios_dir_names() {
+        let ios = MobileFormat::Ios;
+        assert_eq!(ios.dir_name("zh-Hans"), "zh-Hans.lproj");
+        assert_eq!(ios.dir_language("pt-BR.lproj").as_deref(), Some("pt-BR"));
+        assert!(ios.dir_language("Base.lproj").is_none());
+        assert!(ios.dir_language("Assets.xcassets").is_none());
+    }
+
+    #[test]
+    fn test_detect() {
+        let android = MobileFormat::Android;
+        assert_eq!(android.detect(Path::new("res/values-de/strings.xml")).unwrap().name(), "Android strings");
+        assert!(android.detect(Path::new("res/values-de/colors.xml")).is_none());
+        assert_eq!(MobileFormat::Ios.detect(Path::new("de.lproj/Localizable.strings")).unwrap().name(), "iOS strings");
+        assert!(MobileFormat::Ios.detect(Path::new("de.lproj/Main.storyboard")).is_none());
+    }
+
+    #[test]
+    fn test_whole_lines() {
+        let content = "a\n  <x/>\nb <y/> c\n";
+        let x = content.find("<x/>").unwrap();
+        assert_eq!(&content[whole_lines(content, x..x + 4)], "  <x/>\n");
+        let y = content.find("<y/>").unwrap();
+        assert_eq!(whole_lines(content, y..y + 4), y..y + 4);
+        assert_eq!(splice(content, vec![(whole_lines(content, x..x + 4), String::new())]), "a\nb <y/> c\n");
+    }
+}
@@ -0,0 +1,17 @@
+//! Translation file formats
+//!
+//! Parsers and writers for formats that do not map onto a nested JSON
+//! object and therefore need their own merge logic, plus the registry that
+//! picks a format for each file. Java `.properties` bundles are built in;
+//! Android `strings.xml` and iOS `.strings` files are registered when the
+//! config selects a [`MobileFormat`].
+
+pub mod android;
+pub mod ios;
+pub mod mobile;
+pub mod po;
+pub mod properties;
+pub mod registry;
+
+pub use mobile::MobileFormat;
+pub use registry::{detect, FormatRegistry, TranslationFormat};
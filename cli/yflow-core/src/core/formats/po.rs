@@ -0,0 +1,536 @@
+//! gettext PO/POT catalogs
+//!
+//! Parses `msgid`/`msgstr` pairs into flat translations and writes them
+//! back, keeping the header entry, comments and entry order intact.
+//!
+//! # Keys
+//!
+//! An entry's key is its `msgctxt` when present, otherwise its `msgid`.
+//! Plural entries produce one key per form: form 0 uses the plain key and
+//! form `n` uses `key[n]`, mirroring `msgstr[n]`.
+//!
+//! ```text
+//! msgctxt "inbox.count"
+//! msgid "One message"
+//! msgid_plural "%d messages"
+//! msgstr[0] "Eine Nachricht"     -> inbox.count
+//! msgstr[1] "%d Nachrichten"     -> inbox.count[1]
+//! ```
+//!
+//! POT templates carry no translations; when read as templates the source
+//! strings (`msgid` / `msgid_plural`) are used as values.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+
+/// PO 条目
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoEntry {
+    /// 条目前的注释行（原样保留，包括 `#`）
+    pub comments: Vec<String>,
+    /// 上下文
+    pub msgctxt: Option<String>,
+    /// 原文
+    pub msgid: String,
+    /// 复数原文
+    pub msgid_plural: Option<String>,
+    /// 译文；非复数条目只有一项
+    pub msgstr: Vec<String>,
+}
+
+impl PoEntry {
+    /// 条目的键：`msgctxt` 优先，否则为 `msgid`
+    pub fn key(&self) -> &str {
+        self.msgctxt.as_deref().unwrap_or(&self.msgid)
+    }
+
+    /// 第 `form` 个复数形式的键
+    fn form_key(&self, form: usize) -> String {
+        plural_key(self.key(), form)
+    }
+
+    /// 第 `form` 个复数形式对应的原文
+    fn source(&self, form: usize) -> &str {
+        match (&self.msgid_plural, form) {
+            (Some(plural), n) if n > 0 => plural,
+            _ => &self.msgid,
+        }
+    }
+}
+
+/// 复数形式的键：形式 0 为原键，其余为 `key[n]`
+pub fn plural_key(key: &str, form: usize) -> String {
+    if form == 0 {
+        key.to_string()
+    } else {
+        format!("{}[{}]", key, form)
+    }
+}
+
+/// PO 目录
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoCatalog {
+    /// 头部条目（`msgid ""`）
+    pub header: Option<PoEntry>,
+    /// 普通条目
+    pub entries: Vec<PoEntry>,
+    /// 文件末尾不属于任何条目的注释（如废弃条目 `#~`）
+    pub trailing_comments: Vec<String>,
+}
+
+/// 续行写入的字段
+#[derive(Clone, Copy)]
+enum Field {
+    None,
+    Ctxt,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+impl PoCatalog {
+    /// 解析 PO/POT 内容
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut catalog = PoCatalog::default();
+        let mut comments: Vec<String> = Vec::new();
+        let mut current: Option<PoEntry> = None;
+        let mut field = Field::None;
+
+        for (index, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            let line_no = index + 1;
+
+            if line.is_empty() {
+                catalog.finish(&mut current, &mut comments);
+                field = Field::None;
+                continue;
+            }
+
+            if line.starts_with('#') {
+                // 注释属于下一个条目
+                if current.as_ref().map(|e| !e.msgstr.is_empty()).unwrap_or(false) {
+                    catalog.finish(&mut current, &mut comments);
+                }
+                comments.push(raw.trim_end().to_string());
+                field = Field::None;
+                continue;
+            }
+
+            let (keyword, rest) = match line.find(char::is_whitespace) {
+                Some(pos) if !line.starts_with('"') => (&line[..pos], &line[pos..]),
+                _ => ("", line),
+            };
+            let text = || unquote(rest).with_context(|| format!("line {}", line_no));
+
+            match keyword {
+                "msgctxt" => {
+                    catalog.finish(&mut current, &mut comments);
+                    current.get_or_insert_with(PoEntry::default).msgctxt = Some(text()?);
+                    field = Field::Ctxt;
+                }
+                "msgid" => {
+                    // 已有完整条目（缺少空行分隔）时先结束
+                    if current.as_ref().map(|e| !e.msgstr.is_empty()).unwrap_or(false) {
+                        catalog.finish(&mut current, &mut comments);
+                    }
+                    current.get_or_insert_with(PoEntry::default).msgid = text()?;
+                    field = Field::Id;
+                }
+                "msgid_plural" => {
+                    let entry = current
+                        .as_mut()
+                        .ok_or_else(|| anyhow::anyhow!("line {}: msgid_plural without msgid", line_no))?;
+                    entry.msgid_plural = Some(text()?);
+                    field = Field::IdPlural;
+                }
+                "" => {
+                    let value = text()?;
+                    let entry = current
+                        .as_mut()
+                        .ok_or_else(|| anyhow::anyhow!("line {}: string outside of an entry", line_no))?;
+                    match field {
+                        Field::Ctxt => entry.msgctxt.get_or_insert_with(String::new).push_str(&value),
+                        Field::Id => entry.msgid.push_str(&value),
+                        Field::IdPlural => entry.msgid_plural.get_or_insert_with(String::new).push_str(&value),
+                        Field::Str(n) => entry.msgstr[n].push_str(&value),
+                        Field::None => {
+                            return Err(anyhow::anyhow!("line {}: string outside of an entry", line_no));
+                        }
+                    }
+                }
+                keyword => {
+                    let form = parse_msgstr_keyword(keyword)
+                        .ok_or_else(|| anyhow::anyhow!("line {}: unexpected '{}'", line_no, keyword))?;
+                    let entry = current
+                        .as_mut()
+                        .ok_or_else(|| anyhow::anyhow!("line {}: msgstr without msgid", line_no))?;
+                    if entry.msgstr.len() <= form {
+                        entry.msgstr.resize(form + 1, String::new());
+                    }
+                    entry.msgstr[form] = text()?;
+                    field = Field::Str(form);
+                }
+            }
+        }
+
+        catalog.finish(&mut current, &mut comments);
+        catalog.trailing_comments = comments;
+        Ok(catalog)
+    }
+
+    /// 结束当前条目
+    fn finish(&mut self, current: &mut Option<PoEntry>, comments: &mut Vec<String>) {
+        let Some(mut entry) = current.take() else {
+            return;
+        };
+        entry.comments = std::mem::take(comments);
+        if entry.msgid.is_empty() && entry.msgctxt.is_none() && self.header.is_none() {
+            self.header = Some(entry);
+        } else {
+            self.entries.push(entry);
+        }
+    }
+
+    /// 头部字段值（如 `Language`）
+    pub fn header_field(&self, name: &str) -> Option<String> {
+        let header = self.header.as_ref()?;
+        let text = header.msgstr.first()?;
+        text.lines().find_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            (field.trim() == name)
+                .then(|| value.trim().to_string())
+                .filter(|v| !v.is_empty())
+        })
+    }
+
+    /// 设置头部字段，不存在时追加（没有头部时创建）
+    pub fn set_header_field(&mut self, name: &str, value: &str) {
+        let header = self.header.get_or_insert_with(|| PoEntry {
+            msgstr: vec![String::new()],
+            ..Default::default()
+        });
+        if header.msgstr.is_empty() {
+            header.msgstr.push(String::new());
+        }
+        let text = &mut header.msgstr[0];
+
+        let mut found = false;
+        let mut lines: Vec<String> = text
+            .lines()
+            .map(|line| match line.split_once(':') {
+                Some((field, _)) if field.trim() == name => {
+                    found = true;
+                    format!("{}: {}", name, value)
+                }
+                _ => line.to_string(),
+            })
+            .collect();
+        if !found {
+            lines.push(format!("{}: {}", name, value));
+        }
+        *text = lines.join("\n") + "\n";
+    }
+
+    /// 头部声明的语言
+    pub fn language(&self) -> Option<String> {
+        self.header_field("Language")
+    }
+
+    /// 转换为扁平翻译
+    ///
+    /// 空译文会被跳过；`template` 为 true（POT）时以原文作为值。
+    pub fn to_translations(&self, template: bool) -> HashMap<String, String> {
+        let mut translations = HashMap::new();
+        for entry in &self.entries {
+            let forms = entry.msgstr.len().max(if entry.msgid_plural.is_some() { 2 } else { 1 });
+            for form in 0..forms {
+                let value = entry.msgstr.get(form).map(String::as_str).unwrap_or_default();
+                let value = if value.is_empty() && template { entry.source(form) } else { value };
+                if !value.is_empty() {
+                    translations.insert(entry.form_key(form), value.to_string());
+                }
+            }
+        }
+        translations
+    }
+
+    /// 每个键对应的原文（`msgid` / `msgid_plural`）
+    pub fn sources(&self) -> HashMap<String, String> {
+        let mut sources = HashMap::new();
+        for entry in &self.entries {
+            for form in 0..entry.msgstr.len().max(1) {
+                sources.insert(entry.form_key(form), entry.source(form).to_string());
+            }
+        }
+        sources
+    }
+
+    /// 合并翻译
+    ///
+    /// 已有条目更新译文（新值总是覆盖旧值，与 JSON 合并一致），
+    /// 目录中不存在的键按键名排序追加为新条目（`msgid` 为键名）。
+    pub fn merge(&mut self, translations: &HashMap<String, String>) {
+        let mut known: HashSet<String> = HashSet::new();
+
+        for entry in &mut self.entries {
+            let forms = entry.msgstr.len().max(if entry.msgid_plural.is_some() { 2 } else { 1 });
+            for form in 0..forms {
+                let key = entry.form_key(form);
+                if let Some(value) = translations.get(&key) {
+                    if entry.msgstr.len() <= form {
+                        entry.msgstr.resize(form + 1, String::new());
+                    }
+                    entry.msgstr[form] = value.clone();
+                }
+                known.insert(key);
+            }
+        }
+
+        let mut new_keys: Vec<&String> = translations.keys().filter(|k| !known.contains(*k)).collect();
+        new_keys.sort();
+        for key in new_keys {
+            self.entries.push(PoEntry {
+                msgid: key.clone(),
+                msgstr: vec![translations[key].clone()],
+                ..Default::default()
+            });
+        }
+    }
+
+    /// 删除键对应的条目（包括所有复数形式）
+    ///
+    /// # Returns
+    ///
+    /// 删除的条目数
+    pub fn remove(&mut self, keys: &HashSet<String>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| !keys.contains(entry.key()));
+        before - self.entries.len()
+    }
+
+    /// 从扁平翻译创建新目录
+    pub fn from_translations(translations: &HashMap<String, String>, language: Option<&str>) -> Self {
+        let mut header = String::from("Content-Type: text/plain; charset=UTF-8\n");
+        if let Some(language) = language {
+            header.push_str(&format!("Language: {}\n", language));
+        }
+        let mut catalog = PoCatalog {
+            header: Some(PoEntry {
+                msgstr: vec![header],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        catalog.merge(translations);
+        catalog
+    }
+
+    /// 序列化为 PO 文本
+    pub fn serialize(&self) -> String {
+        let mut blocks: Vec<String> = Vec::new();
+        if let Some(header) = &self.header {
+            blocks.push(write_entry(header));
+        }
+        blocks.extend(self.entries.iter().map(write_entry));
+        if !self.trailing_comments.is_empty() {
+            blocks.push(self.trailing_comments.join("\n") + "\n");
+        }
+        blocks.join("\n")
+    }
+}
+
+/// 解析 `msgstr` / `msgstr[n]`，返回复数形式序号
+fn parse_msgstr_keyword(keyword: &str) -> Option<usize> {
+    if keyword == "msgstr" {
+        return Some(0);
+    }
+    keyword
+        .strip_prefix("msgstr[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+fn write_entry(entry: &PoEntry) -> String {
+    let mut out = String::new();
+    for comment in &entry.comments {
+        out.push_str(comment);
+        out.push('\n');
+    }
+    if let Some(ctxt) = &entry.msgctxt {
+        write_field(&mut out, "msgctxt", ctxt);
+    }
+    write_field(&mut out, "msgid", &entry.msgid);
+    if let Some(plural) = &entry.msgid_plural {
+        write_field(&mut out, "msgid_plural", plural);
+        for (form, value) in entry.msgstr.iter().enumerate() {
+            write_field(&mut out, &format!("msgstr[{}]", form), value);
+        }
+    } else {
+        write_field(&mut out, "msgstr", entry.msgstr.first().map(String::as_str).unwrap_or_default());
+    }
+    out
+}
+
+/// 写入字段；含换行的字符串按 gettext 惯例拆成多行
+fn write_field(out: &mut String, keyword: &str, value: &str) {
+    let lines: Vec<&str> = value.split_inclusive('\n').collect();
+    if lines.len() <= 1 {
+        out.push_str(&format!("{} \"{}\"\n", keyword, escape(value)));
+        return;
+    }
+    out.push_str(&format!("{} \"\"\n", keyword));
+    for line in lines {
+        out.push_str(&format!("\"{}\"\n", escape(line)));
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 解析带引号的字符串
+pub fn unquote(s: &str) -> Result<String> {
+    let s = s.trim();
+    let inner = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| anyhow::anyhow!("Expected quoted string: {}", s))?;
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"# German translations
+msgid ""
+msgstr ""
+"Project-Id-Version: app 1.0\n"
+"Language: de\n"
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+#: src/app.js:10
+msgid "Hello %s"
+msgstr "Hallo %s"
+
+msgctxt "inbox.count"
+msgid "One message"
+msgid_plural "%d messages"
+msgstr[0] "Eine Nachricht"
+msgstr[1] "%d Nachrichten"
+
+msgid "Untranslated"
+msgstr ""
+
+#~ msgid "Old"
+#~ msgstr "Alt"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let catalog = PoCatalog::parse(SAMPLE).unwrap();
+        assert_eq!(catalog.language().as_deref(), Some("de"));
+        assert_eq!(catalog.header.as_ref().unwrap().comments, vec!["# German translations"]);
+        assert_eq!(catalog.entries.len(), 3);
+        assert_eq!(catalog.trailing_comments.len(), 2);
+
+        let translations = catalog.to_translations(false);
+        assert_eq!(translations.len(), 3);
+        assert_eq!(translations["Hello %s"], "Hallo %s");
+        assert_eq!(translations["inbox.count"], "Eine Nachricht");
+        assert_eq!(translations["inbox.count[1]"], "%d Nachrichten");
+
+        let sources = catalog.sources();
+        assert_eq!(sources["inbox.count[1]"], "%d messages");
+    }
+
+    #[test]
+    fn test_template_uses_source() {
+        let catalog = PoCatalog::parse("msgid \"Save\"\nmsgstr \"\"\n").unwrap();
+        assert!(catalog.to_translations(false).is_empty());
+        assert_eq!(catalog.to_translations(true)["Save"], "Save");
+    }
+
+    #[test]
+    fn test_roundtrip_preserves_file() {
+        let catalog = PoCatalog::parse(SAMPLE).unwrap();
+        assert_eq!(catalog.serialize(), SAMPLE);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut catalog = PoCatalog::parse(SAMPLE).unwrap();
+        catalog.merge(&HashMap::from([
+            ("Untranslated".to_string(), "Unübersetzt".to_string()),
+            ("inbox.count[1]".to_string(), "%d neue Nachrichten".to_string()),
+            ("new \"key\"".to_string(), "Neu\nZeile".to_string()),
+        ]));
+
+        let written = catalog.serialize();
+        assert!(written.contains("\"Project-Id-Version: app 1.0\\n\""));
+        assert!(written.contains("msgstr[1] \"%d neue Nachrichten\""));
+        assert!(written.contains("msgid \"Untranslated\"\nmsgstr \"Unübersetzt\""));
+        assert!(written.contains("msgid \"new \\\"key\\\"\"\nmsgstr \"\"\n\"Neu\\n\"\n\"Zeile\""));
+
+        let reparsed = PoCatalog::parse(&written).unwrap().to_translations(false);
+        assert_eq!(reparsed["new \"key\""], "Neu\nZeile");
+        assert_eq!(reparsed.len(), 5);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut catalog = PoCatalog::parse(SAMPLE).unwrap();
+        let removed = catalog.remove(&HashSet::from(["inbox.count".to_string(), "missing".to_string()]));
+        assert_eq!(removed, 1);
+
+        let translations = PoCatalog::parse(&catalog.serialize()).unwrap().to_translations(false);
+        assert_eq!(translations.len(), 1);
+        assert!(translations.contains_key("Hello %s"));
+    }
+
+    #[test]
+    fn test_set_header_field() {
+        let mut catalog = PoCatalog::parse(SAMPLE).unwrap();
+        catalog.set_header_field("Language", "fr");
+        assert_eq!(catalog.language().as_deref(), Some("fr"));
+        assert_eq!(catalog.header_field("Project-Id-Version").as_deref(), Some("app 1.0"));
+
+        let mut empty = PoCatalog::default();
+        empty.set_header_field("Language", "fr");
+        assert_eq!(empty.serialize(), "msgid \"\"\nmsgstr \"Language: fr\\n\"\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(PoCatalog::parse("msgstr \"x\"\n").is_err());
+        assert!(PoCatalog::parse("msgid \"x\"\nbogus \"y\"\n").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Java properties files (`messages.properties`)
+//!
+//! Reads and writes the resource bundles of JVM projects, one language per
+//! file (`de/messages.properties` or `de.properties`). Keys end at the first
+//! unescaped `=`, `:` or whitespace; lines ending in an odd number of
+//! backslashes continue on the next line. `#` and `!` comment lines directly
+//! above an entry belong to it and are removed with it. Escapes (`\t`, `\n`,
+//! `\r`, `\f`, `\\`, `\uXXXX` including surrogate pairs) are decoded.
+//!
+//! Writing only replaces the values that changed and appends new keys at
+//! the end, so comments and ordering are kept. Written keys and values
+//! escape every non-ASCII character as `\uXXXX`, so files stay valid
+//! ISO-8859-1 for `ResourceBundle` on any JVM version. Files are read as
+//! UTF-8; plain ASCII files with `\u` escapes are read on every platform.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use super::mobile::{splice, whole_lines};
+use super::registry::TranslationFormat;
+use crate::core::flatten::DEFAULT_SEPARATOR;
+
+/// Java `.properties` 格式
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JavaProperties;
+
+/// 一个条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    /// 解码后的键
+    key: String,
+    /// 解码后的值
+    value: String,
+    /// 值（到逻辑行末尾）的字节范围
+    value_span: Range<usize>,
+    /// 条目（含紧邻的前置注释）的字节范围
+    span: Range<usize>,
+}
+
+impl TranslationFormat for JavaProperties {
+    fn name(&self) -> &str {
+        "Java properties"
+    }
+
+    fn read_flat(&self, content: &str, _separator: &str) -> Result<HashMap<String, String>> {
+        Ok(parse(content)?.into_iter().map(|entry| (entry.key, entry.value)).collect())
+    }
+
+    fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        _force: bool,
+        _separator: &str,
+    ) -> Result<Option<String>> {
+        let entries = parse(content)?;
+
+        // 1. 修改已有的值（重复的键以最后一个为准）
+        let mut edits = Vec::new();
+        let mut new_keys = Vec::new();
+        for (key, value) in translations {
+            match entries.iter().rev().find(|entry| entry.key == *key) {
+                Some(entry) if entry.value != *value => edits.push((entry.value_span.clone(), escape(value, false))),
+                Some(_) => {}
+                None => new_keys.push((key, value)),
+            }
+        }
+        let mut merged = splice(content, edits);
+
+        // 2. 新键加到文件末尾
+        new_keys.sort();
+        if !new_keys.is_empty() && !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        for (key, value) in new_keys {
+            merged.push_str(&format!("{}={}\n", escape(key, true), escape(value, false)));
+        }
+        Ok(Some(merged))
+    }
+
+    fn remove(&self, content: &str, keys: &[String], _separator: &str) -> Result<Option<String>> {
+        let keys: HashSet<&str> = keys.iter().map(String::as_str).collect();
+        let edits = parse(content)?
+            .into_iter()
+            .filter(|entry| keys.contains(entry.key.as_str()))
+            .map(|entry| (whole_lines(content, entry.span), String::new()))
+            .collect();
+        Ok(Some(splice(content, edits)))
+    }
+
+    /// 新语言不保留空值（空字符串会覆盖默认语言的 bundle），只保留注释
+    fn scaffold(&self, content: &str, _lang: &str, fill_source: bool) -> Result<Option<String>> {
+        if fill_source {
+            return Ok(Some(content.to_string()));
+        }
+        let keys: Vec<String> = self.read_flat(content, DEFAULT_SEPARATOR)?.into_keys().collect();
+        self.remove(content, &keys, DEFAULT_SEPARATOR)
+    }
+}
+
+/// 解析文件
+fn parse(content: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut position = 0;
+    // 紧邻下一个条目的注释的起始位置
+    let mut comment_start = None;
+    while position < content.len() {
+        let physical_end = line_end(content, position);
+        let start = skip_whitespace(content, position, physical_end);
+
+        // 空行和注释
+        let rest = content[start..physical_end].trim_end_matches('\r');
+        if rest.is_empty() {
+            comment_start = None;
+            position = physical_end + 1;
+            continue;
+        }
+        if rest.starts_with(['#', '!']) {
+            comment_start.get_or_insert(start);
+            position = physical_end + 1;
+            continue;
+        }
+
+        // 逻辑行：以奇数个反斜杠结尾的行在下一行继续
+        let mut end = physical_end;
+        while end < content.len() && continues(&content[start..end]) {
+            end = line_end(content, end + 1);
+        }
+        let value_end = if content[..end].ends_with('\r') { end - 1 } else { end };
+
+        // 键、分隔符和值
+        let (key, after_key) = read(content, start..value_end, true)?;
+        let mut value_start = skip_whitespace(content, after_key, value_end);
+        if content[value_start..value_end].starts_with(['=', ':']) {
+            value_start = skip_whitespace(content, value_start + 1, value_end);
+        }
+        let (value, _) = read(content, value_start..value_end, false)?;
+
+        entries.push(Entry {
+            key,
+            value,
+            value_span: value_start..value_end,
+            span: comment_start.take().unwrap_or(start)..end,
+        });
+        position = end + 1;
+    }
+    Ok(entries)
+}
+
+/// 行末（换行符或内容末尾）的位置
+fn line_end(content: &str, position: usize) -> usize {
+    content[position..].find('\n').map_or(content.len(), |i| position + i)
+}
+
+/// 行是否在下一行继续（以奇数个反斜杠结尾）
+fn continues(line: &str) -> bool {
+    let line = line.trim_end_matches('\r');
+    (line.len() - line.trim_end_matches('\\').len()) % 2 == 1
+}
+
+/// 跳过空白和续行，返回下一个有效字符的位置
+fn skip_whitespace(content: &str, mut position: usize, end: usize) -> usize {
+    loop {
+        let rest = &content[position..end];
+        let trimmed = rest.trim_start_matches([' ', '\t', '\x0c']);
+        position += rest.len() - trimmed.len();
+        match continuation(trimmed) {
+            Some(length) => position += length,
+            None => return position,
+        }
+    }
+}
+
+/// 以续行（反斜杠加换行）开头时返回其长度
+fn continuation(text: &str) -> Option<usize> {
+    if text.starts_with("\\\r\n") {
+        Some(3)
+    } else if text.starts_with("\\\n") {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// 读取并解码键或值，返回解码后的文本和结束位置
+///
+/// `key` 为 true 时在第一个未转义的 `=`、`:` 或空白处结束。
+fn read(content: &str, range: Range<usize>, key: bool) -> Result<(String, usize)> {
+    let mut text = String::new();
+    let mut position = range.start;
+    while position < range.end {
+        let rest = &content[position..range.end];
+        if let Some(length) = continuation(rest) {
+            // 续行的前导空白不属于内容
+            position = skip_whitespace(content, position + length, range.end);
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        if key && matches!(c, '=' | ':' | ' ' | '\t' | '\x0c') {
+            return Ok((text, position));
+        }
+        if c != '\\' {
+            text.push(c);
+            position += c.len_utf8();
+            continue;
+        }
+
+        let escaped = rest[1..].chars().next();
+        position += 1 + escaped.map_or(0, char::len_utf8);
+        match escaped {
+            Some('t') => text.push('\t'),
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('f') => text.push('\x0c'),
+            Some('u') => {
+                let mut units = vec![read_hex(content, position, range.end)?];
+                position += 4;
+                // 代理对：高位之后是另一个 \u 转义
+                if (0xD800..0xDC00).contains(&units[0]) && content[position..range.end].starts_with("\\u") {
+                    units.push(read_hex(content, position + 2, range.end)?);
+                    position += 6;
+                }
+                for decoded in char::decode_utf16(units) {
+                    text.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+            }
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    Ok((text, range.end))
+}
+
+/// 读取 4 位十六进制数
+fn read_hex(content: &str, position: usize, end: usize) -> Result<u16> {
+    content
+        .get(position..(position + 4).min(end))
+        .filter(|hex| hex.len() == 4)
+        .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| error(content, position, "malformed \\uXXXX escape"))
+}
+
+/// 编码键或值（非 ASCII 字符编码为 `\uXXXX`）
+///
+/// 键中的分隔符和注释符也会被转义；值只转义开头的空格。
+fn escape(text: &str, key: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (index, c) in text.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\x0c' => escaped.push_str("\\f"),
+            ' ' if key || index == 0 => escaped.push_str("\\ "),
+            '=' | ':' | '#' | '!' if key => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            c => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+        }
+    }
+    escaped
+}
+
+/// 带行号的解析错误
+fn error(content: &str, position: usize, message: &str) -> anyhow::Error {
+    let line = content[..position.min(content.len())].matches('\n').count() + 1;
+    anyhow::anyhow!("line {}: {}", line, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r"# Generated from messages.properties
+
+# Inbox title
+inbox.title = Inbox
+greeting:Hello, {0}!\nWelcome
+! Accents
+accent=Caf\u00e9 \uD83D\uDE00
+long.text = First line, \
+            second line
+path\ name\=x   C:\\temp
+empty
+";
+
+    #[test]
+    fn test_read_flat() {
+        let flat = JavaProperties.read_flat(SAMPLE, ".").unwrap();
+        assert_eq!(flat["inbox.title"], "Inbox");
+        assert_eq!(flat["greeting"], "Hello, {0}!\nWelcome");
+        assert_eq!(flat["accent"], "Café 😀");
+        assert_eq!(flat["long.text"], "First line, second line");
+        assert_eq!(flat["path name=x"], "C:\\temp");
+        assert_eq!(flat["empty"], "");
+        assert_eq!(flat.len(), 6);
+
+        let windows = JavaProperties.read_flat("a = b \\\r\n  c\r\nd=e\r\n", ".").unwrap();
+        assert_eq!(windows["a"], "b c");
+        assert_eq!(windows["d"], "e");
+    }
+
+    #[test]
+    fn test_merge_and_remove() {
+        let translations = HashMap::from([
+            ("inbox.title".to_string(), "Posteingang".to_string()),
+            ("greeting".to_string(), "Hello, {0}!\nWelcome".to_string()),
+            ("long.text".to_string(), "Größe".to_string()),
+            ("new key".to_string(), " Grüße 😀".to_string()),
+        ]);
+        let merged = JavaProperties.merge(SAMPLE, &translations, false, ".").unwrap().unwrap();
+        assert!(merged.contains("# Inbox title\ninbox.title = Posteingang\n"));
+        assert!(merged.contains("greeting:Hello, {0}!\\nWelcome\n"));
+        assert!(merged.contains("long.text = Gr\\u00F6\\u00DFe\npath"));
+        assert!(merged.ends_with("empty\nnew\\ key=\\ Gr\\u00FC\\u00DFe \\uD83D\\uDE00\n"));
+        assert!(merged.is_ascii());
+        let flat = JavaProperties.read_flat(&merged, ".").unwrap();
+        assert_eq!(flat["long.text"], "Größe");
+        assert_eq!(flat["new key"], " Grüße 😀");
+
+        let removed = JavaProperties
+            .remove(&merged, &["inbox.title".to_string(), "accent".to_string()], ".")
+            .unwrap()
+            .unwrap();
+        assert!(removed.starts_with("# Generated from messages.properties\n\ngreeting:"));
+        assert!(!removed.contains("Accents"));
+        assert_eq!(JavaProperties.read_flat(&removed, ".").unwrap().len(), 5);
+
+        let created = JavaProperties.merge("", &translations, false, ".").unwrap().unwrap();
+        assert_eq!(JavaProperties.read_flat(&created, ".").unwrap(), translations);
+
+        let scaffold = JavaProperties.scaffold(SAMPLE, "de", false).unwrap().unwrap();
+        assert_eq!(scaffold, "# Generated from messages.properties\n\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = JavaProperties.read_flat("a=b\nc=\\u00g1", ".").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: malformed \\uXXXX escape");
+        assert!(JavaProperties.read_flat("a=\\u12", ".").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Translation format registry
+//!
+//! Maps file names to the [`TranslationFormat`] that reads and writes them.
+//! The scanner, the writers and `watch` all ask the registry instead of
+//! matching extensions themselves, so adding a format (ARB, XLIFF, a
+//! project-specific plugin) is a single [`register_extension`] or
+//! [`register_glob`] call.
+//!
+//! Glob patterns are matched against the file name (`*.strings.json`) and
+//! take precedence over extensions; among patterns, and for the same
+//! extension, the latest registration wins. The built-in formats are JSON,
+//! YAML, PO, POT and Java properties.
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use crate::core::lint::key_glob;
+use crate::core::scanner::FileFormat;
+
+use super::properties::JavaProperties;
+
+/// 翻译文件格式
+pub trait TranslationFormat: fmt::Debug + Send + Sync {
+    /// 格式名称（用于日志和错误信息）
+    fn name(&self) -> &str;
+
+    /// 将文件内容读取为扁平的翻译
+    ///
+    /// 嵌套结构按 `separator` 拼接键名（见 [`crate::core::flatten`]）。
+    fn read_flat(&self, content: &str, separator: &str) -> Result<HashMap<String, String>>;
+
+    /// 将翻译合并到已有文件内容中
+    ///
+    /// 返回新内容；只读格式返回 `None`。
+    fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        force: bool,
+        separator: &str,
+    ) -> Result<Option<String>>;
+
+    /// 从已有文件内容中删除键
+    ///
+    /// 返回新内容；只读或不支持删除的格式返回 `None`（键保留）。
+    fn remove(&self, _content: &str, _keys: &[String], _separator: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// 根据源语言文件生成新语言的文件
+    ///
+    /// 不支持时返回 `None`（文件不会被复制）。
+    fn scaffold(&self, _content: &str, _lang: &str, _fill_source: bool) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// 是否为模板（模板中的值不会覆盖真正的翻译）
+    fn is_template(&self) -> bool {
+        false
+    }
+}
+
+impl TranslationFormat for FileFormat {
+    fn name(&self) -> &str {
+        FileFormat::name(self)
+    }
+
+    fn read_flat(&self, content: &str, separator: &str) -> Result<HashMap<String, String>> {
+        FileFormat::read_flat(self, content, separator)
+    }
+
+    fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        force: bool,
+        separator: &str,
+    ) -> Result<Option<String>> {
+        FileFormat::merge(self, content, translations, force, separator)
+    }
+
+    fn remove(&self, content: &str, keys: &[String], separator: &str) -> Result<Option<String>> {
+        FileFormat::remove(self, content, keys, separator)
+    }
+
+    fn scaffold(&self, content: &str, lang: &str, fill_source: bool) -> Result<Option<String>> {
+        FileFormat::scaffold(self, content, lang, fill_source)
+    }
+
+    fn is_template(&self) -> bool {
+        *self == FileFormat::Pot
+    }
+}
+
+/// 文件格式注册表
+#[derive(Debug, Clone, Default)]
+pub struct FormatRegistry {
+    /// 按文件名 glob 注册的格式（后注册的优先）
+    globs: Vec<(Regex, Arc<dyn TranslationFormat>)>,
+    /// 按扩展名注册的格式
+    extensions: HashMap<String, Arc<dyn TranslationFormat>>,
+}
+
+impl FormatRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含内置格式（JSON、YAML、PO、POT、Java properties）的注册表
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register_extension("json", Arc::new(FileFormat::Json));
+        registry.register_extension("yaml", Arc::new(FileFormat::Yaml));
+        registry.register_extension("yml", Arc::new(FileFormat::Yaml));
+        registry.register_extension("po", Arc::new(FileFormat::Po));
+        registry.register_extension("pot", Arc::new(FileFormat::Pot));
+        registry.register_extension("properties", Arc::new(JavaProperties));
+        registry
+    }
+
+    /// 按扩展名注册格式（不含点，已注册的扩展名会被替换）
+    pub fn register_extension(&mut self, extension: &str, format: Arc<dyn TranslationFormat>) {
+        self.extensions
+            .insert(extension.trim_start_matches('.').to_ascii_lowercase(), format);
+    }
+
+    /// 按文件名 glob 注册格式（如 `*.arb`、`strings-*.json`）
+    ///
+    /// # Errors
+    ///
+    /// glob 模式无效时返回错误
+    pub fn register_glob(&mut self, pattern: &str, format: Arc<dyn TranslationFormat>) -> Result<()> {
+        self.globs.push((key_glob(pattern)?, format));
+        Ok(())
+    }
+
+    /// 确定文件的格式，不支持的文件返回 `None`
+    pub fn detect(&self, path: &Path) -> Option<Arc<dyn TranslationFormat>> {
+        let file_name = path.file_name()?.to_str()?;
+        if let Some((_, format)) = self.globs.iter().rev().find(|(glob, _)| glob.is_match(file_name)) {
+            return Some(format.clone());
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.extensions.get(&extension).cloned()
+    }
+
+    /// 已注册的扩展名（排序）
+    pub fn extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self.extensions.keys().cloned().collect();
+        extensions.sort();
+        extensions
+    }
+}
+
+/// 进程内共享的注册表
+static REGISTRY: LazyLock<RwLock<FormatRegistry>> = LazyLock::new(|| RwLock::new(FormatRegistry::with_builtins()));
+
+/// 在共享注册表中按扩展名注册格式
+pub fn register_extension(extension: &str, format: Arc<dyn TranslationFormat>) {
+    REGISTRY.write().register_extension(extension, format);
+}
+
+/// 在共享注册表中按文件名 glob 注册格式
+///
+/// # Errors
+///
+/// glob 模式无效时返回错误
+pub fn register_glob(pattern: &str, format: Arc<dyn TranslationFormat>) -> Result<()> {
+    REGISTRY.write().register_glob(pattern, format)
+}
+
+/// 使用共享注册表确定文件的格式
+pub fn detect(path: &Path) -> Option<Arc<dyn TranslationFormat>> {
+    REGISTRY.read().detect(path)
+}
+
+/// 是否为可保存翻译的文件（已注册且不是模板）
+pub fn is_translation_file(path: &Path) -> bool {
+    detect(path).is_some_and(|format| !format.is_template())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每行一个 `key=value` 的测试格式
+    #[derive(Debug)]
+    struct LinesFormat;
+
+    impl TranslationFormat for LinesFormat {
+        fn name(&self) -> &str {
+            "lines"
+        }
+
+        fn read_flat(&self, content: &str, _separator: &str) -> Result<HashMap<String, String>> {
+            Ok(content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect())
+        }
+
+        fn merge(
+            &self,
+            _content: &str,
+            _translations: &HashMap<String, String>,
+            _force: bool,
+            _separator: &str,
+        ) -> Result<Option<String>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_builtin_detection() {
+        let registry = FormatRegistry::with_builtins();
+        assert_eq!(registry.detect(Path::new("en/common.json")).unwrap().name(), "JSON");
+        assert_eq!(registry.detect(Path::new("en/common.YML")).unwrap().name(), "YAML");
+        assert!(registry.detect(Path::new("fr/messages.pot")).unwrap().is_template());
+        assert!(!registry.detect(Path::new("fr/messages.po")).unwrap().is_template());
+        assert_eq!(registry.detect(Path::new("de/messages.properties")).unwrap().name(), "Java properties");
+        assert!(registry.detect(Path::new("en/README.md")).is_none());
+        assert!(registry.detect(Path::new("en/Makefile")).is_none());
+        assert_eq!(registry.extensions(), ["json", "po", "pot", "properties", "yaml", "yml"]);
+    }
+
+    #[test]
+    fn test_glob_takes_precedence_over_extension() {
+        let mut registry = FormatRegistry::with_builtins();
+        registry.register_glob("*.lines.json", Arc::new(LinesFormat)).unwrap();
+        registry.register_extension(".LINES", Arc::new(LinesFormat));
+
+        assert_eq!(registry.detect(Path::new("en/app.lines.json")).unwrap().name(), "lines");
+        assert_eq!(registry.detect(Path::new("en/app.json")).unwrap().name(), "JSON");
+        assert_eq!(registry.detect(Path::new("en/app.lines")).unwrap().name(), "lines");
+
+        let format = registry.detect(Path::new("app.lines")).unwrap();
+        assert_eq!(format.read_flat("a=1\nb=2", ".").unwrap().get("b").unwrap(), "2");
+        assert!(format.scaffold("a=1", "de", false).unwrap().is_none());
+    }
+}
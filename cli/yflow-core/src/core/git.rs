@@ -0,0 +1,555 @@
+//! Git integration
+//!
+//! Lets commands commit the files they wrote (`sync --commit`). Committing,
+//! branching and pushing run the `git` executable, which must be on the
+//! `PATH`, so the user's hooks, signing keys and credential helpers apply.
+//! Only the given paths are staged and committed, so changes the user
+//! already had staged stay out of the commit and remain staged. The commit
+//! author is the contributor from [`attribution`](super::attribution) when
+//! it has an email (otherwise git's own identity is used), and `sign` adds
+//! `-S` for GPG/SSH signed commits.
+//!
+//! For automated update PRs (`sync --branch-per-run`) the commit goes on a
+//! new branch that can be pushed; [`pull_request_url`] derives the page for
+//! opening the pull request on GitHub, GitLab or Bitbucket from the remote
+//! URL, and [`run_pr_command`] runs the configured `prCommand` instead.
+//!
+//! [`changed_files`] lists the files changed since a commit or tag, with
+//! their content at that commit, for `import --since`. It only reads the
+//! repository, which it does with libgit2; it is the one function here that
+//! does not need the `git` executable.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use super::attribution::{current_author, Author};
+use super::secrets::shell_command;
+
+/// 运行 git 命令，不检查退出码（只有 git 无法启动时返回错误）
+///
+/// 固定使用英文消息，以便识别 `nothing to commit`。
+fn run_git(dir: &Path, args: &[&str], paths: &[PathBuf]) -> Result<Output> {
+    Command::new("git")
+        .current_dir(dir)
+        .env("LC_ALL", "C")
+        .args(args)
+        .args(paths)
+        .output()
+        .context("Failed to run git (is it installed?)")
+}
+
+/// git 失败的错误（包含 stderr）
+fn git_error(args: &[&str], output: &Output) -> anyhow::Error {
+    anyhow::anyhow!(
+        "git {} failed: {}",
+        args.first().copied().unwrap_or_default(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+}
+
+/// 运行 git 命令，退出码不为 0 时返回包含 stderr 的错误
+fn git(dir: &Path, args: &[&str], paths: &[PathBuf]) -> Result<Output> {
+    let output = run_git(dir, args, paths)?;
+    if !output.status.success() {
+        return Err(git_error(args, &output));
+    }
+    Ok(output)
+}
+
+/// 运行以退出码作答的 git 命令（`diff --quiet`、`rev-parse --verify`）
+///
+/// 退出码 0 返回 `true`，1 返回 `false`，其他退出码返回错误。
+fn git_test(dir: &Path, args: &[&str], paths: &[PathBuf]) -> Result<bool> {
+    let output = run_git(dir, args, paths)?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => Err(git_error(args, &output)),
+    }
+}
+
+/// 提交指定文件
+///
+/// 暂存 `paths` 并只提交这些文件（`git commit -- <paths>`），
+/// 其他已暂存的修改不受影响。
+///
+/// # 参数
+///
+/// * `dir` - 仓库内的任意目录（通常为 messages 目录）
+/// * `paths` - 要提交的文件
+/// * `message` - 提交信息
+/// * `author` - 显式指定的作者（`--author`），未指定时见 [`current_author`]
+/// * `sign` - 是否签名提交（`git commit -S`）
+///
+/// # Returns
+///
+/// 新提交的哈希；这些文件与 HEAD 相同、没有可提交的内容时返回 `None`
+///
+/// # Errors
+///
+/// `dir` 不在 git 仓库中、git 不可用或提交失败（如签名失败）时返回错误
+pub fn commit_files(
+    dir: &Path,
+    paths: &[PathBuf],
+    message: &str,
+    author: Option<&Author>,
+    sign: bool,
+) -> Result<Option<String>> {
+    // 1. 确认在 git 仓库中
+    let inside = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .output()
+        .context("Failed to run git (is it installed?)")?;
+    if !inside.status.success() || String::from_utf8_lossy(&inside.stdout).trim() != "true" {
+        return Err(anyhow::anyhow!("{} is not inside a git work tree", dir.display()));
+    }
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    // git 在 dir 中运行，路径统一转为绝对路径
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| std::path::absolute(path).unwrap_or_else(|_| path.clone()))
+        .collect();
+
+    // 2. 暂存文件，检查相对 HEAD 是否有修改
+    git(dir, &["add", "--"], &paths)?;
+    let has_head = git_test(dir, &["rev-parse", "--verify", "--quiet", "HEAD"], &[])?;
+    if has_head && git_test(dir, &["diff", "--cached", "--quiet", "HEAD", "--"], &paths)? {
+        return Ok(None);
+    }
+
+    // 3. 只提交这些文件
+    let author = current_author(author)
+        .filter(|author| author.email.is_some())
+        .map(|author| format!("--author={}", author));
+    let mut args = vec!["commit", "--quiet", "-m", message];
+    if let Some(author) = &author {
+        args.push(author);
+    }
+    if sign {
+        args.push("-S");
+    }
+    args.push("--");
+    let commit = run_git(dir, &args, &paths)?;
+    if !commit.status.success() {
+        // git commit 以退出码 1 报告没有可提交的内容，不是错误
+        if String::from_utf8_lossy(&commit.stdout).contains("nothing to commit") {
+            return Ok(None);
+        }
+        return Err(anyhow::anyhow!(
+            "git commit failed: {}",
+            String::from_utf8_lossy(&commit.stderr).trim()
+        ));
+    }
+
+    let head = git(dir, &["rev-parse", "HEAD"], &[])?;
+    Ok(Some(String::from_utf8_lossy(&head.stdout).trim().to_string()))
+}
+
+/// 自某个提交以来修改过的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// 文件的绝对路径
+    pub path: PathBuf,
+    /// 文件在该提交中的内容（新增的文件为 None）
+    pub previous: Option<String>,
+}
+
+/// 一组目录下自 `since`（提交、标签或分支）以来修改过的文件
+///
+/// 包括已提交、已暂存和未暂存的修改以及未跟踪（未被忽略）的文件；
+/// 已删除的文件不在结果中。通过 libgit2 读取，不需要安装 git。
+///
+/// # 参数
+///
+/// * `dir` - 仓库内的任意目录（通常为 messages 目录）
+/// * `paths` - 要比较的目录（messages 目录、`languageDirs` 和覆盖层目录）
+/// * `since` - 提交、标签或分支
+///
+/// # Errors
+///
+/// `dir` 或 `paths` 不在 git 仓库中、`since` 不是有效的提交时返回错误
+pub fn changed_files(dir: &Path, paths: &[PathBuf], since: &str) -> Result<Vec<ChangedFile>> {
+    // 1. 打开仓库，解析 since 指向的提交
+    let repo = git2::Repository::discover(dir)
+        .map_err(|_| anyhow::anyhow!("{} is not inside a git work tree", dir.display()))?;
+    let root = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("{} is not inside a git work tree", dir.display()))?
+        .to_path_buf();
+    let tree = repo
+        .revparse_single(since)
+        .and_then(|object| object.peel_to_commit())
+        .and_then(|commit| commit.tree())
+        .map_err(|_| anyhow::anyhow!("Unknown git revision '{}'", since))?;
+
+    // 2. 目录转为相对仓库根目录的路径（按字面匹配，不作为通配符）
+    let canonical_root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", root.display()))?;
+    let mut pathspecs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let canonical = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        let relative = canonical
+            .strip_prefix(&canonical_root)
+            .map_err(|_| anyhow::anyhow!("{} is not inside the git work tree {}", path.display(), root.display()))?;
+        pathspecs.push(relative.to_string_lossy().replace('\\', "/"));
+    }
+    let mut options = git2::DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .disable_pathspec_match(true);
+    // 包含仓库根目录时比较所有文件
+    if !pathspecs.iter().any(String::is_empty) {
+        for pathspec in pathspecs {
+            options.pathspec(pathspec);
+        }
+    }
+
+    // 3. 与 since 相比修改过和未跟踪的文件
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+        .with_context(|| format!("Failed to diff against '{}'", since))?;
+    let mut changed = Vec::new();
+    for delta in diff.deltas() {
+        let previous = match delta.status() {
+            git2::Delta::Deleted => continue,
+            git2::Delta::Added | git2::Delta::Untracked => None,
+            _ => {
+                let blob = repo
+                    .find_blob(delta.old_file().id())
+                    .with_context(|| format!("Failed to read the '{}' version of a changed file", since))?;
+                Some(String::from_utf8_lossy(blob.content()).to_string())
+            }
+        };
+        let Some(path) = delta.new_file().path() else {
+            continue;
+        };
+        changed.push(ChangedFile {
+            path: root.join(path),
+            previous,
+        });
+    }
+    Ok(changed)
+}
+
+/// 当前分支名（分离 HEAD 时返回错误）
+pub fn current_branch(dir: &Path) -> Result<String> {
+    let args = ["symbolic-ref", "--quiet", "--short", "HEAD"];
+    let output = run_git(dir, &args, &[])?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match output.status.code() {
+        Some(0) if !branch.is_empty() => Ok(branch),
+        Some(0 | 1) => Err(anyhow::anyhow!("HEAD is detached; check out a branch first")),
+        _ => Err(git_error(&args, &output)),
+    }
+}
+
+/// 从当前提交创建并切换到新分支（保留工作区的修改）
+pub fn create_branch(dir: &Path, name: &str) -> Result<()> {
+    let output = run_git(dir, &["checkout", "--quiet", "-b", name], &[])?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to create branch {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// 切换回 `branch` 并删除 `name` 分支（用于没有提交的运行）
+pub fn discard_branch(dir: &Path, name: &str, branch: &str) -> Result<()> {
+    git(dir, &["checkout", "--quiet", branch], &[])?;
+    git(dir, &["branch", "--quiet", "-D", name], &[])?;
+    Ok(())
+}
+
+/// 推送分支并设置上游
+pub fn push_branch(dir: &Path, remote: &str, branch: &str) -> Result<()> {
+    let output = run_git(dir, &["push", "--quiet", "--set-upstream", remote, branch], &[])?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to push {} to {}: {}",
+            branch,
+            remote,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// 远程仓库的 URL
+pub fn remote_url(dir: &Path, remote: &str) -> Result<String> {
+    let output = run_git(dir, &["remote", "get-url", remote], &[])?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Unknown git remote '{}'", remote));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 根据远程 URL 生成创建拉取请求的页面地址
+///
+/// 支持 GitHub、GitLab 和 Bitbucket 的 HTTPS 与 SSH 地址
+/// （`git@github.com:org/repo.git`、`ssh://git@gitlab.com/org/repo.git`），
+/// 无法识别的主机返回 `None`。
+pub fn pull_request_url(remote_url: &str, base: &str, branch: &str) -> Option<String> {
+    let url = remote_url.trim().trim_end_matches('/').trim_end_matches(".git");
+    // 去掉协议和用户名，得到 host/path
+    let location = match url.split_once("://") {
+        Some((_, rest)) => rest.rsplit_once('@').map_or(rest, |(_, rest)| rest).to_string(),
+        None => {
+            let (host, path) = url.rsplit_once('@').map_or(url, |(_, rest)| rest).split_once(':')?;
+            format!("{}/{}", host, path)
+        }
+    };
+    let (host, path) = location.split_once('/')?;
+    // 去掉端口（ssh://git@host:22/org/repo）
+    let host = host.split(':').next()?;
+    let repo = format!("https://{}/{}", host, path);
+
+    if host.contains("github") {
+        Some(format!("{}/compare/{}...{}?expand=1", repo, base, branch))
+    } else if host.contains("gitlab") {
+        Some(format!(
+            "{}/-/merge_requests/new?merge_request[source_branch]={}&merge_request[target_branch]={}",
+            repo, branch, base
+        ))
+    } else if host.contains("bitbucket") {
+        Some(format!("{}/pull-requests/new?source={}&dest={}", repo, branch, base))
+    } else {
+        None
+    }
+}
+
+/// `prCommand` 的占位符和传递其值的环境变量
+const PR_COMMAND_VARS: [(&str, &str); 4] = [
+    ("{branch}", "YFLOW_BRANCH"),
+    ("{base}", "YFLOW_BASE"),
+    ("{remote}", "YFLOW_REMOTE"),
+    ("{url}", "YFLOW_PR_URL"),
+];
+
+/// 运行配置的 `prCommand`
+///
+/// 分支名、基础分支、远程名和地址（无法生成时为空）通过环境变量
+/// `YFLOW_BRANCH`、`YFLOW_BASE`、`YFLOW_REMOTE` 和 `YFLOW_PR_URL` 传给命令；
+/// 命令中的 `{branch}`、`{base}`、`{remote}` 和 `{url}` 替换为对这些变量的
+/// 引用（带引号），值本身不会被 shell 解析。返回命令的标准输出
+/// （如 `gh pr create` 打印的地址）。
+pub fn run_pr_command(
+    dir: &Path,
+    command: &str,
+    branch: &str,
+    base: &str,
+    remote: &str,
+    url: Option<&str>,
+) -> Result<String> {
+    let values = [branch, base, remote, url.unwrap_or_default()];
+    let mut shell = String::from(command);
+    for (placeholder, var) in PR_COMMAND_VARS {
+        let reference = if cfg!(windows) {
+            format!("\"%{}%\"", var)
+        } else {
+            format!("\"${}\"", var)
+        };
+        shell = shell.replace(placeholder, &reference);
+    }
+    let output = shell_command(&shell)
+        .current_dir(dir)
+        .envs(PR_COMMAND_VARS.iter().map(|(_, var)| *var).zip(values))
+        .output()
+        .with_context(|| format!("Failed to run prCommand: {}", command))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "prCommand failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git").current_dir(dir).args(args).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_commit_files_only_commits_given_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert!(commit_files(dir, &[], "sync", None, false).is_err());
+
+        run(dir, &["init", "--quiet"]);
+        run(dir, &["config", "user.name", "Bot"]);
+        run(dir, &["config", "user.email", "bot@example.com"]);
+        std::fs::create_dir_all(dir.join("messages/en")).unwrap();
+        std::fs::write(dir.join("messages/en/common.json"), "{}").unwrap();
+        std::fs::write(dir.join("unrelated.txt"), "staged").unwrap();
+        run(dir, &["add", "unrelated.txt"]);
+
+        let file = dir.join("messages/en/common.json");
+        let commit = commit_files(&dir.join("messages"), std::slice::from_ref(&file), "chore: sync", None, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(run(dir, &["rev-parse", "HEAD"]), commit);
+        assert_eq!(run(dir, &["log", "-1", "--format=%s"]), "chore: sync");
+        assert_eq!(run(dir, &["show", "--name-only", "--format=", "HEAD"]), "messages/en/common.json");
+        // 用户已暂存的文件仍在暂存区
+        assert_eq!(run(dir, &["diff", "--cached", "--name-only"]), "unrelated.txt");
+
+        // 没有修改时不创建提交
+        assert_eq!(commit_files(dir, &[file], "chore: sync", None, false).unwrap(), None);
+    }
+
+    #[test]
+    fn test_git_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        run(dir, &["init", "--quiet"]);
+        run(dir, &["config", "user.name", "Bot"]);
+        run(dir, &["config", "user.email", "bot@example.com"]);
+        let file = dir.join("en.json");
+        std::fs::write(&file, "{}").unwrap();
+        commit_files(dir, std::slice::from_ref(&file), "init", None, false).unwrap().unwrap();
+
+        // 只有退出码 0 算成功
+        std::fs::write(&file, r#"{"a": "A"}"#).unwrap();
+        assert!(git(dir, &["diff", "--quiet"], &[]).is_err());
+        assert!(!git_test(dir, &["diff", "--quiet"], &[]).unwrap());
+        assert!(git_test(dir, &["rev-parse", "--verify", "--quiet", "no-such-ref"], &[]).is_ok());
+        let outside = TempDir::new().unwrap();
+        assert!(git_test(outside.path(), &["rev-parse", "--verify", "--quiet", "HEAD"], &[]).is_err());
+        assert!(git(dir, &["checkout", "--quiet", "no-such-branch"], &[]).is_err());
+
+        // 分离 HEAD
+        run(dir, &["checkout", "--quiet", "--detach"]);
+        assert!(current_branch(dir).unwrap_err().to_string().contains("detached"));
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        run(dir, &["init", "--quiet"]);
+        run(dir, &["config", "user.name", "Bot"]);
+        run(dir, &["config", "user.email", "bot@example.com"]);
+        std::fs::create_dir_all(dir.join("messages/en")).unwrap();
+        for file in ["common.json", "same.json", "gone.json"] {
+            std::fs::write(dir.join("messages/en").join(file), r#"{"a": "A"}"#).unwrap();
+        }
+        std::fs::write(dir.join("outside.json"), "{}").unwrap();
+        // languageDirs 和覆盖层目录在 messages 目录之外
+        std::fs::create_dir_all(dir.join("locales/de")).unwrap();
+        std::fs::write(dir.join("locales/de/common.json"), r#"{"a": "A"}"#).unwrap();
+        std::fs::create_dir_all(dir.join("brand/en")).unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "--quiet", "-m", "base"]);
+        run(dir, &["tag", "v1"]);
+
+        std::fs::write(dir.join("messages/en/common.json"), r#"{"a": "B"}"#).unwrap();
+        std::fs::remove_file(dir.join("messages/en/gone.json")).unwrap();
+        std::fs::write(dir.join("messages/en/new.json"), "{}").unwrap();
+        std::fs::write(dir.join("outside.json"), r#"{"x": 1}"#).unwrap();
+        std::fs::write(dir.join("locales/de/common.json"), r#"{"a": "B"}"#).unwrap();
+        std::fs::write(dir.join("brand/en/common.json"), "{}").unwrap();
+
+        let messages = dir.join("messages");
+        let paths = [messages.clone(), dir.join("locales/de"), dir.join("brand")];
+        let mut changed = changed_files(&messages, &paths, "v1").unwrap();
+        changed.sort_by(|a, b| a.path.cmp(&b.path));
+        let names: Vec<String> = changed
+            .iter()
+            .map(|file| file.path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            names,
+            ["brand/en/common.json", "locales/de/common.json", "messages/en/common.json", "messages/en/new.json"]
+        );
+        assert_eq!(changed[0].previous, None);
+        assert_eq!(changed[1].previous.as_deref(), Some(r#"{"a": "A"}"#));
+        assert_eq!(changed[2].previous.as_deref(), Some(r#"{"a": "A"}"#));
+        assert_eq!(changed[3].previous, None);
+
+        // 只比较给定的目录
+        let changed = changed_files(&messages, std::slice::from_ref(&messages), "v1").unwrap();
+        assert_eq!(changed.len(), 2);
+
+        assert!(changed_files(&messages, &paths, "no-such-ref").is_err());
+    }
+
+    #[test]
+    fn test_branch_commit_and_push() {
+        let temp_dir = TempDir::new().unwrap();
+        let remote = temp_dir.path().join("remote.git");
+        let dir = temp_dir.path().join("work");
+        std::fs::create_dir_all(&dir).unwrap();
+        run(temp_dir.path(), &["init", "--quiet", "--bare", "remote.git"]);
+        run(&dir, &["init", "--quiet"]);
+        run(&dir, &["config", "user.name", "Bot"]);
+        run(&dir, &["config", "user.email", "bot@example.com"]);
+        run(&dir, &["remote", "add", "origin", remote.to_str().unwrap()]);
+        let file = dir.join("en.json");
+        std::fs::write(&file, "{}").unwrap();
+        commit_files(&dir, std::slice::from_ref(&file), "init", None, false).unwrap();
+        let base = current_branch(&dir).unwrap();
+
+        std::fs::write(&file, r#"{"a": "A"}"#).unwrap();
+        create_branch(&dir, "yflow/sync-1").unwrap();
+        assert!(commit_files(&dir, std::slice::from_ref(&file), "chore: sync", None, false).unwrap().is_some());
+        push_branch(&dir, "origin", "yflow/sync-1").unwrap();
+        assert_eq!(current_branch(&dir).unwrap(), "yflow/sync-1");
+        assert!(run(&remote, &["branch", "--list"]).contains("yflow/sync-1"));
+        assert_eq!(remote_url(&dir, "origin").unwrap(), remote.to_str().unwrap());
+        assert!(remote_url(&dir, "upstream").is_err());
+
+        let output = run_pr_command(&dir, "echo {remote} {base}..{branch}", "yflow/sync-1", &base, "origin", None).unwrap();
+        assert_eq!(output, format!("origin {}..yflow/sync-1", base));
+        // 值不经过 shell 解析
+        let output = run_pr_command(&dir, "echo {base}", "b", "x;touch pwned$(touch pwned)", "origin", None).unwrap();
+        assert_eq!(output, "x;touch pwned$(touch pwned)");
+        assert!(!dir.join("pwned").exists());
+        let output = run_pr_command(&dir, "echo {remote} {url}", "b", "main", "origin", Some("https://x/?a=1&b=2")).unwrap();
+        assert_eq!(output, "origin https://x/?a=1&b=2");
+
+        create_branch(&dir, "yflow/sync-2").unwrap();
+        discard_branch(&dir, "yflow/sync-2", "yflow/sync-1").unwrap();
+        assert!(!run(&dir, &["branch", "--list"]).contains("yflow/sync-2"));
+    }
+
+    #[test]
+    fn test_pull_request_url() {
+        assert_eq!(
+            pull_request_url("git@github.com:acme/web.git", "main", "yflow/sync-1").as_deref(),
+            Some("https://github.com/acme/web/compare/main...yflow/sync-1?expand=1")
+        );
+        assert_eq!(
+            pull_request_url("https://token@github.com/acme/web", "main", "b").as_deref(),
+            Some("https://github.com/acme/web/compare/main...b?expand=1")
+        );
+        assert_eq!(
+            pull_request_url("ssh://git@gitlab.example.com:2222/group/sub/app.git", "main", "b").as_deref(),
+            Some("https://gitlab.example.com/group/sub/app/-/merge_requests/new?merge_request[source_branch]=b&merge_request[target_branch]=main")
+        );
+        assert_eq!(
+            pull_request_url("https://bitbucket.org/acme/web.git", "main", "b").as_deref(),
+            Some("https://bitbucket.org/acme/web/pull-requests/new?source=b&dest=main")
+        );
+        assert_eq!(pull_request_url("/srv/git/web.git", "main", "b"), None);
+        assert_eq!(pull_request_url("https://git.example.com/acme/web", "main", "b"), None);
+    }
+}
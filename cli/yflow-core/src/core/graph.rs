@@ -0,0 +1,310 @@
+//! Key reference graph
+//!
+//! Builds a graph of translation domains: which files define keys in which
+//! namespaces, and which source files reference them. A key's namespace is
+//! its first key segment (`checkout.button.pay` → `checkout`); single-segment
+//! keys, including escaped ones like `legal\.notice`, belong to the `(root)`
+//! namespace.
+//!
+//! Source references are found by looking for string literals that equal a
+//! known key, so no extraction step is needed.
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::key_path::KeyPath;
+use super::scanner::walk_files;
+
+/// 没有点号的键所属的命名空间
+pub const ROOT_NAMESPACE: &str = "(root)";
+
+/// 扫描源码引用时读取的文件扩展名
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "js", "jsx", "ts", "tsx", "mjs", "cjs", "vue", "svelte", "html", "rs", "go", "py", "rb",
+    "php", "java", "kt", "swift", "dart",
+];
+
+/// 节点类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// 命名空间
+    Namespace,
+    /// 翻译文件
+    File,
+    /// 源码文件
+    Source,
+}
+
+/// 边类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// 翻译文件定义了命名空间中的键
+    Defines,
+    /// 源码引用了命名空间中的键
+    References,
+}
+
+/// 图节点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Node {
+    /// 节点 ID（命名空间名或文件路径）
+    pub id: String,
+    /// 节点类型
+    pub kind: NodeKind,
+    /// 关联的键数
+    pub keys: usize,
+}
+
+/// 图的边
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    /// 起点节点 ID
+    pub from: String,
+    /// 终点节点 ID（命名空间）
+    pub to: String,
+    /// 边类型
+    pub kind: EdgeKind,
+    /// 涉及的键数
+    pub weight: usize,
+}
+
+/// 键引用图
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct KeyGraph {
+    /// 节点
+    pub nodes: Vec<Node>,
+    /// 边
+    pub edges: Vec<Edge>,
+}
+
+/// 键所属的命名空间（按 `separator` 分段的第一段）
+pub fn namespace_of(key: &str, separator: &str) -> String {
+    match KeyPath::parse(key, separator).into_segments().as_slice() {
+        [namespace, _, ..] if !namespace.is_empty() => namespace.clone(),
+        _ => ROOT_NAMESPACE.to_string(),
+    }
+}
+
+fn string_literal() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"["'`]([A-Za-z0-9_.:\-]+)["'`]"#).expect("valid regex"))
+}
+
+/// 图构建器
+#[derive(Debug, Default)]
+pub struct GraphBuilder {
+    /// 命名空间 -> 键集合
+    namespaces: BTreeMap<String, HashSet<String>>,
+    /// (节点, 命名空间, 边类型) -> 键集合
+    links: BTreeMap<(String, NodeKind, String), HashSet<String>>,
+    /// 所有已知键
+    keys: HashSet<String>,
+    /// 键分隔符
+    separator: String,
+}
+
+impl GraphBuilder {
+    /// 创建构建器，键按 `separator` 划分命名空间
+    pub fn new(separator: &str) -> Self {
+        Self {
+            separator: separator.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// 添加一个翻译文件定义的键
+    pub fn add_file<'a>(&mut self, file: &str, keys: impl IntoIterator<Item = &'a String>) {
+        for key in keys {
+            let namespace = namespace_of(key, &self.separator);
+            self.namespaces.entry(namespace.clone()).or_default().insert(key.clone());
+            self.links
+                .entry((file.to_string(), NodeKind::File, namespace))
+                .or_default()
+                .insert(key.clone());
+            self.keys.insert(key.clone());
+        }
+    }
+
+    /// 添加一段源码中的键引用
+    pub fn add_source(&mut self, source: &str, content: &str) {
+        for caps in string_literal().captures_iter(content) {
+            let key = &caps[1];
+            if !self.keys.contains(key) {
+                continue;
+            }
+            self.links
+                .entry((source.to_string(), NodeKind::Source, namespace_of(key, &self.separator)))
+                .or_default()
+                .insert(key.to_string());
+        }
+    }
+
+    /// 扫描源码目录中的引用（遵守 `.gitignore`）
+    ///
+    /// 必须在添加完所有翻译文件之后调用。
+    ///
+    /// # Returns
+    ///
+    /// 扫描的源码文件数
+    pub fn add_source_dir(&mut self, dir: &Path) -> Result<usize> {
+        let mut scanned = 0;
+        for path in walk_files(dir)? {
+            let is_source = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| SOURCE_EXTENSIONS.contains(&e))
+                .unwrap_or(false);
+            if !is_source {
+                continue;
+            }
+            // 跳过非 UTF-8 文件
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            self.add_source(&display_path(&path), &content);
+            scanned += 1;
+        }
+        Ok(scanned)
+    }
+
+    /// 生成图
+    pub fn build(self) -> KeyGraph {
+        let mut graph = KeyGraph::default();
+
+        for (namespace, keys) in &self.namespaces {
+            graph.nodes.push(Node {
+                id: namespace.clone(),
+                kind: NodeKind::Namespace,
+                keys: keys.len(),
+            });
+        }
+
+        let mut node_keys: BTreeMap<(NodeKind, String), usize> = BTreeMap::new();
+        for ((node, kind, namespace), keys) in &self.links {
+            *node_keys.entry((*kind, node.clone())).or_default() += keys.len();
+            graph.edges.push(Edge {
+                from: node.clone(),
+                to: namespace.clone(),
+                kind: match kind {
+                    NodeKind::Source => EdgeKind::References,
+                    _ => EdgeKind::Defines,
+                },
+                weight: keys.len(),
+            });
+        }
+        for ((kind, id), keys) in node_keys {
+            graph.nodes.push(Node { id, kind, keys });
+        }
+
+        graph
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+impl KeyGraph {
+    /// 输出 Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph yflow {\n  rankdir=LR;\n  node [fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Namespace => "ellipse",
+                NodeKind::File => "note",
+                NodeKind::Source => "box",
+            };
+            out.push_str(&format!(
+                "  {} [label={}, shape={}];\n",
+                dot_id(node.kind, &node.id),
+                dot_quote(&format!("{}\n({} keys)", node.id, node.keys)),
+                shape
+            ));
+        }
+        for edge in &self.edges {
+            let from_kind = match edge.kind {
+                EdgeKind::Defines => NodeKind::File,
+                EdgeKind::References => NodeKind::Source,
+            };
+            let style = match edge.kind {
+                EdgeKind::Defines => "solid",
+                EdgeKind::References => "dashed",
+            };
+            out.push_str(&format!(
+                "  {} -> {} [label=\"{}\", style={}];\n",
+                dot_id(from_kind, &edge.from),
+                dot_id(NodeKind::Namespace, &edge.to),
+                edge.weight,
+                style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// 节点 ID 加类型前缀，避免命名空间与文件同名时冲突
+fn dot_id(kind: NodeKind, id: &str) -> String {
+    let prefix = match kind {
+        NodeKind::Namespace => "ns",
+        NodeKind::File => "file",
+        NodeKind::Source => "src",
+    };
+    dot_quote(&format!("{}:{}", prefix, id))
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_namespace_of() {
+        assert_eq!(namespace_of("checkout.button.pay", "."), "checkout");
+        assert_eq!(namespace_of("title", "."), ROOT_NAMESPACE);
+        assert_eq!(namespace_of(".hidden", "."), ROOT_NAMESPACE);
+        assert_eq!(namespace_of(r"legal\.notice", "."), ROOT_NAMESPACE);
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let mut builder = GraphBuilder::new(".");
+        builder.add_file("en/common.json", &keys(&["common.ok", "common.cancel", "title"]));
+        builder.add_file("en/checkout.json", &keys(&["checkout.pay"]));
+        builder.add_source("src/Pay.tsx", r#"t("checkout.pay"); t('common.ok'); t("unknown.key")"#);
+
+        let graph = builder.build();
+        let namespaces: Vec<(&str, usize)> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Namespace)
+            .map(|n| (n.id.as_str(), n.keys))
+            .collect();
+        assert_eq!(namespaces, vec![("(root)", 1), ("checkout", 1), ("common", 2)]);
+
+        let references: Vec<&Edge> = graph.edges.iter().filter(|e| e.kind == EdgeKind::References).collect();
+        assert_eq!(references.len(), 2);
+        assert!(references.iter().all(|e| e.from == "src/Pay.tsx"));
+
+        let source = graph.nodes.iter().find(|n| n.kind == NodeKind::Source).unwrap();
+        assert_eq!(source.keys, 2);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph yflow {"));
+        assert!(dot.contains("\"file:en/common.json\" -> \"ns:common\" [label=\"2\", style=solid];"));
+        assert!(dot.contains("\"src:src/Pay.tsx\" -> \"ns:checkout\" [label=\"1\", style=dashed];"));
+    }
+}
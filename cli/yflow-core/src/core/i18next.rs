@@ -0,0 +1,225 @@
+//! i18next key conventions
+//!
+//! With `"dialect": "i18next"` keys follow i18next's suffix conventions:
+//! the plural forms of a key are siblings named `<key>_<category>`
+//! (`item_one`, `item_other`, ordinal forms `place_ordinal_one`) and context
+//! variants are `<key>_<context>` (`friend_male`, `friend_male_one`).
+//!
+//! All forms of a key make up one group: the scanner writes a new form to
+//! the file already holding its siblings (see [`super::namespaces`]), and
+//! `validate` reports plural groups missing a category the language needs
+//! (`ru` needs `one`, `few`, `many` and `other`). `_zero` is always allowed;
+//! ordinal forms are grouped but not checked.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// 复数类别（CLDR）
+pub const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// 键的命名约定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dialect {
+    /// 键没有特殊含义
+    #[default]
+    Plain,
+    /// i18next 的复数和上下文后缀
+    I18next,
+}
+
+impl Dialect {
+    /// 是否为默认的普通键
+    pub fn is_plain(&self) -> bool {
+        *self == Self::Plain
+    }
+}
+
+/// 复数形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluralForm<'a> {
+    /// 复数类别（`one`、`other` 等）
+    pub category: &'a str,
+    /// 是否为序数形式（`_ordinal_one`）
+    pub ordinal: bool,
+}
+
+/// 拆分键的复数后缀
+///
+/// # Returns
+///
+/// (去掉复数后缀的键, 复数形式)，没有复数后缀时为 (键, None)
+pub fn split_plural(key: &str) -> (&str, Option<PluralForm<'_>>) {
+    let Some((rest, category)) = key.rsplit_once('_') else {
+        return (key, None);
+    };
+    if rest.is_empty() || !PLURAL_CATEGORIES.contains(&category) {
+        return (key, None);
+    }
+    match rest.strip_suffix("_ordinal") {
+        Some(base) if !base.is_empty() => (base, Some(PluralForm { category, ordinal: true })),
+        _ => (rest, Some(PluralForm { category, ordinal: false })),
+    }
+}
+
+/// 键所属的组
+///
+/// 去掉复数后缀；剩余部分是 `known` 中某个组的上下文变体
+/// （`friend_male` 且存在 `friend`）时为该组。
+pub fn group_of<'a>(key: &'a str, known: &HashSet<&str>) -> &'a str {
+    let (stem, _) = split_plural(key);
+    match stem.rsplit_once('_') {
+        Some((base, _)) if !base.is_empty() && known.contains(base) => base,
+        _ => stem,
+    }
+}
+
+/// 将键按组归类
+///
+/// # Returns
+///
+/// 组 -> 该组的键（排序）
+pub fn group_keys<'a>(keys: impl IntoIterator<Item = &'a str>) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let keys: Vec<&str> = keys.into_iter().collect();
+    let stems: HashSet<&str> = keys.iter().map(|key| split_plural(key).0).collect();
+    let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for key in keys {
+        groups.entry(group_of(key, &stems)).or_default().push(key);
+    }
+    for members in groups.values_mut() {
+        members.sort_unstable();
+    }
+    groups
+}
+
+/// 语言需要的复数类别（CLDR 基数规则）
+///
+/// 按主语言子标签查找（`pt_BR` -> `pt`），未知语言为 `one`、`other`。
+pub fn plural_categories(lang: &str) -> &'static [&'static str] {
+    let primary = lang.split(['_', '-']).next().unwrap_or(lang).to_lowercase();
+    match primary.as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "my" | "km" => &["other"],
+        "fr" | "es" | "it" | "pt" | "ca" => &["one", "many", "other"],
+        "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "lt" => &["one", "few", "many", "other"],
+        "ro" | "hr" | "sr" | "bs" => &["one", "few", "other"],
+        "he" => &["one", "two", "other"],
+        "lv" => &["zero", "one", "other"],
+        "sl" => &["one", "two", "few", "other"],
+        "ga" => &["one", "two", "few", "many", "other"],
+        "ar" | "cy" => &["zero", "one", "two", "few", "many", "other"],
+        _ => &["one", "other"],
+    }
+}
+
+/// 缺少复数形式的组
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluralGap {
+    /// 去掉复数后缀的键（包含上下文）
+    pub key: String,
+    /// 该组已有的第一个复数形式的键
+    pub first: String,
+    /// 缺少的复数类别
+    pub missing: Vec<&'static str>,
+}
+
+/// 检查一个语言的复数形式是否完整
+///
+/// 每个带基数复数后缀的键（包括上下文变体）都必须具备语言需要的所有类别。
+///
+/// # Returns
+///
+/// 按键排序的缺口
+pub fn plural_gaps<'a>(lang: &str, keys: impl IntoIterator<Item = &'a str>) -> Vec<PluralGap> {
+    // 去掉复数后缀的键 -> 已有的类别及键
+    let mut plurals: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+    for key in keys {
+        if let (stem, Some(form)) = split_plural(key) {
+            if !form.ordinal {
+                plurals.entry(stem).or_default().insert(form.category, key);
+            }
+        }
+    }
+    let required = plural_categories(lang);
+    plurals
+        .into_iter()
+        .filter_map(|(stem, present)| {
+            let missing: Vec<&'static str> =
+                required.iter().copied().filter(|category| !present.contains_key(category)).collect();
+            if missing.is_empty() {
+                return None;
+            }
+            Some(PluralGap {
+                key: stem.to_string(),
+                first: present.values().min().copied().unwrap_or_default().to_string(),
+                missing,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_plural() {
+        assert_eq!(split_plural("item_one"), ("item", Some(PluralForm { category: "one", ordinal: false })));
+        assert_eq!(
+            split_plural("cart.place_ordinal_few"),
+            ("cart.place", Some(PluralForm { category: "few", ordinal: true }))
+        );
+        assert_eq!(split_plural("friend_male_other").0, "friend_male");
+        assert_eq!(split_plural("button_label"), ("button_label", None));
+        assert_eq!(split_plural("_one"), ("_one", None));
+        assert_eq!(split_plural("title"), ("title", None));
+    }
+
+    #[test]
+    fn test_group_keys() {
+        let groups = group_keys([
+            "friend",
+            "friend_male",
+            "friend_female_one",
+            "friend_female_other",
+            "item_one",
+            "item_other",
+            "button_label",
+        ]);
+        assert_eq!(
+            groups,
+            BTreeMap::from([
+                ("button_label", vec!["button_label"]),
+                ("friend", vec!["friend", "friend_female_one", "friend_female_other", "friend_male"]),
+                ("item", vec!["item_one", "item_other"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_plural_gaps() {
+        let keys = ["item_one", "item_other", "friend_male_one", "place_ordinal_one", "title"];
+        let gaps: Vec<String> = plural_gaps("en", keys).into_iter().map(|gap| gap.key).collect();
+        assert_eq!(gaps, ["friend_male"]);
+        assert_eq!(
+            plural_gaps("ru", keys),
+            vec![
+                PluralGap {
+                    key: "friend_male".to_string(),
+                    first: "friend_male_one".to_string(),
+                    missing: vec!["few", "many", "other"],
+                },
+                PluralGap {
+                    key: "item".to_string(),
+                    first: "item_one".to_string(),
+                    missing: vec!["few", "many"],
+                },
+            ]
+        );
+        // 只有 other 的语言
+        assert!(plural_gaps("zh_CN", ["item_other"]).is_empty());
+        // _zero 总是允许
+        assert!(plural_gaps("de", ["item_zero", "item_one", "item_other"]).is_empty());
+        assert_eq!(plural_categories("pt-BR"), ["one", "many", "other"]);
+    }
+}
@@ -0,0 +1,380 @@
+//! ICU MessageFormat helpers
+//!
+//! A lightweight parser for ICU message syntax, used to find the arguments
+//! a message expects (`{name}`, `{count, plural, ...}`, `{gender, select, ...}`)
+//! and to derive sample argument sets that exercise every branch.
+//!
+//! Only the structure is parsed; formatting is left to the host project's
+//! ICU implementation.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// 参数类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgKind {
+    /// 普通文本参数（`{name}`）
+    Plain,
+    /// 数值参数（`plural` / `selectordinal` / `number`）
+    Number {
+        /// 显式写出的精确值（`=0`、`=1` 等）
+        exact: Vec<String>,
+    },
+    /// 选择参数（`select`），包含全部选项（不含 `other`）
+    Select(Vec<String>),
+    /// 日期时间参数（`date` / `time`）
+    DateTime,
+}
+
+/// 示例参数值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SampleValue {
+    /// 文本
+    Text(String),
+    /// 数字
+    Number(i64),
+    /// UNIX 时间戳（毫秒）
+    Timestamp(i64),
+}
+
+impl SampleValue {
+    /// 转为字符串形式
+    pub fn as_string(&self) -> String {
+        match self {
+            SampleValue::Text(s) => s.clone(),
+            SampleValue::Number(n) | SampleValue::Timestamp(n) => n.to_string(),
+        }
+    }
+}
+
+/// 一组示例参数（按参数名排序）
+pub type SampleArgs = BTreeMap<String, SampleValue>;
+
+/// 判断值是否包含 ICU 参数
+pub fn is_icu_message(value: &str) -> bool {
+    parse_arguments(value)
+        .map(|args| !args.is_empty())
+        .unwrap_or(true)
+}
+
+/// 解析消息中的参数
+///
+/// # Returns
+///
+/// 按首次出现顺序排列的 (参数名, 类型)，同名参数合并
+///
+/// # Errors
+///
+/// 括号不匹配或复杂参数格式错误时返回错误
+pub fn parse_arguments(message: &str) -> Result<Vec<(String, ArgKind)>> {
+    let chars: Vec<char> = message.chars().collect();
+    let mut args = Vec::new();
+    let mut pos = 0;
+    parse_message(&chars, &mut pos, &mut args, false)?;
+    if pos < chars.len() {
+        return Err(anyhow::anyhow!("Unmatched '}}' at position {}", pos));
+    }
+    Ok(args)
+}
+
+/// 解析消息文本直到遇到未匹配的 `}` 或结尾
+fn parse_message(
+    chars: &[char],
+    pos: &mut usize,
+    args: &mut Vec<(String, ArgKind)>,
+    nested: bool,
+) -> Result<()> {
+    while *pos < chars.len() {
+        match chars[*pos] {
+            '\'' => skip_quoted(chars, pos),
+            '{' => {
+                *pos += 1;
+                parse_argument(chars, pos, args)?;
+            }
+            '}' => {
+                if nested {
+                    return Ok(());
+                }
+                return Err(anyhow::anyhow!("Unmatched '}}' at position {}", pos));
+            }
+            _ => *pos += 1,
+        }
+    }
+    if nested {
+        return Err(anyhow::anyhow!("Unclosed '{{' in message"));
+    }
+    Ok(())
+}
+
+/// 跳过 ICU 引号转义（`''` 表示单引号，`'{'` 表示字面量）
+fn skip_quoted(chars: &[char], pos: &mut usize) {
+    *pos += 1;
+    if *pos < chars.len() && chars[*pos] == '\'' {
+        *pos += 1;
+        return;
+    }
+    if *pos < chars.len() && matches!(chars[*pos], '{' | '}' | '#' | '|') {
+        while *pos < chars.len() && chars[*pos] != '\'' {
+            *pos += 1;
+        }
+        *pos += 1;
+    }
+}
+
+/// 读取到指定分隔符之前的片段（去除空白）
+fn read_until(chars: &[char], pos: &mut usize, stops: &[char]) -> String {
+    let start = *pos;
+    while *pos < chars.len() && !stops.contains(&chars[*pos]) {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect::<String>().trim().to_string()
+}
+
+/// 解析 `{` 之后的参数定义
+fn parse_argument(chars: &[char], pos: &mut usize, args: &mut Vec<(String, ArgKind)>) -> Result<()> {
+    let name = read_until(chars, pos, &[',', '}']);
+    if name.is_empty() {
+        return Err(anyhow::anyhow!("Empty argument name at position {}", pos));
+    }
+    if *pos >= chars.len() {
+        return Err(anyhow::anyhow!("Unclosed argument '{}'", name));
+    }
+
+    if chars[*pos] == '}' {
+        *pos += 1;
+        add_argument(args, name, ArgKind::Plain);
+        return Ok(());
+    }
+
+    *pos += 1;
+    let kind = read_until(chars, pos, &[',', '}']);
+    if *pos >= chars.len() {
+        return Err(anyhow::anyhow!("Unclosed argument '{}'", name));
+    }
+
+    match kind.as_str() {
+        "plural" | "selectordinal" | "select" => {
+            if chars[*pos] != ',' {
+                return Err(anyhow::anyhow!("Argument '{}' ({}) has no options", name, kind));
+            }
+            *pos += 1;
+            let selectors = parse_options(chars, pos, args, &name)?;
+            let arg_kind = if kind == "select" {
+                ArgKind::Select(selectors.into_iter().filter(|s| s != "other").collect())
+            } else {
+                ArgKind::Number {
+                    exact: selectors
+                        .iter()
+                        .filter_map(|s| s.strip_prefix('=').map(|v| v.to_string()))
+                        .collect(),
+                }
+            };
+            add_argument(args, name, arg_kind);
+        }
+        "number" | "date" | "time" => {
+            // 跳过可选的样式部分
+            let mut depth = 0;
+            while *pos < chars.len() && !(chars[*pos] == '}' && depth == 0) {
+                match chars[*pos] {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                *pos += 1;
+            }
+            if *pos >= chars.len() {
+                return Err(anyhow::anyhow!("Unclosed argument '{}'", name));
+            }
+            *pos += 1;
+            let arg_kind = if kind == "number" {
+                ArgKind::Number { exact: Vec::new() }
+            } else {
+                ArgKind::DateTime
+            };
+            add_argument(args, name, arg_kind);
+        }
+        other => {
+            return Err(anyhow::anyhow!("Unknown argument type '{}' for '{}'", other, name));
+        }
+    }
+    Ok(())
+}
+
+/// 解析 plural/select 的选项，返回选择器列表
+fn parse_options(
+    chars: &[char],
+    pos: &mut usize,
+    args: &mut Vec<(String, ArgKind)>,
+    name: &str,
+) -> Result<Vec<String>> {
+    let mut selectors = Vec::new();
+    loop {
+        let selector = read_until(chars, pos, &['{', '}']);
+        if *pos >= chars.len() {
+            return Err(anyhow::anyhow!("Unclosed argument '{}'", name));
+        }
+        if chars[*pos] == '}' {
+            if !selector.is_empty() {
+                return Err(anyhow::anyhow!("Option '{}' of '{}' has no message", selector, name));
+            }
+            *pos += 1;
+            break;
+        }
+
+        // `offset:1 one {..}` 中的 offset 与第一个选择器写在一起
+        let selector = selector
+            .split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .to_string();
+        if selector.is_empty() {
+            return Err(anyhow::anyhow!("Missing selector in '{}'", name));
+        }
+
+        *pos += 1;
+        parse_message(chars, pos, args, true)?;
+        *pos += 1;
+        selectors.push(selector);
+    }
+
+    if !selectors.iter().any(|s| s == "other") {
+        return Err(anyhow::anyhow!("Argument '{}' is missing the required 'other' option", name));
+    }
+    Ok(selectors)
+}
+
+/// 记录参数；同名参数保留信息更多的类型
+fn add_argument(args: &mut Vec<(String, ArgKind)>, name: String, kind: ArgKind) {
+    match args.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, existing)) if *existing == ArgKind::Plain => *existing = kind,
+        Some(_) => {}
+        None => args.push((name, kind)),
+    }
+}
+
+/// 生成覆盖各分支的示例参数组
+///
+/// 数值参数依次取 0、1、2、5 以及显式写出的精确值；选择参数依次取每个选项
+/// 以及一个不存在的值（命中 `other`）。各参数按下标对齐组合，组数为最多变体数。
+pub fn sample_arguments(args: &[(String, ArgKind)]) -> Vec<SampleArgs> {
+    let variants: Vec<(&String, Vec<SampleValue>)> = args
+        .iter()
+        .map(|(name, kind)| {
+            let values = match kind {
+                ArgKind::Plain => vec![SampleValue::Text(format!("[{}]", name))],
+                ArgKind::Number { exact } => {
+                    let mut numbers = vec![0, 1, 2, 5];
+                    for value in exact.iter().filter_map(|v| v.parse::<i64>().ok()) {
+                        if !numbers.contains(&value) {
+                            numbers.push(value);
+                        }
+                    }
+                    numbers.into_iter().map(SampleValue::Number).collect()
+                }
+                ArgKind::Select(options) => options
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once("__other__".to_string()))
+                    .map(SampleValue::Text)
+                    .collect(),
+                ArgKind::DateTime => vec![SampleValue::Timestamp(1_700_000_000_000)],
+            };
+            (name, values)
+        })
+        .collect();
+
+    let count = variants.iter().map(|(_, v)| v.len()).max().unwrap_or(0);
+    (0..count)
+        .map(|i| {
+            variants
+                .iter()
+                .map(|(name, values)| ((*name).clone(), values[i % values.len()].clone()))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_arguments() {
+        let args = parse_arguments("Hello {name}, you have {count} items").unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ("name".to_string(), ArgKind::Plain),
+                ("count".to_string(), ArgKind::Plain)
+            ]
+        );
+        assert!(parse_arguments("No arguments").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_plural_and_select() {
+        let args = parse_arguments(
+            "{gender, select, male {He} female {She} other {They}} has {count, plural, =0 {no items} one {# item} other {# items}}",
+        )
+        .unwrap();
+        assert_eq!(
+            args[0],
+            (
+                "gender".to_string(),
+                ArgKind::Select(vec!["male".to_string(), "female".to_string()])
+            )
+        );
+        assert_eq!(
+            args[1],
+            (
+                "count".to_string(),
+                ArgKind::Number {
+                    exact: vec!["0".to_string()]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_arguments() {
+        let args =
+            parse_arguments("{count, plural, offset:1 one {{name} alone} other {{name} and # others}}").unwrap();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0].0, "name");
+        assert_eq!(args[1].0, "count");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_arguments("Hello {name").is_err());
+        assert!(parse_arguments("Hello name}").is_err());
+        assert!(parse_arguments("{n, plural, one {item}}").is_err());
+        assert!(parse_arguments("{n, bogus}").is_err());
+    }
+
+    #[test]
+    fn test_quoted_braces_are_literal() {
+        assert!(parse_arguments("Use '{braces}' literally, it''s fine").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_icu_message() {
+        assert!(is_icu_message("Hi {name}"));
+        assert!(!is_icu_message("Plain text"));
+        // 语法错误的消息也需要被测试覆盖
+        assert!(is_icu_message("Broken {name"));
+    }
+
+    #[test]
+    fn test_sample_arguments() {
+        let args = parse_arguments("{gender, select, male {He} other {They}} {count, plural, =7 {seven} other {#}}").unwrap();
+        let samples = sample_arguments(&args);
+
+        // 数值参数 0, 1, 2, 5, 7 共 5 组
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[4]["count"], SampleValue::Number(7));
+        assert_eq!(samples[0]["gender"], SampleValue::Text("male".to_string()));
+        assert_eq!(samples[1]["gender"], SampleValue::Text("__other__".to_string()));
+        assert!(sample_arguments(&[]).is_empty());
+    }
+}
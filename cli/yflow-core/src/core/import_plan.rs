@@ -0,0 +1,313 @@
+//! Import plan module
+//!
+//! `import` compares the local translations with the backend before pushing
+//! and records the result as an [`ImportPlan`]: per backend project and
+//! locale, the keys to create, the values to update (with the value they
+//! replace) and the number of keys skipped because they are unchanged
+//! (equivalent under the project's `comparator`).
+//!
+//! `import --dry-run` renders the plan, `import --plan-file plan.json` saves
+//! it for review, and `import --apply plan.json` pushes exactly the values in
+//! the file. Before applying, the backend is checked again: a plan whose
+//! keys changed on the backend since it was made is rejected as stale.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::compare::Comparator;
+use super::Translations;
+
+/// 计划文件格式版本
+pub const PLAN_VERSION: u32 = 1;
+
+/// 值的修改
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueChange {
+    /// 后端当前的值
+    pub from: String,
+    /// 要写入的值
+    pub to: String,
+}
+
+/// 单个后端项目的导入计划
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetPlan {
+    /// 项目 ID
+    pub project_id: u64,
+    /// API 地址
+    pub api_url: String,
+    /// 要新建的键（后端语言代码 -> 键 -> 值）
+    #[serde(default)]
+    pub create: BTreeMap<String, BTreeMap<String, String>>,
+    /// 要更新的键（后端语言代码 -> 键 -> 修改）
+    #[serde(default)]
+    pub update: BTreeMap<String, BTreeMap<String, ValueChange>>,
+    /// 与后端相同而跳过的键数
+    #[serde(default)]
+    pub unchanged: usize,
+}
+
+impl TargetPlan {
+    /// 比较本地翻译和后端翻译，生成计划
+    ///
+    /// # 参数
+    ///
+    /// * `project_id` - 项目 ID
+    /// * `api_url` - API 地址
+    /// * `local` - 要导入的翻译（后端语言代码）
+    /// * `remote` - 后端当前的翻译
+    /// * `comparator` - 判断值是否未变化的比较方式
+    pub fn compute(
+        project_id: u64,
+        api_url: &str,
+        local: &Translations,
+        remote: &Translations,
+        comparator: Comparator,
+    ) -> Self {
+        let mut plan = Self {
+            project_id,
+            api_url: api_url.to_string(),
+            ..Default::default()
+        };
+
+        for (locale, keys) in local {
+            let remote_keys = remote.get(locale);
+            for (key, value) in keys {
+                match remote_keys.and_then(|keys| keys.get(key)) {
+                    None => {
+                        plan.create
+                            .entry(locale.clone())
+                            .or_default()
+                            .insert(key.clone(), value.clone());
+                    }
+                    Some(current) if comparator.equivalent(current, value) => plan.unchanged += 1,
+                    Some(current) => {
+                        plan.update.entry(locale.clone()).or_default().insert(
+                            key.clone(),
+                            ValueChange {
+                                from: current.clone(),
+                                to: value.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        plan
+    }
+
+    /// 计划是否不需要推送任何键
+    pub fn is_empty(&self) -> bool {
+        self.create.values().all(BTreeMap::is_empty) && self.update.values().all(BTreeMap::is_empty)
+    }
+
+    /// 要新建的键数
+    pub fn create_count(&self) -> usize {
+        self.create.values().map(BTreeMap::len).sum()
+    }
+
+    /// 要更新的键数
+    pub fn update_count(&self) -> usize {
+        self.update.values().map(BTreeMap::len).sum()
+    }
+
+    /// 需要推送的翻译（新建和更新的键）
+    pub fn to_push(&self) -> Translations {
+        let mut translations = Translations::new();
+        for (locale, keys) in &self.create {
+            translations
+                .entry(locale.clone())
+                .or_default()
+                .extend(keys.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        for (locale, keys) in &self.update {
+            translations
+                .entry(locale.clone())
+                .or_default()
+                .extend(keys.iter().map(|(k, change)| (k.clone(), change.to.clone())));
+        }
+        translations.retain(|_, keys| !keys.is_empty());
+        translations
+    }
+
+    /// 计划生成后在后端被修改过的键（语言代码, 键），已排序
+    ///
+    /// 新建的键现在已存在、或更新的键不再是计划中的原值时视为过期；
+    /// 后端已经是计划中的新值的键不算过期。
+    pub fn stale_keys(&self, remote: &Translations) -> Vec<(String, String)> {
+        let current = |locale: &str, key: &str| remote.get(locale).and_then(|keys| keys.get(key));
+
+        let mut stale: Vec<(String, String)> = Vec::new();
+        for (locale, keys) in &self.create {
+            for (key, value) in keys {
+                if current(locale, key).is_some_and(|current| current != value) {
+                    stale.push((locale.clone(), key.clone()));
+                }
+            }
+        }
+        for (locale, keys) in &self.update {
+            for (key, change) in keys {
+                if current(locale, key).is_none_or(|current| *current != change.from && *current != change.to) {
+                    stale.push((locale.clone(), key.clone()));
+                }
+            }
+        }
+        stale.sort();
+        stale
+    }
+}
+
+/// 导入计划
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlan {
+    /// 计划文件格式版本
+    pub version: u32,
+    /// 生成时间（RFC 3339）
+    pub created_at: String,
+    /// 各后端项目的计划
+    pub targets: Vec<TargetPlan>,
+}
+
+impl ImportPlan {
+    /// 创建计划
+    pub fn new(targets: Vec<TargetPlan>) -> Self {
+        Self {
+            version: PLAN_VERSION,
+            created_at: super::timestamp::format_rfc3339(super::timestamp::now()),
+            targets,
+        }
+    }
+
+    /// 计划是否不需要推送任何键
+    pub fn is_empty(&self) -> bool {
+        self.targets.iter().all(TargetPlan::is_empty)
+    }
+
+    /// 保存计划（格式化的 JSON）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize import plan")?;
+        std::fs::write(path, content + "\n")
+            .with_context(|| format!("Failed to write import plan: {}", path.display()))
+    }
+
+    /// 读取计划
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读取、格式无效或格式版本不同时返回错误
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read import plan: {}", path.display()))?;
+        let plan: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid import plan: {}", path.display()))?;
+        if plan.version != PLAN_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported import plan version {} in {} (expected {}); create the plan again",
+                plan.version,
+                path.display(),
+                PLAN_VERSION
+            ));
+        }
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn sorted(keys: &HashMap<String, String>) -> BTreeMap<&str, &str> {
+        keys.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+    }
+
+    fn translations(locale: &str, pairs: &[(&str, &str)]) -> Translations {
+        HashMap::from([(
+            locale.to_string(),
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        )])
+    }
+
+    fn sample_plan() -> TargetPlan {
+        let local = translations("en", &[("new", "New"), ("title", "Title v2"), ("same", "Same")]);
+        let remote = translations("en", &[("title", "Title"), ("same", "Same"), ("other", "Other")]);
+        TargetPlan::compute(1, "http://localhost", &local, &remote, Comparator::Exact)
+    }
+
+    #[test]
+    fn test_compute() {
+        let plan = sample_plan();
+        assert_eq!(plan.create_count(), 1);
+        assert_eq!(plan.create["en"]["new"], "New");
+        assert_eq!(
+            plan.update["en"]["title"],
+            ValueChange {
+                from: "Title".to_string(),
+                to: "Title v2".to_string()
+            }
+        );
+        assert_eq!(plan.unchanged, 1);
+        assert!(!plan.is_empty());
+        assert_eq!(
+            sorted(&plan.to_push()["en"]),
+            BTreeMap::from([("new", "New"), ("title", "Title v2")])
+        );
+
+        let unchanged = TargetPlan::compute(
+            1,
+            "",
+            &translations("en", &[("a", "A")]),
+            &translations("en", &[("a", "A")]),
+            Comparator::Exact,
+        );
+        assert!(unchanged.is_empty());
+        assert!(unchanged.to_push().is_empty());
+
+        // 规范化后相同的值不推送
+        let local = translations("en", &[("a", "Line<br/>next")]);
+        let remote = translations("en", &[("a", "Line<br>next")]);
+        assert!(!TargetPlan::compute(1, "", &local, &remote, Comparator::Exact).is_empty());
+        let plan = TargetPlan::compute(1, "", &local, &remote, Comparator::NormalizedHtml);
+        assert!(plan.is_empty());
+        assert_eq!(plan.unchanged, 1);
+    }
+
+    #[test]
+    fn test_stale_keys() {
+        let plan = sample_plan();
+        // 后端未变化，或已经是计划中的新值
+        assert!(plan.stale_keys(&translations("en", &[("title", "Title")])).is_empty());
+        assert!(plan
+            .stale_keys(&translations("en", &[("title", "Title v2"), ("new", "New")]))
+            .is_empty());
+
+        // 其他人修改了 title、新建了 new
+        let remote = translations("en", &[("title", "Changed"), ("new", "Other")]);
+        assert_eq!(
+            plan.stale_keys(&remote),
+            [("en".to_string(), "new".to_string()), ("en".to_string(), "title".to_string())]
+        );
+        // 要更新的键已被删除
+        assert_eq!(plan.stale_keys(&Translations::new()).len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("plan.json");
+        let plan = ImportPlan::new(vec![sample_plan()]);
+        plan.save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"projectId\": 1"));
+        assert_eq!(ImportPlan::load(&path).unwrap(), plan);
+
+        std::fs::write(&path, content.replace("\"version\": 1", "\"version\": 99")).unwrap();
+        assert!(ImportPlan::load(&path).unwrap_err().to_string().contains("version 99"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Multi-project import summary
+//!
+//! When `routes` send keys to several backend projects, `import` collects
+//! one row per project with the result of every language, how long the
+//! project took and whether it failed, and prints them as a matrix:
+//!
+//! ```text
+//! PROJECT           de     en     TOTAL     TIME  STATUS
+//! 1 (https://a.io)  +3 ~1  =      +3 ~1     1s    ok
+//! 7 (https://b.io)  -      +5 !2  +5 !2     3s    ok
+//! 9 (https://c.io)  -      -      -         0ms   failed: ...
+//! TOTAL             +3 ~1  +5 !2  +8 ~1 !2  4s
+//! + added  ~ updated  ! failed  = unchanged  - not imported
+//! ```
+//!
+//! `import --summary-json report.json` writes the same data as JSON, along
+//! with the per-language [`RunSummary`] that `sync --summary-json` writes too.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::Duration;
+
+use super::deadline::format_duration;
+use super::run_summary::RunSummary;
+use super::{ImportResult, LanguageCounts};
+
+/// 项目的导入状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+pub enum ProjectStatus {
+    /// 导入完成（可能有失败的键）
+    Ok,
+    /// 导入出错
+    Failed(String),
+    /// 因其他项目出错而未执行（`--fail-fast`）
+    Skipped,
+}
+
+/// 单个项目的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSummary {
+    /// 项目 ID
+    pub project_id: u64,
+    /// API 地址
+    pub api_url: String,
+    /// 状态
+    #[serde(flatten)]
+    pub status: ProjectStatus,
+    /// 耗时（毫秒）
+    pub duration_ms: u64,
+    /// 各语言（后端语言代码）的结果
+    pub languages: BTreeMap<String, LanguageCounts>,
+    /// 合计
+    pub total: LanguageCounts,
+}
+
+impl ProjectSummary {
+    /// 由项目的导入结果创建
+    pub fn new(project_id: u64, api_url: &str, result: &Result<ImportResult>, duration: Duration) -> Self {
+        let (status, languages, total) = match result {
+            Ok(result) => (ProjectStatus::Ok, result.languages.clone(), result.counts()),
+            Err(e) => (ProjectStatus::Failed(format!("{:#}", e)), BTreeMap::new(), LanguageCounts::default()),
+        };
+        Self {
+            project_id,
+            api_url: api_url.to_string(),
+            status,
+            duration_ms: duration.as_millis() as u64,
+            languages,
+            total,
+        }
+    }
+
+    /// 未执行的项目
+    pub fn skipped(project_id: u64, api_url: &str) -> Self {
+        Self {
+            project_id,
+            api_url: api_url.to_string(),
+            status: ProjectStatus::Skipped,
+            duration_ms: 0,
+            languages: BTreeMap::new(),
+            total: LanguageCounts::default(),
+        }
+    }
+}
+
+/// 所有项目的导入汇总
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    /// 各项目的结果（按配置中的顺序）
+    pub projects: Vec<ProjectSummary>,
+    /// 各语言所有项目合计
+    pub languages: BTreeMap<String, LanguageCounts>,
+    /// 所有项目合计
+    pub total: LanguageCounts,
+    /// 总耗时（毫秒）
+    pub duration_ms: u64,
+    /// 各语言的汇总表
+    pub summary: RunSummary,
+}
+
+impl ImportSummary {
+    /// 汇总各项目的结果
+    pub fn new(projects: Vec<ProjectSummary>, duration: Duration) -> Self {
+        let mut languages: BTreeMap<String, LanguageCounts> = BTreeMap::new();
+        let mut total = LanguageCounts::default();
+        for project in &projects {
+            for (lang, counts) in &project.languages {
+                languages.entry(lang.clone()).or_default().absorb(counts);
+            }
+            total.absorb(&project.total);
+        }
+        Self {
+            summary: RunSummary::new(&languages, duration),
+            projects,
+            languages,
+            total,
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+
+    /// 出错的项目
+    pub fn failed(&self) -> Vec<&ProjectSummary> {
+        self.projects
+            .iter()
+            .filter(|project| matches!(project.status, ProjectStatus::Failed(_)))
+            .collect()
+    }
+
+    /// 渲染为文本矩阵（项目 × 语言）
+    pub fn render(&self) -> String {
+        let languages: BTreeSet<&String> = self.projects.iter().flat_map(|project| project.languages.keys()).collect();
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut header = vec!["PROJECT".to_string()];
+        header.extend(languages.iter().map(|lang| lang.to_string()));
+        header.extend(["TOTAL", "TIME", "STATUS"].map(String::from));
+        rows.push(header);
+
+        for project in &self.projects {
+            let mut row = vec![format!("{} ({})", project.project_id, project.api_url)];
+            row.extend(languages.iter().map(|lang| match project.languages.get(*lang) {
+                Some(counts) => cell(counts),
+                None => "-".to_string(),
+            }));
+            let (total, status) = match &project.status {
+                ProjectStatus::Ok => (cell(&project.total), "ok".to_string()),
+                ProjectStatus::Failed(error) => ("-".to_string(), format!("failed: {}", error)),
+                ProjectStatus::Skipped => ("-".to_string(), "skipped".to_string()),
+            };
+            row.extend([total, format_duration(Duration::from_millis(project.duration_ms)), status]);
+            rows.push(row);
+        }
+
+        let mut total = vec!["TOTAL".to_string()];
+        total.extend(languages.iter().map(|lang| self.languages.get(*lang).map_or("-".to_string(), cell)));
+        total.extend([
+            cell(&self.total),
+            format_duration(Duration::from_millis(self.duration_ms)),
+            String::new(),
+        ]);
+        rows.push(total);
+
+        // 除最后一列外按列宽对齐
+        let columns = rows[0].len();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        let mut out = String::new();
+        for row in rows {
+            let line: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, value)| match column + 1 == columns {
+                    true => value.clone(),
+                    false => format!("{:<width$}", value, width = widths[column]),
+                })
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        out.push_str("+ added  ~ updated  ! failed  = unchanged  - not imported\n");
+        out
+    }
+
+    /// 保存为 JSON
+    ///
+    /// # Errors
+    ///
+    /// 文件无法写入时返回错误
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write import summary {}", path.display()))
+    }
+}
+
+/// 单元格：非零的新增、更新、失败数，全部未变化时为 `=`
+fn cell(counts: &LanguageCounts) -> String {
+    let parts: Vec<String> = [("+", counts.added), ("~", counts.updated), ("!", counts.failed)]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(sign, count)| format!("{}{}", sign, count))
+        .collect();
+    if parts.is_empty() {
+        "=".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn counts(added: usize, updated: usize, unchanged: usize, failed: usize) -> LanguageCounts {
+        LanguageCounts {
+            added,
+            updated,
+            unchanged,
+            failed,
+        }
+    }
+
+    fn result(languages: &[(&str, LanguageCounts)]) -> ImportResult {
+        let mut result = ImportResult::default();
+        for (lang, counts) in languages {
+            result.added += counts.added;
+            result.updated += counts.updated;
+            result.unchanged += counts.unchanged;
+            result.failed += counts.failed;
+            result.languages.insert(lang.to_string(), *counts);
+        }
+        result
+    }
+
+    fn summary() -> ImportSummary {
+        let first = result(&[("de", counts(3, 1, 0, 0)), ("en", counts(0, 0, 4, 0))]);
+        let second = result(&[("en", counts(5, 0, 0, 2))]);
+        ImportSummary::new(
+            vec![
+                ProjectSummary::new(1, "https://a", &Ok(first), Duration::from_secs(1)),
+                ProjectSummary::new(7, "https://b", &Ok(second), Duration::from_secs(3)),
+                ProjectSummary::new(9, "https://c", &Err(anyhow::anyhow!("unauthorized")), Duration::ZERO),
+                ProjectSummary::skipped(11, "https://d"),
+            ],
+            Duration::from_secs(4),
+        )
+    }
+
+    #[test]
+    fn test_totals() {
+        let summary = summary();
+        assert_eq!(summary.total, counts(8, 1, 4, 2));
+        assert_eq!(summary.languages["en"], counts(5, 0, 4, 2));
+        assert_eq!(summary.failed().len(), 1);
+        assert_eq!(summary.failed()[0].project_id, 9);
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = summary().render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "PROJECT         de     en     TOTAL     TIME  STATUS");
+        assert_eq!(lines[1], "1 (https://a)   +3 ~1  =      +3 ~1     1s    ok");
+        assert_eq!(lines[2], "7 (https://b)   -      +5 !2  +5 !2     3s    ok");
+        assert_eq!(lines[3], "9 (https://c)   -      -      -         0ms   failed: unauthorized");
+        assert_eq!(lines[4], "11 (https://d)  -      -      -         0ms   skipped");
+        assert_eq!(lines[5], "TOTAL           +3 ~1  +5 !2  +8 ~1 !2  4s");
+        assert_eq!(lines.len(), 7);
+    }
+
+    #[test]
+    fn test_save_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.json");
+        summary().save(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["projects"][0]["status"], "ok");
+        assert_eq!(json["projects"][0]["languages"]["de"]["added"], 3);
+        assert_eq!(json["projects"][2]["status"], "failed");
+        assert_eq!(json["projects"][2]["error"], "unauthorized");
+        assert_eq!(json["projects"][3]["status"], "skipped");
+        assert_eq!(json["total"]["failed"], 2);
+        assert_eq!(json["durationMs"], 4000);
+        assert_eq!(json["summary"]["languages"]["en"]["skipped"], 4);
+        assert_eq!(json["summary"]["total"]["added"], 8);
+    }
+}
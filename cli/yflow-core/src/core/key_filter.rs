@@ -0,0 +1,146 @@
+//! Key filter module
+//!
+//! Restricts import/sync to keys matching `--key-prefix` and/or
+//! `--key-glob` patterns. Patterns are matched against flattened keys
+//! (`checkout.total`), using the same `*` glob syntax as lint rules.
+//!
+//! A key is selected when it matches any prefix or any glob; without
+//! patterns every key is selected. Prefixes are compared segment by segment
+//! (see [`KeyPrefix`]), so `legal.` does not select `legal\.notice`.
+
+use super::key_path::KeyPrefix;
+use super::lint::key_glob;
+use super::Translations;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// 键过滤器
+#[derive(Debug, Clone, Default)]
+pub struct KeyFilter {
+    /// 键名前缀
+    prefixes: Vec<KeyPrefix>,
+    /// 原始 glob 模式（用于日志）
+    patterns: Vec<String>,
+    /// 编译后的 glob
+    globs: Vec<Regex>,
+}
+
+impl KeyFilter {
+    /// 创建键过滤器，前缀按键分隔符 `separator` 解析
+    ///
+    /// # Errors
+    ///
+    /// glob 模式无效时返回错误
+    pub fn new(prefixes: &[String], globs: &[String], separator: &str) -> Result<Self> {
+        Ok(Self {
+            prefixes: prefixes.iter().map(|prefix| KeyPrefix::parse(prefix, separator)).collect(),
+            patterns: globs.to_vec(),
+            globs: globs.iter().map(|g| key_glob(g)).collect::<Result<_>>()?,
+        })
+    }
+
+    /// 从混合的模式列表创建键过滤器
+    ///
+    /// 含 `*` 的模式按 glob 匹配，其余按前缀匹配（`legal.*` 与 `legal.` 等价）。
+    ///
+    /// # Errors
+    ///
+    /// glob 模式无效时返回错误
+    pub fn from_patterns(patterns: &[String], separator: &str) -> Result<Self> {
+        let (globs, prefixes): (Vec<String>, Vec<String>) =
+            patterns.iter().cloned().partition(|pattern| pattern.contains('*'));
+        Self::new(&prefixes, &globs, separator)
+    }
+
+    /// 是否设置了任何过滤条件
+    pub fn is_active(&self) -> bool {
+        !self.prefixes.is_empty() || !self.globs.is_empty()
+    }
+
+    /// 检查键是否被选中
+    pub fn matches(&self, key: &str) -> bool {
+        !self.is_active()
+            || self.prefixes.iter().any(|prefix| prefix.matches_key(key))
+            || self.globs.iter().any(|glob| glob.is_match(key))
+    }
+
+    /// 只保留被选中的键（没有剩余键的语言也会移除）
+    pub fn apply(&self, translations: Translations) -> Translations {
+        if !self.is_active() {
+            return translations;
+        }
+        translations
+            .into_iter()
+            .map(|(lang, keys)| {
+                let keys: HashMap<String, String> =
+                    keys.into_iter().filter(|(key, _)| self.matches(key)).collect();
+                (lang, keys)
+            })
+            .filter(|(_, keys)| !keys.is_empty())
+            .collect()
+    }
+
+    /// 过滤条件描述（用于日志）
+    pub fn description(&self) -> String {
+        let patterns: Vec<String> = self
+            .prefixes
+            .iter()
+            .map(|prefix| format!("{}*", prefix))
+            .chain(self.patterns.iter().cloned())
+            .collect();
+        format!("Key filter: {}", patterns.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = KeyFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches("anything"));
+
+        let filter = KeyFilter::new(&strings(&["checkout."]), &strings(&["*.label"]), ".").unwrap();
+        assert!(filter.matches("checkout.total"));
+        assert!(filter.matches("buttons.save.label"));
+        assert!(!filter.matches("cart.total"));
+        assert!(!filter.matches(r"checkout\.total"));
+        assert_eq!(filter.description(), "Key filter: checkout.*, *.label");
+    }
+
+    #[test]
+    fn test_from_patterns() {
+        let filter = KeyFilter::from_patterns(&strings(&["legal.*", "emails."]), ".").unwrap();
+        assert!(filter.matches("legal.terms.v2"));
+        assert!(filter.matches("emails.welcome"));
+        assert!(!filter.matches("legalese"));
+        assert!(!filter.matches("checkout.total"));
+        assert!(!KeyFilter::from_patterns(&[], ".").unwrap().is_active());
+    }
+
+    #[test]
+    fn test_apply() {
+        let translations: Translations = HashMap::from([
+            (
+                "en".to_string(),
+                HashMap::from([
+                    ("checkout.total".to_string(), "Total".to_string()),
+                    ("cart.title".to_string(), "Cart".to_string()),
+                ]),
+            ),
+            ("de".to_string(), HashMap::from([("cart.title".to_string(), "Korb".to_string())])),
+        ]);
+
+        let filtered = KeyFilter::new(&[], &strings(&["checkout.*"]), ".").unwrap().apply(translations);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered["en"].len(), 1);
+        assert!(filtered["en"].contains_key("checkout.total"));
+    }
+}
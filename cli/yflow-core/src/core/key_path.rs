@@ -0,0 +1,228 @@
+//! Key paths and namespaces
+//!
+//! A flattened key such as `checkout.promo.code` is a path of segments
+//! joined by the key separator (see [`super::flatten`]). [`KeyPath`] holds
+//! the unescaped segments, so a key whose name contains the separator
+//! (`legal\.notice`, one segment) never matches the namespace `legal`.
+//! Namespaces (`checkout.promo`, the namespace of `checkout/promo.json`)
+//! are key paths too.
+//!
+//! Prefix options (`--key-prefix`, route `keyPrefix`, `owners`, locks) are
+//! parsed into a [`KeyPrefix`]: its complete segments must match the key's
+//! leading segments, and an unfinished last segment (`auth` in
+//! `checkout.auth`) must start the next one.
+//!
+//! Reading and writing locale files (scanner, formats) goes through
+//! [`super::flatten`], which splits keys with the same escaping rules, so
+//! those modules keep passing flattened keys as strings. The validator is
+//! the exception on purpose: it joins raw JSON key names so that
+//! `{"a.b": ..., "a": {"b": ...}}` is reported as a duplicate.
+
+use std::fmt;
+
+use super::flatten::{escape_segment, split_key};
+
+/// 展平的键：未转义的键名片段
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyPath {
+    /// 键名片段
+    segments: Vec<String>,
+}
+
+/// 命名空间：一组键共同的键路径
+pub type Namespace = KeyPath;
+
+impl KeyPath {
+    /// 用指定分隔符解析展平的键（还原转义）
+    ///
+    /// 空字符串解析为根路径。
+    pub fn parse(key: &str, separator: &str) -> Self {
+        if key.is_empty() {
+            return Self::default();
+        }
+        Self {
+            segments: split_key(key, separator),
+        }
+    }
+
+    /// 由未转义的片段构造
+    pub fn from_segments<I, S>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            segments: segments.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 是否为根路径（没有片段）
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// 未转义的片段
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// 取出片段
+    pub fn into_segments(self) -> Vec<String> {
+        self.segments
+    }
+
+    /// 最后一个片段
+    pub fn leaf(&self) -> Option<&str> {
+        self.segments.last().map(String::as_str)
+    }
+
+    /// 去掉最后一个片段的路径（根路径没有父路径）
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.segments.split_last()?;
+        Some(Self::from_segments(parent.iter().cloned()))
+    }
+
+    /// 追加一个片段
+    pub fn child(&self, segment: impl Into<String>) -> Self {
+        let mut child = self.clone();
+        child.segments.push(segment.into());
+        child
+    }
+
+    /// 拼接另一个路径
+    pub fn join(&self, other: &KeyPath) -> Self {
+        let mut joined = self.clone();
+        joined.segments.extend(other.segments.iter().cloned());
+        joined
+    }
+
+    /// 是否位于命名空间内（等于命名空间或以其全部片段开头）
+    pub fn starts_with(&self, namespace: &Namespace) -> bool {
+        self.segments.starts_with(&namespace.segments)
+    }
+
+    /// 去掉命名空间前缀后的路径
+    pub fn strip_prefix(&self, namespace: &Namespace) -> Option<Self> {
+        self.segments
+            .strip_prefix(namespace.segments.as_slice())
+            .map(|rest| Self::from_segments(rest.iter().cloned()))
+    }
+
+    /// 用指定分隔符转义并拼接为展平的键
+    pub fn to_key_with(&self, separator: &str) -> String {
+        self.segments
+            .iter()
+            .map(|segment| escape_segment(segment, separator))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+/// 键名前缀
+///
+/// `checkout.` 选中 `checkout` 命名空间下的键，`checkout.pro` 还要求
+/// 下一个片段以 `pro` 开头。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyPrefix {
+    /// 完整的片段
+    namespace: Namespace,
+    /// 未完成的最后一个片段（以分隔符结尾时为 None）
+    partial: Option<String>,
+    /// 解析时使用的分隔符
+    separator: String,
+}
+
+impl KeyPrefix {
+    /// 用指定分隔符解析前缀
+    pub fn parse(prefix: &str, separator: &str) -> Self {
+        let mut segments = KeyPath::parse(prefix, separator).into_segments();
+        let partial = segments.pop().filter(|last| !last.is_empty());
+        Self {
+            namespace: KeyPath::from_segments(segments),
+            partial,
+            separator: separator.to_string(),
+        }
+    }
+
+    /// 是否选中键
+    pub fn matches(&self, key: &KeyPath) -> bool {
+        let Some(rest) = key.segments.strip_prefix(self.namespace.segments.as_slice()) else {
+            return false;
+        };
+        match (&self.partial, rest.first()) {
+            (None, next) => self.namespace.is_root() || next.is_some(),
+            (Some(partial), Some(next)) => next.starts_with(partial.as_str()),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// 是否选中展平的键（使用解析前缀时的分隔符）
+    pub fn matches_key(&self, key: &str) -> bool {
+        self.matches(&KeyPath::parse(key, &self.separator))
+    }
+}
+
+impl fmt::Display for KeyPrefix {
+    /// 以解析时的分隔符输出前缀
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.namespace.to_key_with(&self.separator))?;
+        if !self.namespace.is_root() {
+            f.write_str(&self.separator)?;
+        }
+        if let Some(partial) = &self.partial {
+            f.write_str(&escape_segment(partial, &self.separator))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let path = KeyPath::parse(r"legal\.notice.title", ".");
+        assert_eq!(path.segments(), ["legal.notice", "title"]);
+        assert_eq!(path.to_key_with("."), r"legal\.notice.title");
+        assert_eq!(path.leaf(), Some("title"));
+        assert_eq!(path.parent().unwrap().to_key_with("/"), "legal.notice");
+        assert!(KeyPath::parse("", ".").is_root());
+        assert_eq!(KeyPath::from_segments(["a", "b"]).child("c").to_key_with("."), "a.b.c");
+    }
+
+    #[test]
+    fn test_namespace_operations() {
+        let key = KeyPath::parse("checkout.promo.code", ".");
+        let namespace = KeyPath::parse("checkout.promo", ".");
+        assert!(key.starts_with(&namespace));
+        assert_eq!(key.strip_prefix(&namespace).unwrap().segments(), ["code"]);
+        assert_eq!(namespace.join(&KeyPath::from_segments(["code"])), key);
+        assert!(!KeyPath::parse(r"checkout\.promo.code", ".").starts_with(&namespace));
+        assert!(!KeyPath::parse("checkoutPage.title", ".").starts_with(&KeyPath::from_segments(["checkout"])));
+    }
+
+    #[test]
+    fn test_key_prefix() {
+        let below = KeyPrefix::parse("checkout.", ".");
+        assert!(below.matches_key("checkout.title"));
+        assert!(!below.matches_key("checkout"));
+        assert!(!below.matches_key(r"checkout\.title"));
+
+        let partial = KeyPrefix::parse("checkout.pro", ".");
+        assert!(partial.matches_key("checkout.promo.code"));
+        assert!(!partial.matches_key("checkout.title"));
+
+        assert!(KeyPrefix::parse("auth", ".").matches_key("authentication.title"));
+        assert!(KeyPrefix::parse("", ".").matches_key("anything"));
+
+        for prefix in ["checkout.", "checkout.pro", "auth", r"legal\.notice."] {
+            assert_eq!(KeyPrefix::parse(prefix, ".").to_string(), prefix);
+        }
+
+        let slash = KeyPrefix::parse("checkout/", "/");
+        assert!(slash.matches_key("checkout/promo.code"));
+        assert!(!slash.matches_key("checkout.promo"));
+        assert_eq!(slash.to_string(), "checkout/");
+    }
+}
@@ -0,0 +1,330 @@
+//! Translation content linting module
+//!
+//! Evaluates custom content rules from the config against translation
+//! values, so product tone guidelines (forbidden phrases, sentence case
+//! for buttons, ...) can be enforced in CI.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "lintRules": [
+//!     { "name": "no-click-here", "forbid": "(?i)click here", "languages": ["en"] },
+//!     { "name": "button-case", "keys": "buttons.*", "sentenceCase": true,
+//!       "allow": ["buttons.brand.*"], "severity": "warning" }
+//!   ]
+//! }
+//! ```
+//!
+//! Key patterns are globs where `*` matches any sequence of characters.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::Translations;
+
+/// 规则严重级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// 警告，不影响退出码
+    Warning,
+    /// 错误，lint 命令失败
+    #[default]
+    Error,
+}
+
+/// 内容规则（配置中的 `lintRules` 项）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct LintRule {
+    /// 规则名称
+    pub name: String,
+    /// 适用的键（glob），未设置时适用于所有键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<String>,
+    /// 适用的语言，为空时适用于所有语言
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    /// 值不得匹配的正则
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forbid: Option<String>,
+    /// 值必须匹配的正则
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require: Option<String>,
+    /// 要求值为句子大小写（首字母大写，其余单词不以大写开头，全大写缩写除外）
+    #[serde(rename = "sentenceCase", default, skip_serializing_if = "std::ops::Not::not")]
+    pub sentence_case: bool,
+    /// 例外的键（glob）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// 自定义提示信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// 严重级别
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// 单个 lint 问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintIssue {
+    /// 规则名称
+    pub rule: String,
+    /// 语言代码
+    pub lang: String,
+    /// 翻译键
+    pub key: String,
+    /// 翻译值
+    pub value: String,
+    /// 提示信息
+    pub message: String,
+    /// 严重级别
+    pub severity: Severity,
+}
+
+/// 编译后的规则
+#[derive(Debug)]
+struct CompiledRule {
+    rule: LintRule,
+    keys: Option<Regex>,
+    forbid: Option<Regex>,
+    require: Option<Regex>,
+    allow: Vec<Regex>,
+}
+
+impl CompiledRule {
+    fn applies_to(&self, lang: &str, key: &str) -> bool {
+        let lang_ok = self.rule.languages.is_empty() || self.rule.languages.iter().any(|l| l == lang);
+        let key_ok = self.keys.as_ref().map(|re| re.is_match(key)).unwrap_or(true);
+        let allowed = self.allow.iter().any(|re| re.is_match(key));
+        lang_ok && key_ok && !allowed
+    }
+
+    /// 检查值，返回违规描述
+    fn check(&self, value: &str) -> Option<String> {
+        if let Some(re) = &self.forbid {
+            if re.is_match(value) {
+                return Some(format!("matches forbidden pattern /{}/", re.as_str()));
+            }
+        }
+        if let Some(re) = &self.require {
+            if !re.is_match(value) {
+                return Some(format!("does not match required pattern /{}/", re.as_str()));
+            }
+        }
+        if self.rule.sentence_case && !is_sentence_case(value) {
+            return Some("is not in sentence case".to_string());
+        }
+        None
+    }
+}
+
+/// 内容检查器
+#[derive(Debug, Default)]
+pub struct Linter {
+    rules: Vec<CompiledRule>,
+}
+
+impl Linter {
+    /// 编译配置中的规则
+    ///
+    /// # Errors
+    ///
+    /// 正则或键模式无效时返回错误（包含规则名称）。
+    pub fn new(rules: &[LintRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let compile = |pattern: &str| {
+                    Regex::new(pattern).with_context(|| {
+                        format!("Invalid pattern in lint rule '{}': {}", rule.name, pattern)
+                    })
+                };
+                Ok(CompiledRule {
+                    keys: rule.keys.as_deref().map(key_glob).transpose()?,
+                    forbid: rule.forbid.as_deref().map(compile).transpose()?,
+                    require: rule.require.as_deref().map(compile).transpose()?,
+                    allow: rule.allow.iter().map(|p| key_glob(p)).collect::<Result<_>>()?,
+                    rule: rule.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    /// 是否没有任何规则
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 检查所有翻译
+    ///
+    /// # Returns
+    ///
+    /// 按语言、键、规则排序的问题列表
+    pub fn lint(&self, translations: &Translations) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        for (lang, lang_translations) in translations {
+            for (key, value) in lang_translations {
+                for compiled in &self.rules {
+                    if !compiled.applies_to(lang, key) {
+                        continue;
+                    }
+                    if let Some(reason) = compiled.check(value) {
+                        issues.push(LintIssue {
+                            rule: compiled.rule.name.clone(),
+                            lang: lang.clone(),
+                            key: key.clone(),
+                            value: value.clone(),
+                            message: compiled.rule.message.clone().unwrap_or(reason),
+                            severity: compiled.rule.severity,
+                        });
+                    }
+                }
+            }
+        }
+
+        issues.sort_by(|a, b| (&a.lang, &a.key, &a.rule).cmp(&(&b.lang, &b.key, &b.rule)));
+        issues
+    }
+}
+
+/// 将键 glob（`*` 匹配任意字符）编译为正则
+pub fn key_glob(pattern: &str) -> Result<Regex> {
+    let escaped = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{}$", escaped))
+        .with_context(|| format!("Invalid key pattern: {}", pattern))
+}
+
+/// 检查是否为句子大小写
+///
+/// 第一个单词需以大写字母开头；之后的单词不得以大写字母开头，
+/// 全大写的缩写（如 `PDF`、`OK`）除外。
+fn is_sentence_case(value: &str) -> bool {
+    let mut words = value.split_whitespace().filter(|w| w.chars().any(char::is_alphabetic));
+
+    let first_ok = words
+        .next()
+        .and_then(|w| w.chars().find(|c| c.is_alphabetic()))
+        .map(|c| !c.is_lowercase())
+        .unwrap_or(true);
+
+    first_ok
+        && words.all(|word| {
+            let starts_upper = word
+                .chars()
+                .find(|c| c.is_alphabetic())
+                .map(char::is_uppercase)
+                .unwrap_or(false);
+            let is_acronym = !word.chars().any(char::is_lowercase);
+            !starts_upper || is_acronym
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn translations(lang: &str, entries: &[(&str, &str)]) -> Translations {
+        HashMap::from([(
+            lang.to_string(),
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )])
+    }
+
+    #[test]
+    fn test_forbid_rule_with_language_filter() {
+        let linter = Linter::new(&[LintRule {
+            name: "no-click-here".to_string(),
+            forbid: Some("(?i)click here".to_string()),
+            languages: vec!["en".to_string()],
+            ..Default::default()
+        }])
+        .unwrap();
+
+        let issues = linter.lint(&translations("en", &[("a", "Click here to start"), ("b", "Start")]));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].key, "a");
+        assert_eq!(issues[0].severity, Severity::Error);
+
+        assert!(linter.lint(&translations("de", &[("a", "click here")])).is_empty());
+    }
+
+    #[test]
+    fn test_sentence_case_with_allowlist() {
+        let linter = Linter::new(&[LintRule {
+            name: "button-case".to_string(),
+            keys: Some("buttons.*".to_string()),
+            sentence_case: true,
+            allow: vec!["buttons.brand.*".to_string()],
+            severity: Severity::Warning,
+            ..Default::default()
+        }])
+        .unwrap();
+
+        let issues = linter.lint(&translations(
+            "en",
+            &[
+                ("buttons.save", "Save Changes"),
+                ("buttons.export", "Export as PDF"),
+                ("buttons.brand.name", "Open YFlow Studio"),
+                ("title.page", "Page Title"),
+            ],
+        ));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].key, "buttons.save");
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_require_rule_custom_message() {
+        let linter = Linter::new(&[LintRule {
+            name: "no-trailing-period".to_string(),
+            require: Some(r"[^.]$".to_string()),
+            message: Some("Remove the trailing period".to_string()),
+            ..Default::default()
+        }])
+        .unwrap();
+
+        let issues = linter.lint(&translations("en", &[("a", "Done.")]));
+        assert_eq!(issues[0].message, "Remove the trailing period");
+    }
+
+    #[test]
+    fn test_invalid_pattern_names_rule() {
+        let error = Linter::new(&[LintRule {
+            name: "broken".to_string(),
+            forbid: Some("(".to_string()),
+            ..Default::default()
+        }])
+        .unwrap_err();
+        assert!(error.to_string().contains("broken"));
+    }
+
+    #[test]
+    fn test_key_glob() {
+        let re = key_glob("buttons.*.label").unwrap();
+        assert!(re.is_match("buttons.save.label"));
+        assert!(!re.is_match("buttonsXsave.label.extra"));
+    }
+
+    #[test]
+    fn test_is_sentence_case() {
+        assert!(is_sentence_case("Save changes"));
+        assert!(is_sentence_case("Export as PDF"));
+        assert!(!is_sentence_case("save changes"));
+        assert!(!is_sentence_case("Save Changes"));
+        assert!(is_sentence_case("123"));
+    }
+}
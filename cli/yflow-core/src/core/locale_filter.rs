@@ -0,0 +1,104 @@
+//! Locale filter module
+//!
+//! Restricts import/sync to a subset of languages via the repeatable
+//! `--locale` and `--exclude-locale` flags. Codes are local language codes
+//! (directory names), before any `languageMapping` is applied.
+//!
+//! An empty include list selects every language; exclusions always win.
+
+use super::Translations;
+
+/// 语言过滤器
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocaleFilter {
+    /// 只包含这些语言（为空表示全部）
+    include: Vec<String>,
+    /// 排除这些语言
+    exclude: Vec<String>,
+}
+
+impl LocaleFilter {
+    /// 创建语言过滤器
+    pub fn new(include: &[String], exclude: &[String]) -> Self {
+        Self {
+            include: include.to_vec(),
+            exclude: exclude.to_vec(),
+        }
+    }
+
+    /// 是否设置了任何过滤条件
+    pub fn is_active(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// 显式包含的语言（为空表示全部）
+    pub fn included(&self) -> &[String] {
+        &self.include
+    }
+
+    /// 检查语言是否被选中
+    pub fn matches(&self, lang: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|l| l == lang))
+            && !self.exclude.iter().any(|l| l == lang)
+    }
+
+    /// 只保留被选中的语言
+    pub fn apply(&self, translations: Translations) -> Translations {
+        translations
+            .into_iter()
+            .filter(|(lang, _)| self.matches(lang))
+            .collect()
+    }
+
+    /// 过滤条件描述（用于日志）
+    pub fn description(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.include.is_empty() {
+            parts.push(format!("only {}", self.include.join(", ")));
+        }
+        if !self.exclude.is_empty() {
+            parts.push(format!("excluding {}", self.exclude.join(", ")));
+        }
+        format!("Locale filter: {}", parts.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn codes(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = LocaleFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches("de"));
+
+        let filter = LocaleFilter::new(&codes(&["zh_CN", "de"]), &codes(&["de"]));
+        assert!(filter.is_active());
+        assert!(filter.matches("zh_CN"));
+        assert!(!filter.matches("de"));
+        assert!(!filter.matches("en"));
+        assert_eq!(filter.description(), "Locale filter: only zh_CN, de; excluding de");
+
+        let filter = LocaleFilter::new(&[], &codes(&["en"]));
+        assert!(filter.matches("fr"));
+        assert!(!filter.matches("en"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let translations: Translations = ["en", "de", "zh_CN"]
+            .iter()
+            .map(|lang| (lang.to_string(), HashMap::from([("k".to_string(), "v".to_string())])))
+            .collect();
+
+        let filtered = LocaleFilter::new(&codes(&["zh_CN"]), &[]).apply(translations);
+        assert_eq!(filtered.len(), 1);
+        assert!(filtered.contains_key("zh_CN"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Key locks module
+//!
+//! Backends that advertise the `locks` capability let a team lock a key
+//! prefix while it edits those keys (`yflow lock checkout.`). `import` skips
+//! keys under a prefix locked by another holder and reports them, so two
+//! teams cannot stomp on each other's edits.
+//!
+//! A lock's holder is the team (`--team` / `I18N_TEAM`), falling back to the
+//! OS user name.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::key_path::{KeyPath, KeyPrefix};
+use super::ownership::TEAM_ENV_VAR;
+use super::Translations;
+
+/// 后端返回的键锁
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeyLock {
+    /// 锁定的键前缀
+    pub prefix: String,
+    /// 持有者（团队或用户）
+    pub holder: String,
+    /// 过期时间（RFC 3339，永不过期时为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+/// 被其他持有者锁定而跳过的键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedKey {
+    /// 语言代码
+    pub lang: String,
+    /// 翻译键
+    pub key: String,
+    /// 锁的持有者
+    pub holder: String,
+}
+
+/// 确定当前持有者
+///
+/// 依次使用显式指定的团队、`I18N_TEAM`、系统用户名。
+pub fn current_holder(team: Option<&str>) -> String {
+    team.map(|t| t.to_string())
+        .or_else(|| std::env::var(TEAM_ENV_VAR).ok())
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .filter(|holder| !holder.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 查找覆盖该键的锁（最长前缀优先），键和前缀按 `separator` 解析
+pub fn lock_for<'a>(locks: &'a [KeyLock], key: &str, separator: &str) -> Option<&'a KeyLock> {
+    let key = KeyPath::parse(key, separator);
+    locks
+        .iter()
+        .filter(|lock| KeyPrefix::parse(&lock.prefix, separator).matches(&key))
+        .max_by_key(|lock| lock.prefix.len())
+}
+
+/// 拆分出被其他持有者锁定的键
+///
+/// # Returns
+///
+/// 可以导入的翻译和被跳过的键
+pub fn partition_locked(
+    translations: Translations,
+    locks: &[KeyLock],
+    holder: &str,
+    separator: &str,
+) -> (Translations, Vec<LockedKey>) {
+    let mut skipped = Vec::new();
+    let allowed = translations
+        .into_iter()
+        .map(|(lang, keys)| {
+            let keys: HashMap<String, String> = keys
+                .into_iter()
+                .filter(|(key, _)| match lock_for(locks, key, separator) {
+                    Some(lock) if lock.holder != holder => {
+                        skipped.push(LockedKey {
+                            lang: lang.clone(),
+                            key: key.clone(),
+                            holder: lock.holder.clone(),
+                        });
+                        false
+                    }
+                    _ => true,
+                })
+                .collect();
+            (lang, keys)
+        })
+        .filter(|(_, keys)| !keys.is_empty())
+        .collect();
+    skipped.sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+    (allowed, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(prefix: &str, holder: &str) -> KeyLock {
+        KeyLock {
+            prefix: prefix.to_string(),
+            holder: holder.to_string(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_lock_for_longest_prefix() {
+        let locks = vec![lock("checkout.", "payments"), lock("checkout.promo.", "growth")];
+        assert_eq!(lock_for(&locks, "checkout.title", ".").unwrap().holder, "payments");
+        assert_eq!(lock_for(&locks, "checkout.promo.code", ".").unwrap().holder, "growth");
+        assert!(lock_for(&locks, "home.title", ".").is_none());
+    }
+
+    #[test]
+    fn test_partition_locked() {
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("checkout.title".to_string(), "Checkout".to_string()),
+                ("checkout.promo.code".to_string(), "Code".to_string()),
+                ("home.title".to_string(), "Home".to_string()),
+            ]),
+        )]);
+        let locks = vec![lock("checkout.", "payments"), lock("checkout.promo.", "growth")];
+
+        let (allowed, skipped) = partition_locked(translations, &locks, "payments", ".");
+        assert_eq!(allowed["en"].len(), 2);
+        assert!(!allowed["en"].contains_key("checkout.promo.code"));
+        assert_eq!(
+            skipped,
+            vec![LockedKey {
+                lang: "en".to_string(),
+                key: "checkout.promo.code".to_string(),
+                holder: "growth".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Messages directory settings
+//!
+//! The config options that change how locale files are found, read and
+//! compared (`layout`, `languageDirs`, `format`, `keySeparator`,
+//! `comparator`, `dialect`, `aliases`, `placeholderPatterns`) belong to one
+//! messages directory. [`MessagesDir`] carries them together with the path:
+//! [`I18nConfig::messages`](super::I18nConfig::messages) builds it from the
+//! config, and the scanner and the modules built on it take it instead of a
+//! bare path. Two directories in the same process (overlays, tests) never
+//! share settings.
+//!
+//! `MessagesDir` dereferences to its [`Path`], so the state files kept next
+//! to the messages (backups, snapshots, caches) accept it unchanged.
+
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::compare::Comparator;
+use super::flatten::DEFAULT_SEPARATOR;
+use super::formats::{registry, MobileFormat, TranslationFormat};
+use super::i18next::Dialect;
+use super::scanner::Layout;
+
+/// messages 目录及其读写设置
+#[derive(Debug, Clone)]
+pub struct MessagesDir {
+    /// 目录路径
+    pub path: PathBuf,
+    /// 目录布局（`layout`）
+    pub layout: Layout,
+    /// 移动端资源格式（`format`）
+    pub format: Option<MobileFormat>,
+    /// 语言 -> 独立的语言目录（`languageDirs`）
+    pub language_dirs: BTreeMap<String, PathBuf>,
+    /// 键分隔符（`keySeparator`）
+    pub separator: String,
+    /// 值比较方式（`comparator`）
+    pub comparator: Comparator,
+    /// 键命名约定（`dialect`）
+    pub dialect: Dialect,
+    /// 别名语言 -> 基础语言（`aliases`）
+    pub aliases: BTreeMap<String, String>,
+    /// 自定义占位符正则（`placeholderPatterns`）
+    pub placeholder_patterns: Vec<Regex>,
+}
+
+impl MessagesDir {
+    /// 使用默认设置的 messages 目录
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            layout: Layout::default(),
+            format: None,
+            language_dirs: BTreeMap::new(),
+            separator: DEFAULT_SEPARATOR.to_string(),
+            comparator: Comparator::default(),
+            dialect: Dialect::default(),
+            aliases: BTreeMap::new(),
+            placeholder_patterns: Vec::new(),
+        }
+    }
+
+    /// 确定文件的格式，不支持的文件返回 `None`
+    ///
+    /// 配置了移动端格式时先匹配其资源文件，其余文件使用共享的格式注册表。
+    pub fn detect(&self, path: &Path) -> Option<Arc<dyn TranslationFormat>> {
+        self.format
+            .and_then(|format| format.detect(path))
+            .or_else(|| registry::detect(path))
+    }
+
+    /// 是否为可保存翻译的文件（格式已知且不是模板）
+    pub fn is_translation_file(&self, path: &Path) -> bool {
+        self.detect(path).is_some_and(|format| !format.is_template())
+    }
+}
+
+impl Default for MessagesDir {
+    fn default() -> Self {
+        Self::new(PathBuf::new())
+    }
+}
+
+impl Deref for MessagesDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for MessagesDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_mobile_format_first() {
+        let plain = MessagesDir::new("res");
+        assert!(plain.detect(Path::new("res/values-de/strings.xml")).is_none());
+        assert!(plain.is_translation_file(Path::new("res/en/common.json")));
+        assert!(!plain.is_translation_file(Path::new("res/en/messages.pot")));
+
+        let android = MessagesDir {
+            format: Some(MobileFormat::Android),
+            ..MessagesDir::new("res")
+        };
+        assert_eq!(android.detect(Path::new("res/values-de/strings.xml")).unwrap().name(), "Android strings");
+        assert!(android.is_translation_file(Path::new("res/en/common.json")));
+        assert_eq!(&*android, Path::new("res"));
+    }
+}
@@ -0,0 +1,281 @@
+//! Missing-translation reports
+//!
+//! Lists, for every language, the keys the base language has a value for
+//! but the language lacks (`missing`) or leaves empty (`empty`), with the
+//! share of base keys that are translated. `yflow missing` renders the
+//! report as a table, JSON or CSV (one row per untranslated key) for
+//! dashboards.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::Translations;
+
+/// 未翻译的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingStatus {
+    /// 语言中没有该键
+    Missing,
+    /// 值为空
+    Empty,
+}
+
+impl MissingStatus {
+    /// 显示名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Empty => "empty",
+        }
+    }
+}
+
+/// 未翻译的键
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingKey {
+    /// 键名
+    pub key: String,
+    /// 原因
+    pub status: MissingStatus,
+}
+
+/// 一个语言的统计
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageMissing {
+    /// 语言代码
+    pub lang: String,
+    /// 基准语言中有值的键数
+    pub total: usize,
+    /// 已翻译的键数
+    pub translated: usize,
+    /// 缺少的键数
+    pub missing: usize,
+    /// 值为空的键数
+    pub empty: usize,
+    /// 完成度（百分比）
+    pub completion: f64,
+    /// 未翻译的键（按键名排序）
+    pub keys: Vec<MissingKey>,
+}
+
+/// 所有语言的未翻译报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MissingReport {
+    /// 基准语言
+    pub base: String,
+    /// 各语言的统计（按语言代码排序，不含基准语言）
+    pub languages: Vec<LanguageMissing>,
+}
+
+impl MissingReport {
+    /// 比较各语言与基准语言
+    ///
+    /// # 参数
+    ///
+    /// * `translations` - 所有语言的翻译
+    /// * `base` - 基准语言
+    /// * `languages` - 要统计的语言（为空时为除基准语言外的所有语言；本地没有的语言视为全部缺少）
+    ///
+    /// # Errors
+    ///
+    /// 基准语言不存在时返回错误
+    pub fn collect(translations: &Translations, base: &str, languages: &[String]) -> Result<Self> {
+        let Some(base_keys) = translations.get(base) else {
+            return Err(anyhow::anyhow!("Base language '{}' not found", base));
+        };
+        let mut expected: Vec<&String> = base_keys
+            .iter()
+            .filter(|(_, value)| !value.trim().is_empty())
+            .map(|(key, _)| key)
+            .collect();
+        expected.sort_unstable();
+
+        let mut langs: Vec<&String> = if languages.is_empty() {
+            translations.keys().filter(|lang| *lang != base).collect()
+        } else {
+            languages.iter().filter(|lang| *lang != base).collect()
+        };
+        langs.sort_unstable();
+        langs.dedup();
+
+        let empty = HashMap::new();
+        let languages = langs
+            .into_iter()
+            .map(|lang| {
+                let keys_of_lang = translations.get(lang).unwrap_or(&empty);
+                let keys: Vec<MissingKey> = expected
+                    .iter()
+                    .filter_map(|key| {
+                        let status = match keys_of_lang.get(*key) {
+                            None => MissingStatus::Missing,
+                            Some(value) if value.trim().is_empty() => MissingStatus::Empty,
+                            Some(_) => return None,
+                        };
+                        Some(MissingKey {
+                            key: key.to_string(),
+                            status,
+                        })
+                    })
+                    .collect();
+                let missing = keys.iter().filter(|key| key.status == MissingStatus::Missing).count();
+                let total = expected.len();
+                let translated = total - keys.len();
+                LanguageMissing {
+                    lang: lang.clone(),
+                    total,
+                    translated,
+                    missing,
+                    empty: keys.len() - missing,
+                    completion: completion(translated, total),
+                    keys,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            base: base.to_string(),
+            languages,
+        })
+    }
+
+    /// 未翻译的键总数
+    pub fn untranslated(&self) -> usize {
+        self.languages.iter().map(|lang| lang.keys.len()).sum()
+    }
+
+    /// 渲染为文本：各语言的统计表，然后列出未翻译的键
+    pub fn render_table(&self) -> String {
+        let mut rows = vec![[
+            "LANGUAGE".to_string(),
+            "TRANSLATED".to_string(),
+            "MISSING".to_string(),
+            "EMPTY".to_string(),
+            "COMPLETE".to_string(),
+        ]];
+        for lang in &self.languages {
+            rows.push([
+                lang.lang.clone(),
+                format!("{}/{}", lang.translated, lang.total),
+                lang.missing.to_string(),
+                lang.empty.to_string(),
+                format!("{:.1}%", lang.completion),
+            ]);
+        }
+        let widths: Vec<usize> = (0..5)
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+
+        let mut out = String::new();
+        for row in &rows {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        for lang in self.languages.iter().filter(|lang| !lang.keys.is_empty()) {
+            out.push_str(&format!("\n{} ({}):\n", lang.lang, lang.keys.len()));
+            let width = lang.keys.iter().map(|key| key.key.chars().count()).max().unwrap_or(0);
+            for key in &lang.keys {
+                out.push_str(&format!("  {:<width$}  {}\n", key.key, key.status.name(), width = width));
+            }
+        }
+        out
+    }
+
+    /// 渲染为 CSV：每个未翻译的键一行（`language,key,status,completion`）
+    ///
+    /// # Errors
+    ///
+    /// CSV 写入失败时返回错误
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(["language", "key", "status", "completion"])?;
+        for lang in &self.languages {
+            let completion = format!("{:.1}", lang.completion);
+            for key in &lang.keys {
+                writer.write_record([lang.lang.as_str(), key.key.as_str(), key.status.name(), completion.as_str()])?;
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+/// 完成度百分比（没有键时为 100%）
+fn completion(translated: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    translated as f64 * 100.0 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations() -> Translations {
+        [
+            ("en", vec![("title", "Title"), ("save", "Save"), ("cancel", "Cancel"), ("blank", "")]),
+            ("de", vec![("title", "Titel"), ("save", "")]),
+            ("fr", vec![("title", "Titre"), ("save", "Enregistrer"), ("cancel", "Annuler")]),
+        ]
+        .into_iter()
+        .map(|(lang, keys)| {
+            let keys = keys.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            (lang.to_string(), keys)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_collect() {
+        let report = MissingReport::collect(&translations(), "en", &[]).unwrap();
+        assert_eq!(report.languages.len(), 2);
+        let de = &report.languages[0];
+        assert_eq!((de.lang.as_str(), de.total, de.translated, de.missing, de.empty), ("de", 3, 1, 1, 1));
+        assert_eq!(
+            de.keys,
+            vec![
+                MissingKey {
+                    key: "cancel".to_string(),
+                    status: MissingStatus::Missing,
+                },
+                MissingKey {
+                    key: "save".to_string(),
+                    status: MissingStatus::Empty,
+                },
+            ]
+        );
+        assert_eq!(report.languages[1].completion, 100.0);
+        assert_eq!(report.untranslated(), 2);
+
+        // 本地没有的语言全部缺少
+        let report = MissingReport::collect(&translations(), "en", &["ja".to_string()]).unwrap();
+        assert_eq!(report.languages[0].missing, 3);
+        assert_eq!(report.languages[0].completion, 0.0);
+
+        assert!(MissingReport::collect(&translations(), "xx", &[]).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let report = MissingReport::collect(&translations(), "en", &[]).unwrap();
+        assert_eq!(
+            report.render_table(),
+            "LANGUAGE  TRANSLATED  MISSING  EMPTY  COMPLETE\n\
+             de        1/3         1        1      33.3%\n\
+             fr        3/3         0        0      100.0%\n\
+             \n\
+             de (2):\n  cancel  missing\n  save    empty\n"
+        );
+        assert_eq!(
+            report.to_csv().unwrap(),
+            "language,key,status,completion\nde,cancel,missing,33.3\nde,save,empty,33.3\n"
+        );
+    }
+}
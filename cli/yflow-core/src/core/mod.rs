@@ -0,0 +1,436 @@
+//! Core modules for YFlow CLI
+//!
+//! This module contains the core business logic components including
+//! configuration management, file scanning, JSON flattening, and language mapping.
+
+#![allow(dead_code)]
+
+pub mod aliases;
+pub mod attribution;
+pub mod backup;
+pub mod change_guard;
+pub mod compare;
+pub mod config;
+pub mod coverage;
+pub mod conflicts;
+pub mod credentials;
+pub mod deadline;
+pub mod delivery;
+pub mod delta;
+pub mod download;
+pub mod drift;
+pub mod failure;
+pub mod scanner;
+pub mod fetch_cache;
+pub mod file_split;
+pub mod flatten;
+pub mod formats;
+pub mod git;
+pub mod graph;
+pub mod i18next;
+pub mod icu;
+pub mod import_plan;
+pub mod import_summary;
+pub mod key_filter;
+pub mod key_path;
+pub mod language_mapping;
+pub mod lint;
+pub mod locale_filter;
+pub mod locks;
+pub mod messages_dir;
+pub mod missing;
+pub mod namespaces;
+pub mod overlays;
+pub mod ownership;
+pub mod packed;
+pub mod paths;
+pub mod placeholders;
+pub mod qa;
+pub mod redact;
+pub mod routing;
+pub mod run_summary;
+pub mod secrets;
+pub mod since;
+pub mod snapshot;
+pub mod stale;
+pub mod status_cache;
+pub mod sync_plan;
+pub mod timestamp;
+pub mod todo;
+pub mod validate;
+
+pub use delta::DeltaCache;
+pub use flatten::{flatten_object, unflatten_object};
+pub use key_filter::KeyFilter;
+pub use key_path::{KeyPath, KeyPrefix, Namespace};
+pub use lint::LintRule;
+pub use locale_filter::LocaleFilter;
+pub use messages_dir::MessagesDir;
+pub use ownership::OwnershipPolicy;
+pub use routing::Route;
+pub use secrets::SecretProvider;
+pub use sync_plan::SyncPlan;
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::api::signing::AuthMode;
+use attribution::Author;
+use crate::api::tls::TlsSettings;
+
+/// 配置文件结构
+///
+/// 对应原 TypeScript 的 I18nConfig 接口
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct I18nConfig {
+    /// messages 目录路径
+    #[serde(rename = "messagesDir")]
+    pub messages_dir: PathBuf,
+    /// 项目 ID
+    #[serde(rename = "projectId")]
+    pub project_id: u64,
+    /// API 地址
+    #[serde(rename = "apiUrl")]
+    pub api_url: String,
+    /// API 密钥
+    #[serde(rename = "apiKey", default)]
+    pub api_key: String,
+    /// 认证方式（`api_key` 或 `hmac`）
+    #[serde(rename = "authMode", default)]
+    pub auth_mode: AuthMode,
+    /// `authMode: hmac` 时用于请求签名的密钥
+    #[serde(rename = "apiSecret", default, skip_serializing_if = "Option::is_none")]
+    pub api_secret: Option<String>,
+    /// 用于读取 API 密钥的外部命令（如 `op read op://vault/yflow/key`）
+    #[serde(rename = "apiKeyCommand", default, skip_serializing_if = "Option::is_none")]
+    pub api_key_command: Option<String>,
+    /// 原生密钥提供方（Vault / AWS Secrets Manager）
+    #[serde(rename = "secretProvider", default, skip_serializing_if = "Option::is_none")]
+    pub secret_provider: Option<SecretProvider>,
+    /// 源语言（本地语言代码），设置后记录源文本的变化以发现过期的翻译
+    #[serde(rename = "sourceLanguage", default, skip_serializing_if = "Option::is_none")]
+    pub source_language: Option<String>,
+    /// 语言代码映射
+    #[serde(rename = "languageMapping", default)]
+    pub language_mapping: HashMap<String, String>,
+    /// 单独存放的语言目录（语言 -> 目录），未列出的语言位于 `messagesDir/<lang>`
+    #[serde(rename = "languageDirs", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub language_dirs: BTreeMap<String, PathBuf>,
+    /// 语言别名（别名语言 -> 基础语言），别名语言的文件只保存与基础语言不同的键
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// 品牌覆盖层目录（结构与 messages 目录相同），导入时按顺序合并到基础翻译之上
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlays: Vec<PathBuf>,
+    /// messages 目录布局（`nested-dirs` 或 `flat-files`）
+    #[serde(default, skip_serializing_if = "scanner::Layout::is_nested_dirs")]
+    pub layout: scanner::Layout,
+    /// 移动端资源格式（`android` 或 `ios`），语言目录为 `values-<lang>/` 或 `<lang>.lproj/`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<formats::MobileFormat>,
+    /// 判断值是否变化的比较方式（`exact`、`trimmed` 或 `normalized-html`）
+    #[serde(default, skip_serializing_if = "compare::Comparator::is_exact")]
+    pub comparator: compare::Comparator,
+    /// 键的命名约定（`i18next` 时按复数/上下文后缀归组，并检查复数形式是否完整）
+    #[serde(default, skip_serializing_if = "i18next::Dialect::is_plain")]
+    pub dialect: i18next::Dialect,
+    /// 按键前缀/语言路由到其他后端项目的规则
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<Route>,
+    /// 键前缀 -> 所属团队
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub owners: HashMap<String, String>,
+    /// 修改其他团队的键时的处理策略
+    #[serde(rename = "ownershipPolicy", default)]
+    pub ownership_policy: OwnershipPolicy,
+    /// 翻译内容检查规则（lint 命令使用）
+    #[serde(rename = "lintRules", default, skip_serializing_if = "Vec::is_empty")]
+    pub lint_rules: Vec<LintRule>,
+    /// 自定义占位符语法（正则，整个匹配视为一个占位符，如 `:[a-z_]+`）
+    #[serde(rename = "placeholderPatterns", default, skip_serializing_if = "Vec::is_empty")]
+    pub placeholder_patterns: Vec<String>,
+    /// 单个翻译文件的最大键数（lint 报告超出的文件，`fmt --split` 按命名空间拆分）
+    #[serde(rename = "maxKeysPerFile", default, skip_serializing_if = "Option::is_none")]
+    pub max_keys_per_file: Option<usize>,
+    /// 展平嵌套键时使用的分隔符（默认 `.`），键名中出现的分隔符会被转义
+    #[serde(rename = "keySeparator", default, skip_serializing_if = "Option::is_none")]
+    pub key_separator: Option<String>,
+    /// 后端 TLS 证书的 SHA-256 指纹（配置后只接受匹配的证书）
+    #[serde(rename = "pinnedSha256", default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_sha256: Vec<String>,
+    /// 单个请求的超时（秒，默认 30），大型项目下载翻译时可调大
+    #[serde(rename = "timeoutSecs", default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// 建立连接的超时（秒，默认 10）
+    #[serde(rename = "connectTimeoutSecs", default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// TLS 设置（额外信任的 CA 证书等）
+    #[serde(default, skip_serializing_if = "TlsSettings::is_empty")]
+    pub tls: TlsSettings,
+    /// 同步时允许覆盖的本地键比例上限（百分比，默认 30），超过时需要确认
+    #[serde(rename = "maxChangePercent", default, skip_serializing_if = "Option::is_none")]
+    pub max_change_percent: Option<u32>,
+    /// `sync --branch-per-run` 推送分支后运行的命令（如 `gh pr create --fill --head {branch}`），
+    /// 支持 `{branch}`、`{base}`、`{remote}`、`{url}` 占位符（值通过 `YFLOW_BRANCH`、`YFLOW_BASE`、
+    /// `YFLOW_REMOTE`、`YFLOW_PR_URL` 环境变量传入，不被 shell 解析）
+    #[serde(rename = "prCommand", default, skip_serializing_if = "Option::is_none")]
+    pub pr_command: Option<String>,
+    /// 推送和提交时使用的作者（只来自命令行 `--author`，不在配置文件中）
+    #[serde(skip)]
+    pub author: Option<Author>,
+}
+
+impl I18nConfig {
+    /// messages 目录及其读写设置
+    ///
+    /// 无效的 `placeholderPatterns` 被忽略（`load_config` 已校验）。
+    pub fn messages(&self) -> MessagesDir {
+        MessagesDir {
+            path: self.messages_dir.clone(),
+            layout: self.layout,
+            format: self.format,
+            language_dirs: self.language_dirs.clone(),
+            separator: self.separator().to_string(),
+            comparator: self.comparator,
+            dialect: self.dialect,
+            aliases: self.aliases.clone(),
+            placeholder_patterns: self
+                .placeholder_patterns
+                .iter()
+                .filter_map(|pattern| Regex::new(pattern).ok())
+                .collect(),
+        }
+    }
+
+    /// 品牌覆盖层目录（与 messages 目录使用相同的布局、键分隔符和键命名约定）
+    pub fn overlay_dirs(&self) -> Vec<MessagesDir> {
+        self.overlays
+            .iter()
+            .map(|dir| MessagesDir {
+                layout: self.layout,
+                separator: self.separator().to_string(),
+                dialect: self.dialect,
+                ..MessagesDir::new(dir.clone())
+            })
+            .collect()
+    }
+
+    /// 键分隔符（`keySeparator`，默认 `.`）
+    pub fn separator(&self) -> &str {
+        self.key_separator.as_deref().unwrap_or(flatten::DEFAULT_SEPARATOR)
+    }
+}
+
+/// 翻译数据格式：语言代码 -> 键值对
+pub type Translations = HashMap<String, HashMap<String, String>>;
+
+/// 键的来源文件：(语言代码, 键) -> 定义该键的文件（与 [`ScanResult::files`] 相同的逻辑路径）
+pub type KeyOrigins = HashMap<(String, String), PathBuf>;
+
+/// 扫描结果
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    /// 按语言分组的翻译
+    pub translations: Translations,
+    /// 扫描的文件列表
+    pub files: Vec<PathBuf>,
+    /// 每个键的来源文件
+    ///
+    /// 同一个键在多个文件中定义时，记录其值生效的文件。
+    pub key_origins: KeyOrigins,
+    /// 总键数
+    pub key_count: usize,
+    /// 无法读取或解析而被跳过的文件
+    pub quarantine: Vec<QuarantinedFile>,
+}
+
+impl ScanResult {
+    /// 定义某个键的文件（逻辑路径，用 [`scanner::resolve_file`] 得到磁盘路径）
+    pub fn origin(&self, lang: &str, key: &str) -> Option<&Path> {
+        self.key_origins
+            .get(&(lang.to_string(), key.to_string()))
+            .map(PathBuf::as_path)
+    }
+}
+
+/// 被隔离的文件
+///
+/// 扫描时无法读取或解析的文件不会中断扫描，而是记录在这里，
+/// 由命令在结束时统一报告。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedFile {
+    /// 文件路径
+    pub path: PathBuf,
+    /// 出错的行号（从 1 开始，解析器提供时）
+    pub line: Option<usize>,
+    /// 出错的列号（从 1 开始，解析器提供时）
+    pub column: Option<usize>,
+    /// 错误信息
+    pub error: String,
+}
+
+impl std::fmt::Display for QuarantinedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+/// 进度事件
+///
+/// 由扫描/写入/导入流程发出，UI 层据此驱动进度条；
+/// 可序列化为 JSON，用于输出机器可读的事件流。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// 开始处理某个语言
+    LanguageStarted {
+        /// 语言代码
+        lang: String,
+        /// 当前语言序号（从 1 开始）
+        index: usize,
+        /// 语言总数
+        total: usize,
+        /// 该语言需要处理的单元数（文件数、键数或下载块数）
+        items: usize,
+    },
+    /// 写入了一个文件
+    FileWritten {
+        /// 语言代码
+        lang: String,
+        /// 写入的文件路径
+        path: PathBuf,
+    },
+    /// 发送了一个批次
+    BatchSent {
+        /// 语言代码
+        lang: String,
+        /// 批次序号（从 1 开始）
+        batch: usize,
+        /// 批次总数
+        total_batches: usize,
+        /// 批次中的键数
+        keys: usize,
+        /// 批次中键和值的字节数
+        bytes: usize,
+    },
+    /// 处理了若干个键
+    KeysProcessed {
+        /// 语言代码
+        lang: String,
+        /// 本次处理的键数
+        n: usize,
+    },
+    /// 某个语言处理完成
+    LanguageFinished {
+        /// 语言代码
+        lang: String,
+    },
+}
+
+/// 进度回调类型
+///
+/// 每个 [`ProgressEvent`] 都会调用一次。
+pub type ProgressCallback = Box<dyn Fn(ProgressEvent) + Send + Sync>;
+
+/// 导入结果
+#[derive(Debug, Clone, Default)]
+pub struct ImportResult {
+    /// 新增的键数
+    pub added: usize,
+    /// 更新的键数
+    pub updated: usize,
+    /// 失败的键数
+    pub failed: usize,
+    /// 被其他持有者锁定而跳过的键数
+    pub locked: usize,
+    /// 与后端相同而跳过的键数
+    pub unchanged: usize,
+    /// 从后端删除（`--prune`）的键数
+    pub deleted: usize,
+    /// 错误列表
+    pub errors: Vec<String>,
+    /// 各语言（后端语言代码）的结果
+    pub languages: BTreeMap<String, LanguageCounts>,
+}
+
+impl ImportResult {
+    /// 合并另一个导入结果（用于多项目路由时汇总）
+    pub fn absorb(&mut self, other: ImportResult) {
+        self.added += other.added;
+        self.updated += other.updated;
+        self.failed += other.failed;
+        self.locked += other.locked;
+        self.unchanged += other.unchanged;
+        self.deleted += other.deleted;
+        self.errors.extend(other.errors);
+        for (lang, counts) in other.languages {
+            self.languages.entry(lang).or_default().absorb(&counts);
+        }
+    }
+
+    /// 所有语言合计的结果
+    pub fn counts(&self) -> LanguageCounts {
+        LanguageCounts {
+            added: self.added,
+            updated: self.updated,
+            unchanged: self.unchanged,
+            failed: self.failed,
+        }
+    }
+}
+
+/// 单个语言的导入结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LanguageCounts {
+    /// 新增的键数
+    pub added: usize,
+    /// 更新的键数
+    pub updated: usize,
+    /// 与后端相同而跳过的键数
+    pub unchanged: usize,
+    /// 失败的键数
+    pub failed: usize,
+}
+
+impl LanguageCounts {
+    /// 累加另一个结果
+    pub fn absorb(&mut self, other: &LanguageCounts) {
+        self.added += other.added;
+        self.updated += other.updated;
+        self.unchanged += other.unchanged;
+        self.failed += other.failed;
+    }
+}
+
+/// 同步结果
+#[derive(Debug, Clone, Default)]
+pub struct SyncResult {
+    /// 下载的键数
+    pub downloaded: usize,
+    /// 写入的文件数
+    pub written: usize,
+    /// 跳过的键数
+    pub skipped: usize,
+    /// 错误列表
+    pub errors: Vec<String>,
+    /// 写入后校验失败的详情（文件路径及缺失/不一致的键）
+    pub verification_failures: Vec<String>,
+    /// 检测到的冲突数（本地和后端自上次同步后都修改过的键）
+    pub conflicts: usize,
+    /// 后端和本地文件都未变化而跳过的语言数
+    pub unchanged: usize,
+    /// `--commit` 创建的 git 提交哈希
+    pub commit: Option<String>,
+    /// 各语言新增、更新和跳过的键数
+    pub languages: BTreeMap<String, LanguageCounts>,
+}
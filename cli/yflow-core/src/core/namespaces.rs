@@ -0,0 +1,217 @@
+//! Namespace routing module
+//!
+//! Decides which file of a language a synced key is written to. Projects
+//! usually split a language into one file per namespace (`errors.json` holds
+//! the `errors.*` keys), so a key goes to:
+//!
+//! 1. every file that already defines it
+//!    (with `"dialect": "i18next"`, else the first file defining another
+//!    plural or context form of it, see [`super::i18next`])
+//! 2. otherwise the file whose namespace is the longest prefix of the key,
+//!    where a file's namespace is its path inside the language directory
+//!    without the extension (`admin/users.json` → `admin.users`)
+//! 3. otherwise the first writable file of the language
+//!
+//! New languages get one file per namespace known from the other languages,
+//! plus `sync.json` for keys outside every namespace.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::i18next::{self, Dialect};
+use super::key_path::{KeyPath, Namespace};
+
+/// 新语言中不属于任何命名空间的键写入的文件
+pub const FALLBACK_FILE: &str = "sync.json";
+
+/// 可写入键的目标文件
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceFile {
+    /// 文件的命名空间（见 [`file_namespace`]）
+    pub namespace: Namespace,
+    /// 文件中已定义的键
+    pub keys: HashSet<String>,
+}
+
+/// 文件的命名空间：语言目录内的相对路径去掉扩展名，各级目录和文件名按键解析
+pub fn file_namespace(relative: &Path, separator: &str) -> Namespace {
+    relative
+        .with_extension("")
+        .components()
+        .fold(Namespace::default(), |namespace, c| {
+            namespace.join(&KeyPath::parse(&c.as_os_str().to_string_lossy(), separator))
+        })
+}
+
+/// 键是否属于命名空间（等于命名空间或位于其下，根命名空间不包含任何键）
+pub fn key_in_namespace(key: &KeyPath, namespace: &Namespace) -> bool {
+    !namespace.is_root() && key.starts_with(namespace)
+}
+
+/// 最长匹配键的命名空间的下标
+fn best_namespace<'a>(key: &KeyPath, namespaces: impl Iterator<Item = (usize, &'a Namespace)>) -> Option<usize> {
+    namespaces
+        .filter(|(_, namespace)| key_in_namespace(key, namespace))
+        .max_by_key(|(_, namespace)| namespace.segments().len())
+        .map(|(index, _)| index)
+}
+
+/// 将一个语言的翻译分配到它的各个文件
+///
+/// 返回与 `files` 一一对应的翻译子集。`files` 为空时返回空列表。
+pub fn route_keys(
+    files: &[NamespaceFile],
+    translations: &HashMap<String, String>,
+    dialect: Dialect,
+    separator: &str,
+) -> Vec<HashMap<String, String>> {
+    let mut routed = vec![HashMap::new(); files.len()];
+    if files.is_empty() {
+        return routed;
+    }
+
+    // i18next：每个文件中键的组
+    let stems: HashSet<&str> = match dialect {
+        Dialect::Plain => HashSet::new(),
+        Dialect::I18next => files
+            .iter()
+            .flat_map(|f| &f.keys)
+            .chain(translations.keys())
+            .map(|key| i18next::split_plural(key).0)
+            .collect(),
+    };
+    let groups: Vec<HashSet<&str>> = files
+        .iter()
+        .map(|f| match dialect {
+            Dialect::Plain => HashSet::new(),
+            Dialect::I18next => f.keys.iter().map(|key| i18next::group_of(key, &stems)).collect(),
+        })
+        .collect();
+
+    for (key, value) in translations {
+        // 1. 已定义此键的文件，或定义了同组其他形式的第一个文件
+        let defining: Vec<usize> = (0..files.len()).filter(|&i| files[i].keys.contains(key)).collect();
+        let sibling = match dialect {
+            Dialect::Plain => None,
+            Dialect::I18next => {
+                let group = i18next::group_of(key, &stems);
+                groups.iter().position(|file_groups| file_groups.contains(group))
+            }
+        };
+        let targets = if !defining.is_empty() {
+            defining
+        } else if let Some(index) = sibling {
+            vec![index]
+        } else {
+            // 2. 命名空间匹配的文件，3. 第一个文件
+            let by_namespace = best_namespace(&KeyPath::parse(key, separator), files.iter().map(|f| &f.namespace).enumerate());
+            vec![by_namespace.unwrap_or(0)]
+        };
+        for index in targets {
+            routed[index].insert(key.clone(), value.clone());
+        }
+    }
+    routed
+}
+
+/// 为新语言按命名空间拆分翻译
+///
+/// `templates` 为其他语言中的文件（语言目录内的相对路径）。返回
+/// 相对路径 -> 翻译；不属于任何命名空间的键放入 [`FALLBACK_FILE`]。
+pub fn split_new_language(
+    templates: &[PathBuf],
+    translations: &HashMap<String, String>,
+    separator: &str,
+) -> BTreeMap<PathBuf, HashMap<String, String>> {
+    let namespaces: Vec<Namespace> = templates.iter().map(|file| file_namespace(file, separator)).collect();
+    let mut files: BTreeMap<PathBuf, HashMap<String, String>> = BTreeMap::new();
+    for (key, value) in translations {
+        let file = match best_namespace(&KeyPath::parse(key, separator), namespaces.iter().enumerate()) {
+            Some(index) => templates[index].clone(),
+            None => PathBuf::from(FALLBACK_FILE),
+        };
+        files.entry(file).or_default().insert(key.clone(), value.clone());
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(namespace: &str, keys: &[&str]) -> NamespaceFile {
+        NamespaceFile {
+            namespace: KeyPath::parse(namespace, "."),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    fn values(keys: &[&str]) -> HashMap<String, String> {
+        keys.iter().map(|k| (k.to_string(), format!("{} value", k))).collect()
+    }
+
+    fn sorted_keys(map: &HashMap<String, String>) -> Vec<&str> {
+        let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_file_namespace() {
+        assert_eq!(file_namespace(Path::new("errors.json"), ".").to_key_with("."), "errors");
+        assert_eq!(file_namespace(Path::new("admin/users.yaml"), ".").segments(), ["admin", "users"]);
+        assert_eq!(file_namespace(Path::new("admin.users.json"), "."), file_namespace(Path::new("admin/users.json"), "."));
+        let errors = KeyPath::parse("errors", ".");
+        assert!(key_in_namespace(&KeyPath::parse("errors.notFound", "."), &errors));
+        assert!(key_in_namespace(&KeyPath::parse("errors", "."), &errors));
+        assert!(!key_in_namespace(&KeyPath::parse("errorsPage.title", "."), &errors));
+        assert!(!key_in_namespace(&KeyPath::parse(r"errors\.notFound", "."), &errors));
+        assert!(!key_in_namespace(&KeyPath::parse("title", "."), &KeyPath::parse("", ".")));
+    }
+
+    #[test]
+    fn test_route_keys() {
+        let files = [
+            file("common", &["title", "shared"]),
+            file("errors", &["errors.old", "shared"]),
+            file("admin", &[]),
+            file("admin.users", &[]),
+        ];
+        let routed = route_keys(
+            &files,
+            &values(&["title", "shared", "errors.new", "admin.title", "admin.users.name", "other"]),
+            Dialect::Plain,
+            ".",
+        );
+        assert_eq!(sorted_keys(&routed[0]), ["other", "shared", "title"]);
+        assert_eq!(sorted_keys(&routed[1]), ["errors.new", "shared"]);
+        assert_eq!(sorted_keys(&routed[2]), ["admin.title"]);
+        assert_eq!(sorted_keys(&routed[3]), ["admin.users.name"]);
+        assert!(route_keys(&[], &values(&["a"]), Dialect::Plain, ".").is_empty());
+    }
+
+    #[test]
+    fn test_route_keys_i18next() {
+        let files = [
+            file("common", &["title"]),
+            file("shop", &["cart.item_one", "cart.item_other", "friend"]),
+        ];
+        let translations = values(&["cart.item_few", "cart.other_one", "friend_male_one"]);
+        let routed = route_keys(&files, &translations, Dialect::I18next, ".");
+        assert_eq!(sorted_keys(&routed[0]), ["cart.other_one"]);
+        assert_eq!(sorted_keys(&routed[1]), ["cart.item_few", "friend_male_one"]);
+
+        let routed = route_keys(&files, &translations, Dialect::Plain, ".");
+        assert_eq!(routed[1].len(), 0);
+    }
+
+    #[test]
+    fn test_split_new_language() {
+        let templates = [PathBuf::from("common.json"), PathBuf::from("errors.json")];
+        let split = split_new_language(&templates, &values(&["errors.notFound", "common.ok", "title"]), ".");
+        assert_eq!(split.len(), 3);
+        assert_eq!(sorted_keys(&split[Path::new("errors.json")]), ["errors.notFound"]);
+        assert_eq!(sorted_keys(&split[Path::new("common.json")]), ["common.ok"]);
+        assert_eq!(sorted_keys(&split[Path::new(FALLBACK_FILE)]), ["title"]);
+    }
+}
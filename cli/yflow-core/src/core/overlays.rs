@@ -0,0 +1,135 @@
+//! Brand overlays
+//!
+//! White-label products keep one set of base messages and small per-brand
+//! deltas: `"overlays": ["./brand/acme/messages"]` lists directories laid
+//! out like the messages directory whose keys replace the base values.
+//! Import pushes the base messages with every overlay merged on top (later
+//! overlays win). Sync diffs each overlay separately: a key defined in an
+//! overlay is written back to that overlay, every other key to the base
+//! messages.
+
+use anyhow::{Context, Result};
+use super::scanner::scan_messages_dir_with;
+use super::{MessagesDir, ScanResult, Translations};
+
+/// 品牌覆盖层
+#[derive(Debug, Clone, Default)]
+pub struct Overlay {
+    /// 覆盖层目录（与 messages 目录结构相同）
+    pub dir: MessagesDir,
+    /// 覆盖层的扫描结果
+    pub scan: ScanResult,
+}
+
+/// 按配置顺序扫描所有覆盖层
+///
+/// # Errors
+///
+/// 覆盖层目录不存在，或 `fail_fast` 时遇到无法解析的文件
+pub async fn scan_overlays(dirs: &[MessagesDir], fail_fast: bool) -> Result<Vec<Overlay>> {
+    let mut overlays = Vec::with_capacity(dirs.len());
+    for dir in dirs {
+        let scan = scan_messages_dir_with(dir, fail_fast)
+            .await
+            .with_context(|| format!("Failed to scan overlay {}", dir.display()))?;
+        overlays.push(Overlay { dir: dir.clone(), scan });
+    }
+    Ok(overlays)
+}
+
+/// 将覆盖层合并到基础扫描结果之上（靠后的覆盖层优先）
+///
+/// 覆盖层中无法解析的文件加入基础结果的隔离列表。
+pub fn apply(scan: &mut ScanResult, overlays: &[Overlay]) {
+    if overlays.is_empty() {
+        return;
+    }
+    for overlay in overlays {
+        for (lang, keys) in &overlay.scan.translations {
+            scan.translations
+                .entry(lang.clone())
+                .or_default()
+                .extend(keys.iter().map(|(key, value)| (key.clone(), value.clone())));
+        }
+        scan.quarantine.extend(overlay.scan.quarantine.iter().cloned());
+    }
+    scan.key_count = scan.translations.values().map(|keys| keys.len()).sum();
+}
+
+/// 按键所在的层拆分要写入的翻译
+///
+/// 覆盖层中定义的键属于定义它的最后一个覆盖层，其余的键属于基础 messages 目录。
+///
+/// # Returns
+///
+/// `(基础部分, 每个覆盖层的部分)`，覆盖层顺序与 `overlays` 相同
+pub fn split(translations: &Translations, overlays: &[Overlay]) -> (Translations, Vec<Translations>) {
+    let mut base = Translations::new();
+    let mut layers = vec![Translations::new(); overlays.len()];
+    for (lang, keys) in translations {
+        for (key, value) in keys {
+            let layer = overlays.iter().rposition(|overlay| {
+                overlay
+                    .scan
+                    .translations
+                    .get(lang)
+                    .is_some_and(|overlay_keys| overlay_keys.contains_key(key))
+            });
+            let target = match layer {
+                Some(index) => &mut layers[index],
+                None => &mut base,
+            };
+            target.entry(lang.clone()).or_default().insert(key.clone(), value.clone());
+        }
+    }
+    (base, layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn keys(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_apply_and_split() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        let acme = MessagesDir::new(temp_dir.path().join("brand/acme"));
+        let seasonal = MessagesDir::new(temp_dir.path().join("brand/seasonal"));
+        write(&base_dir, "en/common.json", r#"{"title": "Welcome", "brand": "YFlow", "save": "Save"}"#);
+        write(&acme, "en/common.json", r#"{"brand": "Acme", "title": "Welcome to Acme"}"#);
+        write(&seasonal, "en/common.json", r#"{"title": "Happy holidays"}"#);
+
+        let mut scan = crate::core::scanner::scan_messages_dir(&base_dir).await.unwrap();
+        let overlays = scan_overlays(&[acme.clone(), seasonal.clone()], false).await.unwrap();
+        apply(&mut scan, &overlays);
+        assert_eq!(
+            scan.translations["en"],
+            keys(&[("title", "Happy holidays"), ("brand", "Acme"), ("save", "Save")])
+        );
+        assert_eq!(scan.key_count, 3);
+
+        let remote = Translations::from([(
+            "en".to_string(),
+            keys(&[("title", "Seasons greetings"), ("brand", "Acme Inc"), ("save", "Save"), ("new", "New")]),
+        )]);
+        let (base, layers) = split(&remote, &overlays);
+        assert_eq!(base["en"], keys(&[("save", "Save"), ("new", "New")]));
+        assert_eq!(layers[0]["en"], keys(&[("brand", "Acme Inc")]));
+        assert_eq!(layers[1]["en"], keys(&[("title", "Seasons greetings")]));
+
+        assert!(scan_overlays(&[MessagesDir::new(temp_dir.path().join("missing"))], false).await.is_err());
+    }
+}
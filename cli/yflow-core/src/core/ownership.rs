@@ -0,0 +1,196 @@
+//! Key ownership module
+//!
+//! Maps key prefixes to owning teams (CODEOWNERS-style) so a run can warn
+//! about, or refuse, changes to keys owned by another team.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "owners": { "checkout.": "payments", "emails.": "growth" },
+//!   "ownershipPolicy": "block"
+//! }
+//! ```
+//!
+//! The longest matching prefix wins. Keys without an owner may be changed
+//! by anyone.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::key_path::KeyPrefix;
+use super::Translations;
+
+/// 提供团队名的环境变量
+pub const TEAM_ENV_VAR: &str = "I18N_TEAM";
+
+/// 违反归属规则时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnershipPolicy {
+    /// 仅输出警告
+    #[default]
+    Warn,
+    /// 阻止本次操作
+    Block,
+}
+
+/// 单个归属冲突
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipViolation {
+    /// 语言代码
+    pub lang: String,
+    /// 翻译键
+    pub key: String,
+    /// 键的所属团队
+    pub owner: String,
+}
+
+/// 归属规则
+#[derive(Debug, Clone, Default)]
+pub struct Ownership {
+    /// (前缀, 团队)，按前缀长度降序排列
+    rules: Vec<(KeyPrefix, String)>,
+}
+
+impl Ownership {
+    /// 从配置中的 `owners` 映射创建，前缀按键分隔符 `separator` 解析
+    pub fn new(owners: &HashMap<String, String>, separator: &str) -> Self {
+        let mut rules: Vec<(String, String)> = owners
+            .iter()
+            .map(|(prefix, team)| (prefix.clone(), team.clone()))
+            .collect();
+        rules.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|(prefix, team)| (KeyPrefix::parse(&prefix, separator), team))
+                .collect(),
+        }
+    }
+
+    /// 是否定义了任何归属规则
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 获取键的所属团队（最长前缀匹配）
+    pub fn owner_of(&self, key: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(prefix, _)| prefix.matches_key(key))
+            .map(|(_, team)| team.as_str())
+    }
+
+    /// 找出属于其他团队的键
+    ///
+    /// # Arguments
+    ///
+    /// * `team` - 当前执行操作的团队
+    /// * `keys` - 本次将要修改的 (语言, 键)
+    pub fn violations<'a, I>(&self, team: &str, keys: I) -> Vec<OwnershipViolation>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut violations: Vec<OwnershipViolation> = keys
+            .into_iter()
+            .filter_map(|(lang, key)| match self.owner_of(key) {
+                Some(owner) if owner != team => Some(OwnershipViolation {
+                    lang: lang.to_string(),
+                    key: key.to_string(),
+                    owner: owner.to_string(),
+                }),
+                _ => None,
+            })
+            .collect();
+        violations.sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+        violations
+    }
+}
+
+/// 计算本地相对于后端新增或修改的键
+///
+/// # Returns
+///
+/// (语言, 键) 列表
+pub fn changed_keys<'a>(local: &'a Translations, remote: &Translations) -> Vec<(&'a str, &'a str)> {
+    let mut changed = Vec::new();
+    for (lang, local_lang) in local {
+        let remote_lang = remote.get(lang);
+        for (key, value) in local_lang {
+            if remote_lang.and_then(|r| r.get(key)) != Some(value) {
+                changed.push((lang.as_str(), key.as_str()));
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ownership() -> Ownership {
+        Ownership::new(
+            &HashMap::from([
+                ("checkout.".to_string(), "payments".to_string()),
+                ("checkout.promo.".to_string(), "growth".to_string()),
+            ]),
+            ".",
+        )
+    }
+
+    #[test]
+    fn test_owner_of_longest_prefix() {
+        let owners = ownership();
+        assert_eq!(owners.owner_of("checkout.title"), Some("payments"));
+        assert_eq!(owners.owner_of("checkout.promo.code"), Some("growth"));
+        assert_eq!(owners.owner_of("common.title"), None);
+    }
+
+    #[test]
+    fn test_violations() {
+        let owners = ownership();
+        let keys = vec![
+            ("en", "checkout.title"),
+            ("en", "checkout.promo.code"),
+            ("en", "common.title"),
+        ];
+
+        let violations = owners.violations("growth", keys);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].key, "checkout.title");
+        assert_eq!(violations[0].owner, "payments");
+    }
+
+    #[test]
+    fn test_changed_keys() {
+        let local: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("same".to_string(), "Same".to_string()),
+                ("changed".to_string(), "New".to_string()),
+                ("added".to_string(), "Added".to_string()),
+            ]),
+        )]);
+        let remote: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("same".to_string(), "Same".to_string()),
+                ("changed".to_string(), "Old".to_string()),
+            ]),
+        )]);
+
+        let mut changed = changed_keys(&local, &remote);
+        changed.sort();
+        assert_eq!(changed, vec![("en", "added"), ("en", "changed")]);
+    }
+
+    #[test]
+    fn test_policy_deserialize() {
+        let policy: OwnershipPolicy = serde_json::from_str(r#""block""#).unwrap();
+        assert_eq!(policy, OwnershipPolicy::Block);
+        assert_eq!(OwnershipPolicy::default(), OwnershipPolicy::Warn);
+    }
+}
@@ -0,0 +1,137 @@
+//! Packed cache format
+//!
+//! Full copies of translation state (the sync base state, snapshots and
+//! downloaded chunks) used to be stored as pretty JSON, which is slow to
+//! parse and large for big projects. They are now stored as
+//!
+//! ```text
+//! "YFPK" | format version (u16, little endian) | zstd(bincode(value))
+//! ```
+//!
+//! Files written with a different format version (or that are not packed at
+//! all) are reported as stale by [`read`], so callers simply treat them as
+//! missing and rewrite them: bump [`FORMAT_VERSION`] whenever a packed type
+//! changes shape.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// 文件头魔数
+const MAGIC: &[u8; 4] = b"YFPK";
+
+/// 打包格式版本（打包的数据结构变化时递增）
+pub const FORMAT_VERSION: u16 = 1;
+
+/// zstd 压缩级别
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// 打包文件扩展名
+pub const EXTENSION: &str = "bin";
+
+/// 编码为打包格式
+pub fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let raw = bincode::serialize(value).context("Failed to serialize cache")?;
+    let compressed = zstd::encode_all(raw.as_slice(), COMPRESSION_LEVEL).context("Failed to compress cache")?;
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 2 + compressed.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&compressed);
+    Ok(bytes)
+}
+
+/// 解码打包格式
+///
+/// 文件头不匹配（非打包文件或其他格式版本）时返回 `None`。
+///
+/// # Errors
+///
+/// 文件头正确但数据损坏时返回错误
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<Option<T>> {
+    let header_len = MAGIC.len() + 2;
+    if bytes.len() < header_len || &bytes[..MAGIC.len()] != MAGIC {
+        return Ok(None);
+    }
+    let version = u16::from_le_bytes([bytes[MAGIC.len()], bytes[MAGIC.len() + 1]]);
+    if version != FORMAT_VERSION {
+        return Ok(None);
+    }
+
+    let raw = zstd::decode_all(&bytes[header_len..]).context("Failed to decompress cache")?;
+    let value = bincode::deserialize(&raw).context("Failed to deserialize cache")?;
+    Ok(Some(value))
+}
+
+/// 读取打包文件
+///
+/// 文件不存在或已失效（格式版本不同）时返回 `None`。
+pub fn read<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    decode(&bytes).with_context(|| format!("Corrupted cache file: {}", path.display()))
+}
+
+/// 写入打包文件
+///
+/// 先写入临时文件再重命名，中断时不会留下不完整的文件。
+pub fn write<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let bytes = encode(value)?;
+    let tmp = path.with_extension(format!("{}.tmp", EXTENSION));
+    std::fs::write(&tmp, bytes).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Translations;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn sample() -> Translations {
+        let keys: HashMap<String, String> = (0..500)
+            .map(|i| (format!("page.section.key_{}", i), format!("Value number {}", i)))
+            .collect();
+        HashMap::from([("en".to_string(), keys)])
+    }
+
+    #[test]
+    fn test_round_trip_is_smaller_than_json() {
+        let translations = sample();
+        let bytes = encode(&translations).unwrap();
+        assert_eq!(&bytes[..4], MAGIC);
+        assert!(bytes.len() < serde_json::to_vec_pretty(&translations).unwrap().len() / 4);
+
+        let decoded: Translations = decode(&bytes).unwrap().unwrap();
+        assert_eq!(decoded, translations);
+    }
+
+    #[test]
+    fn test_stale_and_foreign_files_are_ignored() {
+        let mut bytes = encode(&sample()).unwrap();
+        bytes[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        assert!(decode::<Translations>(&bytes).unwrap().is_none());
+        assert!(decode::<Translations>(br#"{"en": {}}"#).unwrap().is_none());
+        assert!(decode::<Translations>(b"").unwrap().is_none());
+
+        let mut corrupted = encode(&sample()).unwrap();
+        corrupted.truncate(20);
+        assert!(decode::<Translations>(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_read_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.bin");
+        assert!(read::<Translations>(&path).unwrap().is_none());
+
+        write(&path, &sample()).unwrap();
+        assert_eq!(read::<Translations>(&path).unwrap(), Some(sample()));
+        assert!(!path.with_extension("bin.tmp").exists());
+    }
+}
@@ -0,0 +1,87 @@
+//! Per-user directories
+//!
+//! Locates the user-level YFlow directories at the platform's conventional
+//! location (via the `directories` crate) instead of ad-hoc `~/.something`
+//! paths:
+//!
+//! | | Linux | macOS | Windows |
+//! |---|---|---|---|
+//! | config | `~/.config/yflow` | `~/Library/Application Support/yflow` | `%APPDATA%\yflow\config` |
+//! | cache | `~/.cache/yflow` | `~/Library/Caches/yflow` | `%LOCALAPPDATA%\yflow\cache` |
+//! | data | `~/.local/share/yflow` | `~/Library/Application Support/yflow` | `%APPDATA%\yflow\data` |
+//!
+//! The XDG variables (`XDG_CONFIG_HOME`, ...) are honoured on Linux, and
+//! `YFLOW_CONFIG_DIR` overrides the config directory on every platform.
+//! The global user config (`config.json`, `.toml` or `.yaml` in the config
+//! directory) provides defaults for every repository, see
+//! [`super::config::read_config`].
+
+use directories::{BaseDirs, ProjectDirs};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// 覆盖用户配置目录的环境变量
+pub const CONFIG_DIR_ENV: &str = "YFLOW_CONFIG_DIR";
+
+/// 用户配置目录中自动发现的配置文件名（按此顺序优先）
+pub const GLOBAL_CONFIG_FILENAMES: [&str; 4] = ["config.json", "config.toml", "config.yaml", "config.yml"];
+
+/// 获取 YFlow 的平台目录（无法确定主目录时返回 `None`）
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "yflow")
+}
+
+/// 获取用户主目录
+pub fn home_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+}
+
+/// 获取用户配置目录（`YFLOW_CONFIG_DIR` 优先）
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var_os(CONFIG_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => project_dirs().map(|dirs| dirs.config_dir().to_path_buf()),
+    }
+}
+
+/// 获取用户缓存目录
+pub fn cache_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.cache_dir().to_path_buf())
+}
+
+/// 获取用户数据目录
+pub fn data_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// 获取全局用户配置文件（不存在时返回 `None`）
+pub fn global_config_file() -> Option<PathBuf> {
+    config_dir().and_then(|dir| find_global_config_in(&dir))
+}
+
+/// 在目录中查找第一个存在的全局配置文件
+pub fn find_global_config_in(dir: &Path) -> Option<PathBuf> {
+    GLOBAL_CONFIG_FILENAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_global_config_in() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(find_global_config_in(temp_dir.path()).is_none());
+
+        std::fs::write(temp_dir.path().join("config.yaml"), "apiUrl: x\n").unwrap();
+        std::fs::write(temp_dir.path().join("config.json"), "{}").unwrap();
+        assert_eq!(
+            find_global_config_in(temp_dir.path()),
+            Some(temp_dir.path().join("config.json"))
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Placeholder extraction
+//!
+//! Collects the placeholders a message uses so translations can be checked
+//! against their source text. Recognised syntaxes:
+//!
+//! - ICU arguments: `{name}`, `{count, plural, ...}`
+//! - i18next interpolation: `{{name}}`
+//! - printf style: `%s`, `%d`, `%1$s`, `%@`
+//! - project-specific syntaxes from `placeholderPatterns` (regexes; the
+//!   whole match is the placeholder)
+//!
+//! [`check`] compares every language with the source language for
+//! `yflow check-placeholders` and `import --check`.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::OnceLock;
+
+use super::icu::parse_arguments;
+use super::Translations;
+
+fn double_brace() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.\-]+)[^}]*\}\}").expect("valid regex"))
+}
+
+fn printf() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"%(\d+\$)?[sdif@]").expect("valid regex"))
+}
+
+fn single_brace() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\s*([A-Za-z0-9_]+)\s*[,}]").expect("valid regex"))
+}
+
+/// 提取消息中的占位符
+///
+/// 返回规范化后的占位符集合：ICU / i18next 参数为 `{name}`，printf 为原样（如 `%1$s`）。
+pub fn extract(message: &str) -> BTreeSet<String> {
+    extract_with(message, &[])
+}
+
+/// 提取消息中的占位符，包括自定义正则匹配的部分（原样）
+pub fn extract_with(message: &str, patterns: &[Regex]) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+
+    for caps in double_brace().captures_iter(message) {
+        found.insert(format!("{{{}}}", &caps[1]));
+    }
+    let without_double = double_brace().replace_all(message, "");
+
+    match parse_arguments(&without_double) {
+        Ok(args) => {
+            for (name, _) in args {
+                found.insert(format!("{{{}}}", name));
+            }
+        }
+        // 语法不完整时退化为正则匹配
+        Err(_) => {
+            for caps in single_brace().captures_iter(&without_double) {
+                found.insert(format!("{{{}}}", &caps[1]));
+            }
+        }
+    }
+
+    for m in printf().find_iter(message) {
+        found.insert(m.as_str().to_string());
+    }
+    for pattern in patterns {
+        for m in pattern.find_iter(message) {
+            found.insert(m.as_str().to_string());
+        }
+    }
+
+    found
+}
+
+/// 比较译文与原文的占位符
+///
+/// # Returns
+///
+/// 不一致时返回描述（缺失与多余的占位符），一致时返回 `None`
+pub fn compare(source: &str, translation: &str) -> Option<String> {
+    compare_with(source, translation, &[])
+}
+
+/// 比较译文与原文的占位符（包括自定义正则）
+pub fn compare_with(source: &str, translation: &str, patterns: &[Regex]) -> Option<String> {
+    let (missing, unexpected) = differences(source, translation, patterns)?;
+    Some(describe(&missing, &unexpected))
+}
+
+/// 原文有而译文没有的占位符、译文多出的占位符（一致时为 None）
+fn differences(source: &str, translation: &str, patterns: &[Regex]) -> Option<(Vec<String>, Vec<String>)> {
+    let expected = extract_with(source, patterns);
+    let actual = extract_with(translation, patterns);
+    if expected == actual {
+        return None;
+    }
+    let missing = expected.difference(&actual).cloned().collect();
+    let unexpected = actual.difference(&expected).cloned().collect();
+    Some((missing, unexpected))
+}
+
+fn describe(missing: &[String], unexpected: &[String]) -> String {
+    let mut parts = Vec::new();
+    if !missing.is_empty() {
+        parts.push(format!("missing {}", missing.join(", ")));
+    }
+    if !unexpected.is_empty() {
+        parts.push(format!("unexpected {}", unexpected.join(", ")));
+    }
+    parts.join("; ")
+}
+
+/// 占位符与源语言不一致的翻译
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderIssue {
+    /// 语言代码
+    pub lang: String,
+    /// 键名
+    pub key: String,
+    /// 译文缺少的占位符
+    pub missing: Vec<String>,
+    /// 译文多出的占位符
+    pub unexpected: Vec<String>,
+}
+
+impl PlaceholderIssue {
+    /// 问题类型：`dropped`（只缺少）、`renamed`（缺少且多出）或 `added`（只多出）
+    pub fn kind(&self) -> &'static str {
+        match (self.missing.is_empty(), self.unexpected.is_empty()) {
+            (false, true) => "dropped",
+            (false, false) => "renamed",
+            _ => "added",
+        }
+    }
+}
+
+impl fmt::Display for PlaceholderIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}: {}",
+            self.lang,
+            self.key,
+            self.kind(),
+            describe(&self.missing, &self.unexpected)
+        )
+    }
+}
+
+/// 比较各语言与源语言的占位符
+///
+/// 源语言没有的键和空值不检查。
+///
+/// # Returns
+///
+/// 按语言、键排序的问题
+pub fn check(translations: &Translations, source: &str, patterns: &[Regex]) -> Vec<PlaceholderIssue> {
+    let Some(source_keys) = translations.get(source) else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+    for (lang, keys) in translations {
+        if lang == source {
+            continue;
+        }
+        for (key, value) in keys {
+            let Some(source_value) = source_keys.get(key) else {
+                continue;
+            };
+            if value.trim().is_empty() {
+                continue;
+            }
+            if let Some((missing, unexpected)) = differences(source_value, value, patterns) {
+                issues.push(PlaceholderIssue {
+                    lang: lang.clone(),
+                    key: key.clone(),
+                    missing,
+                    unexpected,
+                });
+            }
+        }
+    }
+    issues.sort_by(|a, b| a.lang.cmp(&b.lang).then_with(|| a.key.cmp(&b.key)));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_mixed_syntaxes() {
+        let found = extract("Hi {name}, {{count}} new, %1$s and %d");
+        let expected: BTreeSet<String> = ["{name}", "{count}", "%1$s", "%d"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_extract_icu_plural() {
+        let found = extract("{count, plural, one {# file} other {# files}} by {user}");
+        assert!(found.contains("{count}"));
+        assert!(found.contains("{user}"));
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    fn test_compare() {
+        assert!(compare("Hello {name}", "Hallo {name}").is_none());
+        let message = compare("Hello {name}", "Hallo {nom} %s").unwrap();
+        assert!(message.contains("missing {name}"));
+        assert!(message.contains("unexpected %s, {nom}"));
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let patterns = [Regex::new(r":[a-z]+").unwrap()];
+        assert!(compare("Hello :name", "Hallo").is_none());
+        assert_eq!(compare_with("Hello :name", "Hallo", &patterns).unwrap(), "missing :name");
+        assert!(compare_with("Hello :name", "Hallo :name", &patterns).is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        let translations: Translations = [
+            ("en", [("greeting", "Hi {name}"), ("count", "%d items"), ("only_en", "{x}")]),
+            ("de", [("greeting", "Hallo {nom}"), ("count", "Elemente"), ("only_en", "")]),
+            ("fr", [("greeting", "Salut {name}"), ("count", "%d éléments"), ("only_en", "{x} {y}")]),
+        ]
+        .into_iter()
+        .map(|(lang, keys)| {
+            let keys = keys.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            (lang.to_string(), keys)
+        })
+        .collect();
+
+        let issues: Vec<String> = check(&translations, "en", &[]).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            issues,
+            [
+                "de: count: dropped: missing %d",
+                "de: greeting: renamed: missing {name}; unexpected {nom}",
+                "fr: only_en: added: unexpected {y}",
+            ]
+        );
+        assert!(check(&translations, "ja", &[]).is_empty());
+    }
+}
@@ -0,0 +1,444 @@
+//! Translation quality scoring
+//!
+//! Combines per-key checks against the source language into a quality score
+//! per language, for `yflow qa-report`:
+//!
+//! - placeholder errors (see [`placeholders::compare`])
+//! - length violations: a translation longer than `max_length_ratio` times
+//!   its source text (sources shorter than [`MIN_LENGTH_CHECK`] characters
+//!   are skipped, where ratios are meaningless)
+//! - empty values
+//! - markup issues: HTML/JSX-style tags that differ from the source or are
+//!   not balanced
+//!
+//! A language's score is the percentage of its checked keys without any
+//! issue, so reports from different releases can be compared directly.
+
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+
+use super::{placeholders, Translations};
+
+/// 默认的最大长度比（译文 / 原文）
+pub const DEFAULT_MAX_LENGTH_RATIO: f64 = 2.0;
+
+/// 参与长度检查的原文最短字符数
+pub const MIN_LENGTH_CHECK: usize = 10;
+
+fn tag() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<(/?)([A-Za-z0-9][A-Za-z0-9\-]*)[^<>]*?(/?)>").expect("valid regex"))
+}
+
+/// 问题类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QaCategory {
+    /// 占位符与原文不一致
+    Placeholder,
+    /// 译文过长
+    Length,
+    /// 空值
+    Empty,
+    /// 标签与原文不一致或不配对
+    Markup,
+}
+
+impl QaCategory {
+    /// 所有类别（报告中的列顺序）
+    pub const ALL: [QaCategory; 4] = [Self::Placeholder, Self::Length, Self::Empty, Self::Markup];
+
+    /// 输出中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Placeholder => "placeholder",
+            Self::Length => "length",
+            Self::Empty => "empty",
+            Self::Markup => "markup",
+        }
+    }
+}
+
+/// 单个问题
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QaIssue {
+    /// 键名
+    pub key: String,
+    /// 类别
+    pub category: QaCategory,
+    /// 描述
+    pub message: String,
+}
+
+/// 单个语言的评分
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageScore {
+    /// 语言代码
+    pub lang: String,
+    /// 检查的键数（原文和译文都存在的键）
+    pub checked: usize,
+    /// 原文中有、该语言缺失的键数（不计入评分）
+    pub missing: usize,
+    /// 各类别的问题数
+    pub counts: BTreeMap<QaCategory, usize>,
+    /// 评分（0-100，无问题键的百分比）
+    pub score: f64,
+    /// 问题列表（按键排序）
+    pub issues: Vec<QaIssue>,
+}
+
+/// QA 报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QaReport {
+    /// 版本标签（如 `v2.3`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<String>,
+    /// 生成时间（RFC 3339）
+    pub generated_at: String,
+    /// 源语言
+    pub source: String,
+    /// 最大长度比
+    pub max_length_ratio: f64,
+    /// 各语言评分（按语言排序）
+    pub languages: Vec<LanguageScore>,
+}
+
+/// 评分选项
+#[derive(Debug, Clone)]
+pub struct QaOptions {
+    /// 源语言
+    pub source: String,
+    /// 最大长度比
+    pub max_length_ratio: f64,
+}
+
+/// 为除源语言外的所有语言评分
+pub fn score_translations(translations: &Translations, options: &QaOptions) -> Vec<LanguageScore> {
+    let empty = Default::default();
+    let source = translations.get(&options.source).unwrap_or(&empty);
+
+    let mut scores: Vec<LanguageScore> = translations
+        .iter()
+        .filter(|(lang, _)| **lang != options.source)
+        .map(|(lang, values)| {
+            let mut keys: Vec<&String> = source.keys().filter(|key| values.contains_key(*key)).collect();
+            keys.sort();
+
+            let mut issues = Vec::new();
+            let mut failing = 0;
+            for key in &keys {
+                let found = check_value(key, &source[*key], &values[*key], options);
+                if !found.is_empty() {
+                    failing += 1;
+                }
+                issues.extend(found);
+            }
+
+            let mut counts: BTreeMap<QaCategory, usize> =
+                QaCategory::ALL.iter().map(|category| (*category, 0)).collect();
+            for issue in &issues {
+                *counts.entry(issue.category).or_default() += 1;
+            }
+            let score = if keys.is_empty() {
+                100.0
+            } else {
+                ((keys.len() - failing) as f64 * 1000.0 / keys.len() as f64).round() / 10.0
+            };
+
+            LanguageScore {
+                lang: lang.clone(),
+                checked: keys.len(),
+                missing: source.len() - keys.len(),
+                counts,
+                score,
+                issues,
+            }
+        })
+        .collect();
+    scores.sort_by(|a, b| a.lang.cmp(&b.lang));
+    scores
+}
+
+/// 检查单个译文
+fn check_value(key: &str, source: &str, value: &str, options: &QaOptions) -> Vec<QaIssue> {
+    let issue = |category, message: String| QaIssue {
+        key: key.to_string(),
+        category,
+        message,
+    };
+
+    // 空值不再做其他检查
+    if value.trim().is_empty() {
+        return if source.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![issue(QaCategory::Empty, "empty translation".to_string())]
+        };
+    }
+
+    let mut issues = Vec::new();
+    if let Some(message) = placeholders::compare(source, value) {
+        issues.push(issue(QaCategory::Placeholder, message));
+    }
+
+    let (source_len, value_len) = (source.chars().count(), value.chars().count());
+    if source_len >= MIN_LENGTH_CHECK && value_len as f64 > source_len as f64 * options.max_length_ratio {
+        issues.push(issue(
+            QaCategory::Length,
+            format!(
+                "{} characters, {:.1}x the source ({})",
+                value_len,
+                value_len as f64 / source_len as f64,
+                source_len
+            ),
+        ));
+    }
+
+    if let Some(message) = compare_markup(source, value) {
+        issues.push(issue(QaCategory::Markup, message));
+    }
+
+    issues
+}
+
+/// 比较译文与原文的标签
+///
+/// # Returns
+///
+/// 不一致或不配对时返回描述，否则返回 `None`
+pub fn compare_markup(source: &str, value: &str) -> Option<String> {
+    let expected = tags(source);
+    let actual = tags(value);
+    if expected != actual {
+        let missing: Vec<&str> = expected
+            .iter()
+            .filter(|t| count(&expected, t) > count(&actual, t))
+            .map(|t| t.as_str())
+            .collect();
+        let extra: Vec<&str> = actual
+            .iter()
+            .filter(|t| count(&actual, t) > count(&expected, t))
+            .map(|t| t.as_str())
+            .collect();
+        let mut parts = Vec::new();
+        if !missing.is_empty() {
+            parts.push(format!("missing {}", dedup(missing).join(", ")));
+        }
+        if !extra.is_empty() {
+            parts.push(format!("unexpected {}", dedup(extra).join(", ")));
+        }
+        if !parts.is_empty() {
+            return Some(parts.join("; "));
+        }
+    }
+
+    unbalanced_tag(value).map(|tag| format!("unbalanced {}", tag))
+}
+
+/// 提取标签（排序，如 `<b>`、`</b>`、`<br/>`）
+fn tags(text: &str) -> Vec<String> {
+    let mut found: Vec<String> = tag()
+        .captures_iter(text)
+        .map(|caps| format!("<{}{}{}>", &caps[1], &caps[2], &caps[3]))
+        .collect();
+    found.sort();
+    found
+}
+
+fn count(tags: &[String], tag: &str) -> usize {
+    tags.iter().filter(|t| *t == tag).count()
+}
+
+fn dedup(mut values: Vec<&str>) -> Vec<&str> {
+    values.dedup();
+    values
+}
+
+/// 查找第一个不配对的标签
+fn unbalanced_tag(text: &str) -> Option<String> {
+    let mut open: Vec<String> = Vec::new();
+    for caps in tag().captures_iter(text) {
+        let (closing, name, self_closing) = (&caps[1] == "/", &caps[2], &caps[3] == "/");
+        if self_closing {
+            continue;
+        }
+        if !closing {
+            open.push(name.to_string());
+        } else if open.last().map(|last| last == name).unwrap_or(false) {
+            open.pop();
+        } else {
+            return Some(format!("</{}>", name));
+        }
+    }
+    open.pop().map(|name| format!("<{}>", name))
+}
+
+impl QaReport {
+    /// 渲染为独立的 HTML 页面
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let title = match &self.release {
+            Some(release) => format!("Translation QA report – {}", release),
+            None => "Translation QA report".to_string(),
+        };
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+             body {{ font-family: sans-serif; margin: 2em; }}\n\
+             table {{ border-collapse: collapse; margin-bottom: 2em; }}\n\
+             th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}\n\
+             .good {{ color: #1a7f37; }} .fair {{ color: #9a6700; }} .poor {{ color: #cf222e; }}\n\
+             </style>\n</head>\n<body>\n<h1>{title}</h1>\n<p>Source language: {source} · Generated: {generated} · Max length ratio: {ratio}</p>\n",
+            title = escape_html(&title),
+            source = escape_html(&self.source),
+            generated = escape_html(&self.generated_at),
+            ratio = self.max_length_ratio,
+        );
+
+        html.push_str("<table>\n<tr><th>Language</th><th>Score</th><th>Checked</th><th>Missing</th>");
+        for category in QaCategory::ALL {
+            let _ = write!(html, "<th>{}</th>", category.name());
+        }
+        html.push_str("</tr>\n");
+        for language in &self.languages {
+            let class = if language.score >= 95.0 {
+                "good"
+            } else if language.score >= 80.0 {
+                "fair"
+            } else {
+                "poor"
+            };
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td class=\"{}\">{:.1}</td><td>{}</td><td>{}</td>",
+                escape_html(&language.lang),
+                class,
+                language.score,
+                language.checked,
+                language.missing
+            );
+            for category in QaCategory::ALL {
+                let _ = write!(html, "<td>{}</td>", language.counts.get(&category).copied().unwrap_or(0));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        for language in self.languages.iter().filter(|l| !l.issues.is_empty()) {
+            let _ = write!(
+                html,
+                "<h2>{}</h2>\n<table>\n<tr><th>Key</th><th>Category</th><th>Issue</th></tr>\n",
+                escape_html(&language.lang)
+            );
+            for issue in &language.issues {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&issue.key),
+                    issue.category.name(),
+                    escape_html(&issue.message)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// 转义 HTML 特殊字符
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn translations() -> Translations {
+        let lang = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        HashMap::from([
+            (
+                "en".to_string(),
+                lang(&[
+                    ("greeting", "Hello {name}"),
+                    ("terms", "Accept the <b>terms</b>"),
+                    ("save", "Save your changes"),
+                    ("cancel", "Cancel"),
+                ]),
+            ),
+            (
+                "de".to_string(),
+                lang(&[
+                    ("greeting", "Hallo"),
+                    ("terms", "Akzeptiere die <b>AGB"),
+                    ("save", "Speichern Sie Ihre Änderungen bitte sofort und ganz sicher"),
+                ]),
+            ),
+            ("fr".to_string(), lang(&[("greeting", "Bonjour {name}"), ("cancel", "")])),
+        ])
+    }
+
+    #[test]
+    fn test_score_translations() {
+        let options = QaOptions {
+            source: "en".to_string(),
+            max_length_ratio: DEFAULT_MAX_LENGTH_RATIO,
+        };
+        let scores = score_translations(&translations(), &options);
+        assert_eq!(scores.len(), 2);
+
+        let de = &scores[0];
+        assert_eq!(de.lang, "de");
+        assert_eq!((de.checked, de.missing), (3, 1));
+        assert_eq!(de.counts[&QaCategory::Placeholder], 1);
+        assert_eq!(de.counts[&QaCategory::Length], 1);
+        assert_eq!(de.counts[&QaCategory::Markup], 1);
+        assert_eq!(de.score, 0.0);
+
+        let fr = &scores[1];
+        assert_eq!(fr.counts[&QaCategory::Empty], 1);
+        assert_eq!(fr.score, 50.0);
+    }
+
+    #[test]
+    fn test_compare_markup() {
+        assert_eq!(compare_markup("<b>Hi</b>", "<b>Hallo</b>"), None);
+        assert_eq!(compare_markup("Line<br/>two", "Zeile<br/>zwei"), None);
+        assert_eq!(
+            compare_markup("<b>Hi</b>", "<i>Hallo</i>").unwrap(),
+            "missing </b>, <b>; unexpected </i>, <i>"
+        );
+        assert_eq!(compare_markup("<b><i>x</i></b>", "<b><i>x</b></i>").unwrap(), "unbalanced </b>");
+        assert_eq!(compare_markup("a < b", "a < b"), None);
+    }
+
+    #[test]
+    fn test_to_html() {
+        let options = QaOptions {
+            source: "en".to_string(),
+            max_length_ratio: DEFAULT_MAX_LENGTH_RATIO,
+        };
+        let report = QaReport {
+            release: Some("v1.2".to_string()),
+            generated_at: "2024-01-01T00:00:00Z".to_string(),
+            source: "en".to_string(),
+            max_length_ratio: options.max_length_ratio,
+            languages: score_translations(&translations(), &options),
+        };
+        let html = report.to_html();
+        assert!(html.contains("<title>Translation QA report – v1.2</title>"));
+        assert!(html.contains("<td>markup</td><td>missing &lt;/b&gt;</td>"));
+        assert!(!html.contains("<b>AGB"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Secret redaction
+//!
+//! `load_config` registers the resolved API key (and the HMAC secret) here,
+//! and the CLI passes everything it prints through [`redact`] — log lines,
+//! error chains and panic messages — so a key that ends up in an error
+//! string (a URL, a server echo) is shown as `***`. `--show-secrets` turns
+//! redaction off for debugging.
+
+use parking_lot::RwLock;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 替换密钥的文本
+pub const MASK: &str = "***";
+
+/// 短于此长度的值不登记（避免把常见短词当作密钥替换）
+const MIN_SECRET_LEN: usize = 4;
+
+/// 已登记的密钥
+static SECRETS: RwLock<Vec<String>> = parking_lot::const_rwlock(Vec::new());
+
+/// 是否显示密钥（`--show-secrets`）
+static SHOW_SECRETS: AtomicBool = AtomicBool::new(false);
+
+/// 登记需要隐藏的密钥
+pub fn register(secret: &str) {
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    let mut secrets = SECRETS.write();
+    if !secrets.iter().any(|known| known == secret) {
+        secrets.push(secret.to_string());
+        // 先替换较长的密钥，避免一个密钥包含另一个时只替换一部分
+        secrets.sort_by_key(|known| std::cmp::Reverse(known.len()));
+    }
+}
+
+/// 设置是否显示密钥（`--show-secrets`）
+pub fn set_show_secrets(show: bool) {
+    SHOW_SECRETS.store(show, Ordering::Relaxed);
+}
+
+/// 将文本中的已登记密钥替换为 `***`
+pub fn redact(text: &str) -> Cow<'_, str> {
+    if SHOW_SECRETS.load(Ordering::Relaxed) {
+        return Cow::Borrowed(text);
+    }
+    let secrets = SECRETS.read();
+    let mut result = Cow::Borrowed(text);
+    for secret in secrets.iter() {
+        if result.contains(secret.as_str()) {
+            result = Cow::Owned(result.replace(secret.as_str(), MASK));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        register("sk-live-123456");
+        register("sk-live-123456-extended");
+        register("abc");
+
+        assert_eq!(
+            redact("GET https://api.example.com/?key=sk-live-123456 failed"),
+            "GET https://api.example.com/?key=*** failed"
+        );
+        assert_eq!(redact("token sk-live-123456-extended"), "token ***");
+        // 过短的值不登记
+        assert_eq!(redact("abc"), "abc");
+        assert!(matches!(redact("nothing secret"), Cow::Borrowed(_)));
+    }
+}
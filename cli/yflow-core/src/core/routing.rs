@@ -0,0 +1,316 @@
+//! Backend routing module
+//!
+//! Routes translation keys to different backend projects based on key
+//! prefixes and/or language codes, so a single repository can feed
+//! several backend projects in one import/sync run.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "routes": [
+//!     { "keyPrefix": "emails.", "projectId": 7 },
+//!     { "language": "ja_JP", "projectId": 9, "apiUrl": "https://jp.example.com/api" }
+//!   ]
+//! }
+//! ```
+//!
+//! Routes are evaluated in order and the first match wins. Keys that
+//! match no route go to the default project from the top-level config.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::key_path::{KeyPath, KeyPrefix};
+use super::{I18nConfig, Translations};
+
+/// 路由规则
+///
+/// `keyPrefix` 和 `language` 均为可选，未设置的条件视为匹配。
+/// `apiUrl` / `apiKey` 未设置时沿用顶层配置。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct Route {
+    /// 键名前缀（如 `"emails."`）
+    #[serde(rename = "keyPrefix", default, skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<String>,
+    /// 本地语言代码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// 目标项目 ID
+    #[serde(rename = "projectId")]
+    pub project_id: u64,
+    /// 目标 API 地址（可选）
+    #[serde(rename = "apiUrl", default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// 目标 API 密钥（可选）
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+impl Route {
+    /// 检查给定语言和键是否匹配该规则，键和前缀按 `separator` 解析
+    pub fn matches(&self, lang: &str, key: &str, separator: &str) -> bool {
+        let prefix = self.key_prefix.as_deref().map(|prefix| KeyPrefix::parse(prefix, separator));
+        self.matches_path(prefix.as_ref(), lang, &KeyPath::parse(key, separator))
+    }
+
+    /// 用已解析的前缀检查语言和键
+    fn matches_path(&self, prefix: Option<&KeyPrefix>, lang: &str, key: &KeyPath) -> bool {
+        let prefix_ok = prefix.map(|prefix| prefix.matches(key)).unwrap_or(true);
+        let lang_ok = self
+            .language
+            .as_deref()
+            .map(|l| l == lang)
+            .unwrap_or(true);
+        prefix_ok && lang_ok
+    }
+}
+
+/// 路由目标：一个后端项目的连接信息
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RouteTarget {
+    /// API 地址
+    pub api_url: String,
+    /// API 密钥
+    pub api_key: String,
+    /// 项目 ID
+    pub project_id: u64,
+}
+
+/// 路由器
+///
+/// 根据配置中的 `routes` 将翻译拆分到不同的后端项目。
+#[derive(Debug, Clone)]
+pub struct Router {
+    /// 规则、解析后的键名前缀和目标
+    routes: Vec<(Route, Option<KeyPrefix>, RouteTarget)>,
+    default_target: RouteTarget,
+    /// 键分隔符
+    separator: String,
+}
+
+impl Router {
+    /// 从配置创建路由器
+    pub fn from_config(config: &I18nConfig) -> Self {
+        let default_target = RouteTarget {
+            api_url: config.api_url.clone(),
+            api_key: config.api_key.clone(),
+            project_id: config.project_id,
+        };
+        let separator = config.separator().to_string();
+
+        let routes = config
+            .routes
+            .iter()
+            .map(|route| {
+                let target = RouteTarget {
+                    api_url: route
+                        .api_url
+                        .clone()
+                        .unwrap_or_else(|| config.api_url.clone()),
+                    api_key: route
+                        .api_key
+                        .clone()
+                        .unwrap_or_else(|| config.api_key.clone()),
+                    project_id: route.project_id,
+                };
+                let prefix = route.key_prefix.as_deref().map(|prefix| KeyPrefix::parse(prefix, &separator));
+                (route.clone(), prefix, target)
+            })
+            .collect();
+
+        Self {
+            routes,
+            default_target,
+            separator,
+        }
+    }
+
+    /// 是否配置了路由规则
+    pub fn has_routes(&self) -> bool {
+        !self.routes.is_empty()
+    }
+
+    /// 默认目标（顶层配置的项目）
+    pub fn default_target(&self) -> &RouteTarget {
+        &self.default_target
+    }
+
+    /// 获取给定语言和键对应的目标
+    pub fn target_for(&self, lang: &str, key: &str) -> &RouteTarget {
+        let key = KeyPath::parse(key, &self.separator);
+        self.routes
+            .iter()
+            .find(|(route, prefix, _)| route.matches_path(prefix.as_ref(), lang, &key))
+            .map(|(_, _, target)| target)
+            .unwrap_or(&self.default_target)
+    }
+
+    /// 获取所有不重复的目标（默认目标排在首位）
+    pub fn targets(&self) -> Vec<&RouteTarget> {
+        let mut targets = vec![&self.default_target];
+        for (_, _, target) in &self.routes {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        targets
+    }
+
+    /// 按目标拆分翻译
+    ///
+    /// 返回的顺序与 [`Router::targets`] 一致，不包含空的分组。
+    pub fn partition(&self, translations: Translations) -> Vec<(RouteTarget, Translations)> {
+        let mut groups: HashMap<RouteTarget, Translations> = HashMap::new();
+
+        for (lang, lang_translations) in translations {
+            for (key, value) in lang_translations {
+                let target = self.target_for(&lang, &key).clone();
+                groups
+                    .entry(target)
+                    .or_default()
+                    .entry(lang.clone())
+                    .or_default()
+                    .insert(key, value);
+            }
+        }
+
+        self.targets()
+            .into_iter()
+            .filter_map(|target| groups.remove(target).map(|t| (target.clone(), t)))
+            .collect()
+    }
+
+    /// 只保留路由到指定目标的键
+    ///
+    /// 同步时使用：从某个项目获取的翻译中，只有按规则属于该项目的键才会被采用。
+    pub fn filter_for(&self, target: &RouteTarget, translations: Translations) -> Translations {
+        translations
+            .into_iter()
+            .map(|(lang, lang_translations)| {
+                let kept = lang_translations
+                    .into_iter()
+                    .filter(|(key, _)| self.target_for(&lang, key) == target)
+                    .collect::<HashMap<_, _>>();
+                (lang, kept)
+            })
+            .filter(|(_, t)| !t.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_config(routes: Vec<Route>) -> I18nConfig {
+        I18nConfig {
+            messages_dir: PathBuf::from("./messages"),
+            project_id: 1,
+            api_url: "http://localhost:8080/api".to_string(),
+            api_key: "default-key".to_string(),
+            routes,
+            ..Default::default()
+        }
+    }
+
+    fn prefix_route(prefix: &str, project_id: u64) -> Route {
+        Route {
+            key_prefix: Some(prefix.to_string()),
+            language: None,
+            project_id,
+            api_url: None,
+            api_key: None,
+        }
+    }
+
+    #[test]
+    fn test_route_matches() {
+        let route = Route {
+            key_prefix: Some("emails.".to_string()),
+            language: Some("de".to_string()),
+            project_id: 7,
+            api_url: None,
+            api_key: None,
+        };
+        assert!(route.matches("de", "emails.welcome", "."));
+        assert!(!route.matches("en", "emails.welcome", "."));
+        assert!(!route.matches("de", "common.title", "."));
+    }
+
+    #[test]
+    fn test_router_without_routes() {
+        let router = Router::from_config(&test_config(vec![]));
+        assert!(!router.has_routes());
+        assert_eq!(router.targets().len(), 1);
+        assert_eq!(router.target_for("en", "any.key").project_id, 1);
+    }
+
+    #[test]
+    fn test_router_first_match_wins() {
+        let router = Router::from_config(&test_config(vec![
+            prefix_route("emails.", 7),
+            prefix_route("emails.admin.", 8),
+        ]));
+        assert_eq!(router.target_for("en", "emails.admin.title").project_id, 7);
+        assert_eq!(router.target_for("en", "common.title").project_id, 1);
+    }
+
+    #[test]
+    fn test_router_prefix_matches_segments() {
+        let router = Router::from_config(&test_config(vec![prefix_route("legal.", 7)]));
+        assert_eq!(router.target_for("en", "legal.terms").project_id, 7);
+        assert_eq!(router.target_for("en", r"legal\.notice").project_id, 1);
+        assert_eq!(router.target_for("en", "legalese").project_id, 1);
+    }
+
+    #[test]
+    fn test_router_inherits_connection_settings() {
+        let router = Router::from_config(&test_config(vec![prefix_route("emails.", 7)]));
+        let target = router.target_for("en", "emails.welcome");
+        assert_eq!(target.api_url, "http://localhost:8080/api");
+        assert_eq!(target.api_key, "default-key");
+    }
+
+    #[test]
+    fn test_router_partition() {
+        let router = Router::from_config(&test_config(vec![prefix_route("emails.", 7)]));
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("emails.welcome".to_string(), "Welcome".to_string()),
+                ("common.title".to_string(), "Title".to_string()),
+            ]),
+        )]);
+
+        let parts = router.partition(translations);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].0.project_id, 1);
+        assert!(parts[0].1["en"].contains_key("common.title"));
+        assert_eq!(parts[1].0.project_id, 7);
+        assert!(parts[1].1["en"].contains_key("emails.welcome"));
+    }
+
+    #[test]
+    fn test_router_filter_for() {
+        let router = Router::from_config(&test_config(vec![prefix_route("emails.", 7)]));
+        let fetched: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("emails.welcome".to_string(), "Welcome".to_string()),
+                ("common.title".to_string(), "Title".to_string()),
+            ]),
+        )]);
+
+        let default_only = router.filter_for(router.default_target(), fetched.clone());
+        assert_eq!(default_only["en"].len(), 1);
+        assert!(default_only["en"].contains_key("common.title"));
+
+        let emails_target = router.target_for("en", "emails.welcome").clone();
+        let emails_only = router.filter_for(&emails_target, fetched);
+        assert_eq!(emails_only["en"].len(), 1);
+        assert!(emails_only["en"].contains_key("emails.welcome"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Per-language run summary
+//!
+//! `import` and `sync` end with one row per language: how many keys were
+//! added, updated, skipped (already identical) and failed, plus how long the
+//! run took. The CLI renders it as a table; `--summary-json` writes the same
+//! data as JSON.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use super::LanguageCounts;
+
+/// 单个语言的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LanguageSummary {
+    /// 新增的键数
+    pub added: usize,
+    /// 更新的键数
+    pub updated: usize,
+    /// 未变化而跳过的键数
+    pub skipped: usize,
+    /// 失败的键数
+    pub failed: usize,
+}
+
+impl From<&LanguageCounts> for LanguageSummary {
+    fn from(counts: &LanguageCounts) -> Self {
+        Self {
+            added: counts.added,
+            updated: counts.updated,
+            skipped: counts.unchanged,
+            failed: counts.failed,
+        }
+    }
+}
+
+/// 一次运行的汇总
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSummary {
+    /// 各语言的结果
+    pub languages: BTreeMap<String, LanguageSummary>,
+    /// 合计
+    pub total: LanguageSummary,
+    /// 耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+impl RunSummary {
+    /// 由各语言的结果创建
+    pub fn new(languages: &BTreeMap<String, LanguageCounts>, duration: Duration) -> Self {
+        let languages: BTreeMap<String, LanguageSummary> = languages
+            .iter()
+            .map(|(lang, counts)| (lang.clone(), LanguageSummary::from(counts)))
+            .collect();
+        let mut total = LanguageSummary::default();
+        for counts in languages.values() {
+            total.added += counts.added;
+            total.updated += counts.updated;
+            total.skipped += counts.skipped;
+            total.failed += counts.failed;
+        }
+        Self {
+            languages,
+            total,
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+
+    /// 耗时
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// 保存为 JSON
+    ///
+    /// # Errors
+    ///
+    /// 文件无法写入时返回错误
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write run summary {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn summary() -> RunSummary {
+        let languages = BTreeMap::from([
+            (
+                "de".to_string(),
+                LanguageCounts {
+                    added: 3,
+                    updated: 1,
+                    unchanged: 0,
+                    failed: 0,
+                },
+            ),
+            (
+                "en".to_string(),
+                LanguageCounts {
+                    added: 5,
+                    updated: 0,
+                    unchanged: 4,
+                    failed: 2,
+                },
+            ),
+        ]);
+        RunSummary::new(&languages, Duration::from_millis(1500))
+    }
+
+    #[test]
+    fn test_totals() {
+        let summary = summary();
+        assert_eq!(
+            summary.total,
+            LanguageSummary {
+                added: 8,
+                updated: 1,
+                skipped: 4,
+                failed: 2,
+            }
+        );
+        assert_eq!(summary.languages["en"].skipped, 4);
+        assert_eq!(summary.duration(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_save_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.json");
+        summary().save(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["languages"]["de"]["added"], 3);
+        assert_eq!(json["languages"]["en"]["skipped"], 4);
+        assert_eq!(json["total"]["failed"], 2);
+        assert_eq!(json["durationMs"], 1500);
+    }
+}
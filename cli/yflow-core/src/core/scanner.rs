@@ -0,0 +1,2501 @@
+//! File scanning module
+//!
+//! Scans the messages directory and collects all translation files.
+//! Supports parallel file processing for improved performance.
+//!
+//! # Directory Structure
+//!
+//! The expected directory structure is:
+//! ```text
+//! messages/
+//!   ├── en/
+//!   │   ├── common.json
+//!   │   └── errors.json
+//!   ├── zh_CN/
+//!   │   ├── common.json
+//!   │   └── errors.json
+//!   └── ...
+//! ```
+//!
+//! Files may be JSON (`.json`), YAML (`.yaml` / `.yml`) or gettext catalogs
+//! (`.po`, plus read-only `.pot` templates); the format is chosen per file by
+//! the [format registry](super::formats::registry), both when reading and when
+//! writing back, so registered plugin formats are scanned and written too.
+//!
+//! Traversal honours `.gitignore` / `.ignore` files (via the `ignore` crate)
+//! and never descends into hidden directories or build output such as
+//! `node_modules/` and `target/`.
+//!
+//! Individual languages can live outside the messages directory: with
+//! `"languageDirs": {"en": "src/locales/en"}` English is read from (and
+//! written to) `src/locales/en` while every other language stays under
+//! `messages/<lang>/`. File paths in a [`ScanResult`] are always logical
+//! `<lang>/<relative path>` paths; [`resolve_file`] maps them to disk.
+//!
+//! With `"layout": "flat-files"` each language is a single file directly in
+//! the messages directory (`messages/en.json`, `messages/zh_CN.yaml`) and the
+//! language is taken from the file name. File paths in a [`ScanResult`] are
+//! then just the file names.
+//!
+//! With `"format": "android"` or `"format": "ios"` the language directories
+//! are named the platform's way (`values-de/`, `de.lproj/`, see
+//! [`formats::mobile`]); logical paths still start with the language code.
+//!
+//! With `"dialect": "i18next"` new plural and context forms of a key
+//! (`item_few`) are written next to their siblings (see [`super::i18next`]).
+
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::warn;
+
+use super::aliases;
+use super::formats::po::PoCatalog;
+use super::formats::TranslationFormat;
+use super::flatten::{flatten_object_with, merge_flat_into, remove_flat_from, unflatten_object_with};
+use super::namespaces::{file_namespace, route_keys, split_new_language, NamespaceFile};
+use super::sync_plan::{FileChange, LanguagePlan, SyncPlan};
+use super::{KeyOrigins, MessagesDir, ProgressEvent, QuarantinedFile, ScanResult, Translations};
+
+pub use super::ProgressCallback;
+
+/// Translation file format, selected per file by extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// `.json`
+    Json,
+    /// `.yaml` / `.yml`
+    Yaml,
+    /// gettext catalog (`.po`)
+    Po,
+    /// gettext template (`.pot`), read-only
+    Pot,
+}
+
+impl FileFormat {
+    /// Detects the format from a file extension, `None` for other files
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Some(Self::Json),
+            Some("yaml") | Some("yml") => Some(Self::Yaml),
+            Some("po") => Some(Self::Po),
+            Some("pot") => Some(Self::Pot),
+            _ => None,
+        }
+    }
+
+    /// Human-readable name used in messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Po => "PO",
+            Self::Pot => "POT",
+        }
+    }
+
+    /// Parses JSON/YAML content into a JSON value
+    ///
+    /// An empty YAML document is treated as an empty object.
+    fn parse_value(&self, content: &str) -> Result<Value> {
+        match self {
+            Self::Yaml => {
+                let value: Value = serde_yaml::from_str(content)?;
+                Ok(if value.is_null() { Value::Object(Default::default()) } else { value })
+            }
+            _ => Ok(serde_json::from_str(content)?),
+        }
+    }
+
+    /// Reads file content as flat translations, joining nested keys with `separator`
+    ///
+    /// POT templates yield their source strings.
+    pub fn read_flat(&self, content: &str, separator: &str) -> Result<HashMap<String, String>> {
+        match self {
+            Self::Json | Self::Yaml => Ok(flatten_object_with(&self.parse_value(content)?, "", separator)),
+            Self::Po => Ok(PoCatalog::parse(content)?.to_translations(false)),
+            Self::Pot => Ok(PoCatalog::parse(content)?.to_translations(true)),
+        }
+    }
+
+    /// Merges translations into existing file content
+    ///
+    /// Returns the new content, or `None` for read-only formats (POT
+    /// templates are produced by extraction tools, not by sync).
+    pub fn merge(
+        &self,
+        content: &str,
+        translations: &HashMap<String, String>,
+        force: bool,
+        separator: &str,
+    ) -> Result<Option<String>> {
+        match self {
+            Self::Json => {
+                let merged =
+                    merge_translations_with_structure(&self.parse_value(content)?, translations, force, separator);
+                Ok(Some(serde_json::to_string_pretty(&merged)?))
+            }
+            Self::Yaml => {
+                let merged =
+                    merge_translations_with_structure(&self.parse_value(content)?, translations, force, separator);
+                Ok(Some(serde_yaml::to_string(&merged)?))
+            }
+            Self::Po => {
+                let mut catalog = PoCatalog::parse(content)?;
+                catalog.merge(translations);
+                Ok(Some(catalog.serialize()))
+            }
+            Self::Pot => Ok(None),
+        }
+    }
+
+    /// Removes keys from existing file content
+    ///
+    /// Only the named string leaves go away (with objects left empty by
+    /// that); everything else is kept. PO catalogs drop the whole entry.
+    /// Returns `None` for read-only formats.
+    pub fn remove(&self, content: &str, keys: &[String], separator: &str) -> Result<Option<String>> {
+        match self {
+            Self::Json | Self::Yaml => {
+                let mut value = self.parse_value(content)?;
+                remove_flat_from(&mut value, keys, separator);
+                Ok(Some(match self {
+                    Self::Yaml => serde_yaml::to_string(&value)?,
+                    _ => serde_json::to_string_pretty(&value)?,
+                }))
+            }
+            Self::Po => {
+                let mut catalog = PoCatalog::parse(content)?;
+                catalog.remove(&keys.iter().cloned().collect());
+                Ok(Some(catalog.serialize()))
+            }
+            Self::Pot => Ok(None),
+        }
+    }
+
+    /// Derives a new language's file from a source language file
+    ///
+    /// Keeps the structure; values are blanked unless `fill_source` is set.
+    /// PO headers get the new `Language`. Returns `None` for POT templates,
+    /// which are shared between languages rather than copied.
+    pub fn scaffold(&self, content: &str, lang: &str, fill_source: bool) -> Result<Option<String>> {
+        match self {
+            Self::Json | Self::Yaml => {
+                let mut value = self.parse_value(content)?;
+                if !fill_source {
+                    blank_strings(&mut value);
+                }
+                Ok(Some(match self {
+                    Self::Yaml => serde_yaml::to_string(&value)?,
+                    _ => serde_json::to_string_pretty(&value)?,
+                }))
+            }
+            Self::Po => {
+                let mut catalog = PoCatalog::parse(content)?;
+                if !fill_source {
+                    for entry in &mut catalog.entries {
+                        entry.msgstr.iter_mut().for_each(String::clear);
+                    }
+                }
+                catalog.set_header_field("Language", lang);
+                Ok(Some(catalog.serialize()))
+            }
+            Self::Pot => Ok(None),
+        }
+    }
+}
+
+/// Replaces every string leaf with an empty string
+fn blank_strings(value: &mut Value) {
+    match value {
+        Value::String(s) => s.clear(),
+        Value::Array(items) => items.iter_mut().for_each(blank_strings),
+        Value::Object(map) => map.values_mut().for_each(blank_strings),
+        _ => {}
+    }
+}
+
+/// Creates a new language directory mirroring a source language's files
+///
+/// Every translation file under `<messages_dir>/<source>` is recreated under
+/// `<messages_dir>/<target>` with the same relative path and structure.
+///
+/// # Arguments
+///
+/// * `messages_dir` - Root messages directory path
+/// * `source` - Source language directory name
+/// * `target` - New language directory name
+/// * `fill_source` - Copy source values instead of leaving them empty
+///
+/// # Errors
+///
+/// Returns an error if the source directory is missing, the target
+/// directory already exists, or a file cannot be parsed or written
+///
+/// # Returns
+///
+/// Full paths of the created files
+pub fn scaffold_language(
+    messages_dir: &MessagesDir,
+    source: &str,
+    target: &str,
+    fill_source: bool,
+) -> Result<Vec<PathBuf>> {
+    if messages_dir.layout == Layout::FlatFiles {
+        return scaffold_flat_language(messages_dir, source, target, fill_source);
+    }
+
+    let source_dir = language_dir(messages_dir, source);
+    let target_dir = language_dir(messages_dir, target);
+    if !source_dir.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Source language directory not found: {}",
+            source_dir.display()
+        ));
+    }
+    if target_dir.exists() {
+        return Err(anyhow::anyhow!(
+            "Language directory already exists: {}",
+            target_dir.display()
+        ));
+    }
+
+    let mut created = Vec::new();
+    for (file, format) in collect_translation_files(messages_dir, &source_dir)? {
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let Some(new_content) = format
+            .scaffold(&content, target, fill_source)
+            .with_context(|| format!("Failed to parse {} {}", format.name(), file.display()))?
+        else {
+            continue;
+        };
+
+        let rel_path = file.strip_prefix(&source_dir).unwrap_or(&file);
+        let output_path = target_dir.join(rel_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(&output_path, new_content)
+            .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+        created.push(output_path);
+    }
+
+    Ok(created)
+}
+
+/// Creates `<target>.<ext>` next to each `<source>.<ext>` file (flat layout)
+fn scaffold_flat_language(
+    messages_dir: &MessagesDir,
+    source: &str,
+    target: &str,
+    fill_source: bool,
+) -> Result<Vec<PathBuf>> {
+    let sources = language_files(messages_dir, source)?;
+    if sources.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Source language file not found: {}",
+            messages_dir.join(format!("{}.*", source)).display()
+        ));
+    }
+    if let Some(existing) = language_files(messages_dir, target)?.first() {
+        return Err(anyhow::anyhow!(
+            "Language file already exists: {}",
+            messages_dir.join(existing).display()
+        ));
+    }
+
+    let mut created = Vec::new();
+    for file in sources {
+        let path = messages_dir.join(&file);
+        let Some(format) = messages_dir.detect(&path) else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let Some(new_content) = format
+            .scaffold(&content, target, fill_source)
+            .with_context(|| format!("Failed to parse {} {}", format.name(), path.display()))?
+        else {
+            continue;
+        };
+
+        let extension = path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        let output_path = messages_dir.join(format!("{}.{}", target, extension));
+        std::fs::write(&output_path, new_content)
+            .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+        created.push(output_path);
+    }
+
+    Ok(created)
+}
+
+/// Reads a single translation file of `messages_dir` as flat translations
+///
+/// # Errors
+///
+/// Returns an error if the extension is not a supported format or the
+/// file cannot be read or parsed
+pub fn read_translation_file(messages_dir: &MessagesDir, path: &Path) -> Result<HashMap<String, String>> {
+    let format = messages_dir
+        .detect(path)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported translation file: {}", path.display()))?;
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file {}", path.display()))?;
+    format
+        .read_flat(&content, &messages_dir.separator)
+        .with_context(|| format!("Failed to parse {} {}", format.name(), path.display()))
+}
+
+/// Updates values in a single translation file
+///
+/// Keeps the file's format and nesting; keys that do not exist yet are added.
+///
+/// # Errors
+///
+/// Returns an error for unsupported or read-only (POT) files, or if the file
+/// cannot be read, parsed or written
+pub fn update_translation_file(messages_dir: &MessagesDir, path: &Path, values: &HashMap<String, String>) -> Result<()> {
+    let format = messages_dir
+        .detect(path)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported translation file: {}", path.display()))?;
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file {}", path.display()))?;
+    let new_content = format
+        .merge(&content, values, true, &messages_dir.separator)
+        .with_context(|| format!("Failed to parse {} {}", format.name(), path.display()))?
+        .ok_or_else(|| anyhow::anyhow!("{} is a read-only template", path.display()))?;
+    std::fs::write(path, new_content)
+        .with_context(|| format!("Failed to write file {}", path.display()))
+}
+
+/// A translation file on disk with the format that reads it
+type TranslationFile = (PathBuf, Arc<dyn TranslationFormat>);
+
+/// Scan of one language: (translations, file paths, key origins, key count, quarantined files)
+type LanguageScan = (Translations, Vec<PathBuf>, KeyOrigins, usize, Vec<QuarantinedFile>);
+
+/// Layout of the messages directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// One directory per language (`messages/en/common.json`)
+    #[default]
+    NestedDirs,
+    /// One file per language (`messages/en.json`)
+    FlatFiles,
+}
+
+impl Layout {
+    /// Whether this is the default one-directory-per-language layout
+    pub fn is_nested_dirs(&self) -> bool {
+        *self == Self::NestedDirs
+    }
+}
+
+/// Returns the language of a flat-layout file (its file stem)
+///
+/// Templates and unsupported files belong to no language.
+fn flat_language(messages_dir: &MessagesDir, path: &Path) -> Option<String> {
+    if !messages_dir.is_translation_file(path) {
+        return None;
+    }
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string())
+}
+
+/// Lists the per-language files of a flat-layout messages directory
+///
+/// `root` is the directory to list (the messages directory, possibly
+/// canonicalized).
+fn flat_files(messages_dir: &MessagesDir, root: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in walker(root).max_depth(Some(1)).build() {
+        let entry = entry.with_context(|| format!("Failed to read directory: {}", root.display()))?;
+        if entry.depth() == 1 && entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+            if let Some(lang) = flat_language(messages_dir, entry.path()) {
+                files.push((lang, entry.into_path()));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the language of a directory directly below `messages_dir`
+///
+/// This is the directory name, or the language of a platform-named
+/// directory with a mobile format (`None` for other directories).
+fn dir_language(messages_dir: &MessagesDir, dir_name: &str) -> Option<String> {
+    match messages_dir.format {
+        Some(format) => format.dir_language(dir_name),
+        None => Some(dir_name.to_string()),
+    }
+}
+
+/// Returns the directory holding the files of `lang`
+///
+/// This is the `languageDirs` entry for the language if there is one,
+/// otherwise `<messages_dir>/<lang>` (or the platform-named directory with
+/// a mobile format). In the flat layout every language lives directly in
+/// `messages_dir`.
+pub fn language_dir(messages_dir: &MessagesDir, lang: &str) -> PathBuf {
+    if messages_dir.layout == Layout::FlatFiles {
+        return messages_dir.to_path_buf();
+    }
+    messages_dir
+        .language_dirs
+        .get(lang)
+        .cloned()
+        .unwrap_or_else(|| match messages_dir.format {
+            Some(format) => messages_dir.join(format.dir_name(lang)),
+            None => messages_dir.join(lang),
+        })
+}
+
+/// Maps a logical `<lang>/<relative path>` file path to its location on disk
+pub fn resolve_file(messages_dir: &MessagesDir, file: &Path) -> PathBuf {
+    if messages_dir.layout == Layout::FlatFiles {
+        return messages_dir.join(file);
+    }
+    let mut components = file.components();
+    match components.next() {
+        Some(Component::Normal(lang)) => language_dir(messages_dir, &lang.to_string_lossy()).join(components.as_path()),
+        _ => messages_dir.join(file),
+    }
+}
+
+/// Returns the language a file on disk belongs to
+///
+/// Checks the `languageDirs` overrides first, then the first directory
+/// below `messages_dir` (the file name in the flat layout).
+pub fn language_of(messages_dir: &MessagesDir, path: &Path) -> Option<String> {
+    if messages_dir.layout == Layout::FlatFiles {
+        return file_language(messages_dir, path.strip_prefix(messages_dir).ok()?);
+    }
+    if let Some((lang, _)) = messages_dir.language_dirs.iter().find(|(_, dir)| path.starts_with(dir)) {
+        return Some(lang.clone());
+    }
+    match path.strip_prefix(messages_dir).ok()?.components().next() {
+        Some(Component::Normal(dir)) => dir_language(messages_dir, &dir.to_string_lossy()),
+        _ => None,
+    }
+}
+
+/// Returns the language of a logical file path from a [`ScanResult`]
+pub fn file_language(messages_dir: &MessagesDir, file: &Path) -> Option<String> {
+    let mut components = file.components();
+    match (messages_dir.layout, components.next()) {
+        (Layout::FlatFiles, Some(Component::Normal(_))) if components.next().is_none() => {
+            flat_language(messages_dir, file)
+        }
+        (Layout::NestedDirs, Some(Component::Normal(lang))) => Some(lang.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
+/// Finds the translation files of a language, relative to its directory
+///
+/// POT templates are skipped since they cannot hold translations.
+pub fn language_files(messages_dir: &MessagesDir, lang: &str) -> Result<Vec<PathBuf>> {
+    if messages_dir.layout == Layout::FlatFiles {
+        if !messages_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        return Ok(flat_files(messages_dir, messages_dir)?
+            .into_iter()
+            .filter(|(file_lang, _)| file_lang == lang)
+            .filter_map(|(_, path)| path.file_name().map(PathBuf::from))
+            .collect());
+    }
+    let lang_dir = language_dir(messages_dir, lang);
+    if !lang_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    Ok(walk_files(&lang_dir)?
+        .into_iter()
+        .filter(|path| messages_dir.is_translation_file(path))
+        .filter_map(|path| path.strip_prefix(&lang_dir).ok().map(|p| p.to_path_buf()))
+        .collect())
+}
+
+/// Finds the file of `lang` that defines `key`, relative to the language directory
+pub fn find_key_file(messages_dir: &MessagesDir, lang: &str, key: &str) -> Result<Option<PathBuf>> {
+    for file in language_files(messages_dir, lang)? {
+        if read_translation_file(messages_dir, &language_dir(messages_dir, lang).join(&file))?.contains_key(key) {
+            return Ok(Some(file));
+        }
+    }
+    Ok(None)
+}
+
+/// Directories that are never traversed, even without a `.gitignore`
+pub const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Builds a directory walker honouring ignore files
+///
+/// `.gitignore` rules apply even outside a git repository, so a messages
+/// directory copied elsewhere is scanned the same way.
+fn walker(root: &Path) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .require_git(false)
+        .filter_entry(|entry| {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            !(is_dir && entry.depth() > 0 && SKIPPED_DIRS.iter().any(|d| entry.file_name() == *d))
+        });
+    builder
+}
+
+/// Lists all files below `root`, sorted
+///
+/// Skips hidden entries, [`SKIPPED_DIRS`] and anything matched by
+/// `.gitignore` / `.ignore` files.
+pub fn walk_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in walker(root).build() {
+        let entry = entry.with_context(|| format!("Failed to walk directory: {}", root.display()))?;
+        if entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+            files.push(entry.into_path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Emits a progress event if a callback is registered
+fn emit(callback: &Option<ProgressCallback>, event: ProgressEvent) {
+    if let Some(callback) = callback {
+        callback(event);
+    }
+}
+
+/// Scans the messages directory and collects all translations
+///
+/// Searches for language subdirectories (e.g., `en/`, `zh_CN/`) and reads
+/// all JSON and YAML files within them. Translation keys are flattened for storage.
+///
+/// # Arguments
+///
+/// * `messages_dir` - The messages directory and its settings
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The directory does not exist
+/// - The path is not a directory
+///
+/// Files that cannot be read or parsed are skipped with a warning and
+/// listed in [`ScanResult::quarantine`].
+///
+/// # Performance
+///
+/// Uses async file operations for better performance on large projects.
+pub async fn scan_messages_dir(messages_dir: &MessagesDir) -> Result<ScanResult> {
+    let result = scan_messages_dir_with(messages_dir, false).await?;
+    for file in &result.quarantine {
+        warn!("Skipped {}", file);
+    }
+    Ok(result)
+}
+
+/// Scans the messages directory, optionally stopping at the first bad file
+///
+/// Without `fail_fast`, files that cannot be read or parsed are skipped and
+/// recorded in [`ScanResult::quarantine`]; with it, the first such file
+/// aborts the scan with its error position.
+pub async fn scan_messages_dir_with(messages_dir: &MessagesDir, fail_fast: bool) -> Result<ScanResult> {
+    let resolved = messages_dir.canonicalize()
+        .with_context(|| format!("Messages directory not found: {}", messages_dir.display()))?;
+
+    if !resolved.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Path is not a directory: {}",
+            resolved.display()
+        ));
+    }
+
+    let mut all_translations = Translations::new();
+    let mut all_files: Vec<PathBuf> = Vec::new();
+    let mut key_origins: KeyOrigins = HashMap::new();
+    let mut total_keys = 0;
+    let mut quarantine: Vec<QuarantinedFile> = Vec::new();
+
+    // Flat layout: one file per language, named after the language
+    if messages_dir.layout == Layout::FlatFiles {
+        let mut by_lang: BTreeMap<String, Vec<TranslationFile>> = BTreeMap::new();
+        for (lang, file) in flat_files(messages_dir, &resolved)? {
+            if let Some(format) = messages_dir.detect(&file) {
+                by_lang.entry(lang).or_default().push((file, format));
+            }
+        }
+        for (lang, translation_files) in by_lang {
+            let files = translation_files
+                .iter()
+                .filter_map(|(file, _)| file.file_name().map(PathBuf::from))
+                .collect();
+            let (translations, files, origins, key_count, skipped) =
+                scan_language_files(messages_dir, &lang, &translation_files, files, fail_fast).await?;
+            all_translations.extend(translations);
+            all_files.extend(files);
+            key_origins.extend(origins);
+            total_keys += key_count;
+            quarantine.extend(skipped);
+        }
+        let mut result = ScanResult {
+            translations: all_translations,
+            files: all_files,
+            key_origins,
+            key_count: total_keys,
+            quarantine,
+        };
+        aliases::expand(messages_dir, &mut result);
+        return Ok(result);
+    }
+
+    // Collect all language directories (hidden directories such as
+    // `.yflow-snapshots` hold CLI state, not translations; ignored
+    // directories are skipped as well)
+    let overrides = &messages_dir.language_dirs;
+    let mut lang_dirs: Vec<(String, PathBuf)> = Vec::new();
+    for entry in walker(&resolved).max_depth(Some(1)).build() {
+        let entry = entry.with_context(|| format!("Failed to read directory: {}", resolved.display()))?;
+        if entry.depth() == 1 && entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            let Some(lang) = dir_language(messages_dir, &entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            if !overrides.contains_key(&lang) {
+                lang_dirs.push((lang, entry.into_path()));
+            }
+        }
+    }
+    // Languages configured in `languageDirs` are read from their own directory
+    lang_dirs.extend(
+        overrides
+            .iter()
+            .filter(|(_, dir)| dir.is_dir())
+            .map(|(lang, dir)| (lang.clone(), dir.clone())),
+    );
+    lang_dirs.sort();
+
+    // Process each language directory
+    for (lang, dir) in lang_dirs {
+        match scan_language_dir(messages_dir, &lang, &dir, fail_fast).await {
+            Ok((translations, files, origins, key_count, skipped)) => {
+                all_translations.extend(translations);
+                all_files.extend(files);
+                key_origins.extend(origins);
+                total_keys += key_count;
+                quarantine.extend(skipped);
+            }
+            Err(e) if fail_fast => return Err(e),
+            Err(e) => {
+                // Record the error but continue processing other languages
+                quarantine.push(quarantined(&dir, &e));
+            }
+        }
+    }
+
+    let mut result = ScanResult {
+        translations: all_translations,
+        files: all_files,
+        key_origins,
+        key_count: total_keys,
+        quarantine,
+    };
+    // Alias languages are copies of their base with the alias files on top
+    aliases::expand(messages_dir, &mut result);
+    Ok(result)
+}
+
+/// Builds a quarantine entry, extracting the parser's error position
+fn quarantined(path: &Path, error: &anyhow::Error) -> QuarantinedFile {
+    let position = error.chain().find_map(|cause| {
+        if let Some(e) = cause.downcast_ref::<serde_json::Error>() {
+            Some((e.line(), e.column()))
+        } else {
+            cause
+                .downcast_ref::<serde_yaml::Error>()
+                .and_then(|e| e.location())
+                .map(|location| (location.line(), location.column()))
+        }
+    });
+    QuarantinedFile {
+        path: path.to_path_buf(),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        error: error.root_cause().to_string(),
+    }
+}
+
+/// Logs the quarantined files as a warning and fails if there are any
+///
+/// Commands call this after finishing their work, so a run that skipped
+/// unreadable files never looks successful.
+pub fn report_quarantine(quarantine: &[QuarantinedFile]) -> Result<()> {
+    if quarantine.is_empty() {
+        return Ok(());
+    }
+    let files: Vec<String> = quarantine.iter().map(ToString::to_string).collect();
+    warn!(
+        "Skipped {} file(s) that could not be parsed:\n  {}",
+        quarantine.len(),
+        files.join("\n  ")
+    );
+    Err(anyhow::anyhow!(
+        "{} file(s) were quarantined; fix them or re-run with --fail-fast to stop at the first one",
+        quarantine.len()
+    ))
+}
+
+/// Scans a single language directory
+///
+/// Reads all JSON/YAML files in the directory and merges translations.
+/// Files are processed recursively for nested subdirectories.
+///
+/// # Arguments
+///
+/// * `messages_dir` - The messages directory the language belongs to
+/// * `lang_code` - Language code of the directory
+/// * `dir_path` - Path to the language directory
+///
+/// # Returns
+///
+/// Tuple of (translations map, file paths, key count, quarantined files)
+async fn scan_language_dir(
+    messages_dir: &MessagesDir,
+    lang_code: &str,
+    dir_path: &Path,
+    fail_fast: bool,
+) -> Result<LanguageScan> {
+    // Recursively collect all translation files
+    let translation_files = collect_translation_files(messages_dir, dir_path)?;
+
+    // Collect file paths relative to the language directory
+    let files = translation_files
+        .iter()
+        .map(|(file, _)| match file.strip_prefix(dir_path) {
+            Ok(rel_path) => PathBuf::from(lang_code).join(rel_path),
+            Err(_) => PathBuf::from(lang_code).join(file.file_name().unwrap()),
+        })
+        .collect();
+
+    scan_language_files(messages_dir, lang_code, &translation_files, files, fail_fast).await
+}
+
+/// Parses the translation files of one language and merges their keys
+///
+/// `files` are the logical paths reported in the [`ScanResult`], in the
+/// same order as `translation_files`.
+async fn scan_language_files(
+    messages_dir: &MessagesDir,
+    lang_code: &str,
+    translation_files: &[TranslationFile],
+    files: Vec<PathBuf>,
+    fail_fast: bool,
+) -> Result<LanguageScan> {
+    let mut translations = HashMap::new();
+    let mut key_origins: KeyOrigins = HashMap::new();
+
+    // Parse all files, each in the format picked by the registry
+    let mut parse_results: Vec<(&dyn TranslationFormat, &PathBuf, HashMap<String, String>)> = Vec::new();
+    let mut quarantine: Vec<QuarantinedFile> = Vec::new();
+    for ((file, format), logical) in translation_files.iter().zip(&files) {
+        let parsed = match fs::read_to_string(file).await {
+            Ok(content) => format
+                .read_flat(&content, &messages_dir.separator)
+                .with_context(|| format!("Failed to parse {} {}", format.name(), file.display())),
+            Err(e) => Err(anyhow::Error::new(e))
+                .with_context(|| format!("Failed to read file {}", file.display())),
+        };
+        match parsed {
+            Ok(flat) => parse_results.push((format.as_ref(), logical, flat)),
+            Err(e) if fail_fast => {
+                let entry = quarantined(file, &e);
+                return Err(e.context(format!("Stopped at {} (--fail-fast)", entry)));
+            }
+            Err(e) => quarantine.push(quarantined(file, &e)),
+        }
+    }
+
+    let lang_code = lang_code.to_string();
+
+    translations.insert(lang_code.clone(), HashMap::new());
+    let lang_translations = translations.get_mut(&lang_code).unwrap();
+
+    for (format, logical, flat) in &parse_results {
+        if format.is_template() {
+            // Template source strings never override real translations
+            for (key, value) in flat {
+                if !lang_translations.contains_key(key) {
+                    lang_translations.insert(key.clone(), value.clone());
+                    key_origins.insert((lang_code.clone(), key.clone()), (*logical).clone());
+                }
+            }
+        } else {
+            for (key, value) in flat {
+                lang_translations.insert(key.clone(), value.clone());
+                key_origins.insert((lang_code.clone(), key.clone()), (*logical).clone());
+            }
+        }
+    }
+
+    let key_count = lang_translations.len();
+
+    Ok((translations, files, key_origins, key_count, quarantine))
+}
+
+/// Recursively collects all translation files in a directory
+///
+/// # Arguments
+///
+/// * `messages_dir` - The messages directory (selects the formats)
+/// * `dir` - Directory to search
+///
+/// # Returns
+///
+/// Vector of paths to all files with a known format, with their format
+fn collect_translation_files(messages_dir: &MessagesDir, dir: &Path) -> Result<Vec<TranslationFile>> {
+    Ok(walk_files(dir)?
+        .into_iter()
+        .filter_map(|path| messages_dir.detect(&path).map(|format| (path, format)))
+        .collect())
+}
+
+/// Writes translations while preserving the original file structure
+///
+/// Builds the [`SyncPlan`] with [`plan_translations_with_structure`] and
+/// applies it. Only the translation keys that exist in the new translations
+/// are updated; all other content in the original files is preserved.
+///
+/// # Arguments
+///
+/// * `messages_dir` - Root messages directory and its settings
+/// * `original_files` - List of original file paths (relative to messages dir)
+/// * `translations` - New translations to merge
+/// * `force` - Whether to overwrite all keys (true) or only new keys (false)
+/// * `progress_callback` - Optional callback receiving a [`ProgressEvent`] for each
+///   language started/finished and each file written
+///
+/// # Returns
+///
+/// Vector of file paths that were written
+///
+/// # Example
+///
+/// ```ignore
+/// let written = write_translations_with_structure(
+///     &messages_dir,
+///     &original_files,
+///     &translations,
+///     false,
+///     Some(Box::new(|event| println!("{:?}", event))),
+/// ).await?;
+/// ```
+pub async fn write_translations_with_structure(
+    messages_dir: &MessagesDir,
+    original_files: &[PathBuf],
+    translations: &Translations,
+    force: bool,
+    progress_callback: Option<ProgressCallback>,
+) -> Result<Vec<PathBuf>> {
+    plan_translations_with_structure(messages_dir, original_files, translations, force)
+        .await?
+        .apply(progress_callback)
+        .await
+}
+
+/// Computes the changes needed to write translations, without writing
+///
+/// Each original file is read and the new translations are merged into it
+/// in its own format (JSON, YAML, PO...); files whose content would not
+/// change are left out of the plan. Values equivalent to the current ones
+/// under the project's comparator (see [`super::compare`]) are not written.
+///
+/// Every key is written to a single place: the file(s) already defining
+/// it, else the file matching its namespace (see [`super::namespaces`]).
+/// New languages get one file per namespace known from other languages.
+///
+/// # Arguments
+///
+/// * `messages_dir` - Root messages directory and its settings
+/// * `original_files` - List of original file paths (relative to messages dir)
+/// * `translations` - New translations to merge
+/// * `force` - Whether to overwrite all keys (true) or only new keys (false)
+///
+/// # Returns
+///
+/// The plan, with one entry per language (existing languages first)
+pub async fn plan_translations_with_structure(
+    messages_dir: &MessagesDir,
+    original_files: &[PathBuf],
+    translations: &Translations,
+    force: bool,
+) -> Result<SyncPlan> {
+    plan_translations_with_removals(messages_dir, original_files, translations, &BTreeMap::new(), force).await
+}
+
+/// Computes the changes needed to write translations and remove keys
+///
+/// Like [`plan_translations_with_structure`], and additionally removes the
+/// given keys from every existing file of their language that defines them
+/// (`sync --prune`), leaving the rest of each file as it is.
+///
+/// # Arguments
+///
+/// * `messages_dir` - Root messages directory and its settings
+/// * `original_files` - List of original file paths (relative to messages dir)
+/// * `translations` - New translations to merge
+/// * `removals` - Keys to remove, by language
+/// * `force` - Whether to overwrite all keys (true) or only new keys (false)
+///
+/// # Errors
+///
+/// Returns an error naming the file when an existing file cannot be parsed
+pub async fn plan_translations_with_removals(
+    messages_dir: &MessagesDir,
+    original_files: &[PathBuf],
+    translations: &Translations,
+    removals: &BTreeMap<String, Vec<String>>,
+    force: bool,
+) -> Result<SyncPlan> {
+    let mut plan = SyncPlan::default();
+    let no_translations = HashMap::new();
+    // Alias files only receive the keys that differ from their base language
+    let translations = &aliases::overrides(messages_dir, translations);
+    let comparator = messages_dir.comparator;
+    let separator = messages_dir.separator.as_str();
+
+    // Group files by language code using proper PathBuf methods
+    let mut files_by_lang: BTreeMap<String, Vec<&PathBuf>> = BTreeMap::new();
+    for file in original_files {
+        if let Some(lang_code) = file_language(messages_dir, file) {
+            files_by_lang.entry(lang_code).or_default().push(file);
+        }
+    }
+    // With a mobile format, keys no file defines go to the platform's
+    // resource file (`strings.xml`, `Localizable.strings`) rather than the
+    // first file of the language
+    if let Some(format) = messages_dir.format {
+        for files in files_by_lang.values_mut() {
+            files.sort_by_key(|file| file.file_name().is_none_or(|name| name != format.file_name()));
+        }
+    }
+
+    // Process each language
+    for (lang_code, files) in &files_by_lang {
+        let mut language = LanguagePlan {
+            lang: lang_code.clone(),
+            changes: Vec::new(),
+        };
+
+        // Languages without translations are kept for progress reporting
+        let lang_removals = removals.get(lang_code).map(Vec::as_slice).unwrap_or_default();
+        let lang_translations = match translations.get(lang_code) {
+            Some(lang_translations) => lang_translations,
+            None if !lang_removals.is_empty() => &no_translations,
+            None => {
+                plan.languages.push(language);
+                continue;
+            }
+        };
+
+        // Read every file of the language and the keys it already defines
+        let mut loaded: Vec<(TranslationFile, String, HashMap<String, String>)> = Vec::new();
+        let mut targets: Vec<NamespaceFile> = Vec::new();
+        for file in files {
+            let full_path = resolve_file(messages_dir, file);
+
+            if !full_path.exists() {
+                continue;
+            }
+            let format = messages_dir.detect(&full_path).unwrap_or_else(|| Arc::new(FileFormat::Json));
+            // Read-only formats (e.g. POT templates) never receive keys
+            if format.is_template() {
+                continue;
+            }
+
+            match fs::read_to_string(&full_path).await {
+                Ok(content) => {
+                    let flat = format
+                        .read_flat(&content, separator)
+                        .with_context(|| format!("Failed to parse {} {}", format.name(), full_path.display()))?;
+                    let relative: PathBuf = match messages_dir.layout {
+                        Layout::FlatFiles => file.to_path_buf(),
+                        Layout::NestedDirs => file.components().skip(1).collect(),
+                    };
+                    targets.push(NamespaceFile {
+                        namespace: file_namespace(&relative, &messages_dir.separator),
+                        keys: flat.keys().cloned().collect(),
+                    });
+                    loaded.push(((full_path, format), content, flat));
+                }
+                Err(e) => {
+                    warn!("Failed to read {}: {}", full_path.display(), e);
+                }
+            }
+        }
+
+        // Each key goes to the file defining it or matching its namespace
+        let routed = route_keys(&targets, lang_translations, messages_dir.dialect, &messages_dir.separator);
+        for (((full_path, format), content, old), mut file_translations) in loaded.into_iter().zip(routed) {
+            // Equivalent values keep their local form
+            if !comparator.is_exact() {
+                file_translations
+                    .retain(|key, value| old.get(key).is_none_or(|current| !comparator.equivalent(current, value)));
+            }
+            let file_removals: Vec<String> =
+                lang_removals.iter().filter(|key| old.contains_key(*key)).cloned().collect();
+            if file_translations.is_empty() && file_removals.is_empty() {
+                continue;
+            }
+            let merged = if file_translations.is_empty() {
+                Ok(Some(content.clone()))
+            } else {
+                format.merge(&content, &file_translations, force, separator)
+            };
+            let merged = match merged {
+                // Formats that cannot remove keys keep them
+                Ok(Some(merged)) if !file_removals.is_empty() => match format.remove(&merged, &file_removals, separator) {
+                    Ok(None) => Ok(Some(merged)),
+                    removed => removed,
+                },
+                other => other,
+            };
+            match merged {
+                // Read-only format
+                Ok(None) => {}
+                // Content unchanged, avoid touching the file
+                Ok(Some(new_content)) if new_content == content => {}
+                Ok(Some(new_content)) => {
+                    // Translations merged into the original structure
+                    let new = format.read_flat(&new_content, separator).with_context(|| {
+                        format!("Failed to parse merged {} {}", format.name(), full_path.display())
+                    })?;
+                    // Same keys and values, only the formatting would change: leave the file alone
+                    if new == old {
+                        continue;
+                    }
+                    language
+                        .changes
+                        .push(FileChange::new(full_path, false, content, new_content, &old, &new));
+                }
+                Err(e) => {
+                    warn!("Failed to parse {} {}: {}", format.name(), full_path.display(), e);
+                }
+            }
+        }
+
+        plan.languages.push(language);
+    }
+
+    // Handle languages that have translations but no original files
+    // 为没有原始文件的新语言规划目录和文件
+    plan.languages
+        .extend(plan_new_language_files(messages_dir, translations, &files_by_lang)?);
+
+    Ok(plan)
+}
+
+/// 为没有原始文件的新语言规划要创建的文件
+///
+/// 当从后端同步翻译时，如果某个语言在本地没有对应的文件，
+/// 此函数会规划新的目录结构：其他语言中有同名命名空间文件的键写入对应文件
+/// （如 `errors.*` 写入 `errors.json`），其余的键写入 sync.json（平铺布局下
+/// 整个语言写入 `<lang>.json`）。
+///
+/// # Arguments
+///
+/// * `messages_dir` - messages 根目录及其设置
+/// * `translations` - 要写入的翻译数据
+/// * `files_by_lang` - 按语言分组的现有文件映射
+///
+/// # Returns
+///
+/// 每个新语言的计划（按语言代码排序）
+///
+/// # Example
+///
+/// ```ignore
+/// let languages = plan_new_language_files(&messages_dir, &translations, &files_by_lang)?;
+/// ```
+fn plan_new_language_files(
+    messages_dir: &MessagesDir,
+    translations: &Translations,
+    files_by_lang: &BTreeMap<String, Vec<&PathBuf>>,
+) -> Result<Vec<LanguagePlan>> {
+    let mut languages = Vec::new();
+
+    // 其他语言中的 JSON/YAML 文件作为新语言的命名空间文件
+    let mut templates: Vec<PathBuf> = files_by_lang
+        .values()
+        .flatten()
+        .filter(|file| matches!(FileFormat::from_path(file), Some(FileFormat::Json | FileFormat::Yaml)))
+        .map(|file| file.components().skip(1).collect::<PathBuf>())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .collect();
+    templates.sort();
+    templates.dedup();
+
+    let mut lang_codes: Vec<&String> = translations.keys().collect();
+    lang_codes.sort();
+    for lang_code in lang_codes {
+        let lang_translations = &translations[lang_code];
+
+        // 检查是否已有原始文件
+        if files_by_lang.contains_key(lang_code) {
+            continue;
+        }
+
+        // 跳过空翻译
+        if lang_translations.is_empty() {
+            continue;
+        }
+
+        // 平铺布局下每个语言一个文件（`<lang>.json`），移动端格式写入平台的
+        // 资源文件，否则按命名空间拆分
+        let mobile = messages_dir.format;
+        let new_files: BTreeMap<PathBuf, HashMap<String, String>> = match (messages_dir.layout, mobile) {
+            (Layout::FlatFiles, _) => BTreeMap::from([(PathBuf::from(format!("{}.json", lang_code)), lang_translations.clone())]),
+            (Layout::NestedDirs, Some(format)) => BTreeMap::from([(PathBuf::from(format.file_name()), lang_translations.clone())]),
+            (Layout::NestedDirs, None) => split_new_language(&templates, lang_translations, &messages_dir.separator),
+        };
+
+        let lang_dir = language_dir(messages_dir, lang_code);
+        let mut language = LanguagePlan {
+            lang: lang_code.clone(),
+            changes: Vec::new(),
+        };
+        for (relative, file_translations) in new_files {
+            // 将展平翻译还原为嵌套结构
+            let merged = unflatten_object_with(file_translations.clone(), &messages_dir.separator);
+            let new_content = match (FileFormat::from_path(&relative), messages_dir.detect(&relative)) {
+                (Some(FileFormat::Yaml), _) => serde_yaml::to_string(&merged)
+                    .with_context(|| "Failed to serialize translations to YAML")?,
+                (None, Some(format)) if mobile.is_some() => format
+                    .merge("", &file_translations, true, &messages_dir.separator)?
+                    .ok_or_else(|| anyhow::anyhow!("{} files are read-only", format.name()))?,
+                _ => serde_json::to_string_pretty(&merged)
+                    .with_context(|| "Failed to serialize translations to JSON")?,
+            };
+
+            language.changes.push(FileChange::new(
+                lang_dir.join(&relative),
+                true,
+                String::new(),
+                new_content,
+                &HashMap::new(),
+                &file_translations,
+            ));
+        }
+        languages.push(language);
+    }
+
+    Ok(languages)
+}
+
+/// Verifies that written files contain the expected translations
+///
+/// Re-reads and re-flattens every written file, then checks that each
+/// key/value of the language is present with the expected value in one of
+/// the language's files (keys are routed to a single file per namespace).
+/// This catches serialization problems (e.g. a key that is both a leaf and
+/// a parent) and filesystem issues right after a sync.
+///
+/// # Arguments
+///
+/// * `messages_dir` - Root messages directory and its settings
+/// * `written` - Full paths of the files that were written
+/// * `translations` - Translations that were expected to be written
+///
+/// # Returns
+///
+/// One human-readable entry per failed check; empty when everything matches
+pub async fn verify_written_files(
+    messages_dir: &MessagesDir,
+    written: &[PathBuf],
+    translations: &Translations,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    let translations = &aliases::overrides(messages_dir, translations);
+
+    // Keys of a language are spread over its files, so check per language
+    let mut written_by_lang: BTreeMap<String, Vec<&PathBuf>> = BTreeMap::new();
+    for path in written {
+        if let Some(lang_code) = language_of(messages_dir, path) {
+            written_by_lang.entry(lang_code).or_default().push(path);
+        }
+    }
+
+    for (lang_code, paths) in written_by_lang {
+        let expected = match translations.get(&lang_code) {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let mut actual: HashMap<String, String> = HashMap::new();
+        let mut unreadable = false;
+        for path in &paths {
+            let content = match fs::read_to_string(path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    failures.push(format!("{}: failed to re-read file: {}", path.display(), e));
+                    unreadable = true;
+                    continue;
+                }
+            };
+
+            let format = messages_dir.detect(path).unwrap_or_else(|| Arc::new(FileFormat::Json));
+            match format.read_flat(&content, &messages_dir.separator) {
+                Ok(flat) => actual.extend(flat),
+                Err(e) => {
+                    failures.push(format!("{}: written file is not valid {}: {}", path.display(), format.name(), e));
+                    unreadable = true;
+                }
+            }
+        }
+        // Missing keys would only repeat the failure above
+        if unreadable {
+            continue;
+        }
+
+        // Keys routed to files that needed no change are read from disk
+        let lang_dir = language_dir(messages_dir, &lang_code);
+        for file in language_files(messages_dir, &lang_code).unwrap_or_default() {
+            let path = lang_dir.join(file);
+            if paths.contains(&&path) {
+                continue;
+            }
+            if let Ok(flat) = read_translation_file(messages_dir, &path) {
+                for (key, value) in flat {
+                    actual.entry(key).or_insert(value);
+                }
+            }
+        }
+
+        let mut mismatched: Vec<&String> = expected
+            .iter()
+            .filter(|(key, value)| actual.get(*key) != Some(*value))
+            .map(|(key, _)| key)
+            .collect();
+
+        if !mismatched.is_empty() {
+            mismatched.sort();
+            let preview: Vec<&str> = mismatched.iter().take(10).map(|k| k.as_str()).collect();
+            let more = if mismatched.len() > 10 {
+                format!(" ... and {} more", mismatched.len() - 10)
+            } else {
+                String::new()
+            };
+            let location = match paths.as_slice() {
+                [path] => path.display().to_string(),
+                _ => lang_dir.display().to_string(),
+            };
+            failures.push(format!(
+                "{}: {} key(s) missing or different after write - {}{}",
+                location,
+                mismatched.len(),
+                preview.join(", "),
+                more
+            ));
+        }
+    }
+
+    failures
+}
+
+/// Merges translations into the original nested structure
+///
+/// Lossless: only string leaves named by `translations` are overwritten (or
+/// created); numbers, booleans, nulls, arrays and every other node of the
+/// original are kept as they are. Keys that point at a non-string value
+/// are skipped with a warning.
+///
+/// Note: This function always overwrites existing keys with new values.
+/// The `force` parameter is maintained for API compatibility but is not
+/// used in the merge logic, matching the TypeScript implementation.
+///
+/// # Arguments
+///
+/// * `original` - Original JSON data
+/// * `translations` - New translations to merge
+/// * `_force` - Reserved for API compatibility (not used)
+/// * `separator` - Key separator of the messages directory
+///
+/// # Returns
+///
+/// Merged JSON data
+fn merge_translations_with_structure(
+    original: &Value,
+    translations: &HashMap<String, String>,
+    _force: bool,
+    separator: &str,
+) -> Value {
+    // Merge translations (new values always overwrite old string values)
+    let mut merged = original.clone();
+    for key in merge_flat_into(&mut merged, translations, separator) {
+        warn!("Skipped {}: the file holds a non-string value there", key);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::compare::Comparator;
+    use crate::core::formats::MobileFormat;
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Creates a test messages directory structure
+    async fn create_test_messages_dir(temp_dir: &TempDir) -> (MessagesDir, Vec<PathBuf>) {
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        // Create language directories
+        let en_dir = messages_dir.join("en");
+        let zh_dir = messages_dir.join("zh_CN");
+        std::fs::create_dir_all(&en_dir).unwrap();
+        std::fs::create_dir_all(&zh_dir).unwrap();
+
+        // Create nested subdirectory
+        let nested_dir = en_dir.join("nested");
+        std::fs::create_dir_all(&nested_dir).unwrap();
+
+        // Create test files
+        let en_common = en_dir.join("common.json");
+        let en_nested = nested_dir.join("deep.json");
+        let zh_common = zh_dir.join("common.json");
+
+        // Write test content
+        let en_content = json!({
+            "greeting": "Hello",
+            "user": {
+                "name": "User Name"
+            }
+        });
+        let mut file = File::create(&en_common).unwrap();
+        writeln!(file, "{}", serde_json::to_string_pretty(&en_content).unwrap()).unwrap();
+
+        let en_deep_content = json!({
+            "level": {
+                "deep": "Deep value"
+            }
+        });
+        let mut file = File::create(&en_nested).unwrap();
+        writeln!(file, "{}", serde_json::to_string_pretty(&en_deep_content).unwrap()).unwrap();
+
+        let zh_content = json!({
+            "greeting": "你好",
+            "user": {
+                "name": "用户名"
+            }
+        });
+        let mut file = File::create(&zh_common).unwrap();
+        writeln!(file, "{}", serde_json::to_string_pretty(&zh_content).unwrap()).unwrap();
+
+        let expected_files = vec![
+            PathBuf::from("en/common.json"),
+            PathBuf::from("en/nested/deep.json"),
+            PathBuf::from("zh_CN/common.json"),
+        ];
+
+        (messages_dir, expected_files)
+    }
+
+    #[tokio::test]
+    async fn test_scan_messages_dir_missing() {
+        let result = scan_messages_dir(&MessagesDir::new("/nonexistent")).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_empty_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations.len(), 0);
+        assert_eq!(result.files.len(), 0);
+        assert_eq!(result.key_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_scan_quarantines_invalid_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en").join("common.json"), r#"{"ok": "OK"}"#).unwrap();
+        std::fs::write(messages_dir.join("en").join("broken.json"), "{\n  \"a\": \"b\",\n  oops\n}").unwrap();
+        std::fs::write(messages_dir.join("en").join("broken.yaml"), "a: [b\nc: d\n").unwrap();
+
+        let result = scan_messages_dir_with(&messages_dir, false).await.unwrap();
+        assert_eq!(result.key_count, 1);
+        assert_eq!(result.quarantine.len(), 2);
+        let json = &result.quarantine[0];
+        assert!(json.path.ends_with("en/broken.json"));
+        assert_eq!((json.line, json.column), (Some(3), Some(3)));
+        assert!(json.to_string().contains("broken.json:3:3: key must be a string"));
+        assert!(result.quarantine[1].line.is_some());
+        assert!(report_quarantine(&result.quarantine).is_err());
+        assert!(report_quarantine(&[]).is_ok());
+
+        let error = scan_messages_dir_with(&messages_dir, true).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("(--fail-fast)"));
+    }
+
+    /// `key=value` 行格式，用于测试注册的插件格式
+    #[derive(Debug)]
+    struct PropertiesFormat;
+
+    impl TranslationFormat for PropertiesFormat {
+        fn name(&self) -> &str {
+            "properties"
+        }
+
+        fn read_flat(&self, content: &str, _separator: &str) -> Result<HashMap<String, String>> {
+            Ok(content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect())
+        }
+
+        fn merge(
+            &self,
+            content: &str,
+            translations: &HashMap<String, String>,
+            _force: bool,
+            separator: &str,
+        ) -> Result<Option<String>> {
+            let mut values = self.read_flat(content, separator)?;
+            values.extend(translations.clone());
+            let mut lines: Vec<String> = values.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            lines.sort();
+            Ok(Some(lines.join("\n")))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_write_registered_format() {
+        crate::core::formats::registry::register_extension("testprops", Arc::new(PropertiesFormat));
+
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en").join("app.testprops"), "title=Title\n").unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations["en"]["title"], "Title");
+        assert_eq!(result.files, vec![PathBuf::from("en/app.testprops")]);
+
+        let translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([("title".to_string(), "New".to_string()), ("body".to_string(), "Body".to_string())]),
+        )]);
+        write_translations_with_structure(&messages_dir, &result.files, &translations, true, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(messages_dir.join("en").join("app.testprops")).unwrap(),
+            "body=Body\ntitle=New"
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_non_string_values() {
+        let content = r#"{"title": "Old", "meta": {"version": 2, "beta": false}, "tags": ["x"]}"#;
+        let translations = HashMap::from([
+            ("title".to_string(), "New".to_string()),
+            ("meta.version".to_string(), "3".to_string()),
+        ]);
+
+        let merged = FileFormat::Json.merge(content, &translations, false, ".").unwrap().unwrap();
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(
+            merged,
+            json!({"title": "New", "meta": {"version": 2, "beta": false}, "tags": ["x"]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_single_language() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        let en_dir = messages_dir.join("en");
+        std::fs::create_dir_all(&en_dir).unwrap();
+
+        // Create test file
+        let test_file = en_dir.join("common.json");
+        let content = json!({
+            "greeting": "Hello",
+            "user": {
+                "name": "User Name"
+            }
+        });
+        let mut file = File::create(&test_file).unwrap();
+        writeln!(file, "{}", serde_json::to_string_pretty(&content).unwrap()).unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+
+        assert_eq!(result.translations.len(), 1);
+        assert!(result.translations.contains_key("en"));
+        assert_eq!(result.key_count, 2);
+        assert!(!result.files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_records_key_origins() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en/admin")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Title", "shared": "A"}"#).unwrap();
+        std::fs::write(messages_dir.join("en/admin/users.yaml"), "shared: B\nusers:\n  name: Name\n").unwrap();
+        std::fs::write(messages_dir.join("en/messages.pot"), "msgid \"title\"\nmsgstr \"\"\n\nmsgid \"hint\"\nmsgstr \"\"\n").unwrap();
+        std::fs::write(messages_dir.join("de/common.json"), r#"{"title": "Titel"}"#).unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.key_origins.len(), 5);
+        assert_eq!(result.origin("en", "title"), Some(Path::new("en/common.json")));
+        assert_eq!(result.origin("en", "users.name"), Some(Path::new("en/admin/users.yaml")));
+        // 多处定义时记录值生效的文件；模板只提供缺失的键
+        assert_eq!(result.translations["en"]["shared"], "A");
+        assert_eq!(result.origin("en", "shared"), Some(Path::new("en/common.json")));
+        assert_eq!(result.origin("en", "hint"), Some(Path::new("en/messages.pot")));
+        assert_eq!(result.origin("de", "title"), Some(Path::new("de/common.json")));
+        assert_eq!(result.origin("de", "users.name"), None);
+        assert_eq!(
+            resolve_file(&messages_dir, result.origin("en", "title").unwrap()),
+            messages_dir.join("en/common.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_multiple_languages() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, _) = create_test_messages_dir(&temp_dir).await;
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+
+        assert_eq!(result.translations.len(), 2);
+        assert!(result.translations.contains_key("en"));
+        assert!(result.translations.contains_key("zh_CN"));
+        assert_eq!(result.key_count, 5); // 2 + 2 + 1 = 5 keys
+        assert!(result.files.len() >= 3);
+    }
+
+    #[tokio::test]
+    async fn test_scan_nested_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, _) = create_test_messages_dir(&temp_dir).await;
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+
+        // Check that nested keys are properly flattened
+        let en_translations = result.translations.get("en").unwrap();
+        assert!(en_translations.contains_key("greeting"));
+        assert!(en_translations.contains_key("user.name"));
+        assert!(en_translations.contains_key("level.deep"));
+    }
+
+    #[tokio::test]
+    async fn test_language_dirs_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let en_dir = temp_dir.path().join("src/locales/en");
+        let messages_dir = MessagesDir {
+            language_dirs: BTreeMap::from([("en".to_string(), en_dir.clone())]),
+            ..MessagesDir::new(temp_dir.path().join("translations"))
+        };
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(&en_dir).unwrap();
+        std::fs::write(messages_dir.join("de/common.json"), r#"{"title": "Titel"}"#).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"stale": "ignored"}"#).unwrap();
+        std::fs::write(en_dir.join("common.json"), r#"{"title": "Title"}"#).unwrap();
+
+        assert_eq!(language_dir(&messages_dir, "en"), en_dir);
+        assert_eq!(language_dir(&messages_dir, "de"), messages_dir.join("de"));
+        assert_eq!(resolve_file(&messages_dir, Path::new("en/common.json")), en_dir.join("common.json"));
+        assert_eq!(language_of(&messages_dir, &en_dir.join("common.json")).as_deref(), Some("en"));
+        assert_eq!(language_of(&messages_dir, &messages_dir.join("de/common.json")).as_deref(), Some("de"));
+
+        // 扫描：en 只从独立目录读取
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(
+            result.translations["en"],
+            HashMap::from([("title".to_string(), "Title".to_string())])
+        );
+        assert_eq!(result.translations["de"]["title"], "Titel");
+        assert!(result.files.contains(&PathBuf::from("en/common.json")));
+
+        // 写回：写入独立目录，校验也能识别语言
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([("title".to_string(), "New title".to_string())]),
+        )]);
+        let written = write_translations_with_structure(&messages_dir, &result.files, &translations, true, None)
+            .await
+            .unwrap();
+        assert_eq!(written, vec![en_dir.join("common.json")]);
+        assert!(verify_written_files(&messages_dir, &written, &translations).await.is_empty());
+        assert!(std::fs::read_to_string(messages_dir.join("en/common.json")).unwrap().contains("stale"));
+
+        let default_dirs = MessagesDir::new(messages_dir.path.clone());
+        assert_eq!(language_dir(&default_dirs, "en"), messages_dir.join("en"));
+    }
+
+    #[tokio::test]
+    async fn test_write_routes_keys_by_namespace() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Title"}"#).unwrap();
+        std::fs::write(messages_dir.join("en/errors.yaml"), "errors:\n  notFound: Not found\n").unwrap();
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+
+        let translations: Translations = HashMap::from([
+            (
+                "en".to_string(),
+                HashMap::from([
+                    ("title".to_string(), "New title".to_string()),
+                    ("errors.forbidden".to_string(), "Forbidden".to_string()),
+                    ("footer.copyright".to_string(), "(c)".to_string()),
+                ]),
+            ),
+            (
+                "de".to_string(),
+                HashMap::from([
+                    ("title".to_string(), "Titel".to_string()),
+                    ("errors.notFound".to_string(), "Nicht gefunden".to_string()),
+                ]),
+            ),
+        ]);
+        let written = write_translations_with_structure(&messages_dir, &result.files, &translations, true, None)
+            .await
+            .unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(verify_written_files(&messages_dir, &written, &translations).await.is_empty());
+
+        // 已有语言：新键写入命名空间文件，不会重复写入其他文件
+        let common = read_translation_file(&messages_dir, &messages_dir.join("en/common.json")).unwrap();
+        let errors = read_translation_file(&messages_dir, &messages_dir.join("en/errors.yaml")).unwrap();
+        assert_eq!(common.len(), 2);
+        assert_eq!(common["footer.copyright"], "(c)");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors["errors.forbidden"], "Forbidden");
+
+        // 新语言：按命名空间创建文件
+        let de_errors = read_translation_file(&messages_dir, &messages_dir.join("de/errors.yaml")).unwrap();
+        assert_eq!(de_errors, HashMap::from([("errors.notFound".to_string(), "Nicht gefunden".to_string())]));
+        let de_sync = read_translation_file(&messages_dir, &messages_dir.join("de/sync.json")).unwrap();
+        assert_eq!(de_sync, HashMap::from([("title".to_string(), "Titel".to_string())]));
+    }
+
+    #[tokio::test]
+    async fn test_flat_files_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir {
+            layout: Layout::FlatFiles,
+            ..MessagesDir::new(temp_dir.path().join("messages"))
+        };
+        std::fs::create_dir_all(messages_dir.join("assets")).unwrap();
+        std::fs::write(messages_dir.join("en.json"), r#"{"home": {"title": "Title"}}"#).unwrap();
+        std::fs::write(messages_dir.join("zh_CN.yaml"), "home:\n  title: 标题\n").unwrap();
+        std::fs::write(messages_dir.join("messages.pot"), "").unwrap();
+        std::fs::write(messages_dir.join("assets/logo.json"), r#"{"ignored": "yes"}"#).unwrap();
+
+        assert_eq!(language_dir(&messages_dir, "en"), messages_dir.path);
+        assert_eq!(language_files(&messages_dir, "zh_CN").unwrap(), vec![PathBuf::from("zh_CN.yaml")]);
+        assert_eq!(language_of(&messages_dir, &messages_dir.join("en.json")).as_deref(), Some("en"));
+        assert_eq!(language_of(&messages_dir, &messages_dir.join("assets/logo.json")), None);
+
+        // 扫描：语言来自文件名，子目录被忽略
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations.len(), 2);
+        assert_eq!(result.translations["en"]["home.title"], "Title");
+        assert_eq!(result.translations["zh_CN"]["home.title"], "标题");
+        assert_eq!(result.files, vec![PathBuf::from("en.json"), PathBuf::from("zh_CN.yaml")]);
+
+        // 写回：已有语言写入原文件，新语言创建 `<lang>.json`
+        let translations: Translations = HashMap::from([
+            ("zh_CN".to_string(), HashMap::from([("home.body".to_string(), "正文".to_string())])),
+            ("de".to_string(), HashMap::from([("home.title".to_string(), "Titel".to_string())])),
+        ]);
+        let mut written = write_translations_with_structure(&messages_dir, &result.files, &translations, false, None)
+            .await
+            .unwrap();
+        written.sort();
+        assert_eq!(written, vec![messages_dir.join("de.json"), messages_dir.join("zh_CN.yaml")]);
+        assert!(verify_written_files(&messages_dir, &written, &translations).await.is_empty());
+        assert!(!messages_dir.join("de").exists());
+
+        let rescanned = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(rescanned.translations["de"]["home.title"], "Titel");
+        assert_eq!(rescanned.translations["zh_CN"]["home.body"], "正文");
+
+        // 新建语言：复制源语言文件的格式
+        let created = scaffold_language(&messages_dir, "zh_CN", "ja", false).unwrap();
+        assert_eq!(created, vec![messages_dir.join("ja.yaml")]);
+        assert!(scaffold_language(&messages_dir, "zh_CN", "ja", false).is_err());
+        assert!(scaffold_language(&messages_dir, "fr", "it", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_android_resource_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let res_path = temp_dir.path().join("res");
+        let res_dir = MessagesDir {
+            format: Some(MobileFormat::Android),
+            language_dirs: BTreeMap::from([("en".to_string(), res_path.join("values"))]),
+            ..MessagesDir::new(&res_path)
+        };
+        for dir in ["values", "values-de", "values-night", "drawable"] {
+            std::fs::create_dir_all(res_dir.join(dir)).unwrap();
+        }
+        std::fs::write(res_dir.join("values/strings.xml"), "<resources><string name=\"title\">Inbox</string></resources>").unwrap();
+        std::fs::write(res_dir.join("values-de/strings.xml"), "<resources><string name=\"title\">Posteingang</string></resources>").unwrap();
+        std::fs::write(res_dir.join("values-de/colors.xml"), "<resources><color name=\"accent\">#f00</color></resources>").unwrap();
+        std::fs::write(res_dir.join("values-night/strings.xml"), "<resources><string name=\"title\">Dark</string></resources>").unwrap();
+
+        assert_eq!(language_dir(&res_dir, "zh_CN"), res_dir.join("values-zh-rCN"));
+        assert_eq!(language_of(&res_dir, &res_dir.join("values-de/strings.xml")).as_deref(), Some("de"));
+        assert_eq!(language_of(&res_dir, &res_dir.join("values/strings.xml")).as_deref(), Some("en"));
+        assert_eq!(language_of(&res_dir, &res_dir.join("drawable/icon.xml")), None);
+
+        // 扫描：只读取语言目录中的 strings.xml
+        let result = scan_messages_dir(&res_dir).await.unwrap();
+        assert_eq!(result.translations.len(), 2);
+        assert_eq!(result.translations["de"]["title"], "Posteingang");
+        assert_eq!(result.translations["en"]["title"], "Inbox");
+        assert_eq!(result.files, vec![PathBuf::from("de/strings.xml"), PathBuf::from("en/strings.xml")]);
+
+        // 写回：新语言创建 values-<lang>/strings.xml
+        let translations: Translations = HashMap::from([
+            ("de".to_string(), HashMap::from([("title".to_string(), "Eingang".to_string())])),
+            ("zh-CN".to_string(), HashMap::from([("title".to_string(), "收件箱".to_string())])),
+        ]);
+        let mut written = write_translations_with_structure(&res_dir, &result.files, &translations, false, None)
+            .await
+            .unwrap();
+        written.sort();
+        assert_eq!(written, vec![res_dir.join("values-de/strings.xml"), res_dir.join("values-zh-rCN/strings.xml")]);
+        assert!(verify_written_files(&res_dir, &written, &translations).await.is_empty());
+
+        let rescanned = scan_messages_dir(&res_dir).await.unwrap();
+        assert_eq!(rescanned.translations["zh-CN"]["title"], "收件箱");
+        assert_eq!(rescanned.translations["de"]["title"], "Eingang");
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_with_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        // Prepare new translations
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello Updated".to_string()),
+                ("new_key".to_string(), "New Value".to_string()),
+            ].iter().cloned().collect()),
+            ("zh_CN".to_string(), [
+                ("greeting".to_string(), "你好更新".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        // Note: merge always overwrites, force parameter is handled at higher level
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // Verify files were written
+        assert!(written.len() >= 2);
+
+        // Verify content was merged correctly
+        let en_common_path = messages_dir.join("en/common.json");
+        let content = fs::read_to_string(&en_common_path).await.unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        // Keys are always overwritten in merge (matching TypeScript behavior)
+        assert_eq!(data["greeting"], "Hello Updated");
+        // New keys should be added
+        assert_eq!(data["new_key"], "New Value");
+    }
+
+    #[tokio::test]
+    async fn test_plan_translations_does_not_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+        let en_common = messages_dir.join("en/common.json");
+        let before = std::fs::read_to_string(&en_common).unwrap();
+
+        let translations: Translations = HashMap::from([
+            ("en".to_string(), HashMap::from([
+                ("greeting".to_string(), "Hello Updated".to_string()),
+                ("user.name".to_string(), "User Name".to_string()),
+                ("new_key".to_string(), "New Value".to_string()),
+            ])),
+            ("ja_JP".to_string(), HashMap::from([("greeting".to_string(), "こんにちは".to_string())])),
+        ]);
+
+        let plan = plan_translations_with_structure(&messages_dir, &original_files, &translations, true)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&en_common).unwrap(), before);
+        assert!(!messages_dir.join("ja_JP").exists());
+
+        let languages: Vec<&str> = plan.languages.iter().map(|l| l.lang.as_str()).collect();
+        assert_eq!(languages, ["en", "zh_CN", "ja_JP"]);
+        let en = plan.changes().find(|c| c.path == en_common).unwrap();
+        assert!(!en.create);
+        assert_eq!(en.added, ["new_key"]);
+        assert_eq!(en.updated, ["greeting"]);
+        assert!(en.removed.is_empty());
+        assert_eq!(plan.files_to_create(), 1);
+
+        let written = plan.apply(None).await.unwrap();
+        assert_eq!(written.len(), 2);
+        assert!(messages_dir.join("ja_JP/sync.json").exists());
+        let files = scan_messages_dir(&messages_dir).await.unwrap().files;
+        let replanned = plan_translations_with_structure(&messages_dir, &files, &translations, true)
+            .await
+            .unwrap();
+        assert!(replanned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_skips_files_with_identical_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        // 缩进和键顺序与写入时不同
+        let content = "{\n    \"title\": \"Title\",\n    \"nested\": {\"b\": \"B\", \"a\": \"A\"}\n}";
+        std::fs::write(messages_dir.join("en/common.json"), content).unwrap();
+        let files = scan_messages_dir(&messages_dir).await.unwrap().files;
+
+        let translations: Translations = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([
+                ("title".to_string(), "Title".to_string()),
+                ("nested.a".to_string(), "A".to_string()),
+                ("nested.b".to_string(), "B".to_string()),
+            ]),
+        )]);
+        let plan = plan_translations_with_structure(&messages_dir, &files, &translations, true).await.unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.files_to_update(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_plan_fails_on_unparseable_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), "{\"title\": ").unwrap();
+        let files = vec![PathBuf::from("en/common.json")];
+
+        let translations: Translations =
+            HashMap::from([("en".to_string(), HashMap::from([("title".to_string(), "Title".to_string())]))]);
+        let error = plan_translations_with_structure(&messages_dir, &files, &translations, true).await.unwrap_err();
+        assert!(error.to_string().contains("common.json"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_plan_translations_with_removals() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations =
+            HashMap::from([("en".to_string(), HashMap::from([("greeting".to_string(), "Hi".to_string())]))]);
+        let removals = BTreeMap::from([
+            ("en".to_string(), vec!["user.name".to_string(), "level.deep".to_string()]),
+            ("zh_CN".to_string(), vec!["user.name".to_string()]),
+        ]);
+
+        let plan = plan_translations_with_removals(&messages_dir, &original_files, &translations, &removals, false)
+            .await
+            .unwrap();
+        let en = plan.changes().find(|c| c.path == messages_dir.join("en/common.json")).unwrap();
+        assert_eq!(en.updated, ["greeting"]);
+        assert_eq!(en.removed, ["user.name"]);
+        assert_eq!(en.keys[1].action, crate::core::sync_plan::KeyAction::Delete);
+        assert_eq!(en.keys[1].old.as_deref(), Some("User Name"));
+        assert_eq!(serde_json::from_str::<Value>(&en.after).unwrap(), json!({"greeting": "Hi"}));
+
+        let zh = plan.changes().find(|c| c.path == messages_dir.join("zh_CN/common.json")).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&zh.after).unwrap(), json!({"greeting": "你好"}));
+        let (_, _, removed) = plan.key_counts();
+        assert_eq!(removed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_with_progress_callback() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello Updated".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        // Track callback invocations
+        use std::sync::atomic::{AtomicUsize, AtomicBool};
+        use std::sync::Arc;
+        let callback_invoked = Arc::new(AtomicBool::new(false));
+        let callback_count = Arc::new(AtomicUsize::new(0));
+        let callback_invoked_clone = callback_invoked.clone();
+        let callback_count_clone = callback_count.clone();
+
+        let callback: ProgressCallback = Box::new(move |event| {
+            callback_invoked_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+            if let ProgressEvent::LanguageStarted { lang, index, total, .. } = event {
+                callback_count_clone.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                assert!(!lang.is_empty());
+                assert!(index > 0);
+                assert!(total > 0);
+            }
+        });
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            Some(callback),
+        ).await.unwrap();
+
+        assert!(!written.is_empty());
+        assert!(callback_invoked.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(callback_count.load(std::sync::atomic::Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_force_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Force Updated".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            true, // Force overwrite
+            None,
+        ).await.unwrap();
+
+        assert!(!written.is_empty());
+
+        let en_common_path = messages_dir.join("en/common.json");
+        let content = fs::read_to_string(&en_common_path).await.unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        // Even existing key should be overwritten with force=true
+        assert_eq!(data["greeting"], "Force Updated");
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_skips_equivalent_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+        let messages_dir = MessagesDir {
+            comparator: Comparator::Trimmed,
+            ..messages_dir
+        };
+
+        let translations: Translations =
+            HashMap::from([("en".to_string(), HashMap::from([("greeting".to_string(), " Hello\n".to_string())]))]);
+        let plan = plan_translations_with_structure(&messages_dir, &original_files, &translations, true)
+            .await
+            .unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_preserves_unrelated_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        // Only update greeting, other keys should remain
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Updated".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        let en_common_path = messages_dir.join("en/common.json");
+        let content = fs::read_to_string(&en_common_path).await.unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        // Original nested key should be preserved
+        assert_eq!(data["user"]["name"], "User Name");
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_creates_language_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        let translations: Translations = [
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let original_files: Vec<PathBuf> = vec![];
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // 新行为：为新语言创建目录和 sync.json 文件
+        assert_eq!(written.len(), 1);
+        let ja_path = messages_dir.join("ja_JP/sync.json");
+        assert_eq!(written[0], ja_path);
+
+        // 验证文件内容
+        let content = fs::read_to_string(&ja_path).await.unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(data["greeting"], "こんにちは");
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_missing_language_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        // Only translations for a language not in original files
+        let translations: Translations = [
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // 新行为：为新语言创建目录和文件
+        assert_eq!(written.len(), 1);
+        let ja_path = messages_dir.join("ja_JP/sync.json");
+        assert_eq!(written[0], ja_path);
+    }
+
+    #[tokio::test]
+    async fn test_write_new_language_files_multiple() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        // 准备多种新语言的翻译
+        let translations: Translations = [
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+                ("farewell".to_string(), "さようなら".to_string()),
+            ].iter().cloned().collect()),
+            ("ko_KR".to_string(), [
+                ("greeting".to_string(), "안녕하세요".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let original_files: Vec<PathBuf> = vec![];
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // 应该为每种新语言创建文件
+        assert_eq!(written.len(), 2);
+
+        // 验证日语文件
+        let ja_path = messages_dir.join("ja_JP/sync.json");
+        assert!(written.contains(&ja_path));
+        let ja_content = fs::read_to_string(&ja_path).await.unwrap();
+        let ja_data: Value = serde_json::from_str(&ja_content).unwrap();
+        assert_eq!(ja_data["greeting"], "こんにちは");
+        assert_eq!(ja_data["farewell"], "さようなら");
+
+        // 验证韩语文件
+        let ko_path = messages_dir.join("ko_KR/sync.json");
+        assert!(written.contains(&ko_path));
+        let ko_content = fs::read_to_string(&ko_path).await.unwrap();
+        let ko_data: Value = serde_json::from_str(&ko_content).unwrap();
+        assert_eq!(ko_data["greeting"], "안녕하세요");
+    }
+
+    #[tokio::test]
+    async fn test_write_new_language_files_mixed_existing_and_new() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        // 混合：已有文件 + 新语言
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello Updated".to_string()),
+            ].iter().cloned().collect()),
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // 应该更新 en 的现有文件，并为 ja_JP 创建新文件
+        assert!(!written.is_empty()); // 至少更新 en 的文件
+        let ja_path = messages_dir.join("ja_JP/sync.json");
+        assert!(written.contains(&ja_path));
+
+        // 验证新创建的文件内容
+        let ja_content = fs::read_to_string(&ja_path).await.unwrap();
+        let ja_data: Value = serde_json::from_str(&ja_content).unwrap();
+        assert_eq!(ja_data["greeting"], "こんにちは");
+    }
+
+    #[tokio::test]
+    async fn test_write_new_language_files_empty_translations() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        // 空翻译不应该创建文件
+        let translations: Translations = [
+            ("empty_lang".to_string(), std::collections::HashMap::new()),
+        ].iter().cloned().collect();
+
+        let original_files: Vec<PathBuf> = vec![];
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // 空翻译不应创建文件
+        assert!(written.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_new_language_files_nested_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        // 嵌套结构的翻译
+        let translations: Translations = [
+            ("de_DE".to_string(), [
+                ("user.name".to_string(), "Hans Müller".to_string()),
+                ("user.profile.email".to_string(), "hans@example.com".to_string()),
+                ("greeting".to_string(), "Guten Tag".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let original_files: Vec<PathBuf> = vec![];
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        assert_eq!(written.len(), 1);
+
+        let de_path = messages_dir.join("de_DE/sync.json");
+        let content = fs::read_to_string(&de_path).await.unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        // 验证嵌套结构被正确还原
+        assert_eq!(data["user"]["name"], "Hans Müller");
+        assert_eq!(data["user"]["profile"]["email"], "hans@example.com");
+        assert_eq!(data["greeting"], "Guten Tag");
+    }
+
+    #[tokio::test]
+    async fn test_verify_written_files_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello Updated".to_string()),
+            ].iter().cloned().collect()),
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        let failures = verify_written_files(&messages_dir, &written, &translations).await;
+        assert!(failures.is_empty(), "unexpected failures: {:?}", failures);
+    }
+
+    #[tokio::test]
+    async fn test_verify_written_files_detects_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, _) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello".to_string()),
+                ("missing.key".to_string(), "Missing".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let written = vec![messages_dir.join("en/common.json")];
+        let failures = verify_written_files(&messages_dir, &written, &translations).await;
+
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("missing.key"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_written_files_invalid_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, _) = create_test_messages_dir(&temp_dir).await;
+
+        let en_common = messages_dir.join("en/common.json");
+        std::fs::write(&en_common, "{ not json").unwrap();
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        let failures = verify_written_files(&messages_dir, &[en_common], &translations).await;
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("not valid JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_write_translations_emits_file_events() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, original_files) = create_test_messages_dir(&temp_dir).await;
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("greeting".to_string(), "Hello Updated".to_string()),
+            ].iter().cloned().collect()),
+            ("ja_JP".to_string(), [
+                ("greeting".to_string(), "こんにちは".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+
+        use std::sync::{Arc, Mutex};
+        let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        let callback: ProgressCallback = Box::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        });
+
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &original_files,
+            &translations,
+            false,
+            Some(callback),
+        ).await.unwrap();
+
+        let events = events.lock().unwrap();
+        let files_written = events
+            .iter()
+            .filter(|e| matches!(e, ProgressEvent::FileWritten { .. }))
+            .count();
+        assert_eq!(files_written, written.len());
+
+        // zh_CN (无翻译)、en 和新语言 ja_JP 各开始/结束一次
+        let started: Vec<(usize, usize)> = events
+            .iter()
+            .filter_map(|e| match e {
+                ProgressEvent::LanguageStarted { index, total, .. } => Some((*index, *total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(started.len(), 3);
+        assert!(started.iter().all(|(_, total)| *total == 3));
+        let finished = events
+            .iter()
+            .filter(|e| matches!(e, ProgressEvent::LanguageFinished { .. }))
+            .count();
+        assert_eq!(finished, 3);
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_write_yaml_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        let en_dir = messages_dir.join("en");
+        std::fs::create_dir_all(&en_dir).unwrap();
+        std::fs::write(en_dir.join("common.yaml"), "greeting: Hello\nbuttons:\n  save: Save\n").unwrap();
+        std::fs::write(en_dir.join("errors.yml"), "").unwrap();
+        std::fs::write(en_dir.join("menu.json"), r#"{"home": "Home"}"#).unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        let en = &result.translations["en"];
+        assert_eq!(en["greeting"], "Hello");
+        assert_eq!(en["buttons.save"], "Save");
+        assert_eq!(en["home"], "Home");
+        assert_eq!(result.files.len(), 3);
+
+        let translations: Translations = [
+            ("en".to_string(), [
+                ("buttons.cancel".to_string(), "Cancel".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+        let common = PathBuf::from("en/common.yaml");
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &[common],
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+        assert_eq!(written, vec![en_dir.join("common.yaml")]);
+
+        // 写回后仍是嵌套的 YAML
+        let content = std::fs::read_to_string(en_dir.join("common.yaml")).unwrap();
+        let data: serde_yaml::Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(data["buttons"]["save"].as_str(), Some("Save"));
+        assert_eq!(data["buttons"]["cancel"].as_str(), Some("Cancel"));
+        assert!(!content.trim_start().starts_with('{'));
+
+        let failures = verify_written_files(&messages_dir, &written, &translations).await;
+        assert!(failures.is_empty(), "unexpected failures: {:?}", failures);
+    }
+
+    #[tokio::test]
+    async fn test_scan_skips_ignored_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        for dir in ["en", "en/node_modules/pkg", "en/generated", "build"] {
+            std::fs::create_dir_all(messages_dir.join(dir)).unwrap();
+        }
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Title"}"#).unwrap();
+        std::fs::write(messages_dir.join("en/node_modules/pkg/x.json"), r#"{"dep": "x"}"#).unwrap();
+        std::fs::write(messages_dir.join("en/generated/out.json"), r#"{"gen": "x"}"#).unwrap();
+        std::fs::write(messages_dir.join("build/common.json"), r#"{"b": "x"}"#).unwrap();
+        std::fs::write(messages_dir.join(".gitignore"), "generated/\nbuild/\n").unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations.len(), 1);
+        assert_eq!(result.translations["en"].len(), 1);
+        assert_eq!(result.files, vec![PathBuf::from("en/common.json")]);
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_write_po_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let messages_dir = MessagesDir::new(temp_dir.path().join("messages"));
+        let de_dir = messages_dir.join("de");
+        std::fs::create_dir_all(&de_dir).unwrap();
+        let original = "msgid \"\"\nmsgstr \"Language: de\\n\"\n\n#: app.js:1\nmsgid \"Save\"\nmsgstr \"Speichern\"\n";
+        std::fs::write(de_dir.join("app.po"), original).unwrap();
+        std::fs::write(de_dir.join("app.pot"), "msgid \"Save\"\nmsgstr \"\"\n").unwrap();
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations["de"]["Save"], "Speichern");
+        assert_eq!(result.files.len(), 2);
+
+        let translations: Translations = [
+            ("de".to_string(), [
+                ("Cancel".to_string(), "Abbrechen".to_string()),
+            ].iter().cloned().collect()),
+        ].iter().cloned().collect();
+        let written = write_translations_with_structure(
+            &messages_dir,
+            &result.files,
+            &translations,
+            false,
+            None,
+        ).await.unwrap();
+
+        // POT 模板不会被写入
+        assert_eq!(written, vec![de_dir.join("app.po")]);
+        let content = std::fs::read_to_string(de_dir.join("app.po")).unwrap();
+        assert!(content.starts_with("msgid \"\"\nmsgstr \"Language: de\\n\"\n\n#: app.js:1\n"));
+        assert!(content.contains("msgid \"Cancel\"\nmsgstr \"Abbrechen\""));
+        assert_eq!(std::fs::read_to_string(de_dir.join("app.pot")).unwrap(), "msgid \"Save\"\nmsgstr \"\"\n");
+
+        let failures = verify_written_files(&messages_dir, &written, &translations).await;
+        assert!(failures.is_empty(), "unexpected failures: {:?}", failures);
+    }
+
+    #[tokio::test]
+    async fn test_scaffold_language() {
+        let temp_dir = TempDir::new().unwrap();
+        let (messages_dir, _) = create_test_messages_dir(&temp_dir).await;
+        std::fs::write(messages_dir.join("en/app.po"), "msgid \"Save\"\nmsgstr \"Save\"\n").unwrap();
+
+        let created = scaffold_language(&messages_dir, "en", "fr", false).unwrap();
+        assert_eq!(created.len(), 3);
+
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        let fr = &result.translations["fr"];
+        // 结构相同，值为空（PO 的空译文不计入）
+        assert_eq!(fr.len(), result.translations["en"].len() - 1);
+        assert!(fr.values().all(|v| v.is_empty()));
+        assert!(messages_dir.join("fr/nested/deep.json").exists());
+        let po = std::fs::read_to_string(messages_dir.join("fr/app.po")).unwrap();
+        assert!(po.contains("Language: fr"));
+
+        // 目标已存在时报错；填充源语言值
+        assert!(scaffold_language(&messages_dir, "en", "fr", true).is_err());
+        scaffold_language(&messages_dir, "en", "de", true).unwrap();
+        let result = scan_messages_dir(&messages_dir).await.unwrap();
+        assert_eq!(result.translations["de"], result.translations["en"]);
+    }
+}
@@ -0,0 +1,524 @@
+//! Secret resolution module
+//!
+//! Resolves the API key from external sources at config load time, so
+//! secrets never have to be stored in `.i18nrc.json`.
+//!
+//! # Supported sources
+//!
+//! - `apiKeyCommand`: runs a shell command (e.g. `op read op://vault/yflow/key`)
+//!   and uses its trimmed stdout as the key
+//! - `secretProvider`: reads the key natively from HashiCorp Vault (KV v1/v2)
+//!   or AWS Secrets Manager
+//!
+//! Provider requests are async and use the same timeouts, `tls.caCertPath`
+//! CAs and proxy settings as the backend client (see
+//! [`http_client_builder`](crate::api::client::http_client_builder)).
+//!
+//! # Example
+//!
+//! ```json
+//! { "secretProvider": { "type": "vault", "path": "secret/data/yflow", "field": "apiKey" } }
+//! { "secretProvider": { "type": "aws-sm", "path": "yflow/api-key", "region": "eu-west-1" } }
+//! ```
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{paths, timestamp};
+
+/// Vault 中默认读取的字段名
+const DEFAULT_VAULT_FIELD: &str = "apiKey";
+
+/// 密钥提供方
+///
+/// 在加载配置时解析为 `apiKey`，适用于不允许将密钥写入文件或环境变量的团队。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum SecretProvider {
+    /// HashiCorp Vault
+    ///
+    /// 地址默认取 `VAULT_ADDR`，令牌取 `VAULT_TOKEN` 或 `~/.vault-token`。
+    #[serde(rename = "vault")]
+    Vault {
+        /// 密钥路径（如 `secret/data/yflow`）
+        path: String,
+        /// 密钥中的字段名，默认 `apiKey`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        /// Vault 地址，未设置时使用 `VAULT_ADDR`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        address: Option<String>,
+    },
+    /// AWS Secrets Manager
+    ///
+    /// 凭证取自 `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`。
+    #[serde(rename = "aws-sm")]
+    AwsSecretsManager {
+        /// 密钥 ID 或 ARN
+        path: String,
+        /// 区域，未设置时使用 `AWS_REGION` / `AWS_DEFAULT_REGION`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        region: Option<String>,
+        /// 当 SecretString 为 JSON 时读取的字段名，未设置时使用整个字符串
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+}
+
+impl SecretProvider {
+    /// 从提供方读取密钥
+    ///
+    /// `http` 应与后端客户端使用相同的超时、CA 证书和代理
+    /// （见 [`http_client_builder`](crate::api::client::http_client_builder)）。
+    pub async fn resolve(&self, http: &reqwest::Client) -> Result<String> {
+        match self {
+            SecretProvider::Vault {
+                path,
+                field,
+                address,
+            } => read_vault_secret(
+                http,
+                path,
+                field.as_deref().unwrap_or(DEFAULT_VAULT_FIELD),
+                address.as_deref(),
+            )
+            .await,
+            SecretProvider::AwsSecretsManager {
+                path,
+                region,
+                field,
+            } => read_aws_secret(http, path, region.as_deref(), field.as_deref()).await,
+        }
+    }
+}
+
+/// 执行外部命令读取密钥
+///
+/// 命令通过系统 shell 执行（Unix 下为 `sh -c`，Windows 下为 `cmd /C`），
+/// 标准输出去除首尾空白后作为密钥返回。
+///
+/// # Arguments
+///
+/// * `command` - 要执行的命令行
+///
+/// # Errors
+///
+/// 命令无法启动、以非零状态退出或输出为空时返回错误。
+/// 错误信息中不包含命令的标准输出，避免密钥泄露。
+pub fn read_command_secret(command: &str) -> Result<String> {
+    let output = shell_command(command)
+        .output()
+        .with_context(|| format!("Failed to run apiKeyCommand: {}", command))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!(
+            "apiKeyCommand exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    let secret = String::from_utf8(output.stdout)
+        .context("apiKeyCommand output is not valid UTF-8")?
+        .trim()
+        .to_string();
+
+    if secret.is_empty() {
+        return Err(anyhow::anyhow!(
+            "apiKeyCommand produced no output: {}",
+            command
+        ));
+    }
+
+    Ok(secret)
+}
+
+/// 构建平台对应的 shell 命令
+pub(crate) fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// 从 Vault 读取密钥
+///
+/// 同时支持 KV v2（`data.data.<field>`）和 KV v1（`data.<field>`）响应格式。
+async fn read_vault_secret(
+    http: &reqwest::Client,
+    path: &str,
+    field: &str,
+    address: Option<&str>,
+) -> Result<String> {
+    let address = address
+        .map(|a| a.to_string())
+        .or_else(|| env::var("VAULT_ADDR").ok())
+        .ok_or_else(|| anyhow::anyhow!("Vault address not set (secretProvider.address or VAULT_ADDR)"))?;
+
+    let token = match env::var("VAULT_TOKEN") {
+        Ok(token) => token,
+        Err(_) => paths::home_dir()
+            .map(|home| home.join(".vault-token"))
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|t| t.trim().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Vault token not found (VAULT_TOKEN or ~/.vault-token)"))?,
+    };
+
+    let url = format!(
+        "{}/v1/{}",
+        address.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+
+    let mut request = http.get(&url).header("X-Vault-Token", &token);
+    if let Ok(namespace) = env::var("VAULT_NAMESPACE") {
+        request = request.header("X-Vault-Namespace", &namespace);
+    }
+
+    let json: serde_json::Value = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| anyhow::anyhow!("Vault request failed for {}: {}", path, e))?
+        .json()
+        .await
+        .context("Failed to parse Vault response as JSON")?;
+
+    let data = json
+        .get("data")
+        .ok_or_else(|| anyhow::anyhow!("Missing 'data' field in Vault response"))?;
+    let secrets = match data.get("data") {
+        Some(inner) if inner.is_object() => inner,
+        _ => data,
+    };
+
+    secrets
+        .get(field)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Field '{}' not found in Vault secret {}", field, path))
+}
+
+/// 从 AWS Secrets Manager 读取密钥
+///
+/// 使用 SigV4 签名直接调用 `GetSecretValue`，无需 AWS CLI 或 SDK。
+async fn read_aws_secret(
+    http: &reqwest::Client,
+    secret_id: &str,
+    region: Option<&str>,
+    field: Option<&str>,
+) -> Result<String> {
+    let region = region
+        .map(|r| r.to_string())
+        .or_else(|| env::var("AWS_REGION").ok())
+        .or_else(|| env::var("AWS_DEFAULT_REGION").ok())
+        .ok_or_else(|| anyhow::anyhow!("AWS region not set (secretProvider.region or AWS_REGION)"))?;
+
+    let credentials = AwsCredentials::from_env()?;
+    let host = format!("secretsmanager.{}.amazonaws.com", region);
+    let body = serde_json::json!({ "SecretId": secret_id }).to_string();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System clock is before UNIX epoch")?
+        .as_secs();
+    let headers = sign_aws_request(&credentials, &region, &host, &body, now);
+
+    let mut request = http.post(format!("https://{}/", host));
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+
+    let json: serde_json::Value = request
+        .body(body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| anyhow::anyhow!("AWS Secrets Manager request failed for {}: {}", secret_id, e))?
+        .json()
+        .await
+        .context("Failed to parse AWS Secrets Manager response as JSON")?;
+
+    let secret_string = json
+        .get("SecretString")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Secret {} has no SecretString", secret_id))?;
+
+    match field {
+        None => Ok(secret_string.trim().to_string()),
+        Some(field) => {
+            let parsed: serde_json::Value = serde_json::from_str(secret_string)
+                .with_context(|| format!("Secret {} is not a JSON object", secret_id))?;
+            parsed
+                .get(field)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| anyhow::anyhow!("Field '{}' not found in secret {}", field, secret_id))
+        }
+    }
+}
+
+/// AWS 凭证
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// 从标准环境变量读取凭证
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            access_key_id: env::var("AWS_ACCESS_KEY_ID")
+                .context("AWS_ACCESS_KEY_ID is not set")?,
+            secret_access_key: env::var("AWS_SECRET_ACCESS_KEY")
+                .context("AWS_SECRET_ACCESS_KEY is not set")?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+/// 为 `GetSecretValue` 请求生成 SigV4 签名后的请求头
+fn sign_aws_request(
+    credentials: &AwsCredentials,
+    region: &str,
+    host: &str,
+    body: &str,
+    unix_secs: u64,
+) -> Vec<(String, String)> {
+    let (amz_date, date_stamp) = amz_timestamp(unix_secs);
+
+    let mut headers: Vec<(String, String)> = vec![
+        ("content-type".to_string(), "application/x-amz-json-1.1".to_string()),
+        ("host".to_string(), host.to_string()),
+        ("x-amz-date".to_string(), amz_date.clone()),
+        ("x-amz-target".to_string(), "secretsmanager.GetSecretValue".to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    headers.sort();
+
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        canonical_headers,
+        signed_headers,
+        hex::encode(Sha256::digest(body.as_bytes()))
+    );
+
+    let scope = format!("{}/{}/secretsmanager/aws4_request", date_stamp, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = aws_signing_key(
+        &credentials.secret_access_key,
+        &date_stamp,
+        region,
+        "secretsmanager",
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    headers.push((
+        "authorization".to_string(),
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    headers.retain(|(name, _)| name != "host");
+    headers
+}
+
+/// 派生 SigV4 签名密钥
+fn aws_signing_key(secret: &str, date_stamp: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date_stamp.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+/// 计算 HMAC-SHA256
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 将 UNIX 时间戳格式化为 (`YYYYMMDDTHHMMSSZ`, `YYYYMMDD`)
+fn amz_timestamp(unix_secs: u64) -> (String, String) {
+    let amz_date = timestamp::format_compact(unix_secs);
+    let date_stamp = amz_date[..8].to_string();
+    (amz_date, date_stamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_provider_deserialize_vault() {
+        let provider: SecretProvider =
+            serde_json::from_str(r#"{"type": "vault", "path": "secret/data/yflow"}"#).unwrap();
+        assert_eq!(
+            provider,
+            SecretProvider::Vault {
+                path: "secret/data/yflow".to_string(),
+                field: None,
+                address: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_secret_provider_deserialize_aws() {
+        let provider: SecretProvider = serde_json::from_str(
+            r#"{"type": "aws-sm", "path": "yflow/key", "region": "eu-west-1", "field": "apiKey"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            provider,
+            SecretProvider::AwsSecretsManager { ref region, .. } if region.as_deref() == Some("eu-west-1")
+        ));
+    }
+
+    #[test]
+    fn test_secret_provider_unknown_type() {
+        let result = serde_json::from_str::<SecretProvider>(r#"{"type": "gcp", "path": "x"}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_amz_timestamp() {
+        // 2015-08-30T12:36:00Z
+        let (amz_date, date_stamp) = amz_timestamp(1_440_938_160);
+        assert_eq!(amz_date, "20150830T123600Z");
+        assert_eq!(date_stamp, "20150830");
+    }
+
+    #[test]
+    fn test_aws_signing_key_reference_vector() {
+        // AWS 文档中的签名密钥派生示例
+        let key = aws_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20150830",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+        );
+    }
+
+    #[test]
+    fn test_sign_aws_request_headers() {
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: Some("token".to_string()),
+        };
+        let headers = sign_aws_request(
+            &credentials,
+            "eu-west-1",
+            "secretsmanager.eu-west-1.amazonaws.com",
+            "{}",
+            1_440_938_160,
+        );
+
+        let auth = headers
+            .iter()
+            .find(|(name, _)| name == "authorization")
+            .map(|(_, value)| value.as_str())
+            .unwrap();
+        assert!(auth.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/eu-west-1/secretsmanager/aws4_request"
+        ));
+        assert!(auth.contains(
+            "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target"
+        ));
+        assert!(headers.iter().any(|(name, _)| name == "x-amz-security-token"));
+        assert!(!headers.iter().any(|(name, _)| name == "host"));
+    }
+
+    #[tokio::test]
+    async fn test_vault_secret_kv_v2() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let body = r#"{"data": {"data": {"apiKey": "vault-key"}, "metadata": {}}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        std::env::set_var("VAULT_TOKEN", "test-token");
+        let secret = read_vault_secret(&reqwest::Client::new(), "secret/data/yflow", "apiKey", Some(&address))
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(secret, "vault-key");
+        assert!(request.starts_with("GET /v1/secret/data/yflow"));
+        assert!(request.to_lowercase().contains("x-vault-token: test-token"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_command_secret_trims_output() {
+        let secret = read_command_secret("printf '  cmd-secret\\n'").unwrap();
+        assert_eq!(secret, "cmd-secret");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_command_secret_failure() {
+        let result = read_command_secret("echo oops >&2; exit 3");
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("oops"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_command_secret_empty_output() {
+        let result = read_command_secret("true");
+        assert!(result.unwrap_err().to_string().contains("no output"));
+    }
+}
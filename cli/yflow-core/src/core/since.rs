@@ -0,0 +1,172 @@
+//! Keys changed since a git revision
+//!
+//! `import --since <ref>` only pushes what changed since a commit or tag:
+//! [`git::changed_files`](super::git::changed_files) lists the changed files
+//! of the messages directory, its `languageDirs` and the overlay directories
+//! with their content at that revision, and a key counts as changed when its
+//! file changed and the key is new there or its value differs from the old
+//! version. Keys of untouched files are skipped without being compared.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::git;
+use super::overlays::Overlay;
+use super::scanner::resolve_file;
+use super::{MessagesDir, ScanResult, Translations};
+
+/// 自 `since` 以来新增或修改过的键：(语言, 键)
+///
+/// 覆盖层中修改过的键也算作修改过的键。
+///
+/// # 参数
+///
+/// * `messages_dir` - messages 目录（位于 git 仓库中）
+/// * `scan` - messages 目录的当前扫描结果（未合并覆盖层）
+/// * `overlays` - 覆盖层及其扫描结果
+/// * `since` - 提交、标签或分支
+///
+/// # Errors
+///
+/// 不在 git 仓库中或 `since` 无效时返回错误
+pub fn changed_keys(
+    messages_dir: &MessagesDir,
+    scan: &ScanResult,
+    overlays: &[Overlay],
+    since: &str,
+) -> Result<HashSet<(String, String)>> {
+    // 1. 要比较的目录：messages 目录、languageDirs 和覆盖层
+    let sources: Vec<(&MessagesDir, &ScanResult)> = std::iter::once((messages_dir, scan))
+        .chain(overlays.iter().map(|overlay| (&overlay.dir, &overlay.scan)))
+        .collect();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for (dir, _) in &sources {
+        dirs.push(dir.to_path_buf());
+        dirs.extend(dir.language_dirs.values().filter(|lang_dir| lang_dir.exists()).cloned());
+    }
+
+    // 2. 修改过的文件 -> 旧版本中的键值
+    let mut previous: HashMap<PathBuf, HashMap<String, String>> = HashMap::new();
+    for file in git::changed_files(messages_dir, &dirs, since)? {
+        let Some(format) = messages_dir.detect(&file.path) else {
+            continue;
+        };
+        let old = match &file.previous {
+            // 旧版本无法解析时视为所有键都已修改
+            Some(content) => format.read_flat(content, &messages_dir.separator).unwrap_or_default(),
+            None => HashMap::new(),
+        };
+        let path = file
+            .path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", file.path.display()))?;
+        previous.insert(path, old);
+    }
+
+    // 3. 修改过的文件中新增或值改变的键
+    let mut changed = HashSet::new();
+    for (dir, scan) in sources {
+        let mut canonical: HashMap<&Path, Option<PathBuf>> = HashMap::new();
+        for ((lang, key), logical) in &scan.key_origins {
+            let path = canonical
+                .entry(logical.as_path())
+                .or_insert_with(|| resolve_file(dir, logical).canonicalize().ok());
+            let Some(old) = path.as_ref().and_then(|path| previous.get(path)) else {
+                continue;
+            };
+            let current = scan.translations.get(lang).and_then(|keys| keys.get(key));
+            if current.is_some() && old.get(key) != current {
+                changed.insert((lang.clone(), key.clone()));
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// 只保留修改过的键
+pub fn retain_changed(translations: Translations, changed: &HashSet<(String, String)>) -> Translations {
+    translations
+        .into_iter()
+        .map(|(lang, keys)| {
+            let keys: HashMap<String, String> = keys
+                .into_iter()
+                .filter(|(key, _)| changed.contains(&(lang.clone(), key.clone())))
+                .collect();
+            (lang, keys)
+        })
+        .filter(|(_, keys)| !keys.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scanner::scan_messages_dir;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let output = Command::new("git").current_dir(dir).args(args).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[tokio::test]
+    async fn test_changed_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        run(dir, &["init", "--quiet"]);
+        run(dir, &["config", "user.name", "Bot"]);
+        run(dir, &["config", "user.email", "bot@example.com"]);
+        let messages_dir = MessagesDir::new(dir.join("messages"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(messages_dir.join("de")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Title", "save": "Save"}"#).unwrap();
+        std::fs::write(messages_dir.join("de/common.json"), r#"{"title": "Titel"}"#).unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "--quiet", "-m", "base"]);
+
+        std::fs::write(
+            messages_dir.join("en/common.json"),
+            r#"{"title": "Title", "save": "Save changes", "cancel": "Cancel"}"#,
+        )
+        .unwrap();
+        std::fs::write(messages_dir.join("en/new.json"), r#"{"extra": "Extra"}"#).unwrap();
+
+        let scan = scan_messages_dir(&messages_dir).await.unwrap();
+        let changed = changed_keys(&messages_dir, &scan, &[], "HEAD").unwrap();
+        let mut keys: Vec<&str> = changed.iter().map(|(_, key)| key.as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["cancel", "extra", "save"]);
+        assert!(changed.iter().all(|(lang, _)| lang == "en"));
+
+        let kept = retain_changed(scan.translations, &changed);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept["en"].len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_changed_keys_in_overlay() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        run(dir, &["init", "--quiet"]);
+        run(dir, &["config", "user.name", "Bot"]);
+        run(dir, &["config", "user.email", "bot@example.com"]);
+        let messages_dir = MessagesDir::new(dir.join("messages"));
+        let overlay_dir = MessagesDir::new(dir.join("brand/acme"));
+        std::fs::create_dir_all(messages_dir.join("en")).unwrap();
+        std::fs::create_dir_all(overlay_dir.join("en")).unwrap();
+        std::fs::write(messages_dir.join("en/common.json"), r#"{"title": "Title"}"#).unwrap();
+        std::fs::write(overlay_dir.join("en/common.json"), r#"{"title": "Acme"}"#).unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "--quiet", "-m", "base"]);
+
+        std::fs::write(overlay_dir.join("en/common.json"), r#"{"title": "Acme Inc."}"#).unwrap();
+
+        let scan = scan_messages_dir(&messages_dir).await.unwrap();
+        let overlays = crate::core::overlays::scan_overlays(&[overlay_dir], false).await.unwrap();
+        let changed = changed_keys(&messages_dir, &scan, &overlays, "HEAD").unwrap();
+        assert_eq!(changed, HashSet::from([("en".to_string(), "title".to_string())]));
+        assert!(changed_keys(&messages_dir, &scan, &[], "HEAD").unwrap().is_empty());
+    }
+}
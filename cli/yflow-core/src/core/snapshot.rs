@@ -0,0 +1,265 @@
+//! Translation snapshots
+//!
+//! A snapshot records the full local translations at a point in time under
+//! `<messagesDir>/.yflow-snapshots/<id>.bin` (packed, see [`super::packed`];
+//! JSON snapshots written by older versions are still read). `export --since-snapshot`
+//! compares the current files against a snapshot to produce a delta, e.g.
+//! as a handoff package for translation vendors who bill per change.
+//!
+//! Snapshot ids are compact UTC timestamps (`20261016T171600Z`), so they
+//! sort chronologically and a plain timestamp can be resolved to the latest
+//! snapshot taken at or before it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{packed, timestamp, Translations};
+
+/// 快照目录名（位于 messages 目录下）
+pub const SNAPSHOT_DIR: &str = ".yflow-snapshots";
+
+/// 翻译快照
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// 快照 ID
+    pub id: String,
+    /// 创建时间（UNIX 秒）
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    /// 快照时的翻译（本地语言代码）
+    pub translations: Translations,
+}
+
+/// 两次快照之间的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDelta {
+    /// 新增或修改的键
+    pub changed: Translations,
+    /// 被删除的键（语言 -> 键列表）
+    pub removed: HashMap<String, Vec<String>>,
+}
+
+impl SnapshotDelta {
+    /// 新增或修改的键数
+    pub fn changed_count(&self) -> usize {
+        self.changed.values().map(|t| t.len()).sum()
+    }
+
+    /// 被删除的键数
+    pub fn removed_count(&self) -> usize {
+        self.removed.values().map(|k| k.len()).sum()
+    }
+}
+
+impl Snapshot {
+    /// 以当前时间创建快照
+    pub fn new(translations: Translations) -> Self {
+        let created_at = timestamp::now();
+        Self {
+            id: timestamp::format_compact(created_at),
+            created_at,
+            translations,
+        }
+    }
+
+    /// 快照目录
+    pub fn dir(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(SNAPSHOT_DIR)
+    }
+
+    /// 保存快照
+    pub fn save(&self, messages_dir: &Path) -> Result<PathBuf> {
+        let dir = Self::dir(messages_dir);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create snapshot directory: {}", dir.display()))?;
+
+        let path = dir.join(format!("{}.{}", self.id, packed::EXTENSION));
+        packed::write(&path, self).with_context(|| format!("Failed to write snapshot: {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// 按 ID 加载快照
+    ///
+    /// 优先读取打包格式，其次读取旧版 JSON 快照。
+    pub fn load(messages_dir: &Path, id: &str) -> Result<Self> {
+        let dir = Self::dir(messages_dir);
+        let path = dir.join(format!("{}.{}", id, packed::EXTENSION));
+        if path.exists() {
+            return packed::read(&path)
+                .with_context(|| format!("Invalid snapshot file: {}", path.display()))?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Snapshot {} was written by an incompatible version of yflow", id)
+                });
+        }
+
+        let path = dir.join(format!("{}.json", id));
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Snapshot not found: {}", id))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid snapshot file: {}", path.display()))
+    }
+
+    /// 列出所有快照 ID（按时间升序）
+    pub fn list(messages_dir: &Path) -> Result<Vec<String>> {
+        let dir = Self::dir(messages_dir);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut ids: Vec<String> = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read snapshot directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let extension = path.extension().and_then(|e| e.to_str())?;
+                if extension != packed::EXTENSION && extension != "json" {
+                    return None;
+                }
+                path.file_stem().and_then(|s| s.to_str()).map(|s| s.to_string())
+            })
+            .collect();
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    /// 解析快照引用
+    ///
+    /// 引用可以是快照 ID，也可以是时间点（见 [`timestamp::parse`]），
+    /// 后者解析为该时间点及之前最近的一个快照。
+    pub fn resolve(messages_dir: &Path, reference: &str) -> Result<Self> {
+        let ids = Self::list(messages_dir)?;
+        if ids.iter().any(|id| id == reference) {
+            return Self::load(messages_dir, reference);
+        }
+
+        let at = timestamp::parse(reference)
+            .with_context(|| format!("Unknown snapshot '{}' and not a valid timestamp", reference))?;
+        let cutoff = timestamp::format_compact(at);
+        let id = ids
+            .iter()
+            .rev()
+            .find(|id| id.as_str() <= cutoff.as_str())
+            .ok_or_else(|| anyhow::anyhow!("No snapshot taken at or before {}", timestamp::format_rfc3339(at)))?;
+        Self::load(messages_dir, id)
+    }
+
+    /// 计算当前翻译相对于快照的差异
+    pub fn delta(&self, current: &Translations) -> SnapshotDelta {
+        let empty = HashMap::new();
+        let mut delta = SnapshotDelta::default();
+
+        for (lang, lang_translations) in current {
+            let base = self.translations.get(lang).unwrap_or(&empty);
+            let changed: HashMap<String, String> = lang_translations
+                .iter()
+                .filter(|(key, value)| base.get(*key) != Some(*value))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            if !changed.is_empty() {
+                delta.changed.insert(lang.clone(), changed);
+            }
+        }
+
+        for (lang, base) in &self.translations {
+            let current_lang = current.get(lang).unwrap_or(&empty);
+            let mut removed: Vec<String> = base
+                .keys()
+                .filter(|key| !current_lang.contains_key(*key))
+                .cloned()
+                .collect();
+            if !removed.is_empty() {
+                removed.sort();
+                delta.removed.insert(lang.clone(), removed);
+            }
+        }
+
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn translations(entries: &[(&str, &str)]) -> Translations {
+        HashMap::from([(
+            "en".to_string(),
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )])
+    }
+
+    fn snapshot_at(id: &str, created_at: u64, entries: &[(&str, &str)]) -> Snapshot {
+        Snapshot {
+            id: id.to_string(),
+            created_at,
+            translations: translations(entries),
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        let snapshot = snapshot_at("s1", 0, &[("same", "A"), ("changed", "Old"), ("gone", "X")]);
+        let current = translations(&[("same", "A"), ("changed", "New"), ("added", "B")]);
+
+        let delta = snapshot.delta(&current);
+        assert_eq!(delta.changed_count(), 2);
+        assert_eq!(delta.changed["en"]["changed"], "New");
+        assert!(delta.changed["en"].contains_key("added"));
+        assert_eq!(delta.removed["en"], vec!["gone".to_string()]);
+    }
+
+    #[test]
+    fn test_save_list_and_resolve() {
+        let temp_dir = TempDir::new().unwrap();
+        snapshot_at("20260101T000000Z", 1_767_225_600, &[("a", "1")])
+            .save(temp_dir.path())
+            .unwrap();
+        snapshot_at("20260201T000000Z", 1_769_904_000, &[("a", "2")])
+            .save(temp_dir.path())
+            .unwrap();
+
+        let ids = Snapshot::list(temp_dir.path()).unwrap();
+        assert_eq!(ids, vec!["20260101T000000Z", "20260201T000000Z"]);
+
+        // 按 ID
+        let snapshot = Snapshot::resolve(temp_dir.path(), "20260201T000000Z").unwrap();
+        assert_eq!(snapshot.translations["en"]["a"], "2");
+
+        // 按时间点：取之前最近的快照
+        let snapshot = Snapshot::resolve(temp_dir.path(), "2026-01-15").unwrap();
+        assert_eq!(snapshot.id, "20260101T000000Z");
+
+        assert!(Snapshot::resolve(temp_dir.path(), "2025-12-01").is_err());
+        assert!(Snapshot::resolve(temp_dir.path(), "unknown").is_err());
+    }
+
+    #[test]
+    fn test_load_legacy_json_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let legacy = snapshot_at("20250101T000000Z", 1_735_689_600, &[("a", "old")]);
+        let dir = Snapshot::dir(temp_dir.path());
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("20250101T000000Z.json"), serde_json::to_string_pretty(&legacy).unwrap()).unwrap();
+        let path = snapshot_at("20260101T000000Z", 1_767_225_600, &[("a", "new")])
+            .save(temp_dir.path())
+            .unwrap();
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("bin"));
+
+        assert_eq!(
+            Snapshot::list(temp_dir.path()).unwrap(),
+            vec!["20250101T000000Z", "20260101T000000Z"]
+        );
+        assert_eq!(Snapshot::load(temp_dir.path(), "20250101T000000Z").unwrap(), legacy);
+        assert_eq!(
+            Snapshot::load(temp_dir.path(), "20260101T000000Z").unwrap().translations["en"]["a"],
+            "new"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Stale translation detection
+//!
+//! With `sourceLanguage` in the config, `.yflow-source-state.json` in the
+//! messages directory records, for every key of every other language, a hash
+//! of the source value and of the translation as they were last seen
+//! together. A translation is *stale* when its source value changed since
+//! and the translation did not: it was written for text that no longer
+//! exists. Editing the translation (or a sync that brings a new one) records
+//! the new pair and the key is current again.
+//!
+//! Keys seen for the first time are recorded as current. The file is meant
+//! to be committed next to the locale files so every checkout agrees on
+//! what is stale; `status` and `validate` report stale keys and
+//! `import --mark-stale` marks them unreviewed on the backend.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::delta::DeltaCache;
+use super::Translations;
+
+/// 源文本状态文件名（位于 messages 目录下）
+pub const STATE_FILENAME: &str = ".yflow-source-state.json";
+
+/// 一个翻译上次确认时的哈希
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceRecord {
+    /// 源语言值的哈希
+    pub source: String,
+    /// 翻译值的哈希
+    pub translation: String,
+}
+
+/// 过期的翻译
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct StaleKey {
+    /// 语言代码（本地）
+    pub lang: String,
+    /// 翻译键
+    pub key: String,
+}
+
+/// 源文本状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceState {
+    /// 记录时的源语言
+    #[serde(default)]
+    pub source: String,
+    /// 语言 -> 键 -> 记录
+    #[serde(default)]
+    pub languages: BTreeMap<String, BTreeMap<String, SourceRecord>>,
+}
+
+impl SourceState {
+    /// 获取状态文件路径
+    pub fn path(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(STATE_FILENAME)
+    }
+
+    /// 加载状态（文件不存在时返回空状态）
+    pub fn load(messages_dir: &Path) -> Result<Self> {
+        let path = Self::path(messages_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read source state: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid source state file: {}", path.display()))
+    }
+
+    /// 保存状态（格式化输出，便于提交和审阅差异）
+    pub fn save(&self, messages_dir: &Path) -> Result<()> {
+        let path = Self::path(messages_dir);
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content + "\n")
+            .with_context(|| format!("Failed to write source state: {}", path.display()))
+    }
+
+    /// 用当前的翻译更新状态，返回过期的翻译
+    ///
+    /// `translations` 必须是所有语言的完整翻译：不再存在的键会从状态中删除。
+    /// 源语言改变时重新开始记录。
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - 源语言（本地语言代码）
+    /// * `translations` - 本地翻译
+    ///
+    /// # Returns
+    ///
+    /// 按语言和键排序的过期翻译
+    pub fn update(&mut self, source: &str, translations: &Translations) -> Vec<StaleKey> {
+        if self.source != source {
+            *self = Self {
+                source: source.to_string(),
+                ..Self::default()
+            };
+        }
+        let empty = HashMap::new();
+        let source_values = translations.get(source).unwrap_or(&empty);
+
+        let mut stale = Vec::new();
+        let mut languages = BTreeMap::new();
+        for (lang, values) in translations.iter().filter(|(lang, _)| *lang != source) {
+            let mut known = self.languages.remove(lang).unwrap_or_default();
+            let mut records = BTreeMap::new();
+            for (key, value) in values {
+                let Some(source_value) = source_values.get(key) else {
+                    continue;
+                };
+                let current = SourceRecord {
+                    source: DeltaCache::hash(source_value.as_bytes()),
+                    translation: DeltaCache::hash(value.as_bytes()),
+                };
+                // 翻译未变化而源文本变化时保留旧记录，直到翻译被更新
+                let record = match known.remove(key) {
+                    Some(record) if record.translation == current.translation && record.source != current.source => {
+                        stale.push(StaleKey {
+                            lang: lang.clone(),
+                            key: key.clone(),
+                        });
+                        record
+                    }
+                    _ => current,
+                };
+                records.insert(key.clone(), record);
+            }
+            if !records.is_empty() {
+                languages.insert(lang.clone(), records);
+            }
+        }
+        self.languages = languages;
+
+        stale.sort();
+        stale
+    }
+}
+
+/// 加载状态、用当前的翻译更新并保存（有变化时），返回过期的翻译
+pub fn track(messages_dir: &Path, source: &str, translations: &Translations) -> Result<Vec<StaleKey>> {
+    let mut state = SourceState::load(messages_dir)?;
+    let before = state.clone();
+    let stale = state.update(source, translations);
+    if state != before {
+        state.save(messages_dir)?;
+    }
+    Ok(stale)
+}
+
+/// 按语言分组过期的翻译及其当前值（用于推送标记）
+pub fn stale_translations(stale: &[StaleKey], translations: &Translations) -> Translations {
+    let mut grouped = Translations::new();
+    for entry in stale {
+        if let Some(value) = translations.get(&entry.lang).and_then(|values| values.get(&entry.key)) {
+            grouped
+                .entry(entry.lang.clone())
+                .or_default()
+                .insert(entry.key.clone(), value.clone());
+        }
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn translations(source: &[(&str, &str)], target: &[(&str, &str)]) -> Translations {
+        let to_map = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        HashMap::from([("en".to_string(), to_map(source)), ("de".to_string(), to_map(target))])
+    }
+
+    fn stale_keys(stale: &[StaleKey]) -> Vec<&str> {
+        stale.iter().map(|entry| entry.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_update_detects_stale_translations() {
+        let mut state = SourceState::default();
+        let first = translations(&[("title", "Home"), ("cta", "Buy")], &[("title", "Start"), ("cta", "Kaufen")]);
+        assert!(state.update("en", &first).is_empty());
+
+        // 源文本变化、翻译未变化：过期
+        let changed = translations(&[("title", "Home page"), ("cta", "Buy")], &[("title", "Start"), ("cta", "Kaufen")]);
+        assert_eq!(stale_keys(&state.update("en", &changed)), vec!["title"]);
+        assert_eq!(stale_keys(&state.update("en", &changed)), vec!["title"]);
+
+        // 翻译更新后不再过期
+        let translated =
+            translations(&[("title", "Home page"), ("cta", "Buy")], &[("title", "Startseite"), ("cta", "Kaufen")]);
+        assert!(state.update("en", &translated).is_empty());
+
+        // 删除的键从状态中移除
+        let removed = translations(&[("title", "Home page")], &[("title", "Startseite")]);
+        state.update("en", &removed);
+        assert_eq!(state.languages["de"].len(), 1);
+
+        let grouped = stale_translations(
+            &[StaleKey {
+                lang: "de".to_string(),
+                key: "title".to_string(),
+            }],
+            &removed,
+        );
+        assert_eq!(grouped["de"]["title"], "Startseite");
+    }
+
+    #[test]
+    fn test_track() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = translations(&[("title", "Home")], &[("title", "Start")]);
+        assert!(track(temp_dir.path(), "en", &first).unwrap().is_empty());
+        assert!(SourceState::path(temp_dir.path()).exists());
+
+        let changed = translations(&[("title", "Home page")], &[("title", "Start")]);
+        assert_eq!(stale_keys(&track(temp_dir.path(), "en", &changed).unwrap()), vec!["title"]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(SourceState::load(temp_dir.path()).unwrap(), SourceState::default());
+
+        let mut state = SourceState::default();
+        state.update("en", &translations(&[("title", "Home")], &[("title", "Start")]));
+        state.save(temp_dir.path()).unwrap();
+        let loaded = SourceState::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.source, "en");
+
+        // 更换源语言时重新记录
+        let mut loaded = loaded;
+        loaded.update("de", &translations(&[("title", "Home page")], &[("title", "Start")]));
+        assert_eq!(loaded.source, "de");
+        assert!(loaded.languages.contains_key("en"));
+        assert!(!loaded.languages.contains_key("de"));
+    }
+}
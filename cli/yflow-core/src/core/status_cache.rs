@@ -0,0 +1,307 @@
+//! Status cache module
+//!
+//! `status` keeps `.yflow-cache/status.json` with the per-language drift it
+//! computed, keyed by a hash of the local and of the backend content of each
+//! language. A repeat run whose local files are unchanged and whose backend
+//! snapshot is younger than `--max-age` answers from the cache without
+//! contacting the backend, which keeps editor integrations that poll status
+//! cheap. Otherwise the backend is fetched again and only languages whose
+//! content hashes changed are compared anew. Changing the `comparator`, or
+//! the backend (`apiUrl` and `projectId`), discards the cached drift, and
+//! every command that writes to the backend deletes the cache
+//! ([`StatusCache::invalidate`]) so the next `status` fetches again.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::delta::DeltaCache;
+use super::compare::Comparator;
+use super::download::CACHE_DIR;
+use super::drift::{compute_drift, LanguageDrift};
+use super::Translations;
+
+/// 缓存文件名（位于 messages 目录的 `.yflow-cache/` 下）
+pub const CACHE_FILENAME: &str = "status.json";
+
+/// 单个语言的缓存统计
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedDrift {
+    /// 本地内容哈希
+    pub local_hash: String,
+    /// 后端内容哈希
+    pub remote_hash: String,
+    /// 计算出的差异
+    pub drift: LanguageDrift,
+}
+
+/// 状态缓存
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCache {
+    /// 后端标识（`<apiUrl>#<projectId>`，见 [`credentials::account`](super::credentials::account)）
+    #[serde(default)]
+    pub backend_id: String,
+    /// 上次获取后端翻译的时间（UNIX 秒）
+    pub fetched_at: u64,
+    /// 计算差异时使用的比较方式
+    #[serde(default)]
+    pub comparator: Comparator,
+    /// 各语言的统计
+    #[serde(default)]
+    pub languages: BTreeMap<String, CachedDrift>,
+}
+
+impl StatusCache {
+    /// 获取缓存文件路径
+    pub fn path(messages_dir: &Path) -> PathBuf {
+        messages_dir.join(CACHE_DIR).join(CACHE_FILENAME)
+    }
+
+    /// 加载缓存（文件不存在或无法解析时返回空缓存）
+    pub fn load(messages_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(messages_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// 保存缓存
+    pub fn save(&self, messages_dir: &Path) -> Result<()> {
+        let path = Self::path(messages_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string(self)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write status cache: {}", path.display()))
+    }
+
+    /// 删除缓存（修改后端后调用，下次 status 重新获取后端翻译）
+    pub fn invalidate(messages_dir: &Path) -> Result<()> {
+        let path = Self::path(messages_dir);
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove status cache: {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 不访问后端即可使用的缓存结果
+    ///
+    /// 后端快照的时间短于 `max_age`，且后端、本地的语言和内容、比较方式与
+    /// 缓存时完全一致时返回按语言排序的差异，否则返回 `None`。
+    pub fn fresh(
+        &self,
+        backend_id: &str,
+        local: &Translations,
+        comparator: Comparator,
+        now: u64,
+        max_age: Duration,
+    ) -> Option<Vec<LanguageDrift>> {
+        if now.saturating_sub(self.fetched_at) >= max_age.as_secs()
+            || self.comparator != comparator
+            || self.backend_id != backend_id
+        {
+            return None;
+        }
+        // 缓存中的语言必须覆盖所有本地语言（只存在于后端的语言本地哈希为空内容）
+        let mut drift = Vec::with_capacity(self.languages.len());
+        for (lang, cached) in &self.languages {
+            let local_hash = local.get(lang).map(language_hash).unwrap_or_else(|| language_hash(&HashMap::new()));
+            if local_hash != cached.local_hash {
+                return None;
+            }
+            drift.push(cached.drift.clone());
+        }
+        if local.keys().any(|lang| !self.languages.contains_key(lang)) {
+            return None;
+        }
+        Some(drift)
+    }
+
+    /// 用新获取的后端翻译计算差异并更新缓存
+    ///
+    /// 本地和后端内容哈希都与缓存一致的语言直接复用缓存的统计
+    /// （比较方式或后端改变时全部重新计算）。
+    ///
+    /// # Returns
+    ///
+    /// 按语言排序的差异，与 [`compute_drift`] 相同
+    pub fn refresh(
+        &mut self,
+        backend_id: &str,
+        local: &Translations,
+        backend: &Translations,
+        comparator: Comparator,
+        now: u64,
+    ) -> Vec<LanguageDrift> {
+        if self.comparator != comparator || self.backend_id != backend_id {
+            self.languages.clear();
+        }
+        let empty = HashMap::new();
+        let mut languages: Vec<&String> = local.keys().chain(backend.keys()).collect();
+        languages.sort();
+        languages.dedup();
+
+        let mut refreshed = BTreeMap::new();
+        for lang in languages {
+            let local_keys = local.get(lang).unwrap_or(&empty);
+            let backend_keys = backend.get(lang).unwrap_or(&empty);
+            let local_hash = language_hash(local_keys);
+            let remote_hash = language_hash(backend_keys);
+
+            let cached = self
+                .languages
+                .remove(lang)
+                .filter(|cached| cached.local_hash == local_hash && cached.remote_hash == remote_hash);
+            let entry = match cached {
+                Some(cached) => cached,
+                None => {
+                    let single = |keys: &HashMap<String, String>| {
+                        Translations::from([(lang.clone(), keys.clone())])
+                    };
+                    let drift = compute_drift(&single(local_keys), &single(backend_keys), comparator)
+                        .pop()
+                        .unwrap_or_default();
+                    CachedDrift {
+                        local_hash,
+                        remote_hash,
+                        drift,
+                    }
+                }
+            };
+            refreshed.insert(lang.clone(), entry);
+        }
+
+        self.backend_id = backend_id.to_string();
+        self.fetched_at = now;
+        self.comparator = comparator;
+        self.languages = refreshed;
+        self.languages.values().map(|cached| cached.drift.clone()).collect()
+    }
+}
+
+/// 语言内容的哈希（与键的顺序无关）
+pub fn language_hash(keys: &HashMap<String, String>) -> String {
+    let mut entries: Vec<(&String, &String)> = keys.iter().collect();
+    entries.sort();
+    let mut content = Vec::new();
+    for (key, value) in entries {
+        content.extend_from_slice(key.as_bytes());
+        content.push(0);
+        content.extend_from_slice(value.as_bytes());
+        content.push(0);
+    }
+    DeltaCache::hash(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BACKEND: &str = "https://api.example.com#1";
+
+    fn translations(entries: &[(&str, &[(&str, &str)])]) -> Translations {
+        entries
+            .iter()
+            .map(|(lang, keys)| {
+                (
+                    lang.to_string(),
+                    keys.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_refresh_matches_compute_drift_and_reuses_entries() {
+        let local = translations(&[("en", &[("a", "A"), ("b", "B")]), ("de", &[("a", "A")])]);
+        let backend = translations(&[("en", &[("a", "A"), ("c", "C")]), ("fr", &[("a", "A")])]);
+
+        let mut cache = StatusCache::default();
+        let drift = cache.refresh(BACKEND, &local, &backend, Comparator::Exact, 100);
+        assert_eq!(drift, compute_drift(&local, &backend, Comparator::Exact));
+        assert_eq!(cache.fetched_at, 100);
+
+        // 标记缓存条目，确认未变化的语言被复用
+        cache.languages.get_mut("en").unwrap().drift.in_sync = 99;
+        let changed_local = translations(&[("en", &[("a", "A"), ("b", "B")]), ("de", &[("a", "X")])]);
+        let drift = cache.refresh(BACKEND, &changed_local, &backend, Comparator::Exact, 200);
+        assert_eq!(drift.iter().find(|d| d.lang == "en").unwrap().in_sync, 99);
+        assert_eq!(drift.iter().find(|d| d.lang == "de").unwrap().local_only, ["a"]);
+
+        // 比较方式改变时不复用
+        let drift = cache.refresh(BACKEND, &changed_local, &backend, Comparator::Trimmed, 300);
+        assert_eq!(drift.iter().find(|d| d.lang == "en").unwrap().in_sync, 1);
+        assert_eq!(cache.comparator, Comparator::Trimmed);
+    }
+
+    #[test]
+    fn test_fresh() {
+        let local = translations(&[("en", &[("a", "A")])]);
+        let backend = translations(&[("en", &[("a", "A")]), ("fr", &[("a", "A")])]);
+        let mut cache = StatusCache::default();
+        let exact = Comparator::Exact;
+        let drift = cache.refresh(BACKEND, &local, &backend, exact, 100);
+
+        let max_age = Duration::from_secs(60);
+        assert_eq!(cache.fresh(BACKEND, &local, exact, 130, max_age), Some(drift));
+        // 过期、本地修改、新增语言或比较方式改变时不可用
+        assert_eq!(cache.fresh(BACKEND, &local, exact, 160, max_age), None);
+        assert_eq!(cache.fresh(BACKEND, &translations(&[("en", &[("a", "B")])]), exact, 130, max_age), None);
+        assert_eq!(
+            cache.fresh(BACKEND, &translations(&[("en", &[("a", "A")]), ("ja", &[("a", "A")])]), exact, 130, max_age),
+            None
+        );
+        assert_eq!(cache.fresh(BACKEND, &local, exact, 100, Duration::ZERO), None);
+        assert_eq!(cache.fresh(BACKEND, &local, Comparator::NormalizedHtml, 130, max_age), None);
+        // 项目或地址改变时不可用
+        assert_eq!(cache.fresh("https://api.example.com#2", &local, exact, 130, max_age), None);
+        assert_eq!(cache.fresh("https://other.example.com#1", &local, exact, 130, max_age), None);
+    }
+
+    #[test]
+    fn test_refresh_discards_other_backend() {
+        let local = translations(&[("en", &[("a", "A")])]);
+        let backend = translations(&[("en", &[("a", "A")])]);
+        let mut cache = StatusCache::default();
+        cache.refresh(BACKEND, &local, &backend, Comparator::Exact, 100);
+        cache.languages.get_mut("en").unwrap().drift.in_sync = 99;
+
+        let drift = cache.refresh("https://api.example.com#2", &local, &backend, Comparator::Exact, 200);
+        assert_eq!(drift[0].in_sync, 1);
+        assert_eq!(cache.backend_id, "https://api.example.com#2");
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let temp_dir = TempDir::new().unwrap();
+        StatusCache::invalidate(temp_dir.path()).unwrap();
+
+        let mut cache = StatusCache::default();
+        cache.refresh(BACKEND, &translations(&[("en", &[("a", "A")])]), &Translations::new(), Comparator::Exact, 5);
+        cache.save(temp_dir.path()).unwrap();
+        StatusCache::invalidate(temp_dir.path()).unwrap();
+        assert!(!StatusCache::path(temp_dir.path()).exists());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = StatusCache::default();
+        cache.refresh(BACKEND, &translations(&[("en", &[("a", "A")])]), &Translations::new(), Comparator::Trimmed, 5);
+        cache.save(temp_dir.path()).unwrap();
+        assert_eq!(StatusCache::load(temp_dir.path()), cache);
+
+        std::fs::write(StatusCache::path(temp_dir.path()), "not json").unwrap();
+        assert_eq!(StatusCache::load(temp_dir.path()), StatusCache::default());
+    }
+}
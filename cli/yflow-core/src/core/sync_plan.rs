@@ -0,0 +1,621 @@
+//! Sync plan module
+//!
+//! `sync` computes every change it is about to make before touching the
+//! disk: for each file, the keys added, updated and removed and its new
+//! content. The same [`SyncPlan`] is printed by `--dry-run`, evaluated by
+//! `--check`, rendered as a unified diff by `--patch` and written by
+//! [`SyncPlan::apply`], so a preview always matches what a real run does.
+//! [`SyncPlan::render_json`] emits it with the old and new value of every
+//! key (`--output json`), for attaching to reviews; after a real run the
+//! report also carries the per-language [`RunSummary`].
+//!
+//! Plans are built by [`super::scanner::plan_translations_with_structure`]
+//! (or [`super::scanner::plan_translations_with_removals`] when `--prune`
+//! also deletes keys).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use super::run_summary::RunSummary;
+use super::{ProgressCallback, ProgressEvent};
+
+/// 差异输出中每处修改前后保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+
+/// 逐行比较的规模上限（旧行数 × 新行数），超过时整段替换
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// 键的修改类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyAction {
+    /// 新增
+    Add,
+    /// 值变化
+    Update,
+    /// 删除
+    Delete,
+}
+
+impl KeyAction {
+    /// 输出中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Add => "add",
+            KeyAction::Update => "update",
+            KeyAction::Delete => "delete",
+        }
+    }
+}
+
+/// 单个键的修改
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyChange {
+    /// 键
+    pub key: String,
+    /// 修改类型
+    pub action: KeyAction,
+    /// 原值（新增时为 `None`）
+    pub old: Option<String>,
+    /// 新值（删除时为 `None`）
+    pub new: Option<String>,
+}
+
+/// 单个文件的修改
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileChange {
+    /// 文件的完整路径
+    pub path: PathBuf,
+    /// 是否为新建文件
+    pub create: bool,
+    /// 新增的键（排序）
+    pub added: Vec<String>,
+    /// 值发生变化的键（排序）
+    pub updated: Vec<String>,
+    /// 不再存在的键（排序，如叶子键变为父级时）
+    pub removed: Vec<String>,
+    /// 每个键的修改及前后的值（按键排序）
+    pub keys: Vec<KeyChange>,
+    /// 修改前的内容（新建文件为空）
+    pub before: String,
+    /// 修改后的内容
+    pub after: String,
+}
+
+impl FileChange {
+    /// 比较文件修改前后的扁平翻译，生成修改记录
+    pub fn new(
+        path: PathBuf,
+        create: bool,
+        before: String,
+        after: String,
+        old: &HashMap<String, String>,
+        new: &HashMap<String, String>,
+    ) -> Self {
+        let mut added: Vec<String> = new.keys().filter(|k| !old.contains_key(*k)).cloned().collect();
+        let mut updated: Vec<String> = new
+            .iter()
+            .filter(|(k, v)| old.get(*k).is_some_and(|old_value| old_value != *v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        let mut removed: Vec<String> = old.keys().filter(|k| !new.contains_key(*k)).cloned().collect();
+        added.sort();
+        updated.sort();
+        removed.sort();
+
+        let value = |map: &HashMap<String, String>, key: &str| map.get(key).cloned();
+        let mut keys: Vec<KeyChange> = added
+            .iter()
+            .map(|key| (key, KeyAction::Add))
+            .chain(updated.iter().map(|key| (key, KeyAction::Update)))
+            .chain(removed.iter().map(|key| (key, KeyAction::Delete)))
+            .map(|(key, action)| KeyChange {
+                key: key.clone(),
+                action,
+                old: value(old, key),
+                new: value(new, key),
+            })
+            .collect();
+        keys.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            path,
+            create,
+            added,
+            updated,
+            removed,
+            keys,
+            before,
+            after,
+        }
+    }
+}
+
+/// 单个语言的修改
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguagePlan {
+    /// 本地语言代码
+    pub lang: String,
+    /// 该语言要写入的文件
+    pub changes: Vec<FileChange>,
+}
+
+/// 同步计划：写入前计算好的所有文件修改
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// 按处理顺序排列的语言（包括没有修改的语言，用于进度显示）
+    pub languages: Vec<LanguagePlan>,
+}
+
+impl SyncPlan {
+    /// 计划是否不修改任何文件
+    pub fn is_empty(&self) -> bool {
+        self.languages.iter().all(|lang| lang.changes.is_empty())
+    }
+
+    /// 所有文件修改
+    pub fn changes(&self) -> impl Iterator<Item = &FileChange> {
+        self.languages.iter().flat_map(|lang| lang.changes.iter())
+    }
+
+    /// 要新建的文件数
+    pub fn files_to_create(&self) -> usize {
+        self.changes().filter(|change| change.create).count()
+    }
+
+    /// 要修改的已有文件数
+    pub fn files_to_update(&self) -> usize {
+        self.changes().filter(|change| !change.create).count()
+    }
+
+    /// 新增、修改和删除的键数
+    pub fn key_counts(&self) -> (usize, usize, usize) {
+        self.changes().fold((0, 0, 0), |(added, updated, removed), change| {
+            (
+                added + change.added.len(),
+                updated + change.updated.len(),
+                removed + change.removed.len(),
+            )
+        })
+    }
+
+    /// 将计划渲染为统一差异格式（unified diff）
+    ///
+    /// 路径相对于 `base`（通常是当前目录），带 `a/`、`b/` 前缀，
+    /// 可以直接用 `git apply` 应用。
+    pub fn render_patch(&self, base: &Path) -> String {
+        let mut patch = String::new();
+        for change in self.changes() {
+            let label = change.path.strip_prefix(base).unwrap_or(&change.path).display().to_string();
+            let old_label = if change.create {
+                "/dev/null".to_string()
+            } else {
+                format!("a/{}", label)
+            };
+            patch.push_str(&format!("--- {}\n+++ b/{}\n", old_label, label));
+            patch.push_str(&unified_diff(&change.before, &change.after));
+        }
+        patch
+    }
+
+    /// 将计划渲染为 JSON
+    ///
+    /// 包含汇总数量，以及每个语言、文件的所有键修改和前后的值；
+    /// 执行后给出 `run` 时还包含各语言的运行汇总（`runSummary`）。
+    /// 文件路径相对于 `base`（通常是 messages 目录）。
+    pub fn render_json(&self, base: &Path, run: Option<&RunSummary>) -> Result<String> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Summary {
+            files_to_create: usize,
+            files_to_update: usize,
+            keys_added: usize,
+            keys_updated: usize,
+            keys_deleted: usize,
+        }
+
+        #[derive(Serialize)]
+        struct File<'a> {
+            path: String,
+            create: bool,
+            keys: &'a [KeyChange],
+        }
+
+        #[derive(Serialize)]
+        struct Language<'a> {
+            lang: &'a str,
+            files: Vec<File<'a>>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Report<'a> {
+            summary: Summary,
+            languages: Vec<Language<'a>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            run_summary: Option<&'a RunSummary>,
+        }
+
+        let (keys_added, keys_updated, keys_deleted) = self.key_counts();
+        let report = Report {
+            summary: Summary {
+                files_to_create: self.files_to_create(),
+                files_to_update: self.files_to_update(),
+                keys_added,
+                keys_updated,
+                keys_deleted,
+            },
+            languages: self
+                .languages
+                .iter()
+                .filter(|language| !language.changes.is_empty())
+                .map(|language| Language {
+                    lang: &language.lang,
+                    files: language
+                        .changes
+                        .iter()
+                        .map(|change| File {
+                            path: change.path.strip_prefix(base).unwrap_or(&change.path).display().to_string(),
+                            create: change.create,
+                            keys: &change.keys,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            run_summary: run,
+        };
+        Ok(serde_json::to_string_pretty(&report).context("Failed to serialize sync plan")? + "\n")
+    }
+
+    /// 执行计划，写入所有文件
+    ///
+    /// 每个语言发出开始/结束事件，每个写入的文件发出 `FileWritten` 事件。
+    ///
+    /// # Returns
+    ///
+    /// 写入的文件路径
+    pub async fn apply(&self, progress_callback: Option<ProgressCallback>) -> Result<Vec<PathBuf>> {
+        let emit = |event: ProgressEvent| {
+            if let Some(callback) = &progress_callback {
+                callback(event);
+            }
+        };
+
+        let mut written = Vec::new();
+        let total = self.languages.len();
+        for (index, language) in self.languages.iter().enumerate() {
+            emit(ProgressEvent::LanguageStarted {
+                lang: language.lang.clone(),
+                index: index + 1,
+                total,
+                items: language.changes.len(),
+            });
+
+            for change in &language.changes {
+                if let Some(parent) = change.path.parent() {
+                    fs::create_dir_all(parent)
+                        .await
+                        .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+                }
+                fs::write(&change.path, &change.after)
+                    .await
+                    .with_context(|| format!("Failed to write file: {}", change.path.display()))?;
+                if change.create {
+                    tracing::info!("Created new language file: {}", change.path.display());
+                }
+
+                emit(ProgressEvent::FileWritten {
+                    lang: language.lang.clone(),
+                    path: change.path.clone(),
+                });
+                written.push(change.path.clone());
+            }
+
+            emit(ProgressEvent::LanguageFinished {
+                lang: language.lang.clone(),
+            });
+        }
+        Ok(written)
+    }
+}
+
+/// 逐行比较的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// 计算将 `old` 变为 `new` 的逐行操作
+///
+/// 先去掉相同的首尾行，中间部分用最长公共子序列比较；
+/// 中间部分过大时整段删除再插入。
+fn line_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<LineOp<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<LineOp> = old[..prefix].iter().map(|line| LineOp::Equal(line)).collect();
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n.saturating_mul(m) <= MAX_DIFF_CELLS {
+        // lengths[i][j] = old_mid[i..] 与 new_mid[j..] 的最长公共子序列长度
+        let width = m + 1;
+        let mut lengths = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lengths[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                ops.push(LineOp::Equal(old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+                ops.push(LineOp::Delete(old_mid[i]));
+                i += 1;
+            } else {
+                ops.push(LineOp::Insert(new_mid[j]));
+                j += 1;
+            }
+        }
+        ops.extend(old_mid[i..].iter().map(|line| LineOp::Delete(line)));
+        ops.extend(new_mid[j..].iter().map(|line| LineOp::Insert(line)));
+    } else {
+        ops.extend(old_mid.iter().map(|line| LineOp::Delete(line)));
+        ops.extend(new_mid.iter().map(|line| LineOp::Insert(line)));
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|line| LineOp::Equal(line)));
+    ops
+}
+
+/// 生成两段文本之间的差异块（不含文件头）
+fn unified_diff(before: &str, after: &str) -> String {
+    let old: Vec<&str> = before.split_inclusive('\n').collect();
+    let new: Vec<&str> = after.split_inclusive('\n').collect();
+    let ops = line_ops(&old, &new);
+
+    // 1. 将修改及其上下文合并为差异块（操作下标范围，含两端）
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, LineOp::Equal(_)) {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES).min(ops.len() - 1);
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 + 1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    // 2. 每个操作之前已经过的旧/新行数
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for op in &ops {
+        positions.push((old_line, new_line));
+        match op {
+            LineOp::Equal(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            LineOp::Delete(_) => old_line += 1,
+            LineOp::Insert(_) => new_line += 1,
+        }
+    }
+
+    // 3. 输出差异块
+    let mut diff = String::new();
+    for (start, end) in hunks {
+        let hunk = &ops[start..=end];
+        let old_count = hunk.iter().filter(|op| !matches!(op, LineOp::Insert(_))).count();
+        let new_count = hunk.iter().filter(|op| !matches!(op, LineOp::Delete(_))).count();
+        let (old_start, new_start) = positions[start];
+        // 范围为空时，起始行号指向其前一行
+        let old_start = if old_count == 0 { old_start } else { old_start + 1 };
+        let new_start = if new_count == 0 { new_start } else { new_start + 1 };
+        diff.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_count, new_start, new_count));
+
+        for op in hunk {
+            let (marker, line) = match op {
+                LineOp::Equal(line) => (' ', line),
+                LineOp::Delete(line) => ('-', line),
+                LineOp::Insert(line) => ('+', line),
+            };
+            diff.push(marker);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LanguageCounts;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn flat(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_file_change_classifies_keys() {
+        let change = FileChange::new(
+            PathBuf::from("en/common.json"),
+            false,
+            String::new(),
+            String::new(),
+            &flat(&[("title", "Old"), ("same", "Same"), ("menu", "Menu")]),
+            &flat(&[("title", "New"), ("same", "Same"), ("menu.open", "Open"), ("added", "Added")]),
+        );
+        assert_eq!(change.added, ["added", "menu.open"]);
+        assert_eq!(change.updated, ["title"]);
+        assert_eq!(change.removed, ["menu"]);
+        let actions: Vec<(&str, KeyAction, Option<&str>, Option<&str>)> = change
+            .keys
+            .iter()
+            .map(|k| (k.key.as_str(), k.action, k.old.as_deref(), k.new.as_deref()))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                ("added", KeyAction::Add, None, Some("Added")),
+                ("menu", KeyAction::Delete, Some("Menu"), None),
+                ("menu.open", KeyAction::Add, None, Some("Open")),
+                ("title", KeyAction::Update, Some("Old"), Some("New")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let change = FileChange::new(
+            PathBuf::from("/project/messages/de/common.json"),
+            false,
+            String::new(),
+            String::new(),
+            &flat(&[("title", "Titel")]),
+            &flat(&[("title", "Überschrift"), ("save", "Speichern")]),
+        );
+        let plan = SyncPlan {
+            languages: vec![
+                LanguagePlan {
+                    lang: "en".to_string(),
+                    changes: Vec::new(),
+                },
+                LanguagePlan {
+                    lang: "de".to_string(),
+                    changes: vec![change],
+                },
+            ],
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&plan.render_json(Path::new("/project/messages"), None).unwrap()).unwrap();
+        assert!(json.get("runSummary").is_none());
+        assert_eq!(json["summary"]["filesToUpdate"], 1);
+        assert_eq!(json["summary"]["keysAdded"], 1);
+        assert_eq!(json["languages"].as_array().unwrap().len(), 1);
+        let file = &json["languages"][0]["files"][0];
+        assert_eq!(file["path"], "de/common.json");
+        assert_eq!(
+            file["keys"][1],
+            serde_json::json!({"key": "title", "action": "update", "old": "Titel", "new": "Überschrift"})
+        );
+
+        // 执行后的报告包含各语言的运行汇总
+        let counts = LanguageCounts {
+            added: 1,
+            updated: 1,
+            unchanged: 0,
+            failed: 0,
+        };
+        let run = RunSummary::new(&[("de".to_string(), counts)].into(), Duration::from_secs(1));
+        let json: serde_json::Value =
+            serde_json::from_str(&plan.render_json(Path::new("/project/messages"), Some(&run)).unwrap()).unwrap();
+        assert_eq!(json["runSummary"]["languages"]["de"]["updated"], 1);
+        assert_eq!(json["runSummary"]["durationMs"], 1000);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let before = "{\n  \"a\": \"1\",\n  \"b\": \"2\",\n  \"c\": \"3\",\n  \"d\": \"4\",\n  \"e\": \"5\"\n}\n";
+        let after = "{\n  \"a\": \"1\",\n  \"b\": \"two\",\n  \"c\": \"3\",\n  \"d\": \"4\",\n  \"e\": \"5\"\n}\n";
+        assert_eq!(
+            unified_diff(before, after),
+            "@@ -1,6 +1,6 @@\n {\n   \"a\": \"1\",\n-  \"b\": \"2\",\n+  \"b\": \"two\",\n   \"c\": \"3\",\n   \"d\": \"4\",\n   \"e\": \"5\"\n"
+        );
+        assert_eq!(unified_diff("same\n", "same\n"), "");
+        assert_eq!(unified_diff("", "{}"), "@@ -0,0 +1,1 @@\n+{}\n\\ No newline at end of file\n");
+    }
+
+    #[test]
+    fn test_unified_diff_splits_distant_changes() {
+        let before: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let after = before.replace("line 2\n", "line two\n").replace("line 19\n", "line nineteen\n");
+        let diff = unified_diff(&before, &after);
+        let headers: Vec<&str> = diff.lines().filter(|line| line.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,5 +1,5 @@", "@@ -16,5 +16,5 @@"]);
+    }
+
+    #[test]
+    fn test_render_patch() {
+        let plan = SyncPlan {
+            languages: vec![LanguagePlan {
+                lang: "de".to_string(),
+                changes: vec![FileChange {
+                    path: PathBuf::from("/project/messages/de/sync.json"),
+                    create: true,
+                    after: "{}\n".to_string(),
+                    ..Default::default()
+                }],
+            }],
+        };
+        assert_eq!(
+            plan.render_patch(Path::new("/project")),
+            "--- /dev/null\n+++ b/messages/de/sync.json\n@@ -0,0 +1,1 @@\n+{}\n"
+        );
+        assert_eq!(plan.files_to_create(), 1);
+        assert_eq!(plan.files_to_update(), 0);
+        assert!(SyncPlan::default().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_writes_files_and_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ja_JP/nested/sync.json");
+        let plan = SyncPlan {
+            languages: vec![
+                LanguagePlan {
+                    lang: "en".to_string(),
+                    changes: Vec::new(),
+                },
+                LanguagePlan {
+                    lang: "ja_JP".to_string(),
+                    changes: vec![FileChange {
+                        path: path.clone(),
+                        create: true,
+                        after: "{\"title\": \"タイトル\"}".to_string(),
+                        ..Default::default()
+                    }],
+                },
+            ],
+        };
+
+        let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        let written = plan
+            .apply(Some(Box::new(move |event| events_clone.lock().unwrap().push(event))))
+            .await
+            .unwrap();
+
+        assert_eq!(written, vec![path.clone()]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"title\": \"タイトル\"}");
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(events[2], ProgressEvent::LanguageStarted { index: 2, total: 2, items: 1, .. }));
+    }
+}
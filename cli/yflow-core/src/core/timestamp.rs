@@ -0,0 +1,152 @@
+//! UTC timestamp helpers
+//!
+//! Small, dependency-free conversions between UNIX seconds and the
+//! calendar formats used in snapshot ids, reports and request signing.
+
+use anyhow::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// UTC 日期时间
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+/// 当前 UNIX 时间（秒）
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// 将 UNIX 时间戳转换为 UTC 日期时间
+pub fn to_datetime(unix_secs: u64) -> DateTime {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // 公历日期换算（Howard Hinnant 的 civil_from_days 算法）
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    DateTime {
+        year,
+        month: month as u32,
+        day: day as u32,
+        hour: (secs_of_day / 3_600) as u32,
+        minute: ((secs_of_day % 3_600) / 60) as u32,
+        second: (secs_of_day % 60) as u32,
+    }
+}
+
+/// 将 UTC 日期时间转换为 UNIX 时间戳
+pub fn from_datetime(dt: DateTime) -> u64 {
+    // 公历日期换算（Howard Hinnant 的 days_from_civil 算法）
+    let year = if dt.month <= 2 { dt.year - 1 } else { dt.year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (dt.month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + dt.day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    (days * 86_400) as u64 + dt.hour as u64 * 3_600 + dt.minute as u64 * 60 + dt.second as u64
+}
+
+/// 格式化为紧凑形式 `YYYYMMDDTHHMMSSZ`
+pub fn format_compact(unix_secs: u64) -> String {
+    let dt = to_datetime(unix_secs);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+    )
+}
+
+/// 格式化为 RFC 3339（`YYYY-MM-DDTHH:MM:SSZ`）
+pub fn format_rfc3339(unix_secs: u64) -> String {
+    let dt = to_datetime(unix_secs);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+    )
+}
+
+/// 解析时间点
+///
+/// 支持 UNIX 秒、`YYYY-MM-DD`、`YYYY-MM-DDTHH:MM:SSZ` 以及紧凑形式
+/// `YYYYMMDDTHHMMSSZ`，均按 UTC 处理。
+pub fn parse(input: &str) -> Result<u64> {
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(secs);
+    }
+
+    let digits: String = input.chars().filter(|c| c.is_ascii_digit()).collect();
+    let valid_shape = input
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '-' | ':' | 'T' | 'Z' | ' '));
+    if !valid_shape || !(digits.len() == 8 || digits.len() == 14) {
+        return Err(anyhow::anyhow!(
+            "Invalid timestamp '{}' (expected UNIX seconds, YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ)",
+            input
+        ));
+    }
+
+    let field = |range: std::ops::Range<usize>| -> u32 {
+        digits.get(range).and_then(|s| s.parse().ok()).unwrap_or(0)
+    };
+    let dt = DateTime {
+        year: field(0..4) as i64,
+        month: field(4..6),
+        day: field(6..8),
+        hour: field(8..10),
+        minute: field(10..12),
+        second: field(12..14),
+    };
+    if !(1..=12).contains(&dt.month) || !(1..=31).contains(&dt.day) || dt.hour > 23 || dt.minute > 59 || dt.second > 60 {
+        return Err(anyhow::anyhow!("Invalid timestamp '{}'", input));
+    }
+
+    Ok(from_datetime(dt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_compact() {
+        // 2015-08-30T12:36:00Z
+        assert_eq!(format_compact(1_440_938_160), "20150830T123600Z");
+        assert_eq!(format_rfc3339(1_440_938_160), "2015-08-30T12:36:00Z");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for secs in [0, 951_782_400, 1_440_938_160, 1_790_000_000] {
+            assert_eq!(from_datetime(to_datetime(secs)), secs);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("1440938160").unwrap(), 1_440_938_160);
+        assert_eq!(parse("2015-08-30T12:36:00Z").unwrap(), 1_440_938_160);
+        assert_eq!(parse("20150830T123600Z").unwrap(), 1_440_938_160);
+        assert_eq!(parse("2015-08-30").unwrap(), 1_440_892_800);
+        assert!(parse("yesterday").is_err());
+        assert!(parse("2015-13-01").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Translation TODO lists
+//!
+//! Collects the keys of one language that are still untranslated — missing
+//! or empty while the source language has a value — together with the
+//! source text and where to find and put them, and renders the result as a
+//! Markdown checklist that a translator can work through in the repository.
+//!
+//! Keys are grouped by the file they belong in: the target-language file
+//! holding the empty value, or for missing keys the counterpart of the
+//! source file that defines them (`en/common.json` → `de/common.json`).
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use super::scanner::{resolve_file, Layout};
+use super::validate::key_lines;
+use super::{MessagesDir, ScanResult};
+
+/// 待翻译的键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoItem {
+    /// 键
+    pub key: String,
+    /// 源语言的文本
+    pub source_text: String,
+    /// 定义该键的源语言文件（磁盘路径）
+    pub source_file: Option<PathBuf>,
+    /// 源语言文件中的行号
+    pub source_line: Option<usize>,
+    /// 翻译应写入的目标语言文件（磁盘路径）
+    pub target_file: Option<PathBuf>,
+    /// 目标语言文件中的行号（键已存在但值为空时）
+    pub target_line: Option<usize>,
+    /// 目标语言中缺少该键（否则为空值）
+    pub missing: bool,
+}
+
+/// 一个语言的待翻译清单
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoList {
+    /// 目标语言
+    pub lang: String,
+    /// 源语言
+    pub source: String,
+    /// 待翻译的键（按目标文件、源文件行号、键排序）
+    pub items: Vec<TodoItem>,
+}
+
+impl TodoList {
+    /// 收集目标语言中未翻译的键
+    ///
+    /// # 参数
+    ///
+    /// * `messages_dir` - messages 目录
+    /// * `scan` - messages 目录的扫描结果
+    /// * `source` - 源语言
+    /// * `lang` - 目标语言
+    pub fn collect(messages_dir: &MessagesDir, scan: &ScanResult, source: &str, lang: &str) -> Result<Self> {
+        let Some(source_keys) = scan.translations.get(source) else {
+            return Err(anyhow::anyhow!("Source language '{}' not found", source));
+        };
+        let empty = HashMap::new();
+        let target_keys = scan.translations.get(lang).unwrap_or(&empty);
+
+        // 每个文件只读取一次行号
+        let mut lines: HashMap<PathBuf, HashMap<String, usize>> = HashMap::new();
+        let mut line_of = |file: &Path, key: &str| -> Result<Option<usize>> {
+            if !lines.contains_key(file) {
+                let located = if file.is_file() { key_lines(file, &messages_dir.separator)? } else { HashMap::new() };
+                lines.insert(file.to_path_buf(), located);
+            }
+            Ok(lines[file].get(key).copied())
+        };
+
+        let mut items = Vec::new();
+        for (key, source_text) in source_keys {
+            if source_text.trim().is_empty() {
+                continue;
+            }
+            let missing = match target_keys.get(key) {
+                Some(value) if !value.trim().is_empty() => continue,
+                Some(_) => false,
+                None => true,
+            };
+
+            let source_origin = scan.origin(source, key);
+            let target_origin = if missing {
+                source_origin.and_then(|origin| counterpart(messages_dir, origin, lang))
+            } else {
+                scan.origin(lang, key).map(Path::to_path_buf)
+            };
+            let source_file = source_origin.map(|origin| resolve_file(messages_dir, origin));
+            let target_file = target_origin.map(|origin| resolve_file(messages_dir, &origin));
+
+            let source_line = match &source_file {
+                Some(file) => line_of(file, key)?,
+                None => None,
+            };
+            let target_line = match &target_file {
+                Some(file) if !missing => line_of(file, key)?,
+                _ => None,
+            };
+            items.push(TodoItem {
+                key: key.clone(),
+                source_text: source_text.clone(),
+                source_file,
+                source_line,
+                target_file,
+                target_line,
+                missing,
+            });
+        }
+        items.sort_by(|a, b| {
+            a.target_file
+                .cmp(&b.target_file)
+                .then_with(|| a.source_file.cmp(&b.source_file))
+                .then_with(|| a.source_line.cmp(&b.source_line))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+
+        Ok(Self {
+            lang: lang.to_string(),
+            source: source.to_string(),
+            items,
+        })
+    }
+
+    /// 渲染为 Markdown 清单
+    ///
+    /// 按目标文件分组，每个键一个复选框，附带源文本和所在位置。
+    ///
+    /// # 参数
+    ///
+    /// * `generated_at` - 生成时间（RFC 3339），写入标题下方
+    pub fn render_markdown(&self, generated_at: &str) -> String {
+        let mut out = format!("# Untranslated keys: {}\n\n", self.lang);
+        out.push_str(&format!(
+            "Source language: `{}` · {} key(s) · generated {}\n",
+            self.source,
+            self.items.len(),
+            generated_at
+        ));
+        if self.items.is_empty() {
+            out.push_str("\nNothing to translate.\n");
+            return out;
+        }
+        out.push_str("\nCheck off each key once its translation is in place.\n");
+
+        let mut groups: BTreeMap<Option<&Path>, Vec<&TodoItem>> = BTreeMap::new();
+        for item in &self.items {
+            groups.entry(item.target_file.as_deref()).or_default().push(item);
+        }
+        for (file, items) in groups {
+            match file {
+                Some(file) => out.push_str(&format!("\n## {}\n\n", file.display())),
+                None => out.push_str("\n## Unknown file\n\n"),
+            }
+            for item in items {
+                let mut line = format!("- [ ] `{}`", item.key);
+                if item.missing {
+                    line.push_str(" — missing");
+                } else {
+                    line.push_str(" — empty");
+                    if let Some(target_line) = item.target_line {
+                        line.push_str(&format!(" (line {})", target_line));
+                    }
+                }
+                if let Some(source_file) = &item.source_file {
+                    line.push_str(&format!(" · source: {}", location(source_file, item.source_line)));
+                }
+                out.push_str(&line);
+                out.push('\n');
+                for text_line in item.source_text.lines() {
+                    out.push_str(&format!("  > {}\n", text_line));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// `path:line` 形式的位置
+fn location(path: &Path, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{}", path.display(), line),
+        None => path.display().to_string(),
+    }
+}
+
+/// 源语言文件在目标语言中对应的文件（逻辑路径）
+///
+/// 目录布局替换第一级目录（`en/common.json` → `de/common.json`），
+/// 平铺布局替换文件名（`en.json` → `de.json`）。
+fn counterpart(messages_dir: &MessagesDir, origin: &Path, lang: &str) -> Option<PathBuf> {
+    let mut components = origin.components();
+    let Some(Component::Normal(_)) = components.next() else {
+        return None;
+    };
+    if messages_dir.layout == Layout::FlatFiles {
+        let extension = origin.extension()?.to_string_lossy();
+        return Some(PathBuf::from(format!("{}.{}", lang, extension)));
+    }
+    Some(Path::new(lang).join(components.as_path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scanner::scan_messages_dir;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_collect_and_render() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = &MessagesDir::new(temp_dir.path());
+        write(
+            dir,
+            "en/common.json",
+            "{\n  \"title\": \"Welcome\",\n  \"user\": {\n    \"name\": \"Name\",\n    \"bio\": \"Line one\\nLine two\"\n  }\n}",
+        );
+        write(dir, "en/errors.json", "{\n  \"errors\": {\n    \"notFound\": \"Not found\"\n  }\n}");
+        write(dir, "de/common.json", "{\n  \"title\": \"Willkommen\",\n  \"user\": {\n    \"name\": \"\"\n  }\n}");
+
+        let scan = scan_messages_dir(dir).await.unwrap();
+        let todo = TodoList::collect(dir, &scan, "en", "de").unwrap();
+        let keys: Vec<&str> = todo.items.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(keys, ["user.name", "user.bio", "errors.notFound"]);
+
+        let name = &todo.items[0];
+        assert!(!name.missing);
+        assert_eq!(name.target_file, Some(dir.join("de/common.json")));
+        assert_eq!(name.target_line, Some(4));
+        assert_eq!(name.source_line, Some(4));
+
+        let not_found = &todo.items[2];
+        assert!(not_found.missing);
+        assert_eq!(not_found.target_file, Some(dir.join("de/errors.json")));
+        assert_eq!(not_found.source_file, Some(dir.join("en/errors.json")));
+        assert_eq!(not_found.source_line, Some(3));
+
+        let markdown = todo.render_markdown("2026-10-16T12:00:00Z");
+        assert!(markdown.starts_with("# Untranslated keys: de\n"));
+        assert!(markdown.contains("· 3 key(s) ·"));
+        assert!(markdown.contains(&format!("\n## {}\n", dir.join("de/errors.json").display())));
+        assert!(markdown.contains(&format!(
+            "- [ ] `errors.notFound` — missing · source: {}:3\n  > Not found\n",
+            dir.join("en/errors.json").display()
+        )));
+        assert!(markdown.contains("- [ ] `user.name` — empty (line 4)"));
+        assert!(markdown.contains("  > Line one\n  > Line two\n"));
+
+        assert!(TodoList::collect(dir, &scan, "fr", "de").is_err());
+        let complete = TodoList::collect(dir, &scan, "en", "en").unwrap();
+        assert!(complete.render_markdown("").contains("Nothing to translate."));
+    }
+}
@@ -0,0 +1,573 @@
+//! Locale file validation
+//!
+//! Checks every translation file below the messages directory for problems
+//! that would otherwise only surface at runtime or during an import:
+//!
+//! - files that fail to parse (with the parser's line number)
+//! - keys defined more than once after flattening, either inside one file
+//!   (`{"a.b": ..., "a": {"b": ...}}`, literal duplicate JSON keys) or in two
+//!   files of the same language
+//! - empty values
+//! - placeholders that differ from the source language
+//! - stale translations, whose source text changed since they were
+//!   translated (given by the caller, see [`super::stale`])
+//! - with `"dialect": "i18next"`, plural keys missing a form the language
+//!   needs (see [`super::i18next`])
+//!
+//! Findings carry the file and, where it can be located, the line of the key.
+
+use anyhow::Result;
+use regex::Regex;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::i18next::{self, Dialect};
+use super::placeholders;
+use super::scanner::{file_language, walk_files, FileFormat, Layout};
+use super::stale::StaleKey;
+use super::MessagesDir;
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FindingKind {
+    /// 文件无法解析
+    InvalidFile,
+    /// 展平后重复的键
+    DuplicateKey,
+    /// 空值
+    EmptyValue,
+    /// 与源语言的占位符不一致
+    PlaceholderMismatch,
+    /// 源文本变化后未更新的翻译
+    StaleTranslation,
+    /// 缺少语言需要的复数形式（i18next）
+    IncompletePlural,
+}
+
+impl FindingKind {
+    /// 输出中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            FindingKind::InvalidFile => "invalid-file",
+            FindingKind::DuplicateKey => "duplicate-key",
+            FindingKind::EmptyValue => "empty-value",
+            FindingKind::PlaceholderMismatch => "placeholder-mismatch",
+            FindingKind::StaleTranslation => "stale-translation",
+            FindingKind::IncompletePlural => "incomplete-plural",
+        }
+    }
+}
+
+/// 校验发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// 文件路径（相对于 messages 目录）
+    pub file: PathBuf,
+    /// 行号（从 1 开始，无法定位时为 None）
+    pub line: Option<usize>,
+    /// 问题类型
+    pub kind: FindingKind,
+    /// 相关的键
+    pub key: Option<String>,
+    /// 描述
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        write!(f, ": {}", self.kind.name())?;
+        if let Some(key) = &self.key {
+            write!(f, ": {}", key)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// 文件中的 (键, 值)，保留文件顺序与重复项
+type Entries = Vec<(String, String)>;
+
+/// 一个键在某个文件中的定义
+#[derive(Debug, Clone)]
+struct Definition {
+    /// 文件路径（相对于 messages 目录）
+    file: PathBuf,
+    /// 行号
+    line: Option<usize>,
+    /// 值
+    value: String,
+}
+
+/// 校验 messages 目录下的所有翻译文件
+///
+/// # Arguments
+///
+/// * `messages_dir` - messages 目录
+/// * `languages` - 只检查这些语言（为空时检查全部）
+/// * `source` - 占位符比较的源语言（None 时使用键最多的语言）
+/// * `stale` - 过期的翻译（报告在定义它们的文件中）
+///
+/// # Returns
+///
+/// 按文件、行号排序的问题列表
+pub fn validate_messages_dir(
+    messages_dir: &MessagesDir,
+    languages: &[String],
+    source: Option<&str>,
+    stale: &[StaleKey],
+) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    // 语言 -> 键 -> 定义
+    let mut definitions: HashMap<String, HashMap<String, Definition>> = HashMap::new();
+
+    for path in walk_files(messages_dir)? {
+        let Some(format) = FileFormat::from_path(&path) else {
+            continue;
+        };
+        let relative = path.strip_prefix(messages_dir).unwrap_or(&path).to_path_buf();
+        // 只检查语言目录下的文件（平铺布局下为以语言命名的文件）
+        let nested = relative.components().count() > 1;
+        let lang = match file_language(messages_dir, &relative) {
+            Some(lang) if nested || messages_dir.layout == Layout::FlatFiles => lang,
+            _ => continue,
+        };
+        if !languages.is_empty() && !languages.contains(&lang) {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let entries = match read_entries(format, &content, &messages_dir.separator) {
+            Ok(entries) => entries,
+            Err((line, message)) => {
+                findings.push(Finding {
+                    file: relative,
+                    line,
+                    kind: FindingKind::InvalidFile,
+                    key: None,
+                    message: format!("invalid {}: {}", format.name(), message),
+                });
+                continue;
+            }
+        };
+        // POT 模板的值总是空的，只检查能否解析
+        if format == FileFormat::Pot {
+            continue;
+        }
+
+        let lines = locate_lines(format, &content, &entries);
+        let lang_definitions = definitions.entry(lang).or_default();
+        let mut seen_in_file: HashMap<&str, Option<usize>> = HashMap::new();
+        for ((key, value), line) in entries.iter().zip(lines) {
+            if let Some(first_line) = seen_in_file.get(key.as_str()) {
+                findings.push(Finding {
+                    file: relative.clone(),
+                    line,
+                    kind: FindingKind::DuplicateKey,
+                    key: Some(key.clone()),
+                    message: match first_line {
+                        Some(first_line) => format!("already defined on line {}", first_line),
+                        None => "already defined in this file".to_string(),
+                    },
+                });
+                continue;
+            }
+            seen_in_file.insert(key, line);
+
+            if let Some(other) = lang_definitions.get(key) {
+                findings.push(Finding {
+                    file: relative.clone(),
+                    line,
+                    kind: FindingKind::DuplicateKey,
+                    key: Some(key.clone()),
+                    message: format!("already defined in {}", other.file.display()),
+                });
+                continue;
+            }
+
+            if value.trim().is_empty() {
+                findings.push(Finding {
+                    file: relative.clone(),
+                    line,
+                    kind: FindingKind::EmptyValue,
+                    key: Some(key.clone()),
+                    message: "value is empty".to_string(),
+                });
+            }
+
+            lang_definitions.insert(
+                key.clone(),
+                Definition {
+                    file: relative.clone(),
+                    line,
+                    value: value.clone(),
+                },
+            );
+        }
+    }
+
+    findings.extend(placeholder_findings(&definitions, source, &messages_dir.placeholder_patterns));
+    if messages_dir.dialect == Dialect::I18next {
+        findings.extend(plural_findings(&definitions));
+    }
+    findings.extend(stale.iter().filter_map(|entry| {
+        let definition = definitions.get(&entry.lang)?.get(&entry.key)?;
+        Some(Finding {
+            file: definition.file.clone(),
+            line: definition.line,
+            kind: FindingKind::StaleTranslation,
+            key: Some(entry.key.clone()),
+            message: "source text changed since this was translated".to_string(),
+        })
+    }));
+    findings.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    Ok(findings)
+}
+
+/// 文件中每个键所在的行号（从 1 开始）
+///
+/// 同一个键定义多次时取第一次出现的行；无法定位的键不在结果中。
+/// 不支持的格式返回空表。非 JSON 文件的键按 `separator` 展平。
+///
+/// # Errors
+///
+/// 文件无法读取或解析时返回错误
+pub fn key_lines(path: &Path, separator: &str) -> Result<HashMap<String, usize>> {
+    let Some(format) = FileFormat::from_path(path) else {
+        return Ok(HashMap::new());
+    };
+    let content = std::fs::read_to_string(path)?;
+    let entries = read_entries(format, &content, separator)
+        .map_err(|(_, message)| anyhow::anyhow!("invalid {} {}: {}", format.name(), path.display(), message))?;
+    let lines = locate_lines(format, &content, &entries);
+
+    let mut result = HashMap::new();
+    for ((key, _), line) in entries.into_iter().zip(lines) {
+        if let Some(line) = line {
+            result.entry(key).or_insert(line);
+        }
+    }
+    Ok(result)
+}
+
+/// 比较各语言与源语言的占位符
+fn placeholder_findings(
+    definitions: &HashMap<String, HashMap<String, Definition>>,
+    source: Option<&str>,
+    patterns: &[Regex],
+) -> Vec<Finding> {
+    let source = match source {
+        Some(source) => source.to_string(),
+        None => {
+            let mut languages: Vec<(&String, usize)> =
+                definitions.iter().map(|(lang, keys)| (lang, keys.len())).collect();
+            languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            match languages.first() {
+                Some((lang, _)) => lang.to_string(),
+                None => return Vec::new(),
+            }
+        }
+    };
+    let Some(source_definitions) = definitions.get(&source) else {
+        return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+    for (lang, lang_definitions) in definitions {
+        if *lang == source {
+            continue;
+        }
+        for (key, definition) in lang_definitions {
+            let Some(source_definition) = source_definitions.get(key) else {
+                continue;
+            };
+            // 空值已单独报告
+            if definition.value.trim().is_empty() {
+                continue;
+            }
+            if let Some(message) = placeholders::compare_with(&source_definition.value, &definition.value, patterns) {
+                findings.push(Finding {
+                    file: definition.file.clone(),
+                    line: definition.line,
+                    kind: FindingKind::PlaceholderMismatch,
+                    key: Some(key.clone()),
+                    message: format!("{} (compared to {})", message, source),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// 检查各语言的 i18next 复数形式是否完整
+///
+/// 报告在组中第一个已有形式的位置。
+fn plural_findings(definitions: &HashMap<String, HashMap<String, Definition>>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (lang, lang_definitions) in definitions {
+        for gap in i18next::plural_gaps(lang, lang_definitions.keys().map(String::as_str)) {
+            let definition = &lang_definitions[&gap.first];
+            findings.push(Finding {
+                file: definition.file.clone(),
+                line: definition.line,
+                kind: FindingKind::IncompletePlural,
+                key: Some(gap.key),
+                message: format!("missing plural form(s) {} required by {}", gap.missing.join(", "), lang),
+            });
+        }
+    }
+    findings
+}
+
+/// 读取文件中的所有键值（保留重复项与文件中的顺序）
+///
+/// 失败时返回 `(行号, 错误描述)`。
+fn read_entries(
+    format: FileFormat,
+    content: &str,
+    separator: &str,
+) -> std::result::Result<Entries, (Option<usize>, String)> {
+    match format {
+        FileFormat::Json => {
+            let mut entries = Vec::new();
+            let mut deserializer = serde_json::Deserializer::from_str(content);
+            EntrySeed {
+                path: String::new(),
+                entries: &mut entries,
+            }
+            .deserialize(&mut deserializer)
+            .and_then(|_| deserializer.end())
+            .map_err(|e| (Some(e.line()), e.to_string()))?;
+            Ok(entries)
+        }
+        _ => {
+            let mut entries: Vec<(String, String)> = format
+                .read_flat(content, separator)
+                .map_err(|e| (None, format!("{:#}", e)))?
+                .into_iter()
+                .collect();
+            entries.sort();
+            Ok(entries)
+        }
+    }
+}
+
+/// 定位每个键所在的行
+///
+/// JSON 的键按文件顺序排列，从上一个键的位置继续向下查找最后一段键名；
+/// 其他格式查找第一次出现的位置。
+fn locate_lines(format: FileFormat, content: &str, entries: &[(String, String)]) -> Vec<Option<usize>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut cursor = 0;
+    entries
+        .iter()
+        .map(|(key, _)| {
+            let leaf = key.rsplit('.').next().unwrap_or(key);
+            // 键名本身可能包含点号（如 "user.title"），依次尝试各个后缀
+            let suffixes: Vec<&str> = std::iter::once(key.as_str())
+                .chain(key.match_indices('.').map(|(i, _)| &key[i + 1..]))
+                .collect();
+            let matches = |line: &str| match format {
+                FileFormat::Json => suffixes.iter().any(|suffix| {
+                    let quoted = format!("\"{}\"", suffix);
+                    line.find(&quoted)
+                        .is_some_and(|i| line[i + quoted.len()..].trim_start().starts_with(':'))
+                }),
+                FileFormat::Yaml => {
+                    let trimmed = line.trim_start();
+                    trimmed.starts_with(&format!("{}:", leaf))
+                        || trimmed.starts_with(&format!("\"{}\":", leaf))
+                        || trimmed.starts_with(&format!("'{}':", leaf))
+                }
+                FileFormat::Po | FileFormat::Pot => line.starts_with(&format!("msgid \"{}\"", key)),
+            };
+            let start = if format == FileFormat::Json { cursor } else { 0 };
+            let found = (start..lines.len()).find(|&i| matches(lines[i]));
+            if let Some(i) = found {
+                if format == FileFormat::Json {
+                    cursor = i + 1;
+                }
+            }
+            found.map(|i| i + 1)
+        })
+        .collect()
+}
+
+/// 收集 JSON 中所有字符串值的反序列化器
+///
+/// 与 `serde_json::Value` 不同，同一对象中重复的键会全部保留。
+struct EntrySeed<'a> {
+    /// 当前的键路径
+    path: String,
+    /// 收集的 (键, 值)
+    entries: &'a mut Vec<(String, String)>,
+}
+
+impl<'de> DeserializeSeed<'de> for EntrySeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntrySeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        let entries = self.entries;
+        while let Some(key) = map.next_key::<String>()? {
+            let path = if self.path.is_empty() {
+                key
+            } else {
+                format!("{}.{}", self.path, key)
+            };
+            map.next_value_seed(EntrySeed {
+                path,
+                entries: &mut *entries,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn visit_str<E>(self, value: &str) -> std::result::Result<(), E> {
+        self.entries.push((self.path, value.to_string()));
+        Ok(())
+    }
+
+    // 与展平规则一致：忽略数组、数字、布尔值和 null
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, _: bool) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<(), E> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, relative: &str, content: &str) {
+        let path = dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_read_entries_keeps_duplicates() {
+        let content = r#"{"a.b": "1", "a": {"b": "2"}, "c": "x", "c": "y", "n": 1, "l": ["z"]}"#;
+        let entries = read_entries(FileFormat::Json, content, ".").unwrap();
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["a.b", "a.b", "c", "c"]);
+    }
+
+    #[test]
+    fn test_read_entries_reports_line() {
+        let content = "{\n  \"a\": \"1\",\n  \"b\": \n}";
+        let (line, _) = read_entries(FileFormat::Json, content, ".").unwrap_err();
+        assert_eq!(line, Some(4));
+    }
+
+    #[test]
+    fn test_validate_messages_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = &MessagesDir::new(temp_dir.path());
+        write(
+            dir,
+            "en/common.json",
+            "{\n  \"greeting\": \"Hello {name}\",\n  \"user\": {\n    \"title\": \"Profile\"\n  }\n}",
+        );
+        write(
+            dir,
+            "zh_CN/common.json",
+            "{\n  \"greeting\": \"你好\",\n  \"user\": {\n    \"title\": \"\"\n  },\n  \"user.title\": \"资料\"\n}",
+        );
+        write(dir, "zh_CN/extra.json", "{\"greeting\": \"嗨 {name}\"}");
+        write(dir, "de/broken.json", "{\n  \"a\": \"1\",\n}");
+
+        let findings = validate_messages_dir(dir, &[], Some("en"), &[]).unwrap();
+        let summary: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+
+        assert!(summary[0].starts_with("de/broken.json:3: invalid-file"));
+        assert!(summary.contains(&"zh_CN/common.json:2: placeholder-mismatch: greeting: missing {name} (compared to en)".to_string()));
+        assert!(summary.contains(&"zh_CN/common.json:4: empty-value: user.title: value is empty".to_string()));
+        assert!(summary.contains(&"zh_CN/common.json:6: duplicate-key: user.title: already defined on line 4".to_string()));
+        assert!(summary.contains(&"zh_CN/extra.json:1: duplicate-key: greeting: already defined in zh_CN/common.json".to_string()));
+        assert_eq!(findings.len(), 5);
+    }
+
+    #[test]
+    fn test_validate_clean_and_language_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = &MessagesDir::new(temp_dir.path());
+        write(dir, "en/common.yaml", "title: Title\nbody: \"Hi {name}\"\n");
+        write(dir, "de/common.yaml", "title: Titel\nbody: \"Hallo\"\n");
+
+        assert_eq!(validate_messages_dir(dir, &["en".to_string()], None, &[]).unwrap(), Vec::new());
+
+        let findings = validate_messages_dir(dir, &[], Some("en"), &[]).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, Some(2));
+        assert_eq!(findings[0].kind, FindingKind::PlaceholderMismatch);
+    }
+
+    #[test]
+    fn test_validate_i18next_plurals() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = &MessagesDir::new(temp_dir.path());
+        write(dir, "en/common.json", "{\n  \"item_one\": \"{{count}} item\",\n  \"item_other\": \"{{count}} items\"\n}");
+        write(
+            dir,
+            "ru/common.json",
+            "{\n  \"item_one\": \"{{count}} товар\",\n  \"item_few\": \"{{count}} товара\",\n  \"item_other\": \"{{count}} товара\"\n}",
+        );
+
+        // 默认约定下不检查
+        assert!(validate_messages_dir(dir, &[], Some("en"), &[]).unwrap().is_empty());
+
+        let i18next_dir = MessagesDir {
+            dialect: Dialect::I18next,
+            ..MessagesDir::new(temp_dir.path())
+        };
+        let findings = validate_messages_dir(&i18next_dir, &[], Some("en"), &[]).unwrap();
+        let summary: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            summary,
+            ["ru/common.json:3: incomplete-plural: item: missing plural form(s) many required by ru"]
+        );
+    }
+}
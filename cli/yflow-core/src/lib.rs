@@ -0,0 +1,70 @@
+//! YFlow core library
+//!
+//! The logic behind the `yflow` CLI, usable from other Rust tools (a
+//! `build.rs` key checker, editor integrations, custom sync jobs) without
+//! shelling out to the binary:
+//!
+//! - [`scan_messages_dir`] reads a messages directory (JSON, YAML, PO) into
+//!   flat `{language: {key: value}}` [`Translations`]
+//! - [`flatten_object`] / [`unflatten_object`] convert between nested locale
+//!   files and dotted keys
+//! - [`LanguageMapper`] translates between local and backend language codes
+//! - [`APIClient`] talks to the YFlow backend (retries, rate limiting, TLS
+//!   pinning and request signing included)
+//! - [`load_config`] reads `.i18nrc.json` / `.toml` / `.yaml` with the same
+//!   rules as the CLI
+//!
+//! Everything else is available through the [`core`] and [`api`] modules.
+//!
+//! # Example
+//!
+//! Checking that every key used by the application exists in English:
+//!
+//! ```no_run
+//! use yflow_core::{scan_messages_dir, MessagesDir};
+//!
+//! # async fn check() -> anyhow::Result<()> {
+//! let scan = scan_messages_dir(&MessagesDir::new("messages")).await?;
+//! let en = scan.translations.get("en").cloned().unwrap_or_default();
+//! for key in ["home.title", "checkout.pay"] {
+//!     assert!(en.contains_key(key), "missing translation key: {}", key);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Pushing local translations to the backend:
+//!
+//! ```no_run
+//! use yflow_core::{load_config, scan_messages_dir, APIClient, LanguageMapper};
+//!
+//! # async fn push() -> anyhow::Result<()> {
+//! let config = load_config(None).await?;
+//! let client = APIClient::from_config(&config)?;
+//! let mapper = LanguageMapper::new(Some(config.language_mapping.clone()));
+//!
+//! let scan = scan_messages_dir(&config.messages()).await?;
+//! let response = client
+//!     .push_translations(mapper.apply_to_translations(scan.translations))
+//!     .await?;
+//! println!("added {} key(s)", response.added.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Settings loaded from the config stay on the [`I18nConfig`]:
+//! [`I18nConfig::messages`] builds the [`MessagesDir`] (layout, key
+//! separator, comparator, ...) the scanner reads, and
+//! [`APIClient::from_config`] the client with its timeouts, TLS pins and
+//! signing secret. Tools that only need a directory use
+//! [`MessagesDir::new`], which has the defaults.
+
+pub mod api;
+pub mod core;
+
+pub use crate::api::client::APIClient;
+pub use crate::core::config::load_config;
+pub use crate::core::flatten::{flatten_object, unflatten_object};
+pub use crate::core::language_mapping::LanguageMapper;
+pub use crate::core::scanner::scan_messages_dir;
+pub use crate::core::{I18nConfig, KeyOrigins, MessagesDir, ScanResult, Translations};